@@ -68,6 +68,8 @@ pub enum OrchestrationError {
     SourceValidationError(String),
     #[error("connection: {0:?} not found")]
     ConnectionNotFound(String),
+    #[error("connection: {0:?} is not a {1} connection")]
+    ConnectionTypeMismatch(String, String),
     #[error("Pipeline validation failed")]
     PipelineValidationError,
     #[error("Output table {0} not used in any sink")]
@@ -24,7 +24,12 @@ use std::hash::Hash;
 use tokio::runtime::Runtime;
 
 use crate::pipeline::dummy_sink::DummySinkFactory;
+use dozer_sink_aerospike::AerospikeSinkFactory;
 use dozer_sink_clickhouse::ClickhouseSinkFactory;
+use dozer_sink_file::{FileSinkFactory, FileStorage};
+use dozer_sink_kafka::KafkaSinkFactory;
+use dozer_sink_oracle::OracleSinkFactory;
+use dozer_sink_postgres::PostgresSinkFactory;
 
 use super::source_builder::SourceBuilder;
 use crate::errors::OrchestrationError;
@@ -78,6 +83,13 @@ impl<'a> PipelineBuilder<'a> {
         }
     }
 
+    fn find_connection(&self, name: &str) -> Result<&'a Connection, OrchestrationError> {
+        self.connections
+            .iter()
+            .find(|connection| connection.name == name)
+            .ok_or_else(|| OrchestrationError::ConnectionNotFound(name.to_string()))
+    }
+
     // Based on used_sources, map it to the connection name and create sources
     // For not breaking current functionality, current format is to be still supported.
     pub async fn get_grouped_tables(
@@ -248,6 +260,73 @@ impl<'a> PipelineBuilder<'a> {
                 SinkConfig::Clickhouse(config) => {
                     let sink =
                         Box::new(ClickhouseSinkFactory::new(config.clone(), runtime.clone()));
+                    // The factory also has the table of the single-table form of the config.
+                    let table_infos = sink
+                        .get_input_ports()
+                        .into_iter()
+                        .map(|port| Ok((get_table_info(&sink.get_input_port_name(&port))?, port)))
+                        .collect::<Result<Vec<_>, OrchestrationError>>()?;
+                    add_sink_to_pipeline(&mut pipeline, sink, id, table_infos);
+                }
+                SinkConfig::Aerospike(config) => {
+                    let ConnectionConfig::Aerospike(connection) =
+                        &self.find_connection(&config.connection)?.config
+                    else {
+                        return Err(OrchestrationError::ConnectionTypeMismatch(
+                            config.connection.clone(),
+                            sink.config.name(),
+                        ));
+                    };
+                    let sink = Box::new(AerospikeSinkFactory::new(
+                        id.clone(),
+                        connection.clone(),
+                        config.clone(),
+                    ));
+                    let table_infos = config
+                        .tables
+                        .iter()
+                        .enumerate()
+                        .map(|(port, table)| {
+                            Ok((
+                                get_table_info(&table.source_table_name)?,
+                                port as PortHandle,
+                            ))
+                        })
+                        .collect::<Result<Vec<_>, OrchestrationError>>()?;
+                    add_sink_to_pipeline(&mut pipeline, sink, id, table_infos);
+                }
+                SinkConfig::Oracle(config) => {
+                    let ConnectionConfig::Oracle(connection) =
+                        &self.find_connection(&config.connection)?.config
+                    else {
+                        return Err(OrchestrationError::ConnectionTypeMismatch(
+                            config.connection.clone(),
+                            sink.config.name(),
+                        ));
+                    };
+                    let sink = Box::new(OracleSinkFactory::new(config.clone(), connection.clone()));
+                    let table_info = get_table_info(&config.table_name)?;
+                    add_sink_to_pipeline(
+                        &mut pipeline,
+                        sink,
+                        id,
+                        vec![(table_info, DEFAULT_PORT_HANDLE)],
+                    );
+                }
+                SinkConfig::Postgres(config) => {
+                    let ConnectionConfig::Postgres(connection) =
+                        &self.find_connection(&config.connection)?.config
+                    else {
+                        return Err(OrchestrationError::ConnectionTypeMismatch(
+                            config.connection.clone(),
+                            sink.config.name(),
+                        ));
+                    };
+                    let sink = Box::new(PostgresSinkFactory::new(
+                        config.clone(),
+                        connection.clone(),
+                        runtime.clone(),
+                    ));
                     let table_info = get_table_info(&config.source_table_name)?;
                     add_sink_to_pipeline(
                         &mut pipeline,
@@ -256,8 +335,56 @@ impl<'a> PipelineBuilder<'a> {
                         vec![(table_info, DEFAULT_PORT_HANDLE)],
                     );
                 }
-                x => {
-                    return Err(OrchestrationError::UnsupportedFeature(x.name()));
+                SinkConfig::Kafka(config) => {
+                    let ConnectionConfig::Kafka(connection) =
+                        &self.find_connection(&config.connection)?.config
+                    else {
+                        return Err(OrchestrationError::ConnectionTypeMismatch(
+                            config.connection.clone(),
+                            sink.config.name(),
+                        ));
+                    };
+                    let sink = Box::new(KafkaSinkFactory::new(
+                        id.clone(),
+                        config.clone(),
+                        connection.clone(),
+                        runtime.clone(),
+                    ));
+                    let table_info = get_table_info(&config.source_table_name)?;
+                    add_sink_to_pipeline(
+                        &mut pipeline,
+                        sink,
+                        id,
+                        vec![(table_info, DEFAULT_PORT_HANDLE)],
+                    );
+                }
+                SinkConfig::File(config) => {
+                    let storage = match &self.find_connection(&config.connection)?.config {
+                        ConnectionConfig::LocalStorage(storage) => {
+                            FileStorage::Local(storage.details.clone())
+                        }
+                        ConnectionConfig::S3Storage(storage) => {
+                            FileStorage::S3(storage.details.clone())
+                        }
+                        _ => {
+                            return Err(OrchestrationError::ConnectionTypeMismatch(
+                                config.connection.clone(),
+                                sink.config.name(),
+                            ))
+                        }
+                    };
+                    let sink = Box::new(FileSinkFactory::new(
+                        config.clone(),
+                        storage,
+                        runtime.clone(),
+                    ));
+                    let table_info = get_table_info(&config.source_table_name)?;
+                    add_sink_to_pipeline(
+                        &mut pipeline,
+                        sink,
+                        id,
+                        vec![(table_info, DEFAULT_PORT_HANDLE)],
+                    );
                 }
             }
         }
@@ -293,8 +420,16 @@ fn table_names(sink: &Sink) -> Vec<&String> {
             .iter()
             .map(|table| &table.source_table_name)
             .collect(),
-        SinkConfig::Clickhouse(sink) => vec![&sink.source_table_name],
+        SinkConfig::Clickhouse(sink) => sink
+            .tables
+            .iter()
+            .map(|table| &table.source_table_name)
+            .chain(&sink.source_table_name)
+            .collect(),
         SinkConfig::Oracle(sink) => vec![&sink.table_name],
+        SinkConfig::Postgres(sink) => vec![&sink.source_table_name],
+        SinkConfig::Kafka(sink) => vec![&sink.source_table_name],
+        SinkConfig::File(sink) => vec![&sink.source_table_name],
     }
 }
 
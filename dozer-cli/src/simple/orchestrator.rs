@@ -91,7 +91,7 @@ impl SimpleOrchestrator {
         let dag_executor = executor
             .create_dag_executor(
                 &self.runtime,
-                get_executor_options(&self.config),
+                get_executor_options(&self.config, &self.home_dir()),
                 shutdown.clone(),
                 self.config.flags.clone(),
             )
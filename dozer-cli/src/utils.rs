@@ -1,7 +1,14 @@
-use dozer_core::executor::ExecutorOptions;
+use std::time::Duration;
+
+use camino::Utf8Path;
+use dozer_core::{checkpoint::CheckpointOptions, executor::ExecutorOptions};
 use dozer_types::models::{
-    app_config::{default_app_buffer_size, default_error_threshold, default_event_hub_capacity},
+    app_config::{
+        default_app_buffer_size, default_checkpoint_interval, default_error_threshold,
+        default_event_hub_capacity,
+    },
     config::Config,
+    flags::default_enable_app_checkpoints,
 };
 
 fn get_buffer_size(config: &Config) -> u32 {
@@ -25,10 +32,27 @@ fn get_event_hub_capacity(config: &Config) -> usize {
         .unwrap_or_else(default_event_hub_capacity)
 }
 
-pub fn get_executor_options(config: &Config) -> ExecutorOptions {
+fn get_checkpoint_options(config: &Config, home_dir: &Utf8Path) -> Option<CheckpointOptions> {
+    let enabled = config
+        .flags
+        .enable_app_checkpoints
+        .unwrap_or_else(default_enable_app_checkpoints);
+    enabled.then(|| CheckpointOptions {
+        dir: home_dir.join("checkpoints").into_std_path_buf(),
+        interval: Duration::from_millis(
+            config
+                .app
+                .checkpoint_interval
+                .unwrap_or_else(default_checkpoint_interval),
+        ),
+    })
+}
+
+pub fn get_executor_options(config: &Config, home_dir: &Utf8Path) -> ExecutorOptions {
     ExecutorOptions {
         channel_buffer_sz: get_buffer_size(config) as usize,
         error_threshold: Some(get_error_threshold(config)),
         event_hub_capacity: get_event_hub_capacity(config),
+        checkpoint: get_checkpoint_options(config, home_dir),
     }
 }
@@ -1,4 +1,4 @@
-use dozer_types::models::flags::{EnableProbabilisticOptimizations, Flags};
+use dozer_types::models::flags::{EnableProbabilisticOptimizations, Flags, JoinStateBackend};
 use dozer_types::node::NodeHandle;
 
 use crate::appsource::{self, AppSourceManager};
@@ -93,12 +93,14 @@ impl AppPipeline {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct PipelineFlags {
     pub enable_probabilistic_optimizations: EnableProbabilisticOptimizations,
+    pub join_state: JoinStateBackend,
 }
 
 impl From<&Flags> for PipelineFlags {
     fn from(flags: &Flags) -> Self {
         Self {
             enable_probabilistic_optimizations: flags.enable_probabilistic_optimizations.clone(),
+            join_state: flags.join_state.clone(),
         }
     }
 }
@@ -5,15 +5,16 @@ use std::{
 
 use daggy::{petgraph::visit::IntoNodeIdentifiers, NodeIndex};
 use dozer_types::{
-    log::warn,
-    node::{NodeHandle, OpIdentifier},
+    log::{info, warn},
+    node::{NodeHandle, OpIdentifier, SourceState},
 };
 
 use crate::{
+    checkpoint::{deserialize_bincode, Checkpoint},
     dag_schemas::{DagHaveSchemas, DagSchemas, EdgeType},
     errors::ExecutionError,
     event::EventHub,
-    node::{Processor, Sink, SinkFactory, Source},
+    node::{PortHandle, Processor, Sink, SinkFactory, Source},
     NodeKind as DagNodeKind,
 };
 
@@ -27,11 +28,12 @@ pub struct NodeType {
 }
 
 #[derive(Debug)]
-/// Node kind, source, processor or sink. Source has a checkpoint to start from.
+/// Node kind, source, processor or sink. Source has a checkpoint to start from, and the states of its record writers.
 pub enum NodeKind {
     Source {
         source: Box<dyn Source>,
         last_checkpoint: Option<OpIdentifier>,
+        record_writer_states: HashMap<PortHandle, Vec<u8>>,
     },
     Processor(Box<dyn Processor>),
     Sink(Box<dyn Sink>),
@@ -44,12 +46,14 @@ pub enum NodeKind {
 pub struct BuilderDag {
     graph: daggy::Dag<NodeType, EdgeType>,
     event_hub: EventHub,
+    initial_epoch_id: u64,
 }
 
 impl BuilderDag {
     pub async fn new(
         dag_schemas: DagSchemas,
         event_hub_capacity: usize,
+        checkpoint: Option<Checkpoint>,
     ) -> Result<Self, ExecutionError> {
         // Collect input output schemas.
         let mut input_schemas = HashMap::new();
@@ -137,6 +141,16 @@ impl BuilderDag {
             }
         }
 
+        // Processors and record writers are restored only if the checkpoint matches the dag and the sinks.
+        let restore_from = checkpoint
+            .filter(|checkpoint| can_restore_from_checkpoint(checkpoint, &nodes, &source_op_ids));
+        if let Some(checkpoint) = &restore_from {
+            info!("Restoring from checkpoint at epoch {}", checkpoint.epoch_id);
+        }
+        let initial_epoch_id = restore_from
+            .as_ref()
+            .map_or(0, |checkpoint| checkpoint.epoch_id + 1);
+
         // Build sources, processors, and collect source states.
         for (node_index, node) in nodes.iter_mut().enumerate() {
             let Some(node) = node.take() else {
@@ -160,18 +174,26 @@ impl BuilderDag {
                         .serialize_state()
                         .await
                         .map_err(ExecutionError::Source)?;
-                    let mut checkpoint = None;
                     for sink in source_id_to_sinks.remove(&node.handle).unwrap_or_default() {
-                        let sink = &mut graph[sink];
-                        let sink_handle = &sink.handle;
-                        let NodeKind::Sink(sink) = &mut sink.kind else {
+                        let NodeKind::Sink(sink) = &mut graph[sink].kind else {
                             unreachable!()
                         };
                         sink.set_source_state(&state)
                             .map_err(ExecutionError::Sink)?;
-                        if let Some(sink_checkpoint) = source_op_ids.remove(sink_handle) {
-                            checkpoint =
-                                Some(checkpoint.unwrap_or(sink_checkpoint).min(sink_checkpoint));
+                    }
+                    // Start from the minimum op id committed by the sinks of this source.
+                    let mut checkpoint = source_op_ids.remove(&node.handle);
+
+                    let mut record_writer_states = HashMap::new();
+                    if let Some(restore_from) = &restore_from {
+                        checkpoint = restore_from
+                            .source_states
+                            .get(&node.handle)
+                            .and_then(SourceState::op_id)
+                            .copied();
+                        if let Some(state) = restore_from.read_node_state(&node.handle)? {
+                            record_writer_states = deserialize_bincode(&state)
+                                .map_err(ExecutionError::RestoreRecordWriter)?;
                         }
                     }
 
@@ -180,6 +202,7 @@ impl BuilderDag {
                         kind: NodeKind::Source {
                             source,
                             last_checkpoint: checkpoint,
+                            record_writer_states,
                         },
                     }
                 }
@@ -193,6 +216,10 @@ impl BuilderDag {
                                 .remove(&node_index)
                                 .expect("we collected all output schemas"),
                             event_hub.clone(),
+                            match &restore_from {
+                                Some(checkpoint) => checkpoint.read_node_state(&node.handle)?,
+                                None => None,
+                            },
                         )
                         .await
                         .map_err(ExecutionError::Factory)?;
@@ -218,18 +245,72 @@ impl BuilderDag {
                 .expect("we know there's no loop");
         }
 
-        Ok(BuilderDag { graph, event_hub })
+        Ok(BuilderDag {
+            graph,
+            event_hub,
+            initial_epoch_id,
+        })
     }
 
     pub fn graph(&self) -> &daggy::Dag<NodeType, EdgeType> {
         &self.graph
     }
 
+    pub fn initial_epoch_id(&self) -> u64 {
+        self.initial_epoch_id
+    }
+
     pub fn into_graph_and_event_hub(self) -> (daggy::Dag<NodeType, EdgeType>, EventHub) {
         (self.graph, self.event_hub)
     }
 }
 
+/// A checkpoint can be restored if it has the state of every processor, every source can restart from it,
+/// and no sink has committed less than the checkpoint.
+fn can_restore_from_checkpoint(
+    checkpoint: &Checkpoint,
+    nodes: &[Option<super::NodeType>],
+    sink_op_ids: &HashMap<NodeHandle, OpIdentifier>,
+) -> bool {
+    for node in nodes.iter().flatten() {
+        match &node.kind {
+            DagNodeKind::Source(_) => match checkpoint.source_states.get(&node.handle) {
+                Some(SourceState::NotStarted) => {}
+                Some(SourceState::Restartable(op_id)) => {
+                    if sink_op_ids
+                        .get(&node.handle)
+                        .is_some_and(|sink_op_id| sink_op_id < op_id)
+                    {
+                        warn!(
+                            "Sinks of source {} are behind the checkpoint, ignoring checkpoint",
+                            node.handle
+                        );
+                        return false;
+                    }
+                }
+                Some(SourceState::NonRestartable) | None => {
+                    warn!(
+                        "Source {} cannot restart from the checkpoint, ignoring checkpoint",
+                        node.handle
+                    );
+                    return false;
+                }
+            },
+            DagNodeKind::Processor(_) => {
+                if !checkpoint.has_node_state(&node.handle) {
+                    warn!(
+                        "Processor {} is not in the checkpoint, ignoring checkpoint",
+                        node.handle
+                    );
+                    return false;
+                }
+            }
+            DagNodeKind::Sink(_) => {}
+        }
+    }
+    true
+}
+
 fn take_sink(node: &mut Option<super::NodeType>) -> Option<(NodeHandle, Box<dyn SinkFactory>)> {
     let super::NodeType { handle, kind } = node.take()?;
     if let super::NodeKind::Sink(sink) = kind {
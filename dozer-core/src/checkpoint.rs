@@ -0,0 +1,287 @@
+use std::{
+    collections::HashMap,
+    fs,
+    io::{BufWriter, ErrorKind, Write},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+
+use dozer_types::{
+    bincode::{self, config},
+    errors::{
+        internal::BoxedError,
+        types::{DeserializationError, SerializationError},
+    },
+    log::{info, warn},
+    node::{NodeHandle, SourceStates},
+};
+
+use crate::{epoch::Epoch, errors::ExecutionError};
+
+const MANIFEST_FILE_NAME: &str = "manifest";
+const MANIFEST_TEMP_FILE_NAME: &str = "manifest.tmp";
+const EPOCH_DIR_PREFIX: &str = "epoch_";
+
+#[derive(Debug, Clone)]
+pub struct CheckpointOptions {
+    /// The directory to write the checkpoint to.
+    pub dir: PathBuf,
+    /// Minimal interval between two checkpoints.
+    pub interval: Duration,
+}
+
+/// Lists the state files of a complete checkpoint.
+///
+/// Node states are written to their own files in the epoch's directory as each node reports. The manifest is
+/// written last and renamed into place, so a checkpoint is either complete or not seen at all.
+#[derive(Debug, bincode::Encode, bincode::Decode)]
+struct Manifest {
+    epoch_id: u64,
+    source_states: SourceStates,
+    /// State file names in the epoch's directory, keyed by node handle.
+    node_files: HashMap<NodeHandle, String>,
+}
+
+/// State of all sources and processors at the end of an epoch.
+#[derive(Debug, Clone)]
+pub struct Checkpoint {
+    /// The epoch this checkpoint was taken at.
+    pub epoch_id: u64,
+    /// Source states at the end of the epoch. Sources resume from here when the checkpoint is restored.
+    pub source_states: SourceStates,
+    /// The directory of the node state files.
+    dir: PathBuf,
+    /// Processor state and source record writer state file names, keyed by node handle.
+    node_files: HashMap<NodeHandle, String>,
+}
+
+impl Checkpoint {
+    pub fn has_node_state(&self, node: &NodeHandle) -> bool {
+        self.node_files.contains_key(node)
+    }
+
+    /// Reads the state of `node`. States are only read when they are restored, so they are never all in memory.
+    pub fn read_node_state(&self, node: &NodeHandle) -> Result<Option<Vec<u8>>, ExecutionError> {
+        let Some(file_name) = self.node_files.get(node) else {
+            return Ok(None);
+        };
+        let path = self.dir.join(file_name);
+        fs::read(&path)
+            .map(Some)
+            .map_err(|e| ExecutionError::FileSystemError(path, e))
+    }
+}
+
+/// Reads the latest checkpoint in `dir`, if any.
+pub fn read_checkpoint(dir: &Path) -> Result<Option<Checkpoint>, ExecutionError> {
+    let Some(manifest) = read_manifest(dir)? else {
+        return Ok(None);
+    };
+    Ok(Some(Checkpoint {
+        epoch_id: manifest.epoch_id,
+        source_states: manifest.source_states,
+        dir: epoch_dir(dir, manifest.epoch_id),
+        node_files: manifest.node_files,
+    }))
+}
+
+fn read_manifest(dir: &Path) -> Result<Option<Manifest>, ExecutionError> {
+    let path = dir.join(MANIFEST_FILE_NAME);
+    let data = match fs::read(&path) {
+        Ok(data) => data,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(ExecutionError::FileSystemError(path, e)),
+    };
+    let (manifest, _) = bincode::decode_from_slice(&data, config::legacy())
+        .map_err(ExecutionError::CorruptedCheckpoint)?;
+    Ok(Some(manifest))
+}
+
+fn epoch_dir(dir: &Path, epoch_id: u64) -> PathBuf {
+    dir.join(format!("{EPOCH_DIR_PREFIX}{epoch_id}"))
+}
+
+/// Where a node writes its state in a checkpoint.
+pub struct StateWriter<'a> {
+    writer: &'a mut dyn Write,
+    dir: &'a Path,
+}
+
+impl<'a> StateWriter<'a> {
+    pub fn new(writer: &'a mut dyn Write, dir: &'a Path) -> Self {
+        Self { writer, dir }
+    }
+
+    /// The checkpoint's directory. A node can put files its state refers to here. They are removed with the checkpoint.
+    pub fn dir(&self) -> &Path {
+        self.dir
+    }
+}
+
+impl Write for StateWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.writer.write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+/// Node state files of an epoch that not every node has reported yet.
+#[derive(Debug)]
+struct PendingCheckpoint {
+    source_states: Arc<SourceStates>,
+    node_files: HashMap<NodeHandle, String>,
+}
+
+/// Writes node states of an epoch as the nodes report them, and commits the checkpoint once every node has reported.
+#[derive(Debug)]
+pub struct CheckpointWriter {
+    options: CheckpointOptions,
+    num_nodes: usize,
+    /// Makes the state file names unique.
+    next_file_id: AtomicUsize,
+    pending: Mutex<HashMap<u64, PendingCheckpoint>>,
+    /// The last committed epoch id. Also serializes manifest writes.
+    last_persisted: Mutex<Option<u64>>,
+}
+
+impl CheckpointWriter {
+    pub fn new(options: CheckpointOptions, num_nodes: usize) -> Result<Self, ExecutionError> {
+        fs::create_dir_all(&options.dir)
+            .map_err(|e| ExecutionError::FileSystemError(options.dir.clone(), e))?;
+        // Epochs a previous run didn't complete are never committed.
+        let last_persisted = read_manifest(&options.dir)?.map(|manifest| manifest.epoch_id);
+        remove_epoch_dirs(&options.dir, |epoch_id| Some(epoch_id) != last_persisted)?;
+        Ok(Self {
+            options,
+            num_nodes,
+            next_file_id: Default::default(),
+            pending: Default::default(),
+            last_persisted: Mutex::new(last_persisted),
+        })
+    }
+
+    pub fn interval(&self) -> Duration {
+        self.options.interval
+    }
+
+    /// Writes `node`'s state at `epoch` to its own file. Called by every source and processor on checkpoint epochs.
+    ///
+    /// `serialize` streams the state to the file, so the writer never holds node states in memory.
+    pub fn write(
+        &self,
+        epoch: &Epoch,
+        node: NodeHandle,
+        serialize: impl FnOnce(&mut StateWriter) -> Result<(), BoxedError>,
+    ) -> Result<(), ExecutionError> {
+        let epoch_id = epoch.common_info.id;
+        let dir = epoch_dir(&self.options.dir, epoch_id);
+        fs::create_dir_all(&dir).map_err(|e| ExecutionError::FileSystemError(dir.clone(), e))?;
+        let file_name = format!("node_{}", self.next_file_id.fetch_add(1, Ordering::Relaxed));
+        let path = dir.join(&file_name);
+        let file = fs::File::create(&path)
+            .map_err(|e| ExecutionError::FileSystemError(path.clone(), e))?;
+        let mut writer = BufWriter::new(file);
+        serialize(&mut StateWriter::new(&mut writer, &dir))
+            .map_err(ExecutionError::FailedToCreateCheckpoint)?;
+        writer
+            .into_inner()
+            .map_err(|e| e.into_error())
+            .and_then(|file| file.sync_all())
+            .map_err(|e| ExecutionError::FileSystemError(path, e))?;
+
+        let manifest = {
+            let mut pending = self.pending.lock().unwrap();
+            let checkpoint = pending
+                .entry(epoch_id)
+                .or_insert_with(|| PendingCheckpoint {
+                    source_states: epoch.common_info.source_states.clone(),
+                    node_files: Default::default(),
+                });
+            checkpoint.node_files.insert(node, file_name);
+            if checkpoint.node_files.len() < self.num_nodes {
+                return Ok(());
+            }
+            let checkpoint = pending
+                .remove(&epoch_id)
+                .expect("we just inserted the checkpoint");
+            Manifest {
+                epoch_id,
+                source_states: checkpoint.source_states.as_ref().clone(),
+                node_files: checkpoint.node_files,
+            }
+        };
+        self.commit(&manifest)
+    }
+
+    fn commit(&self, manifest: &Manifest) -> Result<(), ExecutionError> {
+        let mut last_persisted = self.last_persisted.lock().unwrap();
+        if last_persisted.is_some_and(|epoch_id| epoch_id > manifest.epoch_id) {
+            // A later checkpoint was completed first.
+            return remove_epoch_dirs(&self.options.dir, |epoch_id| epoch_id == manifest.epoch_id);
+        }
+
+        let data = serialize_bincode(manifest)
+            .map_err(|e| ExecutionError::FailedToCreateCheckpoint(e.into()))?;
+        let temp_path = self.options.dir.join(MANIFEST_TEMP_FILE_NAME);
+        let mut file = fs::File::create(&temp_path)
+            .map_err(|e| ExecutionError::FileSystemError(temp_path.clone(), e))?;
+        file.write_all(&data)
+            .and_then(|()| file.sync_all())
+            .map_err(|e| ExecutionError::FileSystemError(temp_path.clone(), e))?;
+        let path = self.options.dir.join(MANIFEST_FILE_NAME);
+        fs::rename(&temp_path, &path).map_err(|e| ExecutionError::FileSystemError(path, e))?;
+
+        info!("Checkpoint created at epoch {}", manifest.epoch_id);
+        *last_persisted = Some(manifest.epoch_id);
+        // Epochs that are still pending are newer, and must be kept.
+        remove_epoch_dirs(&self.options.dir, |epoch_id| epoch_id < manifest.epoch_id)
+    }
+}
+
+/// Removes the state directories of the epochs matching `filter`.
+fn remove_epoch_dirs(dir: &Path, filter: impl Fn(u64) -> bool) -> Result<(), ExecutionError> {
+    let entries = fs::read_dir(dir).map_err(|e| ExecutionError::FileSystemError(dir.into(), e))?;
+    for entry in entries {
+        let entry = entry.map_err(|e| ExecutionError::FileSystemError(dir.into(), e))?;
+        let Some(epoch_id) = entry
+            .file_name()
+            .to_str()
+            .and_then(|name| name.strip_prefix(EPOCH_DIR_PREFIX))
+            .and_then(|epoch_id| epoch_id.parse().ok())
+        else {
+            continue;
+        };
+        if filter(epoch_id) {
+            if let Err(e) = fs::remove_dir_all(entry.path()) {
+                // Leftover state files only take space.
+                warn!("Failed to remove checkpoint {:?}: {e}", entry.path());
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn serialize_bincode<T: bincode::Encode>(value: T) -> Result<Vec<u8>, SerializationError> {
+    Ok(bincode::encode_to_vec(value, config::legacy())?)
+}
+
+/// Same as [`serialize_bincode`], but writes to `writer`.
+pub fn serialize_bincode_into<T: bincode::Encode>(
+    value: T,
+    mut writer: &mut dyn Write,
+) -> Result<(), SerializationError> {
+    bincode::encode_into_std_write(value, &mut writer, config::legacy())?;
+    Ok(())
+}
+
+pub fn deserialize_bincode<T: bincode::Decode>(data: &[u8]) -> Result<T, DeserializationError> {
+    let (value, _) = bincode::decode_from_slice(data, config::legacy())?;
+    Ok(value)
+}
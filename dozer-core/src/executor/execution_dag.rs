@@ -6,6 +6,7 @@ use std::{
 
 use crate::{
     builder_dag::{BuilderDag, NodeKind},
+    checkpoint::{CheckpointOptions, CheckpointWriter},
     dag_schemas::EdgeKind,
     error_manager::ErrorManager,
     errors::ExecutionError,
@@ -57,6 +58,7 @@ pub struct ExecutionDag {
     error_manager: Arc<ErrorManager>,
     labels: DozerMonitorContext,
     event_hub: EventHub,
+    checkpoint_writer: Option<Arc<CheckpointWriter>>,
 }
 
 impl ExecutionDag {
@@ -65,6 +67,7 @@ impl ExecutionDag {
         labels: DozerMonitorContext,
         channel_buffer_sz: usize,
         error_threshold: Option<u32>,
+        checkpoint_options: Option<CheckpointOptions>,
     ) -> Result<Self, ExecutionError> {
         // We only create record writer once for every output port. Every `HashMap` in this `Vec` tracks if a node's output ports already have the record writer created.
         let mut all_record_writers = vec![
@@ -87,24 +90,34 @@ impl ExecutionDag {
             let edge_kind = edge.edge_kind.clone();
 
             // Create or get record writer.
-            let record_writer =
-                match all_record_writers[source_node_index.index()].entry(output_port) {
-                    Entry::Vacant(entry) => {
-                        let record_writer = match &edge_kind {
-                            EdgeKind::FromSource {
-                                port_type: OutputPortType::StatefulWithPrimaryKeyLookup,
-                                ..
-                            } => Some(
-                                create_record_writer(edge.schema.clone())
+            let record_writer = match all_record_writers[source_node_index.index()]
+                .entry(output_port)
+            {
+                Entry::Vacant(entry) => {
+                    let record_writer = match &edge_kind {
+                        EdgeKind::FromSource {
+                            port_type: OutputPortType::StatefulWithPrimaryKeyLookup,
+                            ..
+                        } => {
+                            let state = match &builder_dag.graph()[source_node_index].kind {
+                                NodeKind::Source {
+                                    record_writer_states,
+                                    ..
+                                } => record_writer_states.get(&output_port),
+                                _ => None,
+                            };
+                            Some(
+                                create_record_writer(edge.schema.clone(), state.map(Vec::as_slice))
                                     .map_err(ExecutionError::RestoreRecordWriter)?,
-                            ),
-                            _ => None,
-                        };
-                        let record_writer = Arc::new(Mutex::new(record_writer));
-                        entry.insert(record_writer).clone()
-                    }
-                    Entry::Occupied(entry) => entry.get().clone(),
-                };
+                            )
+                        }
+                        _ => None,
+                    };
+                    let record_writer = Arc::new(Mutex::new(record_writer));
+                    entry.insert(record_writer).clone()
+                }
+                Entry::Occupied(entry) => entry.get().clone(),
+            };
 
             // Create or get channel.
             let (sender, receiver) = match channels.entry((source_node_index, target_node_index)) {
@@ -128,7 +141,21 @@ impl ExecutionDag {
             edges.push(Some(edge));
         }
 
+        // Every source and processor contributes its state to a checkpoint.
+        let checkpoint_writer = checkpoint_options
+            .map(|options| {
+                let num_nodes = builder_dag
+                    .graph()
+                    .raw_nodes()
+                    .iter()
+                    .filter(|node| !matches!(node.weight.kind, NodeKind::Sink(_)))
+                    .count();
+                CheckpointWriter::new(options, num_nodes).map(Arc::new)
+            })
+            .transpose()?;
+
         // Create new graph.
+        let initial_epoch_id = builder_dag.initial_epoch_id();
         let (graph, event_hub) = builder_dag.into_graph_and_event_hub();
         let graph = graph.map_owned(
             |_, node| NodeType {
@@ -143,7 +170,7 @@ impl ExecutionDag {
         );
         Ok(ExecutionDag {
             graph,
-            initial_epoch_id: 0,
+            initial_epoch_id,
             error_manager: Arc::new(if let Some(threshold) = error_threshold {
                 ErrorManager::new_threshold(threshold)
             } else {
@@ -151,6 +178,7 @@ impl ExecutionDag {
             }),
             labels,
             event_hub,
+            checkpoint_writer,
         })
     }
 
@@ -178,6 +206,10 @@ impl ExecutionDag {
         &self.event_hub
     }
 
+    pub fn checkpoint_writer(&self) -> Option<&Arc<CheckpointWriter>> {
+        self.checkpoint_writer.as_ref()
+    }
+
     pub fn collect_senders(&self, node_index: daggy::NodeIndex) -> Vec<SenderWithPortMapping> {
         // Map from target node index to `SenderWithPortMapping`.
         let mut senders = HashMap::<daggy::NodeIndex, SenderWithPortMapping>::new();
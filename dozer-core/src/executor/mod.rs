@@ -1,4 +1,5 @@
 use crate::builder_dag::{BuilderDag, NodeKind};
+use crate::checkpoint::{read_checkpoint, CheckpointOptions};
 use crate::dag_schemas::DagSchemas;
 use crate::errors::ExecutionError;
 use crate::Dag;
@@ -19,6 +20,8 @@ pub struct ExecutorOptions {
     pub channel_buffer_sz: usize,
     pub event_hub_capacity: usize,
     pub error_threshold: Option<u32>,
+    /// If set, processor states are checkpointed periodically and restored on restart.
+    pub checkpoint: Option<CheckpointOptions>,
 }
 
 impl Default for ExecutorOptions {
@@ -27,6 +30,7 @@ impl Default for ExecutorOptions {
             channel_buffer_sz: 20_000,
             event_hub_capacity: 100,
             error_threshold: Some(0),
+            checkpoint: None,
         }
     }
 }
@@ -59,7 +63,12 @@ impl DagExecutor {
     pub async fn new(dag: Dag, options: ExecutorOptions) -> Result<Self, ExecutionError> {
         let dag_schemas = DagSchemas::new(dag).await?;
 
-        let builder_dag = BuilderDag::new(dag_schemas, options.event_hub_capacity).await?;
+        let checkpoint = match &options.checkpoint {
+            Some(checkpoint_options) => read_checkpoint(&checkpoint_options.dir)?,
+            None => None,
+        };
+        let builder_dag =
+            BuilderDag::new(dag_schemas, options.event_hub_capacity, checkpoint).await?;
 
         Ok(Self {
             builder_dag,
@@ -84,6 +93,7 @@ impl DagExecutor {
             labels,
             self.options.channel_buffer_sz,
             self.options.error_threshold,
+            self.options.checkpoint.clone(),
         )?;
         let node_indexes = execution_dag.graph().node_identifiers().collect::<Vec<_>>();
 
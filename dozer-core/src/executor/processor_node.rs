@@ -6,6 +6,7 @@ use daggy::NodeIndex;
 use dozer_types::node::{NodeHandle, OpIdentifier};
 use dozer_types::types::TableOperation;
 
+use crate::checkpoint::CheckpointWriter;
 use crate::epoch::Epoch;
 use crate::error_manager::ErrorManager;
 use crate::executor_operation::ExecutorOperation;
@@ -32,6 +33,8 @@ pub struct ProcessorNode {
     channel_manager: ChannelManager,
     /// The error manager, for reporting non-fatal errors.
     error_manager: Arc<ErrorManager>,
+    /// The checkpoint writer, if checkpointing is enabled.
+    checkpoint_writer: Option<Arc<CheckpointWriter>>,
 }
 
 impl ProcessorNode {
@@ -65,6 +68,7 @@ impl ProcessorNode {
             processor,
             channel_manager,
             error_manager: dag.error_manager().clone(),
+            checkpoint_writer: dag.checkpoint_writer().cloned(),
         }
     }
 
@@ -106,6 +110,14 @@ impl ReceiverLoop for ProcessorNode {
             self.error_manager.report(e);
         }
 
+        if epoch.checkpoint {
+            if let Some(checkpoint_writer) = &self.checkpoint_writer {
+                checkpoint_writer.write(&epoch, self.node_handle.clone(), |writer| {
+                    self.processor.serialize_state(writer)
+                })?;
+            }
+        }
+
         self.channel_manager.send_commit(epoch)
     }
 
@@ -1,4 +1,11 @@
-use std::{fmt::Debug, future::Future, pin::pin, sync::Arc, time::SystemTime};
+use std::{
+    fmt::Debug,
+    future::Future,
+    io::Write,
+    pin::pin,
+    sync::Arc,
+    time::{Instant, SystemTime},
+};
 
 use daggy::petgraph::visit::IntoNodeIdentifiers;
 use dozer_types::{
@@ -13,6 +20,7 @@ use tokio::{
 
 use crate::{
     builder_dag::NodeKind,
+    checkpoint::CheckpointWriter,
     epoch::Epoch,
     errors::ExecutionError,
     executor_operation::ExecutorOperation,
@@ -37,6 +45,10 @@ pub struct SourceNode<F> {
     shutdown: F,
     /// The runtime to run the source in.
     runtime: Arc<Runtime>,
+    /// The checkpoint writer, if checkpointing is enabled.
+    checkpoint_writer: Option<Arc<CheckpointWriter>>,
+    /// When the last checkpoint epoch was emitted.
+    last_checkpoint_instant: Instant,
 }
 
 impl<F: Future + Unpin> Node for SourceNode<F> {
@@ -121,6 +133,19 @@ impl<F: Future + Unpin> Node for SourceNode<F> {
                                 if let Some(st) = source_time {
                                     epoch = epoch.with_source_time(st);
                                 }
+                                if let Some(checkpoint_writer) = &self.checkpoint_writer {
+                                    if self.last_checkpoint_instant.elapsed()
+                                        >= checkpoint_writer.interval()
+                                    {
+                                        epoch = epoch.with_checkpoint();
+                                        self.last_checkpoint_instant = Instant::now();
+                                        write_record_writer_states(
+                                            &self.sources,
+                                            checkpoint_writer,
+                                            &epoch,
+                                        )?;
+                                    }
+                                }
                                 send_to_all_nodes(
                                     &self.sources,
                                     ExecutorOperation::Commit { epoch },
@@ -159,6 +184,21 @@ struct SourceRunner {
     sender: Sender<(PortHandle, IngestionMessage)>,
 }
 
+/// Contributes the record writer states of all sources to the checkpoint of `epoch`.
+fn write_record_writer_states(
+    sources: &[RunningSource],
+    checkpoint_writer: &CheckpointWriter,
+    epoch: &Epoch,
+) -> Result<(), ExecutionError> {
+    for source in sources {
+        let state = source.channel_manager.serialize_record_writers()?;
+        checkpoint_writer.write(epoch, source.channel_manager.owner().clone(), |writer| {
+            Ok(writer.write_all(&state)?)
+        })?;
+    }
+    Ok(())
+}
+
 /// Returns if the operation is sent successfully.
 fn send_to_all_nodes(
     sources: &[RunningSource],
@@ -194,6 +234,7 @@ pub async fn create_source_node<F>(
         let NodeKind::Source {
             source,
             last_checkpoint,
+            ..
         } = node.kind.take().unwrap()
         else {
             continue;
@@ -229,6 +270,8 @@ pub async fn create_source_node<F>(
         epoch_id: dag.initial_epoch_id(),
         shutdown,
         runtime,
+        checkpoint_writer: dag.checkpoint_writer().cloned(),
+        last_checkpoint_instant: Instant::now(),
     }
 }
 
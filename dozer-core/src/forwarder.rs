@@ -1,4 +1,5 @@
 use crate::channels::ProcessorChannelForwarder;
+use crate::checkpoint::serialize_bincode;
 use crate::epoch::Epoch;
 use crate::error_manager::ErrorManager;
 use crate::errors::ExecutionError;
@@ -119,6 +120,17 @@ impl ChannelManager {
         &self.owner
     }
 
+    pub fn serialize_record_writers(&self) -> Result<Vec<u8>, ExecutionError> {
+        let mut states = HashMap::new();
+        for (port, writer) in &self.record_writers {
+            let state = writer
+                .serialize_state()
+                .map_err(ExecutionError::SerializeRecordWriter)?;
+            states.insert(*port, state);
+        }
+        serialize_bincode(&states).map_err(ExecutionError::SerializeRecordWriter)
+    }
+
     pub fn new(
         owner: NodeHandle,
         record_writers: HashMap<PortHandle, Box<dyn RecordWriter>>,
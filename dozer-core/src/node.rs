@@ -1,4 +1,5 @@
 use crate::channels::ProcessorChannelForwarder;
+use crate::checkpoint::StateWriter;
 use crate::epoch::Epoch;
 use crate::event::EventHub;
 
@@ -81,6 +82,7 @@ pub trait ProcessorFactory: Send + Sync + Debug {
         input_schemas: HashMap<PortHandle, Schema>,
         output_schemas: HashMap<PortHandle, Schema>,
         event_hub: EventHub,
+        checkpoint_data: Option<Vec<u8>>,
     ) -> Result<Box<dyn Processor>, BoxedError>;
     fn type_name(&self) -> String;
     fn id(&self) -> String;
@@ -93,6 +95,10 @@ pub trait Processor: Send + Sync + Debug {
         op: TableOperation,
         fw: &mut dyn ProcessorChannelForwarder,
     ) -> Result<(), BoxedError>;
+
+    /// Writes the processor's state to `writer`. It will be passed to [ProcessorFactory::build] on restart.
+    /// Stateless processors write nothing.
+    fn serialize_state(&self, writer: &mut StateWriter) -> Result<(), BoxedError>;
 }
 
 #[async_trait]
@@ -1,4 +1,5 @@
-use dozer_types::errors::types::DeserializationError;
+use crate::checkpoint::{deserialize_bincode, serialize_bincode};
+use dozer_types::errors::types::{DeserializationError, SerializationError};
 use dozer_types::thiserror::Error;
 use dozer_types::types::{Operation, Record, Schema};
 use std::collections::HashMap;
@@ -12,6 +13,7 @@ pub enum RecordWriterError {
 
 pub trait RecordWriter: Send + Sync {
     fn write(&mut self, op: Operation) -> Result<Operation, RecordWriterError>;
+    fn serialize_state(&self) -> Result<Vec<u8>, SerializationError>;
 }
 
 impl Debug for dyn RecordWriter {
@@ -20,8 +22,11 @@ impl Debug for dyn RecordWriter {
     }
 }
 
-pub fn create_record_writer(schema: Schema) -> Result<Box<dyn RecordWriter>, DeserializationError> {
-    let writer = Box::new(PrimaryKeyLookupRecordWriter::new(schema)?);
+pub fn create_record_writer(
+    schema: Schema,
+    state: Option<&[u8]>,
+) -> Result<Box<dyn RecordWriter>, DeserializationError> {
+    let writer = Box::new(PrimaryKeyLookupRecordWriter::new(schema, state)?);
     Ok(writer)
 }
 
@@ -32,16 +37,17 @@ pub(crate) struct PrimaryKeyLookupRecordWriter {
 }
 
 impl PrimaryKeyLookupRecordWriter {
-    pub(crate) fn new(schema: Schema) -> Result<Self, DeserializationError> {
+    pub(crate) fn new(schema: Schema, state: Option<&[u8]>) -> Result<Self, DeserializationError> {
         debug_assert!(
             !schema.primary_index.is_empty(),
             "PrimaryKeyLookupRecordWriter can only be used with a schema that has a primary key."
         );
 
-        Ok(Self {
-            schema,
-            index: Default::default(),
-        })
+        let index = match state {
+            Some(state) => deserialize_bincode(state)?,
+            None => Default::default(),
+        };
+        Ok(Self { schema, index })
     }
 }
 
@@ -84,4 +90,8 @@ impl RecordWriter for PrimaryKeyLookupRecordWriter {
             }
         }
     }
+
+    fn serialize_state(&self) -> Result<Vec<u8>, SerializationError> {
+        serialize_bincode(&self.index)
+    }
 }
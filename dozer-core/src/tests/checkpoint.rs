@@ -0,0 +1,291 @@
+use std::collections::HashMap;
+use std::io::Write;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use dozer_types::errors::internal::BoxedError;
+use dozer_types::node::{NodeHandle, OpIdentifier, SourceState};
+use dozer_types::tonic::async_trait;
+use dozer_types::types::{Field, Operation, Record, Schema, TableOperation};
+
+use crate::builder_dag::{BuilderDag, NodeKind};
+use crate::channels::ProcessorChannelForwarder;
+use crate::checkpoint::{
+    deserialize_bincode, read_checkpoint, serialize_bincode_into, CheckpointOptions,
+    CheckpointWriter, StateWriter,
+};
+use crate::dag_schemas::DagSchemas;
+use crate::epoch::Epoch;
+use crate::errors::ExecutionError;
+use crate::event::EventHub;
+use crate::node::{PortHandle, Processor, ProcessorFactory};
+use crate::tests::sinks::{CountingSinkFactory, COUNTING_SINK_INPUT_PORT};
+use crate::tests::sources::{GeneratorSourceFactory, GENERATOR_SOURCE_OUTPUT_PORT};
+use crate::{Dag, Endpoint, DEFAULT_PORT_HANDLE};
+
+use super::create_test_runtime;
+
+#[derive(Debug)]
+struct CountingProcessorFactory;
+
+#[async_trait]
+impl ProcessorFactory for CountingProcessorFactory {
+    fn type_name(&self) -> String {
+        "Counting".to_owned()
+    }
+
+    async fn get_output_schema(
+        &self,
+        _output_port: &PortHandle,
+        input_schemas: &HashMap<PortHandle, Schema>,
+    ) -> Result<Schema, BoxedError> {
+        Ok(input_schemas.get(&DEFAULT_PORT_HANDLE).unwrap().clone())
+    }
+
+    fn get_input_ports(&self) -> Vec<PortHandle> {
+        vec![DEFAULT_PORT_HANDLE]
+    }
+
+    fn get_output_ports(&self) -> Vec<PortHandle> {
+        vec![DEFAULT_PORT_HANDLE]
+    }
+
+    async fn build(
+        &self,
+        _input_schemas: HashMap<PortHandle, Schema>,
+        _output_schemas: HashMap<PortHandle, Schema>,
+        _event_hub: EventHub,
+        checkpoint_data: Option<Vec<u8>>,
+    ) -> Result<Box<dyn Processor>, BoxedError> {
+        let count = match checkpoint_data {
+            Some(data) => deserialize_bincode(&data)?,
+            None => 0,
+        };
+        Ok(Box::new(CountingProcessor { count }))
+    }
+
+    fn id(&self) -> String {
+        "Counting".to_owned()
+    }
+}
+
+/// Counts the operations it has seen, so its state is observable after a restore.
+#[derive(Debug)]
+struct CountingProcessor {
+    count: u64,
+}
+
+impl Processor for CountingProcessor {
+    fn commit(&self, _epoch_details: &Epoch) -> Result<(), BoxedError> {
+        Ok(())
+    }
+
+    fn process(
+        &mut self,
+        mut op: TableOperation,
+        fw: &mut dyn ProcessorChannelForwarder,
+    ) -> Result<(), BoxedError> {
+        self.count += 1;
+        op.port = DEFAULT_PORT_HANDLE;
+        fw.send(op);
+        Ok(())
+    }
+
+    fn serialize_state(&self, writer: &mut StateWriter) -> Result<(), BoxedError> {
+        Ok(serialize_bincode_into(self.count, writer)?)
+    }
+}
+
+struct DiscardingForwarder;
+
+impl ProcessorChannelForwarder for DiscardingForwarder {
+    fn send(&mut self, _op: TableOperation) {}
+}
+
+fn source_handle() -> NodeHandle {
+    NodeHandle::new(None, "source".to_string())
+}
+
+fn proc_handle() -> NodeHandle {
+    NodeHandle::new(None, "proc".to_string())
+}
+
+fn create_dag() -> Dag {
+    let latch = Arc::new(AtomicBool::new(true));
+    let sink_handle = NodeHandle::new(None, "sink".to_string());
+
+    let mut dag = Dag::new();
+    dag.add_source(
+        source_handle(),
+        Box::new(GeneratorSourceFactory::new(1, latch.clone(), false)),
+    );
+    dag.add_processor(proc_handle(), Box::new(CountingProcessorFactory));
+    dag.add_sink(
+        sink_handle.clone(),
+        Box::new(CountingSinkFactory::new(1, latch)),
+    );
+    dag.connect(
+        Endpoint::new(source_handle(), GENERATOR_SOURCE_OUTPUT_PORT),
+        Endpoint::new(proc_handle(), DEFAULT_PORT_HANDLE),
+    )
+    .unwrap();
+    dag.connect(
+        Endpoint::new(proc_handle(), DEFAULT_PORT_HANDLE),
+        Endpoint::new(sink_handle, COUNTING_SINK_INPUT_PORT),
+    )
+    .unwrap();
+    dag
+}
+
+/// Builds the test dag from `checkpoint_dir`, returning the initial epoch id, the source's starting point and the processor.
+async fn build_dag(
+    checkpoint_dir: &std::path::Path,
+) -> (u64, Option<OpIdentifier>, Box<dyn Processor>) {
+    let checkpoint = read_checkpoint(checkpoint_dir).unwrap();
+    let dag_schemas = DagSchemas::new(create_dag()).await.unwrap();
+    let builder_dag = BuilderDag::new(dag_schemas, 16, checkpoint).await.unwrap();
+    let initial_epoch_id = builder_dag.initial_epoch_id();
+
+    let (graph, _) = builder_dag.into_graph_and_event_hub();
+    let (nodes, _) = graph.into_graph().into_nodes_edges();
+    let mut last_checkpoint = None;
+    let mut processor = None;
+    for node in nodes {
+        match node.weight.kind {
+            NodeKind::Source {
+                last_checkpoint: op_id,
+                ..
+            } => last_checkpoint = op_id,
+            NodeKind::Processor(node) => processor = Some(node),
+            NodeKind::Sink(_) => {}
+        }
+    }
+    (initial_epoch_id, last_checkpoint, processor.unwrap())
+}
+
+fn create_epoch(id: u64, op_id: OpIdentifier) -> Epoch {
+    let source_states = [(source_handle(), SourceState::Restartable(op_id))]
+        .into_iter()
+        .collect();
+    Epoch::new(id, Arc::new(source_states), SystemTime::now()).with_checkpoint()
+}
+
+fn write_state(
+    writer: &CheckpointWriter,
+    epoch: &Epoch,
+    node: &NodeHandle,
+    state: &[u8],
+) -> Result<(), ExecutionError> {
+    writer.write(epoch, node.clone(), |file| Ok(file.write_all(state)?))
+}
+
+#[test]
+fn test_checkpoint_writer_waits_for_all_nodes() {
+    let dir = tempfile::tempdir().unwrap();
+    let options = CheckpointOptions {
+        dir: dir.path().to_path_buf(),
+        interval: Duration::from_secs(1),
+    };
+    assert!(read_checkpoint(dir.path()).unwrap().is_none());
+
+    let writer = CheckpointWriter::new(options.clone(), 2).unwrap();
+    let node1 = NodeHandle::new(None, "node1".to_string());
+    let node2 = NodeHandle::new(None, "node2".to_string());
+
+    let epoch = create_epoch(3, OpIdentifier::new(0, 5));
+    write_state(&writer, &epoch, &node1, &[1]).unwrap();
+    assert!(read_checkpoint(dir.path()).unwrap().is_none());
+    write_state(&writer, &epoch, &node2, &[2]).unwrap();
+
+    let checkpoint = read_checkpoint(dir.path()).unwrap().unwrap();
+    assert_eq!(checkpoint.epoch_id, 3);
+    assert_eq!(
+        checkpoint.source_states.get(&source_handle()),
+        Some(&SourceState::Restartable(OpIdentifier::new(0, 5)))
+    );
+    assert_eq!(checkpoint.read_node_state(&node1).unwrap(), Some(vec![1]));
+    assert_eq!(checkpoint.read_node_state(&node2).unwrap(), Some(vec![2]));
+
+    // An older epoch completing late doesn't overwrite the newer checkpoint.
+    let old_epoch = create_epoch(2, OpIdentifier::new(0, 4));
+    write_state(&writer, &old_epoch, &node1, &[3]).unwrap();
+    write_state(&writer, &old_epoch, &node2, &[4]).unwrap();
+    assert_eq!(read_checkpoint(dir.path()).unwrap().unwrap().epoch_id, 3);
+
+    // A newer checkpoint is committed with its own files, and the older one is removed.
+    let new_epoch = create_epoch(4, OpIdentifier::new(0, 6));
+    write_state(&writer, &new_epoch, &node1, &[5]).unwrap();
+    assert_eq!(checkpoint.read_node_state(&node1).unwrap(), Some(vec![1]));
+    write_state(&writer, &new_epoch, &node2, &[6]).unwrap();
+    let new_checkpoint = read_checkpoint(dir.path()).unwrap().unwrap();
+    assert_eq!(new_checkpoint.epoch_id, 4);
+    assert_eq!(
+        new_checkpoint.read_node_state(&node1).unwrap(),
+        Some(vec![5])
+    );
+    assert!(checkpoint.read_node_state(&node1).is_err());
+
+    // Files of an epoch that never completed are removed on restart.
+    write_state(
+        &writer,
+        &create_epoch(5, OpIdentifier::new(0, 7)),
+        &node1,
+        &[7],
+    )
+    .unwrap();
+    drop(writer);
+    let entries = || std::fs::read_dir(dir.path()).unwrap().count();
+    assert_eq!(entries(), 3);
+    let _writer = CheckpointWriter::new(options, 2).unwrap();
+    assert_eq!(entries(), 2);
+    assert_eq!(read_checkpoint(dir.path()).unwrap().unwrap().epoch_id, 4);
+}
+
+#[test]
+fn test_restore_processor_state_through_builder_dag() {
+    let dir = tempfile::tempdir().unwrap();
+    let runtime = create_test_runtime();
+
+    // Run a fresh processor for a few operations and checkpoint its state.
+    let (initial_epoch_id, last_checkpoint, mut processor) =
+        runtime.block_on(build_dag(dir.path()));
+    assert_eq!(initial_epoch_id, 0);
+    assert_eq!(last_checkpoint, None);
+    for n in 0..10 {
+        let op = TableOperation {
+            id: Some(OpIdentifier::new(0, n)),
+            op: Operation::Insert {
+                new: Record::new(vec![
+                    Field::String(format!("key_{n}")),
+                    Field::String(format!("value_{n}")),
+                ]),
+            },
+            port: DEFAULT_PORT_HANDLE,
+        };
+        processor.process(op, &mut DiscardingForwarder).unwrap();
+    }
+
+    let options = CheckpointOptions {
+        dir: dir.path().to_path_buf(),
+        interval: Duration::from_secs(1),
+    };
+    let writer = CheckpointWriter::new(options, 1).unwrap();
+    let op_id = OpIdentifier::new(0, 9);
+    writer
+        .write(&create_epoch(7, op_id), proc_handle(), |file| {
+            processor.serialize_state(file)
+        })
+        .unwrap();
+
+    // Rebuilding the dag restores the processor and resumes the source after the checkpoint.
+    let (initial_epoch_id, last_checkpoint, processor) = runtime.block_on(build_dag(dir.path()));
+    assert_eq!(initial_epoch_id, 8);
+    assert_eq!(last_checkpoint, Some(op_id));
+    let mut state = vec![];
+    processor
+        .serialize_state(&mut StateWriter::new(&mut state, dir.path()))
+        .unwrap();
+    let count: u64 = deserialize_bincode(&state).unwrap();
+    assert_eq!(count, 10);
+}
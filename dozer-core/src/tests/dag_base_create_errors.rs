@@ -192,6 +192,7 @@ impl ProcessorFactory for CreateErrProcessorFactory {
         _input_schemas: HashMap<PortHandle, Schema>,
         _output_schemas: HashMap<PortHandle, Schema>,
         _event_hub: EventHub,
+        _checkpoint_data: Option<Vec<u8>>,
     ) -> Result<Box<dyn Processor>, BoxedError> {
         if self.panic {
             panic!("Generated error");
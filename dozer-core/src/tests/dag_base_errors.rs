@@ -18,6 +18,7 @@ use dozer_types::types::{
 };
 use tokio::sync::mpsc::Sender;
 
+use crate::checkpoint::StateWriter;
 use std::collections::HashMap;
 use std::panic;
 
@@ -61,6 +62,7 @@ impl ProcessorFactory for ErrorProcessorFactory {
         _input_schemas: HashMap<PortHandle, Schema>,
         _output_schemas: HashMap<PortHandle, Schema>,
         _event_hub: EventHub,
+        _checkpoint_data: Option<Vec<u8>>,
     ) -> Result<Box<dyn Processor>, BoxedError> {
         Ok(Box::new(ErrorProcessor {
             err_on: self.err_on,
@@ -104,6 +106,10 @@ impl Processor for ErrorProcessor {
         fw.send(op);
         Ok(())
     }
+
+    fn serialize_state(&self, _writer: &mut StateWriter) -> Result<(), BoxedError> {
+        Ok(())
+    }
 }
 
 #[test]
@@ -16,6 +16,7 @@ use dozer_types::tonic::async_trait;
 use dozer_types::types::{Schema, TableOperation};
 use tokio::sync::oneshot;
 
+use crate::checkpoint::StateWriter;
 use std::collections::HashMap;
 use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
@@ -54,6 +55,7 @@ impl ProcessorFactory for NoopProcessorFactory {
         _input_schemas: HashMap<PortHandle, Schema>,
         _output_schemas: HashMap<PortHandle, Schema>,
         _event_hub: EventHub,
+        _checkpoint_data: Option<Vec<u8>>,
     ) -> Result<Box<dyn Processor>, BoxedError> {
         Ok(Box::new(NoopProcessor {}))
     }
@@ -71,6 +73,10 @@ impl Processor for NoopProcessor {
         Ok(())
     }
 
+    fn serialize_state(&self, _writer: &mut StateWriter) -> Result<(), BoxedError> {
+        Ok(())
+    }
+
     fn process(
         &mut self,
         mut op: TableOperation,
@@ -202,6 +208,7 @@ impl ProcessorFactory for NoopJoinProcessorFactory {
         _input_schemas: HashMap<PortHandle, Schema>,
         _output_schemas: HashMap<PortHandle, Schema>,
         _event_hub: EventHub,
+        _checkpoint_data: Option<Vec<u8>>,
     ) -> Result<Box<dyn Processor>, BoxedError> {
         Ok(Box::new(NoopJoinProcessor {}))
     }
@@ -219,6 +226,10 @@ impl Processor for NoopJoinProcessor {
         Ok(())
     }
 
+    fn serialize_state(&self, _writer: &mut StateWriter) -> Result<(), BoxedError> {
+        Ok(())
+    }
+
     fn process(
         &mut self,
         mut op: TableOperation,
@@ -6,6 +6,7 @@ use tokio::runtime::{self, Runtime};
 use crate::{errors::ExecutionError, executor::DagExecutor, Dag};
 
 mod app;
+mod checkpoint;
 mod checkpoint_ns;
 mod dag_base_create_errors;
 mod dag_base_errors;
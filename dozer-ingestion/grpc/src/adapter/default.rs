@@ -3,7 +3,7 @@ use dozer_ingestion_connector::{
     dozer_types::{
         self, chrono,
         grpc_types::{self, ingest::IngestRequest},
-        json_types::prost_to_json_value,
+        json_types::{json_to_string, prost_to_json_value},
         models::ingestion_types::{IngestionMessage, TransactionInfo},
         ordered_float::OrderedFloat,
         rust_decimal::Decimal,
@@ -112,7 +112,7 @@ fn map_record(rec: Vec<grpc_types::types::Value>, schema: &Schema) -> Result<Rec
     }
 
     for (idx, v) in rec.into_iter().enumerate() {
-        let typ = schema.fields[idx].typ;
+        let typ = schema.fields[idx].typ.clone();
 
         let val = v.value.map(|value| match (value, typ) {
             (
@@ -151,6 +151,20 @@ fn map_record(rec: Vec<grpc_types::types::Value>, schema: &Schema) -> Result<Rec
                 grpc_types::types::value::Value::JsonValue(a),
                 dozer_types::types::FieldType::Json,
             ) => Ok(dozer_types::types::Field::Json(prost_to_json_value(a))),
+            (
+                grpc_types::types::value::Value::JsonValue(a),
+                typ @ (dozer_types::types::FieldType::List(_)
+                | dozer_types::types::FieldType::Struct(_)),
+            ) => {
+                let json = json_to_string(&prost_to_json_value(a.clone()));
+                dozer_types::types::Field::from_str(&json, typ.clone(), true).map_err(|_| {
+                    Error::FieldTypeMismatch {
+                        index: idx,
+                        value: grpc_types::types::value::Value::JsonValue(a),
+                        field_type: typ,
+                    }
+                })
+            }
             (
                 grpc_types::types::value::Value::TimestampValue(a),
                 dozer_types::types::FieldType::Timestamp,
@@ -169,7 +183,7 @@ fn map_record(rec: Vec<grpc_types::types::Value>, schema: &Schema) -> Result<Rec
             ),
             (
                 grpc_types::types::value::Value::DecimalValue(d),
-                dozer_types::types::FieldType::Decimal,
+                dozer_types::types::FieldType::Decimal(_),
             ) => Ok(dozer_types::types::Field::Decimal(Decimal::from_parts(
                 d.lo, d.mid, d.hi, d.negative, d.scale,
             ))),
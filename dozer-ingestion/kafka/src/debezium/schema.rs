@@ -33,7 +33,7 @@ pub fn map_type(schema: &DebeziumSchemaStruct) -> Result<FieldType, KafkaSchemaE
             | "org.apache.kafka.connect.data.Timestamp" => Ok(FieldType::Timestamp),
             "io.debezium.time.Date" | "org.apache.kafka.connect.data.Date" => Ok(FieldType::Date),
             "org.apache.kafka.connect.data.Decimal" | "io.debezium.data.VariableScaleDecimal" => {
-                Ok(FieldType::Decimal)
+                Ok(FieldType::Decimal(None))
             }
             "io.debezium.data.Json" => Ok(FieldType::Json),
             _ => Err(KafkaSchemaError::TypeNotSupported(name)),
@@ -292,7 +292,7 @@ mod tests {
         test_map_type!(
             "int8",
             Some("org.apache.kafka.connect.data.Decimal".to_string()),
-            Ok(FieldType::Decimal)
+            Ok(FieldType::Decimal(None))
         );
         test_map_type!(
             "string",
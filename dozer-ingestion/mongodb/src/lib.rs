@@ -468,7 +468,27 @@ impl Connector for MongodbConnector {
     }
 
     fn types_mapping() -> Vec<(String, Option<FieldType>)> {
-        todo!();
+        // Documents are ingested whole as `Json`. Sub-documents have no fixed fields, so they
+        // stay `Json`, while arrays are lists of arbitrary values.
+        vec![
+            ("double".into(), Some(FieldType::Float)),
+            ("string".into(), Some(FieldType::String)),
+            ("object".into(), Some(FieldType::Json)),
+            (
+                "array".into(),
+                Some(FieldType::List(Box::new(FieldType::Json))),
+            ),
+            ("binData".into(), Some(FieldType::Binary)),
+            ("objectId".into(), Some(FieldType::String)),
+            ("bool".into(), Some(FieldType::Boolean)),
+            ("date".into(), Some(FieldType::Timestamp)),
+            ("null".into(), None),
+            ("regex".into(), Some(FieldType::String)),
+            ("int".into(), Some(FieldType::Int)),
+            ("timestamp".into(), Some(FieldType::Timestamp)),
+            ("long".into(), Some(FieldType::Int)),
+            ("decimal".into(), Some(FieldType::Decimal(None))),
+        ]
     }
 
     async fn list_columns(
@@ -399,7 +399,7 @@ impl BinlogIngestor<'_, '_, '_> {
                                                                     Err(BreakingSchemaChange::ColumnDataTypeChanged{
                                                                         table_name: table.to_string(),
                                                                         column_name: column.to_string(),
-                                                                        old_data_type: column.typ,
+                                                                        old_data_type: column.typ.clone(),
                                                                         new_column_name: new_type,
                                                                     })?
                                                                 }
@@ -53,7 +53,7 @@ impl Connector for MySQLConnector {
         Self: Sized,
     {
         vec![
-            ("decimal".into(), Some(FieldType::Decimal)),
+            ("decimal".into(), Some(FieldType::Decimal(None))),
             ("tinyint unsigned".into(), Some(FieldType::UInt)),
             ("tinyint".into(), Some(FieldType::Int)),
             ("smallint unsigned".into(), Some(FieldType::UInt)),
@@ -86,7 +86,7 @@ impl Connector for MySQLConnector {
             ("point".into(), Some(FieldType::Point)),
             ("json".into(), Some(FieldType::Json)),
             ("bit".into(), Some(FieldType::Int)),
-            ("enum".into(), Some(FieldType::String)),
+            ("enum".into(), Some(FieldType::Enum(vec![]))),
             ("set".into(), Some(FieldType::String)),
             ("null".into(), None),
             ("linestring".into(), None),
@@ -370,7 +370,7 @@ impl MySQLConnector {
                     let field_types: Vec<FieldType> = td
                         .columns
                         .iter()
-                        .map(|ColumnDefinition { typ, .. }| *typ)
+                        .map(|ColumnDefinition { typ, .. }| typ.clone())
                         .collect();
 
                     while let Some(result) = rows.next().await {
@@ -1,9 +1,10 @@
 use dozer_ingestion_connector::dozer_types::{
-    chrono::{DateTime, NaiveDate, NaiveDateTime, Offset, Utc},
+    chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Offset, Utc},
+    helper::json_value_to_field,
     json_types::{serde_json_to_json_value, JsonValue},
     rust_decimal::Decimal,
     serde_json,
-    types::{DozerDuration, DozerPoint, Field, FieldType, TimeUnit},
+    types::{DecimalType, DozerDuration, DozerPoint, Field, FieldType, TimeUnit},
 };
 use geozero::{wkb, GeomProcessor};
 use mysql_async::{Row, Value};
@@ -26,12 +27,8 @@ pub fn get_field_type_for_mysql_column_type(
     let is_array = column_type.ends_with(" array");
     let is_unsigned = column_type.contains(" unsigned");
 
-    if is_array {
-        return Err(MySQLConnectorError::UnsupportedFieldType(data_type.into()));
-    }
-
     let field_type = match data_type {
-        "decimal" => FieldType::Decimal,
+        "decimal" => FieldType::Decimal(parse_decimal_type(column_type)),
         "int" | "tinyint" | "smallint" | "mediumint" | "bigint" => {
             if is_unsigned {
                 FieldType::UInt
@@ -60,7 +57,7 @@ pub fn get_field_type_for_mysql_column_type(
         "point" => FieldType::Point,
         "json" => FieldType::Json,
         "bit" => FieldType::Int,
-        "enum" => FieldType::String,
+        "enum" => FieldType::Enum(parse_enum_values(column_type)),
         "set" => FieldType::String,
         "null" | "linestring" | "polygon" | "multipoint" | "multilinestring" | "multipolygon"
         | "geomcollection" | "geometry" => {
@@ -69,17 +66,80 @@ pub fn get_field_type_for_mysql_column_type(
         _ => Err(MySQLConnectorError::UnsupportedFieldType(data_type.into()))?,
     };
 
+    // Multi-valued index columns (`CAST(... AS <type> ARRAY)`) hold a list of the element type.
+    if is_array {
+        return Ok(FieldType::List(Box::new(field_type)));
+    }
+
     Ok(field_type)
 }
 
+/// Returns what's between the parentheses of a column type, e.g. `5,2` for `decimal(5,2)`.
+fn type_arguments(column_type: &str) -> Option<&str> {
+    let start = column_type.find('(')?;
+    let end = column_type.rfind(')')?;
+    column_type.get(start + 1..end)
+}
+
+fn parse_decimal_type(column_type: &str) -> Option<DecimalType> {
+    let mut arguments = type_arguments(column_type)?.split(',');
+    let precision = arguments.next()?.trim().parse().ok()?;
+    let scale = match arguments.next() {
+        Some(scale) => scale.trim().parse().ok()?,
+        None => 0,
+    };
+    Some(DecimalType::new(precision, scale))
+}
+
+/// Parses the quoted values of `enum('a','b')`. A quote inside a value is written as `''`.
+fn parse_enum_values(column_type: &str) -> Vec<String> {
+    let Some(arguments) = type_arguments(column_type) else {
+        return vec![];
+    };
+
+    let mut values = vec![];
+    let mut chars = arguments.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\'' {
+            continue;
+        }
+        let mut value = String::new();
+        while let Some(c) = chars.next() {
+            if c == '\'' {
+                if chars.peek() == Some(&'\'') {
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+            value.push(c);
+        }
+        values.push(value);
+    }
+    values
+}
+
+fn sql_decimal_type(info: &sqlparser::ast::ExactNumberInfo) -> Option<DecimalType> {
+    use sqlparser::ast::ExactNumberInfo;
+    match info {
+        ExactNumberInfo::None => None,
+        ExactNumberInfo::Precision(precision) => {
+            Some(DecimalType::new(u8::try_from(*precision).ok()?, 0))
+        }
+        ExactNumberInfo::PrecisionAndScale(precision, scale) => Some(DecimalType::new(
+            u8::try_from(*precision).ok()?,
+            u8::try_from(*scale).ok()?,
+        )),
+    }
+}
+
 pub fn get_field_type_for_sql_type(sql_data_type: &sqlparser::ast::DataType) -> FieldType {
     use sqlparser::ast::DataType;
     match sql_data_type {
-        DataType::Character(_)
-        | DataType::Char(_)
-        | DataType::String(_)
-        | DataType::Enum(_)
-        | DataType::Set(_) => FieldType::String,
+        DataType::Character(_) | DataType::Char(_) | DataType::String(_) | DataType::Set(_) => {
+            FieldType::String
+        }
+        DataType::Enum(values) => FieldType::Enum(values.clone()),
         DataType::CharacterVarying(_)
         | DataType::CharVarying(_)
         | DataType::Varchar(_)
@@ -98,11 +158,11 @@ pub fn get_field_type_for_sql_type(sql_data_type: &sqlparser::ast::DataType) ->
         | DataType::Blob(_)
         | DataType::Bytes(_)
         | DataType::Bytea => FieldType::Binary,
-        DataType::Numeric(_)
-        | DataType::Decimal(_)
-        | DataType::BigNumeric(_)
-        | DataType::BigDecimal(_)
-        | DataType::Dec(_) => FieldType::Decimal,
+        DataType::Numeric(info)
+        | DataType::Decimal(info)
+        | DataType::BigNumeric(info)
+        | DataType::BigDecimal(info)
+        | DataType::Dec(info) => FieldType::Decimal(sql_decimal_type(info)),
         DataType::Float(_)
         | DataType::Float4
         | DataType::Float64
@@ -200,7 +260,7 @@ impl<'a> IntoField<'a> for Value {
                 FieldType::String => Field::String(from_value_opt::<String>(value)?),
                 FieldType::Text => Field::Text(from_value_opt::<String>(value)?),
                 FieldType::Binary => Field::Binary(from_value_opt::<Vec<u8>>(value)?),
-                FieldType::Decimal => Field::Decimal(from_value_opt::<Decimal>(value)?),
+                FieldType::Decimal(_) => Field::Decimal(from_value_opt::<Decimal>(value)?),
                 FieldType::Timestamp => {
                     let date_time = from_value_opt::<NaiveDateTime>(value)?;
                     Field::Timestamp(DateTime::from_naive_utc_and_offset(date_time, Utc.fix()))
@@ -211,6 +271,20 @@ impl<'a> IntoField<'a> for Value {
                         serde_json_to_json_value(from_value_opt::<serde_json::Value>(value)?)?;
                     Field::Json(json)
                 }
+                // Binlog rows hold the 1-based index of the value instead of the value.
+                FieldType::Enum(values) => match value {
+                    Int(index) => enum_value(values, index.try_into().unwrap_or(0)),
+                    UInt(index) => enum_value(values, index),
+                    value => Field::String(from_value_opt::<String>(value)?),
+                },
+                FieldType::Uuid => {
+                    Field::from_str(&from_value_opt::<String>(value)?, FieldType::Uuid, false)?
+                }
+                FieldType::Time => Field::Time(from_value_opt::<NaiveTime>(value)?),
+                FieldType::List(_) | FieldType::Struct(_) => {
+                    let json = from_value_opt::<serde_json::Value>(value)?;
+                    json_value_to_field(json, field_type.clone(), true)?
+                }
                 FieldType::Point => {
                     let bytes = from_value_opt::<Vec<u8>>(value)?;
                     let (_srid, mut wkb_point) = bytes.as_slice().split_at(4);
@@ -230,6 +304,16 @@ impl<'a> IntoField<'a> for Value {
     }
 }
 
+/// Index 0 is MySQL's empty error value.
+fn enum_value(values: &[String], index: u64) -> Field {
+    let value = index
+        .checked_sub(1)
+        .and_then(|index| values.get(index as usize))
+        .cloned()
+        .unwrap_or_default();
+    Field::String(value)
+}
+
 pub trait IntoJsonValue {
     fn into_json_value(self) -> Result<JsonValue, MySQLConnectorError>;
 }
@@ -272,8 +356,15 @@ mod tests {
             ("mediumint", FieldType::Int),
             ("bigint unsigned", FieldType::UInt),
             ("year", FieldType::Int),
-            ("decimal(5, 2)", FieldType::Decimal),
-            ("decimal(10, 0) unsigned", FieldType::Decimal),
+            (
+                "decimal(5, 2)",
+                FieldType::Decimal(Some(DecimalType::new(5, 2))),
+            ),
+            (
+                "decimal(10,0) unsigned",
+                FieldType::Decimal(Some(DecimalType::new(10, 0))),
+            ),
+            ("decimal", FieldType::Decimal(None)),
             ("float", FieldType::Float),
             ("double", FieldType::Float),
             ("timestamp(4)", FieldType::Timestamp),
@@ -293,10 +384,18 @@ mod tests {
             ("tinyblob", FieldType::Binary),
             ("mediumblob", FieldType::Binary),
             ("longblob", FieldType::Binary),
-            ("enum('a','b','c')", FieldType::String),
+            (
+                "enum('a','b','it''s')",
+                FieldType::Enum(vec!["a".into(), "b".into(), "it's".into()]),
+            ),
             ("set('1','2','3')", FieldType::String),
             ("json", FieldType::Json),
             ("point", FieldType::Point),
+            ("int array", FieldType::List(Box::new(FieldType::Int))),
+            (
+                "char(10) array",
+                FieldType::List(Box::new(FieldType::String)),
+            ),
         ];
 
         let unsupported_types = [
@@ -309,7 +408,7 @@ mod tests {
             "geomcollection",
             "geometry",
             "some fictional type",
-            "int array",
+            "geometry array",
         ];
 
         for (mysql_type, expected_field_type) in supported_types {
@@ -374,7 +473,7 @@ mod tests {
         );
         assert_eq!(
             Field::Decimal(9.into()),
-            Value::Int(9).into_field(&FieldType::Decimal).unwrap()
+            Value::Int(9).into_field(&FieldType::Decimal(None)).unwrap()
         );
         assert_eq!(
             Field::Timestamp(DateTime::from_naive_utc_and_offset(
@@ -418,6 +517,28 @@ mod tests {
                 .unwrap()
         );
 
+        let enum_type = FieldType::Enum(vec!["a".into(), "b".into()]);
+        assert_eq!(
+            Field::String("b".into()),
+            Value::Bytes(b"b".as_slice().into())
+                .into_field(&enum_type)
+                .unwrap()
+        );
+        assert_eq!(
+            Field::String("b".into()),
+            Value::Int(2).into_field(&enum_type).unwrap()
+        );
+        assert_eq!(
+            Field::String("".into()),
+            Value::Int(0).into_field(&enum_type).unwrap()
+        );
+        assert_eq!(
+            Field::Time(NaiveTime::from_hms_micro_opt(10, 30, 0, 15).unwrap()),
+            Value::Time(false, 0, 10, 30, 0, 15)
+                .into_field(&FieldType::Time)
+                .unwrap()
+        );
+
         assert_eq!(
             Field::Null,
             Value::NULL.into_field(&FieldType::Int).unwrap()
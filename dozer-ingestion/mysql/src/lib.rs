@@ -1,5 +1,5 @@
 use dozer_ingestion_connector::dozer_types::{
-    errors::types::DeserializationError,
+    errors::types::{DeserializationError, TypeError},
     thiserror::{self, Error},
     types::FieldType,
 };
@@ -32,6 +32,9 @@ pub enum MySQLConnectorError {
     #[error("Invalid json value. {0}")]
     JsonDeserializationError(#[from] DeserializationError),
 
+    #[error("Invalid list or struct value. {0}")]
+    InvalidNestedValue(#[from] TypeError),
+
     #[error("Invalid geometric value. {0}")]
     InvalidGeometricValue(#[from] GeozeroError),
 
@@ -8,7 +8,7 @@ use dozer_ingestion_connector::dozer_types::{
     rust_decimal, serde_json,
     types::*,
 };
-use postgres_types::{FromSql, Type, WasNull};
+use postgres_types::{FromSql, Kind, Type, WasNull};
 use rust_decimal::prelude::FromPrimitive;
 use rust_decimal::Decimal;
 use std::error::Error;
@@ -99,7 +99,14 @@ pub fn postgres_type_to_field(
     value: Option<&Bytes>,
     column: &TableColumn,
 ) -> Result<Field, PostgresSchemaError> {
-    let column_type = column.r#type.clone();
+    postgres_value_to_field(value, &column.r#type)
+}
+
+fn postgres_value_to_field(
+    value: Option<&Bytes>,
+    column_type: &Type,
+) -> Result<Field, PostgresSchemaError> {
+    let column_type = column_type.clone();
     value.map_or(Ok(Field::Null), |v| match column_type {
         Type::INT2 | Type::INT4 | Type::INT8 => Ok(Field::Int(
             String::from_utf8(v.to_vec()).unwrap().parse().unwrap(),
@@ -113,7 +120,15 @@ pub fn postgres_type_to_field(
         Type::TEXT | Type::VARCHAR | Type::CHAR | Type::BPCHAR | Type::ANYENUM => {
             Ok(Field::String(String::from_utf8(v.to_vec()).unwrap()))
         }
-        Type::UUID => Ok(Field::String(String::from_utf8(v.to_vec()).unwrap())),
+        Type::UUID => Ok(Field::Uuid(
+            String::from_utf8(v.to_vec())?
+                .parse::<Uuid>()
+                .map_err(|e| PostgresSchemaError::ValueConversionError(e.to_string()))?,
+        )),
+        Type::TIME => Ok(Field::Time(NaiveTime::parse_from_str(
+            &String::from_utf8(v.to_vec())?,
+            TIME_FORMAT,
+        )?)),
         Type::BYTEA => Ok(Field::Binary(v.to_vec())),
         Type::NUMERIC => Ok(Field::Decimal(
             Decimal::from_f64(
@@ -172,36 +187,91 @@ pub fn postgres_type_to_field(
                 .parse::<DozerPoint>()
                 .map_err(|_| PostgresSchemaError::PointParseError)?,
         )),
-        _ => Err(PostgresSchemaError::ColumnTypeNotSupported(
-            column_type.name().to_string(),
-        )),
+        _ => match column_type.kind() {
+            Kind::Array(element_type) => {
+                let text = String::from_utf8(v.to_vec())?;
+                parse_array_literal(&text)?
+                    .into_iter()
+                    .map(|element| {
+                        postgres_value_to_field(element.map(Bytes::from).as_ref(), element_type)
+                    })
+                    .collect::<Result<_, _>>()
+                    .map(Field::List)
+            }
+            _ => Err(PostgresSchemaError::ColumnTypeNotSupported(
+                column_type.name().to_string(),
+            )),
+        },
     })
 }
 
+/// Splits the text representation of a one-dimensional array, e.g. `{1,NULL,"a,b"}`, into its elements.
+fn parse_array_literal(text: &str) -> Result<Vec<Option<String>>, PostgresSchemaError> {
+    let error = || PostgresSchemaError::ArrayParseError(text.to_string());
+    let inner = text
+        .strip_prefix('{')
+        .and_then(|text| text.strip_suffix('}'))
+        .ok_or_else(error)?;
+    let mut elements = vec![];
+    if inner.is_empty() {
+        return Ok(elements);
+    }
+
+    let mut element = String::new();
+    let mut quoted = false;
+    let mut in_quotes = false;
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => element.push(chars.next().ok_or_else(error)?),
+            '"' => {
+                in_quotes = !in_quotes;
+                quoted = true;
+            }
+            ',' if !in_quotes => {
+                elements.push(array_element(std::mem::take(&mut element), quoted));
+                quoted = false;
+            }
+            // Multi-dimensional arrays are not supported.
+            '{' | '}' if !in_quotes => return Err(error()),
+            c => element.push(c),
+        }
+    }
+    if in_quotes {
+        return Err(error());
+    }
+    elements.push(array_element(element, quoted));
+    Ok(elements)
+}
+
+fn array_element(element: String, quoted: bool) -> Option<String> {
+    (quoted || !element.eq_ignore_ascii_case("NULL")).then_some(element)
+}
+
 pub fn postgres_type_to_dozer_type(column_type: Type) -> Result<FieldType, PostgresSchemaError> {
     match column_type {
         Type::BOOL => Ok(FieldType::Boolean),
         Type::INT2 | Type::INT4 | Type::INT8 => Ok(FieldType::Int),
-        Type::CHAR | Type::TEXT | Type::VARCHAR | Type::BPCHAR | Type::UUID | Type::ANYENUM => {
+        Type::CHAR | Type::TEXT | Type::VARCHAR | Type::BPCHAR | Type::ANYENUM => {
             Ok(FieldType::String)
         }
+        Type::UUID => Ok(FieldType::Uuid),
+        Type::TIME => Ok(FieldType::Time),
         Type::FLOAT4 | Type::FLOAT8 => Ok(FieldType::Float),
         Type::BYTEA => Ok(FieldType::Binary),
         Type::TIMESTAMP | Type::TIMESTAMPTZ => Ok(FieldType::Timestamp),
-        Type::NUMERIC => Ok(FieldType::Decimal),
-        Type::JSONB
-        | Type::JSON
-        | Type::JSONB_ARRAY
-        | Type::JSON_ARRAY
-        | Type::TEXT_ARRAY
-        | Type::CHAR_ARRAY
-        | Type::VARCHAR_ARRAY
-        | Type::BPCHAR_ARRAY => Ok(FieldType::Json),
+        Type::NUMERIC => Ok(FieldType::Decimal(None)),
+        Type::JSONB | Type::JSON | Type::JSONB_ARRAY | Type::JSON_ARRAY => Ok(FieldType::Json),
         Type::DATE => Ok(FieldType::Date),
         Type::POINT => Ok(FieldType::Point),
-        _ => Err(PostgresSchemaError::ColumnTypeNotSupported(
-            column_type.name().to_string(),
-        )),
+        _ => match column_type.kind() {
+            Kind::Array(element_type) => Ok(FieldType::List(Box::new(
+                postgres_type_to_dozer_type(element_type.clone())?,
+            ))),
+            _ => Err(PostgresSchemaError::ColumnTypeNotSupported(
+                column_type.name().to_string(),
+            )),
+        },
     }
 }
 
@@ -232,10 +302,51 @@ conversion_fn!(convert_timestamp, |v: NaiveDateTime| Field::Timestamp(
 ));
 conversion_fn!(convert_timestamptz, Field::Timestamp);
 conversion_fn!(convert_date_snapshot, Field::Date);
+conversion_fn!(convert_time, Field::Time);
 conversion_fn!(convert_binary, Field::Binary);
 conversion_fn!(convert_point, |v: GeoPoint| Field::Point(v.x_y().into()));
 conversion_fn!(convert_decimal, Field::Decimal);
 
+macro_rules! array_conversion_fn {
+    ($name:ident, $element:ty, $typ:expr) => {
+        fn $name(row: &Row, idx: usize) -> Result<Field, PostgresSchemaError> {
+            row.try_get(idx)
+                .map(|v: Vec<Option<$element>>| {
+                    Field::List(v.into_iter().map(|v| v.map_or(Field::Null, $typ)).collect())
+                })
+                .or_else(handle_error)
+        }
+    };
+}
+
+array_conversion_fn!(convert_bool_array, bool, Field::Boolean);
+array_conversion_fn!(convert_int2_array, i16, |v| Field::Int(v.into()));
+array_conversion_fn!(convert_int4_array, i32, |v| Field::Int(v.into()));
+array_conversion_fn!(convert_int8_array, i64, Field::Int);
+array_conversion_fn!(convert_float_array, f32, |v| Field::Float(OrderedFloat(
+    v.into()
+)));
+array_conversion_fn!(convert_double_array, f64, |v| Field::Float(OrderedFloat(v)));
+array_conversion_fn!(convert_string_array, String, Field::String);
+array_conversion_fn!(
+    convert_timestamp_array,
+    NaiveDateTime,
+    |v| Field::Timestamp(v.and_utc().fixed_offset())
+);
+array_conversion_fn!(
+    convert_timestamptz_array,
+    DateTime<FixedOffset>,
+    Field::Timestamp
+);
+array_conversion_fn!(convert_decimal_array, Decimal, Field::Decimal);
+array_conversion_fn!(convert_date_array, NaiveDate, Field::Date);
+array_conversion_fn!(convert_time_array, NaiveTime, Field::Time);
+array_conversion_fn!(convert_binary_array, Vec<u8>, Field::Binary);
+array_conversion_fn!(convert_point_array, GeoPoint, |v| Field::Point(
+    v.x_y().into()
+));
+array_conversion_fn!(convert_uuid_array, Uuid, Field::Uuid);
+
 #[inline(always)]
 fn convert_int<'a, T: Into<i64> + FromSql<'a>>(
     row: &'a Row,
@@ -278,14 +389,9 @@ fn convert_jsonarray(row: &Row, idx: usize) -> Result<Field, PostgresSchemaError
         Ok(Field::Json(field.into()))
     })
 }
-fn convert_textarray(row: &Row, idx: usize) -> Result<Field, PostgresSchemaError> {
-    let value: Result<Vec<String>, _> = row.try_get(idx);
-    value.map_or_else(handle_error, |val| Ok(Field::Json(val.into())))
-}
-
 fn convert_uuid(row: &Row, idx: usize) -> Result<Field, PostgresSchemaError> {
     let value: Result<Uuid, _> = row.try_get(idx);
-    value.map_or_else(handle_error, |val| Ok(Field::from(val.to_string())))
+    value.map_or_else(handle_error, |val| Ok(Field::Uuid(val)))
 }
 
 type ConversionFn = fn(&Row, usize) -> Result<Field, PostgresSchemaError>;
@@ -305,15 +411,30 @@ pub fn get_conversion_fn(col_type: &Type) -> Result<ConversionFn, PostgresSchema
         &Type::TIMESTAMPTZ => Ok(convert_timestamptz),
         &Type::NUMERIC => Ok(convert_decimal),
         &Type::DATE => Ok(convert_date_snapshot),
+        &Type::TIME => Ok(convert_time),
         &Type::BYTEA => Ok(convert_binary),
         &Type::JSONB | &Type::JSON => Ok(convert_json),
         &Type::JSONB_ARRAY | &Type::JSON_ARRAY => Ok(convert_jsonarray),
-        &Type::CHAR_ARRAY | &Type::TEXT_ARRAY | &Type::VARCHAR_ARRAY | &Type::BPCHAR_ARRAY => {
-            Ok(convert_textarray)
-        }
         &Type::POINT => Ok(convert_point),
         // &Type::UUID => convert_row_value_to_field!(row, idx, Uuid),
         &Type::UUID => Ok(convert_uuid),
+        &Type::BOOL_ARRAY => Ok(convert_bool_array),
+        &Type::INT2_ARRAY => Ok(convert_int2_array),
+        &Type::INT4_ARRAY => Ok(convert_int4_array),
+        &Type::INT8_ARRAY => Ok(convert_int8_array),
+        &Type::CHAR_ARRAY | &Type::TEXT_ARRAY | &Type::VARCHAR_ARRAY | &Type::BPCHAR_ARRAY => {
+            Ok(convert_string_array)
+        }
+        &Type::FLOAT4_ARRAY => Ok(convert_float_array),
+        &Type::FLOAT8_ARRAY => Ok(convert_double_array),
+        &Type::TIMESTAMP_ARRAY => Ok(convert_timestamp_array),
+        &Type::TIMESTAMPTZ_ARRAY => Ok(convert_timestamptz_array),
+        &Type::NUMERIC_ARRAY => Ok(convert_decimal_array),
+        &Type::DATE_ARRAY => Ok(convert_date_array),
+        &Type::TIME_ARRAY => Ok(convert_time_array),
+        &Type::BYTEA_ARRAY => Ok(convert_binary_array),
+        &Type::POINT_ARRAY => Ok(convert_point_array),
+        &Type::UUID_ARRAY => Ok(convert_uuid_array),
         _ => {
             if col_type.schema() == "pg_catalog" {
                 Err(PostgresSchemaError::ColumnTypeNotSupported(
@@ -403,13 +524,16 @@ mod tests {
         test_conversion!("Test text", Type::TEXT, Field::String(value.clone()));
         test_conversion!("Test text", Type::ANYENUM, Field::String(value));
 
-        let value = String::from("a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11");
+        let value = Uuid::parse_str("a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11").unwrap();
         test_conversion!(
             "a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11",
             Type::UUID,
-            Field::String(value)
+            Field::Uuid(value)
         );
 
+        let value = NaiveTime::from_hms_micro_opt(13, 45, 7, 250_000).unwrap();
+        test_conversion!("13:45:07.25", Type::TIME, Field::Time(value));
+
         // UTF-8 bytes representation of json (https://www.charset.org/utf-8)
         let value: Vec<u8> = vec![98, 121, 116, 101, 97];
         test_conversion!("bytea", Type::BYTEA, Field::Binary(value));
@@ -502,6 +626,22 @@ mod tests {
             Type::POINT,
             Field::Point(DozerPoint::from((1.234, 2.456)))
         );
+
+        test_conversion!(
+            "{1,NULL,3}",
+            Type::INT4_ARRAY,
+            Field::List(vec![Field::Int(1), Field::Null, Field::Int(3)])
+        );
+        test_conversion!(
+            r#"{foo,"a, \"b\"","NULL"}"#,
+            Type::TEXT_ARRAY,
+            Field::List(vec![
+                Field::String("foo".to_string()),
+                Field::String("a, \"b\"".to_string()),
+                Field::String("NULL".to_string()),
+            ])
+        );
+        test_conversion!("{}", Type::BOOL_ARRAY, Field::List(vec![]));
     }
 
     #[test]
@@ -510,15 +650,24 @@ mod tests {
         test_type_mapping!(Type::FLOAT8, FieldType::Float);
         test_type_mapping!(Type::VARCHAR, FieldType::String);
         test_type_mapping!(Type::ANYENUM, FieldType::String);
-        test_type_mapping!(Type::UUID, FieldType::String);
+        test_type_mapping!(Type::UUID, FieldType::Uuid);
+        test_type_mapping!(Type::TIME, FieldType::Time);
         test_type_mapping!(Type::BYTEA, FieldType::Binary);
-        test_type_mapping!(Type::NUMERIC, FieldType::Decimal);
+        test_type_mapping!(Type::NUMERIC, FieldType::Decimal(None));
         test_type_mapping!(Type::TIMESTAMP, FieldType::Timestamp);
         test_type_mapping!(Type::TIMESTAMPTZ, FieldType::Timestamp);
         test_type_mapping!(Type::JSONB, FieldType::Json);
         test_type_mapping!(Type::JSON, FieldType::Json);
         test_type_mapping!(Type::JSONB_ARRAY, FieldType::Json);
         test_type_mapping!(Type::JSON_ARRAY, FieldType::Json);
+        test_type_mapping!(
+            Type::TEXT_ARRAY,
+            FieldType::List(Box::new(FieldType::String))
+        );
+        test_type_mapping!(
+            Type::TIMESTAMPTZ_ARRAY,
+            FieldType::List(Box::new(FieldType::Timestamp))
+        );
         test_type_mapping!(Type::BOOL, FieldType::Boolean);
         test_type_mapping!(Type::POINT, FieldType::Point);
     }
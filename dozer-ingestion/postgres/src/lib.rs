@@ -178,6 +178,9 @@ pub enum PostgresSchemaError {
     #[error("Point parse failed")]
     PointParseError,
 
+    #[error("Array parse failed: {0}")]
+    ArrayParseError(String),
+
     #[error("Unsupported replication type - '{0}'")]
     UnsupportedReplicationType(String),
 
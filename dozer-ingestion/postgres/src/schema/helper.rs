@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 
 use dozer_ingestion_connector::{
-    dozer_types::types::{FieldDefinition, FieldType, Schema, SourceDefinition},
+    dozer_types::types::{DecimalType, FieldDefinition, FieldType, Schema, SourceDefinition},
     utils::ListOrFilterColumns,
     CdcType, SourceSchema,
 };
@@ -365,6 +365,10 @@ impl SchemaHelper {
                 postgres_type_to_dozer_type,
             )?
         };
+        let typ = match typ {
+            FieldType::Decimal(None) => FieldType::Decimal(numeric_type(row.get(9), row.get(10))),
+            typ => typ,
+        };
 
         let replication_type =
             String::from_utf8(vec![replication_type_int as u8]).map_err(|_e| {
@@ -381,6 +385,14 @@ impl SchemaHelper {
     }
 }
 
+/// `NUMERIC` columns declared without a precision report no precision and are left unbounded.
+fn numeric_type(precision: Option<i32>, scale: Option<i32>) -> Option<DecimalType> {
+    Some(DecimalType::new(
+        u8::try_from(precision?).ok()?,
+        u8::try_from(scale.unwrap_or(0)).ok()?,
+    ))
+}
+
 pub const DEFAULT_SCHEMA_NAME: &str = "public";
 
 fn find_table(
@@ -419,7 +431,9 @@ SELECT table_info.table_name,
        pc.relreplident,
        pt.oid                                                           AS type_oid,
        t.table_type,
-       t.table_schema
+       t.table_schema,
+       table_info.numeric_precision::int4                               AS numeric_precision,
+       table_info.numeric_scale::int4                                   AS numeric_scale
 FROM information_schema.columns table_info
          LEFT JOIN information_schema.tables t ON t.table_name = table_info.table_name AND t.table_schema = table_info.table_schema
          LEFT JOIN pg_namespace ns ON t.table_schema = ns.nspname
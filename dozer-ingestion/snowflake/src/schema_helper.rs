@@ -39,7 +39,7 @@ impl SchemaHelper {
         match type_name {
             "NUMBER" => scale.map_or(Ok(FieldType::Int), |scale| {
                 if scale > 0 {
-                    Ok(FieldType::Decimal)
+                    Ok(FieldType::Decimal(None))
                 } else {
                     Ok(FieldType::Int)
                 }
@@ -306,12 +306,19 @@ fn assert_record_matches_schema(record: &Record, schema: &Schema, only_match_pk:
             FieldType::String => assert!(value.as_string().is_some()),
             FieldType::Text => assert!(value.as_text().is_some()),
             FieldType::Binary => assert!(value.as_binary().is_some()),
-            FieldType::Decimal => assert!(value.as_decimal().is_some()),
+            FieldType::Decimal(_) => assert!(value.as_decimal().is_some()),
             FieldType::Timestamp => assert!(value.as_timestamp().is_some()),
             FieldType::Date => assert!(value.as_date().is_some()),
             FieldType::Json => assert!(value.as_json().is_some()),
             FieldType::Point => assert!(value.as_point().is_some()),
             FieldType::Duration => assert!(value.as_duration().is_some()),
+            FieldType::List(_) => assert!(value.as_list().is_some()),
+            FieldType::Struct(_) => assert!(value.as_struct().is_some()),
+            FieldType::Uuid => assert!(value.as_uuid().is_some()),
+            FieldType::Time => assert!(value.as_time().is_some()),
+            FieldType::Enum(ref values) => {
+                assert!(values.iter().any(|v| Some(v.as_str()) == value.as_string()))
+            }
         }
     }
 }
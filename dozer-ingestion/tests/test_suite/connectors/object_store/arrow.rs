@@ -273,7 +273,7 @@ pub fn record_batch_with_all_supported_data_types() -> arrow::record_batch::Reco
         .expect("BUG in record_batch_with_all_supported_data_types")
 }
 
-fn field_type_to_arrow(field_type: FieldType) -> Option<arrow::datatypes::DataType> {
+fn field_type_to_arrow(field_type: &FieldType) -> Option<arrow::datatypes::DataType> {
     match field_type {
         FieldType::UInt => Some(arrow::datatypes::DataType::UInt64),
         FieldType::U128 => None,
@@ -285,7 +285,7 @@ fn field_type_to_arrow(field_type: FieldType) -> Option<arrow::datatypes::DataTy
         FieldType::String => Some(arrow::datatypes::DataType::Utf8),
         FieldType::Text => Some(arrow::datatypes::DataType::LargeUtf8),
         FieldType::Binary => Some(arrow::datatypes::DataType::LargeBinary),
-        FieldType::Decimal => None,
+        FieldType::Decimal(_) => None,
         FieldType::Timestamp => Some(arrow::datatypes::DataType::Timestamp(
             arrow::datatypes::TimeUnit::Nanosecond,
             None,
@@ -296,11 +296,16 @@ fn field_type_to_arrow(field_type: FieldType) -> Option<arrow::datatypes::DataTy
         FieldType::Duration => Some(arrow::datatypes::DataType::Duration(
             arrow::datatypes::TimeUnit::Nanosecond,
         )),
+        FieldType::List(_)
+        | FieldType::Struct(_)
+        | FieldType::Uuid
+        | FieldType::Time
+        | FieldType::Enum(_) => None,
     }
 }
 
 fn field_definition_to_arrow(field_definition: FieldDefinition) -> Option<arrow::datatypes::Field> {
-    field_type_to_arrow(field_definition.typ).map(|data_type| {
+    field_type_to_arrow(&field_definition.typ).map(|data_type| {
         arrow::datatypes::Field::new(field_definition.name, data_type, field_definition.nullable)
     })
 }
@@ -315,7 +320,7 @@ pub fn schema_to_arrow(fields: Vec<FieldDefinition>) -> (arrow::datatypes::Schem
 
     let fields = fields
         .into_iter()
-        .filter_map(|field| field_type_to_arrow(field.typ).map(|_| field))
+        .filter_map(|field| field_type_to_arrow(&field.typ).map(|_| field))
         .collect();
 
     (arrow_schema, (fields, vec![]))
@@ -324,7 +329,7 @@ pub fn schema_to_arrow(fields: Vec<FieldDefinition>) -> (arrow::datatypes::Schem
 fn fields_to_arrow<'a, F: IntoIterator<Item = &'a Field>>(
     fields: F,
     count: usize,
-    field_type: FieldType,
+    field_type: &FieldType,
 ) -> Arc<dyn arrow::array::Array> {
     match field_type {
         FieldType::UInt => {
@@ -418,7 +423,7 @@ fn fields_to_arrow<'a, F: IntoIterator<Item = &'a Field>>(
             }
             Arc::new(builder.finish())
         }
-        FieldType::Decimal => panic!("Decimal not supported"),
+        FieldType::Decimal(_) => panic!("Decimal not supported"),
         FieldType::Timestamp => {
             let mut builder = arrow::array::TimestampNanosecondArray::builder(count);
             for field in fields {
@@ -456,6 +461,10 @@ fn fields_to_arrow<'a, F: IntoIterator<Item = &'a Field>>(
             Arc::new(builder.finish())
         }
         FieldType::Point => panic!("Point not supported"),
+        FieldType::List(_) | FieldType::Struct(_) => panic!("List and struct not supported"),
+        FieldType::Uuid | FieldType::Time | FieldType::Enum(_) => {
+            panic!("Uuid, time and enum not supported")
+        }
         FieldType::Duration => {
             let mut builder = arrow::array::DurationNanosecondArray::builder(count);
             for field in fields {
@@ -476,9 +485,9 @@ pub fn records_to_arrow(
 ) -> arrow::record_batch::RecordBatch {
     let mut columns = vec![];
     for (index, field) in fields.iter().enumerate() {
-        if field_type_to_arrow(field.typ).is_some() {
+        if field_type_to_arrow(&field.typ).is_some() {
             let fields = records.iter().map(|record| &record[index]);
-            let column = fields_to_arrow(fields, records.len(), field.typ);
+            let column = fields_to_arrow(fields, records.len(), &field.typ);
             columns.push(column);
         }
     }
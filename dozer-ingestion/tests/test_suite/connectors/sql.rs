@@ -153,7 +153,7 @@ pub fn create_schema(name: &str) -> String {
     )
 }
 
-fn field_type_to_sql(field_type: FieldType) -> Option<String> {
+fn field_type_to_sql(field_type: &FieldType) -> Option<String> {
     match field_type {
         FieldType::UInt => None,
         FieldType::U128 => None,
@@ -165,17 +165,19 @@ fn field_type_to_sql(field_type: FieldType) -> Option<String> {
         FieldType::String => Some("TEXT".to_string()),
         FieldType::Text => None,
         FieldType::Binary => Some("BYTEA".to_string()),
-        FieldType::Decimal => Some("NUMERIC".to_string()),
+        FieldType::Decimal(_) => Some("NUMERIC".to_string()),
         FieldType::Timestamp => Some("TIMESTAMP".to_string()),
         FieldType::Date => Some("DATE".to_string()),
         FieldType::Json => Some("JSONB".to_string()),
         FieldType::Point => Some("POINT".to_string()),
         FieldType::Duration => Some("DURATION".to_string()),
+        FieldType::Uuid => Some("UUID".to_string()),
+        FieldType::List(_) | FieldType::Struct(_) | FieldType::Time | FieldType::Enum(_) => None,
     }
 }
 
 fn field_definition_to_sql(field_definition: &FieldDefinition) -> Option<String> {
-    let field_type = field_type_to_sql(field_definition.typ)?;
+    let field_type = field_type_to_sql(&field_definition.typ)?;
     let nullable = if field_definition.nullable {
         ""
     } else {
@@ -225,8 +227,9 @@ pub fn create_table(schema_name: Option<&str>, table_name: &str, schema: &Fields
     )
 }
 
-fn field_to_sql(field: &Field) -> String {
-    match field {
+/// The SQL literal of a value, or `None` if there's no column type for it.
+fn field_to_sql(field: &Field) -> Option<String> {
+    Some(match field {
         Field::UInt(i) => i.to_string(),
         Field::U128(i) => i.to_string(),
         Field::Int(i) => i.to_string(),
@@ -244,7 +247,16 @@ fn field_to_sql(field: &Field) -> String {
         Field::Point(p) => format!("'({},{})'", p.0.x(), p.0.y()),
         Field::Duration(_) => field.to_string(),
         Field::Null => "NULL".to_string(),
-    }
+        Field::Uuid(u) => format!("'{}'", u),
+        Field::Time(t) => format!("'{}'", t),
+        Field::List(_) | Field::Struct(_) => return None,
+    })
+}
+
+/// The SQL literal of a value in a column, or `None` if the column is not in the table `schema_to_sql` creates.
+fn column_value_to_sql(field_definition: &FieldDefinition, value: &Field) -> Option<String> {
+    field_type_to_sql(&field_definition.typ)?;
+    field_to_sql(value)
 }
 
 pub fn insert_record(
@@ -253,19 +265,22 @@ pub fn insert_record(
     record: &[Field],
     fields: &[FieldDefinition],
 ) -> String {
+    let mut columns_sql = vec![];
     let mut values_sql = vec![];
     for (field, value) in fields.iter().zip(record.iter()) {
-        if field_type_to_sql(field.typ).is_none() {
+        let Some(value_sql) = column_value_to_sql(field, value) else {
             continue;
-        }
-        values_sql.push(field_to_sql(value));
+        };
+        columns_sql.push(field.name.as_str());
+        values_sql.push(value_sql);
     }
 
     format!(
         r#"
-        INSERT INTO {table_name} VALUES ({values});
+        INSERT INTO {table_name} ({columns}) VALUES ({values});
         "#,
         table_name = full_table_name(schema_name, table_name),
+        columns = columns_sql.join(", "),
         values = values_sql.join(", "),
     )
 }
@@ -282,24 +297,19 @@ fn update_record(
     for (index, ((old_field, new_field), field_definition)) in
         old.iter().zip(new).zip(fields).enumerate()
     {
-        if field_type_to_sql(field_definition.typ).is_none() {
+        let (Some(new_sql), Some(old_sql)) = (
+            column_value_to_sql(field_definition, new_field),
+            column_value_to_sql(field_definition, old_field),
+        ) else {
             continue;
-        }
+        };
 
-        set.push(format!(
-            "{} = {}",
-            field_definition.name,
-            field_to_sql(new_field)
-        ));
+        set.push(format!("{} = {}", field_definition.name, new_sql));
 
         let is_primary_key = primary_key.iter().any(|i| *i == index);
 
         if is_primary_key {
-            where_.push(format!(
-                "{} = {}",
-                field_definition.name,
-                field_to_sql(old_field)
-            ));
+            where_.push(format!("{} = {}", field_definition.name, old_sql));
         }
     }
 
@@ -321,18 +331,14 @@ fn delete_record(
 ) -> String {
     let mut where_ = vec![];
     for (index, (field, field_definition)) in record.iter().zip(fields).enumerate() {
-        if field_type_to_sql(field_definition.typ).is_none() {
+        let Some(value_sql) = column_value_to_sql(field_definition, field) else {
             continue;
-        }
+        };
 
         let is_primary_key = primary_key.iter().any(|i| *i == index);
 
         if is_primary_key {
-            where_.push(format!(
-                "{} = {}",
-                field_definition.name,
-                field_to_sql(field)
-            ));
+            where_.push(format!("{} = {}", field_definition.name, value_sql));
         }
     }
 
@@ -1,6 +1,7 @@
 use std::{net::AddrParseError, path::PathBuf};
 
 use dozer_ingestion_connector::dozer_types::{
+    errors::types::TypeError,
     serde_json,
     thiserror::{self, Error},
 };
@@ -22,6 +23,8 @@ pub enum Error {
     SchemaNotFound(String),
     #[error("field {0} not found in schema")]
     FieldNotFound(String),
+    #[error("type error: {0}")]
+    Type(#[from] TypeError),
     #[error("actix web start error: {0}")]
     ActixWebStartError(#[from] std::io::Error),
 }
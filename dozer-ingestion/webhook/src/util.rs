@@ -2,6 +2,7 @@ use crate::Error;
 use dozer_ingestion_connector::{
     dozer_types::{
         chrono::{self, NaiveDate},
+        helper::json_value_to_field,
         json_types::json_from_str,
         models::ingestion_types::WebhookConfigSchemas,
         ordered_float::OrderedFloat,
@@ -112,7 +113,7 @@ pub fn map_record(
                     let field = Field::Binary(str_value.into_bytes());
                     values.push(field);
                 }
-                FieldType::Decimal => {
+                FieldType::Decimal(_) => {
                     let str_value: String = serde_json::from_value(value.clone())?;
                     let decimal_value: Decimal =
                         Decimal::from_str_exact(str_value.as_str()).unwrap();
@@ -125,6 +126,14 @@ pub fn map_record(
                     let field = Field::Json(ivalue_str);
                     values.push(field);
                 }
+                FieldType::List(_)
+                | FieldType::Struct(_)
+                | FieldType::Uuid
+                | FieldType::Time
+                | FieldType::Enum(_) => {
+                    let field = json_value_to_field(value.clone(), field.typ, field.nullable)?;
+                    values.push(field);
+                }
                 FieldType::Point => {
                     values.push(Field::Null);
                 }
@@ -0,0 +1,101 @@
+use std::{
+    collections::HashMap,
+    fmt::{self, Debug, Formatter},
+};
+
+use aerospike::{
+    Bin, Bins as BinSelection, Client, ClientPolicy, ErrorKind, Key, ReadPolicy,
+    RecordExistsAction, ResultCode, Value, WritePolicy,
+};
+use dozer_types::models::connection::AerospikeConnection;
+
+use crate::errors::AerospikeSinkError;
+
+/// Bins of an Aerospike record, by name.
+pub type Bins = HashMap<String, Value>;
+
+/// Identifies an Aerospike record.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AerospikeKey {
+    pub namespace: String,
+    pub set: String,
+    pub key: Value,
+}
+
+impl AerospikeKey {
+    pub fn new(namespace: impl Into<String>, set: impl Into<String>, key: Value) -> Self {
+        Self {
+            namespace: namespace.into(),
+            set: set.into(),
+            key,
+        }
+    }
+}
+
+/// The operations the sink needs from Aerospike. Implemented by [`aerospike::Client`], and faked in tests.
+pub trait AerospikeClient: Send + Sync {
+    /// Reads all the bins of a record, or `None` if the record doesn't exist.
+    fn get(&self, key: &AerospikeKey) -> Result<Option<Bins>, AerospikeSinkError>;
+
+    /// Writes a record, replacing all its bins.
+    fn put(&self, key: &AerospikeKey, bins: &Bins) -> Result<(), AerospikeSinkError>;
+
+    /// Deletes a record. It's not an error if the record doesn't exist.
+    fn delete(&self, key: &AerospikeKey) -> Result<(), AerospikeSinkError>;
+}
+
+/// [`aerospike::Client`] doesn't implement `Debug`.
+impl Debug for dyn AerospikeClient {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("AerospikeClient")
+    }
+}
+
+pub fn connect(connection: &AerospikeConnection) -> Result<Client, AerospikeSinkError> {
+    Ok(Client::new(&ClientPolicy::default(), &connection.hosts)?)
+}
+
+impl AerospikeClient for Client {
+    fn get(&self, key: &AerospikeKey) -> Result<Option<Bins>, AerospikeSinkError> {
+        match Client::get(
+            self,
+            &ReadPolicy::default(),
+            &to_key(key)?,
+            BinSelection::All,
+        ) {
+            Ok(record) => Ok(Some(record.bins)),
+            Err(aerospike::Error(ErrorKind::ServerError(ResultCode::KeyNotFoundError), _)) => {
+                Ok(None)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn put(&self, key: &AerospikeKey, bins: &Bins) -> Result<(), AerospikeSinkError> {
+        let policy = WritePolicy {
+            record_exists_action: RecordExistsAction::Replace,
+            send_key: true,
+            ..Default::default()
+        };
+        // Nil bins don't exist in Aerospike, and a replace removes bins that are not written anyway.
+        let bins = bins
+            .iter()
+            .filter(|(_, value)| !matches!(value, Value::Nil))
+            .map(|(name, value)| Bin::new(name, value.clone()))
+            .collect::<Vec<_>>();
+        Ok(Client::put(self, &policy, &to_key(key)?, &bins)?)
+    }
+
+    fn delete(&self, key: &AerospikeKey) -> Result<(), AerospikeSinkError> {
+        Client::delete(self, &WritePolicy::default(), &to_key(key)?)?;
+        Ok(())
+    }
+}
+
+fn to_key(key: &AerospikeKey) -> Result<Key, AerospikeSinkError> {
+    Ok(Key::new(
+        key.namespace.clone(),
+        key.set.clone(),
+        key.key.clone(),
+    )?)
+}
@@ -0,0 +1,41 @@
+use dozer_types::{
+    thiserror::{self, Error},
+    types::FieldType,
+};
+
+#[derive(Error, Debug)]
+pub enum AerospikeSinkError {
+    /// The client's errors are not `Sync`, so only their message is kept.
+    #[error("Aerospike error: {0}")]
+    Aerospike(String),
+
+    #[error("Table {0} has no primary key. Set `primary_key` in the sink table config")]
+    NoPrimaryKey(String),
+
+    #[error("Column {0} not found in table {1}")]
+    ColumnNotFound(String, String),
+
+    #[error("Field of type {0} can't be used in an Aerospike key")]
+    UnsupportedKeyType(FieldType),
+
+    #[error("Null can't be used in an Aerospike key")]
+    NullKey,
+
+    #[error("Bin {0} can't be used in an Aerospike key")]
+    UnsupportedKeyBin(String),
+
+    #[error("Denormalized record has no bin {0} for its primary key")]
+    MissingPrimaryKeyBin(String),
+
+    #[error("Bin {0} of aggregated record is not a list")]
+    NotAList(String),
+
+    #[error("Unexpected value in metadata bin {0}")]
+    InvalidMetadata(&'static str),
+}
+
+impl From<aerospike::Error> for AerospikeSinkError {
+    fn from(error: aerospike::Error) -> Self {
+        Self::Aerospike(error.to_string())
+    }
+}
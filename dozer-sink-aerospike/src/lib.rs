@@ -0,0 +1,8 @@
+pub mod client;
+pub mod errors;
+mod metadata;
+mod sink;
+pub use sink::AerospikeSinkFactory;
+#[cfg(test)]
+mod tests;
+pub mod types;
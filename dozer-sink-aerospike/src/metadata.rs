@@ -0,0 +1,82 @@
+use aerospike::Value;
+use dozer_types::node::OpIdentifier;
+
+use crate::{
+    client::{AerospikeClient, AerospikeKey, Bins},
+    errors::AerospikeSinkError,
+};
+
+// Replication Metadata Constants
+pub const DEFAULT_METADATA_SET: &str = "__dozer_replication_metadata";
+const SOURCE_STATE_BIN: &str = "source_state";
+const TXID_BIN: &str = "txid";
+const SEQ_IN_TX_BIN: &str = "seq_in_tx";
+
+/// The replication progress of a sink, stored as one record of the metadata set, keyed by the sink name.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ReplicationMetadata {
+    pub source_state: Option<Vec<u8>>,
+    pub op_id: Option<OpIdentifier>,
+}
+
+impl ReplicationMetadata {
+    pub fn key(namespace: &str, set: Option<&str>, sink_name: &str) -> AerospikeKey {
+        AerospikeKey::new(
+            namespace,
+            set.unwrap_or(DEFAULT_METADATA_SET),
+            Value::String(sink_name.to_string()),
+        )
+    }
+
+    pub fn read(
+        client: &dyn AerospikeClient,
+        key: &AerospikeKey,
+    ) -> Result<Self, AerospikeSinkError> {
+        let Some(bins) = client.get(key)? else {
+            return Ok(Self::default());
+        };
+
+        let source_state = match bins.get(SOURCE_STATE_BIN) {
+            None | Some(Value::Nil) => None,
+            Some(Value::Blob(state)) => Some(state.clone()),
+            Some(_) => return Err(AerospikeSinkError::InvalidMetadata(SOURCE_STATE_BIN)),
+        };
+        let op_id = match (read_u64(&bins, TXID_BIN)?, read_u64(&bins, SEQ_IN_TX_BIN)?) {
+            (Some(txid), Some(seq_in_tx)) => Some(OpIdentifier::new(txid, seq_in_tx)),
+            _ => None,
+        };
+        Ok(Self {
+            source_state,
+            op_id,
+        })
+    }
+
+    pub fn write(
+        &self,
+        client: &dyn AerospikeClient,
+        key: &AerospikeKey,
+    ) -> Result<(), AerospikeSinkError> {
+        let mut bins = Bins::new();
+        if let Some(state) = &self.source_state {
+            bins.insert(SOURCE_STATE_BIN.to_string(), Value::Blob(state.clone()));
+        }
+        if let Some(op_id) = self.op_id {
+            // Aerospike integers are signed. The bits are kept as is.
+            bins.insert(TXID_BIN.to_string(), Value::Int(op_id.txid as i64));
+            bins.insert(
+                SEQ_IN_TX_BIN.to_string(),
+                Value::Int(op_id.seq_in_tx as i64),
+            );
+        }
+        client.put(key, &bins)
+    }
+}
+
+fn read_u64(bins: &Bins, name: &'static str) -> Result<Option<u64>, AerospikeSinkError> {
+    match bins.get(name) {
+        None | Some(Value::Nil) => Ok(None),
+        Some(Value::Int(value)) => Ok(Some(*value as u64)),
+        Some(Value::UInt(value)) => Ok(Some(*value)),
+        Some(_) => Err(AerospikeSinkError::InvalidMetadata(name)),
+    }
+}
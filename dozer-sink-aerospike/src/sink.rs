@@ -0,0 +1,521 @@
+use std::collections::HashMap;
+
+use aerospike::Value;
+use dozer_core::epoch::Epoch;
+use dozer_core::event::EventHub;
+use dozer_core::node::{PortHandle, Sink, SinkFactory};
+use dozer_types::errors::internal::BoxedError;
+use dozer_types::log::debug;
+use dozer_types::models::connection::AerospikeConnection;
+use dozer_types::models::sink::{AerospikeSinkConfig, AerospikeSinkTable, DenormKey};
+use dozer_types::node::OpIdentifier;
+use dozer_types::tonic::async_trait;
+use dozer_types::types::{Field, Operation, Record, Schema, TableOperation};
+
+use crate::client::{connect, AerospikeClient, AerospikeKey, Bins};
+use crate::errors::AerospikeSinkError;
+use crate::metadata::ReplicationMetadata;
+use crate::types::{bins_key_value, key_value, record_to_bins};
+
+#[derive(Debug)]
+pub struct AerospikeSinkFactory {
+    name: String,
+    connection: AerospikeConnection,
+    config: AerospikeSinkConfig,
+}
+
+impl AerospikeSinkFactory {
+    /// `name` identifies the sink's replication metadata, so it must not change between runs.
+    pub fn new(name: String, connection: AerospikeConnection, config: AerospikeSinkConfig) -> Self {
+        Self {
+            name,
+            connection,
+            config,
+        }
+    }
+
+    fn create_tables(
+        &self,
+        mut input_schemas: HashMap<PortHandle, Schema>,
+    ) -> Result<Vec<SinkTable>, AerospikeSinkError> {
+        self.config
+            .tables
+            .iter()
+            .enumerate()
+            .map(|(port, table)| {
+                let schema = input_schemas
+                    .remove(&(port as PortHandle))
+                    .expect("we have a port for every table");
+                SinkTable::new(table, schema)
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl SinkFactory for AerospikeSinkFactory {
+    fn type_name(&self) -> String {
+        "aerospike".to_string()
+    }
+
+    fn get_input_ports(&self) -> Vec<PortHandle> {
+        (0..self.config.tables.len() as PortHandle).collect()
+    }
+
+    fn get_input_port_name(&self, port: &PortHandle) -> String {
+        self.config.tables[*port as usize].source_table_name.clone()
+    }
+
+    fn prepare(&self, input_schemas: HashMap<PortHandle, Schema>) -> Result<(), BoxedError> {
+        self.create_tables(input_schemas)?;
+        Ok(())
+    }
+
+    async fn build(
+        &self,
+        input_schemas: HashMap<PortHandle, Schema>,
+        _event_hub: EventHub,
+    ) -> Result<Box<dyn Sink>, BoxedError> {
+        let tables = self.create_tables(input_schemas)?;
+        let client = connect(&self.connection)?;
+        let sink = AerospikeSink::new(Box::new(client), tables, &self.config, &self.name)?;
+        Ok(Box::new(sink))
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct AerospikeSink {
+    writer: Writer,
+    tables: Vec<SinkTable>,
+    metadata_key: AerospikeKey,
+    metadata: ReplicationMetadata,
+    n_threads: usize,
+    preferred_batch_size: Option<u64>,
+    max_batch_duration_ms: Option<u64>,
+}
+
+impl AerospikeSink {
+    pub(crate) fn new(
+        client: Box<dyn AerospikeClient>,
+        tables: Vec<SinkTable>,
+        config: &AerospikeSinkConfig,
+        name: &str,
+    ) -> Result<Self, AerospikeSinkError> {
+        let metadata_key = ReplicationMetadata::key(
+            &config.metadata_namespace,
+            config.metadata_set.as_deref(),
+            name,
+        );
+        let metadata = ReplicationMetadata::read(&*client, &metadata_key)?;
+        Ok(Self {
+            writer: Writer {
+                client,
+                pending: HashMap::new(),
+            },
+            tables,
+            metadata_key,
+            metadata,
+            n_threads: config.n_threads.map_or(1, |n| n.get()),
+            preferred_batch_size: config.preferred_batch_size,
+            max_batch_duration_ms: config.max_batch_duration_ms,
+        })
+    }
+}
+
+impl Sink for AerospikeSink {
+    fn commit(&mut self, _epoch_details: &Epoch) -> Result<(), BoxedError> {
+        Ok(())
+    }
+
+    fn process(&mut self, op: TableOperation) -> Result<(), BoxedError> {
+        if let Some(id) = op.id {
+            self.metadata.op_id = Some(id);
+        }
+        let table = &self.tables[op.port as usize];
+        match op.op {
+            Operation::Insert { new } => table.insert(&mut self.writer, &new)?,
+            Operation::Delete { old } => table.delete(&mut self.writer, &old)?,
+            Operation::Update { old, new } => {
+                table.delete(&mut self.writer, &old)?;
+                table.insert(&mut self.writer, &new)?;
+            }
+            Operation::BatchInsert { new } => {
+                for record in &new {
+                    table.insert(&mut self.writer, record)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn on_source_snapshotting_started(
+        &mut self,
+        _connection_name: String,
+    ) -> Result<(), BoxedError> {
+        Ok(())
+    }
+
+    fn on_source_snapshotting_done(
+        &mut self,
+        _connection_name: String,
+        id: Option<OpIdentifier>,
+    ) -> Result<(), BoxedError> {
+        if id.is_some() {
+            self.metadata.op_id = id;
+        }
+        Ok(())
+    }
+
+    fn set_source_state(&mut self, source_state: &[u8]) -> Result<(), BoxedError> {
+        self.metadata.source_state = Some(source_state.to_vec());
+        self.metadata
+            .write(&*self.writer.client, &self.metadata_key)?;
+        Ok(())
+    }
+
+    fn get_source_state(&mut self) -> Result<Option<Vec<u8>>, BoxedError> {
+        Ok(self.metadata.source_state.clone())
+    }
+
+    fn get_latest_op_id(&mut self) -> Result<Option<OpIdentifier>, BoxedError> {
+        Ok(self.metadata.op_id)
+    }
+
+    fn preferred_batch_size(&self) -> Option<u64> {
+        self.preferred_batch_size
+    }
+
+    fn max_batch_duration_ms(&self) -> Option<u64> {
+        self.max_batch_duration_ms
+    }
+
+    fn flush_batch(&mut self) -> Result<(), BoxedError> {
+        debug!(
+            "[Sink] Flushing {} records to Aerospike",
+            self.writer.pending.len()
+        );
+        self.writer.flush(self.n_threads)?;
+        // Progress is recorded only after the data it covers is written.
+        self.metadata
+            .write(&*self.writer.client, &self.metadata_key)?;
+        Ok(())
+    }
+
+    fn supports_batching(&self) -> bool {
+        true
+    }
+}
+
+/// Buffers writes until the batch is flushed. Reads see the buffered writes.
+#[derive(Debug)]
+struct Writer {
+    client: Box<dyn AerospikeClient>,
+    /// Latest write of every record. `None` deletes the record.
+    pending: HashMap<AerospikeKey, Option<Bins>>,
+}
+
+impl Writer {
+    fn get(&self, key: &AerospikeKey) -> Result<Option<Bins>, AerospikeSinkError> {
+        match self.pending.get(key) {
+            Some(bins) => Ok(bins.clone()),
+            None => self.client.get(key),
+        }
+    }
+
+    fn put(&mut self, key: AerospikeKey, bins: Bins) {
+        self.pending.insert(key, Some(bins));
+    }
+
+    fn delete(&mut self, key: AerospikeKey) {
+        self.pending.insert(key, None);
+    }
+
+    /// Writes the pending records with `n_threads` threads.
+    fn flush(&mut self, n_threads: usize) -> Result<(), AerospikeSinkError> {
+        let pending = self.pending.iter().collect::<Vec<_>>();
+        let chunk_size = pending.len().div_ceil(n_threads).max(1);
+        let client = &*self.client;
+        std::thread::scope(|scope| {
+            let handles = pending
+                .chunks(chunk_size)
+                .map(|chunk| {
+                    scope.spawn(move || {
+                        chunk.iter().try_for_each(|(key, bins)| match bins {
+                            Some(bins) => client.put(key, bins),
+                            None => client.delete(key),
+                        })
+                    })
+                })
+                .collect::<Vec<_>>();
+            handles
+                .into_iter()
+                .try_for_each(|handle| handle.join().expect("writer thread panicked"))
+        })?;
+        self.pending.clear();
+        Ok(())
+    }
+}
+
+/// A source table, and where its records are written.
+#[derive(Debug)]
+pub(crate) struct SinkTable {
+    namespace: String,
+    set: String,
+    schema: Schema,
+    primary_key: Vec<usize>,
+    denormalizations: Vec<Denormalization>,
+    denormalized_output: Option<DenormalizedOutput>,
+}
+
+/// Bins looked up from another set, by the value of some columns of the record.
+#[derive(Debug)]
+struct Denormalization {
+    namespace: String,
+    set: String,
+    key: Vec<usize>,
+    /// (source bin, destination bin)
+    columns: Vec<(String, String)>,
+}
+
+#[derive(Debug)]
+struct DenormalizedOutput {
+    namespace: String,
+    set: String,
+    /// Bins of the denormalized record that form its key.
+    primary_key: Vec<String>,
+    /// If set, records with the same key are written to one Aerospike record, whose bins are lists of values.
+    aggregate_by_pk: bool,
+    /// Bins of the denormalized record that identify the source record, used to remove it from an aggregated record.
+    source_primary_key: Vec<String>,
+}
+
+impl SinkTable {
+    pub(crate) fn new(
+        config: &AerospikeSinkTable,
+        schema: Schema,
+    ) -> Result<Self, AerospikeSinkError> {
+        let table_name = &config.source_table_name;
+        let primary_key = if config.primary_key.is_empty() {
+            schema.primary_index.clone()
+        } else {
+            field_indexes(&schema, &config.primary_key, table_name)?
+        };
+        if primary_key.is_empty() {
+            return Err(AerospikeSinkError::NoPrimaryKey(table_name.clone()));
+        }
+
+        let denormalizations = config
+            .denormalize
+            .iter()
+            .map(|denormalization| {
+                let key = match &denormalization.key {
+                    DenormKey::Simple(column) => std::slice::from_ref(column),
+                    DenormKey::Composite(columns) => columns.as_slice(),
+                };
+                Ok(Denormalization {
+                    namespace: denormalization.from_namespace.clone(),
+                    set: denormalization.from_set.clone(),
+                    key: field_indexes(&schema, key, table_name)?,
+                    columns: denormalization
+                        .columns
+                        .iter()
+                        .map(|column| {
+                            let (source, destination) = column.to_src_dst();
+                            (source.to_string(), destination.to_string())
+                        })
+                        .collect(),
+                })
+            })
+            .collect::<Result<Vec<_>, AerospikeSinkError>>()?;
+
+        let denormalized_output = match &config.write_denormalized_to {
+            Some(output) => {
+                if output.primary_key.is_empty() {
+                    return Err(AerospikeSinkError::NoPrimaryKey(output.set.clone()));
+                }
+                Some(DenormalizedOutput {
+                    namespace: output.namespace.clone(),
+                    set: output.set.clone(),
+                    primary_key: output.primary_key.clone(),
+                    aggregate_by_pk: config.aggregate_by_pk,
+                    source_primary_key: primary_key
+                        .iter()
+                        .map(|index| schema.fields[*index].name.clone())
+                        .collect(),
+                })
+            }
+            None => None,
+        };
+
+        Ok(Self {
+            namespace: config.namespace.clone(),
+            set: config.set_name.clone(),
+            schema,
+            primary_key,
+            denormalizations,
+            denormalized_output,
+        })
+    }
+
+    fn key(&self, record: &Record) -> Result<AerospikeKey, AerospikeSinkError> {
+        let key = key_value(self.primary_key.iter().map(|index| &record.values[*index]))?;
+        Ok(AerospikeKey::new(&self.namespace, &self.set, key))
+    }
+
+    fn insert(&self, writer: &mut Writer, record: &Record) -> Result<(), AerospikeSinkError> {
+        writer.put(
+            self.key(record)?,
+            record_to_bins(&self.schema.fields, &record.values),
+        );
+        if let Some(output) = &self.denormalized_output {
+            let bins = self.denormalize(writer, record)?;
+            output.insert(writer, bins)?;
+        }
+        Ok(())
+    }
+
+    fn delete(&self, writer: &mut Writer, record: &Record) -> Result<(), AerospikeSinkError> {
+        writer.delete(self.key(record)?);
+        if let Some(output) = &self.denormalized_output {
+            let bins = self.denormalize(writer, record)?;
+            output.delete(writer, &bins)?;
+        }
+        Ok(())
+    }
+
+    /// Adds the looked up bins to the record's bins. Bins of records that are not found, or whose key has a null, are `Nil`.
+    fn denormalize(&self, writer: &Writer, record: &Record) -> Result<Bins, AerospikeSinkError> {
+        let mut bins = record_to_bins(&self.schema.fields, &record.values);
+        for denormalization in &self.denormalizations {
+            let key_fields = denormalization
+                .key
+                .iter()
+                .map(|index| &record.values[*index]);
+            let looked_up = if key_fields.clone().any(|field| field == &Field::Null) {
+                None
+            } else {
+                writer.get(&AerospikeKey::new(
+                    &denormalization.namespace,
+                    &denormalization.set,
+                    key_value(key_fields)?,
+                ))?
+            };
+            for (source, destination) in &denormalization.columns {
+                let value = looked_up
+                    .as_ref()
+                    .and_then(|looked_up| looked_up.get(source))
+                    .cloned()
+                    .unwrap_or(Value::Nil);
+                bins.insert(destination.clone(), value);
+            }
+        }
+        Ok(bins)
+    }
+}
+
+impl DenormalizedOutput {
+    fn key(&self, bins: &Bins) -> Result<AerospikeKey, AerospikeSinkError> {
+        let key = bins_key_value(bins, &self.primary_key)?;
+        Ok(AerospikeKey::new(&self.namespace, &self.set, key))
+    }
+
+    fn insert(&self, writer: &mut Writer, bins: Bins) -> Result<(), AerospikeSinkError> {
+        let key = self.key(&bins)?;
+        if !self.aggregate_by_pk {
+            writer.put(key, bins);
+            return Ok(());
+        }
+
+        let mut aggregated = writer.get(&key)?.unwrap_or_default();
+        let num_records = aggregated_len(&aggregated)?;
+        for (name, value) in bins {
+            aggregated_list(&mut aggregated, name, num_records)?.push(value);
+        }
+        // Bins the new record doesn't have.
+        for value in aggregated.values_mut() {
+            if let Value::List(list) = value {
+                if list.len() == num_records {
+                    list.push(Value::Nil);
+                }
+            }
+        }
+        writer.put(key, aggregated);
+        Ok(())
+    }
+
+    fn delete(&self, writer: &mut Writer, bins: &Bins) -> Result<(), AerospikeSinkError> {
+        let key = self.key(bins)?;
+        if !self.aggregate_by_pk {
+            writer.delete(key);
+            return Ok(());
+        }
+
+        let Some(mut aggregated) = writer.get(&key)? else {
+            return Ok(());
+        };
+        let num_records = aggregated_len(&aggregated)?;
+        let position = (0..num_records).find(|index| {
+            self.source_primary_key.iter().all(|name| {
+                let aggregated_value = match aggregated.get(name) {
+                    Some(Value::List(list)) => list.get(*index),
+                    _ => None,
+                };
+                aggregated_value == bins.get(name)
+            })
+        });
+        let Some(position) = position else {
+            return Ok(());
+        };
+
+        if num_records == 1 {
+            writer.delete(key);
+        } else {
+            for value in aggregated.values_mut() {
+                if let Value::List(list) = value {
+                    list.remove(position);
+                }
+            }
+            writer.put(key, aggregated);
+        }
+        Ok(())
+    }
+}
+
+/// Number of records in an aggregated record.
+fn aggregated_len(aggregated: &Bins) -> Result<usize, AerospikeSinkError> {
+    match aggregated.iter().next() {
+        None => Ok(0),
+        Some((_, Value::List(list))) => Ok(list.len()),
+        Some((name, _)) => Err(AerospikeSinkError::NotAList(name.clone())),
+    }
+}
+
+fn aggregated_list(
+    aggregated: &mut Bins,
+    name: String,
+    num_records: usize,
+) -> Result<&mut Vec<Value>, AerospikeSinkError> {
+    match aggregated
+        .entry(name.clone())
+        .or_insert_with(|| Value::List(vec![Value::Nil; num_records]))
+    {
+        Value::List(list) => Ok(list),
+        _ => Err(AerospikeSinkError::NotAList(name)),
+    }
+}
+
+fn field_indexes(
+    schema: &Schema,
+    names: &[String],
+    table_name: &str,
+) -> Result<Vec<usize>, AerospikeSinkError> {
+    names
+        .iter()
+        .map(|name| {
+            schema
+                .get_field_index(name)
+                .map(|(index, _)| index)
+                .map_err(|_| AerospikeSinkError::ColumnNotFound(name.clone(), table_name.into()))
+        })
+        .collect()
+}
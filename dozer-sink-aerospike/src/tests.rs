@@ -0,0 +1,371 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use aerospike::Value;
+use dozer_core::node::Sink;
+use dozer_types::{
+    models::sink::{
+        AerospikeDenormalizations, AerospikeSet, AerospikeSinkConfig, AerospikeSinkTable,
+        DenormColumn, DenormKey,
+    },
+    node::OpIdentifier,
+    types::{
+        Field, FieldDefinition, FieldType, Operation, Record, Schema, SourceDefinition,
+        TableOperation,
+    },
+};
+
+use crate::{
+    client::{AerospikeClient, AerospikeKey, Bins},
+    errors::AerospikeSinkError,
+    sink::{AerospikeSink, SinkTable},
+    types::{bins_key_value, key_value},
+};
+
+#[derive(Debug, Default)]
+struct FakeClient {
+    records: Mutex<HashMap<AerospikeKey, Bins>>,
+}
+
+impl AerospikeClient for FakeClient {
+    fn get(&self, key: &AerospikeKey) -> Result<Option<Bins>, AerospikeSinkError> {
+        Ok(self.records.lock().unwrap().get(key).cloned())
+    }
+
+    fn put(&self, key: &AerospikeKey, bins: &Bins) -> Result<(), AerospikeSinkError> {
+        let bins = bins
+            .iter()
+            .filter(|(_, value)| !matches!(value, Value::Nil))
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect();
+        self.records.lock().unwrap().insert(key.clone(), bins);
+        Ok(())
+    }
+
+    fn delete(&self, key: &AerospikeKey) -> Result<(), AerospikeSinkError> {
+        self.records.lock().unwrap().remove(key);
+        Ok(())
+    }
+}
+
+impl AerospikeClient for Arc<FakeClient> {
+    fn get(&self, key: &AerospikeKey) -> Result<Option<Bins>, AerospikeSinkError> {
+        (**self).get(key)
+    }
+
+    fn put(&self, key: &AerospikeKey, bins: &Bins) -> Result<(), AerospikeSinkError> {
+        (**self).put(key, bins)
+    }
+
+    fn delete(&self, key: &AerospikeKey) -> Result<(), AerospikeSinkError> {
+        (**self).delete(key)
+    }
+}
+
+fn schema(fields: &[(&str, FieldType)], primary_index: Vec<usize>) -> Schema {
+    Schema {
+        fields: fields
+            .iter()
+            .map(|(name, typ)| FieldDefinition {
+                name: name.to_string(),
+                typ: typ.clone(),
+                nullable: true,
+                source: SourceDefinition::Dynamic,
+                description: None,
+            })
+            .collect(),
+        primary_index,
+    }
+}
+
+fn sink_config(tables: Vec<AerospikeSinkTable>) -> AerospikeSinkConfig {
+    AerospikeSinkConfig {
+        connection: "aerospike".to_string(),
+        n_threads: None,
+        tables,
+        max_batch_duration_ms: None,
+        preferred_batch_size: None,
+        metadata_namespace: "test".to_string(),
+        metadata_set: None,
+    }
+}
+
+fn table_config(source_table_name: &str, set_name: &str) -> AerospikeSinkTable {
+    AerospikeSinkTable {
+        source_table_name: source_table_name.to_string(),
+        namespace: "test".to_string(),
+        set_name: set_name.to_string(),
+        denormalize: vec![],
+        write_denormalized_to: None,
+        primary_key: vec![],
+        aggregate_by_pk: false,
+    }
+}
+
+fn create_sink(
+    client: &Arc<FakeClient>,
+    tables: Vec<(AerospikeSinkTable, Schema)>,
+) -> AerospikeSink {
+    let sink_tables = tables
+        .iter()
+        .map(|(config, schema)| SinkTable::new(config, schema.clone()).unwrap())
+        .collect();
+    let config = sink_config(tables.into_iter().map(|(config, _)| config).collect());
+    AerospikeSink::new(Box::new(client.clone()), sink_tables, &config, "sink").unwrap()
+}
+
+fn key(set: &str, key: i64) -> AerospikeKey {
+    AerospikeKey::new("test", set, Value::Int(key))
+}
+
+fn get(client: &FakeClient, key: &AerospikeKey) -> Option<Bins> {
+    client.get(key).unwrap()
+}
+
+fn op(port: u16, op: Operation) -> TableOperation {
+    TableOperation { id: None, op, port }
+}
+
+#[test]
+fn test_insert_update_delete() {
+    let client = Arc::new(FakeClient::default());
+    let users = schema(
+        &[("id", FieldType::Int), ("name", FieldType::String)],
+        vec![0],
+    );
+    let mut sink = create_sink(&client, vec![(table_config("users", "users"), users)]);
+
+    let alice = Record::new(vec![Field::Int(1), Field::String("alice".into())]);
+    sink.process(op(0, Operation::Insert { new: alice.clone() }))
+        .unwrap();
+    // Nothing is written until the batch is flushed.
+    assert_eq!(get(&client, &key("users", 1)), None);
+    sink.flush_batch().unwrap();
+    assert_eq!(
+        get(&client, &key("users", 1)).unwrap()["name"],
+        Value::from("alice")
+    );
+
+    let bob = Record::new(vec![Field::Int(1), Field::String("bob".into())]);
+    sink.process(op(
+        0,
+        Operation::Update {
+            old: alice,
+            new: bob.clone(),
+        },
+    ))
+    .unwrap();
+    sink.flush_batch().unwrap();
+    assert_eq!(
+        get(&client, &key("users", 1)).unwrap()["name"],
+        Value::from("bob")
+    );
+
+    sink.process(op(0, Operation::Delete { old: bob })).unwrap();
+    sink.flush_batch().unwrap();
+    assert_eq!(get(&client, &key("users", 1)), None);
+}
+
+#[test]
+fn test_denormalize_aggregate_by_pk() {
+    let client = Arc::new(FakeClient::default());
+    let customers = schema(
+        &[("id", FieldType::Int), ("name", FieldType::String)],
+        vec![0],
+    );
+    let orders = schema(
+        &[
+            ("order_id", FieldType::Int),
+            ("customer_id", FieldType::Int),
+            ("amount", FieldType::Int),
+        ],
+        vec![0],
+    );
+    let orders_config = AerospikeSinkTable {
+        denormalize: vec![AerospikeDenormalizations {
+            from_namespace: "test".to_string(),
+            from_set: "customers".to_string(),
+            key: DenormKey::Simple("customer_id".to_string()),
+            columns: vec![DenormColumn::Renamed {
+                source: "name".to_string(),
+                destination: "customer_name".to_string(),
+            }],
+        }],
+        write_denormalized_to: Some(AerospikeSet {
+            namespace: "test".to_string(),
+            set: "customer_orders".to_string(),
+            primary_key: vec!["customer_id".to_string()],
+        }),
+        aggregate_by_pk: true,
+        ..table_config("orders", "orders")
+    };
+    let mut sink = create_sink(
+        &client,
+        vec![
+            (table_config("customers", "customers"), customers),
+            (orders_config, orders),
+        ],
+    );
+
+    let order = |order_id, amount| {
+        Record::new(vec![
+            Field::Int(order_id),
+            Field::Int(7),
+            Field::Int(amount),
+        ])
+    };
+    // The customer is looked up from the same batch.
+    sink.process(op(
+        0,
+        Operation::Insert {
+            new: Record::new(vec![Field::Int(7), Field::String("alice".into())]),
+        },
+    ))
+    .unwrap();
+    sink.process(op(
+        1,
+        Operation::BatchInsert {
+            new: vec![order(1, 10), order(2, 20)],
+        },
+    ))
+    .unwrap();
+    sink.flush_batch().unwrap();
+
+    let aggregated = get(&client, &key("customer_orders", 7)).unwrap();
+    assert_eq!(
+        aggregated["amount"],
+        Value::List(vec![Value::Int(10), Value::Int(20)])
+    );
+    assert_eq!(
+        aggregated["customer_name"],
+        Value::List(vec![Value::from("alice"), Value::from("alice")])
+    );
+
+    sink.process(op(1, Operation::Delete { old: order(1, 10) }))
+        .unwrap();
+    sink.flush_batch().unwrap();
+    let aggregated = get(&client, &key("customer_orders", 7)).unwrap();
+    assert_eq!(aggregated["order_id"], Value::List(vec![Value::Int(2)]));
+    assert_eq!(aggregated["amount"], Value::List(vec![Value::Int(20)]));
+
+    sink.process(op(1, Operation::Delete { old: order(2, 20) }))
+        .unwrap();
+    sink.flush_batch().unwrap();
+    assert_eq!(get(&client, &key("customer_orders", 7)), None);
+}
+
+#[test]
+fn test_denormalize_null_key() {
+    let client = Arc::new(FakeClient::default());
+    let customers = schema(
+        &[("id", FieldType::Int), ("name", FieldType::String)],
+        vec![0],
+    );
+    let orders = schema(
+        &[
+            ("order_id", FieldType::Int),
+            ("customer_id", FieldType::Int),
+        ],
+        vec![0],
+    );
+    let orders_config = AerospikeSinkTable {
+        denormalize: vec![AerospikeDenormalizations {
+            from_namespace: "test".to_string(),
+            from_set: "customers".to_string(),
+            key: DenormKey::Simple("customer_id".to_string()),
+            columns: vec![DenormColumn::Renamed {
+                source: "name".to_string(),
+                destination: "customer_name".to_string(),
+            }],
+        }],
+        write_denormalized_to: Some(AerospikeSet {
+            namespace: "test".to_string(),
+            set: "denormalized_orders".to_string(),
+            primary_key: vec!["order_id".to_string()],
+        }),
+        ..table_config("orders", "orders")
+    };
+    let mut sink = create_sink(
+        &client,
+        vec![
+            (table_config("customers", "customers"), customers),
+            (orders_config, orders),
+        ],
+    );
+
+    // An order without a customer is written without the customer's bins.
+    sink.process(op(
+        1,
+        Operation::Insert {
+            new: Record::new(vec![Field::Int(1), Field::Null]),
+        },
+    ))
+    .unwrap();
+    sink.flush_batch().unwrap();
+
+    let denormalized = get(&client, &key("denormalized_orders", 1)).unwrap();
+    assert_eq!(denormalized["order_id"], Value::Int(1));
+    assert!(!denormalized.contains_key("customer_name"));
+}
+
+#[test]
+fn test_composite_keys_encode_the_same_from_fields_and_bins() {
+    let fields = [Field::Int(1), Field::String("a".into())];
+    let bins = Bins::from([
+        ("id".to_string(), Value::Int(1)),
+        ("name".to_string(), Value::from("a")),
+    ]);
+    let names = ["id".to_string(), "name".to_string()];
+    assert_eq!(
+        key_value(fields.iter()).unwrap(),
+        bins_key_value(&bins, &names).unwrap()
+    );
+
+    // Parts are length prefixed, so moving bytes between parts changes the key.
+    let other = [Field::String("ab".into()), Field::String("c".into())];
+    let moved = [Field::String("a".into()), Field::String("bc".into())];
+    assert_ne!(
+        key_value(other.iter()).unwrap(),
+        key_value(moved.iter()).unwrap()
+    );
+
+    assert!(matches!(
+        key_value([Field::Null].iter()),
+        Err(AerospikeSinkError::NullKey)
+    ));
+}
+
+#[test]
+fn test_replication_metadata() {
+    let client = Arc::new(FakeClient::default());
+    let users = schema(&[("id", FieldType::Int)], vec![0]);
+    let mut sink = create_sink(
+        &client,
+        vec![(table_config("users", "users"), users.clone())],
+    );
+    assert_eq!(sink.get_source_state().unwrap(), None);
+    assert_eq!(sink.get_latest_op_id().unwrap(), None);
+
+    sink.set_source_state(b"state").unwrap();
+    sink.process(TableOperation {
+        id: Some(OpIdentifier::new(3, 4)),
+        op: Operation::Insert {
+            new: Record::new(vec![Field::Int(1)]),
+        },
+        port: 0,
+    })
+    .unwrap();
+    sink.flush_batch().unwrap();
+
+    let mut restarted = create_sink(&client, vec![(table_config("users", "users"), users)]);
+    assert_eq!(
+        restarted.get_source_state().unwrap(),
+        Some(b"state".to_vec())
+    );
+    assert_eq!(
+        restarted.get_latest_op_id().unwrap(),
+        Some(OpIdentifier::new(3, 4))
+    );
+}
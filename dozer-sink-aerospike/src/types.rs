@@ -0,0 +1,129 @@
+use aerospike::Value;
+use dozer_types::{
+    chrono::SecondsFormat,
+    json_types::json_to_string,
+    types::{Field, FieldDefinition, DATE_FORMAT, TIME_FORMAT},
+};
+
+use crate::{client::Bins, errors::AerospikeSinkError};
+
+/// Converts a field to the closest Aerospike value. Types Aerospike doesn't have are written as strings.
+pub fn field_to_value(field: &Field) -> Value {
+    match field {
+        Field::UInt(v) => Value::UInt(*v),
+        Field::U128(v) => Value::String(v.to_string()),
+        Field::Int(v) => Value::Int(*v),
+        Field::Int8(v) => Value::Int((*v).into()),
+        Field::I128(v) => Value::String(v.to_string()),
+        Field::Float(v) => Value::from(v.0),
+        Field::Boolean(v) => Value::Bool(*v),
+        Field::String(v) | Field::Text(v) => Value::String(v.clone()),
+        Field::Binary(v) => Value::Blob(v.clone()),
+        Field::Decimal(v) => Value::String(v.to_string()),
+        Field::Timestamp(v) => Value::String(v.to_rfc3339_opts(SecondsFormat::AutoSi, true)),
+        Field::Date(v) => Value::String(v.format(DATE_FORMAT).to_string()),
+        Field::Time(v) => Value::String(v.format(TIME_FORMAT).to_string()),
+        Field::Uuid(v) => Value::String(v.to_string()),
+        Field::Json(v) => Value::String(json_to_string(v)),
+        Field::Point(v) => Value::GeoJSON(format!(
+            r#"{{"type":"Point","coordinates":[{},{}]}}"#,
+            v.0.x(),
+            v.0.y()
+        )),
+        Field::Duration(v) => Value::Int(v.0.as_nanos() as i64),
+        Field::Null => Value::Nil,
+        Field::List(v) | Field::Struct(v) => Value::List(v.iter().map(field_to_value).collect()),
+    }
+}
+
+/// Converts a record to bins named after the schema fields.
+pub fn record_to_bins(fields: &[FieldDefinition], values: &[Field]) -> Bins {
+    fields
+        .iter()
+        .zip(values)
+        .map(|(definition, value)| (definition.name.clone(), field_to_value(value)))
+        .collect()
+}
+
+/// Aerospike keys can only be integers, strings or blobs.
+///
+/// A single field key is written as is, so records can be looked up by the plain value.
+/// Composite keys are encoded into a blob.
+pub fn key_value<'a>(
+    fields: impl ExactSizeIterator<Item = &'a Field>,
+) -> Result<Value, AerospikeSinkError> {
+    let composite = fields.len() > 1;
+    let values = fields
+        .map(|field| match field {
+            Field::Null => Err(AerospikeSinkError::NullKey),
+            Field::UInt(_)
+            | Field::Int(_)
+            | Field::Int8(_)
+            | Field::String(_)
+            | Field::Text(_)
+            | Field::Binary(_) => Ok(field_to_value(field)),
+            _ if composite => Ok(field_to_value(field)),
+            _ => Err(AerospikeSinkError::UnsupportedKeyType(
+                field.ty().expect("null is handled above"),
+            )),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(values_key(values))
+}
+
+/// Same as [`key_value`], but reads the key fields from bins, for denormalized records.
+pub fn bins_key_value(bins: &Bins, key_names: &[String]) -> Result<Value, AerospikeSinkError> {
+    let composite = key_names.len() > 1;
+    let values = key_names
+        .iter()
+        .map(|name| match bins.get(name) {
+            None => Err(AerospikeSinkError::MissingPrimaryKeyBin(name.clone())),
+            Some(Value::Nil) => Err(AerospikeSinkError::NullKey),
+            Some(value @ (Value::Int(_) | Value::UInt(_) | Value::String(_) | Value::Blob(_))) => {
+                Ok(value.clone())
+            }
+            Some(value) if composite => Ok(value.clone()),
+            Some(_) => Err(AerospikeSinkError::UnsupportedKeyBin(name.clone())),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(values_key(values))
+}
+
+/// Builds the key from the values of the key fields, so [`key_value`] and [`bins_key_value`] encode keys the same way.
+fn values_key(mut values: Vec<Value>) -> Value {
+    if values.len() == 1 {
+        return values.pop().expect("we checked the length");
+    }
+    let mut blob = vec![];
+    for value in &values {
+        // Every part is tagged and variable length parts are length prefixed, so different keys can't encode the same.
+        match value {
+            Value::Int(v) => {
+                blob.push(0);
+                blob.extend(v.to_be_bytes());
+            }
+            Value::UInt(v) => {
+                blob.push(1);
+                blob.extend(v.to_be_bytes());
+            }
+            Value::String(v) => {
+                blob.push(2);
+                extend_length_prefixed(&mut blob, v.as_bytes());
+            }
+            Value::Blob(v) => {
+                blob.push(3);
+                extend_length_prefixed(&mut blob, v);
+            }
+            _ => {
+                blob.push(4);
+                extend_length_prefixed(&mut blob, value.to_string().as_bytes());
+            }
+        }
+    }
+    Value::Blob(blob)
+}
+
+fn extend_length_prefixed(blob: &mut Vec<u8>, bytes: &[u8]) {
+    blob.extend((bytes.len() as u64).to_be_bytes());
+    blob.extend(bytes);
+}
@@ -1,7 +1,8 @@
 #![allow(dead_code)]
-use super::ddl::get_create_table_query;
+use super::ddl::{get_alter_table_query, get_create_table_query, get_delete_query};
 use super::types::ValueWrapper;
 use crate::errors::QueryError;
+use crate::schema::SchemaChange;
 use crate::types::{insert_multi, map_value_wrapper_to_field};
 use clickhouse_rs::types::Query;
 use clickhouse_rs::{ClientHandle, Pool};
@@ -74,6 +75,34 @@ impl ClickhouseClient {
         Ok(())
     }
 
+    pub async fn alter_table(
+        &self,
+        table_name: &str,
+        changes: &[SchemaChange],
+        cluster: Option<String>,
+    ) -> Result<(), QueryError> {
+        let mut client = self.pool.get_handle().await?;
+        let ddl = get_alter_table_query(table_name, changes, cluster);
+        info!("{ddl}");
+        client.execute(ddl).await?;
+        Ok(())
+    }
+
+    pub async fn delete(
+        &self,
+        table_name: &str,
+        key_fields: &[FieldDefinition],
+        keys: &[Vec<Field>],
+        query_id: Option<String>,
+    ) -> Result<(), QueryError> {
+        let mut client = self.pool.get_handle().await?;
+        let query = get_delete_query(table_name, key_fields, keys)?;
+        debug!("{query}");
+        let query = Query::new(query).id(query_id.unwrap_or("".to_string()));
+        client.execute(query).await?;
+        Ok(())
+    }
+
     pub async fn fetch_all(
         &self,
         query: &str,
@@ -127,7 +156,7 @@ impl ClickhouseClient {
         query_id: Option<String>,
     ) -> Result<(), QueryError> {
         let client = self.pool.get_handle().await?;
-        insert_multi(client, table_name, fields, vec![values], query_id).await
+        insert_multi(client, table_name, fields, vec![values], query_id, None).await
     }
 
     pub async fn insert_multi(
@@ -136,8 +165,17 @@ impl ClickhouseClient {
         fields: &[FieldDefinition],
         rows: Vec<Vec<Field>>,
         query_id: Option<String>,
+        deduplication_token: Option<String>,
     ) -> Result<(), QueryError> {
         let client = self.pool.get_handle().await?;
-        insert_multi(client, table_name, fields, rows, query_id).await
+        insert_multi(
+            client,
+            table_name,
+            fields,
+            rows,
+            query_id,
+            deduplication_token,
+        )
+        .await
     }
 }
@@ -1,9 +1,13 @@
 use dozer_types::models::sink::ClickhouseTableOptions;
-use dozer_types::types::FieldDefinition;
+use dozer_types::types::{Field, FieldDefinition};
 
-use crate::schema::map_field_to_type;
+use crate::engine::{TableEngine, DEFAULT_SIGN_COLUMN, DEFAULT_VERSION_COLUMN};
+use crate::errors::QueryError;
+use crate::schema::{map_field_to_type, SchemaChange};
+use crate::types::field_to_literal;
 
 const DEFAULT_TABLE_ENGINE: &str = "MergeTree()";
+const DEDUPLICATION_WINDOW: u64 = 100;
 
 pub fn get_create_table_query(
     table_name: &str,
@@ -14,10 +18,12 @@ pub fn get_create_table_query(
         .as_ref()
         .and_then(|c| c.engine.clone())
         .unwrap_or_else(|| DEFAULT_TABLE_ENGINE.to_string());
-    let engine_name = if engine == "CollapsingMergeTree" {
-        "CollapsingMergeTree(sign)".to_string()
-    } else {
-        engine.to_owned()
+    let engine_name = match engine.as_str() {
+        "CollapsingMergeTree" => format!("CollapsingMergeTree({DEFAULT_SIGN_COLUMN})"),
+        "VersionedCollapsingMergeTree" => {
+            format!("VersionedCollapsingMergeTree({DEFAULT_SIGN_COLUMN}, {DEFAULT_VERSION_COLUMN})")
+        }
+        _ => engine.to_owned(),
     };
     let mut parts = fields
         .iter()
@@ -26,8 +32,11 @@ pub fn get_create_table_query(
             format!("{} {}", field.name, typ)
         })
         .collect::<Vec<_>>();
-    if engine == "CollapsingMergeTree" {
-        parts.push("sign Int8".to_string());
+    // Columns the engine needs, such as `sign` or `version`, unless the schema already has them.
+    for column in TableEngine::parse(&engine_name).columns() {
+        if !fields.iter().any(|field| field.name == column.name) {
+            parts.push(format!("{} {}", column.name, map_field_to_type(&column)));
+        }
     }
 
     parts.push(
@@ -59,6 +68,13 @@ pub fn get_create_table_query(
         .map_or("".to_string(), |order_by| {
             format!("ORDER BY ({})\n", order_by.join(", "))
         });
+    // Lets a batch that's written again after a restart be deduplicated.
+    // Replicated tables deduplicate inserts by default.
+    let settings = if engine_name.contains("MergeTree") && !engine_name.starts_with("Replicated") {
+        format!("SETTINGS non_replicated_deduplication_window = {DEDUPLICATION_WINDOW}\n")
+    } else {
+        "".to_string()
+    };
     let cluster = table_options
         .as_ref()
         .and_then(|options| options.cluster.clone())
@@ -74,6 +90,59 @@ pub fn get_create_table_query(
             {order_by}
             {partition_by}
             {sample_by}
+            {settings}
             ",
     )
 }
+
+pub fn get_alter_table_query(
+    table_name: &str,
+    changes: &[SchemaChange],
+    cluster: Option<String>,
+) -> String {
+    let cluster = cluster.map_or("".to_string(), |cluster| format!("ON CLUSTER {cluster} "));
+    let changes = changes
+        .iter()
+        .map(|change| match change {
+            SchemaChange::AddColumn(field) => format!(
+                "ADD COLUMN IF NOT EXISTS {} {}",
+                field.name,
+                map_field_to_type(field)
+            ),
+            SchemaChange::ModifyColumn(field) => {
+                format!("MODIFY COLUMN {} {}", field.name, map_field_to_type(field))
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!("ALTER TABLE {table_name} {cluster}{changes}")
+}
+
+/// Lightweight `DELETE` of the rows whose key columns match one of `keys`.
+pub fn get_delete_query(
+    table_name: &str,
+    key_fields: &[FieldDefinition],
+    keys: &[Vec<Field>],
+) -> Result<String, QueryError> {
+    let columns = key_fields
+        .iter()
+        .map(|field| field.name.clone())
+        .collect::<Vec<_>>()
+        .join(", ");
+    let values = keys
+        .iter()
+        .map(|key| {
+            let literals = key
+                .iter()
+                .zip(key_fields)
+                .map(|(value, field)| field_to_literal(value, &field.typ))
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(format!("({})", literals.join(", ")))
+        })
+        .collect::<Result<Vec<_>, QueryError>>()?
+        .join(", ");
+
+    Ok(format!(
+        "DELETE FROM {table_name} WHERE ({columns}) IN ({values})"
+    ))
+}
@@ -0,0 +1,104 @@
+use dozer_types::types::{FieldDefinition, FieldType, SourceDefinition};
+
+pub const DEFAULT_SIGN_COLUMN: &str = "sign";
+pub const DEFAULT_VERSION_COLUMN: &str = "version";
+
+/// The table engines the sink knows how to apply updates and deletes to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TableEngine {
+    /// Any other engine. Deletes are lightweight `DELETE`s by primary key.
+    MergeTree,
+    /// Keeps the row with the greatest `version` per sorting key.
+    /// Deletes write a row with `is_deleted` set, or a lightweight `DELETE` if there's no such column.
+    ReplacingMergeTree {
+        version: Option<String>,
+        is_deleted: Option<String>,
+    },
+    /// Deletes write the old row again with `sign` -1, which cancels it out.
+    CollapsingMergeTree { sign: String },
+    /// Like `CollapsingMergeTree`, but the cancel row must also have the `version` of the old row.
+    VersionedCollapsingMergeTree { sign: String, version: String },
+}
+
+impl TableEngine {
+    /// Parses an engine clause, such as `ReplacingMergeTree(ver, is_deleted)`
+    /// or the `engine_full` column of `system.tables`.
+    pub fn parse(engine: &str) -> Self {
+        let engine = engine.trim();
+        let name_end = engine
+            .find(|c: char| c == '(' || c.is_whitespace())
+            .unwrap_or(engine.len());
+        let name = &engine[..name_end];
+        let args = engine[name_end..]
+            .strip_prefix('(')
+            .and_then(|rest| rest.split_once(')'))
+            .map_or(vec![], |(args, _)| {
+                args.split(',')
+                    .map(|arg| arg.trim().trim_matches('`').to_string())
+                    // Replicated engines start with the ZooKeeper path and replica name.
+                    .filter(|arg| !arg.is_empty() && !arg.starts_with('\''))
+                    .collect()
+            });
+        let arg = |index: usize, default: &str| {
+            args.get(index)
+                .cloned()
+                .unwrap_or_else(|| default.to_string())
+        };
+
+        match name.strip_prefix("Replicated").unwrap_or(name) {
+            "ReplacingMergeTree" => TableEngine::ReplacingMergeTree {
+                version: args.first().cloned(),
+                is_deleted: args.get(1).cloned(),
+            },
+            "CollapsingMergeTree" => TableEngine::CollapsingMergeTree {
+                sign: arg(0, DEFAULT_SIGN_COLUMN),
+            },
+            "VersionedCollapsingMergeTree" => TableEngine::VersionedCollapsingMergeTree {
+                sign: arg(0, DEFAULT_SIGN_COLUMN),
+                version: arg(1, DEFAULT_VERSION_COLUMN),
+            },
+            _ => TableEngine::MergeTree,
+        }
+    }
+
+    /// Columns the engine needs in addition to the table's own, in the order the sink writes them.
+    pub fn columns(&self) -> Vec<FieldDefinition> {
+        let column = |name: &str, typ| FieldDefinition {
+            name: name.to_string(),
+            typ,
+            nullable: false,
+            source: SourceDefinition::Dynamic,
+            description: None,
+        };
+        match self {
+            TableEngine::MergeTree => vec![],
+            TableEngine::ReplacingMergeTree {
+                version,
+                is_deleted,
+            } => version
+                .iter()
+                .map(|name| column(name, FieldType::UInt))
+                .chain(
+                    is_deleted
+                        .iter()
+                        .map(|name| column(name, FieldType::Boolean)),
+                )
+                .collect(),
+            TableEngine::CollapsingMergeTree { sign } => vec![column(sign, FieldType::Int8)],
+            TableEngine::VersionedCollapsingMergeTree { sign, version } => vec![
+                column(sign, FieldType::Int8),
+                column(version, FieldType::UInt),
+            ],
+        }
+    }
+
+    /// Whether a delete can be written as a row, instead of a `DELETE` statement.
+    pub fn writes_deletes_as_rows(&self) -> bool {
+        match self {
+            TableEngine::MergeTree => false,
+            TableEngine::ReplacingMergeTree { is_deleted, .. } => is_deleted.is_some(),
+            TableEngine::CollapsingMergeTree { .. }
+            | TableEngine::VersionedCollapsingMergeTree { .. } => true,
+        }
+    }
+}
@@ -5,7 +5,7 @@ use dozer_types::{
 
 #[derive(Error, Debug)]
 pub enum ClickhouseSinkError {
-    #[error("Only MergeTree engine is supported for delete operation")]
+    #[error("Updates and deletes need a primary key on the source table")]
     UnsupportedOperation,
 
     #[error("Column {0} not found in sink table")]
@@ -23,6 +23,9 @@ pub enum ClickhouseSinkError {
     #[error("Sink table does not exist and create_table_options is not set")]
     SinkTableDoesNotExist,
 
+    #[error("No tables to sink, set tables or source_table_name and sink_table_name")]
+    NoTables,
+
     #[error("Expected primary key {0:?} but got {1:?}")]
     PrimaryKeyMismatch(Vec<String>, Vec<String>),
 
@@ -1,9 +1,55 @@
-use dozer_types::types::{FieldDefinition, FieldType, Schema, SourceDefinition};
+use dozer_types::node::OpIdentifier;
+use dozer_types::types::{Field, FieldDefinition, FieldType, Schema, SourceDefinition};
+
+use crate::client::ClickhouseClient;
+use crate::errors::QueryError;
+use crate::types::field_to_literal;
 
 // Replication Metadata Constants
 pub const REPLICA_METADATA_TABLE: &str = "__dozer_replication_metadata";
 pub const META_TABLE_COL: &str = "table";
 pub const META_TXN_ID_COL: &str = "txn_id";
+pub const META_SEQ_IN_TX_COL: &str = "seq_in_tx";
+pub const META_PENDING_TXN_ID_COL: &str = "pending_txn_id";
+pub const META_PENDING_SEQ_IN_TX_COL: &str = "pending_seq_in_tx";
+
+/// What a sink table has written, as recorded in the metadata table.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SinkProgress {
+    /// The last operation that is in the table.
+    pub committed: Option<OpIdentifier>,
+    /// The last operation of the batch that was being written after `committed`,
+    /// which may be partially in the table.
+    pub pending: Option<OpIdentifier>,
+}
+
+impl SinkProgress {
+    /// Values of the metadata row for `table_name`, or `None` if nothing has been committed.
+    pub fn to_row(&self, table_name: &str) -> Option<Vec<Field>> {
+        let committed = self.committed?;
+        Some(vec![
+            Field::String(table_name.to_string()),
+            Field::UInt(committed.txid),
+            Field::UInt(committed.seq_in_tx),
+            self.pending.map_or(Field::Null, |op| Field::UInt(op.txid)),
+            self.pending
+                .map_or(Field::Null, |op| Field::UInt(op.seq_in_tx)),
+        ])
+    }
+
+    pub fn from_row(row: &[Field]) -> Self {
+        let op_id = |txid: &Field, seq_in_tx: &Field| match (txid, seq_in_tx) {
+            (Field::UInt(txid), Field::UInt(seq_in_tx)) => {
+                Some(OpIdentifier::new(*txid, *seq_in_tx))
+            }
+            _ => None,
+        };
+        Self {
+            committed: op_id(&row[0], &row[1]),
+            pending: op_id(&row[2], &row[3]),
+        }
+    }
+}
 
 pub struct ReplicationMetadata {
     pub schema: Schema,
@@ -15,6 +61,28 @@ impl ReplicationMetadata {
         &self.schema
     }
 
+    /// Reads the latest progress recorded for `table_name`.
+    pub async fn get_progress(
+        client: &ClickhouseClient,
+        table_name: &str,
+    ) -> Result<SinkProgress, QueryError> {
+        let metadata = Self::get_metadata();
+        let query = format!(
+            "SELECT \"{META_TXN_ID_COL}\", \"{META_SEQ_IN_TX_COL}\", \"{META_PENDING_TXN_ID_COL}\", \"{META_PENDING_SEQ_IN_TX_COL}\" \
+            FROM \"{REPLICA_METADATA_TABLE}\" WHERE \"{META_TABLE_COL}\" = {} \
+            ORDER BY \"{META_TXN_ID_COL}\" DESC, \"{META_SEQ_IN_TX_COL}\" DESC, \"{META_PENDING_TXN_ID_COL}\" IS NULL \
+            LIMIT 1",
+            field_to_literal(&Field::String(table_name.to_string()), &FieldType::String)?
+        );
+        let result = client
+            .fetch_all(&query, metadata.schema.fields[1..].to_vec(), None)
+            .await?;
+        Ok(result
+            .rows
+            .first()
+            .map_or_else(SinkProgress::default, |row| SinkProgress::from_row(row)))
+    }
+
     pub fn get_primary_keys(&self) -> Vec<String> {
         vec![META_TABLE_COL.to_string()]
     }
@@ -43,6 +111,36 @@ impl ReplicationMetadata {
                     },
                     false,
                 )
+                .field(
+                    FieldDefinition {
+                        name: META_SEQ_IN_TX_COL.to_owned(),
+                        typ: FieldType::UInt,
+                        nullable: false,
+                        source: SourceDefinition::Dynamic,
+                        description: None,
+                    },
+                    false,
+                )
+                .field(
+                    FieldDefinition {
+                        name: META_PENDING_TXN_ID_COL.to_owned(),
+                        typ: FieldType::UInt,
+                        nullable: true,
+                        source: SourceDefinition::Dynamic,
+                        description: None,
+                    },
+                    false,
+                )
+                .field(
+                    FieldDefinition {
+                        name: META_PENDING_SEQ_IN_TX_COL.to_owned(),
+                        typ: FieldType::UInt,
+                        nullable: true,
+                        source: SourceDefinition::Dynamic,
+                        description: None,
+                    },
+                    false,
+                )
                 .clone(),
         }
     }
@@ -3,15 +3,14 @@ use crate::errors::ClickhouseSinkError::{self, SinkTableDoesNotExist};
 use clickhouse_rs::types::Complex;
 use clickhouse_rs::{Block, ClientHandle};
 use dozer_types::log::warn;
-use dozer_types::models::sink::ClickhouseSinkConfig;
 use dozer_types::serde::{Deserialize, Serialize};
-use dozer_types::types::{FieldDefinition, FieldType, Schema};
+use dozer_types::types::{DecimalType, FieldDefinition, FieldType, Schema};
 
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(crate = "dozer_types::serde")]
 pub struct ClickhouseSchemaColumn {
-    name: String,
-    type_: String,
+    pub name: String,
+    pub type_: String,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -31,29 +30,41 @@ pub struct ClickhouseKeyColumnDef {
     pub constraint_schema: String,
 }
 
+/// A change to a sink table that makes it match the Dozer schema.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchemaChange {
+    /// A column the table doesn't have.
+    AddColumn(FieldDefinition),
+    /// A column whose type should be widened to the type of the field.
+    ModifyColumn(FieldDefinition),
+}
+
 pub struct ClickhouseSchema {}
 
 impl ClickhouseSchema {
     pub async fn get_clickhouse_table(
         client: ClickhouseClient,
-        config: &ClickhouseSinkConfig,
+        sink_table_name: &str,
     ) -> Result<ClickhouseTable, ClickhouseSinkError> {
         let mut client = client.get_client_handle().await?;
-        let query = format!("DESCRIBE TABLE {}", config.sink_table_name);
+        let query = format!("DESCRIBE TABLE {}", sink_table_name);
         let block: Block<Complex> = client.query(&query).fetch_all().await?;
 
         if block.row_count() == 0 {
             Err(SinkTableDoesNotExist)
         } else {
-            Self::fetch_sink_table_info(client, &config.sink_table_name).await
+            Self::fetch_sink_table_info(client, sink_table_name).await
         }
     }
 
+    /// Returns the changes that make the table match the schema, which can only be
+    /// new nullable columns and wider types with `schema_evolution`.
     pub async fn compare_with_dozer_schema(
         client: ClickhouseClient,
         schema: &Schema,
         table: &ClickhouseTable,
-    ) -> Result<(), ClickhouseSinkError> {
+        schema_evolution: bool,
+    ) -> Result<Vec<SchemaChange>, ClickhouseSinkError> {
         let mut client = client.get_client_handle().await?;
         let block: Block<Complex> = client
             .query(&format!(
@@ -76,22 +87,7 @@ impl ClickhouseSchema {
             })
             .collect();
 
-        for field in &schema.fields {
-            let Some(column) = columns.iter().find(|column| column.name == field.name) else {
-                return Err(ClickhouseSinkError::ColumnNotFound(field.name.clone()));
-            };
-            let expected_type = map_field_to_type(field);
-            let column_type = column.type_.clone();
-            if expected_type != column_type {
-                return Err(ClickhouseSinkError::ColumnTypeMismatch(
-                    field.name.clone(),
-                    expected_type.to_string(),
-                    column_type.to_string(),
-                ));
-            }
-        }
-
-        Ok(())
+        get_schema_changes(&columns, schema, schema_evolution)
     }
 
     async fn fetch_sink_table_info(
@@ -143,10 +139,123 @@ impl ClickhouseSchema {
     }
 }
 
+pub fn get_schema_changes(
+    columns: &[ClickhouseSchemaColumn],
+    schema: &Schema,
+    schema_evolution: bool,
+) -> Result<Vec<SchemaChange>, ClickhouseSinkError> {
+    let mut changes = vec![];
+    for field in &schema.fields {
+        let Some(column) = columns.iter().find(|column| column.name == field.name) else {
+            if schema_evolution && field.nullable {
+                changes.push(SchemaChange::AddColumn(field.clone()));
+                continue;
+            }
+            return Err(ClickhouseSinkError::ColumnNotFound(field.name.clone()));
+        };
+        let expected_type = map_field_to_type(field);
+        let column_type = column.type_.clone();
+        if canonical_type(&expected_type) != canonical_type(&column_type) {
+            if schema_evolution && is_widening(&column_type, &expected_type) {
+                changes.push(SchemaChange::ModifyColumn(field.clone()));
+                continue;
+            }
+            return Err(ClickhouseSinkError::ColumnTypeMismatch(
+                field.name.clone(),
+                expected_type.to_string(),
+                column_type.to_string(),
+            ));
+        }
+    }
+    Ok(changes)
+}
+
+/// `DESCRIBE TABLE` shows aliases by their canonical name.
+fn canonical_type(typ: &str) -> String {
+    typ.replace("Boolean", "Bool")
+}
+
+/// Whether every value of a column of type `from` fits in type `to`.
+pub fn is_widening(from: &str, to: &str) -> bool {
+    let (from, from_nullable) = strip_nullable(from);
+    let (to, to_nullable) = strip_nullable(to);
+    if from_nullable && !to_nullable {
+        return false;
+    }
+    if from == to {
+        return true;
+    }
+
+    let bits = |typ: &str, prefix: &str| {
+        typ.strip_prefix(prefix)
+            .and_then(|bits| bits.parse::<u32>().ok())
+    };
+    if let (Some(from), Some(to)) = (bits(from, "UInt"), bits(to, "UInt")) {
+        return from < to;
+    }
+    if let (Some(from), Some(to)) = (bits(from, "Int"), bits(to, "Int")) {
+        return from < to;
+    }
+    if let (Some(from), Some(to)) = (bits(from, "UInt"), bits(to, "Int")) {
+        return from < to;
+    }
+    if let (Some(from), Some(to)) = (bits(from, "Float"), bits(to, "Float")) {
+        return from < to;
+    }
+    if let (Some((from_precision, from_scale)), Some((to_precision, to_scale))) =
+        (decimal_parameters(from), decimal_parameters(to))
+    {
+        return to_scale >= from_scale && to_precision - to_scale >= from_precision - from_scale;
+    }
+    from.starts_with("FixedString(") && to == "String"
+}
+
+fn strip_nullable(typ: &str) -> (&str, bool) {
+    match typ
+        .strip_prefix("Nullable(")
+        .and_then(|typ| typ.strip_suffix(')'))
+    {
+        Some(typ) => (typ, true),
+        None => (typ, false),
+    }
+}
+
+fn decimal_parameters(typ: &str) -> Option<(i64, i64)> {
+    let (precision, scale) = typ
+        .strip_prefix("Decimal(")?
+        .strip_suffix(')')?
+        .split_once(',')?;
+    Some((precision.trim().parse().ok()?, scale.trim().parse().ok()?))
+}
+
 pub fn map_field_to_type(field: &FieldDefinition) -> String {
+    let typ = map_type(&field.typ);
+    if field.nullable {
+        if can_be_nullable(&field.typ) {
+            format!("Nullable({})", typ)
+        } else {
+            warn!(
+                "{} field cannot be nullable, ignoring nullable flag",
+                field.typ
+            );
+            typ
+        }
+    } else {
+        typ
+    }
+}
+
+/// ClickHouse arrays and tuples can't be `Nullable`.
+fn can_be_nullable(typ: &FieldType) -> bool {
+    !matches!(
+        typ,
+        FieldType::Binary | FieldType::List(_) | FieldType::Struct(_)
+    )
+}
+
+fn map_type(typ: &FieldType) -> String {
     const DECIMAL_SCALE: u8 = 4;
-    let decimal = format!("Decimal(10, {})", DECIMAL_SCALE);
-    let typ: &str = match field.typ {
+    let typ = match typ {
         FieldType::UInt => "UInt64",
         FieldType::U128 => "UInt128",
         FieldType::Int => "Int64",
@@ -156,23 +265,34 @@ pub fn map_field_to_type(field: &FieldDefinition) -> String {
         FieldType::Boolean => "Boolean",
         FieldType::String => "String",
         FieldType::Text => "String",
+        // Written as strings.
+        FieldType::Uuid | FieldType::Time | FieldType::Enum(_) => "String",
         FieldType::Binary => "Array(UInt8)",
-        FieldType::Decimal => &decimal,
+        // `clickhouse-rs` writes decimals with a 64 bit mantissa.
+        FieldType::Decimal(Some(DecimalType { precision, scale })) if *precision <= 18 => {
+            return format!("Decimal({precision}, {scale})")
+        }
+        FieldType::Decimal(_) => return format!("Decimal(10, {})", DECIMAL_SCALE),
         FieldType::Timestamp => "DateTime64(3)",
         FieldType::Date => "Date",
         FieldType::Json => "JSON",
         FieldType::Point => "Point",
         FieldType::Duration => unimplemented!(),
-    };
-
-    if field.nullable {
-        if field.typ != FieldType::Binary {
-            format!("Nullable({})", typ)
-        } else {
-            warn!("Binary field cannot be nullable, ignoring nullable flag");
-            typ.to_string()
+        // List elements can be null.
+        FieldType::List(element_type) => {
+            return if can_be_nullable(element_type) {
+                format!("Array(Nullable({}))", map_type(element_type))
+            } else {
+                format!("Array({})", map_type(element_type))
+            };
         }
-    } else {
-        typ.to_string()
-    }
+        FieldType::Struct(fields) => {
+            let fields = fields
+                .iter()
+                .map(|field| format!("{} {}", field.name, map_field_to_type(field)))
+                .collect::<Vec<_>>();
+            return format!("Tuple({})", fields.join(", "));
+        }
+    };
+    typ.to_string()
 }
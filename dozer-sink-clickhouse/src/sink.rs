@@ -2,24 +2,24 @@ use dozer_core::epoch::Epoch;
 use dozer_core::event::EventHub;
 use dozer_core::node::{PortHandle, Sink, SinkFactory};
 use dozer_core::tokio::runtime::Runtime;
-use dozer_core::DEFAULT_PORT_HANDLE;
 use dozer_types::errors::internal::BoxedError;
 
-use dozer_types::log::debug;
+use dozer_types::log::{debug, info};
 use dozer_types::models::sink::{ClickhouseSinkConfig, ClickhouseTableOptions};
 use dozer_types::node::OpIdentifier;
 
 use crate::client::ClickhouseClient;
+use crate::engine::TableEngine;
 use crate::errors::ClickhouseSinkError;
-use crate::metadata::{
-    ReplicationMetadata, META_TABLE_COL, META_TXN_ID_COL, REPLICA_METADATA_TABLE,
-};
-use crate::schema::{ClickhouseSchema, ClickhouseTable};
+use crate::metadata::{ReplicationMetadata, SinkProgress, REPLICA_METADATA_TABLE};
+use crate::schema::{ClickhouseSchema, ClickhouseTable, SchemaChange};
 use dozer_types::tonic::async_trait;
-use dozer_types::types::{Field, FieldDefinition, Operation, Schema, TableOperation};
-use std::collections::HashMap;
+use dozer_types::types::{Field, Operation, Schema, TableOperation};
+use std::collections::{HashMap, HashSet};
 use std::fmt::Debug;
+use std::hash::{Hash, Hasher};
 use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 const BATCH_SIZE: usize = 100;
 
@@ -30,7 +30,8 @@ pub struct ClickhouseSinkFactory {
 }
 
 impl ClickhouseSinkFactory {
-    pub fn new(config: ClickhouseSinkConfig, runtime: Arc<Runtime>) -> Self {
+    pub fn new(mut config: ClickhouseSinkConfig, runtime: Arc<Runtime>) -> Self {
+        config.move_single_table_to_tables();
         Self { config, runtime }
     }
 
@@ -40,17 +41,19 @@ impl ClickhouseSinkFactory {
 
         let primary_keys = repl_metadata.get_primary_keys();
         let partition_by = format!("({})", primary_keys.join(","));
+        let cluster = self.config.tables.iter().find_map(|table| {
+            table
+                .create_table_options
+                .as_ref()
+                .and_then(|o| o.cluster.clone())
+        });
         let create_table_options = ClickhouseTableOptions {
             engine: Some("ReplacingMergeTree".to_string()),
             primary_keys: Some(repl_metadata.get_primary_keys()),
             partition_by: Some(partition_by),
             // Replaced using this key
             order_by: Some(repl_metadata.get_primary_keys()),
-            cluster: self
-                .config
-                .create_table_options
-                .as_ref()
-                .and_then(|o| o.cluster.clone()),
+            cluster: cluster.clone(),
             sample_by: None,
         };
         client
@@ -61,6 +64,15 @@ impl ClickhouseSinkFactory {
                 None,
             )
             .await?;
+        // Tables created by older versions only have the transaction id.
+        let changes = repl_metadata.schema.fields[2..]
+            .iter()
+            .cloned()
+            .map(SchemaChange::AddColumn)
+            .collect::<Vec<_>>();
+        client
+            .alter_table(&repl_metadata.table_name, &changes, cluster)
+            .await?;
 
         Ok(())
     }
@@ -73,15 +85,15 @@ impl SinkFactory for ClickhouseSinkFactory {
     }
 
     fn get_input_ports(&self) -> Vec<PortHandle> {
-        vec![DEFAULT_PORT_HANDLE]
+        (0..self.config.tables.len() as PortHandle).collect()
     }
 
-    fn get_input_port_name(&self, _port: &PortHandle) -> String {
-        self.config.source_table_name.clone()
+    fn get_input_port_name(&self, port: &PortHandle) -> String {
+        self.config.tables[*port as usize].source_table_name.clone()
     }
 
     fn prepare(&self, input_schemas: HashMap<PortHandle, Schema>) -> Result<(), BoxedError> {
-        debug_assert!(input_schemas.len() == 1);
+        debug_assert!(input_schemas.len() == self.config.tables.len());
         Ok(())
     }
 
@@ -90,37 +102,65 @@ impl SinkFactory for ClickhouseSinkFactory {
         mut input_schemas: HashMap<PortHandle, Schema>,
         _event_hub: EventHub,
     ) -> Result<Box<dyn Sink>, BoxedError> {
-        let schema = input_schemas.remove(&DEFAULT_PORT_HANDLE).unwrap();
-
+        if self.config.tables.is_empty() {
+            return Err(ClickhouseSinkError::NoTables.into());
+        }
         let client = ClickhouseClient::new(self.config.clone());
 
-        let config = &self.config;
-
         // Create Sink Table
         self.create_replication_metadata_table().await?;
 
-        // Create Sink Table
-        if self.config.create_table_options.is_some() {
-            client
-                .create_table(
-                    &config.sink_table_name,
-                    &schema.fields,
-                    self.config.create_table_options.clone(),
-                    None,
-                )
-                .await?;
-        }
-        let table = ClickhouseSchema::get_clickhouse_table(client.clone(), &self.config).await?;
+        let mut tables = vec![];
+        for (port, config) in self.config.tables.iter().enumerate() {
+            let schema = input_schemas.remove(&(port as PortHandle)).unwrap();
+
+            // Create Sink Table
+            if config.create_table_options.is_some() {
+                client
+                    .create_table(
+                        &config.sink_table_name,
+                        &schema.fields,
+                        config.create_table_options.clone(),
+                        None,
+                    )
+                    .await?;
+            }
+            let table =
+                ClickhouseSchema::get_clickhouse_table(client.clone(), &config.sink_table_name)
+                    .await?;
+
+            let changes = ClickhouseSchema::compare_with_dozer_schema(
+                client.clone(),
+                &schema,
+                &table,
+                self.config.schema_evolution,
+            )
+            .await?;
+            if !changes.is_empty() {
+                info!(
+                    "Altering ClickHouse table {} to match the schema of {}",
+                    config.sink_table_name, config.source_table_name
+                );
+                let cluster = config
+                    .create_table_options
+                    .as_ref()
+                    .and_then(|o| o.cluster.clone());
+                client
+                    .alter_table(&config.sink_table_name, &changes, cluster)
+                    .await?;
+            }
 
-        ClickhouseSchema::compare_with_dozer_schema(client.clone(), &schema, &table).await?;
-        let sink = ClickhouseSink::new(
-            client,
-            self.config.clone(),
-            schema,
-            self.runtime.clone(),
-            table,
-        );
+            let progress =
+                ReplicationMetadata::get_progress(&client, &config.sink_table_name).await?;
+            tables.push(SinkTable::new(
+                config.sink_table_name.clone(),
+                schema,
+                table,
+                progress,
+            ));
+        }
 
+        let sink = ClickhouseSink::new(client, self.runtime.clone(), tables);
         Ok(Box::new(sink))
     }
 }
@@ -128,74 +168,82 @@ impl SinkFactory for ClickhouseSinkFactory {
 pub(crate) struct ClickhouseSink {
     pub(crate) client: ClickhouseClient,
     pub(crate) runtime: Arc<Runtime>,
-    pub(crate) schema: Schema,
-    pub(crate) sink_table_name: String,
-    pub(crate) table: ClickhouseTable,
-    batch: Vec<Vec<Field>>,
+    tables: Vec<SinkTable>,
     metadata: ReplicationMetadata,
-    latest_txid: Option<u64>,
 }
 
 impl Debug for ClickhouseSink {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("ClickhouseSink")
-            .field("sink_table_name", &self.sink_table_name)
-            .field("table", &self.table)
-            .field("schema", &self.schema)
+            .field("tables", &self.tables)
             .finish()
     }
 }
 
 impl ClickhouseSink {
+    pub fn new(client: ClickhouseClient, runtime: Arc<Runtime>, tables: Vec<SinkTable>) -> Self {
+        Self {
+            client,
+            runtime,
+            tables,
+            metadata: ReplicationMetadata::get_metadata(),
+        }
+    }
+}
+
+/// A table the sink writes to, with the changes that aren't written yet.
+#[derive(Debug)]
+pub(crate) struct SinkTable {
+    pub(crate) schema: Schema,
+    pub(crate) sink_table_name: String,
+    pub(crate) table: ClickhouseTable,
+    engine: TableEngine,
+    batch: Vec<Vec<Field>>,
+    /// Keys to remove with a lightweight `DELETE` before `batch` is inserted.
+    deleted_keys: HashSet<Vec<Field>>,
+    progress: SinkProgress,
+    /// The last operation in `batch`.
+    latest_op_id: Option<OpIdentifier>,
+    last_version: u64,
+}
+
+impl SinkTable {
     pub fn new(
-        client: ClickhouseClient,
-        config: ClickhouseSinkConfig,
+        sink_table_name: String,
         schema: Schema,
-        runtime: Arc<Runtime>,
         table: ClickhouseTable,
+        progress: SinkProgress,
     ) -> Self {
+        let engine = TableEngine::parse(&table.engine_full);
         let mut schema = schema.clone();
-
-        if table.engine == "CollapsingMergeTree" && !schema.fields.is_empty() {
-            // get source from any field in schema
-            let source = schema.fields[0].source.clone();
-            schema.fields.push(FieldDefinition {
-                name: "sign".to_string(),
-                typ: dozer_types::types::FieldType::Int8,
-                nullable: false,
-                description: None,
-                source,
-            });
-        }
+        schema.fields.extend(engine.columns());
         Self {
-            client,
-            runtime,
             schema,
-            sink_table_name: config.sink_table_name,
+            sink_table_name,
             table,
+            engine,
             batch: Vec::new(),
-            latest_txid: None,
-            metadata: ReplicationMetadata::get_metadata(),
+            deleted_keys: HashSet::new(),
+            progress,
+            latest_op_id: None,
+            last_version: 0,
         }
     }
 
-    pub async fn insert_metadata(&self) -> Result<(), BoxedError> {
+    async fn insert_metadata(
+        &self,
+        client: &ClickhouseClient,
+        metadata: &ReplicationMetadata,
+        progress: SinkProgress,
+    ) -> Result<(), BoxedError> {
         debug!(
             "[Sink] Inserting metadata record {:?} {}",
-            self.latest_txid,
+            progress,
             self.sink_table_name.clone()
         );
-        if let Some(txid) = self.latest_txid {
-            self.client
-                .insert(
-                    REPLICA_METADATA_TABLE,
-                    &self.metadata.schema.fields,
-                    vec![
-                        Field::String(self.sink_table_name.clone()),
-                        Field::UInt(txid),
-                    ],
-                    None,
-                )
+        if let Some(row) = progress.to_row(&self.sink_table_name) {
+            client
+                .insert(REPLICA_METADATA_TABLE, &metadata.schema.fields, row, None)
                 .await?;
         }
         Ok(())
@@ -207,41 +255,230 @@ impl ClickhouseSink {
         Ok(())
     }
 
-    fn commit_batch(&mut self) -> Result<(), BoxedError> {
+    /// Appends the values of the engine's columns to a row.
+    fn engine_row(&mut self, mut values: Vec<Field>, deleted: bool) -> Vec<Field> {
+        match &self.engine {
+            TableEngine::MergeTree => {}
+            TableEngine::ReplacingMergeTree {
+                version,
+                is_deleted,
+            } => {
+                let (has_version, has_is_deleted) = (version.is_some(), is_deleted.is_some());
+                if has_version {
+                    let version = self.next_version();
+                    values.push(Field::UInt(version));
+                }
+                if has_is_deleted {
+                    values.push(Field::Boolean(deleted));
+                }
+            }
+            TableEngine::CollapsingMergeTree { .. } => {
+                values.push(Field::Int8(if deleted { -1 } else { 1 }));
+            }
+            TableEngine::VersionedCollapsingMergeTree { .. } => {
+                // The cancel row must have the same version as the row it cancels.
+                let version = row_version(&values);
+                values.push(Field::Int8(if deleted { -1 } else { 1 }));
+                values.push(Field::UInt(version));
+            }
+        }
+        values
+    }
+
+    /// Strictly increasing, so the latest write of a key wins in `ReplacingMergeTree`,
+    /// also across restarts.
+    fn next_version(&mut self) -> u64 {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_nanos() as u64);
+        self.last_version = now.max(self.last_version + 1);
+        self.last_version
+    }
+
+    fn key(&self, values: &[Field]) -> Vec<Field> {
+        self.schema
+            .primary_index
+            .iter()
+            .map(|index| values[*index].clone())
+            .collect()
+    }
+
+    fn insert(&mut self, values: Vec<Field>) -> Result<(), BoxedError> {
+        let row = self.engine_row(values, false);
+        self.insert_values(&row)
+    }
+
+    fn delete(&mut self, values: Vec<Field>) -> Result<(), BoxedError> {
+        if self.engine.writes_deletes_as_rows() {
+            let row = self.engine_row(values, true);
+            return self.insert_values(&row);
+        }
+
+        if self.schema.primary_index.is_empty() {
+            return Err(ClickhouseSinkError::UnsupportedOperation.into());
+        }
+        // The `DELETE` runs before the batch is inserted, so drop the batched rows it supersedes.
+        let key = self.key(&values);
+        self.batch.retain(|row| {
+            !self
+                .schema
+                .primary_index
+                .iter()
+                .zip(&key)
+                .all(|(index, value)| &row[*index] == value)
+        });
+        self.deleted_keys.insert(key);
+        Ok(())
+    }
+
+    fn update(&mut self, old: Vec<Field>, new: Vec<Field>) -> Result<(), BoxedError> {
+        // `ReplacingMergeTree` replaces the old row by itself, unless the key changed.
+        let replaces_old = matches!(self.engine, TableEngine::ReplacingMergeTree { .. })
+            && self.key(&old) == self.key(&new);
+        if !replaces_old {
+            self.delete(old)?;
+        }
+        self.insert(new)
+    }
+
+    fn commit_batch(
+        &mut self,
+        client: &ClickhouseClient,
+        runtime: &Runtime,
+        metadata: &ReplicationMetadata,
+    ) -> Result<(), BoxedError> {
         let batch = std::mem::take(&mut self.batch);
-        self.runtime.block_on(async {
-            //Insert batch
-            self.client
-                .insert_multi(&self.sink_table_name, &self.schema.fields, batch, None)
+        let mut deleted_keys = std::mem::take(&mut self.deleted_keys)
+            .into_iter()
+            .collect::<Vec<_>>();
+        let committed = self.progress.committed;
+        let replaying = self.progress.pending.take().is_some();
+        // Never goes back, for example if a snapshot finishes at an older operation.
+        let end = self.latest_op_id.max(committed);
+
+        // A batch after a committed operation is recorded before it's written, so that after a restart
+        // it's rebuilt with the same operations and ClickHouse deduplicates it by its token.
+        let pending = match (committed, end) {
+            (Some(committed), Some(end))
+                if end > committed && !(batch.is_empty() && deleted_keys.is_empty()) =>
+            {
+                Some((committed, end))
+            }
+            _ => None,
+        };
+        let mut deduplication_token = pending.map(|(committed, end)| {
+            format!(
+                "{}-{}.{}-{}.{}",
+                self.sink_table_name, committed.txid, committed.seq_in_tx, end.txid, end.seq_in_tx
+            )
+        });
+        if replaying && !deleted_keys.is_empty() {
+            // The `DELETE` would also remove rows of the batch that were written before the restart,
+            // and a deduplicated insert wouldn't add them back, so delete and insert all of them again.
+            deleted_keys.extend(batch.iter().map(|row| self.key(row)));
+            deduplication_token = None;
+        }
+
+        runtime.block_on(async {
+            if let Some((committed, end)) = pending {
+                self.insert_metadata(
+                    client,
+                    metadata,
+                    SinkProgress {
+                        committed: Some(committed),
+                        pending: Some(end),
+                    },
+                )
                 .await?;
+            }
+
+            if !deleted_keys.is_empty() {
+                let key_fields = self
+                    .schema
+                    .primary_index
+                    .iter()
+                    .map(|index| self.schema.fields[*index].clone())
+                    .collect::<Vec<_>>();
+                client
+                    .delete(&self.sink_table_name, &key_fields, &deleted_keys, None)
+                    .await?;
+            }
+
+            //Insert batch
+            if !batch.is_empty() {
+                client
+                    .insert_multi(
+                        &self.sink_table_name,
+                        &self.schema.fields,
+                        batch,
+                        None,
+                        deduplication_token,
+                    )
+                    .await?;
+            }
 
-            self.insert_metadata().await?;
+            self.insert_metadata(
+                client,
+                metadata,
+                SinkProgress {
+                    committed: end,
+                    pending: None,
+                },
+            )
+            .await?;
             Ok::<(), BoxedError>(())
         })?;
 
+        if end.is_some() {
+            self.progress.committed = end;
+        }
         Ok(())
     }
 
-    fn _get_latest_op(&mut self) -> Result<Option<OpIdentifier>, BoxedError> {
-        let op = self.runtime.block_on(async {
-            let mut client = self.client.get_client_handle().await?;
-            let table_name = self.sink_table_name.clone();
-            let query = format!("SELECT \"{META_TXN_ID_COL}\" FROM \"{REPLICA_METADATA_TABLE}\" WHERE \"{META_TABLE_COL}\" = '\"{table_name}\"' ORDER BY \"{META_TXN_ID_COL}\" LIMIT 1");
-            let block = client
-                .query(query)
-                .fetch_all()
-                .await?;
+    fn process(
+        &mut self,
+        op: TableOperation,
+        client: &ClickhouseClient,
+        runtime: &Runtime,
+        metadata: &ReplicationMetadata,
+    ) -> Result<(), BoxedError> {
+        if let Some(id) = op.id {
+            // Written before a restart.
+            if self
+                .progress
+                .committed
+                .is_some_and(|committed| id <= committed)
+            {
+                return Ok(());
+            }
+            // The last operation of the batch being rebuilt didn't come again.
+            if self.progress.pending.is_some_and(|pending| id > pending) {
+                self.commit_batch(client, runtime, metadata)?;
+            }
+            self.latest_op_id = Some(id);
+        }
 
-            let row = block.rows().next();
-            match row {
-                Some(row) => {
-                    let txid: u64 = row.get(META_TXN_ID_COL)?;
-                    Ok::<Option<dozer_types::node::OpIdentifier>, BoxedError>(Some(OpIdentifier { txid, seq_in_tx: 0 }))
-                },
-                None => Ok::<Option<dozer_types::node::OpIdentifier>, BoxedError>(None),
+        let is_batch_insert = matches!(op.op, Operation::BatchInsert { .. });
+        match op.op {
+            Operation::Insert { new } => self.insert(new.values)?,
+            Operation::Delete { old } => self.delete(old.values)?,
+            Operation::Update { new, old } => self.update(old.values, new.values)?,
+            Operation::BatchInsert { new } => {
+                for record in new {
+                    self.insert(record.values)?;
+                }
             }
-        })?;
-        Ok(op)
+        }
+
+        let batch_done = match self.progress.pending {
+            Some(pending) => self.latest_op_id >= Some(pending),
+            None => is_batch_insert || self.batch.len() + self.deleted_keys.len() > BATCH_SIZE - 1,
+        };
+        if batch_done {
+            self.commit_batch(client, runtime, metadata)?;
+        }
+
+        Ok(())
     }
 }
 
@@ -251,59 +488,17 @@ impl Sink for ClickhouseSink {
     }
 
     fn flush_batch(&mut self) -> Result<(), BoxedError> {
-        self.commit_batch()?;
+        for table in &mut self.tables {
+            // The batch being rebuilt is written once it has all its operations.
+            if table.progress.pending.is_none() {
+                table.commit_batch(&self.client, &self.runtime, &self.metadata)?;
+            }
+        }
         Ok(())
     }
 
     fn process(&mut self, op: TableOperation) -> Result<(), BoxedError> {
-        self.latest_txid = op.id.map(|id| id.txid);
-        match op.op {
-            Operation::Insert { new } => {
-                if self.table.engine == "CollapsingMergeTree" {
-                    let mut values = new.values;
-                    values.push(Field::Int8(1));
-                    self.insert_values(&values)?;
-                } else {
-                    self.insert_values(&new.values)?;
-                }
-
-                if self.batch.len() > BATCH_SIZE - 1 {
-                    self.commit_batch()?;
-                }
-            }
-            Operation::Delete { old } => {
-                if self.table.engine != "CollapsingMergeTree" {
-                    return Err(BoxedError::from(ClickhouseSinkError::UnsupportedOperation));
-                }
-                let mut values = old.values;
-                values.push(Field::Int(-1));
-                self.insert_values(&values)?;
-            }
-            Operation::Update { new, old } => {
-                if self.table.engine != "CollapsingMergeTree" {
-                    return Err(BoxedError::from(ClickhouseSinkError::UnsupportedOperation));
-                }
-                let mut values = old.values;
-                values.push(Field::Int8(-1));
-                self.insert_values(&values)?;
-
-                let mut values = new.values;
-                values.push(Field::Int8(1));
-                self.insert_values(&values)?;
-            }
-            Operation::BatchInsert { new } => {
-                for record in new {
-                    let mut values = record.values;
-                    if self.table.engine == "CollapsingMergeTree" {
-                        values.push(Field::Int8(1));
-                    }
-                    self.insert_values(&values)?;
-                }
-                self.commit_batch()?;
-            }
-        }
-
-        Ok(())
+        self.tables[op.port as usize].process(op, &self.client, &self.runtime, &self.metadata)
     }
 
     fn on_source_snapshotting_started(
@@ -318,8 +513,10 @@ impl Sink for ClickhouseSink {
         _connection_name: String,
         id: Option<OpIdentifier>,
     ) -> Result<(), BoxedError> {
-        self.latest_txid = id.map(|opid| opid.txid);
-        self.commit_batch()?;
+        for table in &mut self.tables {
+            table.latest_op_id = id;
+            table.commit_batch(&self.client, &self.runtime, &self.metadata)?;
+        }
         Ok(())
     }
 
@@ -332,7 +529,39 @@ impl Sink for ClickhouseSink {
     }
 
     fn get_latest_op_id(&mut self) -> Result<Option<OpIdentifier>, BoxedError> {
-        // self.get_latest_op()
-        Ok(None)
+        // Tables that are ahead skip the operations they already have.
+        Ok(self
+            .tables
+            .iter()
+            .map(|table| table.progress.committed)
+            .min()
+            .flatten())
+    }
+}
+
+/// Version of a row in a `VersionedCollapsingMergeTree`, derived from its values
+/// so that a cancel row gets the same version as the row it cancels.
+pub(crate) fn row_version(values: &[Field]) -> u64 {
+    let mut hasher = Fnv1aHasher(FNV_OFFSET_BASIS);
+    values.hash(&mut hasher);
+    hasher.finish()
+}
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
+/// Unlike `DefaultHasher`, gives the same hashes across Rust releases, so versions stay
+/// valid after an upgrade.
+struct Fnv1aHasher(u64);
+
+impl Hasher for Fnv1aHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 = (self.0 ^ *byte as u64).wrapping_mul(FNV_PRIME);
+        }
     }
 }
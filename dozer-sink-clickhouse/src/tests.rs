@@ -1,9 +1,20 @@
 use crate::client::ClickhouseClient;
-use crate::schema::ClickhouseSchema;
+use crate::ddl::{get_alter_table_query, get_create_table_query, get_delete_query};
+use crate::engine::TableEngine;
+use crate::errors::ClickhouseSinkError;
+use crate::metadata::{ReplicationMetadata, SinkProgress, REPLICA_METADATA_TABLE};
+use crate::schema::{
+    get_schema_changes, is_widening, map_field_to_type, ClickhouseSchema, ClickhouseSchemaColumn,
+    SchemaChange,
+};
+use crate::sink::row_version;
 use clickhouse_rs::types::Query;
 use dozer_core::tokio;
-use dozer_types::models::sink::ClickhouseSinkConfig;
-use dozer_types::types::{FieldDefinition, FieldType, Schema};
+use dozer_types::models::sink::{
+    ClickhouseSinkConfig, ClickhouseSinkTable, ClickhouseTableOptions,
+};
+use dozer_types::node::OpIdentifier;
+use dozer_types::types::{DecimalType, Field, FieldDefinition, FieldType, Schema};
 
 fn get_client() -> ClickhouseClient {
     ClickhouseClient::new(get_sink_config())
@@ -11,10 +22,16 @@ fn get_client() -> ClickhouseClient {
 
 fn get_sink_config() -> ClickhouseSinkConfig {
     ClickhouseSinkConfig {
-        source_table_name: "source_table".to_string(),
-        sink_table_name: "sink_table".to_string(),
-        scheme: "tcp".to_string(),
+        tables: vec![ClickhouseSinkTable {
+            source_table_name: "source_table".to_string(),
+            sink_table_name: "sink_table".to_string(),
+            create_table_options: None,
+        }],
+        source_table_name: None,
+        sink_table_name: None,
         create_table_options: None,
+        schema_evolution: false,
+        scheme: "tcp".to_string(),
         user: "default".to_string(),
         password: None,
         database: "default".to_string(),
@@ -63,12 +80,12 @@ async fn create_table(table_name: &str) {
 #[ignore]
 async fn test_get_clickhouse_table() {
     let client = get_client();
-    let sink_config = get_sink_config();
-    create_table(&sink_config.sink_table_name).await;
-    let clickhouse_table = ClickhouseSchema::get_clickhouse_table(client, &sink_config)
+    let sink_table_name = &get_sink_config().tables[0].sink_table_name;
+    create_table(sink_table_name).await;
+    let clickhouse_table = ClickhouseSchema::get_clickhouse_table(client, sink_table_name)
         .await
         .unwrap();
-    assert_eq!(clickhouse_table.name, sink_config.sink_table_name);
+    assert_eq!(&clickhouse_table.name, sink_table_name);
 }
 
 use clickhouse_rs::{Block, Pool};
@@ -103,3 +120,255 @@ async fn clickhouse_test() -> Result<(), Box<dyn Error>> {
     client.insert(table, block).await?;
     Ok(())
 }
+
+#[test]
+fn test_parse_table_engine() {
+    assert_eq!(TableEngine::parse("MergeTree()"), TableEngine::MergeTree);
+    assert_eq!(
+        TableEngine::parse("MergeTree PRIMARY KEY (id) ORDER BY (id)"),
+        TableEngine::MergeTree
+    );
+    assert_eq!(
+        TableEngine::parse("ReplacingMergeTree"),
+        TableEngine::ReplacingMergeTree {
+            version: None,
+            is_deleted: None,
+        }
+    );
+    assert_eq!(
+        TableEngine::parse(
+            "ReplicatedReplacingMergeTree('/clickhouse/tables/{shard}/users', '{replica}', ver, `is_deleted`) ORDER BY id"
+        ),
+        TableEngine::ReplacingMergeTree {
+            version: Some("ver".to_string()),
+            is_deleted: Some("is_deleted".to_string()),
+        }
+    );
+    assert_eq!(
+        TableEngine::parse("CollapsingMergeTree(s) PRIMARY KEY id ORDER BY id"),
+        TableEngine::CollapsingMergeTree {
+            sign: "s".to_string()
+        }
+    );
+    assert_eq!(
+        TableEngine::parse("VersionedCollapsingMergeTree(sign, version) ORDER BY id"),
+        TableEngine::VersionedCollapsingMergeTree {
+            sign: "sign".to_string(),
+            version: "version".to_string(),
+        }
+    );
+}
+
+#[test]
+fn test_create_table_query_adds_engine_columns() {
+    let options = |engine: &str| ClickhouseTableOptions {
+        engine: Some(engine.to_string()),
+        primary_keys: Some(vec!["id".to_string()]),
+        partition_by: None,
+        sample_by: None,
+        order_by: Some(vec!["id".to_string()]),
+        cluster: None,
+    };
+    let fields = _get_dozer_schema().fields;
+
+    let query = get_create_table_query("users", &fields, Some(options("CollapsingMergeTree")));
+    assert!(query.contains("sign Int8"));
+    assert!(query.contains("ENGINE = CollapsingMergeTree(sign)"));
+
+    let query = get_create_table_query(
+        "users",
+        &fields,
+        Some(options("ReplacingMergeTree(ver, is_deleted)")),
+    );
+    assert!(query.contains("ver UInt64"));
+    assert!(query.contains("is_deleted Boolean"));
+
+    let query = get_create_table_query("users", &fields, Some(options("MergeTree()")));
+    assert!(!query.contains("sign"));
+    assert!(query.contains("SETTINGS non_replicated_deduplication_window"));
+
+    let query = get_create_table_query(
+        "users",
+        &fields,
+        Some(options(
+            "ReplicatedMergeTree('/clickhouse/tables/{shard}/users', '{replica}')",
+        )),
+    );
+    assert!(!query.contains("SETTINGS"));
+}
+
+#[test]
+fn test_delete_query() {
+    let schema = _get_dozer_schema();
+    let query = get_delete_query(
+        "users",
+        &schema.fields,
+        &[
+            vec![Field::UInt(1), Field::String("it's".to_string())],
+            vec![Field::UInt(2), Field::String("a\\b".to_string())],
+        ],
+    )
+    .unwrap();
+    assert_eq!(
+        query,
+        r"DELETE FROM users WHERE (id, data) IN ((1, 'it\'s'), (2, 'a\\b'))"
+    );
+}
+
+#[test]
+fn test_row_version_is_stable() {
+    let row = vec![Field::UInt(1), Field::String("data".to_string())];
+    assert_eq!(row_version(&row), row_version(&row.clone()));
+    assert_ne!(
+        row_version(&row),
+        row_version(&[Field::UInt(2), Field::String("data".to_string())])
+    );
+}
+
+#[test]
+fn test_sink_progress_row() {
+    let progress = SinkProgress {
+        committed: Some(OpIdentifier::new(3, 4)),
+        pending: Some(OpIdentifier::new(5, 0)),
+    };
+    let row = progress.to_row("users").unwrap();
+    assert_eq!(row[0], Field::String("users".to_string()));
+    assert_eq!(SinkProgress::from_row(&row[1..]), progress);
+
+    let progress = SinkProgress {
+        committed: Some(OpIdentifier::new(3, 4)),
+        pending: None,
+    };
+    let row = progress.to_row("users").unwrap();
+    assert_eq!(row[3], Field::Null);
+    assert_eq!(SinkProgress::from_row(&row[1..]), progress);
+
+    assert_eq!(SinkProgress::default().to_row("users"), None);
+}
+
+#[test]
+fn test_alter_table_query() {
+    let metadata = ReplicationMetadata::get_metadata();
+    let changes = metadata.schema.fields[2..]
+        .iter()
+        .cloned()
+        .map(SchemaChange::AddColumn)
+        .collect::<Vec<_>>();
+    let query = get_alter_table_query(REPLICA_METADATA_TABLE, &changes, Some("dozer".to_string()));
+    assert_eq!(
+        query,
+        "ALTER TABLE __dozer_replication_metadata ON CLUSTER dozer \
+        ADD COLUMN IF NOT EXISTS seq_in_tx UInt64, \
+        ADD COLUMN IF NOT EXISTS pending_txn_id Nullable(UInt64), \
+        ADD COLUMN IF NOT EXISTS pending_seq_in_tx Nullable(UInt64)"
+    );
+
+    let mut field = metadata.schema.fields[1].clone();
+    field.nullable = true;
+    let query = get_alter_table_query("users", &[SchemaChange::ModifyColumn(field)], None);
+    assert_eq!(
+        query,
+        "ALTER TABLE users MODIFY COLUMN txn_id Nullable(UInt64)"
+    );
+}
+
+#[test]
+fn test_schema_changes() {
+    let column = |name: &str, type_: &str| ClickhouseSchemaColumn {
+        name: name.to_string(),
+        type_: type_.to_string(),
+    };
+    let mut schema = _get_dozer_schema();
+    schema.fields.push(FieldDefinition {
+        name: "email".to_string(),
+        typ: FieldType::String,
+        nullable: true,
+        source: Default::default(),
+        description: None,
+    });
+    let columns = vec![column("id", "UInt32"), column("data", "String")];
+
+    assert!(matches!(
+        get_schema_changes(&columns, &schema, false),
+        Err(ClickhouseSinkError::ColumnNotFound(name)) if name == "email"
+    ));
+    assert_eq!(
+        get_schema_changes(&columns, &schema, true).unwrap(),
+        vec![
+            SchemaChange::ModifyColumn(schema.fields[0].clone()),
+            SchemaChange::AddColumn(schema.fields[2].clone()),
+        ]
+    );
+
+    // New columns that aren't nullable can't be added to existing rows.
+    schema.fields[2].nullable = false;
+    assert!(get_schema_changes(&columns, &schema, true).is_err());
+
+    // Narrowing is never done.
+    let columns = vec![column("id", "UInt64"), column("data", "Nullable(String)")];
+    assert!(matches!(
+        get_schema_changes(&columns, &_get_dozer_schema(), true),
+        Err(ClickhouseSinkError::ColumnTypeMismatch(name, _, _)) if name == "data"
+    ));
+}
+
+#[test]
+fn test_is_widening() {
+    assert!(is_widening("Int32", "Int64"));
+    assert!(is_widening("UInt32", "Int64"));
+    assert!(is_widening("Float32", "Nullable(Float64)"));
+    assert!(is_widening("String", "Nullable(String)"));
+    assert!(is_widening("Decimal(10, 2)", "Decimal(12, 4)"));
+    assert!(is_widening("FixedString(3)", "String"));
+    assert!(!is_widening("Int64", "Int32"));
+    assert!(!is_widening("Int64", "UInt64"));
+    assert!(!is_widening("Nullable(Int32)", "Int64"));
+    assert!(!is_widening("Decimal(10, 2)", "Decimal(10, 4)"));
+}
+
+#[test]
+fn test_map_list_and_struct_types() {
+    let field = |name: &str, typ: FieldType, nullable: bool| {
+        FieldDefinition::new(name.to_string(), typ, nullable, Default::default())
+    };
+    assert_eq!(
+        map_field_to_type(&field(
+            "tags",
+            FieldType::List(Box::new(FieldType::String)),
+            true
+        )),
+        "Array(Nullable(String))"
+    );
+    assert_eq!(
+        map_field_to_type(&field(
+            "address",
+            FieldType::Struct(vec![
+                field("city", FieldType::String, false),
+                field("zip", FieldType::Int, true),
+                field("lines", FieldType::List(Box::new(FieldType::Binary)), false),
+            ]),
+            false
+        )),
+        "Tuple(city String, zip Nullable(Int64), lines Array(Array(UInt8)))"
+    );
+}
+
+#[test]
+fn test_map_decimal_uuid_time_and_enum_types() {
+    let field =
+        |typ: FieldType| FieldDefinition::new("column".to_string(), typ, false, Default::default());
+    assert_eq!(
+        map_field_to_type(&field(FieldType::Decimal(Some(DecimalType::new(12, 3))))),
+        "Decimal(12, 3)"
+    );
+    assert_eq!(
+        map_field_to_type(&field(FieldType::Decimal(Some(DecimalType::new(30, 3))))),
+        "Decimal(10, 4)"
+    );
+    assert_eq!(map_field_to_type(&field(FieldType::Uuid)), "String");
+    assert_eq!(map_field_to_type(&field(FieldType::Time)), "String");
+    assert_eq!(
+        map_field_to_type(&field(FieldType::Enum(vec!["a".to_string()]))),
+        "String"
+    );
+}
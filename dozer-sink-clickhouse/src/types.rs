@@ -8,7 +8,7 @@ use dozer_types::chrono::{DateTime, FixedOffset, Offset, TimeZone};
 use dozer_types::ordered_float::OrderedFloat;
 use dozer_types::rust_decimal::{self};
 use dozer_types::serde_json;
-use dozer_types::types::{Field, FieldDefinition, FieldType};
+use dozer_types::types::{DecimalType, Field, FieldDefinition, FieldType, TIME_FORMAT};
 use either::Either;
 
 use clickhouse_rs::types::{FromSql, Query, Value, ValueRef};
@@ -83,6 +83,16 @@ pub fn map_value_wrapper_to_field(
                 clickhouse_rs::types::Value::String(_) => Ok(Field::String(value.to_string())),
                 _ => Err(QueryError::CustomError("Invalid String value".to_string())),
             },
+            FieldType::Uuid | FieldType::Time | FieldType::Enum(_) => match value {
+                clickhouse_rs::types::Value::String(_) => {
+                    Field::from_str(&value.to_string(), field.typ.clone(), false)
+                        .map_err(|e| QueryError::CustomError(e.to_string()))
+                }
+                _ => Err(QueryError::CustomError(format!(
+                    "Invalid {} value",
+                    field.typ
+                ))),
+            },
             FieldType::Binary => match value {
                 clickhouse_rs::types::Value::String(val) => {
                     let val = (*val).clone();
@@ -90,7 +100,7 @@ pub fn map_value_wrapper_to_field(
                 }
                 _ => Err(QueryError::CustomError("Invalid Binary value".to_string())),
             },
-            FieldType::Decimal => match value {
+            FieldType::Decimal(_) => match value {
                 clickhouse_rs::types::Value::Decimal(v) => Ok(Field::Decimal(
                     rust_decimal::Decimal::new(v.internal(), v.scale() as u32),
                 )),
@@ -201,12 +211,12 @@ fn add_last_column_to_block(
     block: Block<clickhouse_rs::Simple>,
     name: &str,
     rows: &mut [Vec<Field>],
-    field_type: FieldType,
+    field_type: &FieldType,
     nullable: bool,
 ) -> Result<Block<clickhouse_rs::Simple>, QueryError> {
     let make_error = || QueryError::TypeMismatch {
         field_name: name.to_string(),
-        field_type,
+        field_type: field_type.clone(),
     };
 
     macro_rules! trivial_mapper {
@@ -238,9 +248,24 @@ fn add_last_column_to_block(
         }),
         FieldType::String => add_last_column.call(trivial_mapper!(Field::String)),
         FieldType::Text => add_last_column.call(trivial_mapper!(Field::Text)),
+        FieldType::Enum(_) => add_last_column.call(trivial_mapper!(Field::String)),
+        FieldType::Uuid => add_last_column.call(|field| match field {
+            Field::Uuid(value) => Ok(value.to_string()),
+            _ => Err(make_error()),
+        }),
+        FieldType::Time => add_last_column.call(|field| match field {
+            Field::Time(value) => Ok(value.format(TIME_FORMAT).to_string()),
+            _ => Err(make_error()),
+        }),
         FieldType::Binary => add_last_column.call(trivial_mapper!(Field::Binary)),
-        FieldType::Decimal => add_last_column.call(|field| match field {
-            Field::Decimal(value) => {
+        FieldType::Decimal(decimal_type) => add_last_column.call(|field| match field {
+            Field::Decimal(mut value) => {
+                // Columns of decimals with a known precision and scale are created with them.
+                if let Some(DecimalType { precision, scale }) = decimal_type {
+                    if *precision <= 18 {
+                        value.rescale(*scale as u32);
+                    }
+                }
                 // This is hardcoded in `clickhouse-rs`.
                 if value.scale() > 18 {
                     return Err(QueryError::DecimalOverflow);
@@ -265,7 +290,7 @@ fn add_last_column_to_block(
             Field::Json(value) => Ok(dozer_types::json_types::json_to_bytes(&value)),
             _ => Err(make_error()),
         }),
-        other => Err(QueryError::UnsupportedFieldType(other)),
+        other => Err(QueryError::UnsupportedFieldType(other.clone())),
     }
 }
 
@@ -275,22 +300,78 @@ pub async fn insert_multi(
     fields: &[FieldDefinition],
     mut rows: Vec<Vec<Field>>,
     query_id: Option<String>,
+    deduplication_token: Option<String>,
 ) -> Result<(), QueryError> {
     let mut block = Block::<clickhouse_rs::Simple>::new();
     for field in fields.iter().rev() {
-        block = add_last_column_to_block(block, &field.name, &mut rows, field.typ, field.nullable)?;
+        block =
+            add_last_column_to_block(block, &field.name, &mut rows, &field.typ, field.nullable)?;
     }
 
     let query_id = query_id.unwrap_or("".to_string());
 
     let table = Query::new(table_name).id(query_id);
 
-    // Insert the block into the table
-    client.insert(table, block).await?;
+    let Some(token) = deduplication_token else {
+        // Insert the block into the table
+        client.insert(table, block).await?;
+        return Ok(());
+    };
+
+    // The token is a session setting, so it's reset before the connection goes back to the pool.
+    client
+        .execute(format!(
+            "SET insert_deduplication_token = {}",
+            field_to_literal(&Field::String(token), &FieldType::String)?
+        ))
+        .await?;
+    let result = client.insert(table, block).await;
+    client
+        .execute("SET insert_deduplication_token = ''")
+        .await?;
+    result?;
 
     Ok(())
 }
 
+/// Renders a field as a ClickHouse SQL literal, for use in `WHERE` clauses.
+pub fn field_to_literal(field: &Field, field_type: &FieldType) -> Result<String, QueryError> {
+    let quote = |value: &str| format!("'{}'", value.replace('\\', "\\\\").replace('\'', "\\'"));
+    Ok(match field {
+        Field::Null => "NULL".to_string(),
+        Field::UInt(value) => value.to_string(),
+        Field::U128(value) => value.to_string(),
+        Field::Int(value) => value.to_string(),
+        Field::Int8(value) => value.to_string(),
+        Field::I128(value) => value.to_string(),
+        Field::Float(value) => value.0.to_string(),
+        Field::Boolean(value) => value.to_string(),
+        Field::String(value) | Field::Text(value) => quote(value),
+        Field::Binary(value) => format!(
+            "[{}]",
+            value
+                .iter()
+                .map(u8::to_string)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        Field::Decimal(value) => value.to_string(),
+        Field::Timestamp(value) => format!(
+            "toDateTime64({}, 3, 'UTC')",
+            quote(
+                &value
+                    .with_timezone(&UTC)
+                    .format("%Y-%m-%d %H:%M:%S%.3f")
+                    .to_string()
+            )
+        ),
+        Field::Date(value) => format!("toDate({})", quote(&value.format("%Y-%m-%d").to_string())),
+        Field::Uuid(value) => quote(&value.to_string()),
+        Field::Time(value) => quote(&value.format(TIME_FORMAT).to_string()),
+        _ => return Err(QueryError::UnsupportedFieldType(field_type.clone())),
+    })
+}
+
 mod tests {
     #[test]
     fn test_add_last_column_to_block() {
@@ -303,10 +384,17 @@ mod tests {
             Field::Decimal(dozer_decimal),
         ]];
         let mut block = Block::<clickhouse_rs::Simple>::new();
-        block = add_last_column_to_block(block, "decimal", &mut rows, FieldType::Decimal, false)
-            .unwrap();
-        block = add_last_column_to_block(block, "text", &mut rows, FieldType::Text, false).unwrap();
-        block = add_last_column_to_block(block, "null", &mut rows, FieldType::UInt, true).unwrap();
+        block = add_last_column_to_block(
+            block,
+            "decimal",
+            &mut rows,
+            &FieldType::Decimal(None),
+            false,
+        )
+        .unwrap();
+        block =
+            add_last_column_to_block(block, "text", &mut rows, &FieldType::Text, false).unwrap();
+        block = add_last_column_to_block(block, "null", &mut rows, &FieldType::UInt, true).unwrap();
         let decimal = block
             .get_column("decimal")
             .unwrap()
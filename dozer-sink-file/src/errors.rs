@@ -0,0 +1,34 @@
+use dozer_types::{
+    arrow::error::ArrowError,
+    arrow_types::errors::FromArrowError,
+    errors::types::{DeserializationError, SerializationError},
+    thiserror::{self, Error},
+};
+use parquet::errors::ParquetError;
+
+#[derive(Error, Debug)]
+pub enum FileSinkError {
+    #[error("Object store error: {0}")]
+    ObjectStore(#[from] object_store::Error),
+
+    #[error("Failed to create directory: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Arrow error: {0}")]
+    Arrow(#[from] ArrowError),
+
+    #[error("Parquet error: {0}")]
+    Parquet(#[from] ParquetError),
+
+    #[error("Failed to read snapshot file: {0}")]
+    Snapshot(#[from] FromArrowError),
+
+    #[error("Table {0} has no primary key, which snapshot mode needs")]
+    NoPrimaryKey(String),
+
+    #[error("Failed to serialize replication metadata: {0}")]
+    Serialization(#[from] SerializationError),
+
+    #[error("Invalid replication metadata: {0}")]
+    Metadata(#[from] DeserializationError),
+}
@@ -0,0 +1,9 @@
+pub mod errors;
+pub mod metadata;
+mod sink;
+pub mod storage;
+pub mod writer;
+pub use sink::FileSinkFactory;
+pub use storage::FileStorage;
+#[cfg(test)]
+mod tests;
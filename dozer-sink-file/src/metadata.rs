@@ -0,0 +1,45 @@
+use bincode::config;
+use dozer_types::bytes::Bytes;
+use dozer_types::errors::types::{DeserializationError, SerializationError};
+use dozer_types::node::OpIdentifier;
+use object_store::path::Path;
+use object_store::ObjectStore;
+
+use crate::errors::FileSinkError;
+
+/// Object holding the sink's metadata, next to the files it writes.
+pub const REPLICA_METADATA_OBJECT: &str = "__dozer_replication_metadata";
+
+/// In snapshot mode, the rows are restored from the snapshot file on restart.
+#[derive(Debug, Clone, Default, PartialEq, Eq, bincode::Encode, bincode::Decode)]
+pub struct ReplicationMetadata {
+    pub source_state: Option<Vec<u8>>,
+    /// The last operation that is in a written file.
+    pub op_id: Option<OpIdentifier>,
+}
+
+impl ReplicationMetadata {
+    pub async fn read(store: &dyn ObjectStore, path: &Path) -> Result<Self, FileSinkError> {
+        let bytes = match store.get(path).await {
+            Ok(result) => result.bytes().await?,
+            Err(object_store::Error::NotFound { .. }) => return Ok(Self::default()),
+            Err(e) => return Err(e.into()),
+        };
+        Self::decode_from(&bytes)
+    }
+
+    pub async fn write(&self, store: &dyn ObjectStore, path: &Path) -> Result<(), FileSinkError> {
+        store.put(path, Bytes::from(self.encode_to_vec()?)).await?;
+        Ok(())
+    }
+
+    pub(crate) fn encode_to_vec(&self) -> Result<Vec<u8>, FileSinkError> {
+        Ok(bincode::encode_to_vec(self, config::legacy()).map_err(SerializationError::from)?)
+    }
+
+    pub(crate) fn decode_from(bytes: &[u8]) -> Result<Self, FileSinkError> {
+        let (metadata, _) = bincode::decode_from_slice(bytes, config::legacy())
+            .map_err(DeserializationError::from)?;
+        Ok(metadata)
+    }
+}
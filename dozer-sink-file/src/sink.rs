@@ -0,0 +1,439 @@
+use dozer_core::epoch::Epoch;
+use dozer_core::event::EventHub;
+use dozer_core::node::{PortHandle, Sink, SinkFactory};
+use dozer_core::tokio::runtime::Runtime;
+use dozer_core::DEFAULT_PORT_HANDLE;
+use dozer_types::arrow::datatypes::SchemaRef;
+use dozer_types::arrow_types::from_arrow::map_record_batch_to_dozer_records;
+use dozer_types::arrow_types::to_arrow::{map_records_to_arrow, map_to_arrow_schema};
+use dozer_types::bytes::Bytes;
+use dozer_types::chrono::Utc;
+use dozer_types::errors::internal::BoxedError;
+use dozer_types::log::debug;
+use dozer_types::models::sink::{FileSinkConfig, FileSinkMode};
+use dozer_types::node::OpIdentifier;
+use dozer_types::tonic::async_trait;
+use dozer_types::types::{
+    Field, FieldDefinition, FieldType, Operation, Record, Schema, SourceDefinition, TableOperation,
+};
+use object_store::path::Path;
+use object_store::ObjectStore;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::errors::FileSinkError;
+use crate::metadata::{ReplicationMetadata, REPLICA_METADATA_OBJECT};
+use crate::storage::FileStorage;
+use crate::writer::{file_extension, read_file, FileWriter};
+
+/// Rows are encoded into the file in batches of this size.
+const BATCH_SIZE: usize = 1000;
+/// Column holding the kind of change in CDC mode.
+pub const OP_COLUMN: &str = "__dozer_op";
+
+#[derive(Debug)]
+pub struct FileSinkFactory {
+    runtime: Arc<Runtime>,
+    config: FileSinkConfig,
+    storage: FileStorage,
+}
+
+impl FileSinkFactory {
+    pub fn new(config: FileSinkConfig, storage: FileStorage, runtime: Arc<Runtime>) -> Self {
+        Self {
+            runtime,
+            config,
+            storage,
+        }
+    }
+}
+
+#[async_trait]
+impl SinkFactory for FileSinkFactory {
+    fn type_name(&self) -> String {
+        "file".to_string()
+    }
+
+    fn get_input_ports(&self) -> Vec<PortHandle> {
+        vec![DEFAULT_PORT_HANDLE]
+    }
+
+    fn get_input_port_name(&self, _port: &PortHandle) -> String {
+        self.config.source_table_name.clone()
+    }
+
+    fn prepare(&self, input_schemas: HashMap<PortHandle, Schema>) -> Result<(), BoxedError> {
+        debug_assert!(input_schemas.len() == 1);
+        if self.config.mode == FileSinkMode::Snapshot
+            && input_schemas[&DEFAULT_PORT_HANDLE].primary_index.is_empty()
+        {
+            return Err(FileSinkError::NoPrimaryKey(self.config.source_table_name.clone()).into());
+        }
+        Ok(())
+    }
+
+    async fn build(
+        &self,
+        mut input_schemas: HashMap<PortHandle, Schema>,
+        _event_hub: EventHub,
+    ) -> Result<Box<dyn Sink>, BoxedError> {
+        let schema = input_schemas.remove(&DEFAULT_PORT_HANDLE).unwrap();
+        let store = self.storage.object_store()?;
+        let metadata_path = object_path(&self.config.path, REPLICA_METADATA_OBJECT);
+        let metadata = ReplicationMetadata::read(store.as_ref(), &metadata_path).await?;
+        let rows = read_snapshot(store.as_ref(), &self.config, &schema, &metadata).await?;
+        Ok(Box::new(FileSink::new(
+            store,
+            self.runtime.clone(),
+            self.config.clone(),
+            schema,
+            metadata,
+            rows,
+        )?))
+    }
+}
+
+fn object_path(folder: &str, name: &str) -> Path {
+    Path::from(format!("{folder}/{name}"))
+}
+
+fn snapshot_file_name(config: &FileSinkConfig) -> String {
+    format!("snapshot.{}", file_extension(config.format))
+}
+
+/// Reads back the rows of the last snapshot file, which a resuming snapshot mode sink starts from.
+pub(crate) async fn read_snapshot(
+    store: &dyn ObjectStore,
+    config: &FileSinkConfig,
+    schema: &Schema,
+    metadata: &ReplicationMetadata,
+) -> Result<Vec<Record>, FileSinkError> {
+    // Without an op id, the source restarts from scratch, and any snapshot file is stale.
+    if config.mode != FileSinkMode::Snapshot || metadata.op_id.is_none() {
+        return Ok(vec![]);
+    }
+    let path = object_path(&config.path, &snapshot_file_name(config));
+    let bytes = match store.get(&path).await {
+        Ok(result) => result.bytes().await?,
+        Err(object_store::Error::NotFound { .. }) => return Ok(vec![]),
+        Err(e) => return Err(e.into()),
+    };
+    let schema = output_schema(schema, config.mode);
+    let arrow_schema = Arc::new(map_to_arrow_schema(&schema)?);
+    let mut rows = vec![];
+    for batch in read_file(config.format, arrow_schema.clone(), bytes)? {
+        // Parquet batches don't keep the schema metadata, which has the Dozer types of the columns.
+        let batch = batch.with_schema(arrow_schema.clone())?;
+        rows.extend(map_record_batch_to_dozer_records(batch, &schema)?);
+    }
+    Ok(rows)
+}
+
+/// Schema of the written files. CDC files get the operation column.
+pub(crate) fn output_schema(schema: &Schema, mode: FileSinkMode) -> Schema {
+    let mut schema = schema.clone();
+    if mode == FileSinkMode::Cdc {
+        schema.field(
+            FieldDefinition {
+                name: OP_COLUMN.to_string(),
+                typ: FieldType::String,
+                nullable: false,
+                source: SourceDefinition::Dynamic,
+                description: None,
+            },
+            false,
+        );
+    }
+    schema
+}
+
+/// Writes the table to files.
+///
+/// In CDC mode every change is appended to the current file, which is uploaded when it rolls.
+/// In snapshot mode the latest state of every row is kept, and rewritten to one file on commit.
+/// The metadata only holds the source state and op id, so committing without changes is cheap.
+pub(crate) struct FileSink {
+    store: Arc<dyn ObjectStore>,
+    runtime: Arc<Runtime>,
+    config: FileSinkConfig,
+    /// Primary key of the input rows.
+    primary_index: Vec<usize>,
+    output_schema: Schema,
+    arrow_schema: SchemaRef,
+    /// CDC rows that are not encoded into the current file yet.
+    pending: Vec<Record>,
+    /// The current CDC file, and when it was started.
+    file: Option<(FileWriter, Instant)>,
+    /// Snapshot mode's latest rows, by primary key. Empty in CDC mode.
+    rows: HashMap<Vec<Field>, Vec<Field>>,
+    /// Whether there are changes since the last snapshot.
+    dirty: bool,
+    /// The last processed operation. It's only persisted once it's written to a file.
+    op_id: Option<OpIdentifier>,
+    metadata: ReplicationMetadata,
+    metadata_path: Path,
+    /// Distinguishes the files written in the same millisecond.
+    file_index: u64,
+}
+
+impl Debug for FileSink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FileSink")
+            .field("config", &self.config)
+            .field("output_schema", &self.output_schema)
+            .finish()
+    }
+}
+
+impl FileSink {
+    pub(crate) fn new(
+        store: Arc<dyn ObjectStore>,
+        runtime: Arc<Runtime>,
+        config: FileSinkConfig,
+        schema: Schema,
+        metadata: ReplicationMetadata,
+        rows: Vec<Record>,
+    ) -> Result<Self, FileSinkError> {
+        let output_schema = output_schema(&schema, config.mode);
+        let arrow_schema = Arc::new(map_to_arrow_schema(&output_schema)?);
+        let rows = rows
+            .into_iter()
+            .map(|record| {
+                let key = schema
+                    .primary_index
+                    .iter()
+                    .map(|index| record.values[*index].clone())
+                    .collect();
+                (key, record.values)
+            })
+            .collect();
+        Ok(Self {
+            store,
+            runtime,
+            metadata_path: object_path(&config.path, REPLICA_METADATA_OBJECT),
+            config,
+            primary_index: schema.primary_index,
+            output_schema,
+            arrow_schema,
+            pending: vec![],
+            file: None,
+            rows,
+            dirty: false,
+            op_id: metadata.op_id,
+            metadata,
+            file_index: 0,
+        })
+    }
+
+    fn key(&self, values: &[Field]) -> Vec<Field> {
+        self.primary_index
+            .iter()
+            .map(|index| values[*index].clone())
+            .collect()
+    }
+
+    fn push_change(&mut self, record: Record, op: &str) -> Result<(), FileSinkError> {
+        let mut values = record.values;
+        values.push(Field::String(op.to_string()));
+        self.pending.push(Record::new(values));
+        if self.pending.len() >= BATCH_SIZE {
+            self.write_pending()?;
+            if self.should_roll(false) {
+                self.roll()?;
+            }
+        }
+        Ok(())
+    }
+
+    fn write_pending(&mut self) -> Result<(), FileSinkError> {
+        if self.pending.is_empty() {
+            return Ok(());
+        }
+        let batch = map_records_to_arrow(std::mem::take(&mut self.pending), &self.output_schema)?;
+        if self.file.is_none() {
+            let writer = FileWriter::new(self.config.format, self.arrow_schema.clone())?;
+            self.file = Some((writer, Instant::now()));
+        }
+        let (writer, _) = self.file.as_mut().unwrap();
+        writer.write(&batch)?;
+        Ok(())
+    }
+
+    fn should_roll(&self, committing: bool) -> bool {
+        let Some((writer, started)) = &self.file else {
+            return false;
+        };
+        let max_size = self.config.max_file_size_bytes;
+        let max_age = self.config.max_file_age_secs;
+        max_size.is_some_and(|max_size| writer.size() as u64 >= max_size)
+            || max_age.is_some_and(|max_age| started.elapsed() >= Duration::from_secs(max_age))
+            || (committing
+                && (self.config.roll_on_commit || (max_size.is_none() && max_age.is_none())))
+    }
+
+    /// Uploads the current CDC file, with the metadata of the operations in it.
+    fn roll(&mut self) -> Result<(), FileSinkError> {
+        let Some((writer, _)) = self.file.take() else {
+            return Ok(());
+        };
+        let num_rows = writer.num_rows();
+        let name = format!(
+            "part-{}-{:05}.{}",
+            Utc::now().timestamp_millis(),
+            self.file_index,
+            file_extension(self.config.format)
+        );
+        self.file_index += 1;
+        debug!("[Sink] Writing {num_rows} rows to {name}");
+        self.put(&name, writer.finish()?)?;
+        self.metadata.op_id = self.op_id;
+        self.write_metadata()
+    }
+
+    /// Rewrites the snapshot file with the latest rows. It's also what the rows are restored from on restart.
+    fn write_snapshot(&mut self) -> Result<(), FileSinkError> {
+        let mut writer = FileWriter::new(self.config.format, self.arrow_schema.clone())?;
+        let rows = self.rows.values().collect::<Vec<_>>();
+        for chunk in rows.chunks(BATCH_SIZE) {
+            let records = chunk
+                .iter()
+                .map(|values| Record::new((*values).clone()))
+                .collect();
+            writer.write(&map_records_to_arrow(records, &self.output_schema)?)?;
+        }
+        let name = snapshot_file_name(&self.config);
+        debug!("[Sink] Writing {} rows to {name}", rows.len());
+        self.put(&name, writer.finish()?)?;
+        self.metadata.op_id = self.op_id;
+        self.dirty = false;
+        self.write_metadata()
+    }
+
+    fn put(&self, name: &str, bytes: Vec<u8>) -> Result<(), FileSinkError> {
+        let path = object_path(&self.config.path, name);
+        self.runtime
+            .block_on(self.store.put(&path, Bytes::from(bytes)))?;
+        Ok(())
+    }
+
+    fn write_metadata(&self) -> Result<(), FileSinkError> {
+        self.runtime.block_on(
+            self.metadata
+                .write(self.store.as_ref(), &self.metadata_path),
+        )
+    }
+
+    fn commit_cdc(&mut self) -> Result<(), FileSinkError> {
+        self.write_pending()?;
+        if self.should_roll(true) {
+            self.roll()?;
+        }
+        // Nothing is left unwritten, so the latest operation can be persisted even without data.
+        if self.file.is_none() && self.metadata.op_id != self.op_id {
+            self.metadata.op_id = self.op_id;
+            self.write_metadata()?;
+        }
+        Ok(())
+    }
+
+    fn commit_snapshot(&mut self) -> Result<(), FileSinkError> {
+        if self.dirty {
+            self.write_snapshot()?;
+        } else if self.metadata.op_id != self.op_id {
+            self.metadata.op_id = self.op_id;
+            self.write_metadata()?;
+        }
+        Ok(())
+    }
+}
+
+impl Sink for FileSink {
+    fn commit(&mut self, _epoch_details: &Epoch) -> Result<(), BoxedError> {
+        match self.config.mode {
+            FileSinkMode::Cdc => self.commit_cdc()?,
+            FileSinkMode::Snapshot => self.commit_snapshot()?,
+        }
+        Ok(())
+    }
+
+    fn process(&mut self, op: TableOperation) -> Result<(), BoxedError> {
+        if let Some(id) = op.id {
+            self.op_id = Some(id);
+        }
+        match self.config.mode {
+            FileSinkMode::Cdc => match op.op {
+                Operation::Insert { new } => self.push_change(new, "insert")?,
+                Operation::Delete { old } => self.push_change(old, "delete")?,
+                Operation::Update { old, new } => {
+                    // A changed primary key is a different row.
+                    if self.key(&old.values) != self.key(&new.values) {
+                        self.push_change(old, "delete")?;
+                        self.push_change(new, "insert")?;
+                    } else {
+                        self.push_change(new, "update")?;
+                    }
+                }
+                Operation::BatchInsert { new } => {
+                    for record in new {
+                        self.push_change(record, "insert")?;
+                    }
+                }
+            },
+            FileSinkMode::Snapshot => {
+                match op.op {
+                    Operation::Insert { new } => {
+                        self.rows.insert(self.key(&new.values), new.values);
+                    }
+                    Operation::Delete { old } => {
+                        self.rows.remove(&self.key(&old.values));
+                    }
+                    Operation::Update { old, new } => {
+                        self.rows.remove(&self.key(&old.values));
+                        self.rows.insert(self.key(&new.values), new.values);
+                    }
+                    Operation::BatchInsert { new } => {
+                        for record in new {
+                            self.rows.insert(self.key(&record.values), record.values);
+                        }
+                    }
+                }
+                self.dirty = true;
+            }
+        }
+        Ok(())
+    }
+
+    fn on_source_snapshotting_started(
+        &mut self,
+        _connection_name: String,
+    ) -> Result<(), BoxedError> {
+        Ok(())
+    }
+
+    fn on_source_snapshotting_done(
+        &mut self,
+        _connection_name: String,
+        id: Option<OpIdentifier>,
+    ) -> Result<(), BoxedError> {
+        if id.is_some() {
+            self.op_id = id;
+        }
+        Ok(())
+    }
+
+    fn set_source_state(&mut self, source_state: &[u8]) -> Result<(), BoxedError> {
+        self.metadata.source_state = Some(source_state.to_vec());
+        self.write_metadata()?;
+        Ok(())
+    }
+
+    fn get_source_state(&mut self) -> Result<Option<Vec<u8>>, BoxedError> {
+        Ok(self.metadata.source_state.clone())
+    }
+
+    fn get_latest_op_id(&mut self) -> Result<Option<OpIdentifier>, BoxedError> {
+        Ok(self.metadata.op_id)
+    }
+}
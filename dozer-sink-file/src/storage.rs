@@ -0,0 +1,34 @@
+use std::sync::Arc;
+
+use dozer_types::models::ingestion_types::{LocalDetails, S3Details};
+use object_store::aws::AmazonS3Builder;
+use object_store::local::LocalFileSystem;
+use object_store::ObjectStore;
+
+use crate::errors::FileSinkError;
+
+/// Where the files are written, from a `LocalStorage` or `S3Storage` connection.
+#[derive(Debug, Clone)]
+pub enum FileStorage {
+    Local(LocalDetails),
+    S3(S3Details),
+}
+
+impl FileStorage {
+    pub fn object_store(&self) -> Result<Arc<dyn ObjectStore>, FileSinkError> {
+        Ok(match self {
+            FileStorage::Local(details) => {
+                std::fs::create_dir_all(&details.path)?;
+                Arc::new(LocalFileSystem::new_with_prefix(&details.path)?)
+            }
+            FileStorage::S3(details) => Arc::new(
+                AmazonS3Builder::new()
+                    .with_bucket_name(&details.bucket_name)
+                    .with_region(&details.region)
+                    .with_access_key_id(&details.access_key_id)
+                    .with_secret_access_key(&details.secret_access_key)
+                    .build()?,
+            ),
+        })
+    }
+}
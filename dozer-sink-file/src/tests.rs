@@ -0,0 +1,278 @@
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use dozer_core::epoch::Epoch;
+use dozer_core::node::Sink;
+use dozer_core::tokio::runtime::Runtime;
+use dozer_types::arrow_types::to_arrow::{map_records_to_arrow, map_to_arrow_schema};
+use dozer_types::models::ingestion_types::LocalDetails;
+use dozer_types::models::sink::{FileSinkConfig, FileSinkFormat, FileSinkMode};
+use dozer_types::node::OpIdentifier;
+use dozer_types::types::{
+    Field, FieldDefinition, FieldType, Operation, Record, Schema, SourceDefinition, TableOperation,
+};
+use tempfile::TempDir;
+
+use crate::metadata::{ReplicationMetadata, REPLICA_METADATA_OBJECT};
+use crate::sink::{output_schema, read_snapshot, FileSink, OP_COLUMN};
+use crate::storage::FileStorage;
+use crate::writer::FileWriter;
+
+fn get_dozer_schema() -> Schema {
+    let field = |name: &str, typ| FieldDefinition {
+        name: name.to_string(),
+        typ,
+        nullable: true,
+        source: SourceDefinition::Dynamic,
+        description: None,
+    };
+    Schema {
+        fields: vec![
+            field("id", FieldType::Int),
+            field("name", FieldType::String),
+        ],
+        primary_index: vec![0],
+    }
+}
+
+fn get_sink_config(format: FileSinkFormat, mode: FileSinkMode) -> FileSinkConfig {
+    FileSinkConfig {
+        connection: "local".to_string(),
+        source_table_name: "users".to_string(),
+        path: "users".to_string(),
+        format,
+        mode,
+        max_file_size_bytes: None,
+        max_file_age_secs: None,
+        roll_on_commit: false,
+    }
+}
+
+fn record(id: i64, name: &str) -> Record {
+    Record::new(vec![Field::Int(id), Field::String(name.to_string())])
+}
+
+fn op(id: u64, op: Operation) -> TableOperation {
+    TableOperation {
+        id: Some(OpIdentifier::new(id, 0)),
+        op,
+        port: 0,
+    }
+}
+
+fn epoch() -> Epoch {
+    Epoch::new(0, Default::default(), SystemTime::now())
+}
+
+fn create_sink(dir: &TempDir, runtime: &Arc<Runtime>, config: FileSinkConfig) -> FileSink {
+    let storage = FileStorage::Local(LocalDetails {
+        path: dir.path().to_str().unwrap().to_string(),
+    });
+    let store = storage.object_store().unwrap();
+    let metadata = runtime
+        .block_on(ReplicationMetadata::read(
+            store.as_ref(),
+            &format!("{}/{REPLICA_METADATA_OBJECT}", config.path).into(),
+        ))
+        .unwrap();
+    let rows = runtime
+        .block_on(read_snapshot(
+            store.as_ref(),
+            &config,
+            &get_dozer_schema(),
+            &metadata,
+        ))
+        .unwrap();
+    FileSink::new(
+        store,
+        runtime.clone(),
+        config,
+        get_dozer_schema(),
+        metadata,
+        rows,
+    )
+    .unwrap()
+}
+
+fn read_files(dir: &TempDir, extension: &str) -> Vec<String> {
+    let mut paths = std::fs::read_dir(dir.path().join("users"))
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == extension))
+        .collect::<Vec<_>>();
+    paths.sort();
+    paths
+        .into_iter()
+        .map(|path| std::fs::read_to_string(path).unwrap())
+        .collect()
+}
+
+#[test]
+fn test_csv_writer() {
+    let schema = get_dozer_schema();
+    let arrow_schema = Arc::new(map_to_arrow_schema(&schema).unwrap());
+    let mut writer = FileWriter::new(FileSinkFormat::Csv, arrow_schema).unwrap();
+    for name in ["alice", "bob"] {
+        let batch = map_records_to_arrow(vec![record(1, name)], &schema).unwrap();
+        writer.write(&batch).unwrap();
+    }
+    assert_eq!(writer.num_rows(), 2);
+    let csv = String::from_utf8(writer.finish().unwrap()).unwrap();
+    // The header is only written once.
+    assert_eq!(csv, "id,name\n1,alice\n1,bob\n");
+}
+
+#[test]
+fn test_cdc_files() {
+    let dir = TempDir::new().unwrap();
+    let runtime = Arc::new(Runtime::new().unwrap());
+    let config = get_sink_config(FileSinkFormat::Ndjson, FileSinkMode::Cdc);
+    let mut sink = create_sink(&dir, &runtime, config.clone());
+
+    sink.process(op(
+        1,
+        Operation::Insert {
+            new: record(1, "alice"),
+        },
+    ))
+    .unwrap();
+    sink.process(op(
+        2,
+        Operation::Update {
+            old: record(1, "alice"),
+            new: record(1, "bob"),
+        },
+    ))
+    .unwrap();
+    // Nothing is written before the commit.
+    assert!(read_files(&dir, "ndjson").is_empty());
+    sink.commit(&epoch()).unwrap();
+    sink.process(op(
+        3,
+        Operation::Delete {
+            old: record(1, "bob"),
+        },
+    ))
+    .unwrap();
+    sink.commit(&epoch()).unwrap();
+
+    let files = read_files(&dir, "ndjson");
+    assert_eq!(files.len(), 2);
+    assert_eq!(
+        files[0],
+        format!(
+            "{{\"id\":1,\"name\":\"alice\",\"{OP_COLUMN}\":\"insert\"}}\n\
+            {{\"id\":1,\"name\":\"bob\",\"{OP_COLUMN}\":\"update\"}}\n"
+        )
+    );
+    assert_eq!(
+        files[1],
+        format!("{{\"id\":1,\"name\":\"bob\",\"{OP_COLUMN}\":\"delete\"}}\n")
+    );
+
+    let mut restarted = create_sink(&dir, &runtime, config);
+    assert_eq!(
+        restarted.get_latest_op_id().unwrap(),
+        Some(OpIdentifier::new(3, 0))
+    );
+}
+
+#[test]
+fn test_snapshot_file() {
+    let dir = TempDir::new().unwrap();
+    let runtime = Arc::new(Runtime::new().unwrap());
+    let config = get_sink_config(FileSinkFormat::Csv, FileSinkMode::Snapshot);
+    let mut sink = create_sink(&dir, &runtime, config.clone());
+
+    sink.process(op(
+        1,
+        Operation::BatchInsert {
+            new: vec![record(1, "alice"), record(2, "bob")],
+        },
+    ))
+    .unwrap();
+    sink.process(op(
+        2,
+        Operation::Delete {
+            old: record(2, "bob"),
+        },
+    ))
+    .unwrap();
+    sink.commit(&epoch()).unwrap();
+    assert_eq!(read_files(&dir, "csv"), vec!["id,name\n1,alice\n"]);
+
+    // The rows are restored on restart, so the next snapshot still has them.
+    let mut restarted = create_sink(&dir, &runtime, config);
+    assert_eq!(
+        restarted.get_latest_op_id().unwrap(),
+        Some(OpIdentifier::new(2, 0))
+    );
+    restarted
+        .process(op(
+            3,
+            Operation::Insert {
+                new: record(3, "carol"),
+            },
+        ))
+        .unwrap();
+    restarted.commit(&epoch()).unwrap();
+    let files = read_files(&dir, "csv");
+    assert!(files[0] == "id,name\n1,alice\n3,carol\n" || files[0] == "id,name\n3,carol\n1,alice\n");
+}
+
+#[test]
+fn test_snapshot_restored_from_parquet() {
+    let dir = TempDir::new().unwrap();
+    let runtime = Arc::new(Runtime::new().unwrap());
+    let config = get_sink_config(FileSinkFormat::Parquet, FileSinkMode::Snapshot);
+    let mut sink = create_sink(&dir, &runtime, config.clone());
+    sink.process(op(
+        1,
+        Operation::BatchInsert {
+            new: vec![record(1, "alice"), record(2, "bob")],
+        },
+    ))
+    .unwrap();
+    sink.commit(&epoch()).unwrap();
+
+    let mut restarted = create_sink(&dir, &runtime, config.clone());
+    restarted
+        .process(op(
+            2,
+            Operation::Delete {
+                old: record(1, "alice"),
+            },
+        ))
+        .unwrap();
+    restarted.commit(&epoch()).unwrap();
+
+    let store = FileStorage::Local(LocalDetails {
+        path: dir.path().to_str().unwrap().to_string(),
+    })
+    .object_store()
+    .unwrap();
+    let metadata = runtime
+        .block_on(ReplicationMetadata::read(
+            store.as_ref(),
+            &format!("users/{REPLICA_METADATA_OBJECT}").into(),
+        ))
+        .unwrap();
+    let rows = runtime
+        .block_on(read_snapshot(
+            store.as_ref(),
+            &config,
+            &get_dozer_schema(),
+            &metadata,
+        ))
+        .unwrap();
+    assert_eq!(rows, vec![record(2, "bob")]);
+}
+
+#[test]
+fn test_output_schema() {
+    let schema = get_dozer_schema();
+    let cdc = output_schema(&schema, FileSinkMode::Cdc);
+    assert_eq!(cdc.fields.len(), 3);
+    assert_eq!(cdc.fields[2].name, OP_COLUMN);
+    assert_eq!(output_schema(&schema, FileSinkMode::Snapshot), schema);
+}
@@ -0,0 +1,112 @@
+use std::io::{BufReader, Cursor};
+
+use dozer_types::arrow::csv::{ReaderBuilder as CsvReaderBuilder, WriterBuilder};
+use dozer_types::arrow::datatypes::SchemaRef;
+use dozer_types::arrow::json::{LineDelimitedWriter, ReaderBuilder as JsonReaderBuilder};
+use dozer_types::arrow::record_batch::RecordBatch;
+use dozer_types::bytes::Bytes;
+use dozer_types::models::sink::FileSinkFormat;
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use parquet::arrow::ArrowWriter;
+
+use crate::errors::FileSinkError;
+
+pub fn file_extension(format: FileSinkFormat) -> &'static str {
+    match format {
+        FileSinkFormat::Parquet => "parquet",
+        FileSinkFormat::Csv => "csv",
+        FileSinkFormat::Ndjson => "ndjson",
+    }
+}
+
+/// Encodes record batches into a file kept in memory.
+pub struct FileWriter {
+    inner: Inner,
+    num_rows: usize,
+}
+
+enum Inner {
+    Parquet(ArrowWriter<Vec<u8>>),
+    Csv(Vec<u8>),
+    Ndjson(Vec<u8>),
+}
+
+impl FileWriter {
+    pub fn new(format: FileSinkFormat, schema: SchemaRef) -> Result<Self, FileSinkError> {
+        let inner = match format {
+            FileSinkFormat::Parquet => Inner::Parquet(ArrowWriter::try_new(vec![], schema, None)?),
+            FileSinkFormat::Csv => Inner::Csv(vec![]),
+            FileSinkFormat::Ndjson => Inner::Ndjson(vec![]),
+        };
+        Ok(Self { inner, num_rows: 0 })
+    }
+
+    pub fn write(&mut self, batch: &RecordBatch) -> Result<(), FileSinkError> {
+        match &mut self.inner {
+            Inner::Parquet(writer) => writer.write(batch)?,
+            Inner::Csv(buffer) => {
+                // Only the first batch of the file gets the header.
+                let mut writer = WriterBuilder::new()
+                    .with_header(self.num_rows == 0)
+                    .build(std::mem::take(buffer));
+                writer.write(batch)?;
+                *buffer = writer.into_inner();
+            }
+            Inner::Ndjson(buffer) => {
+                let mut writer = LineDelimitedWriter::new(std::mem::take(buffer));
+                writer.write(batch)?;
+                writer.finish()?;
+                *buffer = writer.into_inner();
+            }
+        }
+        self.num_rows += batch.num_rows();
+        Ok(())
+    }
+
+    pub fn num_rows(&self) -> usize {
+        self.num_rows
+    }
+
+    /// Size of the file so far. For Parquet, the row group being built is estimated.
+    pub fn size(&self) -> usize {
+        match &self.inner {
+            Inner::Parquet(writer) => {
+                let flushed = writer
+                    .flushed_row_groups()
+                    .iter()
+                    .map(|row_group| row_group.compressed_size() as usize)
+                    .sum::<usize>();
+                flushed + writer.in_progress_size()
+            }
+            Inner::Csv(buffer) | Inner::Ndjson(buffer) => buffer.len(),
+        }
+    }
+
+    pub fn finish(self) -> Result<Vec<u8>, FileSinkError> {
+        match self.inner {
+            Inner::Parquet(writer) => Ok(writer.into_inner()?),
+            Inner::Csv(buffer) | Inner::Ndjson(buffer) => Ok(buffer),
+        }
+    }
+}
+
+/// Decodes a file written by [`FileWriter`].
+pub fn read_file(
+    format: FileSinkFormat,
+    schema: SchemaRef,
+    bytes: Bytes,
+) -> Result<Vec<RecordBatch>, FileSinkError> {
+    let batches = match format {
+        FileSinkFormat::Parquet => ParquetRecordBatchReaderBuilder::try_new(bytes)?
+            .build()?
+            .collect::<Result<_, _>>()?,
+        FileSinkFormat::Csv => CsvReaderBuilder::new(schema)
+            .with_header(true)
+            .build(Cursor::new(bytes))?
+            .collect::<Result<_, _>>()?,
+        FileSinkFormat::Ndjson => JsonReaderBuilder::new(schema)
+            .build(BufReader::new(Cursor::new(bytes)))?
+            .collect::<Result<_, _>>()?,
+    };
+    Ok(batches)
+}
@@ -0,0 +1,248 @@
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use apache_avro::types::Value as AvroValue;
+use dozer_core::tokio::runtime::Runtime;
+use dozer_types::json_types::{field_to_json_value, json_to_string};
+use dozer_types::serde_json::{json, Value};
+use dozer_types::types::{Field, FieldDefinition, Record, Schema};
+use schema_registry_converter::async_impl::avro::AvroEncoder as RegistryEncoder;
+use schema_registry_converter::async_impl::schema_registry::SrSettings;
+use schema_registry_converter::schema_registry_common::{
+    SchemaType, SubjectNameStrategy, SuppliedSchema,
+};
+
+use crate::debezium::{
+    date_to_days, duration_to_nanos, map_field_type, time_to_nanos, timestamp_to_micros,
+    uint_to_int64, ChangeEvent, ConnectType, POINT,
+};
+use crate::errors::KafkaSinkError;
+
+/// Encodes keys and change events with Avro, in the Confluent wire format.
+/// The schemas are registered under the topic's `-key` and `-value` subjects.
+pub struct AvroEncoder {
+    encoder: RegistryEncoder<'static>,
+    runtime: Arc<Runtime>,
+    fields: Vec<FieldDefinition>,
+    primary_index: Vec<usize>,
+    key_strategy: SubjectNameStrategy,
+    value_strategy: SubjectNameStrategy,
+}
+
+impl Debug for AvroEncoder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AvroEncoder")
+            .field("fields", &self.fields)
+            .field("primary_index", &self.primary_index)
+            .finish()
+    }
+}
+
+impl AvroEncoder {
+    pub fn new(
+        topic: &str,
+        schema: &Schema,
+        schema_registry_url: &str,
+        runtime: Arc<Runtime>,
+    ) -> Self {
+        let strategy = |is_key: bool, name: &str, schema: Value| {
+            SubjectNameStrategy::TopicNameStrategyWithSchema(
+                topic.to_string(),
+                is_key,
+                SuppliedSchema {
+                    name: Some(format!("{}.{name}", avro_namespace(topic))),
+                    schema_type: SchemaType::Avro,
+                    schema: schema.to_string(),
+                    references: vec![],
+                },
+            )
+        };
+        Self {
+            encoder: RegistryEncoder::new(SrSettings::new(schema_registry_url.to_string())),
+            runtime,
+            fields: schema.fields.clone(),
+            primary_index: schema.primary_index.clone(),
+            key_strategy: strategy(true, "Key", key_schema(topic, schema)),
+            value_strategy: strategy(false, "Envelope", value_schema(topic, schema)),
+        }
+    }
+
+    pub fn encode_key(&self, record: &Record) -> Result<Vec<u8>, KafkaSinkError> {
+        let key = self.row(record, &self.primary_index)?;
+        self.encode(key, &self.key_strategy)
+    }
+
+    pub fn encode_value(&self, event: &ChangeEvent) -> Result<Vec<u8>, KafkaSinkError> {
+        let all_fields = (0..self.fields.len()).collect::<Vec<_>>();
+        let row = |record: Option<&Record>| match record {
+            Some(record) => Ok(AvroValue::Union(
+                1,
+                Box::new(self.row(record, &all_fields)?),
+            )),
+            None => Ok::<_, KafkaSinkError>(AvroValue::Union(0, Box::new(AvroValue::Null))),
+        };
+        let value = AvroValue::Record(vec![
+            ("before".to_string(), row(event.before)?),
+            ("after".to_string(), row(event.after)?),
+            ("op".to_string(), AvroValue::String(event.op.to_string())),
+            (
+                "ts_ms".to_string(),
+                AvroValue::Union(1, Box::new(AvroValue::Long(event.ts_ms))),
+            ),
+        ]);
+        self.encode(value, &self.value_strategy)
+    }
+
+    fn row(&self, record: &Record, indexes: &[usize]) -> Result<AvroValue, KafkaSinkError> {
+        let mut row = Vec::with_capacity(indexes.len());
+        for index in indexes {
+            let field = &self.fields[*index];
+            let mut value = field_to_avro(&record.values[*index])?;
+            if field.nullable {
+                value = match value {
+                    AvroValue::Null => AvroValue::Union(0, Box::new(value)),
+                    value => AvroValue::Union(1, Box::new(value)),
+                };
+            }
+            row.push((avro_name(&field.name), value));
+        }
+        Ok(AvroValue::Record(row))
+    }
+
+    fn encode(
+        &self,
+        value: AvroValue,
+        strategy: &SubjectNameStrategy,
+    ) -> Result<Vec<u8>, KafkaSinkError> {
+        self.runtime
+            .block_on(self.encoder.encode_value(value, strategy))
+            .map_err(|e| KafkaSinkError::SchemaRegistry(e.to_string()))
+    }
+}
+
+/// Avro schema of the topic's keys, a record of the primary key fields.
+pub fn key_schema(topic: &str, schema: &Schema) -> Value {
+    let fields = schema
+        .primary_index
+        .iter()
+        .map(|index| &schema.fields[*index]);
+    record_schema("Key", &avro_namespace(topic), fields)
+}
+
+/// Avro schema of the topic's values, Debezium's change event envelope.
+pub fn value_schema(topic: &str, schema: &Schema) -> Value {
+    let namespace = avro_namespace(topic);
+    let row = record_schema("Value", &namespace, schema.fields.iter());
+    json!({
+        "type": "record",
+        "name": "Envelope",
+        "namespace": namespace,
+        "fields": [
+            { "name": "before", "type": ["null", row], "default": null },
+            // The row record is defined once, then referenced by name.
+            { "name": "after", "type": ["null", "Value"], "default": null },
+            { "name": "op", "type": "string" },
+            { "name": "ts_ms", "type": ["null", "long"], "default": null },
+        ],
+    })
+}
+
+fn record_schema<'a>(
+    name: &str,
+    namespace: &str,
+    fields: impl Iterator<Item = &'a FieldDefinition>,
+) -> Value {
+    let mut point_defined = false;
+    let fields = fields
+        .map(|field| {
+            let typ = field_schema(field, &mut point_defined);
+            if field.nullable {
+                json!({ "name": avro_name(&field.name), "type": ["null", typ], "default": null })
+            } else {
+                json!({ "name": avro_name(&field.name), "type": typ })
+            }
+        })
+        .collect::<Vec<_>>();
+    json!({ "type": "record", "name": name, "namespace": namespace, "fields": fields })
+}
+
+fn field_schema(field: &FieldDefinition, point_defined: &mut bool) -> Value {
+    let (typ, semantic_type) = map_field_type(&field.typ);
+    let avro_type = match typ {
+        ConnectType::Int8 => return json!({ "type": "int", "connect.type": "int8" }),
+        ConnectType::Int32 => "int",
+        ConnectType::Int64 => "long",
+        ConnectType::Float64 => "double",
+        ConnectType::Boolean => "boolean",
+        ConnectType::String => "string",
+        ConnectType::Bytes => "bytes",
+        ConnectType::Point => {
+            // A named type can only be defined once per schema.
+            if std::mem::replace(point_defined, true) {
+                return json!(POINT);
+            }
+            return json!({
+                "type": "record",
+                "name": "Point",
+                "namespace": "io.debezium.data.geometry",
+                "fields": [
+                    { "name": "x", "type": "double" },
+                    { "name": "y", "type": "double" },
+                ],
+            });
+        }
+    };
+    match semantic_type {
+        Some(name) => json!({ "type": avro_type, "connect.name": name }),
+        None => json!(avro_type),
+    }
+}
+
+pub fn field_to_avro(field: &Field) -> Result<AvroValue, KafkaSinkError> {
+    Ok(match field {
+        Field::UInt(value) => AvroValue::Long(uint_to_int64(*value)?),
+        Field::U128(value) => AvroValue::String(value.to_string()),
+        Field::Int(value) => AvroValue::Long(*value),
+        Field::Int8(value) => AvroValue::Int(*value as i32),
+        Field::I128(value) => AvroValue::String(value.to_string()),
+        Field::Float(value) => AvroValue::Double(value.0),
+        Field::Boolean(value) => AvroValue::Boolean(*value),
+        Field::String(value) | Field::Text(value) => AvroValue::String(value.clone()),
+        Field::Binary(value) => AvroValue::Bytes(value.clone()),
+        Field::Decimal(value) => AvroValue::String(value.to_string()),
+        Field::Timestamp(value) => AvroValue::Long(timestamp_to_micros(value)),
+        Field::Date(value) => AvroValue::Int(date_to_days(value)),
+        Field::Json(value) => AvroValue::String(json_to_string(value)),
+        Field::Point(value) => AvroValue::Record(vec![
+            ("x".to_string(), AvroValue::Double(value.0.x().0)),
+            ("y".to_string(), AvroValue::Double(value.0.y().0)),
+        ]),
+        Field::Duration(value) => AvroValue::Long(duration_to_nanos(value)?),
+        Field::Null => AvroValue::Null,
+        Field::Uuid(value) => AvroValue::String(value.to_string()),
+        Field::Time(value) => AvroValue::Long(time_to_nanos(value)),
+        Field::List(_) | Field::Struct(_) => {
+            AvroValue::String(json_to_string(&field_to_json_value(field.clone())))
+        }
+    })
+}
+
+/// Avro names must match `[A-Za-z_][A-Za-z0-9_]*`.
+pub fn avro_name(name: &str) -> String {
+    let mut result = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect::<String>();
+    if !result.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_') {
+        result.insert(0, '_');
+    }
+    result
+}
+
+fn avro_namespace(topic: &str) -> String {
+    topic
+        .split('.')
+        .map(avro_name)
+        .collect::<Vec<_>>()
+        .join(".")
+}
@@ -0,0 +1,108 @@
+use dozer_types::chrono::{DateTime, FixedOffset, NaiveDate, NaiveTime, Timelike};
+use dozer_types::types::{DozerDuration, FieldType, Record};
+
+use crate::errors::KafkaSinkError;
+
+/// Debezium operation codes.
+pub const OP_CREATE: &str = "c";
+pub const OP_UPDATE: &str = "u";
+pub const OP_DELETE: &str = "d";
+/// Rows of the snapshot are emitted as reads.
+pub const OP_READ: &str = "r";
+
+/// Debezium semantic types.
+pub const MICRO_TIMESTAMP: &str = "io.debezium.time.MicroTimestamp";
+pub const DATE: &str = "io.debezium.time.Date";
+pub const NANO_DURATION: &str = "io.debezium.time.NanoDuration";
+pub const NANO_TIME: &str = "io.debezium.time.NanoTime";
+pub const UUID: &str = "io.debezium.data.Uuid";
+pub const ENUM: &str = "io.debezium.data.Enum";
+pub const JSON: &str = "io.debezium.data.Json";
+pub const POINT: &str = "io.debezium.data.geometry.Point";
+
+/// Kafka Connect schema types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectType {
+    Int8,
+    Int32,
+    Int64,
+    Float64,
+    Boolean,
+    String,
+    Bytes,
+    /// Debezium's `Point`, a struct of the `x` and `y` coordinates.
+    Point,
+}
+
+impl ConnectType {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ConnectType::Int8 => "int8",
+            ConnectType::Int32 => "int32",
+            ConnectType::Int64 => "int64",
+            ConnectType::Float64 => "double",
+            ConnectType::Boolean => "boolean",
+            ConnectType::String => "string",
+            ConnectType::Bytes => "bytes",
+            ConnectType::Point => "struct",
+        }
+    }
+}
+
+/// Kafka Connect type of a field, and the Debezium semantic type it carries if any.
+///
+/// 128 bit integers and decimals don't fit in any Connect type without losing precision,
+/// so they are written as strings. Lists and structs are written as JSON. Enum values are
+/// written as strings, without the allowed values Debezium attaches as schema parameters.
+pub fn map_field_type(typ: &FieldType) -> (ConnectType, Option<&'static str>) {
+    match typ {
+        FieldType::UInt | FieldType::Int => (ConnectType::Int64, None),
+        FieldType::Int8 => (ConnectType::Int8, None),
+        FieldType::U128 | FieldType::I128 | FieldType::Decimal(_) => (ConnectType::String, None),
+        FieldType::Float => (ConnectType::Float64, None),
+        FieldType::Boolean => (ConnectType::Boolean, None),
+        FieldType::String | FieldType::Text => (ConnectType::String, None),
+        FieldType::Binary => (ConnectType::Bytes, None),
+        FieldType::Timestamp => (ConnectType::Int64, Some(MICRO_TIMESTAMP)),
+        FieldType::Date => (ConnectType::Int32, Some(DATE)),
+        FieldType::Time => (ConnectType::Int64, Some(NANO_TIME)),
+        FieldType::Uuid => (ConnectType::String, Some(UUID)),
+        FieldType::Enum(_) => (ConnectType::String, Some(ENUM)),
+        FieldType::Json | FieldType::List(_) | FieldType::Struct(_) => {
+            (ConnectType::String, Some(JSON))
+        }
+        FieldType::Point => (ConnectType::Point, Some(POINT)),
+        FieldType::Duration => (ConnectType::Int64, Some(NANO_DURATION)),
+    }
+}
+
+pub fn uint_to_int64(value: u64) -> Result<i64, KafkaSinkError> {
+    i64::try_from(value).map_err(|_| KafkaSinkError::ValueOutOfRange(value.to_string()))
+}
+
+pub fn timestamp_to_micros(value: &DateTime<FixedOffset>) -> i64 {
+    value.timestamp_micros()
+}
+
+pub fn date_to_days(value: &NaiveDate) -> i32 {
+    let epoch = NaiveDate::from_ymd_opt(1970, 1, 1).unwrap();
+    value.signed_duration_since(epoch).num_days() as i32
+}
+
+pub fn time_to_nanos(value: &NaiveTime) -> i64 {
+    value.num_seconds_from_midnight() as i64 * 1_000_000_000 + value.nanosecond() as i64
+}
+
+pub fn duration_to_nanos(value: &DozerDuration) -> Result<i64, KafkaSinkError> {
+    i64::try_from(value.0.as_nanos())
+        .map_err(|_| KafkaSinkError::ValueOutOfRange(format!("{:?}", value.0)))
+}
+
+/// A change of one row, in the shape of a Debezium event's payload.
+#[derive(Debug, Clone, Copy)]
+pub struct ChangeEvent<'a> {
+    pub op: &'static str,
+    pub before: Option<&'a Record>,
+    pub after: Option<&'a Record>,
+    pub ts_ms: i64,
+}
@@ -0,0 +1,36 @@
+use dozer_types::{
+    errors::types::{DeserializationError, SerializationError},
+    thiserror::{self, Error},
+};
+use rdkafka::{error::KafkaError, types::RDKafkaErrorCode};
+
+#[derive(Error, Debug)]
+pub enum KafkaSinkError {
+    #[error("Kafka error: {0}")]
+    Kafka(#[from] KafkaError),
+
+    #[error("Failed to create topic {0}: {1}")]
+    CreateTopic(String, RDKafkaErrorCode),
+
+    #[error("Avro format needs a schema registry. Set `schema_registry_url` in the connection")]
+    NoSchemaRegistry,
+
+    /// The schema registry client's errors are not `Sync`, so only their message is kept.
+    #[error("Schema registry error: {0}")]
+    SchemaRegistry(String),
+
+    #[error("Table {0} has no primary key")]
+    NoPrimaryKey(String),
+
+    #[error("Value {0} doesn't fit in an int64")]
+    ValueOutOfRange(String),
+
+    #[error("Serialization error: {0}")]
+    Serialization(#[from] SerializationError),
+
+    #[error("Invalid replication metadata: {0}")]
+    Metadata(#[from] DeserializationError),
+
+    #[error("Timed out reading replication metadata of {0}")]
+    MetadataTimeout(String),
+}
@@ -0,0 +1,148 @@
+use base64::{engine, Engine};
+use dozer_types::json_types::{field_to_json_value, json_to_string};
+use dozer_types::serde_json::{json, Map, Value};
+use dozer_types::types::{Field, FieldDefinition, Record, Schema};
+
+use crate::debezium::{
+    date_to_days, duration_to_nanos, map_field_type, time_to_nanos, timestamp_to_micros,
+    uint_to_int64, ChangeEvent, ConnectType,
+};
+use crate::errors::KafkaSinkError;
+
+/// Encodes keys and change events like Kafka Connect's `JsonConverter` with `schemas.enable`,
+/// that is `{"schema": ..., "payload": ...}` objects.
+#[derive(Debug)]
+pub struct JsonEncoder {
+    fields: Vec<FieldDefinition>,
+    primary_index: Vec<usize>,
+    key_schema: Value,
+    value_schema: Value,
+}
+
+impl JsonEncoder {
+    pub fn new(topic: &str, schema: &Schema) -> Self {
+        let key_fields = schema
+            .primary_index
+            .iter()
+            .map(|index| &schema.fields[*index]);
+        let key_schema = struct_schema(&format!("{topic}.Key"), key_fields, false);
+
+        let row_schema = |field: &str| {
+            let mut row = struct_schema(&format!("{topic}.Value"), schema.fields.iter(), true);
+            row["field"] = json!(field);
+            row
+        };
+        let value_schema = json!({
+            "type": "struct",
+            "name": format!("{topic}.Envelope"),
+            "optional": false,
+            "fields": [
+                row_schema("before"),
+                row_schema("after"),
+                { "type": "string", "optional": false, "field": "op" },
+                { "type": "int64", "optional": true, "field": "ts_ms" },
+            ],
+        });
+
+        Self {
+            fields: schema.fields.clone(),
+            primary_index: schema.primary_index.clone(),
+            key_schema,
+            value_schema,
+        }
+    }
+
+    pub fn encode_key(&self, record: &Record) -> Result<Vec<u8>, KafkaSinkError> {
+        let payload = self.row(record, &self.primary_index)?;
+        Ok(envelope(&self.key_schema, payload))
+    }
+
+    pub fn encode_value(&self, event: &ChangeEvent) -> Result<Vec<u8>, KafkaSinkError> {
+        let all_fields = (0..self.fields.len()).collect::<Vec<_>>();
+        let row = |record: Option<&Record>| {
+            record.map_or(Ok(Value::Null), |record| self.row(record, &all_fields))
+        };
+        let payload = json!({
+            "before": row(event.before)?,
+            "after": row(event.after)?,
+            "op": event.op,
+            "ts_ms": event.ts_ms,
+        });
+        Ok(envelope(&self.value_schema, payload))
+    }
+
+    fn row(&self, record: &Record, indexes: &[usize]) -> Result<Value, KafkaSinkError> {
+        let mut row = Map::new();
+        for index in indexes {
+            row.insert(
+                self.fields[*index].name.clone(),
+                field_to_json(&record.values[*index])?,
+            );
+        }
+        Ok(Value::Object(row))
+    }
+}
+
+fn envelope(schema: &Value, payload: Value) -> Vec<u8> {
+    json!({ "schema": schema, "payload": payload })
+        .to_string()
+        .into_bytes()
+}
+
+fn struct_schema<'a>(
+    name: &str,
+    fields: impl Iterator<Item = &'a FieldDefinition>,
+    optional: bool,
+) -> Value {
+    json!({
+        "type": "struct",
+        "name": name,
+        "optional": optional,
+        "fields": fields.map(field_schema).collect::<Vec<_>>(),
+    })
+}
+
+fn field_schema(field: &FieldDefinition) -> Value {
+    let (typ, semantic_type) = map_field_type(&field.typ);
+    let mut schema = json!({
+        "type": typ.name(),
+        "optional": field.nullable,
+        "field": field.name,
+    });
+    if let Some(name) = semantic_type {
+        schema["name"] = json!(name);
+    }
+    if typ == ConnectType::Point {
+        schema["fields"] = json!([
+            { "type": "double", "optional": false, "field": "x" },
+            { "type": "double", "optional": false, "field": "y" },
+        ]);
+    }
+    schema
+}
+
+pub fn field_to_json(field: &Field) -> Result<Value, KafkaSinkError> {
+    Ok(match field {
+        Field::UInt(value) => json!(uint_to_int64(*value)?),
+        Field::U128(value) => json!(value.to_string()),
+        Field::Int(value) => json!(value),
+        Field::Int8(value) => json!(value),
+        Field::I128(value) => json!(value.to_string()),
+        Field::Float(value) => json!(value.0),
+        Field::Boolean(value) => json!(value),
+        Field::String(value) | Field::Text(value) => json!(value),
+        Field::Binary(value) => json!(engine::general_purpose::STANDARD.encode(value)),
+        Field::Decimal(value) => json!(value.to_string()),
+        Field::Timestamp(value) => json!(timestamp_to_micros(value)),
+        Field::Date(value) => json!(date_to_days(value)),
+        Field::Json(value) => json!(json_to_string(value)),
+        Field::Point(value) => json!({ "x": value.0.x().0, "y": value.0.y().0 }),
+        Field::Duration(value) => json!(duration_to_nanos(value)?),
+        Field::Null => Value::Null,
+        Field::Uuid(value) => json!(value.to_string()),
+        Field::Time(value) => json!(time_to_nanos(value)),
+        Field::List(_) | Field::Struct(_) => {
+            json!(json_to_string(&field_to_json_value(field.clone())))
+        }
+    })
+}
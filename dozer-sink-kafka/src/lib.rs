@@ -0,0 +1,9 @@
+pub mod avro;
+pub mod debezium;
+pub mod errors;
+pub mod json;
+pub mod metadata;
+mod sink;
+pub use sink::KafkaSinkFactory;
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,107 @@
+use std::time::{Duration, Instant};
+
+use bincode::config;
+use dozer_types::errors::types::{DeserializationError, SerializationError};
+use dozer_types::log::debug;
+use dozer_types::node::OpIdentifier;
+use rdkafka::admin::{AdminClient, AdminOptions, NewTopic, TopicReplication};
+use rdkafka::client::DefaultClientContext;
+use rdkafka::config::ClientConfig;
+use rdkafka::consumer::{BaseConsumer, Consumer};
+use rdkafka::message::Message;
+use rdkafka::types::RDKafkaErrorCode;
+use rdkafka::{Offset, TopicPartitionList};
+
+use crate::errors::KafkaSinkError;
+
+/// Compacted topic holding the latest metadata of every sink, keyed by sink name.
+pub const REPLICA_METADATA_TOPIC: &str = "__dozer_replication_metadata";
+const TIMEOUT: Duration = Duration::from_secs(10);
+/// How long a single poll waits for a message, before checking whether the high watermark was reached.
+const POLL_TIMEOUT: Duration = Duration::from_millis(100);
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, bincode::Encode, bincode::Decode)]
+pub struct ReplicationMetadata {
+    pub source_state: Option<Vec<u8>>,
+    pub op_id: Option<OpIdentifier>,
+}
+
+impl ReplicationMetadata {
+    pub async fn create_topic(client_config: &ClientConfig) -> Result<(), KafkaSinkError> {
+        let admin: AdminClient<DefaultClientContext> = client_config.create()?;
+        let topic = NewTopic::new(REPLICA_METADATA_TOPIC, 1, TopicReplication::Fixed(-1))
+            .set("cleanup.policy", "compact");
+        for result in admin
+            .create_topics(
+                &[topic],
+                &AdminOptions::new().operation_timeout(Some(TIMEOUT)),
+            )
+            .await?
+        {
+            match result {
+                Ok(_) | Err((_, RDKafkaErrorCode::TopicAlreadyExists)) => (),
+                Err((topic, code)) => return Err(KafkaSinkError::CreateTopic(topic, code)),
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads the latest committed metadata of the sink `key`. This blocks, so call it from a blocking task.
+    pub fn read(client_config: &ClientConfig, key: &str) -> Result<Self, KafkaSinkError> {
+        let consumer: BaseConsumer = client_config
+            .clone()
+            .set("group.id", "dozer-replication-metadata")
+            .set("enable.auto.commit", "false")
+            .set("isolation.level", "read_committed")
+            .create()?;
+        let mut metadata = Self::default();
+        let (low, high) = consumer.fetch_watermarks(REPLICA_METADATA_TOPIC, 0, TIMEOUT)?;
+        if low >= high {
+            return Ok(metadata);
+        }
+
+        let mut partitions = TopicPartitionList::new();
+        partitions.add_partition_offset(REPLICA_METADATA_TOPIC, 0, Offset::Offset(low))?;
+        consumer.assign(&partitions)?;
+        // The last offsets may be transaction markers, which are never delivered, but the consumer's
+        // position still moves past them. Reading stops once the position reaches the high watermark.
+        let deadline = Instant::now() + TIMEOUT;
+        loop {
+            if let Some(message) = consumer.poll(POLL_TIMEOUT) {
+                let message = message?;
+                if message.key() == Some(key.as_bytes()) {
+                    if let Some(payload) = message.payload() {
+                        metadata = Self::decode_from(payload)?;
+                    }
+                }
+                if message.offset() + 1 >= high {
+                    break;
+                }
+            }
+            let position = consumer.position()?;
+            if let Some(Offset::Offset(offset)) = position
+                .find_partition(REPLICA_METADATA_TOPIC, 0)
+                .map(|partition| partition.offset())
+            {
+                if offset >= high {
+                    break;
+                }
+            }
+            if Instant::now() >= deadline {
+                return Err(KafkaSinkError::MetadataTimeout(key.to_string()));
+            }
+        }
+        debug!("[Sink] Read replication metadata of {key}: {metadata:?}");
+        Ok(metadata)
+    }
+
+    pub fn encode_to_vec(&self) -> Result<Vec<u8>, KafkaSinkError> {
+        Ok(bincode::encode_to_vec(self, config::legacy()).map_err(SerializationError::from)?)
+    }
+
+    pub(crate) fn decode_from(payload: &[u8]) -> Result<Self, KafkaSinkError> {
+        let (metadata, _) = bincode::decode_from_slice(payload, config::legacy())
+            .map_err(DeserializationError::from)?;
+        Ok(metadata)
+    }
+}
@@ -0,0 +1,347 @@
+use dozer_core::epoch::Epoch;
+use dozer_core::event::EventHub;
+use dozer_core::node::{PortHandle, Sink, SinkFactory};
+use dozer_core::tokio::{self, runtime::Runtime};
+use dozer_core::DEFAULT_PORT_HANDLE;
+use dozer_types::chrono::Utc;
+use dozer_types::errors::internal::BoxedError;
+use dozer_types::models::ingestion_types::KafkaConfig;
+use dozer_types::models::sink::{KafkaSinkConfig, KafkaSinkFormat};
+use dozer_types::node::OpIdentifier;
+use dozer_types::tonic::async_trait;
+use dozer_types::types::{Field, Operation, Record, Schema, TableOperation};
+use rdkafka::config::ClientConfig;
+use rdkafka::error::KafkaError;
+use rdkafka::producer::{BaseRecord, DefaultProducerContext, Producer, ThreadedProducer};
+use rdkafka::types::RDKafkaErrorCode;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::avro::AvroEncoder;
+use crate::debezium::{ChangeEvent, OP_CREATE, OP_DELETE, OP_READ, OP_UPDATE};
+use crate::errors::KafkaSinkError;
+use crate::json::JsonEncoder;
+use crate::metadata::{ReplicationMetadata, REPLICA_METADATA_TOPIC};
+
+const TIMEOUT: Duration = Duration::from_secs(30);
+/// How long to wait for room in the producer queue before retrying a send.
+const QUEUE_FULL_BACKOFF: Duration = Duration::from_millis(100);
+
+#[derive(Debug)]
+pub struct KafkaSinkFactory {
+    name: String,
+    runtime: Arc<Runtime>,
+    config: KafkaSinkConfig,
+    connection: KafkaConfig,
+}
+
+impl KafkaSinkFactory {
+    pub fn new(
+        name: String,
+        config: KafkaSinkConfig,
+        connection: KafkaConfig,
+        runtime: Arc<Runtime>,
+    ) -> Self {
+        Self {
+            name,
+            runtime,
+            config,
+            connection,
+        }
+    }
+}
+
+#[async_trait]
+impl SinkFactory for KafkaSinkFactory {
+    fn type_name(&self) -> String {
+        "kafka".to_string()
+    }
+
+    fn get_input_ports(&self) -> Vec<PortHandle> {
+        vec![DEFAULT_PORT_HANDLE]
+    }
+
+    fn get_input_port_name(&self, _port: &PortHandle) -> String {
+        self.config.source_table_name.clone()
+    }
+
+    fn prepare(&self, input_schemas: HashMap<PortHandle, Schema>) -> Result<(), BoxedError> {
+        debug_assert!(input_schemas.len() == 1);
+        if input_schemas[&DEFAULT_PORT_HANDLE].primary_index.is_empty() {
+            return Err(KafkaSinkError::NoPrimaryKey(self.config.source_table_name.clone()).into());
+        }
+        if self.config.format == KafkaSinkFormat::Avro
+            && self.connection.schema_registry_url.is_none()
+        {
+            return Err(KafkaSinkError::NoSchemaRegistry.into());
+        }
+        Ok(())
+    }
+
+    async fn build(
+        &self,
+        mut input_schemas: HashMap<PortHandle, Schema>,
+        _event_hub: EventHub,
+    ) -> Result<Box<dyn Sink>, BoxedError> {
+        let schema = input_schemas.remove(&DEFAULT_PORT_HANDLE).unwrap();
+        let mut client_config = ClientConfig::new();
+        client_config.set("bootstrap.servers", &self.connection.broker);
+
+        // Fences off any previous instance of this sink, aborting its open transaction,
+        // so the metadata read below is the last committed one.
+        let producer: ThreadedProducer<DefaultProducerContext> = client_config
+            .clone()
+            .set("transactional.id", format!("dozer-{}", self.name))
+            .create()
+            .map_err(KafkaSinkError::from)?;
+        producer
+            .init_transactions(TIMEOUT)
+            .map_err(KafkaSinkError::from)?;
+
+        ReplicationMetadata::create_topic(&client_config).await?;
+        let metadata = {
+            let client_config = client_config.clone();
+            let name = self.name.clone();
+            tokio::task::spawn_blocking(move || ReplicationMetadata::read(&client_config, &name))
+                .await??
+        };
+
+        let encoder = match self.config.format {
+            KafkaSinkFormat::Json => Encoder::Json(JsonEncoder::new(&self.config.topic, &schema)),
+            KafkaSinkFormat::Avro => {
+                let Some(url) = &self.connection.schema_registry_url else {
+                    return Err(KafkaSinkError::NoSchemaRegistry.into());
+                };
+                Encoder::Avro(AvroEncoder::new(
+                    &self.config.topic,
+                    &schema,
+                    url,
+                    self.runtime.clone(),
+                ))
+            }
+        };
+
+        let sink = KafkaSink {
+            producer,
+            encoder,
+            name: self.name.clone(),
+            topic: self.config.topic.clone(),
+            schema,
+            metadata,
+            in_transaction: false,
+            snapshotting: false,
+        };
+        Ok(Box::new(sink))
+    }
+}
+
+#[derive(Debug)]
+enum Encoder {
+    Json(JsonEncoder),
+    Avro(AvroEncoder),
+}
+
+impl Encoder {
+    fn encode_key(&self, record: &Record) -> Result<Vec<u8>, KafkaSinkError> {
+        match self {
+            Encoder::Json(encoder) => encoder.encode_key(record),
+            Encoder::Avro(encoder) => encoder.encode_key(record),
+        }
+    }
+
+    fn encode_value(&self, event: &ChangeEvent) -> Result<Vec<u8>, KafkaSinkError> {
+        match self {
+            Encoder::Json(encoder) => encoder.encode_value(event),
+            Encoder::Avro(encoder) => encoder.encode_value(event),
+        }
+    }
+}
+
+/// Writes every operation as a Debezium change event, keyed by primary key.
+///
+/// Events are produced in a transaction that is committed with the sink's metadata on every
+/// epoch commit, so consumers reading committed messages see each epoch exactly once.
+pub(crate) struct KafkaSink {
+    producer: ThreadedProducer<DefaultProducerContext>,
+    encoder: Encoder,
+    name: String,
+    topic: String,
+    schema: Schema,
+    metadata: ReplicationMetadata,
+    in_transaction: bool,
+    snapshotting: bool,
+}
+
+impl Debug for KafkaSink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("KafkaSink")
+            .field("name", &self.name)
+            .field("topic", &self.topic)
+            .field("encoder", &self.encoder)
+            .finish()
+    }
+}
+
+impl KafkaSink {
+    fn key(&self, record: &Record) -> Vec<Field> {
+        self.schema
+            .primary_index
+            .iter()
+            .map(|index| record.values[*index].clone())
+            .collect()
+    }
+
+    fn begin_transaction(&mut self) -> Result<(), KafkaSinkError> {
+        if !self.in_transaction {
+            self.producer.begin_transaction()?;
+            self.in_transaction = true;
+        }
+        Ok(())
+    }
+
+    fn commit_transaction(&mut self) -> Result<(), KafkaSinkError> {
+        if self.in_transaction {
+            let metadata = self.metadata.encode_to_vec()?;
+            self.send(
+                REPLICA_METADATA_TOPIC,
+                self.name.as_bytes(),
+                Some(&metadata),
+            )?;
+            self.producer.commit_transaction(TIMEOUT)?;
+            self.in_transaction = false;
+        }
+        Ok(())
+    }
+
+    /// Sends a message, waiting for room in the producer queue if it's full.
+    /// A message without payload is a tombstone.
+    fn send(&self, topic: &str, key: &[u8], payload: Option<&[u8]>) -> Result<(), KafkaSinkError> {
+        let mut record = BaseRecord::<[u8], [u8]>::to(topic).key(key);
+        if let Some(payload) = payload {
+            record = record.payload(payload);
+        }
+        loop {
+            match self.producer.send(record) {
+                Ok(()) => return Ok(()),
+                Err((KafkaError::MessageProduction(RDKafkaErrorCode::QueueFull), returned)) => {
+                    record = returned;
+                    std::thread::sleep(QUEUE_FULL_BACKOFF);
+                }
+                Err((e, _)) => return Err(e.into()),
+            }
+        }
+    }
+
+    fn emit(&self, event: ChangeEvent, key_record: &Record) -> Result<(), KafkaSinkError> {
+        let key = self.encoder.encode_key(key_record)?;
+        let value = self.encoder.encode_value(&event)?;
+        self.send(&self.topic, &key, Some(&value))
+    }
+
+    fn insert(&self, new: &Record, ts_ms: i64) -> Result<(), KafkaSinkError> {
+        let event = ChangeEvent {
+            op: if self.snapshotting {
+                OP_READ
+            } else {
+                OP_CREATE
+            },
+            before: None,
+            after: Some(new),
+            ts_ms,
+        };
+        self.emit(event, new)
+    }
+
+    /// Like Debezium, a delete is followed by a tombstone so compaction can drop the key.
+    fn delete(&self, old: &Record, ts_ms: i64) -> Result<(), KafkaSinkError> {
+        let event = ChangeEvent {
+            op: OP_DELETE,
+            before: Some(old),
+            after: None,
+            ts_ms,
+        };
+        self.emit(event, old)?;
+        let key = self.encoder.encode_key(old)?;
+        self.send(&self.topic, &key, None)
+    }
+
+    fn update(&self, old: &Record, new: &Record, ts_ms: i64) -> Result<(), KafkaSinkError> {
+        // A changed primary key moves the row to another message key.
+        if self.key(old) != self.key(new) {
+            self.delete(old, ts_ms)?;
+            return self.insert(new, ts_ms);
+        }
+        let event = ChangeEvent {
+            op: OP_UPDATE,
+            before: Some(old),
+            after: Some(new),
+            ts_ms,
+        };
+        self.emit(event, new)
+    }
+}
+
+impl Sink for KafkaSink {
+    fn commit(&mut self, _epoch_details: &Epoch) -> Result<(), BoxedError> {
+        self.commit_transaction()?;
+        Ok(())
+    }
+
+    fn process(&mut self, op: TableOperation) -> Result<(), BoxedError> {
+        self.begin_transaction()?;
+        if let Some(id) = op.id {
+            self.metadata.op_id = Some(id);
+        }
+        let ts_ms = Utc::now().timestamp_millis();
+        match op.op {
+            Operation::Insert { new } => self.insert(&new, ts_ms)?,
+            Operation::Delete { old } => self.delete(&old, ts_ms)?,
+            Operation::Update { old, new } => self.update(&old, &new, ts_ms)?,
+            Operation::BatchInsert { new } => {
+                for record in &new {
+                    self.insert(record, ts_ms)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn on_source_snapshotting_started(
+        &mut self,
+        _connection_name: String,
+    ) -> Result<(), BoxedError> {
+        self.snapshotting = true;
+        Ok(())
+    }
+
+    fn on_source_snapshotting_done(
+        &mut self,
+        _connection_name: String,
+        id: Option<OpIdentifier>,
+    ) -> Result<(), BoxedError> {
+        self.snapshotting = false;
+        if id.is_some() {
+            self.metadata.op_id = id;
+        }
+        self.begin_transaction()?;
+        self.commit_transaction()?;
+        Ok(())
+    }
+
+    fn set_source_state(&mut self, source_state: &[u8]) -> Result<(), BoxedError> {
+        self.metadata.source_state = Some(source_state.to_vec());
+        self.begin_transaction()?;
+        self.commit_transaction()?;
+        Ok(())
+    }
+
+    fn get_source_state(&mut self) -> Result<Option<Vec<u8>>, BoxedError> {
+        Ok(self.metadata.source_state.clone())
+    }
+
+    fn get_latest_op_id(&mut self) -> Result<Option<OpIdentifier>, BoxedError> {
+        Ok(self.metadata.op_id)
+    }
+}
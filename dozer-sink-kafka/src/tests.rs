@@ -0,0 +1,186 @@
+use apache_avro::types::Value as AvroValue;
+use dozer_types::chrono::{NaiveDate, NaiveTime};
+use dozer_types::serde_json::{self, json, Value};
+use dozer_types::types::{
+    DozerPoint, Field, FieldDefinition, FieldType, Record, Schema, SourceDefinition,
+};
+
+use crate::avro::{avro_name, field_to_avro, key_schema, value_schema};
+use crate::debezium::{date_to_days, time_to_nanos, ChangeEvent, OP_DELETE, OP_UPDATE};
+use crate::json::JsonEncoder;
+use crate::metadata::ReplicationMetadata;
+
+fn get_dozer_schema() -> Schema {
+    let field = |name: &str, typ, nullable| FieldDefinition {
+        name: name.to_string(),
+        typ,
+        nullable,
+        source: SourceDefinition::Dynamic,
+        description: None,
+    };
+    Schema {
+        fields: vec![
+            field("id", FieldType::Int, false),
+            field("name", FieldType::String, true),
+            field("created", FieldType::Date, true),
+            field("location", FieldType::Point, true),
+            field("last location", FieldType::Point, true),
+        ],
+        primary_index: vec![0],
+    }
+}
+
+fn record(id: i64, name: &str) -> Record {
+    Record::new(vec![
+        Field::Int(id),
+        Field::String(name.to_string()),
+        Field::Date(NaiveDate::from_ymd_opt(1970, 1, 11).unwrap()),
+        Field::Point(DozerPoint::from((1.0, 2.0))),
+        Field::Null,
+    ])
+}
+
+fn parse(bytes: Vec<u8>) -> Value {
+    serde_json::from_slice(&bytes).unwrap()
+}
+
+#[test]
+fn test_json_key() {
+    let encoder = JsonEncoder::new("dozer.users", &get_dozer_schema());
+    let key = parse(encoder.encode_key(&record(1, "alice")).unwrap());
+    assert_eq!(key["payload"], json!({ "id": 1 }));
+    assert_eq!(key["schema"]["name"], json!("dozer.users.Key"));
+    assert_eq!(
+        key["schema"]["fields"],
+        json!([{ "type": "int64", "optional": false, "field": "id" }])
+    );
+}
+
+#[test]
+fn test_json_change_event() {
+    let encoder = JsonEncoder::new("dozer.users", &get_dozer_schema());
+    let event = ChangeEvent {
+        op: OP_UPDATE,
+        before: Some(&record(1, "alice")),
+        after: Some(&record(1, "bob")),
+        ts_ms: 42,
+    };
+    let value = parse(encoder.encode_value(&event).unwrap());
+
+    let payload = &value["payload"];
+    assert_eq!(payload["op"], json!("u"));
+    assert_eq!(payload["ts_ms"], json!(42));
+    assert_eq!(payload["before"]["name"], json!("alice"));
+    assert_eq!(payload["after"]["name"], json!("bob"));
+    assert_eq!(payload["after"]["created"], json!(10));
+    assert_eq!(payload["after"]["location"], json!({ "x": 1.0, "y": 2.0 }));
+    assert_eq!(payload["after"]["last location"], Value::Null);
+
+    let schema = &value["schema"];
+    assert_eq!(schema["name"], json!("dozer.users.Envelope"));
+    let after = &schema["fields"][1];
+    assert_eq!(after["field"], json!("after"));
+    assert_eq!(after["optional"], json!(true));
+    assert_eq!(
+        after["fields"][2],
+        json!({
+            "type": "int32",
+            "optional": true,
+            "field": "created",
+            "name": "io.debezium.time.Date",
+        })
+    );
+
+    let event = ChangeEvent {
+        op: OP_DELETE,
+        before: Some(&record(1, "bob")),
+        after: None,
+        ts_ms: 42,
+    };
+    let value = parse(encoder.encode_value(&event).unwrap());
+    assert_eq!(value["payload"]["after"], Value::Null);
+}
+
+#[test]
+fn test_avro_schemas() {
+    let schema = get_dozer_schema();
+    let value_schema =
+        apache_avro::Schema::parse(&value_schema("dozer.users-v1", &schema)).unwrap();
+    let key_schema = apache_avro::Schema::parse(&key_schema("dozer.users-v1", &schema)).unwrap();
+
+    let row = |record: &Record| {
+        AvroValue::Record(
+            schema
+                .fields
+                .iter()
+                .zip(&record.values)
+                .map(|(field, value)| {
+                    let value = field_to_avro(value).unwrap();
+                    let value = match (field.nullable, value) {
+                        (false, value) => value,
+                        (true, AvroValue::Null) => AvroValue::Union(0, Box::new(AvroValue::Null)),
+                        (true, value) => AvroValue::Union(1, Box::new(value)),
+                    };
+                    (avro_name(&field.name), value)
+                })
+                .collect(),
+        )
+    };
+    let envelope = AvroValue::Record(vec![
+        (
+            "before".to_string(),
+            AvroValue::Union(0, Box::new(AvroValue::Null)),
+        ),
+        (
+            "after".to_string(),
+            AvroValue::Union(1, Box::new(row(&record(1, "alice")))),
+        ),
+        ("op".to_string(), AvroValue::String("c".to_string())),
+        (
+            "ts_ms".to_string(),
+            AvroValue::Union(1, Box::new(AvroValue::Long(42))),
+        ),
+    ]);
+    assert!(envelope.validate(&value_schema));
+
+    let key = AvroValue::Record(vec![("id".to_string(), AvroValue::Long(1))]);
+    assert!(key.validate(&key_schema));
+}
+
+#[test]
+fn test_avro_name() {
+    assert_eq!(avro_name("last location"), "last_location");
+    assert_eq!(avro_name("1st"), "_1st");
+    assert_eq!(avro_name("_id"), "_id");
+}
+
+#[test]
+fn test_date_to_days() {
+    assert_eq!(
+        date_to_days(&NaiveDate::from_ymd_opt(1970, 1, 1).unwrap()),
+        0
+    );
+    assert_eq!(
+        date_to_days(&NaiveDate::from_ymd_opt(1969, 12, 31).unwrap()),
+        -1
+    );
+}
+
+#[test]
+fn test_time_to_nanos() {
+    assert_eq!(time_to_nanos(&NaiveTime::MIN), 0);
+    assert_eq!(
+        time_to_nanos(&NaiveTime::from_hms_nano_opt(1, 0, 2, 5).unwrap()),
+        3_602_000_000_005
+    );
+}
+
+#[test]
+fn test_metadata_encoding() {
+    let metadata = ReplicationMetadata {
+        source_state: Some(b"state".to_vec()),
+        op_id: Some(dozer_types::node::OpIdentifier::new(3, 4)),
+    };
+    let bytes = metadata.encode_to_vec().unwrap();
+    assert_eq!(ReplicationMetadata::decode_from(&bytes).unwrap(), metadata);
+}
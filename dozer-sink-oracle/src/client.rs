@@ -0,0 +1,46 @@
+use dozer_types::{log::info, models::ingestion_types::OracleConfig, types::FieldDefinition};
+use oracle::Connection;
+
+use crate::{ddl::get_create_table_query, errors::OracleSinkError};
+
+pub fn connect(config: &OracleConfig) -> Result<Connection, OracleSinkError> {
+    let connect_string = format!("{}:{}/{}", config.host, config.port, config.sid);
+    let connection = Connection::connect(&config.user, &config.password, connect_string)?;
+    if let Some(pdb) = &config.pdb {
+        connection.execute(&format!("ALTER SESSION SET CONTAINER = {pdb}"), &[])?;
+    }
+    Ok(connection)
+}
+
+pub fn table_exists(
+    connection: &Connection,
+    owner: Option<&str>,
+    table_name: &str,
+) -> Result<bool, OracleSinkError> {
+    let count = connection.query_row_as::<i64>(
+        "SELECT COUNT(*) FROM ALL_TABLES \
+        WHERE OWNER = NVL(:1, SYS_CONTEXT('USERENV', 'CURRENT_SCHEMA')) AND TABLE_NAME = :2",
+        &[&owner, &table_name],
+    )?;
+    Ok(count > 0)
+}
+
+/// Creates the table if it doesn't exist. An existing table is used as is.
+pub fn create_table_if_not_exists(
+    connection: &Connection,
+    owner: Option<&str>,
+    table_name: &str,
+    qualified_table_name: &str,
+    fields: &[FieldDefinition],
+    unique_key: &[usize],
+) -> Result<(), OracleSinkError> {
+    if table_exists(connection, owner, table_name)? {
+        return Ok(());
+    }
+    info!("[Sink] Creating table {qualified_table_name}");
+    connection.execute(
+        &get_create_table_query(qualified_table_name, fields, unique_key),
+        &[],
+    )?;
+    Ok(())
+}
@@ -0,0 +1,120 @@
+use dozer_types::types::FieldDefinition;
+
+use crate::schema::map_field_to_type;
+
+pub fn quote_identifier(name: &str) -> String {
+    format!("\"{name}\"")
+}
+
+pub fn qualified_table_name(owner: Option<&str>, table_name: &str) -> String {
+    match owner {
+        Some(owner) => format!(
+            "{}.{}",
+            quote_identifier(owner),
+            quote_identifier(table_name)
+        ),
+        None => quote_identifier(table_name),
+    }
+}
+
+pub fn get_create_table_query(
+    table_name: &str,
+    fields: &[FieldDefinition],
+    unique_key: &[usize],
+) -> String {
+    let mut parts = fields
+        .iter()
+        .map(|field| {
+            format!(
+                "{} {}",
+                quote_identifier(&field.name),
+                map_field_to_type(field)
+            )
+        })
+        .collect::<Vec<_>>();
+    parts.push(format!(
+        "PRIMARY KEY ({})",
+        column_list(unique_key.iter().map(|index| &fields[*index]))
+    ));
+
+    format!("CREATE TABLE {table_name} (\n{}\n)", parts.join(",\n"))
+}
+
+/// Upserts a row. The bind parameters are the values of all `fields`, in order.
+pub fn get_merge_query(
+    table_name: &str,
+    fields: &[FieldDefinition],
+    unique_key: &[usize],
+) -> String {
+    let source = fields
+        .iter()
+        .enumerate()
+        .map(|(index, field)| format!(":{} {}", index + 1, quote_identifier(&field.name)))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let on = unique_key
+        .iter()
+        .map(|index| {
+            let name = quote_identifier(&fields[*index].name);
+            format!("D.{name} = S.{name}")
+        })
+        .collect::<Vec<_>>()
+        .join(" AND ");
+    let update = fields
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| !unique_key.contains(index))
+        .map(|(_, field)| {
+            let name = quote_identifier(&field.name);
+            format!("D.{name} = S.{name}")
+        })
+        .collect::<Vec<_>>();
+    let when_matched = if update.is_empty() {
+        // Every column is in the key, so there's nothing to update.
+        String::new()
+    } else {
+        format!("WHEN MATCHED THEN UPDATE SET {}\n", update.join(", "))
+    };
+    let values = fields
+        .iter()
+        .map(|field| format!("S.{}", quote_identifier(&field.name)))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    format!(
+        "MERGE INTO {table_name} D\n\
+        USING (SELECT {source} FROM DUAL) S\n\
+        ON ({on})\n\
+        {when_matched}\
+        WHEN NOT MATCHED THEN INSERT ({}) VALUES ({values})",
+        column_list(fields.iter())
+    )
+}
+
+/// Deletes a row. The bind parameters are the values of the `unique_key` fields, in order.
+pub fn get_delete_query(
+    table_name: &str,
+    fields: &[FieldDefinition],
+    unique_key: &[usize],
+) -> String {
+    let condition = unique_key
+        .iter()
+        .enumerate()
+        .map(|(param, index)| {
+            format!(
+                "{} = :{}",
+                quote_identifier(&fields[*index].name),
+                param + 1
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(" AND ");
+    format!("DELETE FROM {table_name} WHERE {condition}")
+}
+
+fn column_list<'a>(fields: impl Iterator<Item = &'a FieldDefinition>) -> String {
+    fields
+        .map(|field| quote_identifier(&field.name))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
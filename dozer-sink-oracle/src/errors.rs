@@ -0,0 +1,19 @@
+use dozer_types::{
+    thiserror::{self, Error},
+    types::FieldType,
+};
+
+#[derive(Error, Debug)]
+pub enum OracleSinkError {
+    #[error("Oracle error: {0}")]
+    Oracle(#[from] oracle::Error),
+
+    #[error("Table {0} has no primary key. Set `unique_key` in the sink config")]
+    NoUniqueKey(String),
+
+    #[error("Column {0} not found in table {1}")]
+    ColumnNotFound(String, String),
+
+    #[error("Column {0} has type {1}, which can't be part of the unique key")]
+    UnsupportedKeyType(String, FieldType),
+}
@@ -0,0 +1,125 @@
+use dozer_types::{
+    node::OpIdentifier,
+    types::{Field, FieldDefinition, FieldType, Schema, SourceDefinition},
+};
+use oracle::{sql_type::ToSql, Connection};
+
+use crate::{
+    client::create_table_if_not_exists,
+    ddl::{get_merge_query, qualified_table_name, quote_identifier},
+    errors::OracleSinkError,
+    types::field_to_sql,
+};
+
+// Replication Metadata Constants
+pub const REPLICA_METADATA_TABLE: &str = "__dozer_replication_metadata";
+pub const META_TABLE_COL: &str = "table";
+pub const META_TXID_COL: &str = "txid";
+pub const META_SEQ_IN_TX_COL: &str = "seq_in_tx";
+pub const META_SOURCE_STATE_COL: &str = "source_state";
+
+/// The replication progress of a sink table, stored as one row of [`REPLICA_METADATA_TABLE`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ReplicationMetadata {
+    pub source_state: Option<Vec<u8>>,
+    pub op_id: Option<OpIdentifier>,
+}
+
+impl ReplicationMetadata {
+    pub fn schema() -> Schema {
+        let field = |name: &str, typ, nullable| FieldDefinition {
+            name: name.to_owned(),
+            typ,
+            nullable,
+            source: SourceDefinition::Dynamic,
+            description: None,
+        };
+        Schema::new()
+            .field(field(META_TABLE_COL, FieldType::String, false), true)
+            .field(field(META_TXID_COL, FieldType::UInt, true), false)
+            .field(field(META_SEQ_IN_TX_COL, FieldType::UInt, true), false)
+            .field(field(META_SOURCE_STATE_COL, FieldType::Binary, true), false)
+            .clone()
+    }
+
+    pub fn create_table(
+        connection: &Connection,
+        owner: Option<&str>,
+    ) -> Result<(), OracleSinkError> {
+        let schema = Self::schema();
+        create_table_if_not_exists(
+            connection,
+            owner,
+            REPLICA_METADATA_TABLE,
+            &qualified_table_name(owner, REPLICA_METADATA_TABLE),
+            &schema.fields,
+            &schema.primary_index,
+        )
+    }
+
+    /// Reads the metadata of `sink_table`, which is empty if the sink has never written anything.
+    pub fn read(
+        connection: &Connection,
+        owner: Option<&str>,
+        sink_table: &str,
+    ) -> Result<Self, OracleSinkError> {
+        let query = format!(
+            "SELECT {}, {}, {} FROM {} WHERE {} = :1",
+            quote_identifier(META_TXID_COL),
+            quote_identifier(META_SEQ_IN_TX_COL),
+            quote_identifier(META_SOURCE_STATE_COL),
+            qualified_table_name(owner, REPLICA_METADATA_TABLE),
+            quote_identifier(META_TABLE_COL),
+        );
+        let row = connection
+            .query_row_as::<(Option<u64>, Option<u64>, Option<Vec<u8>>)>(&query, &[&sink_table]);
+        let (txid, seq_in_tx, source_state) = match row {
+            Ok(row) => row,
+            Err(oracle::Error::NoDataFound) => return Ok(Self::default()),
+            Err(e) => return Err(e.into()),
+        };
+        let op_id = match (txid, seq_in_tx) {
+            (Some(txid), Some(seq_in_tx)) => Some(OpIdentifier::new(txid, seq_in_tx)),
+            _ => None,
+        };
+        Ok(Self {
+            source_state,
+            op_id,
+        })
+    }
+
+    /// Upserts the metadata of `sink_table`. It's committed with the rest of the transaction.
+    pub fn write(
+        &self,
+        connection: &Connection,
+        owner: Option<&str>,
+        sink_table: &str,
+    ) -> Result<(), OracleSinkError> {
+        let schema = Self::schema();
+        let values = [
+            Field::String(sink_table.to_string()),
+            self.op_id
+                .map_or(Field::Null, |op_id| Field::UInt(op_id.txid)),
+            self.op_id
+                .map_or(Field::Null, |op_id| Field::UInt(op_id.seq_in_tx)),
+            self.source_state.clone().map_or(Field::Null, Field::Binary),
+        ];
+        let params = values
+            .iter()
+            .zip(&schema.fields)
+            .map(|(value, field)| field_to_sql(value, &field.typ))
+            .collect::<Vec<_>>();
+        connection.execute(
+            &get_merge_query(
+                &qualified_table_name(owner, REPLICA_METADATA_TABLE),
+                &schema.fields,
+                &schema.primary_index,
+            ),
+            &params
+                .iter()
+                .map(AsRef::as_ref)
+                .collect::<Vec<&dyn ToSql>>(),
+        )?;
+        Ok(())
+    }
+}
@@ -0,0 +1,53 @@
+use dozer_types::types::{DecimalType, FieldDefinition, FieldType};
+
+pub fn map_field_to_type(field: &FieldDefinition) -> String {
+    let decimal;
+    let typ = match &field.typ {
+        FieldType::UInt => "NUMBER(20)",
+        FieldType::U128 => "NUMBER(39)",
+        FieldType::Int => "NUMBER(19)",
+        FieldType::Int8 => "NUMBER(3)",
+        FieldType::I128 => "NUMBER(39)",
+        FieldType::Float => "BINARY_DOUBLE",
+        // Oracle only has a BOOLEAN column type since 23c.
+        FieldType::Boolean => "NUMBER(1)",
+        FieldType::String | FieldType::Enum(_) => "VARCHAR2(4000)",
+        FieldType::Text => "CLOB",
+        FieldType::Binary => "BLOB",
+        // Oracle numbers have at most 38 digits.
+        FieldType::Decimal(Some(DecimalType { precision, scale })) if *precision <= 38 => {
+            decimal = format!("NUMBER({precision}, {scale})");
+            &decimal
+        }
+        FieldType::Decimal(_) => "NUMBER",
+        FieldType::Timestamp => "TIMESTAMP(9) WITH TIME ZONE",
+        FieldType::Date => "DATE",
+        // Oracle has no time of day type, so times are written as `HH:MM:SS.fffffffff`.
+        FieldType::Time => "VARCHAR2(18)",
+        FieldType::Uuid => "VARCHAR2(36)",
+        // Lists and structs are written as JSON.
+        FieldType::Json | FieldType::List(_) | FieldType::Struct(_) => "CLOB",
+        // Written as WKT.
+        FieldType::Point => "VARCHAR2(100)",
+        // Written as nanoseconds.
+        FieldType::Duration => "NUMBER(20)",
+    };
+
+    if field.nullable {
+        typ.to_string()
+    } else {
+        format!("{typ} NOT NULL")
+    }
+}
+
+/// LOB columns can't be compared, so they can't be part of the unique key.
+pub fn is_key_type(typ: &FieldType) -> bool {
+    !matches!(
+        typ,
+        FieldType::Text
+            | FieldType::Binary
+            | FieldType::Json
+            | FieldType::List(_)
+            | FieldType::Struct(_)
+    )
+}
@@ -0,0 +1,313 @@
+use dozer_core::epoch::Epoch;
+use dozer_core::event::EventHub;
+use dozer_core::node::{PortHandle, Sink, SinkFactory};
+use dozer_core::DEFAULT_PORT_HANDLE;
+use dozer_types::errors::internal::BoxedError;
+use dozer_types::log::debug;
+use dozer_types::models::ingestion_types::OracleConfig;
+use dozer_types::models::sink::OracleSinkConfig;
+use dozer_types::node::OpIdentifier;
+use dozer_types::tonic::async_trait;
+use dozer_types::types::{Field, FieldType, Operation, Record, Schema, TableOperation};
+use oracle::sql_type::ToSql;
+use oracle::Connection;
+use std::collections::HashMap;
+use std::fmt::Debug;
+
+use crate::client::{connect, create_table_if_not_exists};
+use crate::ddl::{get_delete_query, get_merge_query, qualified_table_name};
+use crate::errors::OracleSinkError;
+use crate::metadata::ReplicationMetadata;
+use crate::schema::is_key_type;
+use crate::types::field_to_sql;
+
+const BATCH_SIZE: u64 = 1000;
+
+#[derive(Debug)]
+pub struct OracleSinkFactory {
+    config: OracleSinkConfig,
+    connection: OracleConfig,
+}
+
+impl OracleSinkFactory {
+    pub fn new(config: OracleSinkConfig, connection: OracleConfig) -> Self {
+        Self { config, connection }
+    }
+}
+
+#[async_trait]
+impl SinkFactory for OracleSinkFactory {
+    fn type_name(&self) -> String {
+        "oracle".to_string()
+    }
+
+    fn get_input_ports(&self) -> Vec<PortHandle> {
+        vec![DEFAULT_PORT_HANDLE]
+    }
+
+    fn get_input_port_name(&self, _port: &PortHandle) -> String {
+        self.config.table_name.clone()
+    }
+
+    fn prepare(&self, input_schemas: HashMap<PortHandle, Schema>) -> Result<(), BoxedError> {
+        debug_assert!(input_schemas.len() == 1);
+        let schema = &input_schemas[&DEFAULT_PORT_HANDLE];
+        get_unique_key(&self.config, schema)?;
+        Ok(())
+    }
+
+    async fn build(
+        &self,
+        mut input_schemas: HashMap<PortHandle, Schema>,
+        _event_hub: EventHub,
+    ) -> Result<Box<dyn Sink>, BoxedError> {
+        let schema = input_schemas.remove(&DEFAULT_PORT_HANDLE).unwrap();
+        let unique_key = get_unique_key(&self.config, &schema)?;
+
+        let connection = connect(&self.connection)?;
+        let owner = self.config.owner.as_deref();
+
+        // Create Sink Table
+        let table_name = qualified_table_name(owner, &self.config.table_name);
+        create_table_if_not_exists(
+            &connection,
+            owner,
+            &self.config.table_name,
+            &table_name,
+            &schema.fields,
+            &unique_key,
+        )?;
+
+        // Create Metadata Table
+        ReplicationMetadata::create_table(&connection, owner)?;
+        let metadata = ReplicationMetadata::read(&connection, owner, &self.config.table_name)?;
+
+        let sink = OracleSink {
+            merge_query: get_merge_query(&table_name, &schema.fields, &unique_key),
+            delete_query: get_delete_query(&table_name, &schema.fields, &unique_key),
+            connection,
+            config: self.config.clone(),
+            schema,
+            unique_key,
+            batch: HashMap::new(),
+            metadata,
+        };
+        Ok(Box::new(sink))
+    }
+}
+
+/// Indexes of the `unique_key` columns, or the schema's primary key if `unique_key` is not set.
+pub(crate) fn get_unique_key(
+    config: &OracleSinkConfig,
+    schema: &Schema,
+) -> Result<Vec<usize>, OracleSinkError> {
+    let unique_key = if config.unique_key.is_empty() {
+        schema.primary_index.clone()
+    } else {
+        config
+            .unique_key
+            .iter()
+            .map(|name| {
+                schema
+                    .get_field_index(name)
+                    .map(|(index, _)| index)
+                    .map_err(|_| {
+                        OracleSinkError::ColumnNotFound(name.clone(), config.table_name.clone())
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?
+    };
+    if unique_key.is_empty() {
+        return Err(OracleSinkError::NoUniqueKey(config.table_name.clone()));
+    }
+    for index in &unique_key {
+        let field = &schema.fields[*index];
+        if !is_key_type(&field.typ) {
+            return Err(OracleSinkError::UnsupportedKeyType(
+                field.name.clone(),
+                field.typ.clone(),
+            ));
+        }
+    }
+    Ok(unique_key)
+}
+
+pub(crate) struct OracleSink {
+    connection: Connection,
+    config: OracleSinkConfig,
+    schema: Schema,
+    unique_key: Vec<usize>,
+    merge_query: String,
+    delete_query: String,
+    /// Latest values of every changed row, by unique key. `None` deletes the row.
+    batch: HashMap<Vec<Field>, Option<Vec<Field>>>,
+    metadata: ReplicationMetadata,
+}
+
+impl Debug for OracleSink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OracleSink")
+            .field("config", &self.config)
+            .field("schema", &self.schema)
+            .field("unique_key", &self.unique_key)
+            .finish()
+    }
+}
+
+impl OracleSink {
+    fn key(&self, record: &Record) -> Vec<Field> {
+        self.unique_key
+            .iter()
+            .map(|index| record.values[*index].clone())
+            .collect()
+    }
+
+    fn upsert(&mut self, record: Record) {
+        self.batch.insert(self.key(&record), Some(record.values));
+    }
+
+    fn delete(&mut self, record: &Record) {
+        self.batch.insert(self.key(record), None);
+    }
+
+    fn execute_batch(
+        &self,
+        query: &str,
+        rows: Vec<Vec<Box<dyn ToSql>>>,
+    ) -> Result<(), OracleSinkError> {
+        if rows.is_empty() {
+            return Ok(());
+        }
+        let mut batch = self.connection.batch(query, rows.len()).build()?;
+        for row in &rows {
+            batch.append_row(&row.iter().map(AsRef::as_ref).collect::<Vec<&dyn ToSql>>())?;
+        }
+        batch.execute()?;
+        Ok(())
+    }
+
+    fn commit_batch(&mut self) -> Result<(), OracleSinkError> {
+        debug!(
+            "[Sink] Flushing {} rows to {}",
+            self.batch.len(),
+            self.config.table_name
+        );
+        let mut deletes = vec![];
+        let mut upserts = vec![];
+        for (key, values) in &self.batch {
+            match values {
+                Some(values) => upserts.push(to_params(
+                    values,
+                    self.schema.fields.iter().map(|field| &field.typ),
+                )),
+                None => deletes.push(to_params(
+                    key,
+                    self.unique_key
+                        .iter()
+                        .map(|index| &self.schema.fields[*index].typ),
+                )),
+            }
+        }
+        // Rows are changed at most once per batch, so deletes and upserts can be executed in any order.
+        let result = self
+            .execute_batch(&self.delete_query, deletes)
+            .and_then(|()| self.execute_batch(&self.merge_query, upserts))
+            .and_then(|()| {
+                self.metadata.write(
+                    &self.connection,
+                    self.config.owner.as_deref(),
+                    &self.config.table_name,
+                )
+            })
+            .and_then(|()| self.connection.commit().map_err(Into::into));
+        if result.is_err() {
+            self.connection.rollback()?;
+            return result;
+        }
+        self.batch.clear();
+        Ok(())
+    }
+}
+
+fn to_params<'a>(
+    values: &[Field],
+    types: impl Iterator<Item = &'a FieldType>,
+) -> Vec<Box<dyn ToSql>> {
+    values
+        .iter()
+        .zip(types)
+        .map(|(value, typ)| field_to_sql(value, typ))
+        .collect()
+}
+
+impl Sink for OracleSink {
+    fn commit(&mut self, _epoch_details: &Epoch) -> Result<(), BoxedError> {
+        Ok(())
+    }
+
+    fn process(&mut self, op: TableOperation) -> Result<(), BoxedError> {
+        if let Some(id) = op.id {
+            self.metadata.op_id = Some(id);
+        }
+        match op.op {
+            Operation::Insert { new } => self.upsert(new),
+            Operation::Delete { old } => self.delete(&old),
+            Operation::Update { old, new } => {
+                self.delete(&old);
+                self.upsert(new);
+            }
+            Operation::BatchInsert { new } => {
+                for record in new {
+                    self.upsert(record);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn on_source_snapshotting_started(
+        &mut self,
+        _connection_name: String,
+    ) -> Result<(), BoxedError> {
+        Ok(())
+    }
+
+    fn on_source_snapshotting_done(
+        &mut self,
+        _connection_name: String,
+        id: Option<OpIdentifier>,
+    ) -> Result<(), BoxedError> {
+        if id.is_some() {
+            self.metadata.op_id = id;
+        }
+        self.commit_batch()?;
+        Ok(())
+    }
+
+    fn set_source_state(&mut self, source_state: &[u8]) -> Result<(), BoxedError> {
+        self.metadata.source_state = Some(source_state.to_vec());
+        self.commit_batch()?;
+        Ok(())
+    }
+
+    fn get_source_state(&mut self) -> Result<Option<Vec<u8>>, BoxedError> {
+        Ok(self.metadata.source_state.clone())
+    }
+
+    fn get_latest_op_id(&mut self) -> Result<Option<OpIdentifier>, BoxedError> {
+        Ok(self.metadata.op_id)
+    }
+
+    fn preferred_batch_size(&self) -> Option<u64> {
+        Some(BATCH_SIZE)
+    }
+
+    fn flush_batch(&mut self) -> Result<(), BoxedError> {
+        self.commit_batch()?;
+        Ok(())
+    }
+
+    fn supports_batching(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,115 @@
+use dozer_types::models::sink::OracleSinkConfig;
+use dozer_types::types::{FieldDefinition, FieldType, Schema};
+
+use crate::ddl::{get_create_table_query, get_delete_query, get_merge_query, qualified_table_name};
+use crate::errors::OracleSinkError;
+use crate::sink::get_unique_key;
+
+fn get_sink_config(unique_key: Vec<String>) -> OracleSinkConfig {
+    OracleSinkConfig {
+        connection: "oracle".to_string(),
+        table_name: "sink_table".to_string(),
+        unique_key,
+        owner: Some("DOZER".to_string()),
+    }
+}
+
+fn get_dozer_schema() -> Schema {
+    Schema {
+        fields: vec![
+            FieldDefinition {
+                name: "id".to_string(),
+                typ: FieldType::UInt,
+                nullable: false,
+                source: Default::default(),
+                description: None,
+            },
+            FieldDefinition {
+                name: "data".to_string(),
+                typ: FieldType::String,
+                nullable: true,
+                source: Default::default(),
+                description: None,
+            },
+            FieldDefinition {
+                name: "payload".to_string(),
+                typ: FieldType::Json,
+                nullable: true,
+                source: Default::default(),
+                description: None,
+            },
+        ],
+        primary_index: vec![0],
+    }
+}
+
+#[test]
+fn test_create_table_query() {
+    let schema = get_dozer_schema();
+    let table_name = qualified_table_name(Some("DOZER"), "sink_table");
+    assert_eq!(
+        get_create_table_query(&table_name, &schema.fields, &[0]),
+        "CREATE TABLE \"DOZER\".\"sink_table\" (\n\
+        \"id\" NUMBER(20) NOT NULL,\n\
+        \"data\" VARCHAR2(4000),\n\
+        \"payload\" CLOB,\n\
+        PRIMARY KEY (\"id\")\n\
+        )"
+    );
+}
+
+#[test]
+fn test_merge_query() {
+    let schema = get_dozer_schema();
+    assert_eq!(
+        get_merge_query("\"sink_table\"", &schema.fields, &[0]),
+        "MERGE INTO \"sink_table\" D\n\
+        USING (SELECT :1 \"id\", :2 \"data\", :3 \"payload\" FROM DUAL) S\n\
+        ON (D.\"id\" = S.\"id\")\n\
+        WHEN MATCHED THEN UPDATE SET D.\"data\" = S.\"data\", D.\"payload\" = S.\"payload\"\n\
+        WHEN NOT MATCHED THEN INSERT (\"id\", \"data\", \"payload\") \
+        VALUES (S.\"id\", S.\"data\", S.\"payload\")"
+    );
+
+    // Nothing to update if every column is in the key.
+    let query = get_merge_query("\"sink_table\"", &schema.fields[..2], &[0, 1]);
+    assert!(!query.contains("WHEN MATCHED"));
+    assert!(query.contains("ON (D.\"id\" = S.\"id\" AND D.\"data\" = S.\"data\")"));
+}
+
+#[test]
+fn test_delete_query() {
+    let schema = get_dozer_schema();
+    assert_eq!(
+        get_delete_query("\"sink_table\"", &schema.fields, &[1, 0]),
+        "DELETE FROM \"sink_table\" WHERE \"data\" = :1 AND \"id\" = :2"
+    );
+}
+
+#[test]
+fn test_unique_key() {
+    let schema = get_dozer_schema();
+    assert_eq!(
+        get_unique_key(&get_sink_config(vec![]), &schema).unwrap(),
+        vec![0]
+    );
+    assert_eq!(
+        get_unique_key(&get_sink_config(vec!["data".to_string()]), &schema).unwrap(),
+        vec![1]
+    );
+    assert!(matches!(
+        get_unique_key(&get_sink_config(vec!["missing".to_string()]), &schema),
+        Err(OracleSinkError::ColumnNotFound(_, _))
+    ));
+    assert!(matches!(
+        get_unique_key(&get_sink_config(vec!["payload".to_string()]), &schema),
+        Err(OracleSinkError::UnsupportedKeyType(_, FieldType::Json))
+    ));
+
+    let mut schema = schema;
+    schema.primary_index.clear();
+    assert!(matches!(
+        get_unique_key(&get_sink_config(vec![]), &schema),
+        Err(OracleSinkError::NoUniqueKey(_))
+    ));
+}
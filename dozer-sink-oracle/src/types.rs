@@ -0,0 +1,60 @@
+use dozer_types::{
+    chrono::{DateTime, FixedOffset, NaiveDate},
+    json_types::{field_to_json_value, json_to_string},
+    types::{Field, FieldType, TIME_FORMAT},
+};
+use oracle::sql_type::ToSql;
+
+/// Converts a field to a bind parameter for a column of type [`map_field_to_type`](crate::schema::map_field_to_type).
+///
+/// `typ` is only used to give `NULL`s the type of their column.
+pub fn field_to_sql(field: &Field, typ: &FieldType) -> Box<dyn ToSql> {
+    match field {
+        Field::UInt(v) => Box::new(*v),
+        Field::U128(v) => Box::new(v.to_string()),
+        Field::Int(v) => Box::new(*v),
+        Field::Int8(v) => Box::new(i64::from(*v)),
+        Field::I128(v) => Box::new(v.to_string()),
+        Field::Float(v) => Box::new(v.0),
+        Field::Boolean(v) => Box::new(i64::from(*v)),
+        Field::String(v) | Field::Text(v) => Box::new(v.clone()),
+        Field::Binary(v) => Box::new(v.clone()),
+        Field::Decimal(v) => Box::new(v.to_string()),
+        Field::Timestamp(v) => Box::new(*v),
+        Field::Date(v) => Box::new(*v),
+        Field::Json(v) => Box::new(json_to_string(v)),
+        Field::Point(v) => Box::new(format!("POINT ({} {})", v.0.x(), v.0.y())),
+        Field::Duration(v) => Box::new(v.0.as_nanos() as i64),
+        Field::Null => null_of_type(typ),
+        Field::Uuid(v) => Box::new(v.to_string()),
+        Field::Time(v) => Box::new(v.format(TIME_FORMAT).to_string()),
+        Field::List(_) | Field::Struct(_) => {
+            Box::new(json_to_string(&field_to_json_value(field.clone())))
+        }
+    }
+}
+
+fn null_of_type(typ: &FieldType) -> Box<dyn ToSql> {
+    match typ {
+        FieldType::UInt => Box::new(None::<u64>),
+        FieldType::Int | FieldType::Int8 | FieldType::Boolean | FieldType::Duration => {
+            Box::new(None::<i64>)
+        }
+        FieldType::Float => Box::new(None::<f64>),
+        FieldType::Binary => Box::new(None::<Vec<u8>>),
+        FieldType::Timestamp => Box::new(None::<DateTime<FixedOffset>>),
+        FieldType::Date => Box::new(None::<NaiveDate>),
+        FieldType::U128
+        | FieldType::I128
+        | FieldType::String
+        | FieldType::Text
+        | FieldType::Decimal(_)
+        | FieldType::Json
+        | FieldType::Point
+        | FieldType::List(_)
+        | FieldType::Struct(_)
+        | FieldType::Uuid
+        | FieldType::Time
+        | FieldType::Enum(_) => Box::new(None::<String>),
+    }
+}
@@ -0,0 +1,71 @@
+use std::sync::Arc;
+
+use dozer_core::tokio;
+use dozer_ingestion_postgres::connection::helper::AcceptAllVerifier;
+use dozer_types::{
+    log::{debug, error},
+    models::connection::PostgresConfig,
+};
+use tokio_postgres::{config::SslMode, Client, Config, NoTls};
+
+use crate::errors::PostgresSinkError;
+
+pub fn map_connection_config(config: &PostgresConfig) -> Result<Config, PostgresSinkError> {
+    let config_replenished = config.replenish()?;
+    let mut config = Config::new();
+    config
+        .host(&config_replenished.host)
+        .port(config_replenished.port as u16)
+        .user(&config_replenished.user)
+        .dbname(&config_replenished.database)
+        .password(&config_replenished.password)
+        .ssl_mode(config_replenished.sslmode);
+    Ok(config)
+}
+
+/// Connects the same way the Postgres connector does, and drives the connection on the current runtime.
+pub async fn connect(config: &PostgresConfig) -> Result<Client, PostgresSinkError> {
+    let config = map_connection_config(config)?;
+
+    let mut roots = rustls::RootCertStore::empty();
+    for cert in
+        rustls_native_certs::load_native_certs().map_err(PostgresSinkError::LoadNativeCerts)?
+    {
+        if let Err(e) = roots.add(cert) {
+            debug!("Failed to add certificate: {}", e);
+        }
+    }
+    let mut rustls_config = rustls::ClientConfig::builder()
+        .with_root_certificates(roots)
+        .with_no_client_auth();
+
+    let client = match config.get_ssl_mode() {
+        SslMode::Disable => {
+            let (client, connection) = config.connect(NoTls).await?;
+            tokio::spawn(async move {
+                if let Err(e) = connection.await {
+                    error!("Postgres connection error: {}", e);
+                }
+            });
+            client
+        }
+        ssl_mode @ (SslMode::Prefer | SslMode::Require) => {
+            if ssl_mode == SslMode::Prefer {
+                rustls_config
+                    .dangerous()
+                    .set_certificate_verifier(Arc::new(AcceptAllVerifier {}));
+            }
+            let (client, connection) = config
+                .connect(tokio_postgres_rustls::MakeRustlsConnect::new(rustls_config))
+                .await?;
+            tokio::spawn(async move {
+                if let Err(e) = connection.await {
+                    error!("Postgres connection error: {}", e);
+                }
+            });
+            client
+        }
+        ssl_mode => return Err(PostgresSinkError::InvalidSslError(ssl_mode)),
+    };
+    Ok(client)
+}
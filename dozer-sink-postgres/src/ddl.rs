@@ -0,0 +1,112 @@
+use dozer_types::types::FieldDefinition;
+
+use crate::schema::map_field_to_type;
+
+pub fn quote_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+pub fn qualified_table_name(schema: Option<&str>, table_name: &str) -> String {
+    match schema {
+        Some(schema) => format!(
+            "{}.{}",
+            quote_identifier(schema),
+            quote_identifier(table_name)
+        ),
+        None => quote_identifier(table_name),
+    }
+}
+
+pub fn get_create_table_query(
+    table_name: &str,
+    fields: &[FieldDefinition],
+    primary_key: &[usize],
+) -> String {
+    let mut parts = fields
+        .iter()
+        .map(|field| {
+            format!(
+                "{} {}",
+                quote_identifier(&field.name),
+                map_field_to_type(field)
+            )
+        })
+        .collect::<Vec<_>>();
+    parts.push(format!(
+        "PRIMARY KEY ({})",
+        column_list(primary_key.iter().map(|index| &fields[*index]))
+    ));
+
+    format!(
+        "CREATE TABLE IF NOT EXISTS {table_name} (\n{}\n)",
+        parts.join(",\n")
+    )
+}
+
+/// Upserts a row. The parameters are the values of all `fields`, in order.
+pub fn get_upsert_query(
+    table_name: &str,
+    fields: &[FieldDefinition],
+    primary_key: &[usize],
+) -> String {
+    let placeholders = (1..=fields.len())
+        .map(|param| format!("${param}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let update = fields
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| !primary_key.contains(index))
+        .map(|(_, field)| {
+            let name = quote_identifier(&field.name);
+            format!("{name} = EXCLUDED.{name}")
+        })
+        .collect::<Vec<_>>();
+    let on_conflict = if update.is_empty() {
+        // Every column is in the key, so there's nothing to update.
+        "DO NOTHING".to_string()
+    } else {
+        format!("DO UPDATE SET {}", update.join(", "))
+    };
+
+    format!(
+        "INSERT INTO {table_name} ({}) VALUES ({placeholders}) ON CONFLICT ({}) {on_conflict}",
+        column_list(fields.iter()),
+        column_list(primary_key.iter().map(|index| &fields[*index])),
+    )
+}
+
+/// Deletes a row. The parameters are the values of the `primary_key` fields, in order.
+pub fn get_delete_query(
+    table_name: &str,
+    fields: &[FieldDefinition],
+    primary_key: &[usize],
+) -> String {
+    let condition = primary_key
+        .iter()
+        .enumerate()
+        .map(|(param, index)| {
+            format!(
+                "{} = ${}",
+                quote_identifier(&fields[*index].name),
+                param + 1
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(" AND ");
+    format!("DELETE FROM {table_name} WHERE {condition}")
+}
+
+pub fn get_copy_query(table_name: &str, fields: &[FieldDefinition]) -> String {
+    format!(
+        "COPY {table_name} ({}) FROM STDIN BINARY",
+        column_list(fields.iter())
+    )
+}
+
+fn column_list<'a>(fields: impl Iterator<Item = &'a FieldDefinition>) -> String {
+    fields
+        .map(|field| quote_identifier(&field.name))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
@@ -0,0 +1,26 @@
+use dozer_types::{
+    errors::types::DeserializationError,
+    thiserror::{self, Error},
+};
+use tokio_postgres::config::SslMode;
+
+#[derive(Error, Debug)]
+pub enum PostgresSinkError {
+    #[error("Failed to map configuration: {0}")]
+    WrongConnectionConfiguration(#[from] DeserializationError),
+
+    #[error("Invalid SslMode: {0:?}")]
+    InvalidSslError(SslMode),
+
+    #[error("Failed to load native certs: {0}")]
+    LoadNativeCerts(#[source] std::io::Error),
+
+    #[error("Postgres error: {0}")]
+    Postgres(#[from] tokio_postgres::Error),
+
+    #[error("Table {0} has no primary key")]
+    NoPrimaryKey(String),
+
+    #[error("Value {0} doesn't fit in a Postgres NUMERIC")]
+    NumericOutOfRange(String),
+}
@@ -0,0 +1,10 @@
+pub mod client;
+pub mod ddl;
+pub mod errors;
+pub mod metadata;
+pub mod schema;
+mod sink;
+pub use sink::PostgresSinkFactory;
+#[cfg(test)]
+mod tests;
+pub mod types;
@@ -0,0 +1,97 @@
+use dozer_types::node::OpIdentifier;
+use tokio_postgres::GenericClient;
+
+use crate::{
+    ddl::{qualified_table_name, quote_identifier},
+    errors::PostgresSinkError,
+};
+
+// Replication Metadata Constants
+pub const REPLICA_METADATA_TABLE: &str = "__dozer_replication_metadata";
+pub const META_TABLE_COL: &str = "table";
+pub const META_TXID_COL: &str = "txid";
+pub const META_SEQ_IN_TX_COL: &str = "seq_in_tx";
+pub const META_SOURCE_STATE_COL: &str = "source_state";
+
+/// The replication progress of a sink table, stored as one row of [`REPLICA_METADATA_TABLE`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ReplicationMetadata {
+    pub source_state: Option<Vec<u8>>,
+    pub op_id: Option<OpIdentifier>,
+}
+
+impl ReplicationMetadata {
+    pub async fn create_table(
+        client: &impl GenericClient,
+        schema: Option<&str>,
+    ) -> Result<(), PostgresSinkError> {
+        let query = format!(
+            "CREATE TABLE IF NOT EXISTS {} ({} TEXT PRIMARY KEY, {} BIGINT, {} BIGINT, {} BYTEA)",
+            qualified_table_name(schema, REPLICA_METADATA_TABLE),
+            quote_identifier(META_TABLE_COL),
+            quote_identifier(META_TXID_COL),
+            quote_identifier(META_SEQ_IN_TX_COL),
+            quote_identifier(META_SOURCE_STATE_COL),
+        );
+        client.batch_execute(&query).await?;
+        Ok(())
+    }
+
+    /// Reads the metadata of `sink_table`, which is empty if the sink has never written anything.
+    pub async fn read(
+        client: &impl GenericClient,
+        schema: Option<&str>,
+        sink_table: &str,
+    ) -> Result<Self, PostgresSinkError> {
+        let query = format!(
+            "SELECT {}, {}, {} FROM {} WHERE {} = $1",
+            quote_identifier(META_TXID_COL),
+            quote_identifier(META_SEQ_IN_TX_COL),
+            quote_identifier(META_SOURCE_STATE_COL),
+            qualified_table_name(schema, REPLICA_METADATA_TABLE),
+            quote_identifier(META_TABLE_COL),
+        );
+        let Some(row) = client.query_opt(&query, &[&sink_table]).await? else {
+            return Ok(Self::default());
+        };
+        let txid: Option<i64> = row.get(0);
+        let seq_in_tx: Option<i64> = row.get(1);
+        let op_id = match (txid, seq_in_tx) {
+            (Some(txid), Some(seq_in_tx)) => Some(OpIdentifier::new(txid as u64, seq_in_tx as u64)),
+            _ => None,
+        };
+        Ok(Self {
+            source_state: row.get(2),
+            op_id,
+        })
+    }
+
+    /// Upserts the metadata of `sink_table`. Written in the transaction of the data it describes.
+    pub async fn write(
+        &self,
+        client: &impl GenericClient,
+        schema: Option<&str>,
+        sink_table: &str,
+    ) -> Result<(), PostgresSinkError> {
+        let query = format!(
+            "INSERT INTO {table} ({name}, {txid}, {seq_in_tx}, {source_state}) VALUES ($1, $2, $3, $4) \
+            ON CONFLICT ({name}) DO UPDATE SET \
+            {txid} = EXCLUDED.{txid}, {seq_in_tx} = EXCLUDED.{seq_in_tx}, {source_state} = EXCLUDED.{source_state}",
+            table = qualified_table_name(schema, REPLICA_METADATA_TABLE),
+            name = quote_identifier(META_TABLE_COL),
+            txid = quote_identifier(META_TXID_COL),
+            seq_in_tx = quote_identifier(META_SEQ_IN_TX_COL),
+            source_state = quote_identifier(META_SOURCE_STATE_COL),
+        );
+        // Postgres has no unsigned integers. The bits are kept as is.
+        let txid = self.op_id.map(|op_id| op_id.txid as i64);
+        let seq_in_tx = self.op_id.map(|op_id| op_id.seq_in_tx as i64);
+        client
+            .execute(
+                &query,
+                &[&sink_table, &txid, &seq_in_tx, &self.source_state],
+            )
+            .await?;
+        Ok(())
+    }
+}
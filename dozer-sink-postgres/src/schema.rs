@@ -0,0 +1,59 @@
+use dozer_types::types::{DecimalType, FieldDefinition, FieldType};
+use tokio_postgres::types::Type;
+
+pub fn map_field_to_type(field: &FieldDefinition) -> String {
+    let decimal;
+    let typ = match &field.typ {
+        FieldType::UInt => "NUMERIC(20)",
+        FieldType::U128 => "NUMERIC(39)",
+        FieldType::Int => "BIGINT",
+        FieldType::Int8 => "SMALLINT",
+        FieldType::I128 => "NUMERIC(39)",
+        FieldType::Float => "DOUBLE PRECISION",
+        FieldType::Boolean => "BOOLEAN",
+        FieldType::String | FieldType::Enum(_) => "TEXT",
+        FieldType::Text => "TEXT",
+        FieldType::Binary => "BYTEA",
+        FieldType::Decimal(Some(DecimalType { precision, scale })) => {
+            decimal = format!("NUMERIC({precision}, {scale})");
+            &decimal
+        }
+        FieldType::Decimal(None) => "NUMERIC",
+        FieldType::Timestamp => "TIMESTAMPTZ",
+        FieldType::Date => "DATE",
+        FieldType::Time => "TIME",
+        FieldType::Uuid => "UUID",
+        // Lists and structs are written as JSON.
+        FieldType::Json | FieldType::List(_) | FieldType::Struct(_) => "JSONB",
+        FieldType::Point => "POINT",
+        // Written as nanoseconds.
+        FieldType::Duration => "BIGINT",
+    };
+
+    if field.nullable {
+        typ.to_string()
+    } else {
+        format!("{typ} NOT NULL")
+    }
+}
+
+/// The type of the column created by [`map_field_to_type`], for binary `COPY`.
+pub fn map_field_to_postgres_type(typ: &FieldType) -> Type {
+    match typ {
+        FieldType::UInt | FieldType::U128 | FieldType::I128 | FieldType::Decimal(_) => {
+            Type::NUMERIC
+        }
+        FieldType::Int | FieldType::Duration => Type::INT8,
+        FieldType::Int8 => Type::INT2,
+        FieldType::Float => Type::FLOAT8,
+        FieldType::Boolean => Type::BOOL,
+        FieldType::String | FieldType::Text | FieldType::Enum(_) => Type::TEXT,
+        FieldType::Binary => Type::BYTEA,
+        FieldType::Timestamp => Type::TIMESTAMPTZ,
+        FieldType::Date => Type::DATE,
+        FieldType::Time => Type::TIME,
+        FieldType::Uuid => Type::UUID,
+        FieldType::Json | FieldType::List(_) | FieldType::Struct(_) => Type::JSONB,
+        FieldType::Point => Type::POINT,
+    }
+}
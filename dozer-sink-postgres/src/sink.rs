@@ -0,0 +1,365 @@
+use dozer_core::epoch::Epoch;
+use dozer_core::event::EventHub;
+use dozer_core::node::{PortHandle, Sink, SinkFactory};
+use dozer_core::tokio::runtime::Runtime;
+use dozer_core::DEFAULT_PORT_HANDLE;
+use dozer_types::errors::internal::BoxedError;
+use dozer_types::log::debug;
+use dozer_types::models::connection::PostgresConfig;
+use dozer_types::models::sink::PostgresSinkConfig;
+use dozer_types::node::OpIdentifier;
+use dozer_types::tonic::async_trait;
+use dozer_types::types::{Field, Operation, Record, Schema, TableOperation};
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::pin::pin;
+use std::sync::Arc;
+use tokio_postgres::binary_copy::BinaryCopyInWriter;
+use tokio_postgres::types::ToSql;
+use tokio_postgres::Client;
+
+use crate::client::connect;
+use crate::ddl::{
+    get_copy_query, get_create_table_query, get_delete_query, get_upsert_query,
+    qualified_table_name,
+};
+use crate::errors::PostgresSinkError;
+use crate::metadata::ReplicationMetadata;
+use crate::schema::map_field_to_postgres_type;
+use crate::types::{field_to_sql, SqlValue};
+
+const BATCH_SIZE: u64 = 1000;
+
+#[derive(Debug)]
+pub struct PostgresSinkFactory {
+    runtime: Arc<Runtime>,
+    config: PostgresSinkConfig,
+    connection: PostgresConfig,
+}
+
+impl PostgresSinkFactory {
+    pub fn new(
+        config: PostgresSinkConfig,
+        connection: PostgresConfig,
+        runtime: Arc<Runtime>,
+    ) -> Self {
+        Self {
+            runtime,
+            config,
+            connection,
+        }
+    }
+}
+
+#[async_trait]
+impl SinkFactory for PostgresSinkFactory {
+    fn type_name(&self) -> String {
+        "postgres".to_string()
+    }
+
+    fn get_input_ports(&self) -> Vec<PortHandle> {
+        vec![DEFAULT_PORT_HANDLE]
+    }
+
+    fn get_input_port_name(&self, _port: &PortHandle) -> String {
+        self.config.source_table_name.clone()
+    }
+
+    fn prepare(&self, input_schemas: HashMap<PortHandle, Schema>) -> Result<(), BoxedError> {
+        debug_assert!(input_schemas.len() == 1);
+        if input_schemas[&DEFAULT_PORT_HANDLE].primary_index.is_empty() {
+            return Err(
+                PostgresSinkError::NoPrimaryKey(self.config.source_table_name.clone()).into(),
+            );
+        }
+        Ok(())
+    }
+
+    async fn build(
+        &self,
+        mut input_schemas: HashMap<PortHandle, Schema>,
+        _event_hub: EventHub,
+    ) -> Result<Box<dyn Sink>, BoxedError> {
+        let schema = input_schemas.remove(&DEFAULT_PORT_HANDLE).unwrap();
+        let pg_schema = self.config.schema.as_deref();
+        let table_name = qualified_table_name(pg_schema, &self.config.sink_table_name);
+
+        let client = connect(&self.connection).await?;
+
+        // Create Sink Table
+        client
+            .batch_execute(&get_create_table_query(
+                &table_name,
+                &schema.fields,
+                &schema.primary_index,
+            ))
+            .await
+            .map_err(PostgresSinkError::from)?;
+
+        // Create Metadata Table
+        ReplicationMetadata::create_table(&client, pg_schema).await?;
+        let metadata =
+            ReplicationMetadata::read(&client, pg_schema, &self.config.sink_table_name).await?;
+
+        let sink = PostgresSink {
+            upsert_query: get_upsert_query(&table_name, &schema.fields, &schema.primary_index),
+            delete_query: get_delete_query(&table_name, &schema.fields, &schema.primary_index),
+            copy_query: get_copy_query(&table_name, &schema.fields),
+            table_name,
+            client,
+            runtime: self.runtime.clone(),
+            config: self.config.clone(),
+            schema,
+            batch: Batch::default(),
+            // A sink that has committed an op id is resuming, and its rows must be kept.
+            truncate_before_snapshot: metadata.op_id.is_none(),
+            copy_snapshot: None,
+            metadata,
+        };
+        Ok(Box::new(sink))
+    }
+}
+
+/// Changes that are not written yet.
+#[derive(Debug, Default)]
+struct Batch {
+    /// Inserts of the snapshot, written with `COPY`.
+    copy: Vec<Vec<Field>>,
+    /// Latest values of every other changed row, by primary key. `None` deletes the row.
+    rows: HashMap<Vec<Field>, Option<Vec<Field>>>,
+    /// Whether the table must be emptied before writing the snapshot.
+    truncate: bool,
+}
+
+impl Batch {
+    fn is_empty(&self) -> bool {
+        self.copy.is_empty() && self.rows.is_empty() && !self.truncate
+    }
+
+    fn len(&self) -> usize {
+        self.copy.len() + self.rows.len()
+    }
+}
+
+pub(crate) struct PostgresSink {
+    client: Client,
+    runtime: Arc<Runtime>,
+    config: PostgresSinkConfig,
+    schema: Schema,
+    table_name: String,
+    upsert_query: String,
+    delete_query: String,
+    copy_query: String,
+    batch: Batch,
+    /// Whether the table has no rows of this run or a previous one yet, so the first snapshot can empty it.
+    truncate_before_snapshot: bool,
+    /// The connection whose snapshot is written with `COPY`. Only set when its snapshot started on an emptied table.
+    copy_snapshot: Option<String>,
+    metadata: ReplicationMetadata,
+}
+
+impl Debug for PostgresSink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PostgresSink")
+            .field("table_name", &self.table_name)
+            .field("schema", &self.schema)
+            .finish()
+    }
+}
+
+impl PostgresSink {
+    fn key(&self, record: &Record) -> Vec<Field> {
+        self.schema
+            .primary_index
+            .iter()
+            .map(|index| record.values[*index].clone())
+            .collect()
+    }
+
+    fn insert(&mut self, record: Record) {
+        let key = self.key(&record);
+        // Rows that were changed in this batch can't be copied, as the copy is written first.
+        if self.copy_snapshot.is_some() && !self.batch.rows.contains_key(&key) {
+            self.batch.copy.push(record.values);
+        } else {
+            self.batch.rows.insert(key, Some(record.values));
+        }
+    }
+
+    fn delete(&mut self, record: &Record) {
+        self.batch.rows.insert(self.key(record), None);
+    }
+
+    fn to_params(
+        &self,
+        values: &[Field],
+        indexes: &[usize],
+    ) -> Result<Vec<SqlValue>, PostgresSinkError> {
+        values
+            .iter()
+            .zip(indexes)
+            .map(|(value, index)| field_to_sql(value, &self.schema.fields[*index].typ))
+            .collect()
+    }
+
+    fn commit_batch(&mut self) -> Result<(), PostgresSinkError> {
+        debug!(
+            "[Sink] Flushing {} rows to {}",
+            self.batch.len(),
+            self.table_name
+        );
+        let all_fields = (0..self.schema.fields.len()).collect::<Vec<_>>();
+        let copy = self
+            .batch
+            .copy
+            .iter()
+            .map(|values| self.to_params(values, &all_fields))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut deletes = vec![];
+        let mut upserts = vec![];
+        for (key, values) in &self.batch.rows {
+            match values {
+                Some(values) => upserts.push(self.to_params(values, &all_fields)?),
+                None => deletes.push(self.to_params(key, &self.schema.primary_index)?),
+            }
+        }
+        let types = self
+            .schema
+            .fields
+            .iter()
+            .map(|field| map_field_to_postgres_type(&field.typ))
+            .collect::<Vec<_>>();
+
+        let runtime = self.runtime.clone();
+        runtime.block_on(async {
+            let transaction = self.client.transaction().await?;
+            if self.batch.truncate {
+                transaction
+                    .batch_execute(&format!("TRUNCATE {}", self.table_name))
+                    .await?;
+            }
+            if !copy.is_empty() {
+                let sink = transaction.copy_in(&self.copy_query).await?;
+                let mut writer = pin!(BinaryCopyInWriter::new(sink, &types));
+                for row in &copy {
+                    writer.as_mut().write(&as_params(row)).await?;
+                }
+                writer.finish().await?;
+            }
+            // Rows are changed at most once per batch, so deletes and upserts can be executed in any order.
+            let statement = transaction.prepare(&self.delete_query).await?;
+            for row in &deletes {
+                transaction.execute(&statement, &as_params(row)).await?;
+            }
+            let statement = transaction.prepare(&self.upsert_query).await?;
+            for row in &upserts {
+                transaction.execute(&statement, &as_params(row)).await?;
+            }
+            self.metadata
+                .write(
+                    &transaction,
+                    self.config.schema.as_deref(),
+                    &self.config.sink_table_name,
+                )
+                .await?;
+            transaction.commit().await?;
+            Ok::<(), PostgresSinkError>(())
+        })?;
+
+        self.batch = Batch::default();
+        Ok(())
+    }
+}
+
+fn as_params(row: &[SqlValue]) -> Vec<&(dyn ToSql + Sync)> {
+    row.iter().map(AsRef::as_ref).collect()
+}
+
+impl Sink for PostgresSink {
+    fn commit(&mut self, _epoch_details: &Epoch) -> Result<(), BoxedError> {
+        Ok(())
+    }
+
+    fn process(&mut self, op: TableOperation) -> Result<(), BoxedError> {
+        if let Some(id) = op.id {
+            self.metadata.op_id = Some(id);
+        }
+        if self.copy_snapshot.is_none() {
+            self.truncate_before_snapshot = false;
+        }
+        match op.op {
+            Operation::Insert { new } => self.insert(new),
+            Operation::Delete { old } => self.delete(&old),
+            Operation::Update { old, new } => {
+                self.delete(&old);
+                self.batch.rows.insert(self.key(&new), Some(new.values));
+            }
+            Operation::BatchInsert { new } => {
+                for record in new {
+                    self.insert(record);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The first snapshot of a sink that isn't resuming empties the table, and is written with `COPY`.
+    /// `COPY` fails on rows that already exist, so any later or concurrent snapshot is upserted instead.
+    fn on_source_snapshotting_started(
+        &mut self,
+        connection_name: String,
+    ) -> Result<(), BoxedError> {
+        if self.truncate_before_snapshot {
+            self.truncate_before_snapshot = false;
+            self.batch.truncate = true;
+            self.copy_snapshot = Some(connection_name);
+        } else {
+            self.copy_snapshot = None;
+        }
+        Ok(())
+    }
+
+    fn on_source_snapshotting_done(
+        &mut self,
+        connection_name: String,
+        id: Option<OpIdentifier>,
+    ) -> Result<(), BoxedError> {
+        if self.copy_snapshot.as_ref() == Some(&connection_name) {
+            self.copy_snapshot = None;
+        }
+        if id.is_some() {
+            self.metadata.op_id = id;
+        }
+        self.commit_batch()?;
+        Ok(())
+    }
+
+    fn set_source_state(&mut self, source_state: &[u8]) -> Result<(), BoxedError> {
+        self.metadata.source_state = Some(source_state.to_vec());
+        self.commit_batch()?;
+        Ok(())
+    }
+
+    fn get_source_state(&mut self) -> Result<Option<Vec<u8>>, BoxedError> {
+        Ok(self.metadata.source_state.clone())
+    }
+
+    fn get_latest_op_id(&mut self) -> Result<Option<OpIdentifier>, BoxedError> {
+        Ok(self.metadata.op_id)
+    }
+
+    fn preferred_batch_size(&self) -> Option<u64> {
+        Some(BATCH_SIZE)
+    }
+
+    fn flush_batch(&mut self) -> Result<(), BoxedError> {
+        if !self.batch.is_empty() {
+            self.commit_batch()?;
+        }
+        Ok(())
+    }
+
+    fn supports_batching(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,97 @@
+use dozer_types::types::{DecimalType, FieldDefinition, FieldType};
+
+use crate::ddl::{
+    get_copy_query, get_create_table_query, get_delete_query, get_upsert_query,
+    qualified_table_name,
+};
+use crate::schema::map_field_to_type;
+
+fn get_fields() -> Vec<FieldDefinition> {
+    vec![
+        FieldDefinition {
+            name: "id".to_string(),
+            typ: FieldType::UInt,
+            nullable: false,
+            source: Default::default(),
+            description: None,
+        },
+        FieldDefinition {
+            name: "data".to_string(),
+            typ: FieldType::String,
+            nullable: true,
+            source: Default::default(),
+            description: None,
+        },
+    ]
+}
+
+#[test]
+fn test_create_table_query() {
+    let table_name = qualified_table_name(Some("public"), "sink_table");
+    assert_eq!(
+        get_create_table_query(&table_name, &get_fields(), &[0]),
+        "CREATE TABLE IF NOT EXISTS \"public\".\"sink_table\" (\n\
+        \"id\" NUMERIC(20) NOT NULL,\n\
+        \"data\" TEXT,\n\
+        PRIMARY KEY (\"id\")\n\
+        )"
+    );
+}
+
+#[test]
+fn test_column_types() {
+    let column_type = |typ| {
+        map_field_to_type(&FieldDefinition {
+            name: "column".to_string(),
+            typ,
+            nullable: true,
+            source: Default::default(),
+            description: None,
+        })
+    };
+    assert_eq!(
+        column_type(FieldType::Decimal(Some(DecimalType::new(10, 2)))),
+        "NUMERIC(10, 2)"
+    );
+    assert_eq!(column_type(FieldType::Decimal(None)), "NUMERIC");
+    assert_eq!(column_type(FieldType::Uuid), "UUID");
+    assert_eq!(column_type(FieldType::Time), "TIME");
+    assert_eq!(
+        column_type(FieldType::Enum(vec!["a".to_string(), "b".to_string()])),
+        "TEXT"
+    );
+}
+
+#[test]
+fn test_upsert_query() {
+    assert_eq!(
+        get_upsert_query("\"sink_table\"", &get_fields(), &[0]),
+        "INSERT INTO \"sink_table\" (\"id\", \"data\") VALUES ($1, $2) \
+        ON CONFLICT (\"id\") DO UPDATE SET \"data\" = EXCLUDED.\"data\""
+    );
+    assert_eq!(
+        get_upsert_query("\"sink_table\"", &get_fields(), &[0, 1]),
+        "INSERT INTO \"sink_table\" (\"id\", \"data\") VALUES ($1, $2) \
+        ON CONFLICT (\"id\", \"data\") DO NOTHING"
+    );
+}
+
+#[test]
+fn test_delete_and_copy_queries() {
+    assert_eq!(
+        get_delete_query("\"sink_table\"", &get_fields(), &[1, 0]),
+        "DELETE FROM \"sink_table\" WHERE \"data\" = $1 AND \"id\" = $2"
+    );
+    assert_eq!(
+        get_copy_query("\"sink_table\"", &get_fields()),
+        "COPY \"sink_table\" (\"id\", \"data\") FROM STDIN BINARY"
+    );
+}
+
+#[test]
+fn test_quote_identifier() {
+    assert_eq!(
+        qualified_table_name(None, "weird\"name"),
+        "\"weird\"\"name\""
+    );
+}
@@ -0,0 +1,68 @@
+use dozer_types::{
+    chrono::{DateTime, FixedOffset, NaiveDate, NaiveTime},
+    geo::Point,
+    json_types::{field_to_json_value, JsonValue},
+    rust_decimal::Decimal,
+    types::{Field, FieldType},
+    uuid::Uuid,
+};
+use tokio_postgres::types::{Json, ToSql};
+
+use crate::errors::PostgresSinkError;
+
+pub type SqlValue = Box<dyn ToSql + Sync>;
+
+/// Converts a field to a parameter for a column of type [`map_field_to_type`](crate::schema::map_field_to_type).
+///
+/// `typ` is only used to give `NULL`s the type of their column.
+pub fn field_to_sql(field: &Field, typ: &FieldType) -> Result<SqlValue, PostgresSinkError> {
+    Ok(match field {
+        Field::UInt(v) => Box::new(Decimal::from(*v)),
+        Field::U128(v) => Box::new(numeric(i128::try_from(*v).ok(), v)?),
+        Field::Int(v) => Box::new(*v),
+        Field::Int8(v) => Box::new(i16::from(*v)),
+        Field::I128(v) => Box::new(numeric(Some(*v), v)?),
+        Field::Float(v) => Box::new(v.0),
+        Field::Boolean(v) => Box::new(*v),
+        Field::String(v) | Field::Text(v) => Box::new(v.clone()),
+        Field::Binary(v) => Box::new(v.clone()),
+        Field::Decimal(v) => Box::new(*v),
+        Field::Timestamp(v) => Box::new(*v),
+        Field::Date(v) => Box::new(*v),
+        Field::Json(v) => Box::new(Json(v.clone())),
+        Field::Point(v) => Box::new(Point::new(v.0.x().0, v.0.y().0)),
+        Field::Duration(v) => Box::new(v.0.as_nanos() as i64),
+        Field::Null => null_of_type(typ),
+        Field::Uuid(v) => Box::new(*v),
+        Field::Time(v) => Box::new(*v),
+        Field::List(_) | Field::Struct(_) => Box::new(Json(field_to_json_value(field.clone()))),
+    })
+}
+
+fn numeric(value: Option<i128>, original: &impl ToString) -> Result<Decimal, PostgresSinkError> {
+    value
+        .and_then(|value| Decimal::try_from_i128_with_scale(value, 0).ok())
+        .ok_or_else(|| PostgresSinkError::NumericOutOfRange(original.to_string()))
+}
+
+fn null_of_type(typ: &FieldType) -> SqlValue {
+    match typ {
+        FieldType::UInt | FieldType::U128 | FieldType::I128 | FieldType::Decimal(_) => {
+            Box::new(None::<Decimal>)
+        }
+        FieldType::Int | FieldType::Duration => Box::new(None::<i64>),
+        FieldType::Int8 => Box::new(None::<i16>),
+        FieldType::Float => Box::new(None::<f64>),
+        FieldType::Boolean => Box::new(None::<bool>),
+        FieldType::String | FieldType::Text | FieldType::Enum(_) => Box::new(None::<String>),
+        FieldType::Binary => Box::new(None::<Vec<u8>>),
+        FieldType::Timestamp => Box::new(None::<DateTime<FixedOffset>>),
+        FieldType::Date => Box::new(None::<NaiveDate>),
+        FieldType::Time => Box::new(None::<NaiveTime>),
+        FieldType::Uuid => Box::new(None::<Uuid>),
+        FieldType::Json | FieldType::List(_) | FieldType::Struct(_) => {
+            Box::new(None::<Json<JsonValue>>)
+        }
+        FieldType::Point => Box::new(None::<Point<f64>>),
+    }
+}
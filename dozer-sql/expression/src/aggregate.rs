@@ -2,29 +2,57 @@ use std::fmt::{Display, Formatter};
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Hash, bincode::Encode, bincode::Decode)]
 pub enum AggregateFunctionType {
+    ApproxCountDistinct,
+    ArrayAgg,
     Avg,
+    BoolAnd,
+    BoolOr,
     Count,
+    /// `COUNT(DISTINCT ..)`, which has no function name of its own.
+    CountDistinct,
+    JsonAgg,
     Max,
     MaxAppendOnly,
     MaxValue,
+    Median,
     Min,
     MinAppendOnly,
     MinValue,
+    PercentileCont,
+    PercentileDisc,
+    StddevPop,
+    StddevSamp,
+    StringAgg,
     Sum,
+    VarPop,
+    VarSamp,
 }
 
 impl AggregateFunctionType {
-    pub(crate) fn new(name: &str) -> Option<AggregateFunctionType> {
+    pub fn new(name: &str) -> Option<AggregateFunctionType> {
         match name {
+            "approx_count_distinct" => Some(AggregateFunctionType::ApproxCountDistinct),
+            "array_agg" => Some(AggregateFunctionType::ArrayAgg),
             "avg" => Some(AggregateFunctionType::Avg),
+            "bool_and" => Some(AggregateFunctionType::BoolAnd),
+            "bool_or" => Some(AggregateFunctionType::BoolOr),
             "count" => Some(AggregateFunctionType::Count),
+            "json_agg" => Some(AggregateFunctionType::JsonAgg),
             "max" => Some(AggregateFunctionType::Max),
             "max_append_only" => Some(AggregateFunctionType::MaxAppendOnly),
             "max_value" => Some(AggregateFunctionType::MaxValue),
+            "median" => Some(AggregateFunctionType::Median),
             "min" => Some(AggregateFunctionType::Min),
             "min_append_only" => Some(AggregateFunctionType::MinAppendOnly),
             "min_value" => Some(AggregateFunctionType::MinValue),
+            "percentile_cont" => Some(AggregateFunctionType::PercentileCont),
+            "percentile_disc" => Some(AggregateFunctionType::PercentileDisc),
+            "stddev_pop" => Some(AggregateFunctionType::StddevPop),
+            "stddev" | "stddev_samp" => Some(AggregateFunctionType::StddevSamp),
+            "string_agg" => Some(AggregateFunctionType::StringAgg),
             "sum" => Some(AggregateFunctionType::Sum),
+            "var_pop" => Some(AggregateFunctionType::VarPop),
+            "variance" | "var_samp" => Some(AggregateFunctionType::VarSamp),
             _ => None,
         }
     }
@@ -33,15 +61,29 @@ impl AggregateFunctionType {
 impl Display for AggregateFunctionType {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
+            AggregateFunctionType::ApproxCountDistinct => f.write_str("APPROX_COUNT_DISTINCT"),
+            AggregateFunctionType::ArrayAgg => f.write_str("ARRAY_AGG"),
             AggregateFunctionType::Avg => f.write_str("AVG"),
+            AggregateFunctionType::BoolAnd => f.write_str("BOOL_AND"),
+            AggregateFunctionType::BoolOr => f.write_str("BOOL_OR"),
             AggregateFunctionType::Count => f.write_str("COUNT"),
+            AggregateFunctionType::CountDistinct => f.write_str("COUNT_DISTINCT"),
+            AggregateFunctionType::JsonAgg => f.write_str("JSON_AGG"),
             AggregateFunctionType::Max => f.write_str("MAX"),
             AggregateFunctionType::MaxAppendOnly => f.write_str("MAX_APPEND_ONLY"),
             AggregateFunctionType::MaxValue => f.write_str("MAX_VALUE"),
+            AggregateFunctionType::Median => f.write_str("MEDIAN"),
             AggregateFunctionType::Min => f.write_str("MIN"),
             AggregateFunctionType::MinAppendOnly => f.write_str("MIN_APPEND_ONLY"),
             AggregateFunctionType::MinValue => f.write_str("MIN_VALUE"),
+            AggregateFunctionType::PercentileCont => f.write_str("PERCENTILE_CONT"),
+            AggregateFunctionType::PercentileDisc => f.write_str("PERCENTILE_DISC"),
+            AggregateFunctionType::StddevPop => f.write_str("STDDEV_POP"),
+            AggregateFunctionType::StddevSamp => f.write_str("STDDEV_SAMP"),
+            AggregateFunctionType::StringAgg => f.write_str("STRING_AGG"),
             AggregateFunctionType::Sum => f.write_str("SUM"),
+            AggregateFunctionType::VarPop => f.write_str("VAR_POP"),
+            AggregateFunctionType::VarSamp => f.write_str("VAR_SAMP"),
         }
     }
 }
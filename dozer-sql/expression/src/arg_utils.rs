@@ -50,7 +50,7 @@ pub fn validate_arg_type(
     argument_index: usize,
 ) -> Result<ExpressionType, Error> {
     let arg_t = arg.get_type(schema)?;
-    if !expected.contains(&arg_t.return_type) {
+    if !expected.iter().any(|typ| typ.same_kind(&arg_t.return_type)) {
         Err(Error::InvalidFunctionArgumentType {
             function_name: function_name.to_string(),
             argument_index,
@@ -2,17 +2,17 @@ use std::sync::Arc;
 
 use crate::aggregate::AggregateFunctionType;
 use crate::conditional::ConditionalExpressionType;
-use crate::datetime::DateTimeFunctionType;
+use crate::datetime::{is_time_zone, DateTimeFunctionType};
 use crate::error::Error;
 use dozer_types::models::udf_config::{UdfConfig, UdfType};
-use dozer_types::types::FieldType;
+use dozer_types::types::{DecimalType, FieldType};
 use dozer_types::{
     ordered_float::OrderedFloat,
     types::{Field, FieldDefinition, Schema, SourceDefinition},
 };
 use sqlparser::ast::{
-    BinaryOperator as SqlBinaryOperator, DataType, DateTimeField, Expr as SqlExpr, Expr, Function,
-    FunctionArg, FunctionArgExpr, Ident, Interval, TrimWhereField,
+    ArrayAgg, BinaryOperator as SqlBinaryOperator, DataType, DateTimeField, ExactNumberInfo,
+    Expr as SqlExpr, Expr, Function, FunctionArg, FunctionArgExpr, Ident, Interval, TrimWhereField,
     UnaryOperator as SqlUnaryOperator, Value as SqlValue,
 };
 use tokio::runtime::Runtime;
@@ -23,6 +23,7 @@ use crate::geo::common::GeoFunctionType;
 use crate::json_functions::JsonFunctionType;
 use crate::operator::{BinaryOperatorType, UnaryOperatorType};
 use crate::scalar::common::ScalarFunctionType;
+use crate::scalar::regexp::{RegexCache, RegexpFunctionType};
 use crate::scalar::string::TrimType;
 
 use super::cast::CastOperatorType;
@@ -89,7 +90,9 @@ impl ExpressionBuilder {
                 .await
             }
             SqlExpr::Identifier(ident) => Self::parse_sql_column(&[ident.clone()], schema),
-            SqlExpr::CompoundIdentifier(ident) => Self::parse_sql_column(ident, schema),
+            SqlExpr::CompoundIdentifier(ident) => {
+                Self::parse_sql_compound_identifier(ident, schema)
+            }
             SqlExpr::Value(SqlValue::Number(n, _)) => Self::parse_sql_number(n),
             SqlExpr::Value(SqlValue::Null) => Ok(Expression::Literal(Field::Null)),
             SqlExpr::Value(SqlValue::SingleQuotedString(s) | SqlValue::DoubleQuotedString(s)) => {
@@ -111,6 +114,10 @@ impl ExpressionBuilder {
                 self.parse_sql_function(parse_aggregations, sql_function, schema, udfs)
                     .await
             }
+            SqlExpr::ArrayAgg(array_agg) => {
+                self.parse_sql_array_agg(parse_aggregations, array_agg, schema, udfs)
+                    .await
+            }
             SqlExpr::Like {
                 negated,
                 expr,
@@ -123,11 +130,59 @@ impl ExpressionBuilder {
                     expr,
                     pattern,
                     escape_char,
+                    false,
                     schema,
                     udfs,
                 )
                 .await
             }
+            SqlExpr::ILike {
+                negated,
+                expr,
+                pattern,
+                escape_char,
+            } => {
+                self.parse_sql_like_operator(
+                    parse_aggregations,
+                    negated,
+                    expr,
+                    pattern,
+                    escape_char,
+                    true,
+                    schema,
+                    udfs,
+                )
+                .await
+            }
+            SqlExpr::SimilarTo {
+                negated,
+                expr,
+                pattern,
+                escape_char,
+            } => {
+                self.parse_sql_similar_to_operator(
+                    parse_aggregations,
+                    negated,
+                    expr,
+                    pattern,
+                    escape_char,
+                    schema,
+                    udfs,
+                )
+                .await
+            }
+            SqlExpr::Position { expr, r#in } => {
+                let args = vec![
+                    self.parse_sql_expression(parse_aggregations, r#in, schema, udfs)
+                        .await?,
+                    self.parse_sql_expression(parse_aggregations, expr, schema, udfs)
+                        .await?,
+                ];
+                Ok(ScalarFunction {
+                    fun: ScalarFunctionType::Strpos,
+                    args,
+                })
+            }
             SqlExpr::InList {
                 expr,
                 list,
@@ -168,6 +223,13 @@ impl ExpressionBuilder {
                 )
                 .await
             }
+            SqlExpr::AtTimeZone {
+                timestamp,
+                time_zone,
+            } => {
+                self.parse_sql_at_time_zone(parse_aggregations, timestamp, time_zone, schema, udfs)
+                    .await
+            }
             SqlExpr::Case {
                 operand,
                 conditions,
@@ -193,10 +255,99 @@ impl ExpressionBuilder {
                 self.parse_sql_isnull_operator(parse_aggregations, &true, expr, schema, udfs)
                     .await
             }
+            SqlExpr::MapAccess { column, keys } => {
+                self.parse_sql_element_access(parse_aggregations, column, keys, schema, udfs)
+                    .await
+            }
+            SqlExpr::ArrayIndex { obj, indexes } => {
+                self.parse_sql_element_access(parse_aggregations, obj, indexes, schema, udfs)
+                    .await
+            }
+            SqlExpr::CompositeAccess { expr, key } => {
+                let arg = self
+                    .parse_sql_expression(parse_aggregations, expr, schema, udfs)
+                    .await?;
+                Self::parse_sql_struct_field(arg, key, schema)
+            }
             _ => Err(Error::UnsupportedExpression(expression.clone())),
         }
     }
 
+    /// Resolves `a.b.c` as a column, falling back to field access on a struct column
+    /// for any trailing identifiers that don't name a table or connection.
+    fn parse_sql_compound_identifier(
+        ident: &[Ident],
+        schema: &Schema,
+    ) -> Result<Expression, Error> {
+        let column_error = match Self::parse_sql_column(ident, schema) {
+            Ok(column) => return Ok(column),
+            Err(e) => e,
+        };
+
+        for split in (1..ident.len()).rev() {
+            let Ok(mut expression) = Self::parse_sql_column(&ident[..split], schema) else {
+                continue;
+            };
+            if !matches!(
+                expression.get_type(schema)?.return_type,
+                FieldType::Struct(_)
+            ) {
+                continue;
+            }
+            for key in &ident[split..] {
+                expression = Self::parse_sql_struct_field(expression, key, schema)?;
+            }
+            return Ok(expression);
+        }
+
+        Err(column_error)
+    }
+
+    fn parse_sql_struct_field(
+        arg: Expression,
+        key: &Ident,
+        schema: &Schema,
+    ) -> Result<Expression, Error> {
+        let typ = arg.get_type(schema)?.return_type;
+        let Some((index, field)) = typ.struct_field(&key.value) else {
+            return Err(Error::UnknownStructField {
+                name: key.value.clone(),
+                typ,
+            });
+        };
+
+        Ok(Expression::StructField {
+            name: field.name.clone(),
+            arg: Box::new(arg),
+            index,
+        })
+    }
+
+    async fn parse_sql_element_access(
+        &mut self,
+        parse_aggregations: bool,
+        expr: &Expr,
+        indexes: &[Expr],
+        schema: &Schema,
+        udfs: &[UdfConfig],
+    ) -> Result<Expression, Error> {
+        let mut expression = self
+            .parse_sql_expression(parse_aggregations, expr, schema, udfs)
+            .await?;
+        for index in indexes {
+            let index = self
+                .parse_sql_expression(parse_aggregations, index, schema, udfs)
+                .await?;
+            expression = Expression::ElementAt {
+                arg: Box::new(expression),
+                index: Box::new(index),
+            };
+            expression.get_type(schema)?;
+        }
+
+        Ok(expression)
+    }
+
     fn parse_sql_column(ident: &[Ident], schema: &Schema) -> Result<Expression, Error> {
         let (src_field, src_table_or_alias, src_connection) = match ident.len() {
             1 => (&ident[0].value, None, None),
@@ -311,7 +462,12 @@ impl ExpressionBuilder {
             return None;
         }
 
-        let aggr = AggregateFunctionType::new(function_name.as_str())?;
+        let aggr = match AggregateFunctionType::new(function_name.as_str())? {
+            AggregateFunctionType::Count if sql_function.distinct => {
+                AggregateFunctionType::CountDistinct
+            }
+            aggr => aggr,
+        };
 
         let mut arg_expr: Vec<Expression> = Vec::new();
         for arg in &sql_function.args {
@@ -321,10 +477,40 @@ impl ExpressionBuilder {
                 .ok()?;
             arg_expr.push(aggregation);
         }
-        let measure = Expression::AggregateFunction {
+        Some(self.add_aggregation(Expression::AggregateFunction {
             fun: aggr,
             args: arg_expr,
-        };
+        }))
+    }
+
+    /// `ARRAY_AGG` has its own syntax, which allows `ORDER BY` and `LIMIT` we don't support.
+    async fn parse_sql_array_agg(
+        &mut self,
+        parse_aggregations: bool,
+        array_agg: &ArrayAgg,
+        schema: &Schema,
+        udfs: &[UdfConfig],
+    ) -> Result<Expression, Error> {
+        if !parse_aggregations
+            || array_agg.distinct
+            || array_agg.order_by.is_some()
+            || array_agg.limit.is_some()
+        {
+            return Err(Error::UnsupportedExpression(SqlExpr::ArrayAgg(
+                array_agg.clone(),
+            )));
+        }
+        let arg = self
+            .parse_sql_expression(true, &array_agg.expr, schema, udfs)
+            .await?;
+        Ok(self.add_aggregation(Expression::AggregateFunction {
+            fun: AggregateFunctionType::ArrayAgg,
+            args: vec![arg],
+        }))
+    }
+
+    /// Returns the column of an aggregation, adding it if it's not already there.
+    fn add_aggregation(&mut self, measure: Expression) -> Expression {
         let index = match self
             .aggregations
             .iter()
@@ -337,9 +523,9 @@ impl ExpressionBuilder {
                 self.aggregations.len() - 1
             }
         };
-        Some(Expression::Column {
+        Expression::Column {
             index: self.offset + index,
-        })
+        }
     }
 
     async fn scalar_function_check(
@@ -366,6 +552,34 @@ impl ExpressionBuilder {
         })
     }
 
+    async fn regexp_function_check(
+        &mut self,
+        function_name: String,
+        parse_aggregations: bool,
+        sql_function: &Function,
+        schema: &Schema,
+        udfs: &[UdfConfig],
+    ) -> Option<Result<Expression, Error>> {
+        let fun = RegexpFunctionType::new(function_name.as_str())?;
+        let mut function_args: Vec<Expression> = Vec::new();
+        for arg in &sql_function.args {
+            function_args.push(
+                self.parse_sql_function_arg(parse_aggregations, arg, schema, udfs)
+                    .await
+                    .ok()?,
+            );
+        }
+
+        // An invalid literal pattern is an error when the query is built rather than for every record.
+        Some(
+            RegexCache::new(fun, &function_args).map(|regex| Expression::Regexp {
+                fun,
+                args: function_args,
+                regex,
+            }),
+        )
+    }
+
     async fn geo_expr_check(
         &mut self,
         function_name: String,
@@ -390,9 +604,32 @@ impl ExpressionBuilder {
         })
     }
 
-    fn datetime_expr_check(&mut self, function_name: String) -> Option<Expression> {
+    async fn datetime_expr_check(
+        &mut self,
+        function_name: String,
+        parse_aggregations: bool,
+        sql_function: &Function,
+        schema: &Schema,
+        udfs: &[UdfConfig],
+    ) -> Option<Expression> {
         let dtf = DateTimeFunctionType::new(function_name.as_str())?;
-        Some(Now { fun: dtf })
+        if dtf == DateTimeFunctionType::Now {
+            return Some(Now { fun: dtf });
+        }
+
+        let mut function_args: Vec<Expression> = Vec::new();
+        for arg in &sql_function.args {
+            function_args.push(
+                self.parse_sql_function_arg(parse_aggregations, arg, schema, udfs)
+                    .await
+                    .ok()?,
+            );
+        }
+
+        Some(Expression::DateTimeFunction {
+            fun: dtf,
+            args: function_args,
+        })
     }
 
     async fn json_func_check(
@@ -487,6 +724,19 @@ impl ExpressionBuilder {
             return Ok(scalar_check);
         }
 
+        if let Some(regexp_check) = self
+            .regexp_function_check(
+                function_name.clone(),
+                parse_aggregations,
+                sql_function,
+                schema,
+                udfs,
+            )
+            .await
+        {
+            return regexp_check;
+        }
+
         if let Some(geo_check) = self
             .geo_expr_check(
                 function_name.clone(),
@@ -513,7 +763,16 @@ impl ExpressionBuilder {
             return Ok(conditional_check);
         }
 
-        if let Some(datetime_check) = self.datetime_expr_check(function_name.clone()) {
+        if let Some(datetime_check) = self
+            .datetime_expr_check(
+                function_name.clone(),
+                parse_aggregations,
+                sql_function,
+                schema,
+                udfs,
+            )
+            .await
+        {
             return Ok(datetime_check);
         }
 
@@ -671,7 +930,7 @@ impl ExpressionBuilder {
                 fun: DateTimeFunctionType::Interval {
                     field: *leading_field,
                 },
-                arg: Box::new(right),
+                args: vec![right],
             })
         } else {
             Err(Error::MissingLeadingFieldInInterval)
@@ -772,6 +1031,7 @@ impl ExpressionBuilder {
         expr: &Expr,
         pattern: &Expr,
         escape_char: &Option<char>,
+        case_insensitive: bool,
         schema: &Schema,
         udfs: &[UdfConfig],
     ) -> Result<Expression, Error> {
@@ -785,6 +1045,7 @@ impl ExpressionBuilder {
             arg: Box::new(arg),
             pattern: Box::new(pattern),
             escape: *escape_char,
+            case_insensitive,
         };
         if *negated {
             Ok(Expression::UnaryOperator {
@@ -796,6 +1057,41 @@ impl ExpressionBuilder {
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
+    async fn parse_sql_similar_to_operator(
+        &mut self,
+        parse_aggregations: bool,
+        negated: &bool,
+        expr: &Expr,
+        pattern: &Expr,
+        escape_char: &Option<char>,
+        schema: &Schema,
+        udfs: &[UdfConfig],
+    ) -> Result<Expression, Error> {
+        let mut args = vec![
+            self.parse_sql_expression(parse_aggregations, expr, schema, udfs)
+                .await?,
+            self.parse_sql_expression(parse_aggregations, pattern, schema, udfs)
+                .await?,
+        ];
+        if let Some(escape_char) = escape_char {
+            args.push(Expression::Literal(Field::String(escape_char.to_string())));
+        }
+        let similar_to_expression = Expression::Regexp {
+            fun: RegexpFunctionType::SimilarTo,
+            regex: RegexCache::new(RegexpFunctionType::SimilarTo, &args)?,
+            args,
+        };
+        if *negated {
+            Ok(Expression::UnaryOperator {
+                operator: UnaryOperatorType::Not,
+                arg: Box::new(similar_to_expression),
+            })
+        } else {
+            Ok(similar_to_expression)
+        }
+    }
+
     async fn parse_sql_extract_operator(
         &mut self,
         parse_aggregations: bool,
@@ -809,7 +1105,29 @@ impl ExpressionBuilder {
             .await?;
         Ok(Expression::DateTimeFunction {
             fun: DateTimeFunctionType::Extract { field: *field },
-            arg: Box::new(right),
+            args: vec![right],
+        })
+    }
+
+    async fn parse_sql_at_time_zone(
+        &mut self,
+        parse_aggregations: bool,
+        timestamp: &Expr,
+        time_zone: &str,
+        schema: &Schema,
+        udfs: &[UdfConfig],
+    ) -> Result<Expression, Error> {
+        if !is_time_zone(time_zone) {
+            return Err(Error::UnknownTimeZone(time_zone.to_string()));
+        }
+        let arg = self
+            .parse_sql_expression(parse_aggregations, timestamp, schema, udfs)
+            .await?;
+        Ok(Expression::DateTimeFunction {
+            fun: DateTimeFunctionType::AtTimeZone {
+                time_zone: time_zone.to_string(),
+            },
+            args: vec![arg],
         })
     }
 
@@ -825,7 +1143,11 @@ impl ExpressionBuilder {
             .parse_sql_expression(parse_aggregations, expr, schema, udfs)
             .await?;
         let cast_to = match data_type {
-            DataType::Decimal(_) => CastOperatorType(FieldType::Decimal),
+            DataType::Decimal(info) | DataType::Numeric(info) => {
+                let decimal_type = decimal_type(info)
+                    .ok_or_else(|| Error::UnsupportedDataType(data_type.clone()))?;
+                CastOperatorType(FieldType::Decimal(decimal_type))
+            }
             DataType::Binary(_) => CastOperatorType(FieldType::Binary),
             DataType::Float(_) => CastOperatorType(FieldType::Float),
             DataType::Int(_) => CastOperatorType(FieldType::Int),
@@ -835,6 +1157,9 @@ impl ExpressionBuilder {
             DataType::Boolean => CastOperatorType(FieldType::Boolean),
             DataType::Date => CastOperatorType(FieldType::Date),
             DataType::Timestamp(..) => CastOperatorType(FieldType::Timestamp),
+            DataType::Time(..) => CastOperatorType(FieldType::Time),
+            DataType::Uuid => CastOperatorType(FieldType::Uuid),
+            DataType::Enum(values) => CastOperatorType(FieldType::Enum(values.clone())),
             DataType::Text => CastOperatorType(FieldType::Text),
             DataType::String => CastOperatorType(FieldType::String),
             DataType::JSON => CastOperatorType(FieldType::Json),
@@ -983,13 +1308,21 @@ impl ExpressionBuilder {
             args.push(arg);
         }
 
-        use crate::javascript::{validate_args, Udf};
-        validate_args(name.clone(), &args, schema)?;
+        use crate::javascript::{validate_args, Error as JavaScriptError, Udf};
+        let batch = config.batch.unwrap_or(false);
+        validate_args(name.clone(), &args, batch)?;
+        let return_type = match &config.return_type {
+            Some(return_type) => FieldType::try_from(return_type.as_str())
+                .map_err(JavaScriptError::InvalidReturnType)?,
+            None => FieldType::Json,
+        };
         let udf = Udf::new(
             self.runtime.clone(),
             name,
             config.module.clone(),
-            args.remove(0),
+            args,
+            return_type,
+            batch,
         )
         .await?;
         Ok(Expression::JavaScriptUdf(udf))
@@ -1062,3 +1395,20 @@ pub fn extend_schema_source_def(schema: &Schema, name: &NameOrAlias) -> Schema {
 
     output_schema
 }
+
+/// Precision and scale of a `DECIMAL(p, s)` type, `Some(None)` if there are none.
+/// Returns `None` if they are out of range, or the scale is larger than the precision.
+fn decimal_type(info: &ExactNumberInfo) -> Option<Option<DecimalType>> {
+    let (precision, scale) = match info {
+        ExactNumberInfo::None => return Some(None),
+        ExactNumberInfo::Precision(precision) => (*precision, 0),
+        ExactNumberInfo::PrecisionAndScale(precision, scale) => (*precision, *scale),
+    };
+    let precision = u8::try_from(precision)
+        .ok()
+        .filter(|precision| *precision > 0)?;
+    let scale = u8::try_from(scale)
+        .ok()
+        .filter(|scale| *scale <= precision)?;
+    Some(Some(DecimalType::new(precision, scale)))
+}
@@ -3,7 +3,8 @@ use std::fmt::{Display, Formatter};
 use dozer_types::types::Record;
 use dozer_types::{
     ordered_float::OrderedFloat,
-    types::{Field, FieldType, Schema},
+    rust_decimal::Decimal,
+    types::{DecimalType, Field, FieldType, Schema},
 };
 
 use crate::arg_utils::validate_arg_type;
@@ -17,7 +18,7 @@ pub struct CastOperatorType(pub FieldType);
 
 impl Display for CastOperatorType {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        match self.0 {
+        match &self.0 {
             FieldType::UInt => f.write_str("CAST AS UINT"),
             FieldType::U128 => f.write_str("CAST AS U128"),
             FieldType::Int => f.write_str("CAST AS INT"),
@@ -28,12 +29,20 @@ impl Display for CastOperatorType {
             FieldType::String => f.write_str("CAST AS STRING"),
             FieldType::Text => f.write_str("CAST AS TEXT"),
             FieldType::Binary => f.write_str("CAST AS BINARY"),
-            FieldType::Decimal => f.write_str("CAST AS DECIMAL"),
+            FieldType::Decimal(None) => f.write_str("CAST AS DECIMAL"),
             FieldType::Timestamp => f.write_str("CAST AS TIMESTAMP"),
             FieldType::Date => f.write_str("CAST AS DATE"),
             FieldType::Json => f.write_str("CAST AS JSON"),
             FieldType::Point => f.write_str("CAST AS POINT"),
             FieldType::Duration => f.write_str("CAST AS DURATION"),
+            FieldType::Uuid => f.write_str("CAST AS UUID"),
+            FieldType::Time => f.write_str("CAST AS TIME"),
+            typ @ (FieldType::Decimal(Some(_))
+            | FieldType::Enum(_)
+            | FieldType::List(_)
+            | FieldType::Struct(_)) => {
+                write!(f, "CAST AS {}", typ.to_string().to_uppercase())
+            }
         }
     }
 }
@@ -46,7 +55,7 @@ impl CastOperatorType {
         record: &Record,
     ) -> Result<Field, Error> {
         let field = arg.evaluate(record, schema)?;
-        cast_field(&field, self.0)
+        cast_field(&field, &self.0)
     }
 
     pub(crate) fn get_return_type(
@@ -54,7 +63,7 @@ impl CastOperatorType {
         schema: &Schema,
         arg: &Expression,
     ) -> Result<ExpressionType, Error> {
-        let (expected_input_type, return_type) = match self.0 {
+        let (expected_input_type, return_type) = match &self.0 {
             FieldType::UInt => (
                 vec![
                     FieldType::Int,
@@ -114,7 +123,7 @@ impl CastOperatorType {
             ),
             FieldType::Float => (
                 vec![
-                    FieldType::Decimal,
+                    FieldType::Decimal(None),
                     FieldType::Float,
                     FieldType::Int,
                     FieldType::I128,
@@ -128,7 +137,7 @@ impl CastOperatorType {
             FieldType::Boolean => (
                 vec![
                     FieldType::Boolean,
-                    FieldType::Decimal,
+                    FieldType::Decimal(None),
                     FieldType::Float,
                     FieldType::Int,
                     FieldType::I128,
@@ -143,7 +152,7 @@ impl CastOperatorType {
                     FieldType::Binary,
                     FieldType::Boolean,
                     FieldType::Date,
-                    FieldType::Decimal,
+                    FieldType::Decimal(None),
                     FieldType::Float,
                     FieldType::Int,
                     FieldType::I128,
@@ -153,6 +162,9 @@ impl CastOperatorType {
                     FieldType::UInt,
                     FieldType::U128,
                     FieldType::Json,
+                    FieldType::Uuid,
+                    FieldType::Time,
+                    FieldType::Enum(vec![]),
                 ],
                 FieldType::String,
             ),
@@ -161,7 +173,7 @@ impl CastOperatorType {
                     FieldType::Binary,
                     FieldType::Boolean,
                     FieldType::Date,
-                    FieldType::Decimal,
+                    FieldType::Decimal(None),
                     FieldType::Float,
                     FieldType::Int,
                     FieldType::I128,
@@ -171,13 +183,16 @@ impl CastOperatorType {
                     FieldType::UInt,
                     FieldType::U128,
                     FieldType::Json,
+                    FieldType::Uuid,
+                    FieldType::Time,
+                    FieldType::Enum(vec![]),
                 ],
                 FieldType::Text,
             ),
             FieldType::Binary => (vec![FieldType::Binary], FieldType::Binary),
-            FieldType::Decimal => (
+            typ @ FieldType::Decimal(_) => (
                 vec![
-                    FieldType::Decimal,
+                    FieldType::Decimal(None),
                     FieldType::Float,
                     FieldType::Int,
                     FieldType::I128,
@@ -185,7 +200,7 @@ impl CastOperatorType {
                     FieldType::UInt,
                     FieldType::U128,
                 ],
-                FieldType::Decimal,
+                typ.clone(),
             ),
             FieldType::Timestamp => (
                 vec![FieldType::String, FieldType::Timestamp],
@@ -222,6 +237,29 @@ impl CastOperatorType {
                 ],
                 FieldType::Duration,
             ),
+            FieldType::Uuid => (
+                vec![
+                    FieldType::Binary,
+                    FieldType::String,
+                    FieldType::Text,
+                    FieldType::Uuid,
+                ],
+                FieldType::Uuid,
+            ),
+            FieldType::Time => (
+                vec![
+                    FieldType::String,
+                    FieldType::Text,
+                    FieldType::Time,
+                    FieldType::Timestamp,
+                ],
+                FieldType::Time,
+            ),
+            typ @ FieldType::Enum(_) => (
+                vec![FieldType::String, FieldType::Text, FieldType::Enum(vec![])],
+                typ.clone(),
+            ),
+            typ @ (FieldType::List(_) | FieldType::Struct(_)) => (vec![typ.clone()], typ.clone()),
         };
 
         let expression_type = validate_arg_type(arg, expected_input_type, schema, self, 0)?;
@@ -234,7 +272,7 @@ impl CastOperatorType {
     }
 }
 
-pub fn cast_field(input: &Field, output_type: FieldType) -> Result<Field, Error> {
+pub fn cast_field(input: &Field, output_type: &FieldType) -> Result<Field, Error> {
     match output_type {
         FieldType::UInt => {
             if let Some(value) = input.to_uint() {
@@ -318,13 +356,17 @@ pub fn cast_field(input: &Field, output_type: FieldType) -> Result<Field, Error>
                 })
             }
         }
-        FieldType::Decimal => {
-            if let Some(value) = input.to_decimal() {
+        FieldType::Decimal(decimal_type) => {
+            let value = input.to_decimal().and_then(|value| match decimal_type {
+                Some(decimal_type) => rescale_decimal(value, *decimal_type),
+                None => Some(value),
+            });
+            if let Some(value) = value {
                 Ok(Field::Decimal(value))
             } else {
                 Err(Error::InvalidCast {
                     from: input.clone(),
-                    to: FieldType::Decimal,
+                    to: output_type.clone(),
                 })
             }
         }
@@ -378,5 +420,78 @@ pub fn cast_field(input: &Field, output_type: FieldType) -> Result<Field, Error>
                 })
             }
         }
+        FieldType::Uuid => {
+            if let Some(value) = input.to_uuid() {
+                Ok(Field::Uuid(value))
+            } else {
+                Err(Error::InvalidCast {
+                    from: input.clone(),
+                    to: FieldType::Uuid,
+                })
+            }
+        }
+        FieldType::Time => {
+            if let Some(value) = input.to_time() {
+                Ok(Field::Time(value))
+            } else {
+                Err(Error::InvalidCast {
+                    from: input.clone(),
+                    to: FieldType::Time,
+                })
+            }
+        }
+        FieldType::Enum(values) => match input.as_string().or_else(|| input.as_text()) {
+            Some(value) if values.iter().any(|v| v == value) => {
+                Ok(Field::String(value.to_string()))
+            }
+            _ => Err(Error::InvalidCast {
+                from: input.clone(),
+                to: output_type.clone(),
+            }),
+        },
+        FieldType::List(element_type) => match input.as_list() {
+            Some(elements) => Ok(Field::List(
+                elements
+                    .iter()
+                    .map(|element| cast_element(element, element_type))
+                    .collect::<Result<_, _>>()?,
+            )),
+            None => Err(Error::InvalidCast {
+                from: input.clone(),
+                to: output_type.clone(),
+            }),
+        },
+        FieldType::Struct(fields) => match input.as_struct() {
+            Some(values) if values.len() == fields.len() => Ok(Field::Struct(
+                values
+                    .iter()
+                    .zip(fields)
+                    .map(|(value, field)| cast_element(value, &field.typ))
+                    .collect::<Result<_, _>>()?,
+            )),
+            _ => Err(Error::InvalidCast {
+                from: input.clone(),
+                to: output_type.clone(),
+            }),
+        },
+    }
+}
+
+/// Rounds `value` to the declared scale. Values with more digits than the precision allows don't fit.
+fn rescale_decimal(mut value: Decimal, decimal_type: DecimalType) -> Option<Decimal> {
+    value.rescale(decimal_type.scale as u32);
+    let digits = value
+        .mantissa()
+        .unsigned_abs()
+        .checked_ilog10()
+        .map_or(1, |log| log + 1);
+    (digits <= decimal_type.precision as u32).then_some(value)
+}
+
+fn cast_element(input: &Field, output_type: &FieldType) -> Result<Field, Error> {
+    if input == &Field::Null {
+        Ok(Field::Null)
+    } else {
+        cast_field(input, output_type)
     }
 }
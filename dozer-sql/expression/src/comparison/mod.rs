@@ -114,7 +114,11 @@ macro_rules! define_comparison {
                     | Field::Json(_)
                     | Field::Point(_)
                     | Field::Duration(_)
-                    | Field::Null => Ok(Field::Null),
+                    | Field::Null
+                    | Field::List(_)
+                    | Field::Struct(_)
+                    | Field::Uuid(_)
+                    | Field::Time(_) => Ok(Field::Null),
                 },
                 Field::Int(left_v) => match right_p {
                     // left: Int, right: Int
@@ -160,7 +164,11 @@ macro_rules! define_comparison {
                     | Field::Timestamp(_)
                     | Field::Date(_)
                     | Field::Json(_)
-                    | Field::Point(_) => Err(PipelineError::InvalidTypeComparison(
+                    | Field::Point(_)
+                    | Field::List(_)
+                    | Field::Struct(_)
+                    | Field::Uuid(_)
+                    | Field::Time(_) => Err(PipelineError::InvalidTypeComparison(
                         left_p,
                         right_p,
                         $op.to_string(),
@@ -216,7 +224,11 @@ macro_rules! define_comparison {
                     | Field::Timestamp(_)
                     | Field::Date(_)
                     | Field::Json(_)
-                    | Field::Point(_) => Err(PipelineError::InvalidTypeComparison(
+                    | Field::Point(_)
+                    | Field::List(_)
+                    | Field::Struct(_)
+                    | Field::Uuid(_)
+                    | Field::Time(_) => Err(PipelineError::InvalidTypeComparison(
                         left_p,
                         right_p,
                         $op.to_string(),
@@ -265,7 +277,11 @@ macro_rules! define_comparison {
                     | Field::Timestamp(_)
                     | Field::Date(_)
                     | Field::Json(_)
-                    | Field::Point(_) => Err(PipelineError::InvalidTypeComparison(
+                    | Field::Point(_)
+                    | Field::List(_)
+                    | Field::Struct(_)
+                    | Field::Uuid(_)
+                    | Field::Time(_) => Err(PipelineError::InvalidTypeComparison(
                         left_p,
                         right_p,
                         $op.to_string(),
@@ -314,7 +330,11 @@ macro_rules! define_comparison {
                     | Field::Timestamp(_)
                     | Field::Date(_)
                     | Field::Json(_)
-                    | Field::Point(_) => Err(PipelineError::InvalidTypeComparison(
+                    | Field::Point(_)
+                    | Field::List(_)
+                    | Field::Struct(_)
+                    | Field::Uuid(_)
+                    | Field::Time(_) => Err(PipelineError::InvalidTypeComparison(
                         left_p,
                         right_p,
                         $op.to_string(),
@@ -367,7 +387,11 @@ macro_rules! define_comparison {
                     | Field::Timestamp(_)
                     | Field::Date(_)
                     | Field::Json(_)
-                    | Field::Point(_) => Err(PipelineError::InvalidTypeComparison(
+                    | Field::Point(_)
+                    | Field::List(_)
+                    | Field::Struct(_)
+                    | Field::Uuid(_)
+                    | Field::Time(_) => Err(PipelineError::InvalidTypeComparison(
                         left_p,
                         right_p,
                         $op.to_string(),
@@ -411,7 +435,11 @@ macro_rules! define_comparison {
                     | Field::Date(_)
                     | Field::Json(_)
                     | Field::Point(_)
-                    | Field::Duration(_) => Err(PipelineError::InvalidTypeComparison(
+                    | Field::Duration(_)
+                    | Field::List(_)
+                    | Field::Struct(_)
+                    | Field::Uuid(_)
+                    | Field::Time(_) => Err(PipelineError::InvalidTypeComparison(
                         left_p,
                         right_p,
                         $op.to_string(),
@@ -492,7 +520,11 @@ macro_rules! define_comparison {
                     | Field::Date(_)
                     | Field::Json(_)
                     | Field::Point(_)
-                    | Field::Duration(_) => Err(PipelineError::InvalidTypeComparison(
+                    | Field::Duration(_)
+                    | Field::List(_)
+                    | Field::Struct(_)
+                    | Field::Uuid(_)
+                    | Field::Time(_) => Err(PipelineError::InvalidTypeComparison(
                         left_p,
                         right_p,
                         $op.to_string(),
@@ -587,7 +619,12 @@ macro_rules! define_comparison {
                         })?;
                         Ok(Field::Boolean($function(left_val, right_v)))
                     }
-                    Field::Binary(_) | Field::Json(_) => Err(PipelineError::InvalidTypeComparison(
+                    Field::Binary(_)
+                    | Field::Json(_)
+                    | Field::List(_)
+                    | Field::Struct(_)
+                    | Field::Uuid(_)
+                    | Field::Time(_) => Err(PipelineError::InvalidTypeComparison(
                         left_p,
                         right_p,
                         $op.to_string(),
@@ -617,7 +654,11 @@ macro_rules! define_comparison {
                     | Field::Date(_)
                     | Field::Json(_)
                     | Field::Point(_)
-                    | Field::Duration(_) => Err(PipelineError::InvalidTypeComparison(
+                    | Field::Duration(_)
+                    | Field::List(_)
+                    | Field::Struct(_)
+                    | Field::Uuid(_)
+                    | Field::Time(_) => Err(PipelineError::InvalidTypeComparison(
                         left_p,
                         right_p,
                         $op.to_string(),
@@ -648,7 +689,11 @@ macro_rules! define_comparison {
                     | Field::Timestamp(_)
                     | Field::Json(_)
                     | Field::Point(_)
-                    | Field::Duration(_) => Err(PipelineError::InvalidTypeComparison(
+                    | Field::Duration(_)
+                    | Field::List(_)
+                    | Field::Struct(_)
+                    | Field::Uuid(_)
+                    | Field::Time(_) => Err(PipelineError::InvalidTypeComparison(
                         left_p,
                         right_p,
                         $op.to_string(),
@@ -675,7 +720,11 @@ macro_rules! define_comparison {
                     | Field::Timestamp(_)
                     | Field::Json(_)
                     | Field::Date(_)
-                    | Field::Duration(_) => Err(PipelineError::InvalidTypeComparison(
+                    | Field::Duration(_)
+                    | Field::List(_)
+                    | Field::Struct(_)
+                    | Field::Uuid(_)
+                    | Field::Time(_) => Err(PipelineError::InvalidTypeComparison(
                         left_p,
                         right_p,
                         $op.to_string(),
@@ -706,13 +755,22 @@ macro_rules! define_comparison {
                     | Field::Timestamp(_)
                     | Field::Json(_)
                     | Field::Date(_)
-                    | Field::Point(_) => Err(PipelineError::InvalidTypeComparison(
+                    | Field::Point(_)
+                    | Field::List(_)
+                    | Field::Struct(_)
+                    | Field::Uuid(_)
+                    | Field::Time(_) => Err(PipelineError::InvalidTypeComparison(
                         left_p,
                         right_p,
                         $op.to_string(),
                     )),
                 },
-                Field::Binary(_) | Field::Json(_) => Err(PipelineError::InvalidTypeComparison(
+                Field::Binary(_)
+                | Field::Json(_)
+                | Field::List(_)
+                | Field::Struct(_)
+                | Field::Uuid(_)
+                | Field::Time(_) => Err(PipelineError::InvalidTypeComparison(
                     left_p,
                     right_p,
                     $op.to_string(),
@@ -798,7 +856,11 @@ pub fn evaluate_lt(
             | Field::Json(_)
             | Field::Point(_)
             | Field::Duration(_)
-            | Field::Null => Ok(Field::Null),
+            | Field::Null
+            | Field::List(_)
+            | Field::Struct(_)
+            | Field::Uuid(_)
+            | Field::Time(_) => Ok(Field::Null),
         },
         Field::Int(left_v) => match right_p {
             // left: Int, right: Int
@@ -841,7 +903,11 @@ pub fn evaluate_lt(
             | Field::Timestamp(_)
             | Field::Date(_)
             | Field::Json(_)
-            | Field::Point(_) => Err(PipelineError::InvalidTypeComparison(
+            | Field::Point(_)
+            | Field::List(_)
+            | Field::Struct(_)
+            | Field::Uuid(_)
+            | Field::Time(_) => Err(PipelineError::InvalidTypeComparison(
                 left_p,
                 right_p,
                 "<".to_string(),
@@ -887,7 +953,11 @@ pub fn evaluate_lt(
             | Field::Timestamp(_)
             | Field::Date(_)
             | Field::Json(_)
-            | Field::Point(_) => Err(PipelineError::InvalidTypeComparison(
+            | Field::Point(_)
+            | Field::List(_)
+            | Field::Struct(_)
+            | Field::Uuid(_)
+            | Field::Time(_) => Err(PipelineError::InvalidTypeComparison(
                 left_p,
                 right_p,
                 "<".to_string(),
@@ -935,7 +1005,11 @@ pub fn evaluate_lt(
             | Field::Timestamp(_)
             | Field::Date(_)
             | Field::Json(_)
-            | Field::Point(_) => Err(PipelineError::InvalidTypeComparison(
+            | Field::Point(_)
+            | Field::List(_)
+            | Field::Struct(_)
+            | Field::Uuid(_)
+            | Field::Time(_) => Err(PipelineError::InvalidTypeComparison(
                 left_p,
                 right_p,
                 "<".to_string(),
@@ -980,7 +1054,11 @@ pub fn evaluate_lt(
             | Field::Timestamp(_)
             | Field::Date(_)
             | Field::Json(_)
-            | Field::Point(_) => Err(PipelineError::InvalidTypeComparison(
+            | Field::Point(_)
+            | Field::List(_)
+            | Field::Struct(_)
+            | Field::Uuid(_)
+            | Field::Time(_) => Err(PipelineError::InvalidTypeComparison(
                 left_p,
                 right_p,
                 "<".to_string(),
@@ -1026,7 +1104,11 @@ pub fn evaluate_lt(
             | Field::Timestamp(_)
             | Field::Date(_)
             | Field::Json(_)
-            | Field::Point(_) => Err(PipelineError::InvalidTypeComparison(
+            | Field::Point(_)
+            | Field::List(_)
+            | Field::Struct(_)
+            | Field::Uuid(_)
+            | Field::Time(_) => Err(PipelineError::InvalidTypeComparison(
                 left_p,
                 right_p,
                 "<".to_string(),
@@ -1068,7 +1150,11 @@ pub fn evaluate_lt(
             | Field::Date(_)
             | Field::Json(_)
             | Field::Point(_)
-            | Field::Duration(_) => Err(PipelineError::InvalidTypeComparison(
+            | Field::Duration(_)
+            | Field::List(_)
+            | Field::Struct(_)
+            | Field::Uuid(_)
+            | Field::Time(_) => Err(PipelineError::InvalidTypeComparison(
                 left_p,
                 right_p,
                 "<".to_string(),
@@ -1134,7 +1220,11 @@ pub fn evaluate_lt(
                 | Field::Date(_)
                 | Field::Json(_)
                 | Field::Point(_)
-                | Field::Duration(_) => Err(PipelineError::InvalidTypeComparison(
+                | Field::Duration(_)
+                | Field::List(_)
+                | Field::Struct(_)
+                | Field::Uuid(_)
+                | Field::Time(_) => Err(PipelineError::InvalidTypeComparison(
                     left_p,
                     right_p,
                     "<".to_string(),
@@ -1217,7 +1307,12 @@ pub fn evaluate_lt(
                 })?;
                 Ok(Field::Boolean(left_val < right_v))
             }
-            Field::Binary(_) | Field::Json(_) => Err(PipelineError::InvalidTypeComparison(
+            Field::Binary(_)
+            | Field::Json(_)
+            | Field::List(_)
+            | Field::Struct(_)
+            | Field::Uuid(_)
+            | Field::Time(_) => Err(PipelineError::InvalidTypeComparison(
                 left_p,
                 right_p,
                 "<".to_string(),
@@ -1244,7 +1339,11 @@ pub fn evaluate_lt(
             | Field::Date(_)
             | Field::Json(_)
             | Field::Point(_)
-            | Field::Duration(_) => Err(PipelineError::InvalidTypeComparison(
+            | Field::Duration(_)
+            | Field::List(_)
+            | Field::Struct(_)
+            | Field::Uuid(_)
+            | Field::Time(_) => Err(PipelineError::InvalidTypeComparison(
                 left_p,
                 right_p,
                 "<".to_string(),
@@ -1271,7 +1370,11 @@ pub fn evaluate_lt(
             | Field::Timestamp(_)
             | Field::Json(_)
             | Field::Point(_)
-            | Field::Duration(_) => Err(PipelineError::InvalidTypeComparison(
+            | Field::Duration(_)
+            | Field::List(_)
+            | Field::Struct(_)
+            | Field::Uuid(_)
+            | Field::Time(_) => Err(PipelineError::InvalidTypeComparison(
                 left_p,
                 right_p,
                 "<".to_string(),
@@ -1298,7 +1401,11 @@ pub fn evaluate_lt(
             | Field::Timestamp(_)
             | Field::Json(_)
             | Field::Date(_)
-            | Field::Duration(_) => Err(PipelineError::InvalidTypeComparison(
+            | Field::Duration(_)
+            | Field::List(_)
+            | Field::Struct(_)
+            | Field::Uuid(_)
+            | Field::Time(_) => Err(PipelineError::InvalidTypeComparison(
                 left_p,
                 right_p,
                 "<".to_string(),
@@ -1325,13 +1432,22 @@ pub fn evaluate_lt(
             | Field::Timestamp(_)
             | Field::Json(_)
             | Field::Date(_)
-            | Field::Point(_) => Err(PipelineError::InvalidTypeComparison(
+            | Field::Point(_)
+            | Field::List(_)
+            | Field::Struct(_)
+            | Field::Uuid(_)
+            | Field::Time(_) => Err(PipelineError::InvalidTypeComparison(
                 left_p,
                 right_p,
                 "<".to_string(),
             )),
         },
-        Field::Binary(_) | Field::Json(_) => Err(PipelineError::InvalidTypeComparison(
+        Field::Binary(_)
+        | Field::Json(_)
+        | Field::List(_)
+        | Field::Struct(_)
+        | Field::Uuid(_)
+        | Field::Time(_) => Err(PipelineError::InvalidTypeComparison(
             left_p,
             right_p,
             "<".to_string(),
@@ -1411,7 +1527,11 @@ pub fn evaluate_gt(
             | Field::Json(_)
             | Field::Point(_)
             | Field::Duration(_)
-            | Field::Null => Ok(Field::Null),
+            | Field::Null
+            | Field::List(_)
+            | Field::Struct(_)
+            | Field::Uuid(_)
+            | Field::Time(_) => Ok(Field::Null),
         },
         Field::Int(left_v) => match right_p {
             // left: Int, right: Int
@@ -1454,7 +1574,11 @@ pub fn evaluate_gt(
             | Field::Timestamp(_)
             | Field::Date(_)
             | Field::Json(_)
-            | Field::Point(_) => Err(PipelineError::InvalidTypeComparison(
+            | Field::Point(_)
+            | Field::List(_)
+            | Field::Struct(_)
+            | Field::Uuid(_)
+            | Field::Time(_) => Err(PipelineError::InvalidTypeComparison(
                 left_p,
                 right_p,
                 ">".to_string(),
@@ -1500,7 +1624,11 @@ pub fn evaluate_gt(
             | Field::Timestamp(_)
             | Field::Date(_)
             | Field::Json(_)
-            | Field::Point(_) => Err(PipelineError::InvalidTypeComparison(
+            | Field::Point(_)
+            | Field::List(_)
+            | Field::Struct(_)
+            | Field::Uuid(_)
+            | Field::Time(_) => Err(PipelineError::InvalidTypeComparison(
                 left_p,
                 right_p,
                 ">".to_string(),
@@ -1547,7 +1675,11 @@ pub fn evaluate_gt(
             | Field::Timestamp(_)
             | Field::Date(_)
             | Field::Json(_)
-            | Field::Point(_) => Err(PipelineError::InvalidTypeComparison(
+            | Field::Point(_)
+            | Field::List(_)
+            | Field::Struct(_)
+            | Field::Uuid(_)
+            | Field::Time(_) => Err(PipelineError::InvalidTypeComparison(
                 left_p,
                 right_p,
                 ">".to_string(),
@@ -1592,7 +1724,11 @@ pub fn evaluate_gt(
             | Field::Timestamp(_)
             | Field::Date(_)
             | Field::Json(_)
-            | Field::Point(_) => Err(PipelineError::InvalidTypeComparison(
+            | Field::Point(_)
+            | Field::List(_)
+            | Field::Struct(_)
+            | Field::Uuid(_)
+            | Field::Time(_) => Err(PipelineError::InvalidTypeComparison(
                 left_p,
                 right_p,
                 ">".to_string(),
@@ -1638,7 +1774,11 @@ pub fn evaluate_gt(
             | Field::Timestamp(_)
             | Field::Date(_)
             | Field::Json(_)
-            | Field::Point(_) => Err(PipelineError::InvalidTypeComparison(
+            | Field::Point(_)
+            | Field::List(_)
+            | Field::Struct(_)
+            | Field::Uuid(_)
+            | Field::Time(_) => Err(PipelineError::InvalidTypeComparison(
                 left_p,
                 right_p,
                 ">".to_string(),
@@ -1679,7 +1819,11 @@ pub fn evaluate_gt(
             | Field::Date(_)
             | Field::Json(_)
             | Field::Point(_)
-            | Field::Duration(_) => Err(PipelineError::InvalidTypeComparison(
+            | Field::Duration(_)
+            | Field::List(_)
+            | Field::Struct(_)
+            | Field::Uuid(_)
+            | Field::Time(_) => Err(PipelineError::InvalidTypeComparison(
                 left_p,
                 right_p,
                 ">".to_string(),
@@ -1745,7 +1889,11 @@ pub fn evaluate_gt(
                 | Field::Date(_)
                 | Field::Json(_)
                 | Field::Point(_)
-                | Field::Duration(_) => Err(PipelineError::InvalidTypeComparison(
+                | Field::Duration(_)
+                | Field::List(_)
+                | Field::Struct(_)
+                | Field::Uuid(_)
+                | Field::Time(_) => Err(PipelineError::InvalidTypeComparison(
                     left_p,
                     right_p,
                     ">".to_string(),
@@ -1828,7 +1976,12 @@ pub fn evaluate_gt(
                 })?;
                 Ok(Field::Boolean(left_val > right_v))
             }
-            Field::Binary(_) | Field::Json(_) => Err(PipelineError::InvalidTypeComparison(
+            Field::Binary(_)
+            | Field::Json(_)
+            | Field::List(_)
+            | Field::Struct(_)
+            | Field::Uuid(_)
+            | Field::Time(_) => Err(PipelineError::InvalidTypeComparison(
                 left_p,
                 right_p,
                 ">".to_string(),
@@ -1855,7 +2008,11 @@ pub fn evaluate_gt(
             | Field::Date(_)
             | Field::Json(_)
             | Field::Point(_)
-            | Field::Duration(_) => Err(PipelineError::InvalidTypeComparison(
+            | Field::Duration(_)
+            | Field::List(_)
+            | Field::Struct(_)
+            | Field::Uuid(_)
+            | Field::Time(_) => Err(PipelineError::InvalidTypeComparison(
                 left_p,
                 right_p,
                 ">".to_string(),
@@ -1882,7 +2039,11 @@ pub fn evaluate_gt(
             | Field::Timestamp(_)
             | Field::Json(_)
             | Field::Point(_)
-            | Field::Duration(_) => Err(PipelineError::InvalidTypeComparison(
+            | Field::Duration(_)
+            | Field::List(_)
+            | Field::Struct(_)
+            | Field::Uuid(_)
+            | Field::Time(_) => Err(PipelineError::InvalidTypeComparison(
                 left_p,
                 right_p,
                 ">".to_string(),
@@ -1909,7 +2070,11 @@ pub fn evaluate_gt(
             | Field::Timestamp(_)
             | Field::Json(_)
             | Field::Date(_)
-            | Field::Duration(_) => Err(PipelineError::InvalidTypeComparison(
+            | Field::Duration(_)
+            | Field::List(_)
+            | Field::Struct(_)
+            | Field::Uuid(_)
+            | Field::Time(_) => Err(PipelineError::InvalidTypeComparison(
                 left_p,
                 right_p,
                 ">".to_string(),
@@ -1936,13 +2101,22 @@ pub fn evaluate_gt(
             | Field::Timestamp(_)
             | Field::Json(_)
             | Field::Date(_)
-            | Field::Point(_) => Err(PipelineError::InvalidTypeComparison(
+            | Field::Point(_)
+            | Field::List(_)
+            | Field::Struct(_)
+            | Field::Uuid(_)
+            | Field::Time(_) => Err(PipelineError::InvalidTypeComparison(
                 left_p,
                 right_p,
                 ">".to_string(),
             )),
         },
-        Field::Binary(_) | Field::Json(_) => Err(PipelineError::InvalidTypeComparison(
+        Field::Binary(_)
+        | Field::Json(_)
+        | Field::List(_)
+        | Field::Struct(_)
+        | Field::Uuid(_)
+        | Field::Time(_) => Err(PipelineError::InvalidTypeComparison(
             left_p,
             right_p,
             ">".to_string(),
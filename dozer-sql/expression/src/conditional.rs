@@ -59,7 +59,7 @@ pub(crate) fn validate_coalesce(
         .iter()
         .map(|expr| expr.get_type(schema).unwrap().return_type)
         .collect::<Vec<FieldType>>();
-    let return_type = return_types[0];
+    let return_type = return_types[0].clone();
 
     Ok(ExpressionType::new(
         return_type,
@@ -97,7 +97,7 @@ pub(crate) fn validate_least(
         .iter()
         .map(|expr| Ok(expr.get_type(schema)?.return_type))
         .collect::<Result<Vec<FieldType>, Error>>()?;
-    let return_type = return_types[0];
+    let return_type = return_types[0].clone();
 
     Ok(ExpressionType::new(
         return_type,
@@ -171,20 +171,20 @@ mod tests {
             let row = Record::new(vec![f.clone()]);
 
             let mut args = vec![null.clone(), uint1.clone()];
-            test_validate_coalesce(&args, typ);
-            test_evaluate_coalesce(&mut args, &row, typ, f.clone());
+            test_validate_coalesce(&args, &typ);
+            test_evaluate_coalesce(&mut args, &row, &typ, f.clone());
 
             let mut args = vec![null.clone(), null.clone(), uint1.clone()];
-            test_validate_coalesce(&args, typ);
-            test_evaluate_coalesce(&mut args, &row, typ, f.clone());
+            test_validate_coalesce(&args, &typ);
+            test_evaluate_coalesce(&mut args, &row, &typ, f.clone());
 
             let mut args = vec![null.clone(), null.clone(), uint1.clone(), null.clone()];
-            test_validate_coalesce(&args, typ);
-            test_evaluate_coalesce(&mut args, &row, typ, f.clone());
+            test_validate_coalesce(&args, &typ);
+            test_evaluate_coalesce(&mut args, &row, &typ, f.clone());
 
             let mut args = vec![null.clone(), null.clone(), uint1, uint2];
-            test_validate_coalesce(&args, typ);
-            test_evaluate_coalesce(&mut args, &row, typ, f);
+            test_validate_coalesce(&args, &typ);
+            test_evaluate_coalesce(&mut args, &row, &typ, f);
 
             // Int
             let typ = FieldType::Int;
@@ -192,20 +192,20 @@ mod tests {
             let row = Record::new(vec![f.clone()]);
 
             let mut args = vec![null.clone(), int1.clone()];
-            test_validate_coalesce(&args, typ);
-            test_evaluate_coalesce(&mut args, &row, typ, f.clone());
+            test_validate_coalesce(&args, &typ);
+            test_evaluate_coalesce(&mut args, &row, &typ, f.clone());
 
             let mut args = vec![null.clone(), null.clone(), int1.clone()];
-            test_validate_coalesce(&args, typ);
-            test_evaluate_coalesce(&mut args, &row, typ, f.clone());
+            test_validate_coalesce(&args, &typ);
+            test_evaluate_coalesce(&mut args, &row, &typ, f.clone());
 
             let mut args = vec![null.clone(), null.clone(), int1.clone(), null.clone()];
-            test_validate_coalesce(&args, typ);
-            test_evaluate_coalesce(&mut args, &row, typ, f.clone());
+            test_validate_coalesce(&args, &typ);
+            test_evaluate_coalesce(&mut args, &row, &typ, f.clone());
 
             let mut args = vec![null.clone(), null.clone(), int1, int2];
-            test_validate_coalesce(&args, typ);
-            test_evaluate_coalesce(&mut args, &row, typ, f);
+            test_validate_coalesce(&args, &typ);
+            test_evaluate_coalesce(&mut args, &row, &typ, f);
 
             // Float
             let typ = FieldType::Float;
@@ -213,41 +213,41 @@ mod tests {
             let row = Record::new(vec![f.clone()]);
 
             let mut args = vec![null.clone(), float1.clone()];
-            test_validate_coalesce(&args, typ);
-            test_evaluate_coalesce(&mut args, &row, typ, f.clone());
+            test_validate_coalesce(&args, &typ);
+            test_evaluate_coalesce(&mut args, &row, &typ, f.clone());
 
             let mut args = vec![null.clone(), null.clone(), float1.clone()];
-            test_validate_coalesce(&args, typ);
-            test_evaluate_coalesce(&mut args, &row, typ, f.clone());
+            test_validate_coalesce(&args, &typ);
+            test_evaluate_coalesce(&mut args, &row, &typ, f.clone());
 
             let mut args = vec![null.clone(), null.clone(), float1.clone(), null.clone()];
-            test_validate_coalesce(&args, typ);
-            test_evaluate_coalesce(&mut args, &row, typ, f.clone());
+            test_validate_coalesce(&args, &typ);
+            test_evaluate_coalesce(&mut args, &row, &typ, f.clone());
 
             let mut args = vec![null.clone(), null.clone(), float1, float2];
-            test_validate_coalesce(&args, typ);
-            test_evaluate_coalesce(&mut args, &row, typ, f);
+            test_validate_coalesce(&args, &typ);
+            test_evaluate_coalesce(&mut args, &row, &typ, f);
 
             // Decimal
-            let typ = FieldType::Decimal;
+            let typ = FieldType::Decimal(None);
             let f = Field::Decimal(d_num1.0);
             let row = Record::new(vec![f.clone()]);
 
             let mut args = vec![null.clone(), dec1.clone()];
-            test_validate_coalesce(&args, typ);
-            test_evaluate_coalesce(&mut args, &row, typ, f.clone());
+            test_validate_coalesce(&args, &typ);
+            test_evaluate_coalesce(&mut args, &row, &typ, f.clone());
 
             let mut args = vec![null.clone(), null.clone(), dec1.clone()];
-            test_validate_coalesce(&args, typ);
-            test_evaluate_coalesce(&mut args, &row, typ, f.clone());
+            test_validate_coalesce(&args, &typ);
+            test_evaluate_coalesce(&mut args, &row, &typ, f.clone());
 
             let mut args = vec![null.clone(), null.clone(), dec1.clone(), null.clone()];
-            test_validate_coalesce(&args, typ);
-            test_evaluate_coalesce(&mut args, &row, typ, f.clone());
+            test_validate_coalesce(&args, &typ);
+            test_evaluate_coalesce(&mut args, &row, &typ, f.clone());
 
             let mut args = vec![null.clone(), null.clone(), dec1, dec2];
-            test_validate_coalesce(&args, typ);
-            test_evaluate_coalesce(&mut args, &row, typ, f);
+            test_validate_coalesce(&args, &typ);
+            test_evaluate_coalesce(&mut args, &row, &typ, f);
 
             // String
             let typ = FieldType::String;
@@ -255,20 +255,20 @@ mod tests {
             let row = Record::new(vec![f.clone()]);
 
             let mut args = vec![null.clone(), str1.clone()];
-            test_validate_coalesce(&args, typ);
-            test_evaluate_coalesce(&mut args, &row, typ, f.clone());
+            test_validate_coalesce(&args, &typ);
+            test_evaluate_coalesce(&mut args, &row, &typ, f.clone());
 
             let mut args = vec![null.clone(), null.clone(), str1.clone()];
-            test_validate_coalesce(&args, typ);
-            test_evaluate_coalesce(&mut args, &row, typ, f.clone());
+            test_validate_coalesce(&args, &typ);
+            test_evaluate_coalesce(&mut args, &row, &typ, f.clone());
 
             let mut args = vec![null.clone(), null.clone(), str1.clone(), null.clone()];
-            test_validate_coalesce(&args, typ);
-            test_evaluate_coalesce(&mut args, &row, typ, f.clone());
+            test_validate_coalesce(&args, &typ);
+            test_evaluate_coalesce(&mut args, &row, &typ, f.clone());
 
             let mut args = vec![null.clone(), null.clone(), str1.clone(), str2.clone()];
-            test_validate_coalesce(&args, typ);
-            test_evaluate_coalesce(&mut args, &row, typ, f);
+            test_validate_coalesce(&args, &typ);
+            test_evaluate_coalesce(&mut args, &row, &typ, f);
 
             // String
             let typ = FieldType::String;
@@ -276,20 +276,20 @@ mod tests {
             let row = Record::new(vec![f.clone()]);
 
             let mut args = vec![null.clone(), str1.clone()];
-            test_validate_coalesce(&args, typ);
-            test_evaluate_coalesce(&mut args, &row, typ, f.clone());
+            test_validate_coalesce(&args, &typ);
+            test_evaluate_coalesce(&mut args, &row, &typ, f.clone());
 
             let mut args = vec![null.clone(), null.clone(), str1.clone()];
-            test_validate_coalesce(&args, typ);
-            test_evaluate_coalesce(&mut args, &row, typ, f.clone());
+            test_validate_coalesce(&args, &typ);
+            test_evaluate_coalesce(&mut args, &row, &typ, f.clone());
 
             let mut args = vec![null.clone(), null.clone(), str1.clone(), null.clone()];
-            test_validate_coalesce(&args, typ);
-            test_evaluate_coalesce(&mut args, &row, typ, f.clone());
+            test_validate_coalesce(&args, &typ);
+            test_evaluate_coalesce(&mut args, &row, &typ, f.clone());
 
             let mut args = vec![null.clone(), null.clone(), str1, str2];
-            test_validate_coalesce(&args, typ);
-            test_evaluate_coalesce(&mut args, &row, typ, f);
+            test_validate_coalesce(&args, &typ);
+            test_evaluate_coalesce(&mut args, &row, &typ, f);
 
             // Timestamp
             let typ = FieldType::Timestamp;
@@ -297,20 +297,20 @@ mod tests {
             let row = Record::new(vec![f.clone()]);
 
             let mut args = vec![null.clone(), t1.clone()];
-            test_validate_coalesce(&args, typ);
-            test_evaluate_coalesce(&mut args, &row, typ, f.clone());
+            test_validate_coalesce(&args, &typ);
+            test_evaluate_coalesce(&mut args, &row, &typ, f.clone());
 
             let mut args = vec![null.clone(), null.clone(), t1.clone()];
-            test_validate_coalesce(&args, typ);
-            test_evaluate_coalesce(&mut args, &row, typ, f.clone());
+            test_validate_coalesce(&args, &typ);
+            test_evaluate_coalesce(&mut args, &row, &typ, f.clone());
 
             let mut args = vec![null.clone(), null.clone(), t1.clone(), null.clone()];
-            test_validate_coalesce(&args, typ);
-            test_evaluate_coalesce(&mut args, &row, typ, f.clone());
+            test_validate_coalesce(&args, &typ);
+            test_evaluate_coalesce(&mut args, &row, &typ, f.clone());
 
             let mut args = vec![null.clone(), null.clone(), t1, t2];
-            test_validate_coalesce(&args, typ);
-            test_evaluate_coalesce(&mut args, &row, typ, f);
+            test_validate_coalesce(&args, &typ);
+            test_evaluate_coalesce(&mut args, &row, &typ, f);
 
             // Date
             let typ = FieldType::Date;
@@ -318,20 +318,20 @@ mod tests {
             let row = Record::new(vec![f.clone()]);
 
             let mut args = vec![null.clone(), dt1.clone()];
-            test_validate_coalesce(&args, typ);
-            test_evaluate_coalesce(&mut args, &row, typ, f.clone());
+            test_validate_coalesce(&args, &typ);
+            test_evaluate_coalesce(&mut args, &row, &typ, f.clone());
 
             let mut args = vec![null.clone(), null.clone(), dt1.clone()];
-            test_validate_coalesce(&args, typ);
-            test_evaluate_coalesce(&mut args, &row, typ, f.clone());
+            test_validate_coalesce(&args, &typ);
+            test_evaluate_coalesce(&mut args, &row, &typ, f.clone());
 
             let mut args = vec![null.clone(), null.clone(), dt1.clone(), null.clone()];
-            test_validate_coalesce(&args, typ);
-            test_evaluate_coalesce(&mut args, &row, typ, f.clone());
+            test_validate_coalesce(&args, &typ);
+            test_evaluate_coalesce(&mut args, &row, &typ, f.clone());
 
             let mut args = vec![null.clone(), null.clone(), dt1, dt2];
-            test_validate_coalesce(&args, typ);
-            test_evaluate_coalesce(&mut args, &row, typ, f);
+            test_validate_coalesce(&args, &typ);
+            test_evaluate_coalesce(&mut args, &row, &typ, f);
 
             // Null
             let typ = FieldType::Date;
@@ -339,36 +339,46 @@ mod tests {
             let row = Record::new(vec![f.clone()]);
 
             let mut args = vec![null.clone()];
-            test_validate_coalesce(&args, typ);
-            test_evaluate_coalesce(&mut args, &row, typ, f.clone());
+            test_validate_coalesce(&args, &typ);
+            test_evaluate_coalesce(&mut args, &row, &typ, f.clone());
 
             let mut args = vec![null.clone(), null];
-            test_validate_coalesce(&args, typ);
-            test_evaluate_coalesce(&mut args, &row, typ, f);
+            test_validate_coalesce(&args, &typ);
+            test_evaluate_coalesce(&mut args, &row, &typ, f);
         });
     }
 
-    fn test_validate_coalesce(args: &[Expression], typ: FieldType) {
+    fn test_validate_coalesce(args: &[Expression], typ: &FieldType) {
         let schema = Schema::default()
             .field(
-                FieldDefinition::new(String::from("field"), typ, false, SourceDefinition::Dynamic),
+                FieldDefinition::new(
+                    String::from("field"),
+                    typ.clone(),
+                    false,
+                    SourceDefinition::Dynamic,
+                ),
                 false,
             )
             .clone();
 
         let result = validate_coalesce(args, &schema).unwrap().return_type;
-        assert_eq!(result, typ);
+        assert_eq!(&result, typ);
     }
 
     fn test_evaluate_coalesce(
         args: &mut [Expression],
         row: &Record,
-        typ: FieldType,
+        typ: &FieldType,
         _result: Field,
     ) {
         let schema = Schema::default()
             .field(
-                FieldDefinition::new(String::from("field"), typ, false, SourceDefinition::Dynamic),
+                FieldDefinition::new(
+                    String::from("field"),
+                    typ.clone(),
+                    false,
+                    SourceDefinition::Dynamic,
+                ),
                 false,
             )
             .clone();
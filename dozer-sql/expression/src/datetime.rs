@@ -1,10 +1,18 @@
-use crate::arg_utils::{extract_timestamp, extract_uint, validate_arg_type};
+use crate::arg_utils::{
+    extract_timestamp, extract_uint, validate_arg_type, validate_num_arguments,
+};
 use crate::error::Error;
 use crate::execution::{Expression, ExpressionType};
 
-use dozer_types::chrono::{DateTime, Datelike, FixedOffset, Offset, Timelike, Utc};
+use chrono_tz::Tz;
+use dozer_types::chrono::{
+    DateTime, Datelike, Duration, FixedOffset, Months, NaiveDate, NaiveDateTime, Offset, TimeZone,
+    Timelike, Utc,
+};
 use dozer_types::types::Record;
-use dozer_types::types::{DozerDuration, Field, FieldType, Schema, TimeUnit};
+use dozer_types::types::{
+    DozerDuration, Field, FieldType, Schema, SourceDefinition, TimeUnit, DATE_FORMAT,
+};
 use num_traits::ToPrimitive;
 use sqlparser::ast::DateTimeField;
 use std::fmt::{Display, Formatter};
@@ -18,6 +26,18 @@ pub enum DateTimeFunctionType {
         field: sqlparser::ast::DateTimeField,
     },
     Now,
+    DateTrunc,
+    DateAdd,
+    DateSub,
+    DateDiff,
+    ToTimestamp,
+    ToDate,
+    FromUnixtime,
+    AtTimeZone {
+        time_zone: String,
+    },
+    MakeDate,
+    LastDay,
 }
 
 impl Display for DateTimeFunctionType {
@@ -30,47 +50,178 @@ impl Display for DateTimeFunctionType {
                 f.write_str(format!("INTERVAL {field}").as_str())
             }
             DateTimeFunctionType::Now => f.write_str("NOW".to_string().as_str()),
+            DateTimeFunctionType::DateTrunc => f.write_str("DATE_TRUNC"),
+            DateTimeFunctionType::DateAdd => f.write_str("DATE_ADD"),
+            DateTimeFunctionType::DateSub => f.write_str("DATE_SUB"),
+            DateTimeFunctionType::DateDiff => f.write_str("DATEDIFF"),
+            DateTimeFunctionType::ToTimestamp => f.write_str("TO_TIMESTAMP"),
+            DateTimeFunctionType::ToDate => f.write_str("TO_DATE"),
+            DateTimeFunctionType::FromUnixtime => f.write_str("FROM_UNIXTIME"),
+            DateTimeFunctionType::AtTimeZone { time_zone } => {
+                f.write_str(format!("AT TIME ZONE '{time_zone}'").as_str())
+            }
+            DateTimeFunctionType::MakeDate => f.write_str("MAKE_DATE"),
+            DateTimeFunctionType::LastDay => f.write_str("LAST_DAY"),
         }
     }
 }
 
+const DATE_TIME_TYPES: [FieldType; 4] = [
+    FieldType::Date,
+    FieldType::Timestamp,
+    FieldType::String,
+    FieldType::Text,
+];
+
+const TEXT_TYPES: [FieldType; 2] = [FieldType::String, FieldType::Text];
+
+const INTEGER_TYPES: [FieldType; 4] = [
+    FieldType::UInt,
+    FieldType::U128,
+    FieldType::Int,
+    FieldType::I128,
+];
+
+const NUMERIC_TYPES: [FieldType; 6] = [
+    FieldType::UInt,
+    FieldType::U128,
+    FieldType::Int,
+    FieldType::I128,
+    FieldType::Float,
+    FieldType::Decimal(None),
+];
+
 pub(crate) fn get_datetime_function_type(
     function: &DateTimeFunctionType,
-    arg: &Expression,
+    args: &[Expression],
     schema: &Schema,
 ) -> Result<ExpressionType, Error> {
-    validate_arg_type(
-        arg,
-        vec![
-            FieldType::Date,
-            FieldType::Timestamp,
-            FieldType::Duration,
-            FieldType::String,
-            FieldType::Text,
-        ],
-        schema,
-        function,
-        0,
-    )?;
-    match function {
-        DateTimeFunctionType::Extract { field: _ } => Ok(ExpressionType::new(
-            FieldType::Int,
-            false,
-            dozer_types::types::SourceDefinition::Dynamic,
-            false,
-        )),
-        DateTimeFunctionType::Interval { field: _ } => Ok(ExpressionType::new(
-            FieldType::Duration,
-            false,
-            dozer_types::types::SourceDefinition::Dynamic,
-            false,
-        )),
-        DateTimeFunctionType::Now => Ok(ExpressionType::new(
-            FieldType::Timestamp,
-            false,
-            dozer_types::types::SourceDefinition::Dynamic,
-            false,
-        )),
+    let return_type = match function {
+        DateTimeFunctionType::Now => {
+            return Ok(ExpressionType::new(
+                FieldType::Timestamp,
+                false,
+                SourceDefinition::Dynamic,
+                false,
+            ));
+        }
+        DateTimeFunctionType::Extract { .. } | DateTimeFunctionType::Interval { .. } => {
+            validate_num_arguments(1..2, args.len(), function)?;
+            validate_arg_type(
+                &args[0],
+                vec![
+                    FieldType::Date,
+                    FieldType::Timestamp,
+                    FieldType::Duration,
+                    FieldType::String,
+                    FieldType::Text,
+                ],
+                schema,
+                function,
+                0,
+            )?;
+            let return_type = match function {
+                DateTimeFunctionType::Extract { .. } => FieldType::Int,
+                _ => FieldType::Duration,
+            };
+            return Ok(ExpressionType::new(
+                return_type,
+                false,
+                SourceDefinition::Dynamic,
+                false,
+            ));
+        }
+        DateTimeFunctionType::DateTrunc => {
+            validate_num_arguments(2..3, args.len(), function)?;
+            validate_arg_type(&args[0], TEXT_TYPES.to_vec(), schema, function, 0)?;
+            let value = validate_arg_type(&args[1], DATE_TIME_TYPES.to_vec(), schema, function, 1)?;
+            date_or_timestamp(value.return_type)
+        }
+        DateTimeFunctionType::DateAdd | DateTimeFunctionType::DateSub => {
+            validate_num_arguments(2..4, args.len(), function)?;
+            let value = if args.len() == 2 {
+                validate_arg_type(&args[1], vec![FieldType::Duration], schema, function, 1)?;
+                validate_arg_type(&args[0], DATE_TIME_TYPES.to_vec(), schema, function, 0)?
+            } else {
+                validate_arg_type(&args[0], TEXT_TYPES.to_vec(), schema, function, 0)?;
+                validate_arg_type(&args[1], INTEGER_TYPES.to_vec(), schema, function, 1)?;
+                validate_arg_type(&args[2], DATE_TIME_TYPES.to_vec(), schema, function, 2)?
+            };
+            date_or_timestamp(value.return_type)
+        }
+        DateTimeFunctionType::DateDiff => {
+            validate_num_arguments(2..4, args.len(), function)?;
+            let first_value = if args.len() == 2 {
+                0
+            } else {
+                validate_arg_type(&args[0], TEXT_TYPES.to_vec(), schema, function, 0)?;
+                1
+            };
+            for (index, arg) in args.iter().enumerate().skip(first_value) {
+                validate_arg_type(arg, DATE_TIME_TYPES.to_vec(), schema, function, index)?;
+            }
+            FieldType::Int
+        }
+        DateTimeFunctionType::ToTimestamp | DateTimeFunctionType::ToDate => {
+            validate_num_arguments(1..3, args.len(), function)?;
+            if args.len() == 2 {
+                validate_arg_type(&args[0], TEXT_TYPES.to_vec(), schema, function, 0)?;
+                validate_arg_type(&args[1], TEXT_TYPES.to_vec(), schema, function, 1)?;
+            } else if *function == DateTimeFunctionType::ToTimestamp {
+                let mut expected = DATE_TIME_TYPES.to_vec();
+                expected.extend(NUMERIC_TYPES);
+                validate_arg_type(&args[0], expected, schema, function, 0)?;
+            } else {
+                validate_arg_type(&args[0], DATE_TIME_TYPES.to_vec(), schema, function, 0)?;
+            }
+            if *function == DateTimeFunctionType::ToTimestamp {
+                FieldType::Timestamp
+            } else {
+                FieldType::Date
+            }
+        }
+        DateTimeFunctionType::FromUnixtime => {
+            validate_num_arguments(1..2, args.len(), function)?;
+            validate_arg_type(&args[0], NUMERIC_TYPES.to_vec(), schema, function, 0)?;
+            FieldType::Timestamp
+        }
+        DateTimeFunctionType::AtTimeZone { .. } => {
+            validate_num_arguments(1..2, args.len(), function)?;
+            validate_arg_type(&args[0], DATE_TIME_TYPES.to_vec(), schema, function, 0)?;
+            FieldType::Timestamp
+        }
+        DateTimeFunctionType::MakeDate => {
+            validate_num_arguments(3..4, args.len(), function)?;
+            for (index, arg) in args.iter().enumerate() {
+                validate_arg_type(arg, INTEGER_TYPES.to_vec(), schema, function, index)?;
+            }
+            FieldType::Date
+        }
+        DateTimeFunctionType::LastDay => {
+            validate_num_arguments(1..2, args.len(), function)?;
+            validate_arg_type(&args[0], DATE_TIME_TYPES.to_vec(), schema, function, 0)?;
+            FieldType::Date
+        }
+    };
+
+    let mut nullable = false;
+    for arg in args {
+        nullable |= arg.get_type(schema)?.nullable;
+    }
+    Ok(ExpressionType::new(
+        return_type,
+        nullable,
+        SourceDefinition::Dynamic,
+        false,
+    ))
+}
+
+/// Functions that shift or truncate their argument return a date for a date, and a timestamp otherwise.
+fn date_or_timestamp(field_type: FieldType) -> FieldType {
+    if field_type == FieldType::Date {
+        FieldType::Date
+    } else {
+        FieldType::Timestamp
     }
 }
 
@@ -78,6 +229,15 @@ impl DateTimeFunctionType {
     pub(crate) fn new(name: &str) -> Option<DateTimeFunctionType> {
         match name {
             "now" => Some(DateTimeFunctionType::Now),
+            "date_trunc" => Some(DateTimeFunctionType::DateTrunc),
+            "date_add" | "dateadd" => Some(DateTimeFunctionType::DateAdd),
+            "date_sub" | "datesub" => Some(DateTimeFunctionType::DateSub),
+            "datediff" | "date_diff" => Some(DateTimeFunctionType::DateDiff),
+            "to_timestamp" => Some(DateTimeFunctionType::ToTimestamp),
+            "to_date" => Some(DateTimeFunctionType::ToDate),
+            "from_unixtime" => Some(DateTimeFunctionType::FromUnixtime),
+            "make_date" => Some(DateTimeFunctionType::MakeDate),
+            "last_day" => Some(DateTimeFunctionType::LastDay),
             _ => None,
         }
     }
@@ -85,23 +245,406 @@ impl DateTimeFunctionType {
     pub(crate) fn evaluate(
         &self,
         schema: &Schema,
-        arg: &mut Expression,
+        args: &mut [Expression],
         record: &Record,
     ) -> Result<Field, Error> {
         match self {
             DateTimeFunctionType::Extract { field } => {
-                evaluate_date_part(schema, field, arg, record)
+                evaluate_date_part(schema, field, &mut args[0], record)
             }
             DateTimeFunctionType::Interval { field } => {
-                evaluate_interval(schema, field, arg, record)
+                evaluate_interval(schema, field, &mut args[0], record)
             }
             DateTimeFunctionType::Now => self.evaluate_now(),
+            _ => {
+                let mut values = Vec::with_capacity(args.len());
+                for arg in args.iter_mut() {
+                    let value = arg.evaluate(record, schema)?;
+                    if value == Field::Null {
+                        return Ok(Field::Null);
+                    }
+                    values.push(value);
+                }
+                self.evaluate_values(&values)
+            }
         }
     }
 
     pub(crate) fn evaluate_now(&self) -> Result<Field, Error> {
         Ok(Field::Timestamp(DateTime::<FixedOffset>::from(Utc::now())))
     }
+
+    fn evaluate_values(&self, values: &[Field]) -> Result<Field, Error> {
+        let invalid = |index: usize| Error::InvalidFunctionArgument {
+            function_name: self.to_string(),
+            argument_index: index,
+            argument: values[index].clone(),
+        };
+        match self {
+            DateTimeFunctionType::DateTrunc => {
+                let unit = DateTimeUnit::from_field(&values[0]).ok_or_else(|| invalid(0))?;
+                let (time, offset) = to_local(&values[1]).ok_or_else(|| invalid(1))?;
+                from_local(unit.truncate(time).ok_or_else(|| invalid(1))?, offset)
+            }
+            DateTimeFunctionType::DateAdd | DateTimeFunctionType::DateSub => {
+                let subtract = *self == DateTimeFunctionType::DateSub;
+                let (value_index, time) = if values.len() == 2 {
+                    let (time, offset) = to_local(&values[0]).ok_or_else(|| invalid(0))?;
+                    let duration = values[1]
+                        .to_duration()
+                        .and_then(|duration| Duration::from_std(duration.0).ok())
+                        .ok_or_else(|| invalid(1))?;
+                    let time = if subtract {
+                        time.checked_sub_signed(duration)
+                    } else {
+                        time.checked_add_signed(duration)
+                    };
+                    (0, time.map(|time| (time, offset)))
+                } else {
+                    let unit = DateTimeUnit::from_field(&values[0]).ok_or_else(|| invalid(0))?;
+                    let amount = values[1].to_int().ok_or_else(|| invalid(1))?;
+                    let amount = if subtract {
+                        amount.checked_neg().ok_or_else(|| invalid(1))?
+                    } else {
+                        amount
+                    };
+                    let (time, offset) = to_local(&values[2]).ok_or_else(|| invalid(2))?;
+                    (2, unit.add(time, amount).map(|time| (time, offset)))
+                };
+                let (time, offset) = time.ok_or_else(|| invalid(value_index))?;
+                from_local(time, offset)
+            }
+            DateTimeFunctionType::DateDiff => {
+                let (unit, start_index, end_index) = if values.len() == 2 {
+                    (DateTimeUnit::Day, 1, 0)
+                } else {
+                    let unit = DateTimeUnit::from_field(&values[0]).ok_or_else(|| invalid(0))?;
+                    (unit, 1, 2)
+                };
+                let (start, start_offset) =
+                    to_local(&values[start_index]).ok_or_else(|| invalid(start_index))?;
+                let (mut end, end_offset) =
+                    to_local(&values[end_index]).ok_or_else(|| invalid(end_index))?;
+                // Timestamps are compared in the offset of the start.
+                if let (Some(start_offset), Some(end_offset)) = (start_offset, end_offset) {
+                    end = end
+                        .checked_add_signed(Duration::seconds(
+                            (start_offset.local_minus_utc() - end_offset.local_minus_utc()) as i64,
+                        ))
+                        .ok_or_else(|| invalid(end_index))?;
+                }
+                unit.diff(start, end)
+                    .map(Field::Int)
+                    .ok_or_else(|| invalid(end_index))
+            }
+            DateTimeFunctionType::ToTimestamp => {
+                let timestamp = match values {
+                    [value, format] => text(value)
+                        .zip(text(format))
+                        .and_then(|(value, format)| parse_timestamp_with_format(value, format)),
+                    [Field::Timestamp(timestamp)] => Some(*timestamp),
+                    [Field::Date(date)] => Some(Utc.from_utc_datetime(&midnight(*date)).into()),
+                    [value] => {
+                        text(value).map_or_else(|| from_unix_seconds(value), parse_timestamp)
+                    }
+                    _ => None,
+                };
+                timestamp.map(Field::Timestamp).ok_or_else(|| invalid(0))
+            }
+            DateTimeFunctionType::ToDate => {
+                let date = match values {
+                    [value, format] => text(value)
+                        .zip(text(format))
+                        .and_then(|(value, format)| NaiveDate::parse_from_str(value, format).ok()),
+                    [value] => text(value)
+                        .map_or_else(|| to_local(value).map(|(time, _)| time.date()), parse_date),
+                    _ => None,
+                };
+                date.map(Field::Date).ok_or_else(|| invalid(0))
+            }
+            DateTimeFunctionType::FromUnixtime => from_unix_seconds(&values[0])
+                .map(Field::Timestamp)
+                .ok_or_else(|| invalid(0)),
+            DateTimeFunctionType::AtTimeZone { time_zone } => {
+                let timestamp = match &values[0] {
+                    Field::Timestamp(timestamp) => Some(*timestamp),
+                    Field::Date(date) => Some(Utc.from_utc_datetime(&midnight(*date)).into()),
+                    value => text(value).and_then(parse_timestamp),
+                };
+                timestamp
+                    .and_then(|timestamp| convert_time_zone(timestamp, time_zone))
+                    .map(Field::Timestamp)
+                    .ok_or_else(|| invalid(0))
+            }
+            DateTimeFunctionType::MakeDate => {
+                let year = values[0]
+                    .to_int()
+                    .and_then(|year| i32::try_from(year).ok())
+                    .ok_or_else(|| invalid(0))?;
+                let month = values[1]
+                    .to_uint()
+                    .and_then(|month| u32::try_from(month).ok())
+                    .filter(|month| (1..=12).contains(month))
+                    .ok_or_else(|| invalid(1))?;
+                let day = values[2]
+                    .to_uint()
+                    .and_then(|day| u32::try_from(day).ok())
+                    .ok_or_else(|| invalid(2))?;
+                NaiveDate::from_ymd_opt(year, month, day)
+                    .map(Field::Date)
+                    .ok_or_else(|| invalid(2))
+            }
+            DateTimeFunctionType::LastDay => {
+                let (time, _) = to_local(&values[0]).ok_or_else(|| invalid(0))?;
+                let date = time.date();
+                date.with_day(1)
+                    .and_then(|first| first.checked_add_months(Months::new(1)))
+                    .and_then(|next| next.pred_opt())
+                    .map(Field::Date)
+                    .ok_or_else(|| invalid(0))
+            }
+            DateTimeFunctionType::Extract { .. }
+            | DateTimeFunctionType::Interval { .. }
+            | DateTimeFunctionType::Now => unreachable!("evaluated on their argument"),
+        }
+    }
+}
+
+/// A unit of `DATE_TRUNC`, `DATE_ADD`, `DATE_SUB` and `DATEDIFF`, like `'day'` or `'month'`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DateTimeUnit {
+    Nanosecond,
+    Microsecond,
+    Millisecond,
+    Second,
+    Minute,
+    Hour,
+    Day,
+    Week,
+    Month,
+    Quarter,
+    Year,
+    Decade,
+    Century,
+    Millennium,
+}
+
+impl DateTimeUnit {
+    fn from_field(field: &Field) -> Option<Self> {
+        match text(field)?.to_lowercase().as_str() {
+            "nanosecond" | "nanoseconds" => Some(DateTimeUnit::Nanosecond),
+            "microsecond" | "microseconds" => Some(DateTimeUnit::Microsecond),
+            "millisecond" | "milliseconds" => Some(DateTimeUnit::Millisecond),
+            "second" | "seconds" => Some(DateTimeUnit::Second),
+            "minute" | "minutes" => Some(DateTimeUnit::Minute),
+            "hour" | "hours" => Some(DateTimeUnit::Hour),
+            "day" | "days" => Some(DateTimeUnit::Day),
+            "week" | "weeks" => Some(DateTimeUnit::Week),
+            "month" | "months" => Some(DateTimeUnit::Month),
+            "quarter" | "quarters" => Some(DateTimeUnit::Quarter),
+            "year" | "years" => Some(DateTimeUnit::Year),
+            "decade" | "decades" => Some(DateTimeUnit::Decade),
+            "century" | "centuries" => Some(DateTimeUnit::Century),
+            "millennium" | "millennia" => Some(DateTimeUnit::Millennium),
+            _ => None,
+        }
+    }
+
+    /// The length of units that are a fixed number of nanoseconds.
+    fn nanoseconds(self) -> Option<i64> {
+        match self {
+            DateTimeUnit::Nanosecond => Some(1),
+            DateTimeUnit::Microsecond => Some(1_000),
+            DateTimeUnit::Millisecond => Some(1_000_000),
+            DateTimeUnit::Second => Some(1_000_000_000),
+            DateTimeUnit::Minute => Some(60 * 1_000_000_000),
+            DateTimeUnit::Hour => Some(60 * 60 * 1_000_000_000),
+            DateTimeUnit::Day => Some(24 * 60 * 60 * 1_000_000_000),
+            DateTimeUnit::Week => Some(7 * 24 * 60 * 60 * 1_000_000_000),
+            _ => None,
+        }
+    }
+
+    /// The length of calendar units, whose number of days varies.
+    fn months(self) -> Option<i32> {
+        match self {
+            DateTimeUnit::Month => Some(1),
+            DateTimeUnit::Quarter => Some(3),
+            DateTimeUnit::Year => Some(12),
+            DateTimeUnit::Decade => Some(120),
+            DateTimeUnit::Century => Some(1200),
+            DateTimeUnit::Millennium => Some(12000),
+            _ => None,
+        }
+    }
+
+    /// The start of the unit that contains `time`. Weeks start on Monday, and centuries and millennia on their year 1.
+    fn truncate(self, time: NaiveDateTime) -> Option<NaiveDateTime> {
+        let date = time.date();
+        match self {
+            DateTimeUnit::Nanosecond => Some(time),
+            DateTimeUnit::Microsecond => time.with_nanosecond(time.nanosecond() / 1_000 * 1_000),
+            DateTimeUnit::Millisecond => {
+                time.with_nanosecond(time.nanosecond() / 1_000_000 * 1_000_000)
+            }
+            DateTimeUnit::Second => time.with_nanosecond(0),
+            DateTimeUnit::Minute => date.and_hms_opt(time.hour(), time.minute(), 0),
+            DateTimeUnit::Hour => date.and_hms_opt(time.hour(), 0, 0),
+            DateTimeUnit::Day => Some(midnight(date)),
+            DateTimeUnit::Week => date
+                .checked_sub_signed(Duration::days(date.weekday().num_days_from_monday() as i64))
+                .map(midnight),
+            DateTimeUnit::Month
+            | DateTimeUnit::Quarter
+            | DateTimeUnit::Year
+            | DateTimeUnit::Decade
+            | DateTimeUnit::Century
+            | DateTimeUnit::Millennium => {
+                let months = self.months()?;
+                let first_year = i32::from(matches!(
+                    self,
+                    DateTimeUnit::Century | DateTimeUnit::Millennium
+                ));
+                let month = (date.year() - first_year) * 12 + date.month0() as i32;
+                let start = month - month.rem_euclid(months);
+                NaiveDate::from_ymd_opt(
+                    start.div_euclid(12) + first_year,
+                    start.rem_euclid(12) as u32 + 1,
+                    1,
+                )
+                .map(midnight)
+            }
+        }
+    }
+
+    /// Adds `amount` units to `time`. Adding months keeps the day of the month, or clamps it to the end of shorter months.
+    fn add(self, time: NaiveDateTime, amount: i64) -> Option<NaiveDateTime> {
+        if let Some(months) = self.months() {
+            let months = amount.checked_mul(months as i64)?;
+            let delta = Months::new(u32::try_from(months.unsigned_abs()).ok()?);
+            return if months < 0 {
+                time.checked_sub_months(delta)
+            } else {
+                time.checked_add_months(delta)
+            };
+        }
+        let nanoseconds = amount as i128 * self.nanoseconds()? as i128;
+        let seconds = i64::try_from(nanoseconds.div_euclid(1_000_000_000)).ok()?;
+        if seconds.unsigned_abs() > (i64::MAX / 1_000) as u64 {
+            return None;
+        }
+        let duration = Duration::seconds(seconds)
+            + Duration::nanoseconds(nanoseconds.rem_euclid(1_000_000_000) as i64);
+        time.checked_add_signed(duration)
+    }
+
+    /// The number of unit boundaries between `start` and `end`, negative if `end` is before `start`.
+    fn diff(self, start: NaiveDateTime, end: NaiveDateTime) -> Option<i64> {
+        let (start, end) = (self.truncate(start)?, self.truncate(end)?);
+        if let Some(months) = self.months() {
+            let month = |time: NaiveDateTime| time.year() as i64 * 12 + time.month0() as i64;
+            return Some((month(end) - month(start)) / months as i64);
+        }
+        let duration = end.signed_duration_since(start);
+        let seconds = duration.num_seconds();
+        let nanoseconds = seconds as i128 * 1_000_000_000
+            + (duration - Duration::seconds(seconds)).num_nanoseconds()? as i128;
+        i64::try_from(nanoseconds / self.nanoseconds()? as i128).ok()
+    }
+}
+
+fn text(field: &Field) -> Option<&str> {
+    match field {
+        Field::String(value) | Field::Text(value) => Some(value),
+        _ => None,
+    }
+}
+
+fn midnight(date: NaiveDate) -> NaiveDateTime {
+    date.and_hms_opt(0, 0, 0).expect("midnight is a valid time")
+}
+
+/// Splits a date or timestamp into its local time and its offset, which dates don't have.
+fn to_local(value: &Field) -> Option<(NaiveDateTime, Option<FixedOffset>)> {
+    match value {
+        Field::Date(date) => Some((midnight(*date), None)),
+        Field::Timestamp(timestamp) => Some((timestamp.naive_local(), Some(*timestamp.offset()))),
+        value => text(value)
+            .and_then(parse_timestamp)
+            .map(|timestamp| (timestamp.naive_local(), Some(*timestamp.offset()))),
+    }
+}
+
+/// The inverse of [`to_local`]. Dates drop the time of day.
+fn from_local(time: NaiveDateTime, offset: Option<FixedOffset>) -> Result<Field, Error> {
+    match offset {
+        None => Ok(Field::Date(time.date())),
+        Some(offset) => offset
+            .from_local_datetime(&time)
+            .single()
+            .map(Field::Timestamp)
+            .ok_or(Error::InvalidTimestamp(time)),
+    }
+}
+
+/// Parses RFC 3339 timestamps, and dates and times without an offset, which are in UTC.
+fn parse_timestamp(value: &str) -> Option<DateTime<FixedOffset>> {
+    DateTime::parse_from_rfc3339(value)
+        .ok()
+        .or_else(|| parse_timestamp_with_format(value, "%Y-%m-%d %H:%M:%S%.f"))
+        .or_else(|| parse_timestamp_with_format(value, "%Y-%m-%dT%H:%M:%S%.f"))
+        .or_else(|| parse_timestamp_with_format(value, DATE_FORMAT))
+}
+
+/// Parses a timestamp with a `strftime` format like `%Y-%m-%d %H:%M`. Formats without an offset are in UTC.
+fn parse_timestamp_with_format(value: &str, format: &str) -> Option<DateTime<FixedOffset>> {
+    DateTime::parse_from_str(value, format).ok().or_else(|| {
+        NaiveDateTime::parse_from_str(value, format)
+            .ok()
+            .or_else(|| NaiveDate::parse_from_str(value, format).ok().map(midnight))
+            .map(|time| Utc.from_utc_datetime(&time).into())
+    })
+}
+
+fn parse_date(value: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(value, DATE_FORMAT)
+        .ok()
+        .or_else(|| parse_timestamp(value).map(|timestamp| timestamp.naive_local().date()))
+}
+
+/// The UTC timestamp of a number of seconds since the Unix epoch.
+fn from_unix_seconds(value: &Field) -> Option<DateTime<FixedOffset>> {
+    let (seconds, nanoseconds) = match value {
+        Field::UInt(_) | Field::U128(_) | Field::Int(_) | Field::I128(_) => (value.to_int()?, 0),
+        Field::Float(_) | Field::Decimal(_) => {
+            let seconds = value.to_float()?;
+            (
+                seconds.floor().to_i64()?,
+                ((seconds - seconds.floor()) * 1e9) as u32,
+            )
+        }
+        _ => return None,
+    };
+    Utc.timestamp_opt(seconds, nanoseconds)
+        .single()
+        .map(DateTime::<FixedOffset>::from)
+}
+
+/// Returns whether `time_zone` is an IANA time zone name like `Europe/Paris`, or a fixed offset like `+08:00`.
+pub(crate) fn is_time_zone(time_zone: &str) -> bool {
+    time_zone.parse::<Tz>().is_ok() || time_zone.parse::<FixedOffset>().is_ok()
+}
+
+/// The same instant as `timestamp`, with the offset `time_zone` has at that instant.
+fn convert_time_zone(
+    timestamp: DateTime<FixedOffset>,
+    time_zone: &str,
+) -> Option<DateTime<FixedOffset>> {
+    let offset = match time_zone.parse::<Tz>() {
+        Ok(tz) => tz.offset_from_utc_datetime(&timestamp.naive_utc()).fix(),
+        Err(_) => time_zone.parse::<FixedOffset>().ok()?,
+    };
+    Some(timestamp.with_timezone(&offset))
 }
 
 pub(crate) fn evaluate_date_part(
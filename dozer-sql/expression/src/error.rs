@@ -1,6 +1,7 @@
 use std::ops::Range;
 
 use dozer_types::{
+    chrono::NaiveDateTime,
     thiserror::{self, Error},
     types::{Field, FieldType},
 };
@@ -62,6 +63,12 @@ pub enum Error {
         expected: Vec<FieldType>,
         actual: FieldType,
     },
+    #[error("Cannot index into {0}")]
+    CannotIndexInto(FieldType),
+    #[error("Invalid index type: {0}")]
+    InvalidIndexType(FieldType),
+    #[error("No field {name} in {typ}")]
+    UnknownStructField { name: String, typ: FieldType },
     #[error("Invalid cast: from: {from}, to: {to}")]
     InvalidCast { from: Field, to: FieldType },
     #[error("Invalid argument for function {function_name}(): argument: {argument}, index: {argument_index}")]
@@ -82,11 +89,19 @@ pub enum Error {
     InvalidLikeEscape(#[from] like::InvalidEscapeError),
     #[error("Invalid like pattern: {0}")]
     InvalidLikePattern(#[from] like::InvalidPatternError),
+    #[error("Invalid regex: {0}")]
+    InvalidRegex(#[from] regex::Error),
+    #[error("Invalid regex flag: {0}")]
+    InvalidRegexFlag(char),
 
     #[error("Unsupported extract: {0}")]
     UnsupportedExtract(DateTimeField),
     #[error("Unsupported interval: {0}")]
     UnsupportedInterval(DateTimeField),
+    #[error("Unknown time zone: {0}")]
+    UnknownTimeZone(String),
+    #[error("Timestamp out of range: {0}")]
+    InvalidTimestamp(NaiveDateTime),
 
     #[error("Invalid json path: {0}")]
     InvalidJsonPath(String),
@@ -1,4 +1,6 @@
-use crate::arg_utils::{validate_one_argument, validate_two_arguments};
+use crate::arg_utils::{
+    validate_arg_type, validate_num_arguments, validate_one_argument, validate_two_arguments,
+};
 use crate::case::evaluate_case;
 use crate::conditional::{get_conditional_expr_type, ConditionalExpressionType};
 use crate::datetime::{get_datetime_function_type, DateTimeFunctionType};
@@ -6,15 +8,19 @@ use crate::error::Error;
 use crate::geo::common::{get_geo_function_type, GeoFunctionType};
 use crate::is_null::{evaluate_is_not_null, evaluate_is_null};
 use crate::json_functions::JsonFunctionType;
+use crate::nested::{
+    evaluate_element_at, evaluate_struct_field, get_element_at_type, get_struct_field_type,
+};
 use crate::operator::{BinaryOperatorType, UnaryOperatorType};
 use crate::scalar::common::{get_scalar_function_type, ScalarFunctionType};
+use crate::scalar::regexp::{evaluate_regexp, validate_regexp, RegexCache, RegexpFunctionType};
 use crate::scalar::string::{evaluate_trim, validate_trim, TrimType};
 use std::iter::zip;
 
 use super::aggregate::AggregateFunctionType;
 use super::cast::CastOperatorType;
 use super::in_list::evaluate_in_list;
-use super::scalar::string::{evaluate_like, get_like_operator_type};
+use super::scalar::string::{evaluate_ilike, evaluate_like, get_like_operator_type};
 use dozer_types::types::Record;
 use dozer_types::types::{Field, FieldType, Schema, SourceDefinition};
 
@@ -41,13 +47,18 @@ pub enum Expression {
         fun: GeoFunctionType,
         args: Vec<Expression>,
     },
+    Regexp {
+        fun: RegexpFunctionType,
+        args: Vec<Expression>,
+        regex: RegexCache,
+    },
     ConditionalExpression {
         fun: ConditionalExpressionType,
         args: Vec<Expression>,
     },
     DateTimeFunction {
         fun: DateTimeFunctionType,
-        arg: Box<Expression>,
+        args: Vec<Expression>,
     },
     AggregateFunction {
         fun: AggregateFunctionType,
@@ -66,6 +77,7 @@ pub enum Expression {
         arg: Box<Expression>,
         pattern: Box<Expression>,
         escape: Option<char>,
+        case_insensitive: bool,
     },
     InList {
         expr: Box<Expression>,
@@ -91,6 +103,15 @@ pub enum Expression {
     IsNotNull {
         arg: Box<Expression>,
     },
+    ElementAt {
+        arg: Box<Expression>,
+        index: Box<Expression>,
+    },
+    StructField {
+        arg: Box<Expression>,
+        name: String,
+        index: usize,
+    },
     #[cfg(feature = "python")]
     PythonUDF {
         name: String,
@@ -240,7 +261,16 @@ impl Expression {
                 arg,
                 pattern,
                 escape: _,
-            } => arg.to_string(schema) + " LIKE " + pattern.to_string(schema).as_str(),
+                case_insensitive,
+            } => {
+                arg.to_string(schema)
+                    + if *case_insensitive {
+                        " ILIKE "
+                    } else {
+                        " LIKE "
+                    }
+                    + pattern.to_string(schema).as_str()
+            }
             Expression::InList {
                 expr,
                 list,
@@ -268,8 +298,27 @@ impl Expression {
                         .as_str()
                     + ")"
             }
-            Expression::DateTimeFunction { fun, arg } => {
-                fun.to_string() + "(" + arg.to_string(schema).as_str() + ")"
+            Expression::Regexp { fun, args, .. } => {
+                fun.to_string()
+                    + "("
+                    + args
+                        .iter()
+                        .map(|e| e.to_string(schema))
+                        .collect::<Vec<String>>()
+                        .join(",")
+                        .as_str()
+                    + ")"
+            }
+            Expression::DateTimeFunction { fun, args } => {
+                fun.to_string()
+                    + "("
+                    + args
+                        .iter()
+                        .map(|e| e.to_string(schema))
+                        .collect::<Vec<String>>()
+                        .join(",")
+                        .as_str()
+                    + ")"
             }
             Expression::Now { fun } => fun.to_string() + "()",
             Expression::Json { fun, args } => {
@@ -287,6 +336,10 @@ impl Expression {
             Expression::JavaScriptUdf(udf) => udf.to_string(schema),
             Expression::IsNull { arg } => arg.to_string(schema) + " IS NULL ",
             Expression::IsNotNull { arg } => arg.to_string(schema) + " IS NOT NULL ",
+            Expression::ElementAt { arg, index } => {
+                arg.to_string(schema) + "[" + index.to_string(schema).as_str() + "]"
+            }
+            Expression::StructField { arg, name, .. } => arg.to_string(schema) + "." + name,
         }
     }
 }
@@ -356,7 +409,14 @@ impl Expression {
                 arg,
                 pattern,
                 escape,
-            } => evaluate_like(schema, arg, pattern, *escape, record),
+                case_insensitive,
+            } => {
+                if *case_insensitive {
+                    evaluate_ilike(schema, arg, pattern, *escape, record)
+                } else {
+                    evaluate_like(schema, arg, pattern, *escape, record)
+                }
+            }
             Expression::InList {
                 expr,
                 list,
@@ -364,8 +424,11 @@ impl Expression {
             } => evaluate_in_list(schema, expr, list, *negated, record),
             Expression::Cast { arg, typ } => typ.evaluate(schema, arg, record),
             Expression::GeoFunction { fun, args } => fun.evaluate(schema, args, record),
+            Expression::Regexp { fun, args, regex } => {
+                evaluate_regexp(*fun, regex, schema, args, record)
+            }
             Expression::ConditionalExpression { fun, args } => fun.evaluate(schema, args, record),
-            Expression::DateTimeFunction { fun, arg } => fun.evaluate(schema, arg, record),
+            Expression::DateTimeFunction { fun, args } => fun.evaluate(schema, args, record),
             Expression::Now { fun } => fun.evaluate_now(),
             Expression::Json { fun, args } => fun.evaluate(schema, args, record),
             Expression::Case {
@@ -376,11 +439,34 @@ impl Expression {
             } => evaluate_case(schema, operand, conditions, results, else_result, record),
             Expression::IsNull { arg } => evaluate_is_null(schema, arg, record),
             Expression::IsNotNull { arg } => evaluate_is_not_null(schema, arg, record),
+            Expression::ElementAt { arg, index } => evaluate_element_at(schema, arg, index, record),
+            Expression::StructField { arg, index, .. } => {
+                evaluate_struct_field(schema, arg, *index, record)
+            }
             #[cfg(feature = "javascript")]
             Expression::JavaScriptUdf(udf) => udf.evaluate(record, schema),
         }
     }
 
+    /// Evaluates the expression for each of `records`.
+    ///
+    /// A JavaScript UDF at the top of the expression is called for the whole batch at once.
+    /// Everything else, including UDFs nested in other expressions, is evaluated record by record.
+    pub fn evaluate_batch(
+        &mut self,
+        records: &[Record],
+        schema: &Schema,
+    ) -> Result<Vec<Field>, Error> {
+        #[cfg(feature = "javascript")]
+        if let Expression::JavaScriptUdf(udf) = self {
+            return udf.evaluate_batch(records, schema);
+        }
+        records
+            .iter()
+            .map(|record| self.evaluate(record, schema))
+            .collect()
+    }
+
     pub fn get_type(&self, schema: &Schema) -> Result<ExpressionType, Error> {
         match self {
             Expression::Literal(field) => {
@@ -399,7 +485,7 @@ impl Expression {
                 let t = schema.fields.get(*index).unwrap();
 
                 Ok(ExpressionType::new(
-                    t.typ,
+                    t.typ.clone(),
                     t.nullable,
                     t.source.clone(),
                     schema.primary_index.contains(index),
@@ -429,6 +515,7 @@ impl Expression {
                 arg,
                 pattern,
                 escape: _,
+                case_insensitive: _,
             } => get_like_operator_type(arg, pattern, schema),
             Expression::InList {
                 expr: _,
@@ -442,8 +529,9 @@ impl Expression {
             )),
             Expression::Cast { arg, typ } => typ.get_return_type(schema, arg),
             Expression::GeoFunction { fun, args } => get_geo_function_type(fun, args, schema),
-            Expression::DateTimeFunction { fun, arg } => {
-                get_datetime_function_type(fun, arg, schema)
+            Expression::Regexp { fun, args, .. } => validate_regexp(*fun, args, schema),
+            Expression::DateTimeFunction { fun, args } => {
+                get_datetime_function_type(fun, args, schema)
             }
             Expression::Now { fun: _ } => Ok(ExpressionType::new(
                 FieldType::Timestamp,
@@ -473,7 +561,7 @@ impl Expression {
             }
             #[cfg(feature = "python")]
             Expression::PythonUDF { return_type, .. } => Ok(ExpressionType::new(
-                *return_type,
+                return_type.clone(),
                 false,
                 SourceDefinition::Dynamic,
                 false,
@@ -499,6 +587,8 @@ impl Expression {
                 SourceDefinition::Dynamic,
                 false,
             )),
+            Expression::ElementAt { arg, index } => get_element_at_type(arg, index, schema),
+            Expression::StructField { arg, name, .. } => get_struct_field_type(arg, name, schema),
         }
     }
 }
@@ -621,12 +711,24 @@ fn get_binary_operator_type(
                     SourceDefinition::Dynamic,
                     false,
                 )),
-                (FieldType::Duration, FieldType::Duration) => Ok(ExpressionType::new(
+                (FieldType::Duration, FieldType::Duration)
+                | (FieldType::Date, FieldType::Date)
+                | (FieldType::Duration, FieldType::UInt)
+                | (FieldType::Duration, FieldType::Int)
+                | (FieldType::Duration, FieldType::Float) => Ok(ExpressionType::new(
                     FieldType::Duration,
                     false,
                     SourceDefinition::Dynamic,
                     false,
                 )),
+                (FieldType::Date, FieldType::Duration) | (FieldType::Duration, FieldType::Date) => {
+                    Ok(ExpressionType::new(
+                        FieldType::Timestamp,
+                        false,
+                        SourceDefinition::Dynamic,
+                        false,
+                    ))
+                }
                 (FieldType::Int, FieldType::Int)
                 | (FieldType::Int, FieldType::UInt)
                 | (FieldType::UInt, FieldType::Int) => Ok(ExpressionType::new(
@@ -661,18 +763,18 @@ fn get_binary_operator_type(
                     SourceDefinition::Dynamic,
                     false,
                 )),
-                (FieldType::Decimal, FieldType::Decimal)
-                | (FieldType::UInt, FieldType::Decimal)
-                | (FieldType::U128, FieldType::Decimal)
-                | (FieldType::Int, FieldType::Decimal)
-                | (FieldType::I128, FieldType::Decimal)
-                | (FieldType::Float, FieldType::Decimal)
-                | (FieldType::Decimal, FieldType::UInt)
-                | (FieldType::Decimal, FieldType::U128)
-                | (FieldType::Decimal, FieldType::Int)
-                | (FieldType::Decimal, FieldType::I128)
-                | (FieldType::Decimal, FieldType::Float) => Ok(ExpressionType::new(
-                    FieldType::Decimal,
+                (FieldType::Decimal(_), FieldType::Decimal(_))
+                | (FieldType::UInt, FieldType::Decimal(_))
+                | (FieldType::U128, FieldType::Decimal(_))
+                | (FieldType::Int, FieldType::Decimal(_))
+                | (FieldType::I128, FieldType::Decimal(_))
+                | (FieldType::Float, FieldType::Decimal(_))
+                | (FieldType::Decimal(_), FieldType::UInt)
+                | (FieldType::Decimal(_), FieldType::U128)
+                | (FieldType::Decimal(_), FieldType::Int)
+                | (FieldType::Decimal(_), FieldType::I128)
+                | (FieldType::Decimal(_), FieldType::Float) => Ok(ExpressionType::new(
+                    FieldType::Decimal(None),
                     false,
                     SourceDefinition::Dynamic,
                     false,
@@ -687,6 +789,14 @@ fn get_binary_operator_type(
 
         BinaryOperatorType::Div => {
             match (left_field_type.return_type, right_field_type.return_type) {
+                (FieldType::Duration, FieldType::UInt)
+                | (FieldType::Duration, FieldType::Int)
+                | (FieldType::Duration, FieldType::Float) => Ok(ExpressionType::new(
+                    FieldType::Duration,
+                    false,
+                    SourceDefinition::Dynamic,
+                    false,
+                )),
                 (FieldType::Int, FieldType::UInt)
                 | (FieldType::Int, FieldType::Int)
                 | (FieldType::Int, FieldType::U128)
@@ -717,18 +827,18 @@ fn get_binary_operator_type(
                     SourceDefinition::Dynamic,
                     false,
                 )),
-                (FieldType::Decimal, FieldType::Decimal)
-                | (FieldType::Decimal, FieldType::UInt)
-                | (FieldType::Decimal, FieldType::U128)
-                | (FieldType::Decimal, FieldType::Int)
-                | (FieldType::Decimal, FieldType::I128)
-                | (FieldType::Decimal, FieldType::Float)
-                | (FieldType::UInt, FieldType::Decimal)
-                | (FieldType::U128, FieldType::Decimal)
-                | (FieldType::Int, FieldType::Decimal)
-                | (FieldType::I128, FieldType::Decimal)
-                | (FieldType::Float, FieldType::Decimal) => Ok(ExpressionType::new(
-                    FieldType::Decimal,
+                (FieldType::Decimal(_), FieldType::Decimal(_))
+                | (FieldType::Decimal(_), FieldType::UInt)
+                | (FieldType::Decimal(_), FieldType::U128)
+                | (FieldType::Decimal(_), FieldType::Int)
+                | (FieldType::Decimal(_), FieldType::I128)
+                | (FieldType::Decimal(_), FieldType::Float)
+                | (FieldType::UInt, FieldType::Decimal(_))
+                | (FieldType::U128, FieldType::Decimal(_))
+                | (FieldType::Int, FieldType::Decimal(_))
+                | (FieldType::I128, FieldType::Decimal(_))
+                | (FieldType::Float, FieldType::Decimal(_)) => Ok(ExpressionType::new(
+                    FieldType::Decimal(None),
                     false,
                     SourceDefinition::Dynamic,
                     false,
@@ -749,7 +859,16 @@ fn get_aggregate_function_type(
     schema: &Schema,
 ) -> Result<ExpressionType, Error> {
     match function {
+        AggregateFunctionType::ApproxCountDistinct | AggregateFunctionType::CountDistinct => {
+            validate_count_distinct(args, schema, function)
+        }
+        AggregateFunctionType::ArrayAgg | AggregateFunctionType::JsonAgg => {
+            validate_array_agg(args, schema, function)
+        }
         AggregateFunctionType::Avg => validate_avg(args, schema),
+        AggregateFunctionType::BoolAnd | AggregateFunctionType::BoolOr => {
+            validate_bool_agg(args, schema, function)
+        }
         AggregateFunctionType::Count => validate_count(args, schema),
         AggregateFunctionType::Max => validate_max(args, schema),
         AggregateFunctionType::MaxAppendOnly => validate_max_append_only(args, schema),
@@ -757,6 +876,14 @@ fn get_aggregate_function_type(
         AggregateFunctionType::Min => validate_min(args, schema),
         AggregateFunctionType::MinAppendOnly => validate_min_append_only(args, schema),
         AggregateFunctionType::MinValue => validate_min_value(args, schema),
+        AggregateFunctionType::Median
+        | AggregateFunctionType::PercentileCont
+        | AggregateFunctionType::PercentileDisc => validate_percentile(args, schema, function),
+        AggregateFunctionType::StddevPop
+        | AggregateFunctionType::StddevSamp
+        | AggregateFunctionType::VarPop
+        | AggregateFunctionType::VarSamp => validate_variance(args, schema, function),
+        AggregateFunctionType::StringAgg => validate_string_agg(args, schema),
         AggregateFunctionType::Sum => validate_sum(args, schema),
     }
 }
@@ -765,13 +892,13 @@ fn validate_avg(args: &[Expression], schema: &Schema) -> Result<ExpressionType,
     let arg = validate_one_argument(args, schema, AggregateFunctionType::Avg)?;
 
     let ret_type = match arg.return_type {
-        FieldType::UInt => FieldType::Decimal,
-        FieldType::U128 => FieldType::Decimal,
-        FieldType::Int => FieldType::Decimal,
-        FieldType::Int8 => FieldType::Decimal,
-        FieldType::I128 => FieldType::Decimal,
+        FieldType::UInt => FieldType::Decimal(None),
+        FieldType::U128 => FieldType::Decimal(None),
+        FieldType::Int => FieldType::Decimal(None),
+        FieldType::Int8 => FieldType::Decimal(None),
+        FieldType::I128 => FieldType::Decimal(None),
         FieldType::Float => FieldType::Float,
-        FieldType::Decimal => FieldType::Decimal,
+        FieldType::Decimal(_) => FieldType::Decimal(None),
         FieldType::Duration => FieldType::Duration,
         FieldType::Boolean
         | FieldType::String
@@ -780,7 +907,12 @@ fn validate_avg(args: &[Expression], schema: &Schema) -> Result<ExpressionType,
         | FieldType::Timestamp
         | FieldType::Binary
         | FieldType::Json
-        | FieldType::Point => {
+        | FieldType::Point
+        | FieldType::List(_)
+        | FieldType::Struct(_)
+        | FieldType::Uuid
+        | FieldType::Time
+        | FieldType::Enum(_) => {
             return Err(Error::InvalidFunctionArgumentType {
                 function_name: AggregateFunctionType::Avg.to_string(),
                 argument_index: 0,
@@ -791,7 +923,7 @@ fn validate_avg(args: &[Expression], schema: &Schema) -> Result<ExpressionType,
                     FieldType::Int,
                     FieldType::I128,
                     FieldType::Float,
-                    FieldType::Decimal,
+                    FieldType::Decimal(None),
                     FieldType::Duration,
                 ],
             });
@@ -825,7 +957,7 @@ fn validate_max(args: &[Expression], schema: &Schema) -> Result<ExpressionType,
         FieldType::Int8 => FieldType::Int8,
         FieldType::I128 => FieldType::I128,
         FieldType::Float => FieldType::Float,
-        FieldType::Decimal => FieldType::Decimal,
+        FieldType::Decimal(_) => FieldType::Decimal(None),
         FieldType::Timestamp => FieldType::Timestamp,
         FieldType::Date => FieldType::Date,
         FieldType::Duration => FieldType::Duration,
@@ -834,13 +966,18 @@ fn validate_max(args: &[Expression], schema: &Schema) -> Result<ExpressionType,
         | FieldType::Text
         | FieldType::Binary
         | FieldType::Json
-        | FieldType::Point => {
+        | FieldType::Point
+        | FieldType::List(_)
+        | FieldType::Struct(_)
+        | FieldType::Uuid
+        | FieldType::Time
+        | FieldType::Enum(_) => {
             return Err(Error::InvalidFunctionArgumentType {
                 function_name: AggregateFunctionType::Max.to_string(),
                 argument_index: 0,
                 actual: arg.return_type,
                 expected: vec![
-                    FieldType::Decimal,
+                    FieldType::Decimal(None),
                     FieldType::UInt,
                     FieldType::U128,
                     FieldType::Int,
@@ -871,7 +1008,7 @@ fn validate_min(args: &[Expression], schema: &Schema) -> Result<ExpressionType,
         FieldType::Int8 => FieldType::Int8,
         FieldType::I128 => FieldType::I128,
         FieldType::Float => FieldType::Float,
-        FieldType::Decimal => FieldType::Decimal,
+        FieldType::Decimal(_) => FieldType::Decimal(None),
         FieldType::Timestamp => FieldType::Timestamp,
         FieldType::Date => FieldType::Date,
         FieldType::Duration => FieldType::Duration,
@@ -880,13 +1017,18 @@ fn validate_min(args: &[Expression], schema: &Schema) -> Result<ExpressionType,
         | FieldType::Text
         | FieldType::Binary
         | FieldType::Json
-        | FieldType::Point => {
+        | FieldType::Point
+        | FieldType::List(_)
+        | FieldType::Struct(_)
+        | FieldType::Uuid
+        | FieldType::Time
+        | FieldType::Enum(_) => {
             return Err(Error::InvalidFunctionArgumentType {
                 function_name: AggregateFunctionType::Min.to_string(),
                 argument_index: 0,
                 actual: arg.return_type,
                 expected: vec![
-                    FieldType::Decimal,
+                    FieldType::Decimal(None),
                     FieldType::UInt,
                     FieldType::U128,
                     FieldType::Int,
@@ -918,22 +1060,27 @@ fn validate_max_append_only(args: &[Expression], schema: &Schema) -> Result<Expr
         FieldType::Int8 => FieldType::Int8,
         FieldType::I128 => FieldType::I128,
         FieldType::Float => FieldType::Float,
-        FieldType::Decimal => FieldType::Decimal,
+        FieldType::Decimal(_) => FieldType::Decimal(None),
         FieldType::Timestamp => FieldType::Timestamp,
         FieldType::Date => FieldType::Date,
+        FieldType::Time => FieldType::Time,
         FieldType::Duration => FieldType::Duration,
         FieldType::Boolean
         | FieldType::String
         | FieldType::Text
         | FieldType::Binary
         | FieldType::Json
-        | FieldType::Point => {
+        | FieldType::Point
+        | FieldType::List(_)
+        | FieldType::Struct(_)
+        | FieldType::Uuid
+        | FieldType::Enum(_) => {
             return Err(Error::InvalidFunctionArgumentType {
                 function_name: AggregateFunctionType::MaxAppendOnly.to_string(),
                 argument_index: 0,
                 actual: arg.return_type,
                 expected: vec![
-                    FieldType::Decimal,
+                    FieldType::Decimal(None),
                     FieldType::UInt,
                     FieldType::U128,
                     FieldType::Int,
@@ -964,22 +1111,27 @@ fn validate_min_append_only(args: &[Expression], schema: &Schema) -> Result<Expr
         FieldType::Int8 => FieldType::Int8,
         FieldType::I128 => FieldType::I128,
         FieldType::Float => FieldType::Float,
-        FieldType::Decimal => FieldType::Decimal,
+        FieldType::Decimal(_) => FieldType::Decimal(None),
         FieldType::Timestamp => FieldType::Timestamp,
         FieldType::Date => FieldType::Date,
+        FieldType::Time => FieldType::Time,
         FieldType::Duration => FieldType::Duration,
         FieldType::Boolean
         | FieldType::String
         | FieldType::Text
         | FieldType::Binary
         | FieldType::Json
-        | FieldType::Point => {
+        | FieldType::Point
+        | FieldType::List(_)
+        | FieldType::Struct(_)
+        | FieldType::Uuid
+        | FieldType::Enum(_) => {
             return Err(Error::InvalidFunctionArgumentType {
                 function_name: AggregateFunctionType::MinAppendOnly.to_string(),
                 argument_index: 0,
                 actual: arg.return_type,
                 expected: vec![
-                    FieldType::Decimal,
+                    FieldType::Decimal(None),
                     FieldType::UInt,
                     FieldType::U128,
                     FieldType::Int,
@@ -1010,7 +1162,7 @@ fn validate_sum(args: &[Expression], schema: &Schema) -> Result<ExpressionType,
         FieldType::Int8 => FieldType::Int8,
         FieldType::I128 => FieldType::I128,
         FieldType::Float => FieldType::Float,
-        FieldType::Decimal => FieldType::Decimal,
+        FieldType::Decimal(_) => FieldType::Decimal(None),
         FieldType::Duration => FieldType::Duration,
         FieldType::Boolean
         | FieldType::String
@@ -1019,7 +1171,12 @@ fn validate_sum(args: &[Expression], schema: &Schema) -> Result<ExpressionType,
         | FieldType::Timestamp
         | FieldType::Binary
         | FieldType::Json
-        | FieldType::Point => {
+        | FieldType::Point
+        | FieldType::List(_)
+        | FieldType::Struct(_)
+        | FieldType::Uuid
+        | FieldType::Time
+        | FieldType::Enum(_) => {
             return Err(Error::InvalidFunctionArgumentType {
                 function_name: AggregateFunctionType::Sum.to_string(),
                 argument_index: 0,
@@ -1030,7 +1187,7 @@ fn validate_sum(args: &[Expression], schema: &Schema) -> Result<ExpressionType,
                     FieldType::Int,
                     FieldType::I128,
                     FieldType::Float,
-                    FieldType::Decimal,
+                    FieldType::Decimal(None),
                     FieldType::Duration,
                 ],
             });
@@ -1054,7 +1211,7 @@ fn validate_max_value(args: &[Expression], schema: &Schema) -> Result<Expression
         FieldType::Int8 => FieldType::Int8,
         FieldType::I128 => FieldType::I128,
         FieldType::Float => FieldType::Float,
-        FieldType::Decimal => FieldType::Decimal,
+        FieldType::Decimal(_) => FieldType::Decimal(None),
         FieldType::Timestamp => FieldType::Timestamp,
         FieldType::Date => FieldType::Date,
         FieldType::Duration => FieldType::Duration,
@@ -1063,13 +1220,18 @@ fn validate_max_value(args: &[Expression], schema: &Schema) -> Result<Expression
         | FieldType::Text
         | FieldType::Binary
         | FieldType::Json
-        | FieldType::Point => {
+        | FieldType::Point
+        | FieldType::List(_)
+        | FieldType::Struct(_)
+        | FieldType::Uuid
+        | FieldType::Time
+        | FieldType::Enum(_) => {
             return Err(Error::InvalidFunctionArgumentType {
                 function_name: AggregateFunctionType::MaxValue.to_string(),
                 argument_index: 0,
                 actual: base_arg.return_type,
                 expected: vec![
-                    FieldType::Decimal,
+                    FieldType::Decimal(None),
                     FieldType::UInt,
                     FieldType::U128,
                     FieldType::Int,
@@ -1101,7 +1263,7 @@ fn validate_min_value(args: &[Expression], schema: &Schema) -> Result<Expression
         FieldType::Int8 => FieldType::Int8,
         FieldType::I128 => FieldType::I128,
         FieldType::Float => FieldType::Float,
-        FieldType::Decimal => FieldType::Decimal,
+        FieldType::Decimal(_) => FieldType::Decimal(None),
         FieldType::Timestamp => FieldType::Timestamp,
         FieldType::Date => FieldType::Date,
         FieldType::Duration => FieldType::Duration,
@@ -1110,13 +1272,18 @@ fn validate_min_value(args: &[Expression], schema: &Schema) -> Result<Expression
         | FieldType::Text
         | FieldType::Binary
         | FieldType::Json
-        | FieldType::Point => {
+        | FieldType::Point
+        | FieldType::List(_)
+        | FieldType::Struct(_)
+        | FieldType::Uuid
+        | FieldType::Time
+        | FieldType::Enum(_) => {
             return Err(Error::InvalidFunctionArgumentType {
                 function_name: AggregateFunctionType::MinValue.to_string(),
                 argument_index: 0,
                 actual: base_arg.return_type,
                 expected: vec![
-                    FieldType::Decimal,
+                    FieldType::Decimal(None),
                     FieldType::UInt,
                     FieldType::U128,
                     FieldType::Int,
@@ -1137,3 +1304,140 @@ fn validate_min_value(args: &[Expression], schema: &Schema) -> Result<Expression
         false,
     ))
 }
+
+const NUMERIC_TYPES: [FieldType; 7] = [
+    FieldType::UInt,
+    FieldType::U128,
+    FieldType::Int,
+    FieldType::Int8,
+    FieldType::I128,
+    FieldType::Float,
+    FieldType::Decimal(None),
+];
+
+fn validate_count_distinct(
+    args: &[Expression],
+    schema: &Schema,
+    function: &AggregateFunctionType,
+) -> Result<ExpressionType, Error> {
+    validate_one_argument(args, schema, function)?;
+    Ok(ExpressionType::new(
+        FieldType::Int,
+        false,
+        SourceDefinition::Dynamic,
+        false,
+    ))
+}
+
+/// `PERCENTILE_CONT(x, fraction)` interpolates, so it's a float, while `PERCENTILE_DISC(x, fraction)` is one of the values.
+/// `MEDIAN(x)` is `PERCENTILE_CONT(x, 0.5)`.
+fn validate_percentile(
+    args: &[Expression],
+    schema: &Schema,
+    function: &AggregateFunctionType,
+) -> Result<ExpressionType, Error> {
+    let arg = if function == &AggregateFunctionType::Median {
+        validate_one_argument(args, schema, function)?
+    } else {
+        validate_num_arguments(2..3, args.len(), function)?;
+        validate_arg_type(
+            &args[1],
+            vec![
+                FieldType::Float,
+                FieldType::Decimal(None),
+                FieldType::Int,
+                FieldType::UInt,
+            ],
+            schema,
+            function,
+            1,
+        )?;
+        args[0].get_type(schema)?
+    };
+
+    let ret_type = if function == &AggregateFunctionType::PercentileDisc {
+        arg.return_type
+    } else if NUMERIC_TYPES
+        .iter()
+        .any(|typ| typ.same_kind(&arg.return_type))
+    {
+        FieldType::Float
+    } else {
+        return Err(Error::InvalidFunctionArgumentType {
+            function_name: function.to_string(),
+            argument_index: 0,
+            actual: arg.return_type,
+            expected: NUMERIC_TYPES.to_vec(),
+        });
+    };
+    Ok(ExpressionType::new(
+        ret_type,
+        true,
+        SourceDefinition::Dynamic,
+        false,
+    ))
+}
+
+fn validate_variance(
+    args: &[Expression],
+    schema: &Schema,
+    function: &AggregateFunctionType,
+) -> Result<ExpressionType, Error> {
+    validate_num_arguments(1..2, args.len(), function)?;
+    validate_arg_type(&args[0], NUMERIC_TYPES.to_vec(), schema, function, 0)?;
+    Ok(ExpressionType::new(
+        FieldType::Float,
+        true,
+        SourceDefinition::Dynamic,
+        false,
+    ))
+}
+
+/// `STRING_AGG(x)` separates the values with commas, `STRING_AGG(x, separator)` with `separator`.
+fn validate_string_agg(args: &[Expression], schema: &Schema) -> Result<ExpressionType, Error> {
+    validate_num_arguments(1..3, args.len(), AggregateFunctionType::StringAgg)?;
+    for (index, arg) in args.iter().enumerate() {
+        validate_arg_type(
+            arg,
+            vec![FieldType::String, FieldType::Text],
+            schema,
+            AggregateFunctionType::StringAgg,
+            index,
+        )?;
+    }
+    Ok(ExpressionType::new(
+        FieldType::String,
+        true,
+        SourceDefinition::Dynamic,
+        false,
+    ))
+}
+
+fn validate_array_agg(
+    args: &[Expression],
+    schema: &Schema,
+    function: &AggregateFunctionType,
+) -> Result<ExpressionType, Error> {
+    validate_one_argument(args, schema, function)?;
+    Ok(ExpressionType::new(
+        FieldType::Json,
+        true,
+        SourceDefinition::Dynamic,
+        false,
+    ))
+}
+
+fn validate_bool_agg(
+    args: &[Expression],
+    schema: &Schema,
+    function: &AggregateFunctionType,
+) -> Result<ExpressionType, Error> {
+    validate_num_arguments(1..2, args.len(), function)?;
+    validate_arg_type(&args[0], vec![FieldType::Boolean], schema, function, 0)?;
+    Ok(ExpressionType::new(
+        FieldType::Boolean,
+        true,
+        SourceDefinition::Dynamic,
+        false,
+    ))
+}
@@ -3,18 +3,22 @@ use std::{num::NonZeroI32, sync::Arc};
 use deno_core::{error::AnyError, *};
 use dozer_types::{
     errors::types::{DeserializationError, SerializationError},
-    json_types::JsonValue,
+    json_types::{field_to_json_value, JsonArray, JsonValue},
     parking_lot, serde_json, thiserror,
     types::{Field, FieldType, Record, Schema, SourceDefinition},
 };
 use tokio::{runtime::Runtime, sync::Mutex};
 
+use crate::cast::cast_field;
 use crate::execution::{Expression, ExpressionType};
 
 #[derive(Debug, Clone)]
 pub struct Udf {
     function_name: String,
-    arg: Box<Expression>,
+    args: Vec<Expression>,
+    return_type: FieldType,
+    /// Whether the function is called once per batch of records, see [`Udf::evaluate_batch`].
+    batch: bool,
     tokio_runtime: Arc<Runtime>,
     /// `Arc<Mutex>` to enable `Clone`. Not sure why `Expression` should be `Clone`.
     deno_runtime: Arc<Mutex<dozer_deno::Runtime>>,
@@ -24,7 +28,7 @@ pub struct Udf {
 impl PartialEq for Udf {
     fn eq(&self, other: &Self) -> bool {
         // This is obviously wrong. We have to lift the `PartialEq` constraint.
-        self.function_name == other.function_name && self.arg == other.arg
+        self.function_name == other.function_name && self.args == other.args
     }
 }
 
@@ -40,6 +44,12 @@ pub enum Error {
     Deserialization(#[from] DeserializationError),
     #[error("serde json: {0}")]
     SerdeJson(#[from] serde_json::Error),
+    #[error("invalid return type: {0}")]
+    InvalidReturnType(String),
+    #[error("batch function must return an array")]
+    BatchResultNotArray,
+    #[error("batch function returned {actual} results for {expected} records")]
+    BatchResultLength { expected: usize, actual: usize },
 }
 
 #[op2]
@@ -58,14 +68,18 @@ impl Udf {
         tokio_runtime: Arc<Runtime>,
         function_name: String,
         module: String,
-        arg: Expression,
+        args: Vec<Expression>,
+        return_type: FieldType,
+        batch: bool,
     ) -> Result<Self, Error> {
         let (deno_runtime, functions) =
             dozer_deno::Runtime::new(vec![module], Vec::<fn() -> Extension>::new()).await?;
         let function = functions[0];
         Ok(Self {
             function_name,
-            arg: Box::new(arg),
+            args,
+            return_type,
+            batch,
             tokio_runtime,
             deno_runtime: Arc::new(Mutex::new(deno_runtime)),
             function,
@@ -74,8 +88,9 @@ impl Udf {
 
     pub fn get_type(&self) -> ExpressionType {
         ExpressionType {
-            return_type: FieldType::Json,
-            nullable: false,
+            return_type: self.return_type.clone(),
+            // A JSON `null` is a JSON value, but it's `NULL` for any other type.
+            nullable: self.return_type != FieldType::Json,
             source: SourceDefinition::Dynamic,
             is_primary_key: false,
         }
@@ -86,42 +101,110 @@ impl Udf {
         record: &Record,
         schema: &Schema,
     ) -> Result<Field, crate::error::Error> {
-        self.tokio_runtime.block_on(evaluate_impl(
-            self.function_name.clone(),
-            &mut self.arg,
-            &self.deno_runtime,
-            self.function,
-            record,
-            schema,
-        ))
+        // A batch function takes arrays of arguments, also when it's evaluated for a single record.
+        if self.batch {
+            let mut results = self.evaluate_batch(std::slice::from_ref(record), schema)?;
+            return Ok(results.pop().expect("we checked the number of results"));
+        }
+
+        let args = evaluate_args(&mut self.args, record, schema)?;
+        let result =
+            self.tokio_runtime
+                .block_on(call_function(&self.deno_runtime, self.function, args))?;
+        to_field(result, &self.return_type)
+    }
+
+    /// Evaluates the function for each of `records`.
+    ///
+    /// Batch functions are called once, with an array of values per argument, and return an array
+    /// with a result per record. Other functions are called once per record.
+    pub fn evaluate_batch(
+        &mut self,
+        records: &[Record],
+        schema: &Schema,
+    ) -> Result<Vec<Field>, crate::error::Error> {
+        if !self.batch {
+            return records
+                .iter()
+                .map(|record| self.evaluate(record, schema))
+                .collect();
+        }
+
+        let mut columns = vec![Vec::with_capacity(records.len()); self.args.len()];
+        for record in records {
+            let args = evaluate_args(&mut self.args, record, schema)?;
+            for (column, arg) in columns.iter_mut().zip(args) {
+                column.push(arg);
+            }
+        }
+        let args = columns
+            .into_iter()
+            .map(|column| column.into_iter().collect::<JsonArray>().into())
+            .collect();
+
+        let result =
+            self.tokio_runtime
+                .block_on(call_function(&self.deno_runtime, self.function, args))?;
+        let results = result
+            .into_array()
+            .map_err(|_| Error::BatchResultNotArray)?;
+        if results.len() != records.len() {
+            return Err(Error::BatchResultLength {
+                expected: records.len(),
+                actual: results.len(),
+            }
+            .into());
+        }
+        results
+            .into_iter()
+            .map(|result| to_field(result, &self.return_type))
+            .collect()
     }
 
     pub fn to_string(&self, schema: &Schema) -> String {
-        format!("{}({})", self.function_name, self.arg.to_string(schema))
+        let args = self
+            .args
+            .iter()
+            .map(|arg| arg.to_string(schema))
+            .collect::<Vec<_>>();
+        format!("{}({})", self.function_name, args.join(", "))
     }
 }
 
-async fn evaluate_impl(
-    function_name: String,
-    arg: &mut Expression,
-    runtime: &Arc<Mutex<dozer_deno::Runtime>>,
-    function: NonZeroI32,
+fn evaluate_args(
+    args: &mut [Expression],
     record: &Record,
     schema: &Schema,
-) -> Result<Field, crate::error::Error> {
-    let arg = arg.evaluate(record, schema)?;
-    let Field::Json(arg) = arg else {
-        return Err(crate::error::Error::InvalidFunctionArgument {
-            function_name,
-            argument_index: 0,
-            argument: arg,
-        });
-    };
+) -> Result<Vec<JsonValue>, crate::error::Error> {
+    args.iter_mut()
+        .map(|arg| Ok(field_to_json_value(arg.evaluate(record, schema)?)))
+        .collect()
+}
 
+async fn call_function(
+    runtime: &Arc<Mutex<dozer_deno::Runtime>>,
+    function: NonZeroI32,
+    args: Vec<JsonValue>,
+) -> Result<JsonValue, Error> {
     let mut runtime = runtime.lock().await;
-    let result = runtime
-        .call_function(function, vec![arg])
+    runtime
+        .call_function(function, args)
         .await
-        .map_err(Error::Evaluate)?;
-    Ok(Field::Json(result))
+        .map_err(Error::Evaluate)
+}
+
+/// JavaScript values are converted like a `CAST` from JSON, except strings, which are converted
+/// like a `CAST` from a string.
+fn to_field(value: JsonValue, return_type: &FieldType) -> Result<Field, crate::error::Error> {
+    if return_type == &FieldType::Json {
+        return Ok(Field::Json(value));
+    }
+    if value.is_null() {
+        return Ok(Field::Null);
+    }
+    let value = match value.into_string() {
+        Ok(value) => Field::String(value.as_str().to_string()),
+        Err(value) => Field::Json(value),
+    };
+    cast_field(&value, return_type)
 }
@@ -3,3 +3,6 @@ mod validate;
 
 pub use evaluate::{Error, Udf};
 pub use validate::validate_args;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,102 @@
+use std::sync::Arc;
+
+use dozer_types::types::{Field, FieldDefinition, FieldType, Record, Schema, SourceDefinition};
+use tokio::runtime::Runtime;
+
+use crate::{execution::Expression, operator::BinaryOperatorType};
+
+use super::Udf;
+
+fn create_runtime() -> Arc<Runtime> {
+    Arc::new(
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap(),
+    )
+}
+
+fn schema() -> Schema {
+    let mut schema = Schema::default();
+    for name in ["a", "b"] {
+        schema.field(
+            FieldDefinition::new(
+                name.to_string(),
+                FieldType::Int,
+                false,
+                SourceDefinition::Dynamic,
+            ),
+            false,
+        );
+    }
+    schema
+}
+
+fn udf(runtime: &Arc<Runtime>, module: &str, batch: bool) -> Udf {
+    runtime
+        .block_on(Udf::new(
+            runtime.clone(),
+            "add".to_string(),
+            format!("src/javascript/{module}"),
+            vec![
+                Expression::Column { index: 0 },
+                Expression::Column { index: 1 },
+            ],
+            FieldType::Int,
+            batch,
+        ))
+        .unwrap()
+}
+
+fn records() -> Vec<Record> {
+    vec![
+        Record::new(vec![Field::Int(1), Field::Int(2)]),
+        Record::new(vec![Field::Int(3), Field::Int(4)]),
+    ]
+}
+
+#[test]
+fn test_udf() {
+    let runtime = create_runtime();
+    let mut udf = udf(&runtime, "add.js", false);
+    assert_eq!(udf.get_type().return_type, FieldType::Int);
+    assert_eq!(
+        udf.evaluate(&records()[0], &schema()).unwrap(),
+        Field::Int(3)
+    );
+    assert_eq!(
+        udf.evaluate_batch(&records(), &schema()).unwrap(),
+        vec![Field::Int(3), Field::Int(7)]
+    );
+}
+
+#[test]
+fn test_batch_udf() {
+    let runtime = create_runtime();
+    let mut udf = udf(&runtime, "add_batch.js", true);
+    assert_eq!(
+        udf.evaluate_batch(&records(), &schema()).unwrap(),
+        vec![Field::Int(3), Field::Int(7)]
+    );
+}
+
+#[test]
+fn test_batch_udf_for_a_single_record() {
+    let runtime = create_runtime();
+    let mut udf = udf(&runtime, "add_batch.js", true);
+    assert_eq!(
+        udf.evaluate(&records()[1], &schema()).unwrap(),
+        Field::Int(7)
+    );
+
+    // Nested in another expression, the function is evaluated record by record.
+    let mut expression = Expression::BinaryOperator {
+        left: Box::new(Expression::JavaScriptUdf(udf)),
+        operator: BinaryOperatorType::Add,
+        right: Box::new(Expression::Literal(Field::Int(1))),
+    };
+    assert_eq!(
+        expression.evaluate_batch(&records(), &schema()).unwrap(),
+        vec![Field::Int(4), Field::Int(8)]
+    );
+}
@@ -1,27 +1,14 @@
-use dozer_types::types::{FieldType, Schema};
-
 use crate::{error::Error, execution::Expression};
 
-pub fn validate_args(
-    function_name: String,
-    args: &[Expression],
-    schema: &Schema,
-) -> Result<(), Error> {
-    if args.len() != 1 {
+/// Arguments can be of any type, they're passed to the function as JSON.
+/// Batch functions take the batch size from their arguments, so they need at least one.
+pub fn validate_args(function_name: String, args: &[Expression], batch: bool) -> Result<(), Error> {
+    if batch && args.is_empty() {
         return Err(Error::InvalidNumberOfArguments {
             function_name,
-            expected: 1..2,
+            expected: 1..usize::MAX,
             actual: args.len(),
         });
     }
-    let typ = args[0].get_type(schema)?;
-    if typ.return_type != FieldType::Json {
-        return Err(Error::InvalidFunctionArgumentType {
-            function_name,
-            argument_index: 0,
-            expected: vec![FieldType::Json],
-            actual: typ.return_type,
-        });
-    }
     Ok(())
 }
@@ -14,6 +14,7 @@ mod is_null;
 mod json_functions;
 mod logical;
 mod mathematical;
+mod nested;
 pub mod operator;
 pub mod scalar;
 
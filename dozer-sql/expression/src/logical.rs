@@ -31,7 +31,11 @@ pub fn evaluate_and(
             | Field::Date(_)
             | Field::Json(_)
             | Field::Point(_)
-            | Field::Duration(_) => Err(Error::InvalidType(r_field, "AND".to_string())),
+            | Field::Duration(_)
+            | Field::List(_)
+            | Field::Struct(_)
+            | Field::Uuid(_)
+            | Field::Time(_) => Err(Error::InvalidType(r_field, "AND".to_string())),
         },
         Field::Boolean(false) => match r_field {
             Field::Boolean(true) => Ok(Field::Boolean(false)),
@@ -51,7 +55,11 @@ pub fn evaluate_and(
             | Field::Date(_)
             | Field::Json(_)
             | Field::Point(_)
-            | Field::Duration(_) => Err(Error::InvalidType(r_field, "AND".to_string())),
+            | Field::Duration(_)
+            | Field::List(_)
+            | Field::Struct(_)
+            | Field::Uuid(_)
+            | Field::Time(_) => Err(Error::InvalidType(r_field, "AND".to_string())),
         },
         Field::Null => Ok(Field::Boolean(false)),
         Field::UInt(_)
@@ -68,7 +76,11 @@ pub fn evaluate_and(
         | Field::Date(_)
         | Field::Json(_)
         | Field::Point(_)
-        | Field::Duration(_) => Err(Error::InvalidType(l_field, "AND".to_string())),
+        | Field::Duration(_)
+        | Field::List(_)
+        | Field::Struct(_)
+        | Field::Uuid(_)
+        | Field::Time(_) => Err(Error::InvalidType(l_field, "AND".to_string())),
     }
 }
 
@@ -99,7 +111,11 @@ pub fn evaluate_or(
             | Field::Date(_)
             | Field::Json(_)
             | Field::Point(_)
-            | Field::Duration(_) => Err(Error::InvalidType(r_field, "OR".to_string())),
+            | Field::Duration(_)
+            | Field::List(_)
+            | Field::Struct(_)
+            | Field::Uuid(_)
+            | Field::Time(_) => Err(Error::InvalidType(r_field, "OR".to_string())),
         },
         Field::Boolean(false) | Field::Null => match right.evaluate(record, schema)? {
             Field::Boolean(false) => Ok(Field::Boolean(false)),
@@ -119,7 +135,11 @@ pub fn evaluate_or(
             | Field::Date(_)
             | Field::Json(_)
             | Field::Point(_)
-            | Field::Duration(_) => Err(Error::InvalidType(r_field, "OR".to_string())),
+            | Field::Duration(_)
+            | Field::List(_)
+            | Field::Struct(_)
+            | Field::Uuid(_)
+            | Field::Time(_) => Err(Error::InvalidType(r_field, "OR".to_string())),
         },
         Field::UInt(_)
         | Field::U128(_)
@@ -135,7 +155,11 @@ pub fn evaluate_or(
         | Field::Date(_)
         | Field::Json(_)
         | Field::Point(_)
-        | Field::Duration(_) => Err(Error::InvalidType(l_field, "OR".to_string())),
+        | Field::Duration(_)
+        | Field::List(_)
+        | Field::Struct(_)
+        | Field::Uuid(_)
+        | Field::Time(_) => Err(Error::InvalidType(l_field, "OR".to_string())),
     }
 }
 
@@ -163,7 +187,11 @@ pub fn evaluate_not(
         | Field::Date(_)
         | Field::Json(_)
         | Field::Point(_)
-        | Field::Duration(_) => Err(Error::InvalidType(value_p, "NOT".to_string())),
+        | Field::Duration(_)
+        | Field::List(_)
+        | Field::Struct(_)
+        | Field::Uuid(_)
+        | Field::Time(_) => Err(Error::InvalidType(value_p, "NOT".to_string())),
     }
 }
 
@@ -1,3 +1,4 @@
+use dozer_types::chrono::TimeZone;
 use dozer_types::rust_decimal::Decimal;
 use dozer_types::types::Record;
 use dozer_types::types::Schema;
@@ -70,21 +71,95 @@ macro_rules! define_math_operator {
                             $op.to_string(),
                         )),
                     },
-                    Field::UInt(_)
-                    | Field::U128(_)
-                    | Field::Int(_)
+                    Field::Date(right_v) => match $op {
+                        "+" => {
+                            let timestamp = date_to_timestamp(right_v)
+                                .checked_add_signed(chrono::Duration::nanoseconds(
+                                    left_v.0.as_nanos() as i64,
+                                ))
+                                .ok_or(PipelineError::SqlError(OperationError::AdditionOverflow))?;
+                            Ok(Field::Timestamp(timestamp))
+                        }
+                        &_ => Err(PipelineError::InvalidTypeComparison(
+                            left_p,
+                            right_p,
+                            $op.to_string(),
+                        )),
+                    },
+                    Field::UInt(_) | Field::Int(_) | Field::Float(_) => match $op {
+                        "*" => scale_duration(left_v, &right_p, $op).ok_or(
+                            PipelineError::SqlError(OperationError::MultiplicationOverflow),
+                        ),
+                        "/" => scale_duration(left_v, &right_p, $op).ok_or(
+                            PipelineError::SqlError(OperationError::DivisionByZeroOrOverflow),
+                        ),
+                        &_ => Err(PipelineError::InvalidTypeComparison(
+                            left_p,
+                            right_p,
+                            $op.to_string(),
+                        )),
+                    },
+                    Field::Null => Ok(Field::Null),
+                    Field::U128(_)
                     | Field::Int8(_)
                     | Field::I128(_)
-                    | Field::Float(_)
                     | Field::Boolean(_)
                     | Field::String(_)
                     | Field::Text(_)
                     | Field::Binary(_)
                     | Field::Decimal(_)
-                    | Field::Date(_)
                     | Field::Json(_)
                     | Field::Point(_)
-                    | Field::Null => Err(PipelineError::InvalidTypeComparison(
+                    | Field::List(_)
+                    | Field::Struct(_)
+                    | Field::Uuid(_)
+                    | Field::Time(_) => Err(PipelineError::InvalidTypeComparison(
+                        left_p,
+                        right_p,
+                        $op.to_string(),
+                    )),
+                },
+                Field::Date(left_v) => match right_p {
+                    Field::Duration(right_v) => {
+                        let duration = chrono::Duration::nanoseconds(right_v.0.as_nanos() as i64);
+                        match $op {
+                            "+" => Ok(Field::Timestamp(
+                                date_to_timestamp(left_v)
+                                    .checked_add_signed(duration)
+                                    .ok_or(PipelineError::SqlError(
+                                        OperationError::AdditionOverflow,
+                                    ))?,
+                            )),
+                            "-" => Ok(Field::Timestamp(
+                                date_to_timestamp(left_v)
+                                    .checked_sub_signed(duration)
+                                    .ok_or(PipelineError::SqlError(
+                                        OperationError::SubtractionOverflow,
+                                    ))?,
+                            )),
+                            &_ => Err(PipelineError::InvalidTypeComparison(
+                                left_p,
+                                right_p,
+                                $op.to_string(),
+                            )),
+                        }
+                    }
+                    Field::Date(right_v) => match $op {
+                        "-" if left_v >= right_v => {
+                            let seconds = (left_v - right_v).num_seconds();
+                            Ok(Field::from(DozerDuration(
+                                std::time::Duration::from_secs(seconds as u64),
+                                TimeUnit::Nanoseconds,
+                            )))
+                        }
+                        &_ => Err(PipelineError::InvalidTypeComparison(
+                            left_p,
+                            right_p,
+                            $op.to_string(),
+                        )),
+                    },
+                    Field::Null => Ok(Field::Null),
+                    _ => Err(PipelineError::InvalidTypeComparison(
                         left_p,
                         right_p,
                         $op.to_string(),
@@ -167,7 +242,11 @@ macro_rules! define_math_operator {
                     | Field::Date(_)
                     | Field::Json(_)
                     | Field::Point(_)
-                    | Field::Null => Err(PipelineError::InvalidTypeComparison(
+                    | Field::Null
+                    | Field::List(_)
+                    | Field::Struct(_)
+                    | Field::Uuid(_)
+                    | Field::Time(_) => Err(PipelineError::InvalidTypeComparison(
                         left_p,
                         right_p,
                         $op.to_string(),
@@ -405,7 +484,11 @@ macro_rules! define_math_operator {
                     | Field::Date(_)
                     | Field::Json(_)
                     | Field::Point(_)
-                    | Field::Duration(_) => Err(PipelineError::InvalidTypeComparison(
+                    | Field::Duration(_)
+                    | Field::List(_)
+                    | Field::Struct(_)
+                    | Field::Uuid(_)
+                    | Field::Time(_) => Err(PipelineError::InvalidTypeComparison(
                         left_p,
                         right_p,
                         $op.to_string(),
@@ -691,7 +774,11 @@ macro_rules! define_math_operator {
                     | Field::Date(_)
                     | Field::Json(_)
                     | Field::Point(_)
-                    | Field::Duration(_) => Err(PipelineError::InvalidTypeComparison(
+                    | Field::Duration(_)
+                    | Field::List(_)
+                    | Field::Struct(_)
+                    | Field::Uuid(_)
+                    | Field::Time(_) => Err(PipelineError::InvalidTypeComparison(
                         left_p,
                         right_p,
                         $op.to_string(),
@@ -981,7 +1068,11 @@ macro_rules! define_math_operator {
                         | Field::Date(_)
                         | Field::Json(_)
                         | Field::Point(_)
-                        | Field::Duration(_) => Err(PipelineError::InvalidTypeComparison(
+                        | Field::Duration(_)
+                        | Field::List(_)
+                        | Field::Struct(_)
+                        | Field::Uuid(_)
+                        | Field::Time(_) => Err(PipelineError::InvalidTypeComparison(
                             left_p,
                             right_p,
                             $op.to_string(),
@@ -1243,7 +1334,11 @@ macro_rules! define_math_operator {
                     | Field::Date(_)
                     | Field::Json(_)
                     | Field::Point(_)
-                    | Field::Duration(_) => Err(PipelineError::InvalidTypeComparison(
+                    | Field::Duration(_)
+                    | Field::List(_)
+                    | Field::Struct(_)
+                    | Field::Uuid(_)
+                    | Field::Time(_) => Err(PipelineError::InvalidTypeComparison(
                         left_p,
                         right_p,
                         $op.to_string(),
@@ -1550,7 +1645,11 @@ macro_rules! define_math_operator {
                     | Field::Date(_)
                     | Field::Json(_)
                     | Field::Point(_)
-                    | Field::Duration(_) => Err(PipelineError::InvalidTypeComparison(
+                    | Field::Duration(_)
+                    | Field::List(_)
+                    | Field::Struct(_)
+                    | Field::Uuid(_)
+                    | Field::Time(_) => Err(PipelineError::InvalidTypeComparison(
                         left_p,
                         right_p,
                         $op.to_string(),
@@ -1829,7 +1928,11 @@ macro_rules! define_math_operator {
                     | Field::Date(_)
                     | Field::Json(_)
                     | Field::Point(_)
-                    | Field::Duration(_) => Err(PipelineError::InvalidTypeComparison(
+                    | Field::Duration(_)
+                    | Field::List(_)
+                    | Field::Struct(_)
+                    | Field::Uuid(_)
+                    | Field::Time(_) => Err(PipelineError::InvalidTypeComparison(
                         left_p,
                         right_p,
                         $op.to_string(),
@@ -1982,7 +2085,11 @@ macro_rules! define_math_operator {
                                 | Field::Date(_)
                                 | Field::Json(_)
                                 | Field::Point(_)
-                                | Field::Duration(_) => Err(PipelineError::InvalidTypeComparison(
+                                | Field::Duration(_)
+                                | Field::List(_)
+                                | Field::Struct(_)
+                                | Field::Uuid(_)
+                                | Field::Time(_) => Err(PipelineError::InvalidTypeComparison(
                                     left_p,
                                     right_p,
                                     $op.to_string(),
@@ -2084,7 +2191,11 @@ macro_rules! define_math_operator {
                                 | Field::Date(_)
                                 | Field::Json(_)
                                 | Field::Point(_)
-                                | Field::Duration(_) => Err(PipelineError::InvalidTypeComparison(
+                                | Field::Duration(_)
+                                | Field::List(_)
+                                | Field::Struct(_)
+                                | Field::Uuid(_)
+                                | Field::Time(_) => Err(PipelineError::InvalidTypeComparison(
                                     left_p,
                                     right_p,
                                     $op.to_string(),
@@ -2186,7 +2297,11 @@ macro_rules! define_math_operator {
                                 | Field::Date(_)
                                 | Field::Json(_)
                                 | Field::Point(_)
-                                | Field::Duration(_) => Err(PipelineError::InvalidTypeComparison(
+                                | Field::Duration(_)
+                                | Field::List(_)
+                                | Field::Struct(_)
+                                | Field::Uuid(_)
+                                | Field::Time(_) => Err(PipelineError::InvalidTypeComparison(
                                     left_p,
                                     right_p,
                                     $op.to_string(),
@@ -2268,7 +2383,11 @@ macro_rules! define_math_operator {
                                 | Field::Date(_)
                                 | Field::Json(_)
                                 | Field::Point(_)
-                                | Field::Duration(_) => Err(PipelineError::InvalidTypeComparison(
+                                | Field::Duration(_)
+                                | Field::List(_)
+                                | Field::Struct(_)
+                                | Field::Uuid(_)
+                                | Field::Time(_) => Err(PipelineError::InvalidTypeComparison(
                                     left_p,
                                     right_p,
                                     $op.to_string(),
@@ -2288,9 +2407,12 @@ macro_rules! define_math_operator {
                 | Field::String(_)
                 | Field::Text(_)
                 | Field::Binary(_)
-                | Field::Date(_)
                 | Field::Json(_)
-                | Field::Point(_) => Err(PipelineError::InvalidTypeComparison(
+                | Field::Point(_)
+                | Field::List(_)
+                | Field::Struct(_)
+                | Field::Uuid(_)
+                | Field::Time(_) => Err(PipelineError::InvalidTypeComparison(
                     left_p,
                     right_p,
                     $op.to_string(),
@@ -2300,6 +2422,44 @@ macro_rules! define_math_operator {
     };
 }
 
+/// Dates are at midnight UTC in arithmetic with durations.
+fn date_to_timestamp(date: chrono::NaiveDate) -> chrono::DateTime<chrono::FixedOffset> {
+    chrono::Utc
+        .from_utc_datetime(&date.and_hms_opt(0, 0, 0).expect("midnight is a valid time"))
+        .into()
+}
+
+/// Multiplies or divides a duration by a non-negative number.
+fn scale_duration(duration: DozerDuration, factor: &Field, op: &str) -> Option<Field> {
+    let nanoseconds = duration.0.as_nanos();
+    let nanoseconds = match factor {
+        Field::UInt(_) | Field::Int(_) => {
+            let factor = u128::try_from(factor.to_i128()?).ok()?;
+            if op == "*" {
+                nanoseconds.checked_mul(factor)?
+            } else {
+                nanoseconds.checked_div(factor)?
+            }
+        }
+        Field::Float(factor) if factor.0 >= 0.0 => {
+            let nanoseconds = if op == "*" {
+                nanoseconds as f64 * factor.0
+            } else {
+                nanoseconds as f64 / factor.0
+            };
+            if !nanoseconds.is_finite() {
+                return None;
+            }
+            nanoseconds.round() as u128
+        }
+        _ => return None,
+    };
+    Some(Field::from(DozerDuration(
+        std::time::Duration::from_nanos(u64::try_from(nanoseconds).ok()?),
+        TimeUnit::Nanoseconds,
+    )))
+}
+
 define_math_operator!(evaluate_add, "+", std::ops::Add::add, 0);
 define_math_operator!(evaluate_sub, "-", std::ops::Sub::sub, 0);
 define_math_operator!(evaluate_mul, "*", std::ops::Mul::mul, 0);
@@ -2330,7 +2490,11 @@ pub fn evaluate_plus(
         | Field::Json(_)
         | Field::Point(_)
         | Field::Duration(_)
-        | Field::Null => Err(PipelineError::InvalidType(
+        | Field::Null
+        | Field::List(_)
+        | Field::Struct(_)
+        | Field::Uuid(_)
+        | Field::Time(_) => Err(PipelineError::InvalidType(
             expression_result,
             "+".to_string(),
         )),
@@ -2361,7 +2525,7 @@ pub fn evaluate_minus(
         | Field::Json(_)
         | Field::Point(_)
         | Field::Duration(_)
-        | Field::Null => Err(PipelineError::InvalidType(
+        | Field::Null | Field::List(_) | Field::Struct(_) | Field::Uuid(_) | Field::Time(_) => Err(PipelineError::InvalidType(
             expression_result,
             "-".to_string(),
         )),
@@ -2316,45 +2316,47 @@ fn test_duration_math(d1: u64, d2: u64, dt1: ArbitraryDateTime) {
     let result = evaluate_mod(&Schema::default(), &mut dur1, &mut dur2, &row);
     assert!(result.is_err());
 
-    // Duration + Timestamp = Error
+    // Dates are at midnight in arithmetic with durations.
+    let midnight = date_to_timestamp(dt1.0.date_naive());
+
+    // Duration + Date = Timestamp
     let result = evaluate_add(&Schema::default(), &mut dur1, &mut v, &row);
-    assert!(result.is_err());
-    // Duration - Timestamp = Error
+    let sum = midnight.checked_add_signed(chrono::Duration::nanoseconds(d1 as i64));
+    if result.is_ok() && sum.is_some() {
+        assert_eq!(result.unwrap(), Field::Timestamp(sum.unwrap()));
+    }
+    // Duration - Date = Error
     let result = evaluate_sub(&Schema::default(), &mut dur1, &mut v, &row);
     assert!(result.is_err());
-    // Duration * Timestamp = Error
+    // Duration * Date = Error
     let result = evaluate_mul(&Schema::default(), &mut dur1, &mut v, &row);
     assert!(result.is_err());
-    // Duration / Timestamp = Error
+    // Duration / Date = Error
     let result = evaluate_div(&Schema::default(), &mut dur1, &mut v, &row);
     assert!(result.is_err());
-    // Duration % Timestamp = Error
+    // Duration % Date = Error
     let result = evaluate_mod(&Schema::default(), &mut dur1, &mut v, &row);
     assert!(result.is_err());
 
-    // Timestamp + Duration = Timestamp
+    // Date + Duration = Timestamp
     let result = evaluate_add(&Schema::default(), &mut v, &mut dur1, &row);
-    let sum = dt1
-        .0
-        .checked_add_signed(chrono::Duration::nanoseconds(d1 as i64));
+    let sum = midnight.checked_add_signed(chrono::Duration::nanoseconds(d1 as i64));
     if result.is_ok() && sum.is_some() {
         assert_eq!(result.unwrap(), Field::Timestamp(sum.unwrap()));
     }
-    // Timestamp - Duration = Timestamp
+    // Date - Duration = Timestamp
     let result = evaluate_sub(&Schema::default(), &mut v, &mut dur2, &row);
-    let diff = dt1
-        .0
-        .checked_sub_signed(chrono::Duration::nanoseconds(d2 as i64));
+    let diff = midnight.checked_sub_signed(chrono::Duration::nanoseconds(d2 as i64));
     if result.is_ok() && diff.is_some() {
         assert_eq!(result.unwrap(), Field::Timestamp(diff.unwrap()));
     }
-    // Timestamp * Duration = Error
+    // Date * Duration = Error
     let result = evaluate_mul(&Schema::default(), &mut v, &mut dur1, &row);
     assert!(result.is_err());
-    // Timestamp / Duration = Error
+    // Date / Duration = Error
     let result = evaluate_div(&Schema::default(), &mut v, &mut dur1, &row);
     assert!(result.is_err());
-    // Timestamp % Duration = Error
+    // Date % Duration = Error
     let result = evaluate_mod(&Schema::default(), &mut v, &mut dur1, &row);
     assert!(result.is_err());
 }
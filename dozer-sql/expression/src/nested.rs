@@ -0,0 +1,257 @@
+use dozer_types::types::{Field, FieldType, Record, Schema, SourceDefinition};
+
+use crate::arg_utils::validate_one_argument;
+use crate::error::Error;
+use crate::execution::{Expression, ExpressionType};
+use crate::scalar::common::ScalarFunctionType;
+
+/// Evaluates `arg[index]`. Indexes are 1-based; out of range indexes and NULL lists yield NULL.
+pub(crate) fn evaluate_element_at(
+    schema: &Schema,
+    arg: &mut Expression,
+    index: &mut Expression,
+    record: &Record,
+) -> Result<Field, Error> {
+    let list = arg.evaluate(record, schema)?;
+    let index = index.evaluate(record, schema)?;
+    if list == Field::Null || index == Field::Null {
+        return Ok(Field::Null);
+    }
+
+    let Some(elements) = list.as_list() else {
+        return Err(Error::InvalidType(list, "[]".to_string()));
+    };
+    let Some(index) = index.to_int() else {
+        return Err(Error::InvalidType(index, "[]".to_string()));
+    };
+
+    Ok(usize::try_from(index)
+        .ok()
+        .and_then(|index| index.checked_sub(1))
+        .and_then(|index| elements.get(index))
+        .cloned()
+        .unwrap_or(Field::Null))
+}
+
+pub(crate) fn get_element_at_type(
+    arg: &Expression,
+    index: &Expression,
+    schema: &Schema,
+) -> Result<ExpressionType, Error> {
+    let arg_type = arg.get_type(schema)?;
+    let Some(element_type) = arg_type.return_type.element_type() else {
+        return Err(Error::CannotIndexInto(arg_type.return_type));
+    };
+
+    let index_type = index.get_type(schema)?.return_type;
+    if !matches!(
+        index_type,
+        FieldType::Int | FieldType::UInt | FieldType::I128 | FieldType::U128
+    ) {
+        return Err(Error::InvalidIndexType(index_type));
+    }
+
+    Ok(ExpressionType::new(
+        element_type.clone(),
+        true,
+        SourceDefinition::Dynamic,
+        false,
+    ))
+}
+
+/// Evaluates `arg.name`, where `index` is the position of `name` in the struct type of `arg`.
+pub(crate) fn evaluate_struct_field(
+    schema: &Schema,
+    arg: &mut Expression,
+    index: usize,
+    record: &Record,
+) -> Result<Field, Error> {
+    let value = arg.evaluate(record, schema)?;
+    if value == Field::Null {
+        return Ok(Field::Null);
+    }
+
+    match value.as_struct() {
+        Some(fields) => Ok(fields.get(index).cloned().unwrap_or(Field::Null)),
+        None => Err(Error::InvalidType(value, ".".to_string())),
+    }
+}
+
+pub(crate) fn get_struct_field_type(
+    arg: &Expression,
+    name: &str,
+    schema: &Schema,
+) -> Result<ExpressionType, Error> {
+    let arg_type = arg.get_type(schema)?;
+    match arg_type.return_type.struct_field(name) {
+        Some((_, field)) => Ok(ExpressionType::new(
+            field.typ.clone(),
+            true,
+            SourceDefinition::Dynamic,
+            false,
+        )),
+        None => Err(Error::UnknownStructField {
+            name: name.to_string(),
+            typ: arg_type.return_type,
+        }),
+    }
+}
+
+pub(crate) fn evaluate_cardinality(
+    schema: &Schema,
+    arg: &mut Expression,
+    record: &Record,
+) -> Result<Field, Error> {
+    let value = arg.evaluate(record, schema)?;
+    if value == Field::Null {
+        return Ok(Field::Null);
+    }
+
+    match value.as_list() {
+        Some(elements) => Ok(Field::UInt(elements.len() as u64)),
+        None => Err(Error::InvalidFunctionArgument {
+            function_name: ScalarFunctionType::Cardinality.to_string(),
+            argument_index: 0,
+            argument: value,
+        }),
+    }
+}
+
+pub(crate) fn validate_cardinality(
+    args: &[Expression],
+    schema: &Schema,
+) -> Result<ExpressionType, Error> {
+    let arg = validate_one_argument(args, schema, ScalarFunctionType::Cardinality)?;
+    if arg.return_type.element_type().is_none() {
+        return Err(Error::InvalidFunctionArgumentType {
+            function_name: ScalarFunctionType::Cardinality.to_string(),
+            argument_index: 0,
+            actual: arg.return_type,
+            expected: vec![FieldType::List(Box::new(FieldType::Json))],
+        });
+    }
+
+    Ok(ExpressionType::new(
+        FieldType::UInt,
+        arg.nullable,
+        SourceDefinition::Dynamic,
+        false,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use dozer_types::types::FieldDefinition;
+
+    use super::*;
+
+    fn point_type() -> FieldType {
+        FieldType::Struct(vec![
+            FieldDefinition::new(
+                "x".to_string(),
+                FieldType::Int,
+                false,
+                SourceDefinition::Dynamic,
+            ),
+            FieldDefinition::new(
+                "y".to_string(),
+                FieldType::Int,
+                false,
+                SourceDefinition::Dynamic,
+            ),
+        ])
+    }
+
+    #[test]
+    fn test_element_at() {
+        let list = Field::List(vec![Field::Int(1), Field::Int(2), Field::Null]);
+        let record = Record::new(vec![]);
+        let schema = Schema::default();
+        let element_at = |index: i64| {
+            evaluate_element_at(
+                &schema,
+                &mut Expression::Literal(list.clone()),
+                &mut Expression::Literal(Field::Int(index)),
+                &record,
+            )
+            .unwrap()
+        };
+
+        assert_eq!(element_at(1), Field::Int(1));
+        assert_eq!(element_at(2), Field::Int(2));
+        assert_eq!(element_at(3), Field::Null);
+        assert_eq!(element_at(4), Field::Null);
+        assert_eq!(element_at(0), Field::Null);
+        assert_eq!(element_at(-1), Field::Null);
+    }
+
+    #[test]
+    fn test_element_at_type() {
+        let mut schema = Schema::default();
+        schema.field(
+            FieldDefinition::new(
+                "tags".to_string(),
+                FieldType::List(Box::new(FieldType::String)),
+                true,
+                SourceDefinition::Dynamic,
+            ),
+            false,
+        );
+
+        let typ = get_element_at_type(
+            &Expression::Column { index: 0 },
+            &Expression::Literal(Field::Int(1)),
+            &schema,
+        )
+        .unwrap();
+        assert_eq!(typ.return_type, FieldType::String);
+
+        assert!(get_element_at_type(
+            &Expression::Literal(Field::Int(1)),
+            &Expression::Literal(Field::Int(1)),
+            &schema,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_struct_field() {
+        let mut schema = Schema::default();
+        schema.field(
+            FieldDefinition::new(
+                "p".to_string(),
+                point_type(),
+                true,
+                SourceDefinition::Dynamic,
+            ),
+            false,
+        );
+        let record = Record::new(vec![Field::Struct(vec![Field::Int(3), Field::Int(4)])]);
+
+        let typ = get_struct_field_type(&Expression::Column { index: 0 }, "Y", &schema).unwrap();
+        assert_eq!(typ.return_type, FieldType::Int);
+        assert!(get_struct_field_type(&Expression::Column { index: 0 }, "z", &schema).is_err());
+
+        assert_eq!(
+            evaluate_struct_field(&schema, &mut Expression::Column { index: 0 }, 1, &record)
+                .unwrap(),
+            Field::Int(4)
+        );
+    }
+
+    #[test]
+    fn test_cardinality() {
+        let record = Record::new(vec![]);
+        let schema = Schema::default();
+        let cardinality = |value: Field| {
+            evaluate_cardinality(&schema, &mut Expression::Literal(value), &record).unwrap()
+        };
+
+        assert_eq!(
+            cardinality(Field::List(vec![Field::Int(1), Field::Null])),
+            Field::UInt(2)
+        );
+        assert_eq!(cardinality(Field::List(vec![])), Field::UInt(0));
+        assert_eq!(cardinality(Field::Null), Field::Null);
+    }
+}
@@ -69,12 +69,17 @@ pub fn evaluate_py_udf(
             FieldType::String => Field::String(res.extract::<String>()?),
             FieldType::Text => Field::Text(res.extract::<String>()?),
             FieldType::Binary => Field::Binary(res.extract::<Vec<u8>>()?),
-            FieldType::Decimal
+            FieldType::Decimal(_)
             | FieldType::Date
             | FieldType::Timestamp
             | FieldType::Point
             | FieldType::Duration
-            | FieldType::Json => return Err(Error::UnsupportedReturnType(*return_type)),
+            | FieldType::Json
+            | FieldType::List(_)
+            | FieldType::Struct(_)
+            | FieldType::Uuid
+            | FieldType::Time
+            | FieldType::Enum(_) => return Err(Error::UnsupportedReturnType(return_type.clone())),
         })
     })
     .map_err(Into::into)
@@ -1,6 +1,7 @@
 use crate::arg_utils::{validate_num_arguments, validate_one_argument, validate_two_arguments};
 use crate::error::Error;
 use crate::execution::{Expression, ExpressionType};
+use crate::nested::{evaluate_cardinality, validate_cardinality};
 use crate::scalar::field::evaluate_nvl;
 use crate::scalar::number::{evaluate_abs, evaluate_round};
 use crate::scalar::string::{
@@ -13,7 +14,8 @@ use std::fmt::{Display, Formatter};
 
 use super::field::{evaluate_decode, validate_decode};
 use super::string::{
-    evaluate_chr, evaluate_replace, evaluate_substr, validate_replace, validate_substr,
+    evaluate_chr, evaluate_replace, evaluate_string_function, evaluate_substr, validate_replace,
+    validate_string_function, validate_substr,
 };
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Hash)]
@@ -29,6 +31,18 @@ pub enum ScalarFunctionType {
     Nvl,
     Replace,
     Decode,
+    SplitPart,
+    Lower,
+    Lpad,
+    Rpad,
+    Strpos,
+    Left,
+    Right,
+    Reverse,
+    Initcap,
+    Md5,
+    Sha256,
+    Cardinality,
 }
 
 impl Display for ScalarFunctionType {
@@ -45,6 +59,18 @@ impl Display for ScalarFunctionType {
             ScalarFunctionType::Nvl => f.write_str("NVL"),
             ScalarFunctionType::Replace => f.write_str("REPLACE"),
             ScalarFunctionType::Decode => f.write_str("DECODE"),
+            ScalarFunctionType::SplitPart => f.write_str("SPLIT_PART"),
+            ScalarFunctionType::Lower => f.write_str("LOWER"),
+            ScalarFunctionType::Lpad => f.write_str("LPAD"),
+            ScalarFunctionType::Rpad => f.write_str("RPAD"),
+            ScalarFunctionType::Strpos => f.write_str("STRPOS"),
+            ScalarFunctionType::Left => f.write_str("LEFT"),
+            ScalarFunctionType::Right => f.write_str("RIGHT"),
+            ScalarFunctionType::Reverse => f.write_str("REVERSE"),
+            ScalarFunctionType::Initcap => f.write_str("INITCAP"),
+            ScalarFunctionType::Md5 => f.write_str("MD5"),
+            ScalarFunctionType::Sha256 => f.write_str("SHA256"),
+            ScalarFunctionType::Cardinality => f.write_str("CARDINALITY"),
         }
     }
 }
@@ -96,6 +122,18 @@ pub(crate) fn get_scalar_function_type(
         }
         ScalarFunctionType::Replace => validate_replace(args, schema),
         ScalarFunctionType::Decode => validate_decode(args, schema),
+        ScalarFunctionType::SplitPart
+        | ScalarFunctionType::Lower
+        | ScalarFunctionType::Lpad
+        | ScalarFunctionType::Rpad
+        | ScalarFunctionType::Strpos
+        | ScalarFunctionType::Left
+        | ScalarFunctionType::Right
+        | ScalarFunctionType::Reverse
+        | ScalarFunctionType::Initcap
+        | ScalarFunctionType::Md5
+        | ScalarFunctionType::Sha256 => validate_string_function(function, args, schema),
+        ScalarFunctionType::Cardinality => validate_cardinality(args, schema),
     }
 }
 
@@ -104,7 +142,8 @@ impl ScalarFunctionType {
         match name {
             "abs" => Some(ScalarFunctionType::Abs),
             "round" => Some(ScalarFunctionType::Round),
-            "ucase" => Some(ScalarFunctionType::Ucase),
+            "ucase" | "upper" => Some(ScalarFunctionType::Ucase),
+            "lcase" | "lower" => Some(ScalarFunctionType::Lower),
             "concat" => Some(ScalarFunctionType::Concat),
             "decode" => Some(ScalarFunctionType::Decode),
             "length" => Some(ScalarFunctionType::Length),
@@ -113,6 +152,17 @@ impl ScalarFunctionType {
             "substr" => Some(ScalarFunctionType::Substr),
             "replace" => Some(ScalarFunctionType::Replace),
             "nvl" => Some(ScalarFunctionType::Nvl),
+            "split_part" => Some(ScalarFunctionType::SplitPart),
+            "lpad" => Some(ScalarFunctionType::Lpad),
+            "rpad" => Some(ScalarFunctionType::Rpad),
+            "strpos" => Some(ScalarFunctionType::Strpos),
+            "left" => Some(ScalarFunctionType::Left),
+            "right" => Some(ScalarFunctionType::Right),
+            "reverse" => Some(ScalarFunctionType::Reverse),
+            "initcap" => Some(ScalarFunctionType::Initcap),
+            "md5" => Some(ScalarFunctionType::Md5),
+            "sha256" => Some(ScalarFunctionType::Sha256),
+            "cardinality" => Some(ScalarFunctionType::Cardinality),
             _ => None,
         }
     }
@@ -194,6 +244,21 @@ impl ScalarFunctionType {
 
                 evaluate_decode(schema, &mut arg0[0], results, default, record)
             }
+            ScalarFunctionType::SplitPart
+            | ScalarFunctionType::Lower
+            | ScalarFunctionType::Lpad
+            | ScalarFunctionType::Rpad
+            | ScalarFunctionType::Strpos
+            | ScalarFunctionType::Left
+            | ScalarFunctionType::Right
+            | ScalarFunctionType::Reverse
+            | ScalarFunctionType::Initcap
+            | ScalarFunctionType::Md5
+            | ScalarFunctionType::Sha256 => evaluate_string_function(self, schema, args, record),
+            ScalarFunctionType::Cardinality => {
+                validate_num_arguments(1..2, args.len(), ScalarFunctionType::Cardinality)?;
+                evaluate_cardinality(schema, &mut args[0], record)
+            }
         }
     }
 }
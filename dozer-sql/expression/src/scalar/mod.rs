@@ -1,4 +1,5 @@
 pub mod common;
 pub mod field;
 pub mod number;
+pub mod regexp;
 pub mod string;
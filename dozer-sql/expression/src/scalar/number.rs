@@ -29,7 +29,11 @@ pub(crate) fn evaluate_abs(
         | Field::Json(_)
         | Field::Point(_)
         | Field::Duration(_)
-        | Field::Null => Err(Error::InvalidFunctionArgument {
+        | Field::Null
+        | Field::List(_)
+        | Field::Struct(_)
+        | Field::Uuid(_)
+        | Field::Time(_) => Err(Error::InvalidFunctionArgument {
             function_name: ScalarFunctionType::Abs.to_string(),
             argument_index: 0,
             argument: value,
@@ -59,7 +63,7 @@ pub(crate) fn evaluate_round(
                     .to_i32()
                     .ok_or(Error::InvalidCast {
                         from: field,
-                        to: FieldType::Decimal,
+                        to: FieldType::Decimal(None),
                     })
                     .unwrap()
             }
@@ -72,7 +76,11 @@ pub(crate) fn evaluate_round(
             | Field::Json(_)
             | Field::Point(_)
             | Field::Duration(_)
-            | Field::Null => {} // Truncate value to 0 decimals
+            | Field::Null
+            | Field::List(_)
+            | Field::Struct(_)
+            | Field::Uuid(_)
+            | Field::Time(_) => {} // Truncate value to 0 decimals
         }
     }
     let order = OrderedFloat(10.0_f64.powi(places));
@@ -94,7 +102,11 @@ pub(crate) fn evaluate_round(
         | Field::Binary(_)
         | Field::Json(_)
         | Field::Point(_)
-        | Field::Duration(_) => Err(Error::InvalidFunctionArgument {
+        | Field::Duration(_)
+        | Field::List(_)
+        | Field::Struct(_)
+        | Field::Uuid(_)
+        | Field::Time(_) => Err(Error::InvalidFunctionArgument {
             function_name: ScalarFunctionType::Round.to_string(),
             argument_index: 0,
             argument: value,
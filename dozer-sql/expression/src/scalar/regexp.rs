@@ -0,0 +1,365 @@
+use std::borrow::Cow;
+use std::fmt::{Display, Formatter};
+
+use crate::arg_utils::{validate_arg_type, validate_num_arguments};
+use crate::error::Error;
+use crate::execution::{Expression, ExpressionType};
+
+use dozer_types::types::{Field, FieldType, Record, Schema, SourceDefinition};
+use regex::{Regex, RegexBuilder};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+pub enum RegexpFunctionType {
+    Like,
+    Replace,
+    Extract,
+    SimilarTo,
+}
+
+impl Display for RegexpFunctionType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RegexpFunctionType::Like => f.write_str("REGEXP_LIKE"),
+            RegexpFunctionType::Replace => f.write_str("REGEXP_REPLACE"),
+            RegexpFunctionType::Extract => f.write_str("REGEXP_EXTRACT"),
+            RegexpFunctionType::SimilarTo => f.write_str("SIMILAR TO"),
+        }
+    }
+}
+
+impl RegexpFunctionType {
+    pub fn new(name: &str) -> Option<RegexpFunctionType> {
+        match name {
+            "regexp_like" => Some(RegexpFunctionType::Like),
+            "regexp_replace" => Some(RegexpFunctionType::Replace),
+            "regexp_extract" | "regexp_substr" => Some(RegexpFunctionType::Extract),
+            _ => None,
+        }
+    }
+
+    /// The index of the argument with the match parameters, or with the escape character of `SIMILAR TO`.
+    fn options_index(&self) -> Option<usize> {
+        match self {
+            RegexpFunctionType::Like | RegexpFunctionType::SimilarTo => Some(2),
+            RegexpFunctionType::Replace => Some(3),
+            RegexpFunctionType::Extract => None,
+        }
+    }
+}
+
+/// The regex of a regex function, compiled when the expression is built if the pattern and options are literals,
+/// or else whenever they differ from the ones of the previous record.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RegexCache(Option<CompiledRegex>);
+
+#[derive(Debug, Clone)]
+struct CompiledRegex {
+    pattern: String,
+    options: Option<String>,
+    regex: Regex,
+}
+
+// The regex is determined by the pattern and options it's compiled from.
+impl PartialEq for CompiledRegex {
+    fn eq(&self, other: &Self) -> bool {
+        self.pattern == other.pattern && self.options == other.options
+    }
+}
+
+impl RegexCache {
+    /// Compiles the regex of a function whose pattern and options are literals.
+    pub fn new(function: RegexpFunctionType, args: &[Expression]) -> Result<Self, Error> {
+        let literal = |index: usize| match args.get(index) {
+            Some(Expression::Literal(value)) if *value != Field::Null => Some(Some(text(value))),
+            Some(_) => None,
+            None => Some(None),
+        };
+        let options = match function.options_index() {
+            Some(index) => literal(index),
+            None => Some(None),
+        };
+        let mut cache = Self::default();
+        if let (Some(Some(pattern)), Some(options)) = (literal(1), options) {
+            cache.get(function, &pattern, options.as_deref())?;
+        }
+        Ok(cache)
+    }
+
+    /// Returns the regex of `pattern` and `options`, compiling it if they're not the cached ones.
+    fn get(
+        &mut self,
+        function: RegexpFunctionType,
+        pattern: &str,
+        options: Option<&str>,
+    ) -> Result<&Regex, Error> {
+        let is_cached = matches!(
+            &self.0,
+            Some(compiled) if compiled.pattern == pattern && compiled.options.as_deref() == options
+        );
+        if !is_cached {
+            self.0 = Some(CompiledRegex {
+                pattern: pattern.to_string(),
+                options: options.map(str::to_string),
+                regex: compile(function, pattern, options)?,
+            });
+        }
+        Ok(&self.0.as_ref().expect("regex was just compiled").regex)
+    }
+}
+
+fn compile(
+    function: RegexpFunctionType,
+    pattern: &str,
+    options: Option<&str>,
+) -> Result<Regex, Error> {
+    let pattern = match function {
+        RegexpFunctionType::SimilarTo => similar_to_regex(pattern, similar_to_escape(options)?),
+        _ => with_flags(pattern, options.unwrap_or_default())?,
+    };
+    Ok(RegexBuilder::new(&pattern).build()?)
+}
+
+/// Prefixes `pattern` with the inline flags of the `i` (case-insensitive), `c` (case-sensitive),
+/// `m` (multi-line) and `s` or `n` (`.` matches newlines) match parameters.
+fn with_flags(pattern: &str, flags: &str) -> Result<String, Error> {
+    let mut inline = String::new();
+    for flag in flags.chars() {
+        match flag {
+            'i' => inline.push('i'),
+            'c' => inline.retain(|flag| flag != 'i'),
+            'm' => inline.push('m'),
+            's' | 'n' => inline.push('s'),
+            _ => return Err(Error::InvalidRegexFlag(flag)),
+        }
+    }
+    Ok(if inline.is_empty() {
+        pattern.to_string()
+    } else {
+        format!("(?{inline}){pattern}")
+    })
+}
+
+/// The escape character of `SIMILAR TO`, which is `\` unless another or an empty one is given.
+fn similar_to_escape(escape: Option<&str>) -> Result<Option<char>, Error> {
+    let Some(escape) = escape else {
+        return Ok(Some('\\'));
+    };
+    let mut chars = escape.chars();
+    match (chars.next(), chars.next()) {
+        (escape, None) => Ok(escape),
+        _ => Err(Error::InvalidFunctionArgument {
+            function_name: RegexpFunctionType::SimilarTo.to_string(),
+            argument_index: 2,
+            argument: Field::String(escape.to_string()),
+        }),
+    }
+}
+
+/// The text of a string argument, which is only copied if it's not a string.
+fn text(value: &Field) -> Cow<'_, str> {
+    match value {
+        Field::String(value) | Field::Text(value) => Cow::Borrowed(value),
+        value => Cow::Owned(value.to_string()),
+    }
+}
+
+pub(crate) fn validate_regexp(
+    function: RegexpFunctionType,
+    args: &[Expression],
+    schema: &Schema,
+) -> Result<ExpressionType, Error> {
+    let (num_arguments, return_type) = match function {
+        RegexpFunctionType::Like => (2..4, FieldType::Boolean),
+        RegexpFunctionType::Replace => (3..5, FieldType::String),
+        RegexpFunctionType::Extract => (2..4, FieldType::String),
+        RegexpFunctionType::SimilarTo => (2..4, FieldType::Boolean),
+    };
+    validate_num_arguments(num_arguments, args.len(), function)?;
+
+    let mut nullable = function == RegexpFunctionType::Extract;
+    for (index, arg) in args.iter().enumerate() {
+        let expected = match (function, index) {
+            (RegexpFunctionType::Extract, 2) => vec![
+                FieldType::UInt,
+                FieldType::U128,
+                FieldType::Int,
+                FieldType::I128,
+            ],
+            _ => vec![FieldType::String, FieldType::Text],
+        };
+        nullable |= validate_arg_type(arg, expected, schema, function, index)?.nullable;
+    }
+    let return_type = match (return_type, args[0].get_type(schema)?.return_type) {
+        (FieldType::String, FieldType::Text) => FieldType::Text,
+        (return_type, _) => return_type,
+    };
+    Ok(ExpressionType::new(
+        return_type,
+        nullable,
+        SourceDefinition::Dynamic,
+        false,
+    ))
+}
+
+pub(crate) fn evaluate_regexp(
+    function: RegexpFunctionType,
+    regex: &mut RegexCache,
+    schema: &Schema,
+    args: &mut [Expression],
+    record: &Record,
+) -> Result<Field, Error> {
+    let mut values = Vec::with_capacity(args.len());
+    for arg in args.iter_mut() {
+        let value = arg.evaluate(record, schema)?;
+        if value == Field::Null {
+            return Ok(Field::Null);
+        }
+        values.push(value);
+    }
+    let string = text(&values[0]);
+    let options = function
+        .options_index()
+        .and_then(|index| values.get(index))
+        .map(text);
+    let regex = regex.get(function, &text(&values[1]), options.as_deref())?;
+    let same_type = |result: String| match &values[0] {
+        Field::Text(_) => Field::Text(result),
+        _ => Field::String(result),
+    };
+
+    match function {
+        RegexpFunctionType::Like | RegexpFunctionType::SimilarTo => {
+            Ok(Field::Boolean(regex.is_match(&string)))
+        }
+        RegexpFunctionType::Replace => {
+            let replacement = to_rust_replacement(&text(&values[2]));
+            Ok(same_type(
+                regex
+                    .replace_all(&string, replacement.as_str())
+                    .into_owned(),
+            ))
+        }
+        RegexpFunctionType::Extract => {
+            // Without a group index, the first group is extracted, or the whole match if there are no groups.
+            let group = match values.get(2) {
+                Some(group) => group
+                    .to_uint()
+                    .and_then(|group| usize::try_from(group).ok())
+                    .filter(|group| *group < regex.captures_len())
+                    .ok_or_else(|| Error::InvalidFunctionArgument {
+                        function_name: function.to_string(),
+                        argument_index: 2,
+                        argument: group.clone(),
+                    })?,
+                None => usize::from(regex.captures_len() > 1),
+            };
+            Ok(regex
+                .captures(&string)
+                .and_then(|captures| captures.get(group))
+                .map_or(Field::Null, |found| same_type(found.as_str().to_string())))
+        }
+    }
+}
+
+/// Replaces the `\1` back-references of SQL replacements with the `${1}` of the regex crate.
+fn to_rust_replacement(replacement: &str) -> String {
+    let mut result = String::with_capacity(replacement.len());
+    let mut chars = replacement.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, chars.peek()) {
+            ('\\', Some(digit)) if digit.is_ascii_digit() => {
+                result.push_str(&format!("${{{digit}}}"));
+                chars.next();
+            }
+            ('\\', Some('\\')) => {
+                result.push('\\');
+                chars.next();
+            }
+            ('$', _) => result.push_str("$$"),
+            (c, _) => result.push(c),
+        }
+    }
+    result
+}
+
+/// Translates a `SIMILAR TO` pattern, which must match the whole string, to a regex.
+/// `%` and `_` are the `LIKE` wildcards, and the other regex operators are kept.
+fn similar_to_regex(pattern: &str, escape: Option<char>) -> String {
+    let mut regex = String::from("^(?:");
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            c if Some(c) == escape => {
+                if let Some(escaped) = chars.next() {
+                    regex.push_str(&regex::escape(&escaped.to_string()));
+                }
+            }
+            '%' => regex.push_str(".*"),
+            '_' => regex.push('.'),
+            '.' | '^' | '$' | '\\' => regex.push_str(&regex::escape(&c.to_string())),
+            c => regex.push(c),
+        }
+    }
+    regex.push_str(")$");
+    regex
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_similar_to_regex() {
+        assert_eq!(similar_to_regex("%(b|d)%", Some('\\')), "^(?:.*(b|d).*)$");
+        assert_eq!(similar_to_regex("a_c.\\%", Some('\\')), "^(?:a.c\\.%)$");
+    }
+
+    #[test]
+    fn test_rust_replacement() {
+        assert_eq!(to_rust_replacement("\\2-\\1 $"), "${2}-${1} $$");
+    }
+
+    #[test]
+    fn test_regex_cache() {
+        let mut cache = RegexCache::default();
+        let like = RegexpFunctionType::Like;
+        assert!(cache.get(like, "^a", Some("i")).unwrap().is_match("ABC"));
+        assert!(!cache.get(like, "^a", None).unwrap().is_match("ABC"));
+        assert!(matches!(
+            cache.get(like, "(", None),
+            Err(Error::InvalidRegex(_))
+        ));
+        assert!(matches!(
+            cache.get(like, "a", Some("x")),
+            Err(Error::InvalidRegexFlag('x'))
+        ));
+        assert!(matches!(
+            cache.get(RegexpFunctionType::SimilarTo, "a", Some("xy")),
+            Err(Error::InvalidFunctionArgument { .. })
+        ));
+    }
+
+    #[test]
+    fn test_literal_pattern_is_compiled_once() {
+        let literal = |value: &str| Expression::Literal(Field::String(value.to_string()));
+        let args = [Expression::Column { index: 0 }, literal("^a"), literal("i")];
+        let cache = RegexCache::new(RegexpFunctionType::Like, &args).unwrap();
+        assert_eq!(
+            cache.0.map(|compiled| compiled.regex.as_str().to_string()),
+            Some("(?i)^a".to_string())
+        );
+
+        let args = [
+            Expression::Column { index: 0 },
+            Expression::Column { index: 1 },
+        ];
+        let cache = RegexCache::new(RegexpFunctionType::Like, &args).unwrap();
+        assert!(cache.0.is_none());
+
+        let args = [Expression::Column { index: 0 }, literal("(")];
+        assert!(matches!(
+            RegexCache::new(RegexpFunctionType::Like, &args),
+            Err(Error::InvalidRegex(_))
+        ));
+    }
+}
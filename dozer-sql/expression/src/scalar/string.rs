@@ -10,7 +10,9 @@ use crate::scalar::common::ScalarFunctionType;
 use dozer_types::log;
 use dozer_types::types::Record;
 use dozer_types::types::{Field, FieldType, Schema};
-use like::{Escape, Like};
+use like::{Escape, ILike, Like};
+use md5::Md5;
+use sha2::{Digest, Sha256};
 
 pub(crate) fn validate_ucase(arg: &Expression, schema: &Schema) -> Result<ExpressionType, Error> {
     validate_arg_type(
@@ -39,7 +41,7 @@ pub fn evaluate_ucase(
         | FieldType::Int8
         | FieldType::I128
         | FieldType::Float
-        | FieldType::Decimal
+        | FieldType::Decimal(_)
         | FieldType::Boolean
         | FieldType::Text
         | FieldType::Date
@@ -47,7 +49,12 @@ pub fn evaluate_ucase(
         | FieldType::Binary
         | FieldType::Json
         | FieldType::Point
-        | FieldType::Duration => Field::Text(ret),
+        | FieldType::Duration
+        | FieldType::List(_)
+        | FieldType::Struct(_)
+        | FieldType::Uuid
+        | FieldType::Time
+        | FieldType::Enum(_) => Field::Text(ret),
     })
 }
 
@@ -99,7 +106,7 @@ pub fn evaluate_concat(
         | FieldType::Int8
         | FieldType::I128
         | FieldType::Float
-        | FieldType::Decimal
+        | FieldType::Decimal(_)
         | FieldType::Boolean
         | FieldType::String
         | FieldType::Date
@@ -107,7 +114,12 @@ pub fn evaluate_concat(
         | FieldType::Binary
         | FieldType::Json
         | FieldType::Point
-        | FieldType::Duration => Field::String(res_str),
+        | FieldType::Duration
+        | FieldType::List(_)
+        | FieldType::Struct(_)
+        | FieldType::Uuid
+        | FieldType::Time
+        | FieldType::Enum(_) => Field::String(res_str),
     })
 }
 
@@ -181,7 +193,7 @@ pub fn evaluate_trim(
         | FieldType::Int8
         | FieldType::I128
         | FieldType::Float
-        | FieldType::Decimal
+        | FieldType::Decimal(_)
         | FieldType::Boolean
         | FieldType::Text
         | FieldType::Date
@@ -189,7 +201,12 @@ pub fn evaluate_trim(
         | FieldType::Binary
         | FieldType::Json
         | FieldType::Point
-        | FieldType::Duration => Field::Text(retval),
+        | FieldType::Duration
+        | FieldType::List(_)
+        | FieldType::Struct(_)
+        | FieldType::Uuid
+        | FieldType::Time
+        | FieldType::Enum(_) => Field::Text(retval),
     })
 }
 
@@ -222,6 +239,36 @@ pub fn evaluate_like(
     escape: Option<char>,
     record: &Record,
 ) -> Result<Field, Error> {
+    evaluate_like_operator(schema, arg, pattern, escape, false, record)
+}
+
+/// The case-insensitive `LIKE`.
+pub fn evaluate_ilike(
+    schema: &Schema,
+    arg: &mut Expression,
+    pattern: &mut Expression,
+    escape: Option<char>,
+    record: &Record,
+) -> Result<Field, Error> {
+    evaluate_like_operator(schema, arg, pattern, escape, true, record)
+}
+
+fn evaluate_like_operator(
+    schema: &Schema,
+    arg: &mut Expression,
+    pattern: &mut Expression,
+    escape: Option<char>,
+    case_insensitive: bool,
+    record: &Record,
+) -> Result<Field, Error> {
+    let like = |value: &str, pattern: &str| {
+        if case_insensitive {
+            ILike::<false>::ilike(value, pattern)
+        } else {
+            Like::<false>::like(value, pattern)
+        }
+    };
+
     let arg_field = arg.evaluate(record, schema)?;
     let arg_value = arg_field.to_string();
     let arg_string = arg_value.as_str();
@@ -232,12 +279,11 @@ pub fn evaluate_like(
 
     if let Some(escape_char) = escape {
         let arg_escape = &arg_string.escape(&escape_char.to_string())?;
-        let result =
-            Like::<false>::like(arg_escape.as_str(), pattern_string).map(Field::Boolean)?;
+        let result = like(arg_escape.as_str(), pattern_string).map(Field::Boolean)?;
         return Ok(result);
     }
 
-    let result = Like::<false>::like(arg_string, pattern_string).map(Field::Boolean)?;
+    let result = like(arg_string, pattern_string).map(Field::Boolean)?;
     Ok(result)
 }
 
@@ -325,7 +371,11 @@ pub(crate) fn evaluate_chr(
         | Field::Json(_)
         | Field::Point(_)
         | Field::Duration(_)
-        | Field::Null => Err(Error::InvalidFunctionArgument {
+        | Field::Null
+        | Field::List(_)
+        | Field::Struct(_)
+        | Field::Uuid(_)
+        | Field::Time(_) => Err(Error::InvalidFunctionArgument {
             function_name: ScalarFunctionType::Chr.to_string(),
             argument_index: 0,
             argument: value,
@@ -513,6 +563,203 @@ pub(crate) fn evaluate_replace(
     Ok(Field::String(result))
 }
 
+const TEXT_TYPES: [FieldType; 2] = [FieldType::String, FieldType::Text];
+
+const INTEGER_TYPES: [FieldType; 4] = [
+    FieldType::UInt,
+    FieldType::U128,
+    FieldType::Int,
+    FieldType::I128,
+];
+
+pub(crate) fn validate_string_function(
+    function: &ScalarFunctionType,
+    args: &[Expression],
+    schema: &Schema,
+) -> Result<ExpressionType, Error> {
+    let expected: Vec<Vec<FieldType>> = match function {
+        ScalarFunctionType::Lower | ScalarFunctionType::Reverse | ScalarFunctionType::Initcap => {
+            vec![TEXT_TYPES.to_vec()]
+        }
+        ScalarFunctionType::SplitPart => vec![
+            TEXT_TYPES.to_vec(),
+            TEXT_TYPES.to_vec(),
+            INTEGER_TYPES.to_vec(),
+        ],
+        ScalarFunctionType::Lpad | ScalarFunctionType::Rpad => {
+            validate_num_arguments(2..4, args.len(), function)?;
+            vec![
+                TEXT_TYPES.to_vec(),
+                INTEGER_TYPES.to_vec(),
+                TEXT_TYPES.to_vec(),
+            ]
+        }
+        ScalarFunctionType::Strpos => vec![TEXT_TYPES.to_vec(), TEXT_TYPES.to_vec()],
+        ScalarFunctionType::Left | ScalarFunctionType::Right => {
+            vec![TEXT_TYPES.to_vec(), INTEGER_TYPES.to_vec()]
+        }
+        ScalarFunctionType::Md5 | ScalarFunctionType::Sha256 => {
+            vec![vec![FieldType::String, FieldType::Text, FieldType::Binary]]
+        }
+        _ => unreachable!("{function} is not a string function"),
+    };
+    if !matches!(
+        function,
+        ScalarFunctionType::Lpad | ScalarFunctionType::Rpad
+    ) {
+        validate_num_arguments(expected.len()..expected.len() + 1, args.len(), function)?;
+    }
+
+    let mut nullable = false;
+    for (index, (arg, expected)) in args.iter().zip(expected).enumerate() {
+        nullable |= validate_arg_type(arg, expected, schema, function, index)?.nullable;
+    }
+    let return_type = match function {
+        ScalarFunctionType::Strpos => FieldType::UInt,
+        ScalarFunctionType::Md5 | ScalarFunctionType::Sha256 => FieldType::String,
+        _ => args[0].get_type(schema)?.return_type,
+    };
+    Ok(ExpressionType::new(
+        return_type,
+        nullable,
+        dozer_types::types::SourceDefinition::Dynamic,
+        false,
+    ))
+}
+
+pub(crate) fn evaluate_string_function(
+    function: &ScalarFunctionType,
+    schema: &Schema,
+    args: &mut [Expression],
+    record: &Record,
+) -> Result<Field, Error> {
+    let mut values = Vec::with_capacity(args.len());
+    for arg in args.iter_mut() {
+        let value = arg.evaluate(record, schema)?;
+        if value == Field::Null {
+            return Ok(Field::Null);
+        }
+        values.push(value);
+    }
+    let int = |index: usize| {
+        values[index]
+            .to_int()
+            .ok_or_else(|| Error::InvalidFunctionArgument {
+                function_name: function.to_string(),
+                argument_index: index,
+                argument: values[index].clone(),
+            })
+    };
+    let string = values[0].to_string();
+    let chars = string.chars().collect::<Vec<_>>();
+
+    let result = match function {
+        ScalarFunctionType::Lower => string.to_lowercase(),
+        ScalarFunctionType::Reverse => chars.iter().rev().collect(),
+        ScalarFunctionType::Initcap => initcap(&string),
+        ScalarFunctionType::SplitPart => {
+            let delimiter = values[1].to_string();
+            let index = int(2)?;
+            let parts = if delimiter.is_empty() {
+                vec![string.as_str()]
+            } else {
+                string.split(delimiter.as_str()).collect()
+            };
+            // Negative indexes count from the end.
+            let part = match index {
+                0 => {
+                    return Err(Error::InvalidFunctionArgument {
+                        function_name: function.to_string(),
+                        argument_index: 2,
+                        argument: values[2].clone(),
+                    })
+                }
+                1.. => parts.get(index as usize - 1),
+                _ => parts
+                    .len()
+                    .checked_sub(index.unsigned_abs() as usize)
+                    .and_then(|index| parts.get(index)),
+            };
+            part.copied().unwrap_or_default().to_string()
+        }
+        ScalarFunctionType::Lpad | ScalarFunctionType::Rpad => {
+            let length = usize::try_from(int(1)?).unwrap_or(0);
+            let fill = values
+                .get(2)
+                .map_or_else(|| vec![' '], |fill| fill.to_string().chars().collect());
+            if chars.len() >= length || fill.is_empty() {
+                // Strings that are too long are truncated, and strings that can't be filled are unchanged.
+                chars.iter().take(length).collect()
+            } else {
+                let padding = fill
+                    .iter()
+                    .cycle()
+                    .take(length - chars.len())
+                    .collect::<String>();
+                if *function == ScalarFunctionType::Lpad {
+                    padding + &string
+                } else {
+                    string + &padding
+                }
+            }
+        }
+        ScalarFunctionType::Strpos => {
+            let substring = values[1].to_string();
+            let position = string
+                .find(substring.as_str())
+                .map_or(0, |index| string[..index].chars().count() as u64 + 1);
+            return Ok(Field::UInt(position));
+        }
+        ScalarFunctionType::Left | ScalarFunctionType::Right => {
+            // A negative length is the number of characters to leave out.
+            let length = int(1)?;
+            let length = if length < 0 {
+                chars.len().saturating_sub(length.unsigned_abs() as usize)
+            } else {
+                (length as usize).min(chars.len())
+            };
+            if *function == ScalarFunctionType::Left {
+                chars[..length].iter().collect()
+            } else {
+                chars[chars.len() - length..].iter().collect()
+            }
+        }
+        ScalarFunctionType::Md5 | ScalarFunctionType::Sha256 => {
+            let bytes = match &values[0] {
+                Field::Binary(bytes) => bytes.as_slice(),
+                _ => string.as_bytes(),
+            };
+            let hash = if *function == ScalarFunctionType::Md5 {
+                format!("{:x}", Md5::digest(bytes))
+            } else {
+                format!("{:x}", Sha256::digest(bytes))
+            };
+            return Ok(Field::String(hash));
+        }
+        _ => unreachable!("{function} is not a string function"),
+    };
+
+    Ok(match values[0] {
+        Field::Text(_) => Field::Text(result),
+        _ => Field::String(result),
+    })
+}
+
+/// Capitalizes the first letter of each word, and lowercases the others.
+fn initcap(string: &str) -> String {
+    let mut result = String::with_capacity(string.len());
+    let mut word_start = true;
+    for c in string.chars() {
+        if word_start {
+            result.extend(c.to_uppercase());
+        } else {
+            result.extend(c.to_lowercase());
+        }
+        word_start = !c.is_alphanumeric();
+    }
+    result
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,10 +1,17 @@
 #![allow(clippy::enum_variant_names)]
 
+use crate::aggregation::approx_count_distinct::ApproxCountDistinctAggregator;
+use crate::aggregation::array_agg::ArrayAggAggregator;
 use crate::aggregation::avg::AvgAggregator;
+use crate::aggregation::bool_agg::BoolAggAggregator;
 use crate::aggregation::count::CountAggregator;
+use crate::aggregation::count_distinct::CountDistinctAggregator;
 use crate::aggregation::max::MaxAggregator;
 use crate::aggregation::min::MinAggregator;
+use crate::aggregation::percentile::PercentileAggregator;
+use crate::aggregation::string_agg::StringAggAggregator;
 use crate::aggregation::sum::SumAggregator;
+use crate::aggregation::variance::VarianceAggregator;
 use crate::calculate_err;
 use crate::errors::PipelineError;
 use dozer_types::chrono::{DateTime, FixedOffset, NaiveDate};
@@ -46,19 +53,40 @@ pub enum AggregatorEnum {
     MaxValueAggregator,
     SumAggregator,
     CountAggregator,
+    CountDistinctAggregator,
+    ApproxCountDistinctAggregator,
+    PercentileAggregator,
+    VarianceAggregator,
+    StringAggAggregator,
+    ArrayAggAggregator,
+    BoolAggAggregator,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
 pub enum AggregatorType {
+    ApproxCountDistinct,
+    ArrayAgg,
     Avg,
+    BoolAnd,
+    BoolOr,
     Count,
+    CountDistinct,
+    JsonAgg,
     Max,
     MaxAppendOnly,
     MaxValue,
+    Median,
     Min,
     MinAppendOnly,
     MinValue,
+    PercentileCont,
+    PercentileDisc,
+    StddevPop,
+    StddevSamp,
+    StringAgg,
     Sum,
+    VarPop,
+    VarSamp,
 }
 
 #[derive(Debug, bincode::Encode, bincode::Decode)]
@@ -178,14 +206,17 @@ impl OrderedAggregatorState {
         Ok(())
     }
 
-    pub(crate) fn new(function_type: AggregateFunctionType, field_type: FieldType) -> Option<Self> {
+    pub(crate) fn new(
+        function_type: AggregateFunctionType,
+        field_type: &FieldType,
+    ) -> Option<Self> {
         let inner = match field_type {
             FieldType::UInt => OrderedAggregatorStateInner::UInt(Default::default()),
             FieldType::U128 => OrderedAggregatorStateInner::U128(Default::default()),
             FieldType::Int => OrderedAggregatorStateInner::Int(Default::default()),
             FieldType::I128 => OrderedAggregatorStateInner::I128(Default::default()),
             FieldType::Float => OrderedAggregatorStateInner::Float(Default::default()),
-            FieldType::Decimal => OrderedAggregatorStateInner::Decimal(Default::default()),
+            FieldType::Decimal(_) => OrderedAggregatorStateInner::Decimal(Default::default()),
             FieldType::Timestamp => OrderedAggregatorStateInner::Timestamp(Default::default()),
             FieldType::Date => OrderedAggregatorStateInner::Date(Default::default()),
             FieldType::Duration => OrderedAggregatorStateInner::Duration(Default::default()),
@@ -241,30 +272,58 @@ impl OrderedAggregatorState {
 impl Display for AggregatorType {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
+            AggregatorType::ApproxCountDistinct => f.write_str("approx_count_distinct"),
+            AggregatorType::ArrayAgg => f.write_str("array_agg"),
             AggregatorType::Avg => f.write_str("avg"),
+            AggregatorType::BoolAnd => f.write_str("bool_and"),
+            AggregatorType::BoolOr => f.write_str("bool_or"),
             AggregatorType::Count => f.write_str("count"),
+            AggregatorType::CountDistinct => f.write_str("count_distinct"),
+            AggregatorType::JsonAgg => f.write_str("json_agg"),
             AggregatorType::Max => f.write_str("max"),
             AggregatorType::MaxAppendOnly => f.write_str("max_append_only"),
             AggregatorType::MaxValue => f.write_str("max_value"),
+            AggregatorType::Median => f.write_str("median"),
             AggregatorType::Min => f.write_str("min"),
             AggregatorType::MinAppendOnly => f.write_str("min_append_only"),
             AggregatorType::MinValue => f.write_str("min_value"),
+            AggregatorType::PercentileCont => f.write_str("percentile_cont"),
+            AggregatorType::PercentileDisc => f.write_str("percentile_disc"),
+            AggregatorType::StddevPop => f.write_str("stddev_pop"),
+            AggregatorType::StddevSamp => f.write_str("stddev_samp"),
+            AggregatorType::StringAgg => f.write_str("string_agg"),
             AggregatorType::Sum => f.write_str("sum"),
+            AggregatorType::VarPop => f.write_str("var_pop"),
+            AggregatorType::VarSamp => f.write_str("var_samp"),
         }
     }
 }
 
 pub fn get_aggregator_from_aggregator_type(typ: AggregatorType) -> AggregatorEnum {
     match typ {
+        AggregatorType::ApproxCountDistinct => ApproxCountDistinctAggregator::new().into(),
+        AggregatorType::ArrayAgg | AggregatorType::JsonAgg => ArrayAggAggregator::new().into(),
         AggregatorType::Avg => AvgAggregator::new().into(),
+        AggregatorType::BoolAnd => BoolAggAggregator::new(true).into(),
+        AggregatorType::BoolOr => BoolAggAggregator::new(false).into(),
         AggregatorType::Count => CountAggregator::new().into(),
+        AggregatorType::CountDistinct => CountDistinctAggregator::new().into(),
         AggregatorType::Max => MaxAggregator::new().into(),
         AggregatorType::MaxAppendOnly => MaxAppendOnlyAggregator::new().into(),
         AggregatorType::MaxValue => MaxValueAggregator::new().into(),
         AggregatorType::Min => MinAggregator::new().into(),
         AggregatorType::MinAppendOnly => MinAppendOnlyAggregator::new().into(),
         AggregatorType::MinValue => MinValueAggregator::new().into(),
+        AggregatorType::Median | AggregatorType::PercentileCont => {
+            PercentileAggregator::new(true).into()
+        }
+        AggregatorType::PercentileDisc => PercentileAggregator::new(false).into(),
+        AggregatorType::StddevPop => VarianceAggregator::new(true, true).into(),
+        AggregatorType::StddevSamp => VarianceAggregator::new(false, true).into(),
+        AggregatorType::StringAgg => StringAggAggregator::new().into(),
         AggregatorType::Sum => SumAggregator::new().into(),
+        AggregatorType::VarPop => VarianceAggregator::new(true, false).into(),
+        AggregatorType::VarSamp => VarianceAggregator::new(false, false).into(),
     }
 }
 
@@ -405,10 +464,46 @@ pub fn get_aggregator_type_from_aggregation_expression(
                 .clone()],
             AggregatorType::Count,
         )),
+        // These take all their arguments, which the expression type check has validated.
+        Expression::AggregateFunction { fun, args } => {
+            let aggregator_type = match fun {
+                AggregateFunctionType::ApproxCountDistinct => AggregatorType::ApproxCountDistinct,
+                AggregateFunctionType::ArrayAgg => AggregatorType::ArrayAgg,
+                AggregateFunctionType::BoolAnd => AggregatorType::BoolAnd,
+                AggregateFunctionType::BoolOr => AggregatorType::BoolOr,
+                AggregateFunctionType::CountDistinct => AggregatorType::CountDistinct,
+                AggregateFunctionType::JsonAgg => AggregatorType::JsonAgg,
+                AggregateFunctionType::Median => AggregatorType::Median,
+                AggregateFunctionType::PercentileCont => AggregatorType::PercentileCont,
+                AggregateFunctionType::PercentileDisc => AggregatorType::PercentileDisc,
+                AggregateFunctionType::StddevPop => AggregatorType::StddevPop,
+                AggregateFunctionType::StddevSamp => AggregatorType::StddevSamp,
+                AggregateFunctionType::StringAgg => AggregatorType::StringAgg,
+                AggregateFunctionType::VarPop => AggregatorType::VarPop,
+                AggregateFunctionType::VarSamp => AggregatorType::VarSamp,
+                _ => return Err(PipelineError::InvalidFunction(e.to_string(schema))),
+            };
+            if args.is_empty() {
+                return Err(PipelineError::NotEnoughArguments(fun.to_string()));
+            }
+            Ok((args.clone(), aggregator_type))
+        }
         _ => Err(PipelineError::InvalidFunction(e.to_string(schema))),
     }
 }
 
+/// Adds or removes one occurrence of a value in a multiset.
+pub(crate) fn update_count_map(map: &mut BTreeMap<Field, u64>, value: &Field, incr: bool) {
+    if incr {
+        *map.entry(value.clone()).or_insert(0) += 1;
+    } else if let Some(count) = map.get_mut(value) {
+        *count -= 1;
+        if *count == 0 {
+            map.remove(value);
+        }
+    }
+}
+
 pub fn update_val_map(
     fields: &[Field],
     val_delta: u64,
@@ -0,0 +1,108 @@
+use crate::aggregation::aggregator::Aggregator;
+use crate::aggregation::count::get_count;
+use crate::errors::PipelineError;
+use crate::utils::record_hashtable_key::get_record_hash;
+use dozer_types::types::{Field, FieldType};
+use std::collections::BTreeMap;
+
+/// The number of bits of a hash that pick its register.
+const PRECISION: u32 = 12;
+const REGISTERS: usize = 1 << PRECISION;
+/// The register values are scaled by `2^SCALE` so their sum is exact.
+const SCALE: u32 = 64 - PRECISION + 1;
+
+/// A HyperLogLog that can forget values.
+///
+/// Instead of the largest rank hashed to each register, it keeps how many values hashed to each rank,
+/// so a deleted value that had the largest rank gives the register back the next largest one.
+#[derive(Debug, bincode::Encode, bincode::Decode)]
+pub struct ApproxCountDistinctAggregator {
+    /// The number of values by register and rank.
+    ranks: BTreeMap<(u16, u8), u64>,
+    /// The sum of `2^(SCALE - rank)` over the largest rank of every register.
+    sum: u128,
+    empty_registers: u64,
+    return_type: Option<FieldType>,
+}
+
+impl ApproxCountDistinctAggregator {
+    pub fn new() -> Self {
+        Self {
+            ranks: BTreeMap::new(),
+            sum: (REGISTERS as u128) << SCALE,
+            empty_registers: REGISTERS as u64,
+            return_type: None,
+        }
+    }
+
+    fn max_rank(&self, register: u16) -> u8 {
+        self.ranks
+            .range((register, 0)..=(register, u8::MAX))
+            .next_back()
+            .map_or(0, |((_, rank), _)| *rank)
+    }
+
+    fn count_value(&mut self, value: &Field, incr: bool) {
+        if value == &Field::Null {
+            return;
+        }
+        let hash = get_record_hash(std::iter::once(value));
+        let register = (hash >> (64 - PRECISION)) as u16;
+        let rank = ((hash << PRECISION).leading_zeros() + 1).min(SCALE) as u8;
+
+        let old_rank = self.max_rank(register);
+        let count = self.ranks.entry((register, rank)).or_insert(0);
+        if incr {
+            *count += 1;
+        } else {
+            *count = count.saturating_sub(1);
+            if *count == 0 {
+                self.ranks.remove(&(register, rank));
+            }
+        }
+        let new_rank = self.max_rank(register);
+
+        if old_rank != new_rank {
+            self.sum -= 1 << (SCALE - old_rank as u32);
+            self.sum += 1 << (SCALE - new_rank as u32);
+            match (old_rank, new_rank) {
+                (0, _) => self.empty_registers -= 1,
+                (_, 0) => self.empty_registers += 1,
+                _ => (),
+            }
+        }
+    }
+
+    fn estimate(&self) -> u64 {
+        let registers = REGISTERS as f64;
+        let alpha = 0.7213 / (1.0 + 1.079 / registers);
+        let estimate = alpha * registers * registers / (self.sum as f64 / (1u128 << SCALE) as f64);
+        // Small cardinalities are better estimated by counting the empty registers.
+        if estimate <= 2.5 * registers && self.empty_registers > 0 {
+            (registers * (registers / self.empty_registers as f64).ln()).round() as u64
+        } else {
+            estimate.round() as u64
+        }
+    }
+}
+
+impl Aggregator for ApproxCountDistinctAggregator {
+    fn init(&mut self, return_type: FieldType) {
+        self.return_type = Some(return_type);
+    }
+
+    fn update(&mut self, old: &[Field], new: &[Field]) -> Result<Field, PipelineError> {
+        self.delete(old)?;
+        self.insert(new)
+    }
+
+    fn delete(&mut self, old: &[Field]) -> Result<Field, PipelineError> {
+        self.count_value(&old[0], false);
+        get_count(self.estimate(), self.return_type.as_ref())
+    }
+
+    fn insert(&mut self, new: &[Field]) -> Result<Field, PipelineError> {
+        self.count_value(&new[0], true);
+        get_count(self.estimate(), self.return_type.as_ref())
+    }
+}
@@ -0,0 +1,57 @@
+use crate::aggregation::aggregator::{update_count_map, Aggregator};
+use crate::errors::PipelineError;
+use dozer_types::json_types::{field_to_json_value, JsonArray};
+use dozer_types::types::{Field, FieldType};
+use std::collections::BTreeMap;
+
+/// `ARRAY_AGG` and `JSON_AGG`, which collect the values, nulls included, into a JSON array.
+///
+/// The values are in order, so the result doesn't depend on the order they arrived in.
+#[derive(Debug, bincode::Encode, bincode::Decode)]
+pub struct ArrayAggAggregator {
+    current_state: BTreeMap<Field, u64>,
+    return_type: Option<FieldType>,
+}
+
+impl ArrayAggAggregator {
+    pub fn new() -> Self {
+        Self {
+            current_state: BTreeMap::new(),
+            return_type: None,
+        }
+    }
+
+    fn get_array(&self) -> Field {
+        if self.current_state.is_empty() {
+            return Field::Null;
+        }
+        let mut array = JsonArray::new();
+        for (value, count) in &self.current_state {
+            for _ in 0..*count {
+                array.push(field_to_json_value(value.clone()));
+            }
+        }
+        Field::Json(array.into())
+    }
+}
+
+impl Aggregator for ArrayAggAggregator {
+    fn init(&mut self, return_type: FieldType) {
+        self.return_type = Some(return_type);
+    }
+
+    fn update(&mut self, old: &[Field], new: &[Field]) -> Result<Field, PipelineError> {
+        self.delete(old)?;
+        self.insert(new)
+    }
+
+    fn delete(&mut self, old: &[Field]) -> Result<Field, PipelineError> {
+        update_count_map(&mut self.current_state, &old[0], false);
+        Ok(self.get_array())
+    }
+
+    fn insert(&mut self, new: &[Field]) -> Result<Field, PipelineError> {
+        update_count_map(&mut self.current_state, &new[0], true);
+        Ok(self.get_array())
+    }
+}
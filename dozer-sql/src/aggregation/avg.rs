@@ -54,7 +54,7 @@ impl Aggregator for AvgAggregator {
             old,
             &mut self.current_state,
             &mut self.current_count,
-            self.return_type,
+            self.return_type.as_ref(),
             true,
         )
     }
@@ -65,7 +65,7 @@ impl Aggregator for AvgAggregator {
             new,
             &mut self.current_state,
             &mut self.current_count,
-            self.return_type,
+            self.return_type.as_ref(),
             false,
         )
     }
@@ -75,7 +75,7 @@ fn get_average(
     field: &[Field],
     current_sum: &mut SumState,
     current_count: &mut u64,
-    return_type: Option<FieldType>,
+    return_type: Option<&FieldType>,
     decr: bool,
 ) -> Result<Field, PipelineError> {
     let sum = get_sum(field, current_sum, return_type, decr)?;
@@ -126,7 +126,7 @@ fn get_average(
                     f_sum.div_wrapping(*current_count as f64),
                 )))
             }
-            FieldType::Decimal => {
+            FieldType::Decimal(_) => {
                 if *current_count == 0 {
                     return Ok(Field::Null);
                 }
@@ -162,7 +162,12 @@ fn get_average(
             | FieldType::Timestamp
             | FieldType::Binary
             | FieldType::Json
-            | FieldType::Point => Err(PipelineError::InvalidReturnType(format!(
+            | FieldType::Point
+            | FieldType::List(_)
+            | FieldType::Struct(_)
+            | FieldType::Uuid
+            | FieldType::Time
+            | FieldType::Enum(_) => Err(PipelineError::InvalidReturnType(format!(
                 "Not supported return type {typ} for {Avg}"
             ))),
         },
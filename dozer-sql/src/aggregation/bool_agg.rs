@@ -0,0 +1,75 @@
+use crate::aggregation::aggregator::Aggregator;
+use crate::calculate_err_field;
+use crate::errors::PipelineError;
+use dozer_sql_expression::aggregate::AggregateFunctionType::{BoolAnd, BoolOr};
+use dozer_types::types::{Field, FieldType};
+
+/// `BOOL_AND` and `BOOL_OR`, which count the true and false values so either can be removed.
+#[derive(Debug, bincode::Encode, bincode::Decode)]
+pub struct BoolAggAggregator {
+    true_count: u64,
+    false_count: u64,
+    /// `BOOL_AND` if true, `BOOL_OR` otherwise.
+    all: bool,
+    return_type: Option<FieldType>,
+}
+
+impl BoolAggAggregator {
+    pub fn new(all: bool) -> Self {
+        Self {
+            true_count: 0,
+            false_count: 0,
+            all,
+            return_type: None,
+        }
+    }
+
+    fn count_value(&mut self, value: &Field, incr: bool) -> Result<(), PipelineError> {
+        if value == &Field::Null {
+            return Ok(());
+        }
+        let function = if self.all { BoolAnd } else { BoolOr };
+        let count = if calculate_err_field!(value.as_boolean(), function, value) {
+            &mut self.true_count
+        } else {
+            &mut self.false_count
+        };
+        if incr {
+            *count += 1;
+        } else {
+            *count -= 1;
+        }
+        Ok(())
+    }
+
+    fn get_value(&self) -> Field {
+        if self.true_count + self.false_count == 0 {
+            Field::Null
+        } else if self.all {
+            Field::Boolean(self.false_count == 0)
+        } else {
+            Field::Boolean(self.true_count > 0)
+        }
+    }
+}
+
+impl Aggregator for BoolAggAggregator {
+    fn init(&mut self, return_type: FieldType) {
+        self.return_type = Some(return_type);
+    }
+
+    fn update(&mut self, old: &[Field], new: &[Field]) -> Result<Field, PipelineError> {
+        self.delete(old)?;
+        self.insert(new)
+    }
+
+    fn delete(&mut self, old: &[Field]) -> Result<Field, PipelineError> {
+        self.count_value(&old[0], false)?;
+        Ok(self.get_value())
+    }
+
+    fn insert(&mut self, new: &[Field]) -> Result<Field, PipelineError> {
+        self.count_value(&new[0], true)?;
+        Ok(self.get_value())
+    }
+}
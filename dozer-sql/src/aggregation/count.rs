@@ -34,16 +34,19 @@ impl Aggregator for CountAggregator {
 
     fn delete(&mut self, old: &[Field]) -> Result<Field, PipelineError> {
         self.current_state -= old.len() as u64;
-        get_count(self.current_state, self.return_type)
+        get_count(self.current_state, self.return_type.as_ref())
     }
 
     fn insert(&mut self, new: &[Field]) -> Result<Field, PipelineError> {
         self.current_state += new.len() as u64;
-        get_count(self.current_state, self.return_type)
+        get_count(self.current_state, self.return_type.as_ref())
     }
 }
 
-fn get_count(count: u64, return_type: Option<FieldType>) -> Result<Field, PipelineError> {
+pub(crate) fn get_count(
+    count: u64,
+    return_type: Option<&FieldType>,
+) -> Result<Field, PipelineError> {
     match return_type {
         Some(typ) => match typ {
             FieldType::UInt => Ok(Field::UInt(count)),
@@ -52,10 +55,10 @@ fn get_count(count: u64, return_type: Option<FieldType>) -> Result<Field, Pipeli
             FieldType::Int8 => Ok(Field::Int8(count as i8)),
             FieldType::I128 => Ok(Field::I128(count as i128)),
             FieldType::Float => Ok(Field::Float(OrderedFloat::from(count as f64))),
-            FieldType::Decimal => Ok(Field::Decimal(calculate_err_type!(
+            FieldType::Decimal(_) => Ok(Field::Decimal(calculate_err_type!(
                 Decimal::from_f64(count as f64),
                 Count,
-                FieldType::Decimal
+                FieldType::Decimal(None)
             ))),
             FieldType::Duration => Ok(Field::Int(count as i64)),
             FieldType::Boolean
@@ -65,7 +68,12 @@ fn get_count(count: u64, return_type: Option<FieldType>) -> Result<Field, Pipeli
             | FieldType::Timestamp
             | FieldType::Binary
             | FieldType::Json
-            | FieldType::Point => Err(PipelineError::InvalidReturnType(format!(
+            | FieldType::Point
+            | FieldType::List(_)
+            | FieldType::Struct(_)
+            | FieldType::Uuid
+            | FieldType::Time
+            | FieldType::Enum(_) => Err(PipelineError::InvalidReturnType(format!(
                 "Not supported return type {typ} for {Count}"
             ))),
         },
@@ -0,0 +1,45 @@
+use crate::aggregation::aggregator::{update_count_map, Aggregator};
+use crate::aggregation::count::get_count;
+use crate::errors::PipelineError;
+use dozer_types::types::{Field, FieldType};
+use std::collections::BTreeMap;
+
+#[derive(Debug, bincode::Encode, bincode::Decode)]
+pub struct CountDistinctAggregator {
+    current_state: BTreeMap<Field, u64>,
+    return_type: Option<FieldType>,
+}
+
+impl CountDistinctAggregator {
+    pub fn new() -> Self {
+        Self {
+            current_state: BTreeMap::new(),
+            return_type: None,
+        }
+    }
+}
+
+impl Aggregator for CountDistinctAggregator {
+    fn init(&mut self, return_type: FieldType) {
+        self.return_type = Some(return_type);
+    }
+
+    fn update(&mut self, old: &[Field], new: &[Field]) -> Result<Field, PipelineError> {
+        self.delete(old)?;
+        self.insert(new)
+    }
+
+    fn delete(&mut self, old: &[Field]) -> Result<Field, PipelineError> {
+        if old[0] != Field::Null {
+            update_count_map(&mut self.current_state, &old[0], false);
+        }
+        get_count(self.current_state.len() as u64, self.return_type.as_ref())
+    }
+
+    fn insert(&mut self, new: &[Field]) -> Result<Field, PipelineError> {
+        if new[0] != Field::Null {
+            update_count_map(&mut self.current_state, &new[0], true);
+        }
+        get_count(self.current_state.len() as u64, self.return_type.as_ref())
+    }
+}
@@ -111,6 +111,7 @@ impl ProcessorFactory for AggregationProcessorFactory {
         input_schemas: HashMap<PortHandle, Schema>,
         _output_schemas: HashMap<PortHandle, Schema>,
         _event_hub: EventHub,
+        checkpoint_data: Option<Vec<u8>>,
     ) -> Result<Box<dyn Processor>, BoxedError> {
         let input_schema = input_schemas
             .get(&DEFAULT_PORT_HANDLE)
@@ -130,9 +131,11 @@ impl ProcessorFactory for AggregationProcessorFactory {
                 planner.aggregation_output,
                 planner.projection_output,
                 planner.having,
+                planner.grouping_sets,
                 input_schema.clone(),
                 planner.post_aggregation_schema,
                 self.enable_probabilistic_optimizations,
+                checkpoint_data,
             )?)
         };
         Ok(processor)
@@ -53,7 +53,7 @@ impl Aggregator for MaxAppendOnlyAggregator {
             if val == &Field::Null {
                 continue;
             }
-            match self.return_type {
+            match &self.return_type {
                 Some(typ) => match typ {
                     FieldType::UInt => {
                         let new_val = calculate_err_field!(val.to_uint(), MaxAppendOnly, val);
@@ -120,7 +120,7 @@ impl Aggregator for MaxAppendOnlyAggregator {
                             self.update_state(Field::Float(OrderedFloat(new_val)));
                         }
                     }
-                    FieldType::Decimal => {
+                    FieldType::Decimal(_) => {
                         let new_val = calculate_err_field!(val.to_decimal(), MaxAppendOnly, val);
                         let max_val = match cur_max {
                             Field::Null => Decimal::MIN,
@@ -153,6 +153,17 @@ impl Aggregator for MaxAppendOnlyAggregator {
                             self.update_state(Field::Date(new_val));
                         }
                     }
+                    FieldType::Time => {
+                        let new_val = calculate_err_field!(val.to_time(), MaxAppendOnly, val);
+                        let max_val = match cur_max {
+                            Field::Null => new_val,
+                            _ => calculate_err_field!(cur_max.to_time(), MaxAppendOnly, val),
+                        };
+
+                        if cur_max == Field::Null || new_val > max_val {
+                            self.update_state(Field::Time(new_val));
+                        }
+                    }
                     FieldType::Duration => {
                         let new_val = calculate_err_field!(val.to_duration(), MaxAppendOnly, val);
                         let max_val = match cur_max {
@@ -171,7 +182,11 @@ impl Aggregator for MaxAppendOnlyAggregator {
                     | FieldType::Text
                     | FieldType::Binary
                     | FieldType::Json
-                    | FieldType::Point => {
+                    | FieldType::Point
+                    | FieldType::List(_)
+                    | FieldType::Struct(_)
+                    | FieldType::Uuid
+                    | FieldType::Enum(_) => {
                         return Err(PipelineError::InvalidReturnType(format!(
                             "Not supported return type {typ} for {MaxAppendOnly}"
                         )));
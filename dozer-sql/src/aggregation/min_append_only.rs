@@ -53,7 +53,7 @@ impl Aggregator for MinAppendOnlyAggregator {
             if val == &Field::Null {
                 continue;
             }
-            match self.return_type {
+            match &self.return_type {
                 Some(typ) => match typ {
                     FieldType::UInt => {
                         let new_val = calculate_err_field!(val.to_uint(), MinAppendOnly, val);
@@ -120,7 +120,7 @@ impl Aggregator for MinAppendOnlyAggregator {
                             self.update_state(Field::Float(OrderedFloat(new_val)));
                         }
                     }
-                    FieldType::Decimal => {
+                    FieldType::Decimal(_) => {
                         let new_val = calculate_err_field!(val.to_decimal(), MinAppendOnly, val);
                         let min_val = match cur_min {
                             Field::Null => Decimal::MAX,
@@ -153,6 +153,17 @@ impl Aggregator for MinAppendOnlyAggregator {
                             self.update_state(Field::Date(new_val));
                         }
                     }
+                    FieldType::Time => {
+                        let new_val = calculate_err_field!(val.to_time(), MinAppendOnly, val);
+                        let min_val = match cur_min {
+                            Field::Null => new_val,
+                            _ => calculate_err_field!(cur_min.to_time(), MinAppendOnly, val),
+                        };
+
+                        if cur_min == Field::Null || new_val < min_val {
+                            self.update_state(Field::Time(new_val));
+                        }
+                    }
                     FieldType::Duration => {
                         let new_val = calculate_err_field!(val.to_duration(), MinAppendOnly, val);
                         let min_val = match cur_min {
@@ -171,7 +182,11 @@ impl Aggregator for MinAppendOnlyAggregator {
                     | FieldType::Text
                     | FieldType::Binary
                     | FieldType::Json
-                    | FieldType::Point => {
+                    | FieldType::Point
+                    | FieldType::List(_)
+                    | FieldType::Struct(_)
+                    | FieldType::Uuid
+                    | FieldType::Enum(_) => {
                         return Err(PipelineError::InvalidReturnType(format!(
                             "Not supported return type {typ} for {MinAppendOnly}"
                         )));
@@ -0,0 +1,121 @@
+use crate::aggregation::aggregator::{update_count_map, Aggregator};
+use crate::calculate_err_field;
+use crate::errors::PipelineError;
+use dozer_sql_expression::aggregate::AggregateFunctionType::{PercentileCont, PercentileDisc};
+use dozer_types::ordered_float::OrderedFloat;
+use dozer_types::types::{Field, FieldType};
+use std::collections::BTreeMap;
+
+/// `PERCENTILE_CONT`, `PERCENTILE_DISC` and `MEDIAN`, which is the continuous 0.5 percentile.
+#[derive(Debug, bincode::Encode, bincode::Decode)]
+pub struct PercentileAggregator {
+    current_state: BTreeMap<Field, u64>,
+    count: u64,
+    /// The fraction of the last value, or 0.5 when there's no fraction argument.
+    fraction: f64,
+    /// Whether to interpolate between the values around the percentile.
+    continuous: bool,
+    return_type: Option<FieldType>,
+}
+
+impl PercentileAggregator {
+    pub fn new(continuous: bool) -> Self {
+        Self {
+            current_state: BTreeMap::new(),
+            count: 0,
+            fraction: 0.5,
+            continuous,
+            return_type: None,
+        }
+    }
+
+    fn count_value(&mut self, fields: &[Field], incr: bool) -> Result<(), PipelineError> {
+        if let Some(fraction) = fields.get(1) {
+            self.fraction = match fraction.to_float() {
+                Some(value) if fraction != &Field::Null && (0.0..=1.0).contains(&value) => value,
+                _ => {
+                    let function = if self.continuous {
+                        PercentileCont
+                    } else {
+                        PercentileDisc
+                    };
+                    return Err(PipelineError::InvalidFunctionArgument(
+                        function.to_string(),
+                        fraction.clone(),
+                        1,
+                    ));
+                }
+            };
+        }
+        if fields[0] == Field::Null {
+            return Ok(());
+        }
+        update_count_map(&mut self.current_state, &fields[0], incr);
+        if incr {
+            self.count += 1;
+        } else {
+            self.count -= 1;
+        }
+        Ok(())
+    }
+
+    /// The value at `index` in the sorted values.
+    fn nth(&self, index: u64) -> Option<&Field> {
+        let mut seen = 0;
+        for (value, count) in &self.current_state {
+            seen += count;
+            if index < seen {
+                return Some(value);
+            }
+        }
+        None
+    }
+
+    fn get_percentile(&self) -> Result<Field, PipelineError> {
+        if self.count == 0 {
+            return Ok(Field::Null);
+        }
+        if !self.continuous {
+            // The first value whose cumulative distribution reaches the fraction.
+            let index = ((self.fraction * self.count as f64).ceil() as u64).max(1) - 1;
+            return Ok(self.nth(index).cloned().unwrap_or(Field::Null));
+        }
+
+        let position = self.fraction * (self.count - 1) as f64;
+        let (lower, upper) = (position.floor(), position.ceil());
+        let to_float = |index: f64| -> Result<f64, PipelineError> {
+            let value = self.nth(index as u64).unwrap_or(&Field::Null);
+            Ok(calculate_err_field!(
+                value.to_float(),
+                PercentileCont,
+                value
+            ))
+        };
+        let lower_value = to_float(lower)?;
+        let upper_value = to_float(upper)?;
+        Ok(Field::Float(OrderedFloat(
+            lower_value + (position - lower) * (upper_value - lower_value),
+        )))
+    }
+}
+
+impl Aggregator for PercentileAggregator {
+    fn init(&mut self, return_type: FieldType) {
+        self.return_type = Some(return_type);
+    }
+
+    fn update(&mut self, old: &[Field], new: &[Field]) -> Result<Field, PipelineError> {
+        self.delete(old)?;
+        self.insert(new)
+    }
+
+    fn delete(&mut self, old: &[Field]) -> Result<Field, PipelineError> {
+        self.count_value(old, false)?;
+        self.get_percentile()
+    }
+
+    fn insert(&mut self, new: &[Field]) -> Result<Field, PipelineError> {
+        self.count_value(new, true)?;
+        self.get_percentile()
+    }
+}
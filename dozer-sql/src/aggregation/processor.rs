@@ -4,13 +4,15 @@ use crate::aggregation::aggregator::Aggregator;
 use crate::errors::PipelineError;
 use crate::utils::record_hashtable_key::{get_record_hash, RecordKey};
 use dozer_core::channels::ProcessorChannelForwarder;
+use dozer_core::checkpoint::{deserialize_bincode, serialize_bincode_into, StateWriter};
 use dozer_core::node::Processor;
 use dozer_core::DEFAULT_PORT_HANDLE;
 use dozer_sql_expression::execution::Expression;
 use dozer_types::bincode;
+use dozer_types::chrono;
 use dozer_types::errors::internal::BoxedError;
-use dozer_types::types::{Field, FieldType, Operation, Record, Schema, TableOperation};
-use std::collections::HashMap;
+use dozer_types::types::{Field, FieldType, Operation, Record, Schema, TableOperation, Timestamp};
+use std::collections::{BTreeMap, HashMap};
 
 use crate::aggregation::aggregator::{
     get_aggregator_from_aggregator_type, get_aggregator_type_from_aggregation_expression,
@@ -25,6 +27,78 @@ struct AggregationState {
     count: usize,
     states: Vec<AggregatorEnum>,
     values: Option<Vec<Field>>,
+    /// When the lifetimes of all the records of the group end, if they all have one.
+    expires_at: Option<EvictionInstant>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, bincode::Encode, bincode::Decode)]
+struct EvictionInstant(#[bincode(with_serde)] Timestamp);
+
+/// The groups to evict once their records expire, so that windows stop taking memory once they close.
+#[derive(Debug, Default, bincode::Encode, bincode::Decode)]
+struct Expirations {
+    /// The latest lifetime reference seen.
+    now: Option<EvictionInstant>,
+    keys: BTreeMap<EvictionInstant, Vec<RecordKey>>,
+}
+
+impl Expirations {
+    /// Moves `now` forward, returning the groups whose records may all have expired.
+    fn advance(&mut self, now: Timestamp) -> Vec<(EvictionInstant, RecordKey)> {
+        let now = EvictionInstant(now);
+        if self.now.map_or(false, |current| current >= now) {
+            return vec![];
+        }
+        self.now = Some(now);
+        let mut expired = vec![];
+        while let Some(entry) = self.keys.first_entry() {
+            if *entry.key() > now {
+                break;
+            }
+            let (instant, keys) = entry.remove_entry();
+            expired.extend(keys.into_iter().map(|key| (instant, key)));
+        }
+        expired
+    }
+
+    fn is_expired(&self, record: &Record) -> bool {
+        match (get_eviction_instant(record), self.now) {
+            (Some(instant), Some(now)) => instant <= now,
+            _ => false,
+        }
+    }
+
+    /// Keeps a group until the lifetime of `record` ends, unless it has a record without a lifetime.
+    fn track(
+        &mut self,
+        state: &mut AggregationState,
+        key: &RecordKey,
+        record: &Record,
+        is_new_group: bool,
+    ) {
+        let instant = get_eviction_instant(record);
+        let expires_at = match (is_new_group, instant, state.expires_at) {
+            (true, instant, _) => instant,
+            (false, Some(instant), Some(expires_at)) => Some(instant.max(expires_at)),
+            (false, _, _) => None,
+        };
+        if expires_at != state.expires_at || is_new_group {
+            if let Some(expires_at) = expires_at {
+                self.keys.entry(expires_at).or_default().push(key.clone());
+            }
+        }
+        state.expires_at = expires_at;
+    }
+}
+
+fn get_eviction_instant(record: &Record) -> Option<EvictionInstant> {
+    let lifetime = record.get_lifetime()?;
+    lifetime
+        .reference
+        .checked_add_signed(chrono::Duration::nanoseconds(
+            lifetime.duration.as_nanos() as i64
+        ))
+        .map(EvictionInstant)
 }
 
 impl AggregationState {
@@ -32,7 +106,7 @@ impl AggregationState {
         let mut states: Vec<AggregatorEnum> = Vec::new();
         for (idx, typ) in types.iter().enumerate() {
             let mut aggr = get_aggregator_from_aggregator_type(*typ);
-            aggr.init(ret_types[idx]);
+            aggr.init(ret_types[idx].clone());
             states.push(aggr);
         }
 
@@ -40,22 +114,33 @@ impl AggregationState {
             count: 0,
             states,
             values: None,
+            expires_at: None,
         }
     }
 }
 
+/// One of the groupings of `GROUPING SETS`, `ROLLUP` or `CUBE`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GroupingSet {
+    /// The indexes of the dimensions this grouping set groups by.
+    pub dimensions: Vec<usize>,
+    /// The projections, with the dimensions this grouping set doesn't group by as nulls.
+    pub projections: Vec<Expression>,
+    pub having: Option<Expression>,
+}
+
 #[derive(Debug)]
 pub struct AggregationProcessor {
     _id: String,
     dimensions: Vec<Expression>,
+    grouping_sets: Vec<GroupingSet>,
     measures: Vec<Vec<Expression>>,
     measures_types: Vec<AggregatorType>,
     measures_return_types: Vec<FieldType>,
-    projections: Vec<Expression>,
-    having: Option<Expression>,
     input_schema: Schema,
     aggregation_schema: Schema,
     states: HashMap<RecordKey, AggregationState>,
+    expirations: Expirations,
     default_segment_key: RecordKey,
     having_eval_schema: Schema,
     accurate_keys: bool,
@@ -74,17 +159,25 @@ impl AggregationProcessor {
         measures: Vec<Expression>,
         projections: Vec<Expression>,
         having: Option<Expression>,
+        grouping_sets: Vec<GroupingSet>,
         input_schema: Schema,
         aggregation_schema: Schema,
         enable_probabilistic_optimizations: bool,
+        checkpoint_data: Option<Vec<u8>>,
     ) -> Result<Self, BoxedError> {
         let mut aggr_types = Vec::new();
         let mut aggr_measures = Vec::new();
         let mut aggr_measures_ret_types = Vec::new();
 
         for measure in measures {
-            let (aggr_measure, aggr_type) =
+            let (aggr_measure, mut aggr_type) =
                 get_aggregator_type_from_aggregation_expression(&measure, &input_schema)?;
+            // Distinct values are only estimated when probabilistic optimizations are enabled.
+            if aggr_type == AggregatorType::ApproxCountDistinct
+                && !enable_probabilistic_optimizations
+            {
+                aggr_type = AggregatorType::CountDistinct;
+            }
             aggr_measures.push(aggr_measure);
             aggr_types.push(aggr_type);
             aggr_measures_ret_types.push(measure.get_type(&input_schema)?.return_type)
@@ -95,15 +188,31 @@ impl AggregationProcessor {
 
         let accurate_keys = !enable_probabilistic_optimizations;
 
+        let (states, expirations) = match checkpoint_data {
+            Some(checkpoint_data) => deserialize_bincode(&checkpoint_data)?,
+            None => Default::default(),
+        };
+
+        // A plain `GROUP BY` is a single grouping set of all the dimensions.
+        let grouping_sets = if grouping_sets.is_empty() {
+            vec![GroupingSet {
+                dimensions: (0..dimensions.len()).collect(),
+                projections,
+                having,
+            }]
+        } else {
+            grouping_sets
+        };
+
         Ok(Self {
             _id: id,
             dimensions,
-            projections,
+            grouping_sets,
             input_schema,
             aggregation_schema,
-            states: Default::default(),
+            states,
+            expirations,
             measures: aggr_measures,
-            having,
             measures_types: aggr_types,
             measures_return_types: aggr_measures_ret_types,
             default_segment_key: {
@@ -180,18 +289,17 @@ impl AggregationProcessor {
         Ok(new_fields)
     }
 
-    fn agg_delete(&mut self, old: &mut Record) -> Result<Vec<Operation>, PipelineError> {
+    fn agg_delete(
+        &mut self,
+        old: &mut Record,
+        grouping_set: usize,
+    ) -> Result<Vec<Operation>, PipelineError> {
         let mut out_rec_delete: Vec<Field> = Vec::with_capacity(self.measures.len());
         let mut out_rec_insert: Vec<Field> = Vec::with_capacity(self.measures.len());
 
-        let key = if !self.dimensions.is_empty() {
-            Some(self.get_key(old)?)
-        } else {
-            None
-        };
-        let key = key.as_ref().unwrap_or(&self.default_segment_key);
+        let key = self.get_key(old, grouping_set)?;
 
-        let curr_state_opt = self.states.get_mut(key);
+        let curr_state_opt = self.states.get_mut(&key);
         assert!(
             curr_state_opt.is_some(),
             "Unable to find aggregator state during DELETE operation"
@@ -210,7 +318,7 @@ impl AggregationProcessor {
         )?;
 
         let (out_rec_delete_having_satisfied, out_rec_insert_having_satisfied) =
-            match &mut self.having {
+            match &mut self.grouping_sets[grouping_set].having {
                 None => (true, true),
                 Some(having) => (
                     Self::having_is_satisfied(
@@ -229,13 +337,13 @@ impl AggregationProcessor {
             };
 
         let res = if curr_state.count == 1 {
-            self.states.remove(key);
+            self.states.remove(&key);
             if out_rec_delete_having_satisfied {
                 vec![Operation::Delete {
                     old: Self::build_projection(
                         old,
                         out_rec_delete,
-                        &mut self.projections,
+                        &mut self.grouping_sets[grouping_set].projections,
                         &self.aggregation_schema,
                     )?,
                 }]
@@ -252,7 +360,7 @@ impl AggregationProcessor {
                 out_rec_delete,
                 out_rec_insert,
                 old,
-                &mut self.projections,
+                &mut self.grouping_sets[grouping_set].projections,
                 &self.aggregation_schema,
             )?
         };
@@ -260,20 +368,25 @@ impl AggregationProcessor {
         Ok(res)
     }
 
-    fn agg_insert(&mut self, new: &mut Record) -> Result<Vec<Operation>, PipelineError> {
+    fn agg_insert(
+        &mut self,
+        new: &mut Record,
+        grouping_set: usize,
+    ) -> Result<Vec<Operation>, PipelineError> {
         let mut out_rec_delete: Vec<Field> = Vec::with_capacity(self.measures.len());
         let mut out_rec_insert: Vec<Field> = Vec::with_capacity(self.measures.len());
 
-        let key = if !self.dimensions.is_empty() {
-            self.get_key(new)?
-        } else {
-            self.default_segment_key.clone()
-        };
+        let key = self.get_key(new, grouping_set)?;
 
-        let curr_state = self.states.entry(key).or_insert(AggregationState::new(
-            &self.measures_types,
-            &self.measures_return_types,
-        ));
+        let curr_state = self
+            .states
+            .entry(key.clone())
+            .or_insert(AggregationState::new(
+                &self.measures_types,
+                &self.measures_return_types,
+            ));
+        let is_new_group = curr_state.count == 0;
+        self.expirations.track(curr_state, &key, new, is_new_group);
 
         let new_values = Self::calc_and_fill_measures(
             curr_state,
@@ -287,7 +400,7 @@ impl AggregationProcessor {
         )?;
 
         let (out_rec_delete_having_satisfied, out_rec_insert_having_satisfied) =
-            match &mut self.having {
+            match &mut self.grouping_sets[grouping_set].having {
                 None => (true, true),
                 Some(having) => (
                     Self::having_is_satisfied(
@@ -311,7 +424,7 @@ impl AggregationProcessor {
                     new: Self::build_projection(
                         new,
                         out_rec_insert,
-                        &mut self.projections,
+                        &mut self.grouping_sets[grouping_set].projections,
                         &self.aggregation_schema,
                     )?,
                 }]
@@ -325,7 +438,7 @@ impl AggregationProcessor {
                 out_rec_delete,
                 out_rec_insert,
                 new,
-                &mut self.projections,
+                &mut self.grouping_sets[grouping_set].projections,
                 &self.aggregation_schema,
             )?
         };
@@ -416,6 +529,7 @@ impl AggregationProcessor {
         old: &mut Record,
         new: &mut Record,
         key: RecordKey,
+        grouping_set: usize,
     ) -> Result<Vec<Operation>, PipelineError> {
         let mut out_rec_delete: Vec<Field> = Vec::with_capacity(self.measures.len());
         let mut out_rec_insert: Vec<Field> = Vec::with_capacity(self.measures.len());
@@ -426,6 +540,7 @@ impl AggregationProcessor {
             "Unable to find aggregator state during UPDATE operation"
         );
         let curr_state = curr_state_opt.unwrap();
+        self.expirations.track(curr_state, &key, new, false);
 
         let new_values = Self::calc_and_fill_measures(
             curr_state,
@@ -439,7 +554,7 @@ impl AggregationProcessor {
         )?;
 
         let (out_rec_delete_having_satisfied, out_rec_insert_having_satisfied) =
-            match &mut self.having {
+            match &mut self.grouping_sets[grouping_set].having {
                 None => (true, true),
                 Some(having) => (
                     Self::having_is_satisfied(
@@ -465,7 +580,7 @@ impl AggregationProcessor {
                 new: Self::build_projection(
                     new,
                     out_rec_insert,
-                    &mut self.projections,
+                    &mut self.grouping_sets[grouping_set].projections,
                     &self.aggregation_schema,
                 )?,
             }],
@@ -473,7 +588,7 @@ impl AggregationProcessor {
                 old: Self::build_projection(
                     old,
                     out_rec_delete,
-                    &mut self.projections,
+                    &mut self.grouping_sets[grouping_set].projections,
                     &self.aggregation_schema,
                 )?,
             }],
@@ -481,13 +596,13 @@ impl AggregationProcessor {
                 new: Self::build_projection(
                     new,
                     out_rec_insert,
-                    &mut self.projections,
+                    &mut self.grouping_sets[grouping_set].projections,
                     &self.aggregation_schema,
                 )?,
                 old: Self::build_projection(
                     old,
                     out_rec_delete,
-                    &mut self.projections,
+                    &mut self.grouping_sets[grouping_set].projections,
                     &self.aggregation_schema,
                 )?,
             }],
@@ -518,46 +633,106 @@ impl AggregationProcessor {
         Ok(output_record)
     }
 
-    pub fn aggregate(&mut self, mut op: Operation) -> Result<Vec<Operation>, PipelineError> {
+    pub fn aggregate(&mut self, op: Operation) -> Result<Vec<Operation>, PipelineError> {
+        let Some(mut op) = self.expire(op) else {
+            return Ok(vec![]);
+        };
+        if let Operation::BatchInsert { new } = op {
+            let mut result = vec![];
+            for record in new {
+                result.extend(self.aggregate(Operation::Insert { new: record })?);
+            }
+            return Ok(result);
+        }
+
+        // Each record is aggregated once for every grouping set.
+        let mut result = vec![];
+        for grouping_set in 0..self.grouping_sets.len() {
+            result.extend(self.aggregate_grouping_set(&mut op, grouping_set)?);
+        }
+        Ok(result)
+    }
+
+    fn aggregate_grouping_set(
+        &mut self,
+        op: &mut Operation,
+        grouping_set: usize,
+    ) -> Result<Vec<Operation>, PipelineError> {
         match op {
-            Operation::Insert { ref mut new } => Ok(self.agg_insert(new)?),
-            Operation::Delete { ref mut old } => Ok(self.agg_delete(old)?),
-            Operation::Update {
-                ref mut old,
-                ref mut new,
-            } => {
-                let (old_record_hash, new_record_hash) = if self.dimensions.is_empty() {
-                    (
-                        self.default_segment_key.clone(),
-                        self.default_segment_key.clone(),
-                    )
-                } else {
-                    (self.get_key(old)?, self.get_key(new)?)
-                };
+            Operation::Insert { new } => Ok(self.agg_insert(new, grouping_set)?),
+            Operation::Delete { old } => Ok(self.agg_delete(old, grouping_set)?),
+            Operation::Update { old, new } => {
+                let old_record_hash = self.get_key(old, grouping_set)?;
+                let new_record_hash = self.get_key(new, grouping_set)?;
 
                 if old_record_hash == new_record_hash {
-                    Ok(self.agg_update(old, new, old_record_hash)?)
+                    Ok(self.agg_update(old, new, old_record_hash, grouping_set)?)
                 } else {
                     let mut r = Vec::with_capacity(2);
-                    r.extend(self.agg_delete(old)?);
-                    r.extend(self.agg_insert(new)?);
+                    r.extend(self.agg_delete(old, grouping_set)?);
+                    r.extend(self.agg_insert(new, grouping_set)?);
                     Ok(r)
                 }
             }
-            Operation::BatchInsert { new } => {
-                let mut result = vec![];
-                for record in new {
-                    result.extend(self.aggregate(Operation::Insert { new: record })?);
+            Operation::BatchInsert { .. } => {
+                unreachable!("batches are aggregated record by record")
+            }
+        }
+    }
+
+    /// Evicts the groups whose records expired before the records of `op`,
+    /// and leaves out the expired records of `op`, as they don't change final results.
+    fn expire(&mut self, op: Operation) -> Option<Operation> {
+        let now = match &op {
+            Operation::Insert { new } => new.get_lifetime(),
+            Operation::Delete { old } => old.get_lifetime(),
+            Operation::Update { old, new } => new.get_lifetime().or(old.get_lifetime()),
+            Operation::BatchInsert { .. } => None,
+        };
+        if let Some(now) = now {
+            for (instant, key) in self.expirations.advance(now.reference) {
+                if self
+                    .states
+                    .get(&key)
+                    .map_or(false, |state| state.expires_at == Some(instant))
+                {
+                    self.states.remove(&key);
                 }
-                Ok(result)
             }
         }
+
+        match op {
+            Operation::Insert { new } if self.expirations.is_expired(&new) => None,
+            Operation::Delete { old } if self.expirations.is_expired(&old) => None,
+            Operation::Update { old, new } => match (
+                self.expirations.is_expired(&old),
+                self.expirations.is_expired(&new),
+            ) {
+                (false, false) => Some(Operation::Update { old, new }),
+                (true, false) => Some(Operation::Insert { new }),
+                (false, true) => Some(Operation::Delete { old }),
+                (true, true) => None,
+            },
+            op => Some(op),
+        }
     }
 
-    fn get_key(&mut self, record: &Record) -> Result<RecordKey, PipelineError> {
-        let mut key = Vec::<Field>::with_capacity(self.dimensions.len());
-        for dimension in self.dimensions.iter_mut() {
-            key.push(dimension.evaluate(record, &self.input_schema)?);
+    /// The key of the group of `record` in a grouping set, which starts with the set's index if there are several.
+    fn get_key(
+        &mut self,
+        record: &Record,
+        grouping_set: usize,
+    ) -> Result<RecordKey, PipelineError> {
+        let dimensions = &self.grouping_sets[grouping_set].dimensions;
+        if self.grouping_sets.len() == 1 && dimensions.is_empty() {
+            return Ok(self.default_segment_key.clone());
+        }
+        let mut key = Vec::<Field>::with_capacity(dimensions.len() + 1);
+        if self.grouping_sets.len() > 1 {
+            key.push(Field::UInt(grouping_set as u64));
+        }
+        for index in dimensions {
+            key.push(self.dimensions[*index].evaluate(record, &self.input_schema)?);
         }
         if self.accurate_keys {
             Ok(RecordKey::Accurate(key))
@@ -572,6 +747,13 @@ impl Processor for AggregationProcessor {
         Ok(())
     }
 
+    fn serialize_state(&self, writer: &mut StateWriter) -> Result<(), BoxedError> {
+        Ok(serialize_bincode_into(
+            (&self.states, &self.expirations),
+            writer,
+        )?)
+    }
+
     fn process(
         &mut self,
         op: TableOperation,
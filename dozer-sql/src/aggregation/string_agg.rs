@@ -0,0 +1,66 @@
+use crate::aggregation::aggregator::{update_count_map, Aggregator};
+use crate::errors::PipelineError;
+use dozer_types::types::{Field, FieldType};
+use std::collections::BTreeMap;
+
+/// Concatenates the values in order, so the result doesn't depend on the order they arrived in.
+#[derive(Debug, bincode::Encode, bincode::Decode)]
+pub struct StringAggAggregator {
+    current_state: BTreeMap<Field, u64>,
+    separator: String,
+    return_type: Option<FieldType>,
+}
+
+impl StringAggAggregator {
+    pub fn new() -> Self {
+        Self {
+            current_state: BTreeMap::new(),
+            separator: ",".to_string(),
+            return_type: None,
+        }
+    }
+
+    fn count_value(&mut self, fields: &[Field], incr: bool) {
+        if let Some(separator) = fields.get(1) {
+            self.separator = separator.to_string();
+        }
+        if fields[0] != Field::Null {
+            update_count_map(&mut self.current_state, &fields[0], incr);
+        }
+    }
+
+    fn get_string(&self) -> Field {
+        if self.current_state.is_empty() {
+            return Field::Null;
+        }
+        let mut values = vec![];
+        for (value, count) in &self.current_state {
+            let value = value.to_string();
+            for _ in 0..*count {
+                values.push(value.clone());
+            }
+        }
+        Field::String(values.join(&self.separator))
+    }
+}
+
+impl Aggregator for StringAggAggregator {
+    fn init(&mut self, return_type: FieldType) {
+        self.return_type = Some(return_type);
+    }
+
+    fn update(&mut self, old: &[Field], new: &[Field]) -> Result<Field, PipelineError> {
+        self.delete(old)?;
+        self.insert(new)
+    }
+
+    fn delete(&mut self, old: &[Field]) -> Result<Field, PipelineError> {
+        self.count_value(old, false);
+        Ok(self.get_string())
+    }
+
+    fn insert(&mut self, new: &[Field]) -> Result<Field, PipelineError> {
+        self.count_value(new, true);
+        Ok(self.get_string())
+    }
+}
@@ -56,18 +56,28 @@ impl Aggregator for SumAggregator {
     }
 
     fn delete(&mut self, old: &[Field]) -> Result<Field, PipelineError> {
-        get_sum(old, &mut self.current_state, self.return_type, true)
+        get_sum(
+            old,
+            &mut self.current_state,
+            self.return_type.as_ref(),
+            true,
+        )
     }
 
     fn insert(&mut self, new: &[Field]) -> Result<Field, PipelineError> {
-        get_sum(new, &mut self.current_state, self.return_type, false)
+        get_sum(
+            new,
+            &mut self.current_state,
+            self.return_type.as_ref(),
+            false,
+        )
     }
 }
 
 pub fn get_sum(
     fields: &[Field],
     current_state: &mut SumState,
-    return_type: Option<FieldType>,
+    return_type: Option<&FieldType>,
     decr: bool,
 ) -> Result<Field, PipelineError> {
     match return_type {
@@ -156,7 +166,7 @@ pub fn get_sum(
                 }
                 Ok(Field::Float(OrderedFloat::from(current_state.float_state)))
             }
-            FieldType::Decimal => {
+            FieldType::Decimal(_) => {
                 if decr {
                     for field in fields {
                         let val = calculate_err_field!(field.to_decimal(), Sum, field);
@@ -194,7 +204,12 @@ pub fn get_sum(
             | FieldType::Timestamp
             | FieldType::Binary
             | FieldType::Json
-            | FieldType::Point => Err(PipelineError::InvalidReturnType(format!(
+            | FieldType::Point
+            | FieldType::List(_)
+            | FieldType::Struct(_)
+            | FieldType::Uuid
+            | FieldType::Time
+            | FieldType::Enum(_) => Err(PipelineError::InvalidReturnType(format!(
                 "Not supported return type {typ} for {Sum}"
             ))),
         },
@@ -411,7 +411,7 @@ fn test_avg_aggregation_uint() {
 
 #[test]
 fn test_avg_aggregation_decimal() {
-    let schema = init_input_schema(Decimal, "AVG");
+    let schema = init_input_schema(Decimal(None), "AVG");
     let mut processor = init_processor(
         "SELECT Country, AVG(Salary) \
         FROM Users \
@@ -858,7 +858,7 @@ fn test_avg_aggregation_float_null() {
 
 #[test]
 fn test_avg_aggregation_decimal_null() {
-    let schema = init_input_schema(Decimal, "AVG");
+    let schema = init_input_schema(Decimal(None), "AVG");
     let mut processor = init_processor(
         "SELECT Country, AVG(Salary) \
         FROM Users \
@@ -266,7 +266,7 @@ fn test_count_aggregation_int() {
 
 #[test]
 fn test_count_aggregation_decimal() {
-    let schema = init_input_schema(Decimal, "COUNT");
+    let schema = init_input_schema(Decimal(None), "COUNT");
     let mut processor = init_processor(
         "SELECT Country, COUNT(Salary) \
             FROM Users \
@@ -648,7 +648,7 @@ fn test_count_aggregation_float_null() {
 
 #[test]
 fn test_count_aggregation_decimal_null() {
-    let schema = init_input_schema(Decimal, "COUNT");
+    let schema = init_input_schema(Decimal(None), "COUNT");
     let mut processor = init_processor(
         "SELECT Country, COUNT(Salary) \
             FROM Users \
@@ -0,0 +1,110 @@
+use crate::aggregation::aggregator::Aggregator;
+use crate::aggregation::approx_count_distinct::ApproxCountDistinctAggregator;
+use crate::aggregation::tests::aggregation_tests_utils::{
+    delete_exp, delete_field, init_input_schema, init_processor, insert_exp, insert_field,
+    update_exp, update_field, FIELD_100_INT, FIELD_1_INT, FIELD_200_INT, FIELD_2_INT, FIELD_50_INT,
+    FIELD_NULL, ITALY,
+};
+use crate::output;
+use dozer_core::DEFAULT_PORT_HANDLE;
+use dozer_types::types::Field;
+use dozer_types::types::FieldType::Int;
+use std::collections::HashMap;
+
+#[test]
+fn test_count_distinct() {
+    let schema = init_input_schema(Int, "COUNT");
+    let mut processor = init_processor(
+        "SELECT Country, COUNT(DISTINCT Salary) FROM Users GROUP BY Country",
+        HashMap::from([(DEFAULT_PORT_HANDLE, schema)]),
+    )
+    .unwrap();
+
+    let out = output!(processor, insert_field(ITALY, FIELD_100_INT));
+    assert_eq!(out, vec![insert_exp(ITALY, FIELD_1_INT)]);
+
+    // A duplicate isn't counted again.
+    let out = output!(processor, insert_field(ITALY, FIELD_100_INT));
+    assert_eq!(
+        out,
+        vec![update_exp(ITALY, ITALY, FIELD_1_INT, FIELD_1_INT)]
+    );
+
+    // Nulls aren't counted.
+    let out = output!(processor, insert_field(ITALY, FIELD_NULL));
+    assert_eq!(
+        out,
+        vec![update_exp(ITALY, ITALY, FIELD_1_INT, FIELD_1_INT)]
+    );
+
+    let out = output!(processor, insert_field(ITALY, FIELD_200_INT));
+    assert_eq!(
+        out,
+        vec![update_exp(ITALY, ITALY, FIELD_1_INT, FIELD_2_INT)]
+    );
+
+    // The value is still there until its last occurrence is deleted.
+    let out = output!(processor, delete_field(ITALY, FIELD_100_INT));
+    assert_eq!(
+        out,
+        vec![update_exp(ITALY, ITALY, FIELD_2_INT, FIELD_2_INT)]
+    );
+
+    let out = output!(
+        processor,
+        update_field(ITALY, ITALY, FIELD_100_INT, FIELD_200_INT)
+    );
+    assert_eq!(
+        out,
+        vec![update_exp(ITALY, ITALY, FIELD_2_INT, FIELD_1_INT)]
+    );
+
+    output!(processor, delete_field(ITALY, FIELD_NULL));
+    output!(processor, delete_field(ITALY, FIELD_200_INT));
+    let out = output!(processor, delete_field(ITALY, FIELD_200_INT));
+    assert_eq!(out, vec![delete_exp(ITALY, FIELD_1_INT)]);
+}
+
+#[test]
+fn test_approx_count_distinct_is_exact_without_probabilistic_optimizations() {
+    let schema = init_input_schema(Int, "APPROX_COUNT_DISTINCT");
+    let mut processor = init_processor(
+        "SELECT Country, APPROX_COUNT_DISTINCT(Salary) FROM Users GROUP BY Country",
+        HashMap::from([(DEFAULT_PORT_HANDLE, schema)]),
+    )
+    .unwrap();
+
+    output!(processor, insert_field(ITALY, FIELD_100_INT));
+    output!(processor, insert_field(ITALY, FIELD_50_INT));
+    let out = output!(processor, insert_field(ITALY, FIELD_50_INT));
+    assert_eq!(
+        out,
+        vec![update_exp(ITALY, ITALY, FIELD_2_INT, FIELD_2_INT)]
+    );
+}
+
+#[test]
+fn test_approx_count_distinct_forgets_deleted_values() {
+    let mut aggregator = ApproxCountDistinctAggregator::new();
+    aggregator.init(Int);
+
+    let mut estimate = Field::Null;
+    for value in 0..10_000 {
+        estimate = aggregator.insert(&[Field::Int(value)]).unwrap();
+    }
+    let estimate = estimate.as_int().unwrap();
+    assert!((9_500..=10_500).contains(&estimate), "{estimate}");
+
+    let mut estimate = Field::Null;
+    for value in 1_000..10_000 {
+        estimate = aggregator.delete(&[Field::Int(value)]).unwrap();
+    }
+    let estimate = estimate.as_int().unwrap();
+    assert!((950..=1_050).contains(&estimate), "{estimate}");
+
+    let mut estimate = Field::Null;
+    for value in 0..1_000 {
+        estimate = aggregator.delete(&[Field::Int(value)]).unwrap();
+    }
+    assert_eq!(estimate, Field::Int(0));
+}
@@ -0,0 +1,84 @@
+use crate::aggregation::tests::aggregation_tests_utils::{
+    delete_field, init_input_schema, init_processor, insert_exp, insert_field, FIELD_100_INT,
+    FIELD_200_INT, FIELD_50_INT, ITALY, SINGAPORE,
+};
+use crate::output;
+use dozer_core::DEFAULT_PORT_HANDLE;
+use dozer_types::types::FieldType::Int;
+use dozer_types::types::{Field, Operation, Record};
+use std::collections::HashMap;
+
+fn row(country: Option<&str>, sum: i64, grouping: i64) -> Record {
+    Record::new(vec![
+        country.map_or(Field::Null, |country| Field::String(country.to_string())),
+        Field::Int(sum),
+        Field::Int(grouping),
+    ])
+}
+
+#[test]
+fn test_rollup() {
+    let schema = init_input_schema(Int, "SUM");
+    let mut processor = init_processor(
+        "SELECT Country, SUM(Salary), GROUPING(Country) FROM Users GROUP BY ROLLUP(Country)",
+        HashMap::from([(DEFAULT_PORT_HANDLE, schema)]),
+    )
+    .unwrap();
+
+    let out = output!(processor, insert_field(ITALY, FIELD_100_INT));
+    assert_eq!(
+        out,
+        vec![
+            Operation::Insert {
+                new: row(Some(ITALY), 100, 0)
+            },
+            Operation::Insert {
+                new: row(None, 100, 1)
+            },
+        ]
+    );
+
+    let out = output!(processor, insert_field(SINGAPORE, FIELD_50_INT));
+    assert_eq!(
+        out,
+        vec![
+            Operation::Insert {
+                new: row(Some(SINGAPORE), 50, 0)
+            },
+            Operation::Update {
+                old: row(None, 100, 1),
+                new: row(None, 150, 1)
+            },
+        ]
+    );
+
+    let out = output!(processor, delete_field(ITALY, FIELD_100_INT));
+    assert_eq!(
+        out,
+        vec![
+            Operation::Delete {
+                old: row(Some(ITALY), 100, 0)
+            },
+            Operation::Update {
+                old: row(None, 150, 1),
+                new: row(None, 50, 1)
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_having_with_alias() {
+    let schema = init_input_schema(Int, "SUM");
+    let mut processor = init_processor(
+        "SELECT Country, SUM(Salary) AS total FROM Users GROUP BY Country HAVING total > 100",
+        HashMap::from([(DEFAULT_PORT_HANDLE, schema)]),
+    )
+    .unwrap();
+
+    let out = output!(processor, insert_field(ITALY, FIELD_100_INT));
+    assert_eq!(out, vec![]);
+
+    let out = output!(processor, insert_field(ITALY, FIELD_100_INT));
+    assert_eq!(out, vec![insert_exp(ITALY, FIELD_200_INT)]);
+}
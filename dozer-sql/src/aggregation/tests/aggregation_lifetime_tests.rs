@@ -0,0 +1,98 @@
+use crate::aggregation::tests::aggregation_tests_utils::{
+    init_input_schema, init_processor, FIELD_100_INT, FIELD_1_INT, ITALY, SINGAPORE,
+};
+use crate::output;
+use dozer_core::DEFAULT_PORT_HANDLE;
+use dozer_types::chrono::{DateTime, Duration};
+use dozer_types::types::FieldType::Int;
+use dozer_types::types::{Field, Lifetime, Operation, Record};
+use std::collections::HashMap;
+
+/// A record with a lifetime of one minute from `minute`.
+fn record(country: &str, minute: i64) -> Record {
+    let mut record = Record::new(vec![
+        Field::Int(0),
+        Field::String(country.to_string()),
+        FIELD_100_INT.clone(),
+        FIELD_100_INT.clone(),
+    ]);
+    record.set_lifetime(Some(Lifetime {
+        reference: DateTime::parse_from_rfc3339("2020-01-01T00:00:00Z").unwrap()
+            + Duration::minutes(minute),
+        duration: std::time::Duration::from_secs(60),
+    }));
+    record
+}
+
+fn values(ops: Vec<Operation>) -> Vec<Operation> {
+    ops.into_iter()
+        .map(|op| match op {
+            Operation::Insert { new } => Operation::Insert {
+                new: Record::new(new.values),
+            },
+            Operation::Delete { old } => Operation::Delete {
+                old: Record::new(old.values),
+            },
+            Operation::Update { old, new } => Operation::Update {
+                old: Record::new(old.values),
+                new: Record::new(new.values),
+            },
+            op => op,
+        })
+        .collect()
+}
+
+#[test]
+fn test_expired_groups_are_evicted() {
+    let schema = init_input_schema(Int, "COUNT");
+    let mut processor = init_processor(
+        "SELECT Country, COUNT(Salary) FROM Users GROUP BY Country",
+        HashMap::from([(DEFAULT_PORT_HANDLE, schema)]),
+    )
+    .unwrap();
+    let count = |country: &str| {
+        Record::new(vec![
+            Field::String(country.to_string()),
+            FIELD_1_INT.clone(),
+        ])
+    };
+
+    let out = output!(
+        processor,
+        Operation::Insert {
+            new: record(ITALY, 0)
+        }
+    );
+    assert_eq!(values(out), vec![Operation::Insert { new: count(ITALY) }]);
+
+    // Evicts Italy, whose only record expired at minute 1.
+    let out = output!(
+        processor,
+        Operation::Insert {
+            new: record(SINGAPORE, 2)
+        }
+    );
+    assert_eq!(
+        values(out),
+        vec![Operation::Insert {
+            new: count(SINGAPORE)
+        }]
+    );
+
+    // Expired records don't change the final result.
+    let out = output!(
+        processor,
+        Operation::Delete {
+            old: record(ITALY, 0)
+        }
+    );
+    assert_eq!(out, vec![]);
+
+    let out = output!(
+        processor,
+        Operation::Insert {
+            new: record(ITALY, 2)
+        }
+    );
+    assert_eq!(values(out), vec![Operation::Insert { new: count(ITALY) }]);
+}
@@ -165,7 +165,7 @@ fn test_max_aggregation_uint() {
 
 #[test]
 fn test_max_aggregation_decimal() {
-    let schema = init_input_schema(Decimal, "MAX_APPEND_ONLY");
+    let schema = init_input_schema(Decimal(None), "MAX_APPEND_ONLY");
     let mut processor = init_processor(
         "SELECT Country, MAX_APPEND_ONLY(Salary) \
         FROM Users \
@@ -459,7 +459,7 @@ fn test_max_aggregation_float_null() {
 
 #[test]
 fn test_max_aggregation_decimal_null() {
-    let schema = init_input_schema(Decimal, "MAX_APPEND_ONLY");
+    let schema = init_input_schema(Decimal(None), "MAX_APPEND_ONLY");
     let mut processor = init_processor(
         "SELECT Country, MAX_APPEND_ONLY(Salary) \
         FROM Users \
@@ -351,7 +351,7 @@ fn test_max_aggregation_uint() {
 
 #[test]
 fn test_max_aggregation_decimal() {
-    let schema = init_input_schema(Decimal, "MAX");
+    let schema = init_input_schema(Decimal(None), "MAX");
     let mut processor = init_processor(
         "SELECT Country, MAX(Salary) \
         FROM Users \
@@ -1059,7 +1059,7 @@ fn test_max_aggregation_float_null() {
 
 #[test]
 fn test_max_aggregation_decimal_null() {
-    let schema = init_input_schema(Decimal, "MAX");
+    let schema = init_input_schema(Decimal(None), "MAX");
     let mut processor = init_processor(
         "SELECT Country, MAX(Salary) \
         FROM Users \
@@ -428,7 +428,7 @@ fn test_max_aggregation_uint() {
 
 #[test]
 fn test_max_aggregation_decimal() {
-    let schema = init_val_input_schema(Decimal, "MAX_VALUE");
+    let schema = init_val_input_schema(Decimal(None), "MAX_VALUE");
     let mut processor = init_processor(
         "SELECT MAX_VALUE(Salary, Country) FROM Users",
         HashMap::from([(DEFAULT_PORT_HANDLE, schema)]),
@@ -1169,7 +1169,7 @@ fn test_max_aggregation_float_null() {
 
 #[test]
 fn test_max_aggregation_decimal_null() {
-    let schema = init_input_schema(Decimal, "MAX_VALUE");
+    let schema = init_input_schema(Decimal(None), "MAX_VALUE");
     let mut processor = init_processor(
         "SELECT MAX_VALUE(Salary, Country) FROM Users",
         HashMap::from([(DEFAULT_PORT_HANDLE, schema)]),
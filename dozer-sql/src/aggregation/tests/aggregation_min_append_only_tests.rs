@@ -165,7 +165,7 @@ fn test_min_aggregation_uint() {
 
 #[test]
 fn test_min_aggregation_decimal() {
-    let schema = init_input_schema(Decimal, "MIN_APPEND_ONLY");
+    let schema = init_input_schema(Decimal(None), "MIN_APPEND_ONLY");
     let mut processor = init_processor(
         "SELECT Country, MIN_APPEND_ONLY(Salary) \
         FROM Users \
@@ -459,7 +459,7 @@ fn test_min_aggregation_float_null() {
 
 #[test]
 fn test_min_aggregation_decimal_null() {
-    let schema = init_input_schema(Decimal, "MIN_APPEND_ONLY");
+    let schema = init_input_schema(Decimal(None), "MIN_APPEND_ONLY");
     let mut processor = init_processor(
         "SELECT Country, MIN_APPEND_ONLY(Salary) \
         FROM Users \
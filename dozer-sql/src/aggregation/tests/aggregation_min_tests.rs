@@ -351,7 +351,7 @@ fn test_min_aggregation_uint() {
 
 #[test]
 fn test_min_aggregation_decimal() {
-    let schema = init_input_schema(Decimal, "MIN");
+    let schema = init_input_schema(Decimal(None), "MIN");
     let mut processor = init_processor(
         "SELECT Country, MIN(Salary) \
         FROM Users \
@@ -1059,7 +1059,7 @@ fn test_min_aggregation_float_null() {
 
 #[test]
 fn test_min_aggregation_decimal_null() {
-    let schema = init_input_schema(Decimal, "MIN");
+    let schema = init_input_schema(Decimal(None), "MIN");
     let mut processor = init_processor(
         "SELECT Country, MIN(Salary) \
         FROM Users \
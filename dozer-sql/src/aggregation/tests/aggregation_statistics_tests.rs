@@ -0,0 +1,135 @@
+use crate::aggregation::tests::aggregation_tests_utils::{
+    delete_field, init_input_schema, init_processor, insert_field, FIELD_100_INT, FIELD_200_INT,
+    FIELD_300_INT, FIELD_50_INT, ITALY, SINGAPORE,
+};
+use crate::output;
+use dozer_core::DEFAULT_PORT_HANDLE;
+use dozer_types::json_types::json;
+use dozer_types::ordered_float::OrderedFloat;
+use dozer_types::types::FieldType::Int;
+use dozer_types::types::{Field, Operation};
+use std::collections::HashMap;
+
+/// The values of the record the last operation outputs.
+fn new_values(ops: Vec<Operation>) -> Vec<Field> {
+    match ops.into_iter().last() {
+        Some(Operation::Insert { new } | Operation::Update { new, .. }) => new.values,
+        op => panic!("unexpected operation {op:?}"),
+    }
+}
+
+fn float(value: f64) -> Field {
+    Field::Float(OrderedFloat(value))
+}
+
+#[test]
+fn test_percentiles() {
+    let schema = init_input_schema(Int, "MEDIAN");
+    let mut processor = init_processor(
+        "SELECT Country, PERCENTILE_CONT(Salary, 0.25), PERCENTILE_DISC(Salary, 0.25), MEDIAN(Salary) \
+        FROM Users GROUP BY Country",
+        HashMap::from([(DEFAULT_PORT_HANDLE, schema)]),
+    )
+    .unwrap();
+
+    for salary in [FIELD_50_INT, FIELD_100_INT, FIELD_200_INT] {
+        output!(processor, insert_field(ITALY, salary));
+    }
+    let out = output!(processor, insert_field(ITALY, FIELD_100_INT));
+    assert_eq!(
+        new_values(out),
+        vec![
+            Field::String(ITALY.to_string()),
+            float(87.5),
+            FIELD_50_INT.clone(),
+            float(100.0),
+        ]
+    );
+
+    let out = output!(processor, delete_field(ITALY, FIELD_50_INT));
+    assert_eq!(
+        new_values(out),
+        vec![
+            Field::String(ITALY.to_string()),
+            float(100.0),
+            FIELD_100_INT.clone(),
+            float(100.0),
+        ]
+    );
+}
+
+#[test]
+fn test_variance() {
+    let schema = init_input_schema(Int, "VAR_SAMP");
+    let mut processor = init_processor(
+        "SELECT Country, VAR_SAMP(Salary), VAR_POP(Salary), STDDEV(Salary) \
+        FROM Users GROUP BY Country",
+        HashMap::from([(DEFAULT_PORT_HANDLE, schema)]),
+    )
+    .unwrap();
+
+    let country = Field::String(ITALY.to_string());
+    let out = output!(processor, insert_field(ITALY, FIELD_100_INT));
+    assert_eq!(
+        new_values(out),
+        vec![country.clone(), Field::Null, float(0.0), Field::Null]
+    );
+
+    let out = output!(processor, insert_field(ITALY, FIELD_200_INT));
+    assert_eq!(
+        new_values(out),
+        vec![
+            country.clone(),
+            float(5000.0),
+            float(2500.0),
+            float(5000.0_f64.sqrt())
+        ]
+    );
+
+    output!(processor, insert_field(ITALY, FIELD_300_INT));
+    let out = output!(processor, delete_field(ITALY, FIELD_100_INT));
+    assert_eq!(
+        new_values(out),
+        vec![
+            country,
+            float(5000.0),
+            float(2500.0),
+            float(5000.0_f64.sqrt())
+        ]
+    );
+}
+
+#[test]
+fn test_collecting_aggregations() {
+    let schema = init_input_schema(Int, "ARRAY_AGG");
+    let mut processor = init_processor(
+        "SELECT STRING_AGG(Country, '|'), ARRAY_AGG(Salary), BOOL_OR(Salary > 100), BOOL_AND(Salary > 100) \
+        FROM Users",
+        HashMap::from([(DEFAULT_PORT_HANDLE, schema)]),
+    )
+    .unwrap();
+
+    output!(processor, insert_field(ITALY, FIELD_200_INT));
+    output!(processor, insert_field(SINGAPORE, FIELD_100_INT));
+    let out = output!(processor, insert_field(ITALY, FIELD_100_INT));
+    assert_eq!(
+        new_values(out),
+        vec![
+            Field::String("Italy|Italy|Singapore".to_string()),
+            Field::Json(json!([100, 100, 200])),
+            Field::Boolean(true),
+            Field::Boolean(false),
+        ]
+    );
+
+    let out = output!(processor, delete_field(ITALY, FIELD_200_INT));
+    assert_eq!(
+        new_values(out),
+        vec![
+            Field::String("Italy|Singapore".to_string()),
+            Field::Json(json!([100, 100])),
+            Field::Boolean(false),
+            Field::Boolean(false),
+        ]
+    );
+}
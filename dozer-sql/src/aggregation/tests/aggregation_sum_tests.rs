@@ -352,7 +352,7 @@ fn test_sum_aggregation_uint() {
 
 #[test]
 fn test_sum_aggregation_decimal() {
-    let schema = init_input_schema(Decimal, "SUM");
+    let schema = init_input_schema(Decimal(None), "SUM");
     let mut processor = init_processor(
         "SELECT Country, SUM(Salary) \
         FROM Users \
@@ -784,7 +784,7 @@ fn test_sum_aggregation_float_null() {
 
 #[test]
 fn test_sum_aggregation_decimal_null() {
-    let schema = init_input_schema(Decimal, "SUM");
+    let schema = init_input_schema(Decimal(None), "SUM");
     let mut processor = init_processor(
         "SELECT Country, SUM(Salary) \
         FROM Users \
@@ -89,9 +89,11 @@ fn test_planner_with_aggregator() {
         projection_planner.aggregation_output,
         projection_planner.projection_output,
         projection_planner.having,
+        projection_planner.grouping_sets,
         schema,
         projection_planner.post_aggregation_schema,
         false,
+        None,
     )
     .unwrap();
 
@@ -41,9 +41,11 @@ pub(crate) fn init_processor(
         projection_planner.aggregation_output,
         projection_planner.projection_output,
         projection_planner.having,
+        projection_planner.grouping_sets,
         input_schema.clone(),
         projection_planner.post_aggregation_schema,
         false,
+        None,
     )
     .unwrap_or_else(|e| panic!("{}", e.to_string()));
 
@@ -73,7 +75,7 @@ pub(crate) fn init_input_schema(field_type: FieldType, aggregator_name: &str) ->
         .field(
             FieldDefinition::new(
                 String::from("Salary"),
-                field_type,
+                field_type.clone(),
                 false,
                 SourceDefinition::Dynamic,
             ),
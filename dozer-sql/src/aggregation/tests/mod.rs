@@ -3,8 +3,14 @@ mod aggregation_avg_tests;
 #[cfg(test)]
 mod aggregation_count_tests;
 #[cfg(test)]
+mod aggregation_distinct_tests;
+#[cfg(test)]
+mod aggregation_grouping_sets_tests;
+#[cfg(test)]
 mod aggregation_having_tests;
 #[cfg(test)]
+mod aggregation_lifetime_tests;
+#[cfg(test)]
 mod aggregation_max_tests;
 #[cfg(test)]
 mod aggregation_max_value_tests;
@@ -15,6 +21,8 @@ mod aggregation_min_value_tests;
 #[cfg(test)]
 mod aggregation_null;
 #[cfg(test)]
+mod aggregation_statistics_tests;
+#[cfg(test)]
 mod aggregation_sum_tests;
 #[cfg(test)]
 mod aggregation_test_planner;
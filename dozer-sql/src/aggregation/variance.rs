@@ -0,0 +1,95 @@
+use crate::aggregation::aggregator::Aggregator;
+use crate::calculate_err_field;
+use crate::errors::PipelineError;
+use dozer_sql_expression::aggregate::AggregateFunctionType::VarSamp;
+use dozer_types::ordered_float::OrderedFloat;
+use dozer_types::types::{Field, FieldType};
+
+/// `VAR_SAMP`, `VAR_POP`, `STDDEV_SAMP` and `STDDEV_POP`.
+///
+/// Keeps the running mean and sum of squared differences from it (Welford's algorithm),
+/// which values can be removed from as well as added to.
+#[derive(Debug, bincode::Encode, bincode::Decode)]
+pub struct VarianceAggregator {
+    count: u64,
+    mean: f64,
+    squared_differences: f64,
+    population: bool,
+    standard_deviation: bool,
+    return_type: Option<FieldType>,
+}
+
+impl VarianceAggregator {
+    pub fn new(population: bool, standard_deviation: bool) -> Self {
+        Self {
+            count: 0,
+            mean: 0.0,
+            squared_differences: 0.0,
+            population,
+            standard_deviation,
+            return_type: None,
+        }
+    }
+
+    fn count_value(&mut self, value: &Field, incr: bool) -> Result<(), PipelineError> {
+        if value == &Field::Null {
+            return Ok(());
+        }
+        let value = calculate_err_field!(value.to_float(), VarSamp, value);
+        if incr {
+            self.count += 1;
+            let difference = value - self.mean;
+            self.mean += difference / self.count as f64;
+            self.squared_differences += difference * (value - self.mean);
+        } else if self.count <= 1 {
+            self.count = 0;
+            self.mean = 0.0;
+            self.squared_differences = 0.0;
+        } else {
+            let old_mean = self.mean;
+            self.count -= 1;
+            self.mean = (old_mean * (self.count + 1) as f64 - value) / self.count as f64;
+            self.squared_differences -= (value - old_mean) * (value - self.mean);
+            self.squared_differences = self.squared_differences.max(0.0);
+        }
+        Ok(())
+    }
+
+    fn get_variance(&self) -> Field {
+        let degrees_of_freedom = if self.population {
+            self.count
+        } else {
+            self.count.saturating_sub(1)
+        };
+        if degrees_of_freedom == 0 {
+            return Field::Null;
+        }
+        let variance = self.squared_differences / degrees_of_freedom as f64;
+        Field::Float(OrderedFloat(if self.standard_deviation {
+            variance.sqrt()
+        } else {
+            variance
+        }))
+    }
+}
+
+impl Aggregator for VarianceAggregator {
+    fn init(&mut self, return_type: FieldType) {
+        self.return_type = Some(return_type);
+    }
+
+    fn update(&mut self, old: &[Field], new: &[Field]) -> Result<Field, PipelineError> {
+        self.delete(old)?;
+        self.insert(new)
+    }
+
+    fn delete(&mut self, old: &[Field]) -> Result<Field, PipelineError> {
+        self.count_value(&old[0], false)?;
+        Ok(self.get_variance())
+    }
+
+    fn insert(&mut self, new: &[Field]) -> Result<Field, PipelineError> {
+        self.count_value(&new[0], true)?;
+        Ok(self.get_variance())
+    }
+}
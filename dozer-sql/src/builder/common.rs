@@ -7,6 +7,8 @@ use crate::errors::{PipelineError, ProductError};
 
 use super::QueryContext;
 
+pub const UNNEST_WITHOUT_TABLE: &str = "UNNEST must be cross joined with a table";
+
 pub fn is_an_entry_point(name: &str, query_context: &QueryContext, pipeline_idx: usize) -> bool {
     if query_context
         .pipeline_map
@@ -54,9 +56,9 @@ pub fn get_name_or_alias(relation: &TableFactor) -> Result<NameOrAlias, Pipeline
         TableFactor::TableFunction { .. } => Err(PipelineError::ProductError(
             ProductError::UnsupportedTableFunction,
         )),
-        TableFactor::UNNEST { .. } => {
-            Err(PipelineError::ProductError(ProductError::UnsupportedUnnest))
-        }
+        TableFactor::UNNEST { .. } => Err(PipelineError::InvalidUnnest(
+            UNNEST_WITHOUT_TABLE.to_string(),
+        )),
         TableFactor::NestedJoin { alias, .. } => {
             if let Some(table_alias) = alias {
                 let alias = table_alias.name.value.clone();
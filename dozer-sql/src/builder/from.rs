@@ -2,12 +2,19 @@ use dozer_core::{
     app::{AppPipeline, PipelineEntryPoint},
     DEFAULT_PORT_HANDLE,
 };
-use dozer_sql_expression::sqlparser::ast::{TableFactor, TableWithJoins};
+use dozer_sql_expression::{
+    builder::ExpressionBuilder,
+    sqlparser::ast::{Join, JoinOperator, TableFactor, TableWithJoins},
+};
 
 use crate::{
     builder::{get_from_source, QueryContext},
     errors::PipelineError,
     product::table::factory::TableProcessorFactory,
+    unnest::{
+        factory::UnnestProcessorFactory,
+        operator::{UnnestColumns, DEFAULT_ELEMENT_COLUMN, DEFAULT_OFFSET_COLUMN},
+    },
 };
 
 use super::{
@@ -18,16 +25,104 @@ use super::{
 };
 
 pub fn insert_from_to_pipeline(
-    from: TableWithJoins,
+    mut from: TableWithJoins,
     pipeline: &mut AppPipeline,
     pipeline_idx: usize,
     query_context: &mut QueryContext,
 ) -> Result<ConnectionInfo, PipelineError> {
-    if from.joins.is_empty() {
-        insert_table_to_pipeline(from.relation, pipeline, pipeline_idx, query_context)
+    // Trailing `CROSS JOIN UNNEST(...)`s flatten the records produced by the rest of the clause
+    let unnest_start = from
+        .joins
+        .iter()
+        .rposition(|join| !is_unnest(&join.relation))
+        .map_or(0, |index| index + 1);
+    let unnests = from.joins.split_off(unnest_start);
+    if from.joins.iter().any(|join| is_unnest(&join.relation)) {
+        return Err(PipelineError::InvalidUnnest(
+            "UNNEST must come after all other joins".to_string(),
+        ));
+    }
+
+    let mut connection_info = if from.joins.is_empty() {
+        insert_table_to_pipeline(from.relation, pipeline, pipeline_idx, query_context)?
     } else {
-        insert_join_to_pipeline(from, pipeline, pipeline_idx, query_context)
+        insert_join_to_pipeline(from, pipeline, pipeline_idx, query_context)?
+    };
+    for join in unnests {
+        connection_info =
+            insert_unnest_to_pipeline(join, connection_info, pipeline, query_context)?;
+    }
+    Ok(connection_info)
+}
+
+fn is_unnest(relation: &TableFactor) -> bool {
+    matches!(relation, TableFactor::UNNEST { .. })
+}
+
+fn insert_unnest_to_pipeline(
+    join: Join,
+    input: ConnectionInfo,
+    pipeline: &mut AppPipeline,
+    query_context: &mut QueryContext,
+) -> Result<ConnectionInfo, PipelineError> {
+    if join.join_operator != JoinOperator::CrossJoin {
+        return Err(PipelineError::InvalidUnnest(
+            "only CROSS JOIN UNNEST is supported".to_string(),
+        ));
+    }
+    let TableFactor::UNNEST {
+        alias,
+        array_expr,
+        with_offset,
+        with_offset_alias,
+    } = join.relation
+    else {
+        unreachable!("only UNNEST joins are inserted as unnest processors")
+    };
+
+    // `UNNEST(..) AS t(x)` names the table and the element column, `UNNEST(..) AS x` only the latter
+    let (alias, element) = match alias {
+        Some(alias) => match alias.columns.first() {
+            Some(column) => (
+                Some(ExpressionBuilder::normalize_ident(&alias.name)),
+                ExpressionBuilder::normalize_ident(column),
+            ),
+            None => (None, ExpressionBuilder::normalize_ident(&alias.name)),
+        },
+        None => (None, DEFAULT_ELEMENT_COLUMN.to_string()),
+    };
+    let offset = with_offset.then(|| match &with_offset_alias {
+        Some(offset_alias) => ExpressionBuilder::normalize_ident(offset_alias),
+        None => DEFAULT_OFFSET_COLUMN.to_string(),
+    });
+
+    let processor_name = format!("unnest--{}", query_context.get_next_processor_id());
+    if !query_context.processors_list.insert(processor_name.clone()) {
+        return Err(PipelineError::ProcessorAlreadyExists(processor_name));
     }
+    let processor = UnnestProcessorFactory::new(
+        processor_name.clone(),
+        *array_expr,
+        UnnestColumns {
+            alias,
+            element,
+            offset,
+        },
+        query_context.udfs.clone(),
+        query_context.runtime.clone(),
+    );
+    pipeline.add_processor(Box::new(processor), processor_name.clone());
+    pipeline.connect_nodes(
+        input.output_node.0,
+        input.output_node.1,
+        processor_name.clone(),
+        DEFAULT_PORT_HANDLE,
+    );
+
+    Ok(ConnectionInfo {
+        input_nodes: input.input_nodes,
+        output_node: (processor_name, DEFAULT_PORT_HANDLE),
+    })
 }
 
 fn insert_table_to_pipeline(
@@ -64,6 +64,9 @@ pub fn insert_join_to_pipeline(
                 .enable_probabilistic_optimizations
                 .in_joins
                 .unwrap_or(false),
+            pipeline.flags().join_state.clone(),
+            query_context.udfs.clone(),
+            query_context.runtime.clone(),
         );
         pipeline.add_processor(
             Box::new(join_processor_factory),
@@ -2,15 +2,18 @@ use crate::aggregation::factory::AggregationProcessorFactory;
 use crate::builder::PipelineError::InvalidQuery;
 use crate::errors::PipelineError;
 use crate::selection::factory::SelectionProcessorFactory;
+use crate::top_n::factory::TopNProcessorFactory;
 use dozer_core::app::AppPipeline;
 use dozer_core::node::PortHandle;
 use dozer_core::DEFAULT_PORT_HANDLE;
 use dozer_sql_expression::builder::{ExpressionBuilder, NameOrAlias};
-use dozer_sql_expression::sqlparser::ast::{SetOperator, SetQuantifier, TableFactor};
+use dozer_sql_expression::sqlparser::ast::{
+    Join, JoinOperator, SetOperator, SetQuantifier, TableFactor,
+};
 use dozer_types::models::udf_config::UdfConfig;
 
 use dozer_sql_expression::sqlparser::{
-    ast::{Query, Select, SetExpr, Statement},
+    ast::{Query, Select, SelectInto, SetExpr, Statement},
     dialect::DozerDialect,
     parser::Parser,
 };
@@ -23,6 +26,14 @@ use super::errors::UnsupportedSqlError;
 
 use super::product::set::set_factory::SetProcessorFactory;
 
+use self::recursive::{is_recursive_cte, recursive_cte_to_pipeline};
+pub(crate) use self::subquery::visit_expr;
+use self::subquery::{insert_subqueries_to_pipeline, take_subqueries};
+use self::top_n::{
+    get_row_number_limits, insert_top_n_to_pipeline, parse_row_count, take_row_number,
+};
+use self::window_function::{insert_window_functions_to_pipeline, take_window_functions};
+
 #[derive(Debug, Clone)]
 pub struct OutputNodeInfo {
     // Name to connect in dag
@@ -50,6 +61,9 @@ pub struct QueryContext {
     // Processors counter
     processor_counter: usize,
 
+    // Limits on ROW_NUMBER() columns, pushed down from the WHERE clause of the enclosing query
+    row_number_limits: HashMap<String, usize>,
+
     // Udf related configs
     udfs: Vec<UdfConfig>,
 
@@ -70,6 +84,7 @@ impl QueryContext {
             used_sources: Default::default(),
             processors_list: Default::default(),
             processor_counter: Default::default(),
+            row_number_limits: Default::default(),
             udfs,
             runtime,
         }
@@ -129,27 +144,31 @@ fn query_to_pipeline(
     pipeline_idx: usize,
     is_top_select: bool,
 ) -> Result<(), PipelineError> {
+    let limit = query.limit.as_ref().map(parse_row_count).transpose()?;
+    let offset = query
+        .offset
+        .as_ref()
+        .map(|offset| parse_row_count(&offset.value))
+        .transpose()?;
+
     // return error if there is unsupported syntax
-    if !query.order_by.is_empty() {
+    if !query.order_by.is_empty() && limit.is_none() {
         return Err(PipelineError::UnsupportedSqlError(
             UnsupportedSqlError::OrderByError,
         ));
     }
 
-    if query.limit.is_some() || query.offset.is_some() {
+    if query.order_by.is_empty() && (limit.is_some() || offset.is_some()) {
         return Err(PipelineError::UnsupportedSqlError(
             UnsupportedSqlError::LimitOffsetError,
         ));
     }
 
+    let table_name = table_info.name.0.clone();
+    let row_number_limits = std::mem::take(&mut query_ctx.row_number_limits);
+
     // Attach the first pipeline if there is with clause
     if let Some(with) = query.with {
-        if with.recursive {
-            return Err(PipelineError::UnsupportedSqlError(
-                UnsupportedSqlError::Recursive,
-            ));
-        }
-
         for table in with.cte_tables {
             if table.from.is_some() {
                 return Err(PipelineError::UnsupportedSqlError(
@@ -165,6 +184,17 @@ fn query_to_pipeline(
                     "WITH query name {table_name:?} specified more than once"
                 )));
             }
+            if with.recursive && is_recursive_cte(&table.query.body, &table_name) {
+                recursive_cte_to_pipeline(
+                    table_name,
+                    table.alias.columns,
+                    *table.query,
+                    pipeline,
+                    query_ctx,
+                    pipeline_idx,
+                )?;
+                continue;
+            }
             query_to_pipeline(
                 TableInfo {
                     name: NameOrAlias(table_name.clone(), Some(table_name)),
@@ -181,6 +211,7 @@ fn query_to_pipeline(
 
     match *query.body {
         SetExpr::Select(select) => {
+            query_ctx.row_number_limits = row_number_limits;
             select_to_pipeline(
                 table_info,
                 *select,
@@ -191,18 +222,14 @@ fn query_to_pipeline(
             )?;
         }
         SetExpr::Query(query) => {
-            let query_name = format!("subquery_{}", query_ctx.get_next_processor_id());
-            let mut ctx = QueryContext::new(query_ctx.udfs.clone(), query_ctx.runtime.clone());
+            // A parenthesized query, which can use the CTEs of the enclosing query
             query_to_pipeline(
-                TableInfo {
-                    name: NameOrAlias(query_name, None),
-                    override_name: None,
-                },
+                table_info,
                 *query,
                 pipeline,
-                &mut ctx,
+                query_ctx,
                 pipeline_idx,
-                false, //Inside a subquery, so not top select
+                is_top_select,
             )?
         }
         SetExpr::SetOperation {
@@ -210,46 +237,88 @@ fn query_to_pipeline(
             set_quantifier,
             left,
             right,
-        } => match op {
-            SetOperator::Union => {
-                set_to_pipeline(
-                    table_info,
-                    left,
-                    right,
-                    set_quantifier,
-                    pipeline,
-                    query_ctx,
-                    pipeline_idx,
-                    is_top_select,
-                )?;
-            }
-            _ => return Err(PipelineError::InvalidOperator(op.to_string())),
-        },
+        } => {
+            set_to_pipeline(
+                table_info,
+                op,
+                left,
+                right,
+                set_quantifier,
+                pipeline,
+                query_ctx,
+                pipeline_idx,
+                is_top_select,
+            )?;
+        }
         _ => {
             return Err(PipelineError::UnsupportedSqlError(
                 UnsupportedSqlError::GenericError("Unsupported query body structure".to_string()),
             ))
         }
     };
+
+    if let Some(limit) = limit {
+        insert_top_n_to_pipeline(
+            &table_name,
+            query.order_by,
+            offset.unwrap_or(0),
+            limit,
+            pipeline,
+            query_ctx,
+            pipeline_idx,
+        )?;
+    }
     Ok(())
 }
 
 fn select_to_pipeline(
     table_info: TableInfo,
-    select: Select,
+    mut select: Select,
     pipeline: &mut AppPipeline,
     query_ctx: &mut QueryContext,
     pipeline_idx: usize,
     is_top_select: bool,
 ) -> Result<String, PipelineError> {
+    let row_number_limits = std::mem::take(&mut query_ctx.row_number_limits);
+    let row_number = take_row_number(&mut select.projection, &row_number_limits)?;
+    if row_number.is_some() && !select.group_by.is_empty() {
+        return Err(PipelineError::UnsupportedSqlError(
+            UnsupportedSqlError::RowNumberError,
+        ));
+    }
+    let windows = take_window_functions(&mut select.projection)?;
+    if let (Some(window), false) = (windows.first(), select.group_by.is_empty()) {
+        return Err(PipelineError::UnsupportedSqlError(
+            UnsupportedSqlError::WindowFunction(window.functions[0].function.to_string()),
+        ));
+    }
+    let subqueries = take_subqueries(&mut select, query_ctx)?;
+
     // FROM clause
-    let Some(from) = select.from.into_iter().next() else {
+    let mut from_items = select.from.into_iter();
+    let Some(mut from) = from_items.next() else {
         return Err(PipelineError::UnsupportedSqlError(
             UnsupportedSqlError::FromCommaSyntax,
         ));
     };
+    // `FROM t, UNNEST(t.col)` is shorthand for `FROM t CROSS JOIN UNNEST(t.col)`
+    for item in from_items {
+        if let (TableFactor::UNNEST { .. }, true) = (&item.relation, item.joins.is_empty()) {
+            from.joins.push(Join {
+                relation: item.relation,
+                join_operator: JoinOperator::CrossJoin,
+            });
+        }
+    }
 
+    // `WHERE rn <= N` on a derived table bounds the ROW_NUMBER() it computes
+    if let (TableFactor::Derived { .. }, true, Some(selection)) =
+        (&from.relation, from.joins.is_empty(), &select.selection)
+    {
+        query_ctx.row_number_limits = get_row_number_limits(selection);
+    }
     let connection_info = from::insert_from_to_pipeline(from, pipeline, pipeline_idx, query_ctx)?;
+    query_ctx.row_number_limits.clear();
 
     let input_nodes = connection_info.input_nodes;
     let output_node = connection_info.output_node;
@@ -259,22 +328,7 @@ fn select_to_pipeline(
     let gen_selection_name = format!("select--{}", query_ctx.get_next_processor_id());
     let (gen_product_name, product_output_port) = output_node;
 
-    for (source_name, processor_name, processor_port) in input_nodes {
-        if let Some(table_info) = query_ctx
-            .pipeline_map
-            .get(&(pipeline_idx, source_name.clone()))
-        {
-            pipeline.connect_nodes(
-                table_info.node.clone(),
-                table_info.port,
-                processor_name,
-                processor_port,
-            );
-            // If not present in pipeline_map, insert into used_sources as this is coming from source
-        } else {
-            query_ctx.used_sources.push(source_name.clone());
-        }
-    }
+    connect_input_nodes(input_nodes, pipeline, query_ctx, pipeline_idx);
 
     let aggregation = AggregationProcessorFactory::new(
         gen_agg_name.clone(),
@@ -292,6 +346,14 @@ fn select_to_pipeline(
 
     pipeline.add_processor(Box::new(aggregation), gen_agg_name.clone());
 
+    let (mut output_node, mut output_port) = insert_subqueries_to_pipeline(
+        subqueries,
+        (gen_product_name, product_output_port),
+        pipeline,
+        query_ctx,
+        pipeline_idx,
+    )?;
+
     // Where clause
     if let Some(selection) = select.selection {
         let selection = SelectionProcessorFactory::new(
@@ -304,27 +366,54 @@ fn select_to_pipeline(
         pipeline.add_processor(Box::new(selection), gen_selection_name.clone());
 
         pipeline.connect_nodes(
-            gen_product_name,
-            product_output_port,
+            output_node,
+            output_port,
             gen_selection_name.clone(),
             DEFAULT_PORT_HANDLE,
         );
+        (output_node, output_port) = (gen_selection_name, DEFAULT_PORT_HANDLE);
+    }
 
-        pipeline.connect_nodes(
-            gen_selection_name,
-            DEFAULT_PORT_HANDLE,
-            gen_agg_name.clone(),
-            DEFAULT_PORT_HANDLE,
+    // Window functions, each OVER (...) clause in its own processor
+    (output_node, output_port) = insert_window_functions_to_pipeline(
+        windows,
+        (output_node, output_port),
+        pipeline,
+        query_ctx,
+    );
+
+    // ROW_NUMBER() OVER (...)
+    if let Some(row_number) = row_number {
+        let gen_top_n_name = format!("top_n--{}", query_ctx.get_next_processor_id());
+        let top_n = TopNProcessorFactory::new(
+            gen_top_n_name.clone(),
+            row_number.partition_by,
+            row_number.order_by,
+            0,
+            row_number.limit,
+            Some(row_number.name),
+            query_ctx.udfs.clone(),
+            query_ctx.runtime.clone(),
         );
-    } else {
+
+        pipeline.add_processor(Box::new(top_n), gen_top_n_name.clone());
+
         pipeline.connect_nodes(
-            gen_product_name,
-            product_output_port,
-            gen_agg_name.clone(),
+            output_node,
+            output_port,
+            gen_top_n_name.clone(),
             DEFAULT_PORT_HANDLE,
         );
+        (output_node, output_port) = (gen_top_n_name, DEFAULT_PORT_HANDLE);
     }
 
+    pipeline.connect_nodes(
+        output_node,
+        output_port,
+        gen_agg_name.clone(),
+        DEFAULT_PORT_HANDLE,
+    );
+
     query_ctx.pipeline_map.insert(
         (pipeline_idx, table_info.name.0.to_string()),
         OutputNodeInfo {
@@ -363,7 +452,8 @@ fn select_to_pipeline(
 #[allow(clippy::too_many_arguments)]
 fn set_to_pipeline(
     table_info: TableInfo,
-    left_select: Box<SetExpr>,
+    op: SetOperator,
+    mut left_select: Box<SetExpr>,
     right_select: Box<SetExpr>,
     set_quantifier: SetQuantifier,
     pipeline: &mut AppPipeline,
@@ -371,6 +461,9 @@ fn set_to_pipeline(
     pipeline_idx: usize,
     is_top_select: bool,
 ) -> Result<String, PipelineError> {
+    // The `INTO` clause is written in the first `SELECT`, but the set operation's output goes into the table.
+    let into = take_set_into(&mut left_select);
+
     let gen_left_set_name = format!("set_left_{}", query_ctx.get_next_processor_id());
     let left_table_info = TableInfo {
         name: NameOrAlias(gen_left_set_name.clone(), None),
@@ -389,22 +482,23 @@ fn set_to_pipeline(
             pipeline,
             query_ctx,
             pipeline_idx,
-            is_top_select,
+            false,
         )?,
         SetExpr::SetOperation {
-            op: _,
+            op,
             set_quantifier,
             left,
             right,
         } => set_to_pipeline(
             left_table_info,
+            op,
             left,
             right,
             set_quantifier,
             pipeline,
             query_ctx,
             pipeline_idx,
-            is_top_select,
+            false,
         )?,
         _ => {
             return Err(PipelineError::InvalidQuery(
@@ -420,22 +514,23 @@ fn set_to_pipeline(
             pipeline,
             query_ctx,
             pipeline_idx,
-            is_top_select,
+            false,
         )?,
         SetExpr::SetOperation {
-            op: _,
+            op,
             set_quantifier,
             left,
             right,
         } => set_to_pipeline(
             right_table_info,
+            op,
             left,
             right,
             set_quantifier,
             pipeline,
             query_ctx,
             pipeline_idx,
-            is_top_select,
+            false,
         )?,
         _ => {
             return Err(PipelineError::InvalidQuery(
@@ -462,6 +557,7 @@ fn set_to_pipeline(
 
     let set_proc_fac = SetProcessorFactory::new(
         gen_set_name.clone(),
+        op,
         set_quantifier,
         pipeline
             .flags()
@@ -498,9 +594,66 @@ fn set_to_pipeline(
         },
     );
 
+    let output_table_name = if let Some(into) = into {
+        Some(into.name.to_string())
+    } else {
+        table_info.override_name.clone()
+    };
+
+    if is_top_select && output_table_name.is_none() {
+        return Err(PipelineError::MissingIntoClause);
+    }
+
+    if let Some(table_name) = output_table_name {
+        if query_ctx.output_tables_map.contains_key(&table_name) {
+            return Err(PipelineError::DuplicateIntoClause(table_name));
+        }
+
+        query_ctx.output_tables_map.insert(
+            table_name,
+            OutputNodeInfo {
+                node: gen_set_name.clone(),
+                port: DEFAULT_PORT_HANDLE,
+            },
+        );
+    }
+
     Ok(gen_set_name)
 }
 
+/// Takes the `INTO` clause of the first `SELECT` of a set operation.
+fn take_set_into(set_expr: &mut SetExpr) -> Option<SelectInto> {
+    match set_expr {
+        SetExpr::Select(select) => select.into.take(),
+        SetExpr::SetOperation { left, .. } => take_set_into(left),
+        _ => None,
+    }
+}
+
+fn connect_input_nodes(
+    input_nodes: Vec<(String, String, PortHandle)>,
+    pipeline: &mut AppPipeline,
+    query_ctx: &mut QueryContext,
+    pipeline_idx: usize,
+) {
+    for (source_name, processor_name, processor_port) in input_nodes {
+        if let Some(table_info) = query_ctx
+            .pipeline_map
+            .get(&(pipeline_idx, source_name.clone()))
+        {
+            pipeline.connect_nodes(
+                table_info.node.clone(),
+                table_info.port,
+                processor_name,
+                processor_port,
+            );
+            // If not present in pipeline_map, insert into used_sources as this is coming from source
+        } else {
+            query_ctx.used_sources.push(source_name.clone());
+        }
+    }
+}
+
 fn get_from_source(
     relation: TableFactor,
     pipeline: &mut AppPipeline,
@@ -561,7 +714,11 @@ struct ConnectionInfo {
 mod common;
 mod from;
 mod join;
+mod recursive;
+mod subquery;
 mod table_operator;
+mod top_n;
+mod window_function;
 
 pub use common::string_from_sql_object_name;
 pub use table_operator::{TableOperatorArg, TableOperatorDescriptor};
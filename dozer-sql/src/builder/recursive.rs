@@ -0,0 +1,206 @@
+use dozer_core::{app::AppPipeline, DEFAULT_PORT_HANDLE};
+use dozer_sql_expression::{
+    builder::NameOrAlias,
+    sqlparser::ast::{
+        Expr, Ident, JoinConstraint, JoinOperator, Query, Select, SetExpr, SetOperator,
+        TableFactor, TableWithJoins,
+    },
+};
+
+use crate::{
+    errors::{PipelineError, UnsupportedSqlError},
+    recursive::factory::{RecursiveProcessorFactory, ANCHOR_PORT, RECURSIVE_PORT},
+};
+
+use super::{
+    common::string_from_sql_object_name, connect_input_nodes, from::insert_from_to_pipeline,
+    select_to_pipeline, set_to_pipeline, OutputNodeInfo, QueryContext, TableInfo,
+};
+
+/// Whether the CTE body is a `UNION` whose right side reads from the CTE itself.
+pub fn is_recursive_cte(body: &SetExpr, cte_name: &str) -> bool {
+    let SetExpr::SetOperation { right, .. } = body else {
+        return false;
+    };
+    let SetExpr::Select(select) = &**right else {
+        return false;
+    };
+    select.from.iter().any(|from| {
+        std::iter::once(&from.relation)
+            .chain(from.joins.iter().map(|join| &join.relation))
+            .any(|relation| is_cte_reference(relation, cte_name))
+    })
+}
+
+fn is_cte_reference(relation: &TableFactor, cte_name: &str) -> bool {
+    match relation {
+        TableFactor::Table { name, .. } => string_from_sql_object_name(name) == cte_name,
+        _ => false,
+    }
+}
+
+/// The recursive term of a recursive CTE: `SELECT .. FROM cte JOIN base ON ..`.
+struct RecursiveTerm {
+    /// The CTE name, with the alias it has in the recursive term.
+    table: NameOrAlias,
+    base: TableFactor,
+    join_constraint: Expr,
+    select: Select,
+}
+
+/// Rows are deduplicated like `UNION` does, so `DISTINCT` makes no difference.
+fn get_recursive_term(mut select: Select, cte_name: &str) -> Result<RecursiveTerm, PipelineError> {
+    let unsupported = || PipelineError::UnsupportedSqlError(UnsupportedSqlError::Recursive);
+    if select.from.len() != 1 || !select.group_by.is_empty() || select.having.is_some() {
+        return Err(unsupported());
+    }
+    let TableWithJoins {
+        relation,
+        mut joins,
+    } = select.from.remove(0);
+    let Some(join) = joins.pop() else {
+        return Err(unsupported());
+    };
+    if !joins.is_empty() {
+        return Err(unsupported());
+    }
+    let JoinOperator::Inner(JoinConstraint::On(join_constraint)) = join.join_operator else {
+        return Err(unsupported());
+    };
+
+    let (cte, base) = match (
+        is_cte_reference(&relation, cte_name),
+        is_cte_reference(&join.relation, cte_name),
+    ) {
+        (true, false) => (relation, join.relation),
+        (false, true) => (join.relation, relation),
+        _ => return Err(unsupported()),
+    };
+    let TableFactor::Table { alias, .. } = cte else {
+        return Err(unsupported());
+    };
+
+    Ok(RecursiveTerm {
+        table: NameOrAlias(cte_name.to_string(), alias.map(|alias| alias.name.value)),
+        base,
+        join_constraint,
+        select,
+    })
+}
+
+/// Adds a processor that maintains the fixpoint of `WITH RECURSIVE cte AS (anchor UNION recursive term)`.
+pub fn recursive_cte_to_pipeline(
+    cte_name: String,
+    columns: Vec<Ident>,
+    query: Query,
+    pipeline: &mut AppPipeline,
+    query_ctx: &mut QueryContext,
+    pipeline_idx: usize,
+) -> Result<(), PipelineError> {
+    let unsupported = || PipelineError::UnsupportedSqlError(UnsupportedSqlError::Recursive);
+    if query.with.is_some() || !query.order_by.is_empty() || query.limit.is_some() {
+        return Err(unsupported());
+    }
+    let SetExpr::SetOperation {
+        op: SetOperator::Union,
+        left,
+        right,
+        ..
+    } = *query.body
+    else {
+        return Err(unsupported());
+    };
+    let SetExpr::Select(right) = *right else {
+        return Err(unsupported());
+    };
+    let term = get_recursive_term(*right, &cte_name)?;
+
+    // The anchor can't refer to the CTE, which isn't in the pipeline map yet.
+    let gen_anchor_name = format!("recursive_anchor_{}", query_ctx.get_next_processor_id());
+    let anchor_table_info = TableInfo {
+        name: NameOrAlias(gen_anchor_name.clone(), None),
+        override_name: None,
+    };
+    match *left {
+        SetExpr::Select(select) => {
+            select_to_pipeline(
+                anchor_table_info,
+                *select,
+                pipeline,
+                query_ctx,
+                pipeline_idx,
+                false,
+            )?;
+        }
+        SetExpr::SetOperation {
+            op: SetOperator::Union,
+            set_quantifier,
+            left,
+            right,
+        } => {
+            set_to_pipeline(
+                anchor_table_info,
+                SetOperator::Union,
+                left,
+                right,
+                set_quantifier,
+                pipeline,
+                query_ctx,
+                pipeline_idx,
+                false,
+            )?;
+        }
+        _ => return Err(unsupported()),
+    }
+    let anchor = query_ctx
+        .pipeline_map
+        .get(&(pipeline_idx, gen_anchor_name))
+        .cloned()
+        .ok_or_else(unsupported)?;
+
+    let base = insert_from_to_pipeline(
+        TableWithJoins {
+            relation: term.base,
+            joins: vec![],
+        },
+        pipeline,
+        pipeline_idx,
+        query_ctx,
+    )?;
+    connect_input_nodes(base.input_nodes, pipeline, query_ctx, pipeline_idx);
+
+    let gen_recursive_name = format!("recursive--{}", query_ctx.get_next_processor_id());
+    let recursive = RecursiveProcessorFactory::new(
+        gen_recursive_name.clone(),
+        term.table,
+        columns.into_iter().map(|column| column.value).collect(),
+        term.join_constraint,
+        term.select.projection,
+        term.select.selection,
+        query_ctx.udfs.clone(),
+        query_ctx.runtime.clone(),
+    );
+    pipeline.add_processor(Box::new(recursive), gen_recursive_name.clone());
+    pipeline.connect_nodes(
+        anchor.node,
+        anchor.port,
+        gen_recursive_name.clone(),
+        ANCHOR_PORT,
+    );
+    let (base_node, base_port) = base.output_node;
+    pipeline.connect_nodes(
+        base_node,
+        base_port,
+        gen_recursive_name.clone(),
+        RECURSIVE_PORT,
+    );
+
+    query_ctx.pipeline_map.insert(
+        (pipeline_idx, cte_name),
+        OutputNodeInfo {
+            node: gen_recursive_name,
+            port: DEFAULT_PORT_HANDLE,
+        },
+    );
+    Ok(())
+}
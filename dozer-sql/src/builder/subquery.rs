@@ -0,0 +1,614 @@
+use dozer_core::{app::AppPipeline, node::PortHandle, DEFAULT_PORT_HANDLE};
+use dozer_sql_expression::{
+    aggregate::AggregateFunctionType,
+    builder::NameOrAlias,
+    sqlparser::{
+        ast::{
+            BinaryOperator, Expr, FunctionArg, FunctionArgExpr, Ident, JoinConstraint,
+            JoinOperator, Query, Select, SelectItem, SetExpr, TableFactor, TableWithJoins,
+            Value as SqlValue,
+        },
+        dialect::DozerDialect,
+        parser::Parser,
+    },
+};
+
+use crate::{
+    errors::{PipelineError, UnsupportedSqlError},
+    product::join::factory::{JoinProcessorFactory, LEFT_JOIN_PORT, RIGHT_JOIN_PORT},
+};
+
+use super::{
+    common::get_name_or_alias, query_to_pipeline, top_n::parse_row_count, QueryContext, TableInfo,
+};
+
+/// A subquery that's evaluated by joining its result with the `FROM` clause of the enclosing query.
+pub struct SubqueryJoin {
+    /// Name of the subquery result, which the join constraint and the rewritten expressions refer to.
+    pub name: String,
+    pub query: Query,
+    pub join_operator: JoinOperator,
+    /// Join condition expressions that must not be nullable, because the anti join of a `NOT IN` doesn't handle `NULL`s.
+    pub non_nullable: Vec<Expr>,
+}
+
+/// Takes the subqueries out of the `WHERE` clause and the projection of `select`.
+///
+/// `[NOT] IN` and `[NOT] EXISTS` conditions become semi and anti joins,
+/// and scalar subqueries become left outer joins whose result column replaces the subquery.
+/// Correlated subqueries are grouped by the columns they're correlated on.
+pub fn take_subqueries(
+    select: &mut Select,
+    query_ctx: &mut QueryContext,
+) -> Result<Vec<SubqueryJoin>, PipelineError> {
+    let outer_relations = get_relation_names(&select.from)?;
+    let mut joins = vec![];
+
+    if let Some(selection) = select.selection.take() {
+        let mut conditions = vec![];
+        for condition in split_conjunction(selection) {
+            match condition {
+                Expr::InSubquery {
+                    expr,
+                    subquery,
+                    negated,
+                } => joins.push(in_subquery_join(
+                    *expr,
+                    *subquery,
+                    negated,
+                    &outer_relations,
+                    query_ctx,
+                )?),
+                Expr::Exists { subquery, negated } => joins.push(exists_join(
+                    *subquery,
+                    negated,
+                    &outer_relations,
+                    query_ctx,
+                )?),
+                mut condition => {
+                    replace_scalar_subqueries(
+                        &mut condition,
+                        &outer_relations,
+                        query_ctx,
+                        &mut joins,
+                    )?;
+                    conditions.push(condition);
+                }
+            }
+        }
+        select.selection = join_conjunction(conditions);
+    }
+
+    let mut has_wildcard = false;
+    for item in select.projection.iter_mut() {
+        let expr = match item {
+            SelectItem::UnnamedExpr(expr) => {
+                // Keep the column name of the original expression.
+                let alias = Ident::new(expr.to_string());
+                let mut expr = expr.clone();
+                let scalar_joins = joins.len();
+                replace_scalar_subqueries(&mut expr, &outer_relations, query_ctx, &mut joins)?;
+                if joins.len() > scalar_joins {
+                    *item = SelectItem::ExprWithAlias { expr, alias };
+                }
+                continue;
+            }
+            SelectItem::ExprWithAlias { expr, .. } => expr,
+            SelectItem::QualifiedWildcard(..) => continue,
+            SelectItem::Wildcard(_) => {
+                has_wildcard = true;
+                continue;
+            }
+        };
+        replace_scalar_subqueries(expr, &outer_relations, query_ctx, &mut joins)?;
+    }
+    if let Some(having) = &mut select.having {
+        visit_expr(having, &mut |expr| match expr {
+            Expr::Subquery(_) | Expr::InSubquery { .. } | Expr::Exists { .. } => {
+                Err(subquery_error(expr))
+            }
+            _ => Ok(true),
+        })?;
+    }
+
+    // The columns scalar subqueries add to the input would show up in `SELECT *`.
+    let has_outer_joins = joins
+        .iter()
+        .any(|join| matches!(join.join_operator, JoinOperator::LeftOuter(_)));
+    if has_wildcard && has_outer_joins {
+        return Err(PipelineError::UnsupportedSqlError(
+            UnsupportedSqlError::Subquery("in a SELECT *".to_string()),
+        ));
+    }
+    Ok(joins)
+}
+
+/// Joins the subqueries with the output of the `FROM` clause, returning the last join.
+pub fn insert_subqueries_to_pipeline(
+    subqueries: Vec<SubqueryJoin>,
+    (mut output_node, mut output_port): (String, PortHandle),
+    pipeline: &mut AppPipeline,
+    query_ctx: &mut QueryContext,
+    pipeline_idx: usize,
+) -> Result<(String, PortHandle), PipelineError> {
+    for subquery in subqueries {
+        let name = NameOrAlias(subquery.name.clone(), Some(subquery.name.clone()));
+        query_to_pipeline(
+            TableInfo {
+                name: name.clone(),
+                override_name: None,
+            },
+            subquery.query,
+            pipeline,
+            query_ctx,
+            pipeline_idx,
+            false, // Inside a subquery, so not top select
+        )?;
+        let subquery_output = query_ctx
+            .pipeline_map
+            .get(&(pipeline_idx, subquery.name.clone()))
+            .cloned()
+            .ok_or_else(|| {
+                PipelineError::InvalidQuery(format!("Invalid subquery {}", subquery.name))
+            })?;
+
+        let join_processor_name = format!("join_{}", query_ctx.get_next_processor_id());
+        if !query_ctx
+            .processors_list
+            .insert(join_processor_name.clone())
+        {
+            return Err(PipelineError::ProcessorAlreadyExists(join_processor_name));
+        }
+        let join_processor_factory = JoinProcessorFactory::new(
+            join_processor_name.clone(),
+            None,
+            Some(name),
+            subquery.join_operator,
+            pipeline
+                .flags()
+                .enable_probabilistic_optimizations
+                .in_joins
+                .unwrap_or(false),
+            pipeline.flags().join_state.clone(),
+            query_ctx.udfs.clone(),
+            query_ctx.runtime.clone(),
+        )
+        .with_non_nullable(subquery.non_nullable);
+        pipeline.add_processor(
+            Box::new(join_processor_factory),
+            join_processor_name.clone(),
+        );
+        pipeline.connect_nodes(
+            output_node,
+            output_port,
+            join_processor_name.clone(),
+            LEFT_JOIN_PORT,
+        );
+        pipeline.connect_nodes(
+            subquery_output.node,
+            subquery_output.port,
+            join_processor_name.clone(),
+            RIGHT_JOIN_PORT,
+        );
+        (output_node, output_port) = (join_processor_name, DEFAULT_PORT_HANDLE);
+    }
+    Ok((output_node, output_port))
+}
+
+fn in_subquery_join(
+    expr: Expr,
+    mut subquery: Query,
+    negated: bool,
+    outer_relations: &[String],
+    query_ctx: &mut QueryContext,
+) -> Result<SubqueryJoin, PipelineError> {
+    let name = format!("__subquery_{}", query_ctx.get_next_processor_id());
+    let select = get_select(&mut subquery)?;
+    let value = get_single_column(select)?;
+    let (outer_keys, inner_keys) = decorrelate(&mut subquery, outer_relations)?;
+
+    let select = get_select(&mut subquery)?;
+    let (join_operator, non_nullable) = if negated {
+        // `x NOT IN (..)` is NULL, not true, if `x` or a value of the subquery is NULL.
+        let non_nullable = vec![expr.clone(), get_key_column(&name, 0)];
+        let constraint = get_join_constraint(&name, std::iter::once(expr).chain(outer_keys));
+        (JoinOperator::LeftAnti(constraint), non_nullable)
+    } else {
+        // Join keys match if both are NULL, but `NULL IN (NULL)` isn't true.
+        let is_not_null = Expr::IsNotNull(Box::new(value.clone()));
+        let condition = if has_aggregate(&value) {
+            &mut select.having
+        } else {
+            &mut select.selection
+        };
+        *condition = join_conjunction(condition.take().into_iter().chain([is_not_null]).collect());
+        let constraint = get_join_constraint(&name, std::iter::once(expr).chain(outer_keys));
+        (JoinOperator::LeftSemi(constraint), vec![])
+    };
+    select.projection = project_keys(std::iter::once(value).chain(inner_keys));
+    Ok(SubqueryJoin {
+        name,
+        query: subquery,
+        join_operator,
+        non_nullable,
+    })
+}
+
+fn exists_join(
+    mut subquery: Query,
+    negated: bool,
+    outer_relations: &[String],
+    query_ctx: &mut QueryContext,
+) -> Result<SubqueryJoin, PipelineError> {
+    let name = format!("__subquery_{}", query_ctx.get_next_processor_id());
+    let (outer_keys, inner_keys) = decorrelate(&mut subquery, outer_relations)?;
+
+    let select = get_select(&mut subquery)?;
+    select.projection = if inner_keys.is_empty() {
+        // Any row will do.
+        project_keys(std::iter::once(Expr::Value(SqlValue::Boolean(true))))
+    } else {
+        project_keys(inner_keys)
+    };
+    let constraint = get_join_constraint(&name, outer_keys);
+    let join_operator = if negated {
+        JoinOperator::LeftAnti(constraint)
+    } else {
+        JoinOperator::LeftSemi(constraint)
+    };
+    Ok(SubqueryJoin {
+        name,
+        query: subquery,
+        join_operator,
+        non_nullable: vec![],
+    })
+}
+
+/// Replaces the scalar subqueries in `expr` with the column of their result.
+fn replace_scalar_subqueries(
+    expr: &mut Expr,
+    outer_relations: &[String],
+    query_ctx: &mut QueryContext,
+    joins: &mut Vec<SubqueryJoin>,
+) -> Result<(), PipelineError> {
+    visit_expr(expr, &mut |expr| {
+        let subquery = match expr {
+            Expr::Subquery(subquery) => (**subquery).clone(),
+            Expr::InSubquery { .. } | Expr::Exists { .. } => return Err(subquery_error(expr)),
+            _ => return Ok(true),
+        };
+        let (join, column) = scalar_subquery_join(subquery, outer_relations, query_ctx)?;
+        joins.push(join);
+        *expr = column;
+        Ok(false)
+    })
+}
+
+fn scalar_subquery_join(
+    mut subquery: Query,
+    outer_relations: &[String],
+    query_ctx: &mut QueryContext,
+) -> Result<(SubqueryJoin, Expr), PipelineError> {
+    let name = format!("__subquery_{}", query_ctx.get_next_processor_id());
+    let select = get_select(&mut subquery)?;
+    let value = get_single_column(select)?;
+    // The join would repeat the row of the enclosing query for each row of the subquery.
+    let is_aggregate = has_aggregate(&value) && select.group_by.is_empty();
+    let is_limit_one = matches!(subquery.limit.as_ref().map(parse_row_count), Some(Ok(1)));
+    if !is_aggregate && !is_limit_one {
+        return Err(PipelineError::UnsupportedSqlError(
+            UnsupportedSqlError::Subquery(subquery.to_string()),
+        ));
+    }
+    let (outer_keys, inner_keys) = decorrelate(&mut subquery, outer_relations)?;
+
+    let select = get_select(&mut subquery)?;
+    let mut projection = vec![SelectItem::ExprWithAlias {
+        expr: value.clone(),
+        alias: Ident::new("__value"),
+    }];
+    projection.extend(project_keys(inner_keys));
+    select.projection = projection;
+
+    // An outer join keeps the rows without a match, for which the subquery returns NULL,
+    // or 0 for a COUNT.
+    let column = Expr::CompoundIdentifier(vec![Ident::new(&name), Ident::new("__value")]);
+    let column = match &value {
+        Expr::Function(function) if function.name.to_string().eq_ignore_ascii_case("count") => {
+            Parser::new(&DozerDialect {})
+                .try_with_sql(&format!("COALESCE({column}, 0)"))
+                .and_then(|mut parser| parser.parse_expr())
+                .map_err(|err| PipelineError::InternalError(Box::new(err)))?
+        }
+        _ => column,
+    };
+    let join_operator = JoinOperator::LeftOuter(get_join_constraint(&name, outer_keys));
+    Ok((
+        SubqueryJoin {
+            name,
+            query: subquery,
+            join_operator,
+            non_nullable: vec![],
+        },
+        column,
+    ))
+}
+
+fn get_select(query: &mut Query) -> Result<&mut Select, PipelineError> {
+    match &mut *query.body {
+        SetExpr::Select(select) => Ok(select.as_mut()),
+        body => Err(PipelineError::UnsupportedSqlError(
+            UnsupportedSqlError::Subquery(body.to_string()),
+        )),
+    }
+}
+
+fn get_single_column(select: &Select) -> Result<Expr, PipelineError> {
+    match select.projection.as_slice() {
+        [SelectItem::UnnamedExpr(expr)] | [SelectItem::ExprWithAlias { expr, .. }] => {
+            Ok(expr.clone())
+        }
+        _ => Err(PipelineError::InvalidQuery(format!(
+            "Subquery {select} must return one column"
+        ))),
+    }
+}
+
+/// Moves the `outer = inner` conditions of a correlated subquery out of its `WHERE` clause,
+/// returning the outer and the inner side of each.
+fn decorrelate(
+    subquery: &mut Query,
+    outer_relations: &[String],
+) -> Result<(Vec<Expr>, Vec<Expr>), PipelineError> {
+    let has_order_by_or_limit = !subquery.order_by.is_empty() || subquery.limit.is_some();
+    let select = get_select(subquery)?;
+    let inner_relations = get_relation_names(&select.from)?;
+    let is_outer_column = |expr: &Expr| match expr {
+        Expr::CompoundIdentifier(idents) if idents.len() == 2 => {
+            let relation = &idents[0].value;
+            outer_relations.contains(relation) && !inner_relations.contains(relation)
+        }
+        _ => false,
+    };
+    let refers_to_outer = |expr: &Expr| -> Result<bool, PipelineError> {
+        let mut found = false;
+        visit_expr(&mut expr.clone(), &mut |expr| {
+            found |= is_outer_column(expr);
+            Ok(!found)
+        })?;
+        Ok(found)
+    };
+
+    let mut outer_keys = vec![];
+    let mut inner_keys = vec![];
+    if let Some(selection) = select.selection.take() {
+        let mut conditions = vec![];
+        for condition in split_conjunction(selection) {
+            if let Expr::BinaryOp {
+                left,
+                op: BinaryOperator::Eq,
+                right,
+            } = &condition
+            {
+                if is_outer_column(left) && !refers_to_outer(right)? {
+                    outer_keys.push((**left).clone());
+                    inner_keys.push((**right).clone());
+                    continue;
+                }
+                if is_outer_column(right) && !refers_to_outer(left)? {
+                    outer_keys.push((**right).clone());
+                    inner_keys.push((**left).clone());
+                    continue;
+                }
+            }
+            if refers_to_outer(&condition)? {
+                return Err(subquery_error(&condition));
+            }
+            conditions.push(condition);
+        }
+        select.selection = join_conjunction(conditions);
+    }
+
+    for item in &select.projection {
+        if let SelectItem::UnnamedExpr(expr) | SelectItem::ExprWithAlias { expr, .. } = item {
+            if refers_to_outer(expr)? {
+                return Err(subquery_error(expr));
+            }
+        }
+    }
+    for expr in select.group_by.iter().chain(&select.having) {
+        if refers_to_outer(expr)? {
+            return Err(subquery_error(expr));
+        }
+    }
+
+    if !inner_keys.is_empty() {
+        if has_order_by_or_limit {
+            return Err(PipelineError::UnsupportedSqlError(
+                UnsupportedSqlError::Subquery(subquery.to_string()),
+            ));
+        }
+        // Aggregate separately for each row of the enclosing query.
+        let select = get_select(subquery)?;
+        let is_aggregate = select.projection.iter().any(|item| match item {
+            SelectItem::UnnamedExpr(expr) | SelectItem::ExprWithAlias { expr, .. } => {
+                has_aggregate(expr)
+            }
+            _ => false,
+        });
+        if is_aggregate || !select.group_by.is_empty() {
+            select.group_by.extend(inner_keys.iter().cloned());
+        }
+    }
+    Ok((outer_keys, inner_keys))
+}
+
+fn has_aggregate(expr: &Expr) -> bool {
+    let mut found = false;
+    let _ = visit_expr(&mut expr.clone(), &mut |expr| {
+        if let Expr::Function(function) = expr {
+            found |=
+                AggregateFunctionType::new(&function.name.to_string().to_lowercase()).is_some();
+        }
+        Ok(!found)
+    });
+    found
+}
+
+fn project_keys(keys: impl IntoIterator<Item = Expr>) -> Vec<SelectItem> {
+    keys.into_iter()
+        .enumerate()
+        .map(|(index, expr)| SelectItem::ExprWithAlias {
+            expr,
+            alias: Ident::new(format!("__key_{index}")),
+        })
+        .collect()
+}
+
+/// `outer_0 = name.__key_0 AND outer_1 = name.__key_1 ..`
+fn get_join_constraint(name: &str, outer_keys: impl IntoIterator<Item = Expr>) -> JoinConstraint {
+    let conditions = outer_keys
+        .into_iter()
+        .enumerate()
+        .map(|(index, outer_key)| Expr::BinaryOp {
+            left: Box::new(outer_key),
+            op: BinaryOperator::Eq,
+            right: Box::new(get_key_column(name, index)),
+        })
+        .collect();
+    match join_conjunction(conditions) {
+        Some(condition) => JoinConstraint::On(condition),
+        None => JoinConstraint::None,
+    }
+}
+
+fn get_key_column(name: &str, index: usize) -> Expr {
+    Expr::CompoundIdentifier(vec![Ident::new(name), Ident::new(format!("__key_{index}"))])
+}
+
+fn get_relation_names(from: &[TableWithJoins]) -> Result<Vec<String>, PipelineError> {
+    let mut names = vec![];
+    for table in from {
+        for relation in
+            std::iter::once(&table.relation).chain(table.joins.iter().map(|join| &join.relation))
+        {
+            // `UNNEST(..) AS t(x)` names a table, but `UNNEST(..) AS x` only names the element column.
+            if let TableFactor::UNNEST { alias, .. } = relation {
+                names.extend(
+                    alias
+                        .iter()
+                        .filter(|alias| !alias.columns.is_empty())
+                        .map(|alias| alias.name.value.clone()),
+                );
+                continue;
+            }
+            let NameOrAlias(name, alias) = get_name_or_alias(relation)?;
+            names.push(alias.unwrap_or(name));
+        }
+    }
+    Ok(names)
+}
+
+fn split_conjunction(expr: Expr) -> Vec<Expr> {
+    match expr {
+        Expr::BinaryOp {
+            left,
+            op: BinaryOperator::And,
+            right,
+        } => {
+            let mut conditions = split_conjunction(*left);
+            conditions.extend(split_conjunction(*right));
+            conditions
+        }
+        Expr::Nested(expr)
+            if matches!(
+                *expr,
+                Expr::BinaryOp {
+                    op: BinaryOperator::And,
+                    ..
+                }
+            ) =>
+        {
+            split_conjunction(*expr)
+        }
+        expr => vec![expr],
+    }
+}
+
+fn join_conjunction(conditions: Vec<Expr>) -> Option<Expr> {
+    conditions.into_iter().reduce(|left, right| Expr::BinaryOp {
+        left: Box::new(left),
+        op: BinaryOperator::And,
+        right: Box::new(right),
+    })
+}
+
+/// Calls `f` on `expr` and, while it returns `true`, on the expressions nested in it.
+pub(crate) fn visit_expr(
+    expr: &mut Expr,
+    f: &mut impl FnMut(&mut Expr) -> Result<bool, PipelineError>,
+) -> Result<(), PipelineError> {
+    if !f(expr)? {
+        return Ok(());
+    }
+    match expr {
+        Expr::BinaryOp { left, right, .. } => {
+            visit_expr(left, f)?;
+            visit_expr(right, f)
+        }
+        Expr::UnaryOp { expr, .. }
+        | Expr::Nested(expr)
+        | Expr::Cast { expr, .. }
+        | Expr::TryCast { expr, .. }
+        | Expr::IsNull(expr)
+        | Expr::IsNotNull(expr)
+        | Expr::IsTrue(expr)
+        | Expr::IsFalse(expr)
+        | Expr::InSubquery { expr, .. } => visit_expr(expr, f),
+        Expr::Between {
+            expr, low, high, ..
+        } => {
+            visit_expr(expr, f)?;
+            visit_expr(low, f)?;
+            visit_expr(high, f)
+        }
+        Expr::InList { expr, list, .. } => {
+            visit_expr(expr, f)?;
+            list.iter_mut().try_for_each(|expr| visit_expr(expr, f))
+        }
+        Expr::Like { expr, pattern, .. }
+        | Expr::ILike { expr, pattern, .. }
+        | Expr::SimilarTo { expr, pattern, .. } => {
+            visit_expr(expr, f)?;
+            visit_expr(pattern, f)
+        }
+        Expr::Case {
+            operand,
+            conditions,
+            results,
+            else_result,
+        } => {
+            operand
+                .iter_mut()
+                .chain(else_result.iter_mut())
+                .try_for_each(|expr| visit_expr(expr, f))?;
+            conditions
+                .iter_mut()
+                .chain(results.iter_mut())
+                .try_for_each(|expr| visit_expr(expr, f))
+        }
+        Expr::Function(function) => function.args.iter_mut().try_for_each(|arg| match arg {
+            FunctionArg::Named {
+                arg: FunctionArgExpr::Expr(expr),
+                ..
+            }
+            | FunctionArg::Unnamed(FunctionArgExpr::Expr(expr)) => visit_expr(expr, f),
+            _ => Ok(()),
+        }),
+        _ => Ok(()),
+    }
+}
+
+fn subquery_error(expr: &Expr) -> PipelineError {
+    PipelineError::UnsupportedSqlError(UnsupportedSqlError::Subquery(expr.to_string()))
+}
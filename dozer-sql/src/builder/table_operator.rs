@@ -14,7 +14,9 @@ use crate::{
 };
 
 use super::{
-    common::{is_a_pipeline_output, is_an_entry_point, string_from_sql_object_name},
+    common::{
+        is_a_pipeline_output, is_an_entry_point, string_from_sql_object_name, UNNEST_WITHOUT_TABLE,
+    },
     ConnectionInfo, QueryContext,
 };
 
@@ -44,7 +46,9 @@ pub fn is_table_operator(
         }
         TableFactor::Derived { .. } => Ok(None),
         TableFactor::TableFunction { .. } => Err(PipelineError::UnsupportedTableFunction),
-        TableFactor::UNNEST { .. } => Err(PipelineError::UnsupportedUnnest),
+        TableFactor::UNNEST { .. } => Err(PipelineError::InvalidUnnest(
+            UNNEST_WITHOUT_TABLE.to_string(),
+        )),
         TableFactor::NestedJoin { .. } => Err(PipelineError::UnsupportedNestedJoin),
         TableFactor::Pivot { .. } => Err(PipelineError::UnsupportedPivot),
     }
@@ -107,8 +111,7 @@ pub fn insert_table_operator_processor_to_pipeline(
                 query_context.runtime.clone(),
             ));
             (processor_name, processor)
-        } else if operator.name.to_uppercase() == "TUMBLE" || operator.name.to_uppercase() == "HOP"
-        {
+        } else if ["TUMBLE", "HOP", "SESSION"].contains(&operator.name.to_uppercase().as_str()) {
             let processor_name = generate_name("WIN", &operator, query_context);
             let processor = Box::new(WindowProcessorFactory::new(
                 processor_name.clone(),
@@ -1,6 +1,18 @@
-use super::statement_to_pipeline;
-use crate::{errors::PipelineError, tests::utils::create_test_runtime};
+use std::collections::HashMap;
+
+use super::subquery::take_subqueries;
+use super::top_n::get_row_number_limits;
+use super::{statement_to_pipeline, QueryContext};
+use crate::{
+    errors::{PipelineError, UnsupportedSqlError},
+    tests::utils::create_test_runtime,
+};
 use dozer_core::app::AppPipeline;
+use dozer_sql_expression::sqlparser::{
+    ast::{JoinConstraint, JoinOperator, SetExpr, Statement},
+    dialect::DozerDialect,
+    parser::Parser,
+};
 #[test]
 #[should_panic]
 fn disallow_zero_outgoing_ndes() {
@@ -209,3 +221,508 @@ from tbl;"#;
     //check if the result is ok
     assert!(result.is_ok());
 }
+
+#[test]
+fn test_order_by_without_limit() {
+    let sql = r#"SELECT a INTO c FROM b ORDER BY a"#;
+    let runtime = create_test_runtime();
+    let result = statement_to_pipeline(
+        sql,
+        &mut AppPipeline::new_with_default_flags(),
+        None,
+        vec![],
+        runtime,
+    );
+    assert!(matches!(
+        result,
+        Err(PipelineError::UnsupportedSqlError(
+            UnsupportedSqlError::OrderByError
+        ))
+    ))
+}
+
+#[test]
+fn test_limit_without_order_by() {
+    let sql = r#"SELECT a INTO c FROM b LIMIT 10"#;
+    let runtime = create_test_runtime();
+    let result = statement_to_pipeline(
+        sql,
+        &mut AppPipeline::new_with_default_flags(),
+        None,
+        vec![],
+        runtime,
+    );
+    assert!(matches!(
+        result,
+        Err(PipelineError::UnsupportedSqlError(
+            UnsupportedSqlError::LimitOffsetError
+        ))
+    ))
+}
+
+#[test]
+fn test_order_by_limit() {
+    let sql =
+        r#"SELECT a, COUNT(b) INTO c FROM d GROUP BY a ORDER BY COUNT(b) DESC LIMIT 10 OFFSET 5"#;
+    let runtime = create_test_runtime();
+    let context = statement_to_pipeline(
+        sql,
+        &mut AppPipeline::new_with_default_flags(),
+        None,
+        vec![],
+        runtime,
+    )
+    .unwrap();
+    assert!(context.output_tables_map["c"].node.starts_with("top_n--"));
+}
+
+#[test]
+fn test_row_number_limit() {
+    let sql = r#"
+        SELECT customer_id, order_id, rn
+        INTO latest_orders
+        FROM (
+            SELECT customer_id, order_id,
+                ROW_NUMBER() OVER (PARTITION BY customer_id ORDER BY created_at DESC) AS rn
+            FROM orders
+        ) o
+        WHERE rn <= 100
+    "#;
+    let runtime = create_test_runtime();
+    let result = statement_to_pipeline(
+        sql,
+        &mut AppPipeline::new_with_default_flags(),
+        None,
+        vec![],
+        runtime,
+    );
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_unbounded_row_number() {
+    // Without a `WHERE rn <= N` bound, ROW_NUMBER() is computed by the window function processor.
+    let sql = r#"
+        SELECT customer_id, order_id,
+            ROW_NUMBER() OVER (PARTITION BY customer_id ORDER BY created_at DESC) AS rn
+        INTO numbered_orders
+        FROM orders
+    "#;
+    let runtime = create_test_runtime();
+    let result = statement_to_pipeline(
+        sql,
+        &mut AppPipeline::new_with_default_flags(),
+        None,
+        vec![],
+        runtime,
+    );
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_row_number_with_group_by() {
+    let sql = r#"
+        SELECT a, rn
+        INTO c
+        FROM (SELECT a, ROW_NUMBER() OVER (ORDER BY a) AS rn FROM b GROUP BY a) t
+        WHERE rn <= 10
+    "#;
+    let runtime = create_test_runtime();
+    let result = statement_to_pipeline(
+        sql,
+        &mut AppPipeline::new_with_default_flags(),
+        None,
+        vec![],
+        runtime,
+    );
+    assert!(matches!(
+        result,
+        Err(PipelineError::UnsupportedSqlError(
+            UnsupportedSqlError::RowNumberError
+        ))
+    ))
+}
+
+#[test]
+fn test_session_window_with_watermark() {
+    let sql = r#"
+        SELECT user_id, window_start, window_end, COUNT(*) AS clicks
+        INTO sessions
+        FROM SESSION(clicks, clicked_at, '30 MINUTES', '1 MINUTE')
+        GROUP BY user_id, window_start, window_end
+    "#;
+    let runtime = create_test_runtime();
+    let result = statement_to_pipeline(
+        sql,
+        &mut AppPipeline::new_with_default_flags(),
+        None,
+        vec![],
+        runtime,
+    );
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_window_functions() {
+    let sql = r#"
+        SELECT user_id, amount,
+            SUM(amount) OVER (PARTITION BY user_id ORDER BY ts ROWS BETWEEN 2 PRECEDING AND CURRENT ROW) AS moving_sum,
+            amount - LAG(amount) OVER (PARTITION BY user_id ORDER BY ts) AS change,
+            RANK() OVER (ORDER BY amount DESC) AS amount_rank
+        INTO payments_with_stats
+        FROM payments
+    "#;
+    let runtime = create_test_runtime();
+    let result = statement_to_pipeline(
+        sql,
+        &mut AppPipeline::new_with_default_flags(),
+        None,
+        vec![],
+        runtime,
+    );
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_window_function_with_group_by() {
+    let sql = r#"SELECT a, RANK() OVER (ORDER BY a) INTO c FROM b GROUP BY a"#;
+    let runtime = create_test_runtime();
+    let result = statement_to_pipeline(
+        sql,
+        &mut AppPipeline::new_with_default_flags(),
+        None,
+        vec![],
+        runtime,
+    );
+    assert!(matches!(
+        result,
+        Err(PipelineError::UnsupportedSqlError(
+            UnsupportedSqlError::WindowFunction(_)
+        ))
+    ))
+}
+
+#[test]
+fn test_row_number_limits() {
+    let dialect = DozerDialect {};
+    let selection = |condition: &str| {
+        let sql = format!("SELECT * FROM t WHERE {condition}");
+        let Statement::Query(query) = Parser::parse_sql(&dialect, &sql).unwrap().remove(0) else {
+            panic!("expected a query");
+        };
+        let SetExpr::Select(select) = *query.body else {
+            panic!("expected a select");
+        };
+        get_row_number_limits(&select.selection.unwrap())
+    };
+
+    assert_eq!(selection("rn <= 3"), HashMap::from([("rn".to_string(), 3)]));
+    assert_eq!(
+        selection("t.rn < 3"),
+        HashMap::from([("rn".to_string(), 2)])
+    );
+    assert_eq!(
+        selection("(1 = rn) AND a > 5"),
+        HashMap::from([("rn".to_string(), 1)])
+    );
+    assert_eq!(
+        selection("rn <= 10 AND rn < 5"),
+        HashMap::from([("rn".to_string(), 4)])
+    );
+    assert!(selection("rn >= 3 OR rn <= 3").is_empty());
+}
+
+#[test]
+fn test_recursive_cte() {
+    let sql = r#"
+        WITH RECURSIVE hierarchy (id, depth) AS (
+            SELECT id, 0 FROM employees WHERE manager_id IS NULL
+            UNION ALL
+            SELECT e.id, h.depth + 1
+            FROM hierarchy h JOIN employees e ON h.id = e.manager_id
+        )
+        SELECT id, depth INTO org_chart FROM hierarchy
+    "#;
+    let runtime = create_test_runtime();
+    let result = statement_to_pipeline(
+        sql,
+        &mut AppPipeline::new_with_default_flags(),
+        None,
+        vec![],
+        runtime,
+    );
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_unsupported_recursive_cte() {
+    let sql = r#"
+        WITH RECURSIVE hierarchy AS (
+            SELECT id FROM employees WHERE manager_id IS NULL
+            UNION
+            SELECT e.id
+            FROM hierarchy h LEFT JOIN employees e ON h.id = e.manager_id
+        )
+        SELECT id INTO org_chart FROM hierarchy
+    "#;
+    let runtime = create_test_runtime();
+    let result = statement_to_pipeline(
+        sql,
+        &mut AppPipeline::new_with_default_flags(),
+        None,
+        vec![],
+        runtime,
+    );
+    assert!(matches!(
+        result,
+        Err(PipelineError::UnsupportedSqlError(
+            UnsupportedSqlError::Recursive
+        ))
+    ))
+}
+
+#[test]
+fn test_cte_referenced_twice() {
+    let sql = r#"
+        WITH totals AS (SELECT customer_id, SUM(amount) AS total FROM orders GROUP BY customer_id)
+        SELECT a.customer_id, b.customer_id
+        INTO pairs
+        FROM totals a JOIN totals b ON a.total = b.total
+    "#;
+    let runtime = create_test_runtime();
+    let mut pipeline = AppPipeline::new_with_default_flags();
+    statement_to_pipeline(sql, &mut pipeline, None, vec![], runtime).unwrap();
+    assert_eq!(pipeline.get_entry_points_sources_names(), vec!["orders"]);
+}
+
+#[test]
+fn test_cte_in_parenthesized_query() {
+    let sql = r#"
+        WITH tbl AS (SELECT a FROM b)
+        (SELECT a INTO c FROM tbl)
+    "#;
+    let runtime = create_test_runtime();
+    let mut pipeline = AppPipeline::new_with_default_flags();
+    statement_to_pipeline(sql, &mut pipeline, None, vec![], runtime).unwrap();
+    assert_eq!(pipeline.get_entry_points_sources_names(), vec!["b"]);
+}
+
+#[test]
+fn test_subqueries() {
+    let sql = r#"
+        SELECT c.id, (SELECT COUNT(o.id) FROM orders o WHERE o.customer_id = c.id) AS order_count
+        INTO customer_stats
+        FROM customers c
+        WHERE c.region IN (SELECT name FROM regions WHERE active = true)
+            AND NOT EXISTS (SELECT 1 FROM blocked b WHERE b.customer_id = c.id)
+            AND c.balance > (SELECT AVG(balance) FROM customers)
+    "#;
+    let runtime = create_test_runtime();
+    let mut pipeline = AppPipeline::new_with_default_flags();
+    statement_to_pipeline(sql, &mut pipeline, None, vec![], runtime).unwrap();
+    let mut sources = pipeline.get_entry_points_sources_names();
+    sources.sort();
+    assert_eq!(
+        sources,
+        vec!["blocked", "customers", "customers", "orders", "regions"]
+    );
+}
+
+#[test]
+fn test_decorrelated_subquery() {
+    let dialect = DozerDialect {};
+    let sql = "SELECT c.id FROM customers c \
+        WHERE EXISTS (SELECT 1 FROM orders o WHERE o.customer_id = c.id AND o.total > 100)";
+    let Statement::Query(query) = Parser::parse_sql(&dialect, sql).unwrap().remove(0) else {
+        panic!("expected a query");
+    };
+    let SetExpr::Select(mut select) = *query.body else {
+        panic!("expected a select");
+    };
+
+    let mut query_ctx = QueryContext::new(vec![], create_test_runtime());
+    let subqueries = take_subqueries(&mut select, &mut query_ctx).unwrap();
+    assert_eq!(select.selection, None);
+    assert_eq!(subqueries.len(), 1);
+    assert_eq!(
+        subqueries[0].query.to_string(),
+        "SELECT o.customer_id AS __key_0 FROM orders AS o WHERE o.total > 100"
+    );
+    let JoinOperator::LeftSemi(JoinConstraint::On(constraint)) = &subqueries[0].join_operator
+    else {
+        panic!("expected a semi join");
+    };
+    assert_eq!(constraint.to_string(), "c.id = __subquery_1.__key_0");
+}
+
+#[test]
+fn test_in_subquery_nulls() {
+    let dialect = DozerDialect {};
+    let sql = "SELECT c.id FROM customers c \
+        WHERE c.region IN (SELECT name FROM regions WHERE active = true) \
+            AND c.id NOT IN (SELECT customer_id FROM blocked)";
+    let Statement::Query(query) = Parser::parse_sql(&dialect, sql).unwrap().remove(0) else {
+        panic!("expected a query");
+    };
+    let SetExpr::Select(mut select) = *query.body else {
+        panic!("expected a select");
+    };
+
+    let mut query_ctx = QueryContext::new(vec![], create_test_runtime());
+    let subqueries = take_subqueries(&mut select, &mut query_ctx).unwrap();
+    assert_eq!(subqueries.len(), 2);
+    // NULLs never match an IN, and NOT IN only supports columns that can't be NULL.
+    assert_eq!(
+        subqueries[0].query.to_string(),
+        "SELECT name AS __key_0 FROM regions WHERE active = true AND name IS NOT NULL"
+    );
+    assert!(subqueries[0].non_nullable.is_empty());
+    let non_nullable = subqueries[1]
+        .non_nullable
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+    assert_eq!(non_nullable, vec!["c.id", "__subquery_2.__key_0"]);
+}
+
+#[test]
+fn test_multi_row_scalar_subquery() {
+    let sql = r#"
+        SELECT c.id, (SELECT o.id FROM orders o WHERE o.customer_id = c.id) AS order_id
+        INTO customer_orders
+        FROM customers c
+    "#;
+    let runtime = create_test_runtime();
+    let result = statement_to_pipeline(
+        sql,
+        &mut AppPipeline::new_with_default_flags(),
+        None,
+        vec![],
+        runtime,
+    );
+    assert!(matches!(
+        result,
+        Err(PipelineError::UnsupportedSqlError(
+            UnsupportedSqlError::Subquery(_)
+        ))
+    ))
+}
+
+#[test]
+fn test_unsupported_subquery() {
+    let sql = r#"
+        SELECT id INTO c FROM customers
+        WHERE region = 'EU' OR id IN (SELECT customer_id FROM orders)
+    "#;
+    let runtime = create_test_runtime();
+    let result = statement_to_pipeline(
+        sql,
+        &mut AppPipeline::new_with_default_flags(),
+        None,
+        vec![],
+        runtime,
+    );
+    assert!(matches!(
+        result,
+        Err(PipelineError::UnsupportedSqlError(
+            UnsupportedSqlError::Subquery(_)
+        ))
+    ))
+}
+
+#[test]
+fn test_join_types() {
+    let sql = r#"
+        SELECT e.id, r.rate INTO enriched_events
+        FROM events e
+            FULL OUTER JOIN users u ON e.user_id = u.id
+            CROSS JOIN settings s
+            LEFT ANTI JOIN blocked b ON u.id = b.user_id
+            LEFT JOIN rates r ON e.currency = r.currency AND e.ts BETWEEN r.valid_from AND r.valid_to
+    "#;
+    let runtime = create_test_runtime();
+    let result = statement_to_pipeline(
+        sql,
+        &mut AppPipeline::new_with_default_flags(),
+        None,
+        vec![],
+        runtime,
+    );
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_as_of_join() {
+    // The TTL bounds the history of rate versions kept by the join.
+    let sql = r#"
+        SELECT e.id, r.rate INTO converted_events
+        FROM events e
+            LEFT JOIN TTL(rates, valid_from, '30 DAYS') r
+                ON e.currency = r.currency AND AS_OF(e.ts, r.valid_from)
+    "#;
+    let runtime = create_test_runtime();
+    let result = statement_to_pipeline(
+        sql,
+        &mut AppPipeline::new_with_default_flags(),
+        None,
+        vec![],
+        runtime,
+    );
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_intersect_and_except() {
+    let sql = r#"
+        SELECT customer_id INTO lapsed_customers FROM orders_2022
+        EXCEPT
+        SELECT customer_id FROM orders_2023
+        INTERSECT ALL
+        SELECT customer_id FROM active_customers
+    "#;
+    let runtime = create_test_runtime();
+    let context = statement_to_pipeline(
+        sql,
+        &mut AppPipeline::new_with_default_flags(),
+        None,
+        vec![],
+        runtime,
+    )
+    .unwrap();
+    // The set operation goes into the table, not the first `SELECT`.
+    assert_eq!(
+        context.output_tables_map.keys().collect::<Vec<_>>(),
+        vec!["lapsed_customers"]
+    );
+    assert!(context.output_tables_map["lapsed_customers"]
+        .node
+        .starts_with("set_"));
+}
+
+#[test]
+fn test_unnest() {
+    let runtime = create_test_runtime();
+    for sql in [
+        "SELECT p.id, tag INTO post_tags FROM posts p CROSS JOIN UNNEST(p.tags) AS t(tag)",
+        "SELECT id, tag, pos INTO post_tags FROM posts, UNNEST(tags) AS tag WITH OFFSET AS pos",
+    ] {
+        let result = statement_to_pipeline(
+            sql,
+            &mut AppPipeline::new_with_default_flags(),
+            None,
+            vec![],
+            runtime.clone(),
+        );
+        assert!(result.is_ok(), "{sql}");
+    }
+
+    let sql = "SELECT tag INTO tags FROM UNNEST(tags) AS tag";
+    let result = statement_to_pipeline(
+        sql,
+        &mut AppPipeline::new_with_default_flags(),
+        None,
+        vec![],
+        runtime,
+    );
+    assert!(matches!(result, Err(PipelineError::InvalidUnnest(_))));
+}
@@ -0,0 +1,182 @@
+use std::collections::HashMap;
+
+use dozer_core::{app::AppPipeline, DEFAULT_PORT_HANDLE};
+use dozer_sql_expression::sqlparser::ast::{
+    BinaryOperator, Expr, Ident, OrderByExpr, SelectItem, Value as SqlValue, WindowType,
+};
+
+use crate::{
+    errors::{PipelineError, UnsupportedSqlError},
+    top_n::factory::TopNProcessorFactory,
+};
+
+use super::{OutputNodeInfo, QueryContext};
+
+/// A `ROW_NUMBER() OVER (PARTITION BY .. ORDER BY ..)` select item.
+pub struct RowNumber {
+    /// Name of the column the Top-N processor adds for the row number.
+    pub name: String,
+    pub partition_by: Vec<Expr>,
+    pub order_by: Vec<OrderByExpr>,
+    /// Largest row number the enclosing query keeps.
+    pub limit: usize,
+}
+
+/// Replaces a `ROW_NUMBER()` select item bounded by `limits` with a reference to the column the Top-N processor adds.
+/// An unbounded `ROW_NUMBER()` is left to the window function processor.
+pub fn take_row_number(
+    projection: &mut [SelectItem],
+    limits: &HashMap<String, usize>,
+) -> Result<Option<RowNumber>, PipelineError> {
+    let mut row_number = None;
+    for item in projection.iter_mut() {
+        let (expr, alias) = match item {
+            SelectItem::UnnamedExpr(expr) => (expr, None),
+            SelectItem::ExprWithAlias { expr, alias } => (expr, Some(alias.value.clone())),
+            _ => continue,
+        };
+        let Expr::Function(function) = &*expr else {
+            continue;
+        };
+        if function.name.to_string().to_lowercase() != "row_number" {
+            continue;
+        }
+        let Some(over) = &function.over else {
+            continue;
+        };
+        let WindowType::WindowSpec(over) = over else {
+            return Err(PipelineError::UnsupportedSqlError(
+                UnsupportedSqlError::RowNumberError,
+            ));
+        };
+        if row_number.is_some() || !function.args.is_empty() || over.order_by.is_empty() {
+            return Err(PipelineError::UnsupportedSqlError(
+                UnsupportedSqlError::RowNumberError,
+            ));
+        }
+
+        let name = alias.unwrap_or_else(|| expr.to_string());
+        let Some(limit) = limits.get(&name).copied() else {
+            continue;
+        };
+        row_number = Some(RowNumber {
+            name: name.clone(),
+            partition_by: over.partition_by.clone(),
+            order_by: over.order_by.clone(),
+            limit,
+        });
+        *expr = Expr::Identifier(Ident::new(name));
+    }
+    Ok(row_number)
+}
+
+/// Finds the `rn <= N`, `rn < N` and `rn = N` conditions in a `WHERE` clause,
+/// which bound the row numbers a derived table has to produce.
+pub fn get_row_number_limits(selection: &Expr) -> HashMap<String, usize> {
+    let mut limits = HashMap::new();
+    collect_row_number_limits(selection, &mut limits);
+    limits
+}
+
+fn collect_row_number_limits(selection: &Expr, limits: &mut HashMap<String, usize>) {
+    let (left, op, right) = match selection {
+        Expr::Nested(expr) => return collect_row_number_limits(expr, limits),
+        Expr::BinaryOp { left, op, right } => (left, op, right),
+        _ => return,
+    };
+    if *op == BinaryOperator::And {
+        collect_row_number_limits(left, limits);
+        collect_row_number_limits(right, limits);
+        return;
+    }
+
+    // Only conditions that hold for every row number up to some N.
+    let (column, inclusive, value) = match (column_name(left), column_name(right), op) {
+        (Some(column), None, BinaryOperator::LtEq | BinaryOperator::Eq) => (column, true, right),
+        (Some(column), None, BinaryOperator::Lt) => (column, false, right),
+        (None, Some(column), BinaryOperator::GtEq | BinaryOperator::Eq) => (column, true, left),
+        (None, Some(column), BinaryOperator::Gt) => (column, false, left),
+        _ => return,
+    };
+    let Ok(value) = parse_row_count(value) else {
+        return;
+    };
+    let limit = if inclusive {
+        value
+    } else {
+        value.saturating_sub(1)
+    };
+    limits
+        .entry(column)
+        .and_modify(|existing| *existing = limit.min(*existing))
+        .or_insert(limit);
+}
+
+fn column_name(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Identifier(ident) => Some(ident.value.clone()),
+        Expr::CompoundIdentifier(idents) => idents.last().map(|ident| ident.value.clone()),
+        _ => None,
+    }
+}
+
+/// Parses the row count of a `LIMIT` or `OFFSET` clause.
+pub fn parse_row_count(expr: &Expr) -> Result<usize, PipelineError> {
+    match expr {
+        Expr::Value(SqlValue::Number(n, _)) => n.to_string().parse().map_err(|_| {
+            PipelineError::UnsupportedSqlError(UnsupportedSqlError::InvalidRowCount(
+                expr.to_string(),
+            ))
+        }),
+        _ => Err(PipelineError::UnsupportedSqlError(
+            UnsupportedSqlError::InvalidRowCount(expr.to_string()),
+        )),
+    }
+}
+
+/// Puts a Top-N processor for `ORDER BY .. LIMIT ..` after the query that outputs `table_name`.
+pub fn insert_top_n_to_pipeline(
+    table_name: &str,
+    order_by: Vec<OrderByExpr>,
+    offset: usize,
+    limit: usize,
+    pipeline: &mut AppPipeline,
+    query_ctx: &mut QueryContext,
+    pipeline_idx: usize,
+) -> Result<(), PipelineError> {
+    let key = (pipeline_idx, table_name.to_string());
+    let input = query_ctx.pipeline_map.get(&key).cloned().ok_or_else(|| {
+        PipelineError::InvalidQuery("ORDER BY and LIMIT need a SELECT or UNION".to_string())
+    })?;
+
+    let gen_top_n_name = format!("top_n--{}", query_ctx.get_next_processor_id());
+    let top_n = TopNProcessorFactory::new(
+        gen_top_n_name.clone(),
+        vec![],
+        order_by,
+        offset,
+        limit,
+        None,
+        query_ctx.udfs.clone(),
+        query_ctx.runtime.clone(),
+    );
+    pipeline.add_processor(Box::new(top_n), gen_top_n_name.clone());
+    pipeline.connect_nodes(
+        input.node.clone(),
+        input.port,
+        gen_top_n_name.clone(),
+        DEFAULT_PORT_HANDLE,
+    );
+
+    let output = OutputNodeInfo {
+        node: gen_top_n_name,
+        port: DEFAULT_PORT_HANDLE,
+    };
+    for output_table in query_ctx.output_tables_map.values_mut() {
+        if output_table.node == input.node && output_table.port == input.port {
+            *output_table = output.clone();
+        }
+    }
+    query_ctx.pipeline_map.insert(key, output);
+    Ok(())
+}
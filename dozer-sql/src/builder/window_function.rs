@@ -0,0 +1,141 @@
+use dozer_core::{app::AppPipeline, node::PortHandle, DEFAULT_PORT_HANDLE};
+use dozer_sql_expression::{
+    aggregate::AggregateFunctionType,
+    sqlparser::ast::{
+        Expr, Function, Ident, OrderByExpr, SelectItem, WindowFrameUnits, WindowType,
+    },
+};
+
+use crate::{
+    errors::{PipelineError, UnsupportedSqlError},
+    window_function::factory::{WindowFunctionCall, WindowFunctionProcessorFactory},
+};
+
+use super::{subquery::visit_expr, QueryContext};
+
+/// The window functions of a select that share an `OVER (PARTITION BY .. ORDER BY ..)` clause.
+pub struct Window {
+    pub partition_by: Vec<Expr>,
+    pub order_by: Vec<OrderByExpr>,
+    pub functions: Vec<WindowFunctionCall>,
+}
+
+const WINDOW_FUNCTIONS: [&str; 7] = [
+    "row_number",
+    "rank",
+    "dense_rank",
+    "lag",
+    "lead",
+    "first_value",
+    "last_value",
+];
+
+/// Replaces the window function calls in a projection with references to the columns the window function processors add.
+pub fn take_window_functions(projection: &mut [SelectItem]) -> Result<Vec<Window>, PipelineError> {
+    let mut windows: Vec<Window> = vec![];
+    for item in projection.iter_mut() {
+        let (expr, alias) = match item {
+            SelectItem::UnnamedExpr(expr) => (expr, None),
+            SelectItem::ExprWithAlias { expr, alias } => (expr, Some(alias.value.clone())),
+            _ => continue,
+        };
+        // A window function that is a whole select item is named by its alias.
+        let mut alias = alias.filter(|_| is_window_function(expr));
+        visit_expr(expr, &mut |expr| {
+            if !is_window_function(expr) {
+                return Ok(true);
+            }
+            let name = alias.take().unwrap_or_else(|| expr.to_string());
+            let Expr::Function(mut function) =
+                std::mem::replace(expr, Expr::Identifier(Ident::new(name.clone())))
+            else {
+                unreachable!("window functions are function calls");
+            };
+            let Some(over) = function.over.take() else {
+                unreachable!("window functions have an OVER clause");
+            };
+            let WindowType::WindowSpec(over) = over else {
+                return Err(PipelineError::UnsupportedSqlError(
+                    UnsupportedSqlError::WindowFunction(function.to_string()),
+                ));
+            };
+
+            let function_name = function.name.to_string().to_lowercase();
+            let is_supported = (WINDOW_FUNCTIONS.contains(&function_name.as_str())
+                || AggregateFunctionType::new(&function_name).is_some())
+                && !function.distinct
+                && over
+                    .window_frame
+                    .as_ref()
+                    .map_or(true, |frame| frame.units != WindowFrameUnits::Groups);
+            if !is_supported {
+                return Err(PipelineError::UnsupportedSqlError(
+                    UnsupportedSqlError::WindowFunction(function.to_string()),
+                ));
+            }
+
+            let call = WindowFunctionCall {
+                name,
+                function,
+                frame: over.window_frame,
+            };
+            match windows.iter_mut().find(|window| {
+                window.partition_by == over.partition_by && window.order_by == over.order_by
+            }) {
+                Some(window) => {
+                    if !window
+                        .functions
+                        .iter()
+                        .any(|existing| existing.name == call.name)
+                    {
+                        window.functions.push(call);
+                    }
+                }
+                None => windows.push(Window {
+                    partition_by: over.partition_by,
+                    order_by: over.order_by,
+                    functions: vec![call],
+                }),
+            }
+            Ok(false)
+        })?;
+    }
+    Ok(windows)
+}
+
+fn is_window_function(expr: &Expr) -> bool {
+    matches!(expr, Expr::Function(Function { over: Some(_), .. }))
+}
+
+/// Chains a window function processor for each window after `input`, returning the last one.
+pub fn insert_window_functions_to_pipeline(
+    windows: Vec<Window>,
+    input: (String, PortHandle),
+    pipeline: &mut AppPipeline,
+    query_ctx: &mut QueryContext,
+) -> (String, PortHandle) {
+    let (mut output_node, mut output_port) = input;
+    for window in windows {
+        let gen_window_function_name =
+            format!("window_function--{}", query_ctx.get_next_processor_id());
+        let window_function = WindowFunctionProcessorFactory::new(
+            gen_window_function_name.clone(),
+            window.partition_by,
+            window.order_by,
+            window.functions,
+            query_ctx.udfs.clone(),
+            query_ctx.runtime.clone(),
+        );
+
+        pipeline.add_processor(Box::new(window_function), gen_window_function_name.clone());
+
+        pipeline.connect_nodes(
+            output_node,
+            output_port,
+            gen_window_function_name.clone(),
+            DEFAULT_PORT_HANDLE,
+        );
+        (output_node, output_port) = (gen_window_function_name, DEFAULT_PORT_HANDLE);
+    }
+    (output_node, output_port)
+}
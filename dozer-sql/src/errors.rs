@@ -3,12 +3,13 @@
 use dozer_core::node::PortHandle;
 use dozer_types::chrono::RoundingError;
 use dozer_types::errors::internal::BoxedError;
-use dozer_types::errors::types::{DeserializationError, TypeError};
+use dozer_types::errors::types::{DeserializationError, SerializationError, TypeError};
 
 use dozer_types::thiserror;
 use dozer_types::thiserror::Error;
 use dozer_types::types::{Field, FieldType};
 use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
 
 #[derive(Debug, Clone)]
 pub struct FieldTypes {
@@ -78,8 +79,8 @@ pub enum PipelineError {
     #[error("Table Function is not supported")]
     UnsupportedTableFunction,
 
-    #[error("UNNEST not supported")]
-    UnsupportedUnnest,
+    #[error("Invalid UNNEST: {0}")]
+    InvalidUnnest(String),
 
     #[error("Nested Join is not supported")]
     UnsupportedNestedJoin,
@@ -123,11 +124,14 @@ pub enum PipelineError {
 
     #[error("Duplicated Processor name: {0}")]
     ProcessorAlreadyExists(String),
+
+    #[error("Recursive CTE {0} didn't reach a fixpoint within {1} iterations")]
+    RecursionLimit(String, usize),
 }
 
 #[derive(Error, Debug)]
 pub enum UnsupportedSqlError {
-    #[error("Recursive CTE is not supported. Please refer to the documentation(https://getdozer.io/docs/reference/sql/introduction) for more information. ")]
+    #[error("A recursive CTE must be a query UNION a SELECT that JOINs the CTE with one other table ON equal columns. Please refer to the documentation(https://getdozer.io/docs/reference/sql/introduction) for more information. ")]
     Recursive,
     #[error("Currently this syntax is not supported for CTEs")]
     CteFromError,
@@ -138,10 +142,20 @@ pub enum UnsupportedSqlError {
 
     #[error("FROM clause doesn't support \"Comma Syntax\"")]
     FromCommaSyntax,
-    #[error("ORDER BY is not supported in SQL. You could achieve the same by using the ORDER BY operator in the cache and APIs")]
+    #[error("ORDER BY is only supported together with LIMIT. You could achieve the same by using the ORDER BY operator in the cache and APIs")]
     OrderByError,
-    #[error("Limit and Offset are not supported in SQL. You could achieve the same by using the LIMIT and OFFSET operators in the cache and APIs")]
+    #[error("LIMIT and OFFSET are only supported together with ORDER BY")]
     LimitOffsetError,
+    #[error("LIMIT and OFFSET must be non-negative integers, found {0}")]
+    InvalidRowCount(String),
+    #[error("ROW_NUMBER() takes no arguments, needs an ORDER BY in its OVER clause, can't be combined with GROUP BY and can only be used once per SELECT")]
+    RowNumberError,
+    #[error("Unsupported window function {0}. Window functions are ROW_NUMBER, RANK, DENSE_RANK, LAG, LEAD, FIRST_VALUE, LAST_VALUE and aggregates with an OVER clause, can't be combined with GROUP BY, and support ROWS frames with whole offsets and RANGE frames with numeric offsets on a single ORDER BY expression")]
+    WindowFunction(String),
+    #[error("Unsupported subquery {0}. Subqueries can be used in `[NOT] IN (SELECT ..)` and `[NOT] EXISTS (SELECT ..)` conditions ANDed in the WHERE clause, or as scalar subqueries that return one row, with an aggregate and no GROUP BY or with LIMIT 1, and can only refer to the enclosing query in `=` conditions on qualified columns")]
+    Subquery(String),
+    #[error("Unsupported grouping {0}. GROUPING() takes expressions of the GROUP BY clause")]
+    Grouping(String),
     #[error("Select statements should specify INTO for creating output tables")]
     IntoError,
 
@@ -183,6 +197,15 @@ pub enum JoinError {
     UnsupportedJoinConstraintType,
     #[error("Unsupported Join type")]
     UnsupportedJoinType,
+    #[error("AS_OF is only supported in INNER and LEFT OUTER joins")]
+    UnsupportedAsOfJoinType,
+    #[error("Invalid AS_OF condition {0}, expected AS_OF(left_time_column, right_version_column)")]
+    InvalidAsOfCondition(String),
+    #[error("NOT IN doesn't support the nullable expression {0}, use NOT EXISTS instead")]
+    NullableNotIn(String),
+
+    #[error("Error evaluating the join condition: {0}")]
+    Expression(#[from] dozer_sql_expression::error::Error),
 
     #[error("Overflow error computing the eviction time in the TTL reference field")]
     EvictionTimeOverflow,
@@ -192,6 +215,12 @@ pub enum JoinError {
 
     #[error("Deserialization error: {0}")]
     Deserialization(#[from] DeserializationError),
+
+    #[error("Serialization error: {0}")]
+    Serialization(#[from] SerializationError),
+
+    #[error("Failed to access join state file {0:?}: {1}")]
+    StateFile(PathBuf, #[source] std::io::Error),
 }
 
 #[derive(Error, Debug)]
@@ -214,9 +243,6 @@ pub enum ProductError {
     #[error("Error in the FROM clause, Table Function is not supported")]
     UnsupportedTableFunction,
 
-    #[error("Error in the FROM clause, UNNEST is not supported")]
-    UnsupportedUnnest,
-
     #[error("Error in the FROM clause, Pivot is not supported")]
     UnsupportedPivot,
 }
@@ -235,6 +261,9 @@ pub enum WindowError {
     #[error("Hop size not specified in the window function")]
     WindowMissingHopSizeArgument,
 
+    #[error("Gap not specified in the session window function")]
+    WindowMissingGapArgument,
+
     #[error("Invalid time reference column {0} in the window function")]
     WindowInvalidColumn(String),
 
@@ -244,15 +273,18 @@ pub enum WindowError {
     #[error("Invalid time hop '{0}' specified in the window function")]
     WindowInvalidHop(String),
 
+    #[error("Invalid session gap '{0}' specified in the window function")]
+    WindowInvalidGap(String),
+
+    #[error("Invalid watermark delay '{0}' specified in the window function")]
+    WindowInvalidWatermark(String),
+
     #[error("Error in the FROM clause, Derived Table is not supported")]
     UnsupportedDerivedTable,
 
     #[error("Error in the FROM clause, Table Function is not supported")]
     UnsupportedTableFunction,
 
-    #[error("Error in the FROM clause, UNNEST is not supported")]
-    UnsupportedUnnest,
-
     #[error("This type of Nested Join is not supported")]
     UnsupportedNestedJoin,
 
@@ -270,6 +302,9 @@ pub enum WindowError {
     #[error("Error in Hop Windowing function:\n{0}")]
     HopRoundingError(#[source] RoundingError),
 
+    #[error("Invalid column specified in Session Windowing function.\nOnly Timestamp types are supported")]
+    SessionInvalidColumnType(),
+
     #[error("Invalid WINDOW function")]
     InvalidWindow(),
 
@@ -1,10 +1,11 @@
 use crate::expression::tests::test_common::*;
 use dozer_types::types::SourceDefinition;
 use dozer_types::{
-    chrono::{DateTime, NaiveDate, TimeZone, Utc},
+    chrono::{DateTime, NaiveDate, NaiveTime, TimeZone, Utc},
     ordered_float::OrderedFloat,
     rust_decimal::Decimal,
     types::{Field, FieldDefinition, FieldType, Schema},
+    uuid::Uuid,
 };
 
 #[test]
@@ -231,7 +232,7 @@ fn test_float() {
             .field(
                 FieldDefinition::new(
                     String::from("field"),
-                    FieldType::Decimal,
+                    FieldType::Decimal(None),
                     false,
                     SourceDefinition::Dynamic,
                 ),
@@ -339,7 +340,7 @@ fn test_boolean() {
             .field(
                 FieldDefinition::new(
                     String::from("field"),
-                    FieldType::Decimal,
+                    FieldType::Decimal(None),
                     false,
                     SourceDefinition::Dynamic,
                 ),
@@ -356,7 +357,7 @@ fn test_boolean() {
             .field(
                 FieldDefinition::new(
                     String::from("field"),
-                    FieldType::Decimal,
+                    FieldType::Decimal(None),
                     false,
                     SourceDefinition::Dynamic,
                 ),
@@ -473,7 +474,7 @@ fn test_string() {
             .field(
                 FieldDefinition::new(
                     String::from("field"),
-                    FieldType::Decimal,
+                    FieldType::Decimal(None),
                     false,
                     SourceDefinition::Dynamic,
                 ),
@@ -643,7 +644,7 @@ fn test_text() {
             .field(
                 FieldDefinition::new(
                     String::from("field"),
-                    FieldType::Decimal,
+                    FieldType::Decimal(None),
                     false,
                     SourceDefinition::Dynamic,
                 ),
@@ -784,3 +785,138 @@ fn test_date() {
         Field::Date(NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap())
     );
 }
+
+#[test]
+fn test_decimal_with_precision_and_scale() {
+    let f = run_fct(
+        "SELECT CAST(field AS DECIMAL(5, 2)) FROM users",
+        Schema::default()
+            .field(
+                FieldDefinition::new(
+                    String::from("field"),
+                    FieldType::Float,
+                    false,
+                    SourceDefinition::Dynamic,
+                ),
+                false,
+            )
+            .clone(),
+        vec![Field::Float(OrderedFloat(12.346))],
+    );
+    assert_eq!(f, Field::Decimal(Decimal::new(1235, 2)));
+
+    let f = run_fct(
+        "SELECT CAST(field AS NUMERIC(5, 2)) FROM users",
+        Schema::default()
+            .field(
+                FieldDefinition::new(
+                    String::from("field"),
+                    FieldType::String,
+                    false,
+                    SourceDefinition::Dynamic,
+                ),
+                false,
+            )
+            .clone(),
+        vec![Field::String("-1.5".to_string())],
+    );
+    assert_eq!(f, Field::Decimal(Decimal::new(-150, 2)));
+}
+
+#[test]
+#[should_panic]
+fn test_decimal_out_of_precision() {
+    run_fct(
+        "SELECT CAST(field AS DECIMAL(3, 2)) FROM users",
+        Schema::default()
+            .field(
+                FieldDefinition::new(
+                    String::from("field"),
+                    FieldType::Int,
+                    false,
+                    SourceDefinition::Dynamic,
+                ),
+                false,
+            )
+            .clone(),
+        vec![Field::Int(42)],
+    );
+}
+
+#[test]
+fn test_uuid_time_and_enum() {
+    let uuid = "a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11";
+    let f = run_fct(
+        "SELECT CAST(field AS UUID) FROM users",
+        Schema::default()
+            .field(
+                FieldDefinition::new(
+                    String::from("field"),
+                    FieldType::String,
+                    false,
+                    SourceDefinition::Dynamic,
+                ),
+                false,
+            )
+            .clone(),
+        vec![Field::String(uuid.to_string())],
+    );
+    assert_eq!(f, Field::Uuid(Uuid::parse_str(uuid).unwrap()));
+
+    let f = run_fct(
+        "SELECT CAST(field AS TIME) FROM users",
+        Schema::default()
+            .field(
+                FieldDefinition::new(
+                    String::from("field"),
+                    FieldType::String,
+                    false,
+                    SourceDefinition::Dynamic,
+                ),
+                false,
+            )
+            .clone(),
+        vec![Field::String("10:30:15.5".to_string())],
+    );
+    assert_eq!(
+        f,
+        Field::Time(NaiveTime::from_hms_milli_opt(10, 30, 15, 500).unwrap())
+    );
+
+    let f = run_fct(
+        "SELECT CAST(field AS ENUM('small', 'large')) FROM users",
+        Schema::default()
+            .field(
+                FieldDefinition::new(
+                    String::from("field"),
+                    FieldType::String,
+                    false,
+                    SourceDefinition::Dynamic,
+                ),
+                false,
+            )
+            .clone(),
+        vec![Field::String("large".to_string())],
+    );
+    assert_eq!(f, Field::String("large".to_string()));
+}
+
+#[test]
+#[should_panic]
+fn test_enum_unknown_value() {
+    run_fct(
+        "SELECT CAST(field AS ENUM('small', 'large')) FROM users",
+        Schema::default()
+            .field(
+                FieldDefinition::new(
+                    String::from("field"),
+                    FieldType::String,
+                    false,
+                    SourceDefinition::Dynamic,
+                ),
+                false,
+            )
+            .clone(),
+        vec![Field::String("medium".to_string())],
+    );
+}
@@ -180,3 +180,213 @@ fn test_now() {
     );
     assert!(f.to_timestamp().is_some())
 }
+
+fn ts_and_date_schema() -> Schema {
+    Schema::default()
+        .field(
+            FieldDefinition::new(
+                String::from("ts"),
+                FieldType::Timestamp,
+                false,
+                SourceDefinition::Dynamic,
+            ),
+            false,
+        )
+        .field(
+            FieldDefinition::new(
+                String::from("d"),
+                FieldType::Date,
+                false,
+                SourceDefinition::Dynamic,
+            ),
+            false,
+        )
+        .clone()
+}
+
+fn run_datetime_fct(sql: &str) -> Field {
+    run_fct(
+        sql,
+        ts_and_date_schema(),
+        vec![
+            Field::Timestamp(
+                DateTime::parse_from_rfc3339("2023-05-17T13:45:30.250+02:00").unwrap(),
+            ),
+            Field::Date(NaiveDate::from_ymd_opt(2024, 1, 31).unwrap()),
+        ],
+    )
+}
+
+fn timestamp(value: &str) -> Field {
+    Field::Timestamp(DateTime::parse_from_rfc3339(value).unwrap())
+}
+
+fn date(year: i32, month: u32, day: u32) -> Field {
+    Field::Date(NaiveDate::from_ymd_opt(year, month, day).unwrap())
+}
+
+#[test]
+fn test_date_trunc() {
+    let cases = vec![
+        ("second", timestamp("2023-05-17T13:45:30+02:00")),
+        ("minute", timestamp("2023-05-17T13:45:00+02:00")),
+        ("hour", timestamp("2023-05-17T13:00:00+02:00")),
+        ("day", timestamp("2023-05-17T00:00:00+02:00")),
+        ("week", timestamp("2023-05-15T00:00:00+02:00")),
+        ("month", timestamp("2023-05-01T00:00:00+02:00")),
+        ("quarter", timestamp("2023-04-01T00:00:00+02:00")),
+        ("year", timestamp("2023-01-01T00:00:00+02:00")),
+        ("century", timestamp("2001-01-01T00:00:00+02:00")),
+    ];
+    for (unit, expected) in cases {
+        assert_eq!(
+            run_datetime_fct(&format!("SELECT DATE_TRUNC('{unit}', ts) FROM users")),
+            expected,
+            "{unit}"
+        );
+    }
+    assert_eq!(
+        run_datetime_fct("SELECT DATE_TRUNC('month', d) FROM users"),
+        date(2024, 1, 1)
+    );
+}
+
+#[test]
+fn test_date_add_sub() {
+    assert_eq!(
+        run_datetime_fct("SELECT DATE_ADD(ts, INTERVAL '30' SECOND) FROM users"),
+        timestamp("2023-05-17T13:46:00.250+02:00")
+    );
+    assert_eq!(
+        run_datetime_fct("SELECT DATE_SUB(ts, INTERVAL '1' DAY) FROM users"),
+        timestamp("2023-05-16T13:45:30.250+02:00")
+    );
+    assert_eq!(
+        run_datetime_fct("SELECT DATE_ADD('hour', 12, ts) FROM users"),
+        timestamp("2023-05-18T01:45:30.250+02:00")
+    );
+    // Months are clamped to the end of shorter months.
+    assert_eq!(
+        run_datetime_fct("SELECT DATE_ADD('month', 1, d) FROM users"),
+        date(2024, 2, 29)
+    );
+    assert_eq!(
+        run_datetime_fct("SELECT DATE_SUB('year', 2, d) FROM users"),
+        date(2022, 1, 31)
+    );
+}
+
+#[test]
+fn test_datediff() {
+    assert_eq!(
+        run_datetime_fct("SELECT DATEDIFF(d, ts) FROM users"),
+        Field::Int(259)
+    );
+    assert_eq!(
+        run_datetime_fct("SELECT DATEDIFF('month', ts, d) FROM users"),
+        Field::Int(8)
+    );
+    assert_eq!(
+        run_datetime_fct("SELECT DATEDIFF('year', d, ts) FROM users"),
+        Field::Int(-1)
+    );
+    assert_eq!(
+        run_datetime_fct("SELECT DATEDIFF('hour', ts, DATE_ADD('minute', 20, ts)) FROM users"),
+        Field::Int(1)
+    );
+}
+
+#[test]
+fn test_to_timestamp_and_to_date() {
+    assert_eq!(
+        run_datetime_fct("SELECT TO_TIMESTAMP('17/05/2023 13:45', '%d/%m/%Y %H:%M') FROM users"),
+        timestamp("2023-05-17T13:45:00Z")
+    );
+    assert_eq!(
+        run_datetime_fct(
+            "SELECT TO_TIMESTAMP('2023-05-17 13:45:30+0200', '%Y-%m-%d %H:%M:%S%z') FROM users"
+        ),
+        timestamp("2023-05-17T13:45:30+02:00")
+    );
+    assert_eq!(
+        run_datetime_fct("SELECT TO_TIMESTAMP('2023-05-17 13:45:30') FROM users"),
+        timestamp("2023-05-17T13:45:30Z")
+    );
+    assert_eq!(
+        run_datetime_fct("SELECT TO_DATE('May 17, 2023', '%B %d, %Y') FROM users"),
+        date(2023, 5, 17)
+    );
+    assert_eq!(
+        run_datetime_fct("SELECT TO_DATE(ts) FROM users"),
+        date(2023, 5, 17)
+    );
+    assert_eq!(
+        run_datetime_fct("SELECT FROM_UNIXTIME(1684324800) FROM users"),
+        timestamp("2023-05-17T12:00:00Z")
+    );
+    assert_eq!(
+        run_datetime_fct("SELECT FROM_UNIXTIME(1684324800.5) FROM users"),
+        timestamp("2023-05-17T12:00:00.5Z")
+    );
+}
+
+#[test]
+fn test_at_time_zone() {
+    let f = run_datetime_fct("SELECT ts AT TIME ZONE 'America/New_York' FROM users");
+    assert_eq!(f, timestamp("2023-05-17T07:45:30.250-04:00"));
+    // The instant doesn't change, only its offset.
+    assert_eq!(
+        f.to_timestamp().unwrap().to_rfc3339(),
+        "2023-05-17T07:45:30.250-04:00"
+    );
+    assert_eq!(
+        run_datetime_fct("SELECT d AT TIME ZONE 'Asia/Kolkata' FROM users"),
+        timestamp("2024-01-31T05:30:00+05:30")
+    );
+    assert_eq!(
+        run_datetime_fct("SELECT ts AT TIME ZONE '+08:00' FROM users"),
+        timestamp("2023-05-17T19:45:30.250+08:00")
+    );
+}
+
+#[test]
+fn test_make_date_and_last_day() {
+    assert_eq!(
+        run_datetime_fct("SELECT MAKE_DATE(2023, 2, 14) FROM users"),
+        date(2023, 2, 14)
+    );
+    assert_eq!(
+        run_datetime_fct("SELECT LAST_DAY(ts) FROM users"),
+        date(2023, 5, 31)
+    );
+    assert_eq!(
+        run_datetime_fct("SELECT LAST_DAY(DATE_ADD('month', 1, d)) FROM users"),
+        date(2024, 2, 29)
+    );
+}
+
+#[test]
+fn test_date_duration_arithmetic() {
+    assert_eq!(
+        run_datetime_fct("SELECT d + INTERVAL '6' SECOND FROM users"),
+        timestamp("2024-01-31T00:00:06Z")
+    );
+    assert_eq!(
+        run_datetime_fct("SELECT d - MAKE_DATE(2024, 1, 1) FROM users"),
+        Field::Duration(DozerDuration(
+            std::time::Duration::from_secs(30 * 24 * 60 * 60),
+            TimeUnit::Nanoseconds
+        ))
+    );
+    assert_eq!(
+        run_datetime_fct("SELECT INTERVAL '90' SECOND * 2 FROM users"),
+        Field::Duration(DozerDuration(
+            std::time::Duration::from_secs(180),
+            TimeUnit::Nanoseconds
+        ))
+    );
+    assert_eq!(
+        run_datetime_fct("SELECT ts + INTERVAL '1' DAY / 4 FROM users"),
+        timestamp("2023-05-17T19:45:30.250+02:00")
+    );
+}
@@ -479,3 +479,92 @@ fn test_to_char() {
     );
     assert_eq!(f, Field::String("%H:%M".to_string()));
 }
+
+fn run_name_fct(sql: &str, name: &str) -> Field {
+    run_fct(
+        sql,
+        Schema::default()
+            .field(
+                FieldDefinition::new(
+                    String::from("name"),
+                    FieldType::String,
+                    false,
+                    SourceDefinition::Dynamic,
+                ),
+                false,
+            )
+            .clone(),
+        vec![Field::String(name.to_string())],
+    )
+}
+
+#[test]
+fn test_regexp() {
+    let f = run_name_fct("SELECT REGEXP_LIKE(name, '^j.*n$', 'i') FROM users", "John");
+    assert_eq!(f, Field::Boolean(true));
+
+    let f = run_name_fct(
+        "SELECT REGEXP_REPLACE(name, '([A-Za-z]+) ([A-Za-z]+)', '\\2, \\1') FROM users",
+        "John Doe",
+    );
+    assert_eq!(f, Field::String("Doe, John".to_string()));
+
+    let f = run_name_fct(
+        "SELECT REGEXP_EXTRACT(name, '([a-z]+)@([a-z.]+)', 2) FROM users",
+        "mail: john@example.com",
+    );
+    assert_eq!(f, Field::String("example.com".to_string()));
+
+    let f = run_name_fct("SELECT REGEXP_EXTRACT(name, '[0-9]+') FROM users", "John");
+    assert_eq!(f, Field::Null);
+}
+
+#[test]
+fn test_ilike_and_similar_to() {
+    let f = run_name_fct("SELECT name ILIKE 'j%' FROM users", "John");
+    assert_eq!(f, Field::Boolean(true));
+
+    let f = run_name_fct("SELECT name SIMILAR TO '(J|D)%n' FROM users", "John");
+    assert_eq!(f, Field::Boolean(true));
+
+    let f = run_name_fct("SELECT name NOT SIMILAR TO '%(a|e)%' FROM users", "John");
+    assert_eq!(f, Field::Boolean(true));
+}
+
+#[test]
+fn test_string_functions() {
+    let cases = [
+        ("SELECT SPLIT_PART(name, '.', 2) FROM users", "a.b.c", "b"),
+        ("SELECT SPLIT_PART(name, '.', -1) FROM users", "a.b.c", "c"),
+        ("SELECT LOWER(name) FROM users", "John", "john"),
+        ("SELECT UPPER(name) FROM users", "John", "JOHN"),
+        ("SELECT LPAD(name, 6, '*') FROM users", "John", "**John"),
+        ("SELECT RPAD(name, 3) FROM users", "John", "Joh"),
+        ("SELECT LEFT(name, 2) FROM users", "John", "Jo"),
+        ("SELECT RIGHT(name, -1) FROM users", "John", "ohn"),
+        ("SELECT REVERSE(name) FROM users", "John", "nhoJ"),
+        ("SELECT INITCAP(name) FROM users", "jOHN doe", "John Doe"),
+        (
+            "SELECT MD5(name) FROM users",
+            "John",
+            "61409aa1fd47d4a5332de23cbf59a36f",
+        ),
+        (
+            "SELECT SHA256(name) FROM users",
+            "",
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+        ),
+    ];
+    for (sql, name, expected) in cases {
+        assert_eq!(
+            run_name_fct(sql, name),
+            Field::String(expected.to_string()),
+            "{sql}"
+        );
+    }
+
+    let f = run_name_fct("SELECT POSITION('h' IN name) FROM users", "John");
+    assert_eq!(f, Field::UInt(3));
+    let f = run_name_fct("SELECT STRPOS(name, 'x') FROM users", "John");
+    assert_eq!(f, Field::UInt(0));
+}
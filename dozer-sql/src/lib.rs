@@ -5,10 +5,14 @@ mod expression;
 mod planner;
 mod product;
 mod projection;
+mod recursive;
 mod selection;
 mod table_operator;
+mod top_n;
+mod unnest;
 mod utils;
 mod window;
+mod window_function;
 
 pub use dozer_sql_expression::sqlparser;
 
@@ -1,11 +1,15 @@
 #![allow(dead_code)]
 use std::sync::Arc;
 
-use crate::builder::string_from_sql_object_name;
-use crate::errors::PipelineError;
+use crate::aggregation::processor::GroupingSet;
+use crate::builder::{string_from_sql_object_name, visit_expr};
+use crate::errors::{PipelineError, UnsupportedSqlError};
+use dozer_sql_expression::aggregate::AggregateFunctionType;
 use dozer_sql_expression::builder::ExpressionBuilder;
 use dozer_sql_expression::execution::Expression;
-use dozer_sql_expression::sqlparser::ast::{Expr, Ident, SelectItem};
+use dozer_sql_expression::sqlparser::ast::{
+    Expr, Function, FunctionArg, FunctionArgExpr, Ident, SelectItem, Value,
+};
 use dozer_types::models::udf_config::UdfConfig;
 use dozer_types::types::{FieldDefinition, Schema};
 use tokio::runtime::Runtime;
@@ -26,6 +30,8 @@ pub struct CommonPlanner<'a> {
     pub having: Option<Expression>,
     pub groupby: Vec<Expression>,
     pub projection_output: Vec<Expression>,
+    /// The grouping sets of `GROUPING SETS`, `ROLLUP` or `CUBE`, empty for a plain `GROUP BY`.
+    pub grouping_sets: Vec<GroupingSet>,
     pub udfs: &'a [UdfConfig],
     pub runtime: Arc<Runtime>,
 }
@@ -48,8 +54,8 @@ impl<'a> CommonPlanner<'_> {
         Ok(())
     }
 
-    async fn add_select_item(&mut self, item: SelectItem) -> Result<(), PipelineError> {
-        let expr_items: Vec<(Expr, Option<String>)> = match item {
+    fn get_select_item_exprs(&self, item: SelectItem) -> Vec<(Expr, Option<String>)> {
+        match item {
             SelectItem::UnnamedExpr(expr) => vec![(expr, None)],
             SelectItem::ExprWithAlias { expr, alias } => vec![(expr, Some(alias.value))],
             SelectItem::QualifiedWildcard(alias, _) => self
@@ -73,9 +79,11 @@ impl<'a> CommonPlanner<'_> {
                 .iter()
                 .map(|col| (Expr::Identifier(Ident::new(col.to_owned().name)), None))
                 .collect(),
-        };
+        }
+    }
 
-        for (expr, alias) in expr_items {
+    async fn add_select_item(&mut self, item: SelectItem) -> Result<(), PipelineError> {
+        for (expr, alias) in self.get_select_item_exprs(item) {
             let mut builder = ExpressionBuilder::new(
                 self.input_schema.fields.len() + self.aggregation_output.len(),
                 self.runtime.clone(),
@@ -155,20 +163,20 @@ impl<'a> CommonPlanner<'_> {
             .build(true, &expr, &self.input_schema, self.udfs)
             .await?;
 
-        let mut post_aggregation_schema = self.input_schema.clone();
-        let mut aggregation_output = Vec::new();
-
-        for new_aggr in builder.aggregations {
+        // Aggregations that are only in `HAVING` are added after the ones of the projection.
+        for new_aggr in builder
+            .aggregations
+            .into_iter()
+            .skip(self.aggregation_output.len())
+        {
             Self::append_to_schema(
                 &new_aggr,
                 None,
                 &self.input_schema,
-                &mut post_aggregation_schema,
+                &mut self.post_aggregation_schema,
             )?;
-            aggregation_output.push(new_aggr);
+            self.aggregation_output.push(new_aggr);
         }
-        self.aggregation_output = aggregation_output;
-        self.post_aggregation_schema = post_aggregation_schema;
 
         self.having = Some(having_expression);
 
@@ -208,21 +216,145 @@ impl<'a> CommonPlanner<'_> {
         Ok(())
     }
 
+    /// Builds an expression over the input record followed by the existing aggregations.
+    async fn build_post_aggregation_expression(
+        &self,
+        expr: &Expr,
+    ) -> Result<Expression, PipelineError> {
+        let mut builder = ExpressionBuilder::from(
+            self.input_schema.fields.len(),
+            self.aggregation_output.clone(),
+            self.runtime.clone(),
+        );
+        Ok(builder
+            .build(true, expr, &self.input_schema, self.udfs)
+            .await?)
+    }
+
+    /// Plans the projections and `HAVING` condition of every grouping set.
+    async fn add_grouping_sets(
+        &mut self,
+        projection: &[SelectItem],
+        having: Option<&Expr>,
+        dimensions: &[Expr],
+        grouping_sets: Vec<Vec<usize>>,
+    ) -> Result<(), PipelineError> {
+        for grouping_set in grouping_sets {
+            let grouped = (0..dimensions.len())
+                .map(|index| grouping_set.contains(&index))
+                .collect::<Vec<_>>();
+
+            let mut projections = vec![];
+            for item in projection {
+                for (expr, _) in self.get_select_item_exprs(item.clone()) {
+                    let expr = replace_grouping(expr, dimensions, &grouped)?;
+                    projections.push(self.build_post_aggregation_expression(&expr).await?);
+                }
+            }
+            let having = match having {
+                Some(having) => {
+                    let having = replace_grouping(having.clone(), dimensions, &grouped)?;
+                    Some(self.build_post_aggregation_expression(&having).await?)
+                }
+                None => None,
+            };
+
+            // Columns are null in the grouping sets that don't group by them.
+            for (field, (projection, full_projection)) in self
+                .post_projection_schema
+                .fields
+                .iter_mut()
+                .zip(projections.iter().zip(&self.projection_output))
+            {
+                if projection != full_projection {
+                    field.nullable = true;
+                }
+            }
+
+            self.grouping_sets.push(GroupingSet {
+                dimensions: grouping_set,
+                projections,
+                having,
+            });
+        }
+
+        // The rows of different grouping sets can have the same values.
+        self.post_projection_schema.primary_index.clear();
+        Ok(())
+    }
+
+    /// Replaces the select item aliases in `HAVING` with their expressions, unless they're also input columns.
+    fn replace_having_aliases(
+        &self,
+        mut having: Expr,
+        projection: &[SelectItem],
+    ) -> Result<Expr, PipelineError> {
+        visit_expr(&mut having, &mut |expr| {
+            let Expr::Identifier(ident) = expr else {
+                return Ok(true);
+            };
+            if self
+                .input_schema
+                .fields
+                .iter()
+                .any(|field| field.name == ident.value)
+            {
+                return Ok(false);
+            }
+            if let Some(aliased) = projection.iter().find_map(|item| match item {
+                SelectItem::ExprWithAlias { expr, alias } if alias.value == ident.value => {
+                    Some(expr)
+                }
+                _ => None,
+            }) {
+                *expr = aliased.clone();
+            }
+            Ok(false)
+        })?;
+        Ok(having)
+    }
+
     pub async fn plan(
         &mut self,
         projection: Vec<SelectItem>,
         group_by: Vec<Expr>,
         having: Option<Expr>,
     ) -> Result<(), PipelineError> {
-        for expr in projection {
-            self.add_select_item(expr).await?;
+        let (dimensions, grouping_sets) = expand_grouping_sets(group_by);
+        let grouped_by_all = vec![true; dimensions.len()];
+        let having = having
+            .map(|having| self.replace_having_aliases(having, &projection))
+            .transpose()?;
+
+        for item in projection.clone() {
+            let item = match item {
+                SelectItem::UnnamedExpr(expr) => {
+                    SelectItem::UnnamedExpr(replace_grouping(expr, &dimensions, &grouped_by_all)?)
+                }
+                SelectItem::ExprWithAlias { expr, alias } => SelectItem::ExprWithAlias {
+                    expr: replace_grouping(expr, &dimensions, &grouped_by_all)?,
+                    alias,
+                },
+                item => item,
+            };
+            self.add_select_item(item).await?;
         }
-        if !group_by.is_empty() {
-            self.add_groupby_items(group_by).await?;
+        if !dimensions.is_empty() {
+            self.add_groupby_items(dimensions.clone()).await?;
+        }
+
+        if let Some(having) = &having {
+            self.add_having_item(replace_grouping(
+                having.clone(),
+                &dimensions,
+                &grouped_by_all,
+            )?)
+            .await?;
         }
 
-        if let Some(having) = having {
-            self.add_having_item(having).await?;
+        if grouping_sets.len() > 1 {
+            self.add_grouping_sets(&projection, having.as_ref(), &dimensions, grouping_sets)
+                .await?;
         }
 
         Ok(())
@@ -241,8 +373,124 @@ impl<'a> CommonPlanner<'_> {
             having: None,
             groupby: Vec::new(),
             projection_output: Vec::new(),
+            grouping_sets: Vec::new(),
             udfs,
             runtime,
         }
     }
 }
+
+/// Expands `GROUPING SETS`, `ROLLUP` and `CUBE` into the distinct grouping expressions,
+/// and the grouping sets as the indexes of the expressions they group by.
+///
+/// Several `GROUP BY` items group by every combination of their grouping sets.
+fn expand_grouping_sets(group_by: Vec<Expr>) -> (Vec<Expr>, Vec<Vec<usize>>) {
+    let mut grouping_sets: Vec<Vec<Expr>> = vec![vec![]];
+    for item in group_by {
+        let item_sets = match item {
+            Expr::GroupingSets(sets) => sets,
+            Expr::Rollup(items) => (0..=items.len())
+                .rev()
+                .map(|len| items[..len].concat())
+                .collect(),
+            Expr::Cube(items) => (0..1_usize << items.len())
+                .rev()
+                .map(|mask| {
+                    items
+                        .iter()
+                        .enumerate()
+                        .filter(|(index, _)| mask & (1 << (items.len() - 1 - index)) != 0)
+                        .flat_map(|(_, exprs)| exprs.clone())
+                        .collect()
+                })
+                .collect(),
+            expr => vec![vec![expr]],
+        };
+        grouping_sets = grouping_sets
+            .iter()
+            .flat_map(|set| {
+                item_sets
+                    .iter()
+                    .map(move |item_set| [set.clone(), item_set.clone()].concat())
+            })
+            .collect();
+    }
+
+    let mut dimensions: Vec<Expr> = vec![];
+    let grouping_sets = grouping_sets
+        .into_iter()
+        .map(|set| {
+            let mut indexes = vec![];
+            for expr in set {
+                let index = match dimensions.iter().position(|dimension| dimension == &expr) {
+                    Some(index) => index,
+                    None => {
+                        dimensions.push(expr);
+                        dimensions.len() - 1
+                    }
+                };
+                if !indexes.contains(&index) {
+                    indexes.push(index);
+                }
+            }
+            indexes.sort();
+            indexes
+        })
+        .collect();
+    (dimensions, grouping_sets)
+}
+
+/// Replaces `GROUPING(..)` with its value in a grouping set, and the dimensions the grouping set doesn't group by with nulls.
+///
+/// `grouped` tells which of the `dimensions` the grouping set groups by. The arguments of aggregations are left as they are.
+fn replace_grouping(
+    mut expr: Expr,
+    dimensions: &[Expr],
+    grouped: &[bool],
+) -> Result<Expr, PipelineError> {
+    visit_expr(&mut expr, &mut |expr| {
+        if let Expr::Function(function) = expr {
+            let name = function.name.to_string().to_lowercase();
+            if name == "grouping" && function.over.is_none() {
+                let value = get_grouping_value(function, dimensions, grouped)?;
+                *expr = Expr::Value(Value::Number(value.to_string(), false));
+                return Ok(false);
+            }
+            if AggregateFunctionType::new(&name).is_some() {
+                return Ok(false);
+            }
+        }
+        match dimensions.iter().position(|dimension| dimension == expr) {
+            Some(index) => {
+                if !grouped[index] {
+                    *expr = Expr::Value(Value::Null);
+                }
+                Ok(false)
+            }
+            None => Ok(true),
+        }
+    })?;
+    Ok(expr)
+}
+
+/// `GROUPING(a, b, ..)` has a bit for each argument, from the most significant one, that's set if the grouping set doesn't group by it.
+fn get_grouping_value(
+    function: &Function,
+    dimensions: &[Expr],
+    grouped: &[bool],
+) -> Result<u64, PipelineError> {
+    let mut value = 0;
+    for arg in &function.args {
+        let index = match arg {
+            FunctionArg::Unnamed(FunctionArgExpr::Expr(arg)) => {
+                dimensions.iter().position(|dimension| dimension == arg)
+            }
+            _ => None,
+        }
+        .ok_or_else(|| {
+            PipelineError::UnsupportedSqlError(UnsupportedSqlError::Grouping(function.to_string()))
+        })?;
+        value = value << 1 | u64::from(!grouped[index]);
+    }
+    Ok(value)
+}
@@ -129,3 +129,79 @@ fn test_basic_projection() {
         })
     );
 }
+
+#[test]
+fn test_grouping_sets_projection() {
+    let sql = "SELECT a, b, COUNT(a), GROUPING(a, b) FROM t0 GROUP BY CUBE(a, b)";
+    let schema = Schema::default()
+        .field(
+            FieldDefinition::new(
+                "a".to_string(),
+                FieldType::Int,
+                false,
+                SourceDefinition::Dynamic,
+            ),
+            false,
+        )
+        .field(
+            FieldDefinition::new(
+                "b".to_string(),
+                FieldType::Int,
+                false,
+                SourceDefinition::Dynamic,
+            ),
+            false,
+        )
+        .to_owned();
+
+    let runtime = create_test_runtime();
+    let mut projection_planner = CommonPlanner::new(schema, &[], runtime.clone());
+    let statement = get_select(sql).unwrap();
+
+    runtime
+        .block_on(projection_planner.plan(
+            statement.projection,
+            statement.group_by,
+            statement.having,
+        ))
+        .unwrap();
+
+    assert_eq!(
+        projection_planner.groupby,
+        vec![
+            Expression::Column { index: 0 },
+            Expression::Column { index: 1 }
+        ]
+    );
+    let null = || Expression::Literal(Field::Null);
+    let a = || Expression::Column { index: 0 };
+    let b = || Expression::Column { index: 1 };
+    let count = || Expression::Column { index: 2 };
+    let grouping = |value| Expression::Literal(Field::Int(value));
+    assert_eq!(
+        projection_planner
+            .grouping_sets
+            .into_iter()
+            .map(|grouping_set| (grouping_set.dimensions, grouping_set.projections))
+            .collect::<Vec<_>>(),
+        vec![
+            (vec![0, 1], vec![a(), b(), count(), grouping(0)]),
+            (vec![0], vec![a(), null(), count(), grouping(1)]),
+            (vec![1], vec![null(), b(), count(), grouping(2)]),
+            (vec![], vec![null(), null(), count(), grouping(3)]),
+        ]
+    );
+
+    // Dimensions are null in the grouping sets that don't group by them.
+    let nullable = projection_planner
+        .post_projection_schema
+        .fields
+        .iter()
+        .map(|field| field.nullable)
+        .collect::<Vec<_>>();
+    assert_eq!(nullable, vec![true, true, false, true]);
+    assert!(projection_planner
+        .post_projection_schema
+        .primary_index
+        .is_empty());
+}
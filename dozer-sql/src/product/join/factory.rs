@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, sync::Arc};
 
 use dozer_core::{
     event::EventHub,
@@ -8,23 +8,25 @@ use dozer_core::{
 use dozer_sql_expression::{
     builder::{ExpressionBuilder, NameOrAlias},
     sqlparser::ast::{
-        BinaryOperator, Expr as SqlExpr, Ident, JoinConstraint as SqlJoinConstraint,
-        JoinOperator as SqlJoinOperator,
+        BinaryOperator, Expr as SqlExpr, FunctionArg, FunctionArgExpr, Ident,
+        JoinConstraint as SqlJoinConstraint, JoinOperator as SqlJoinOperator,
     },
 };
 
 use dozer_types::{
     errors::internal::BoxedError,
+    models::{flags::JoinStateBackend, udf_config::UdfConfig},
     tonic::async_trait,
     types::{FieldDefinition, Schema},
 };
+use tokio::runtime::Runtime;
 
 use crate::errors::JoinError;
 use crate::errors::PipelineError;
 use dozer_sql_expression::builder::extend_schema_source_def;
 
 use super::{
-    operator::{JoinOperator, JoinType},
+    operator::{as_of::AsOf, JoinOperator, JoinType},
     processor::ProductProcessor,
 };
 
@@ -38,15 +40,24 @@ pub struct JoinProcessorFactory {
     right: Option<NameOrAlias>,
     join_operator: SqlJoinOperator,
     enable_probabilistic_optimizations: bool,
+    state_backend: JoinStateBackend,
+    udfs: Vec<UdfConfig>,
+    runtime: Arc<Runtime>,
+    /// Expressions on the joined records that must not be nullable.
+    non_nullable: Vec<SqlExpr>,
 }
 
 impl JoinProcessorFactory {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         id: String,
         left: Option<NameOrAlias>,
         right: Option<NameOrAlias>,
         join_operator: SqlJoinOperator,
         enable_probabilistic_optimizations: bool,
+        state_backend: JoinStateBackend,
+        udfs: Vec<UdfConfig>,
+        runtime: Arc<Runtime>,
     ) -> Self {
         Self {
             id,
@@ -54,8 +65,35 @@ impl JoinProcessorFactory {
             right,
             join_operator,
             enable_probabilistic_optimizations,
+            state_backend,
+            udfs,
+            runtime,
+            non_nullable: vec![],
         }
     }
+
+    /// Fails the build if any of `non_nullable` is nullable, for joins that don't handle `NULL`s like SQL does.
+    pub fn with_non_nullable(mut self, non_nullable: Vec<SqlExpr>) -> Self {
+        self.non_nullable = non_nullable;
+        self
+    }
+
+    /// The join type and its constraint, which is `None` for a `CROSS JOIN`.
+    fn get_join_type(&self) -> Result<(JoinType, Option<&SqlJoinConstraint>), JoinError> {
+        let (join_type, constraint) = match &self.join_operator {
+            SqlJoinOperator::Inner(constraint) => (JoinType::Inner, constraint),
+            SqlJoinOperator::LeftOuter(constraint) => (JoinType::LeftOuter, constraint),
+            SqlJoinOperator::RightOuter(constraint) => (JoinType::RightOuter, constraint),
+            SqlJoinOperator::FullOuter(constraint) => (JoinType::FullOuter, constraint),
+            SqlJoinOperator::LeftSemi(constraint) => (JoinType::LeftSemi, constraint),
+            SqlJoinOperator::LeftAnti(constraint) => (JoinType::LeftAnti, constraint),
+            SqlJoinOperator::RightSemi(constraint) => (JoinType::RightSemi, constraint),
+            SqlJoinOperator::RightAnti(constraint) => (JoinType::RightAnti, constraint),
+            SqlJoinOperator::CrossJoin => return Ok((JoinType::Inner, None)),
+            _ => return Err(JoinError::UnsupportedJoinType),
+        };
+        Ok((join_type, Some(constraint)))
+    }
 }
 
 #[async_trait]
@@ -102,7 +140,13 @@ impl ProcessorFactory for JoinProcessorFactory {
             right_schema = extend_schema_source_def(&right_schema, right_table_name);
         }
 
-        let output_schema = append_schema(&left_schema, &right_schema);
+        // Semi and anti joins only filter the records of one side.
+        let (join_type, _) = self.get_join_type().map_err(PipelineError::JoinError)?;
+        let output_schema = match join_type {
+            JoinType::LeftSemi | JoinType::LeftAnti => left_schema,
+            JoinType::RightSemi | JoinType::RightAnti => right_schema,
+            _ => append_schema(&left_schema, &right_schema),
+        };
 
         Ok(output_schema)
     }
@@ -112,22 +156,10 @@ impl ProcessorFactory for JoinProcessorFactory {
         input_schemas: HashMap<PortHandle, dozer_types::types::Schema>,
         _output_schemas: HashMap<PortHandle, dozer_types::types::Schema>,
         _event_hub: EventHub,
+        checkpoint_data: Option<Vec<u8>>,
     ) -> Result<Box<dyn Processor>, BoxedError> {
-        let (join_type, join_constraint) = match &self.join_operator {
-            SqlJoinOperator::Inner(constraint) => (JoinType::Inner, constraint),
-            SqlJoinOperator::LeftOuter(constraint) => (JoinType::LeftOuter, constraint),
-            SqlJoinOperator::RightOuter(constraint) => (JoinType::RightOuter, constraint),
-            _ => return Err(PipelineError::JoinError(JoinError::UnsupportedJoinType).into()),
-        };
-
-        let expression = match join_constraint {
-            SqlJoinConstraint::On(expression) => expression,
-            _ => {
-                return Err(
-                    PipelineError::JoinError(JoinError::UnsupportedJoinConstraintType).into(),
-                )
-            }
-        };
+        let (join_type, join_constraint) =
+            self.get_join_type().map_err(PipelineError::JoinError)?;
 
         let mut left_schema = input_schemas
             .get(&LEFT_JOIN_PORT)
@@ -149,15 +181,59 @@ impl ProcessorFactory for JoinProcessorFactory {
             right_schema = extend_schema_source_def(&right_schema, right_table_name);
         }
 
-        let (left_join_key_indexes, right_join_key_indexes) =
-            parse_join_constraint(expression, &left_schema, &right_schema)?;
+        let (join_keys, residual, as_of) = match join_constraint {
+            Some(SqlJoinConstraint::On(expression)) => {
+                split_join_constraint(expression, &left_schema, &right_schema)
+                    .map_err(PipelineError::JoinError)?
+            }
+            // Every left record matches every right record.
+            Some(SqlJoinConstraint::None) | None => ((vec![], vec![]), None, None),
+            _ => {
+                return Err(
+                    PipelineError::JoinError(JoinError::UnsupportedJoinConstraintType).into(),
+                )
+            }
+        };
+        let schema = append_schema(&left_schema, &right_schema);
+        for expr in &self.non_nullable {
+            let expression = ExpressionBuilder::new(schema.fields.len(), self.runtime.clone())
+                .build(false, expr, &schema, &self.udfs)
+                .await?;
+            if expression
+                .get_type(&schema)
+                .map_err(JoinError::Expression)?
+                .nullable
+            {
+                return Err(
+                    PipelineError::JoinError(JoinError::NullableNotIn(expr.to_string())).into(),
+                );
+            }
+        }
+        let residual = match residual {
+            Some(residual) => Some(
+                ExpressionBuilder::new(schema.fields.len(), self.runtime.clone())
+                    .build(false, &residual, &schema, &self.udfs)
+                    .await?,
+            ),
+            None => None,
+        };
+        if as_of.is_some() && !matches!(join_type, JoinType::Inner | JoinType::LeftOuter) {
+            return Err(PipelineError::JoinError(JoinError::UnsupportedAsOfJoinType).into());
+        }
 
-        let join_operator = JoinOperator::new(
+        let mut join_operator = JoinOperator::new(
+            &self.id,
             join_type,
-            (left_join_key_indexes, right_join_key_indexes),
+            join_keys,
+            residual,
+            as_of,
             (&left_schema, &right_schema),
             self.enable_probabilistic_optimizations,
+            &self.state_backend,
         )?;
+        if let Some(checkpoint_data) = checkpoint_data {
+            join_operator.restore_state(&checkpoint_data)?;
+        }
 
         Ok(Box::new(ProductProcessor::new(
             self.id.clone(),
@@ -190,7 +266,126 @@ fn append_schema(left_schema: &Schema, right_schema: &Schema) -> Schema {
     output_schema
 }
 
-fn parse_join_constraint(
+/// The left and the right column indexes of a join key.
+type JoinKeyIndexes = (Vec<usize>, Vec<usize>);
+
+/// Splits an `ON` condition into the equalities of a left and a right column, which make up the join key,
+/// the `AS_OF(left_time, right_version)` condition of a temporal join,
+/// and the other conditions, which are evaluated on the records with matching keys.
+fn split_join_constraint(
+    expression: &SqlExpr,
+    left_join_table: &Schema,
+    right_join_table: &Schema,
+) -> Result<(JoinKeyIndexes, Option<SqlExpr>, Option<AsOf>), JoinError> {
+    let mut conditions = vec![];
+    collect_conjuncts(expression, &mut conditions);
+
+    let mut left_keys = vec![];
+    let mut right_keys = vec![];
+    let mut residual: Option<SqlExpr> = None;
+    let mut as_of = None;
+    for condition in conditions {
+        if let Some(condition_as_of) =
+            parse_as_of_condition(condition, left_join_table, right_join_table)?
+        {
+            if as_of.replace(condition_as_of).is_some() {
+                return Err(JoinError::InvalidAsOfCondition(condition.to_string()));
+            }
+            continue;
+        }
+        if let SqlExpr::BinaryOp {
+            op: BinaryOperator::Eq,
+            ..
+        } = condition
+        {
+            if let Ok((left, right)) =
+                parse_join_constraint(condition, left_join_table, right_join_table)
+            {
+                if let ([left], [right]) = (left.as_slice(), right.as_slice()) {
+                    left_keys.push(*left);
+                    right_keys.push(*right);
+                    continue;
+                }
+            }
+        }
+        residual = Some(match residual {
+            Some(residual) => SqlExpr::BinaryOp {
+                left: Box::new(residual),
+                op: BinaryOperator::And,
+                right: Box::new(condition.clone()),
+            },
+            None => condition.clone(),
+        });
+    }
+    Ok(((left_keys, right_keys), residual, as_of))
+}
+
+/// Parses `AS_OF(left_time, right_version)`, or returns `None` if `condition` isn't an `AS_OF` call.
+///
+/// Standard SQL puts the time on the right table, as in `JOIN rates FOR SYSTEM_TIME AS OF orders.time ON ..`,
+/// but our SQL parser doesn't parse `FOR SYSTEM_TIME AS OF` on a table, so the time is a condition of the join instead:
+/// `JOIN rates ON orders.currency = rates.currency AND AS_OF(orders.time, rates.valid_from)`.
+/// Sources have no system time periods either, so the condition also names the column a right version is valid from.
+fn parse_as_of_condition(
+    condition: &SqlExpr,
+    left_join_table: &Schema,
+    right_join_table: &Schema,
+) -> Result<Option<AsOf>, JoinError> {
+    let SqlExpr::Function(function) = condition else {
+        return Ok(None);
+    };
+    if !function.name.to_string().eq_ignore_ascii_case("as_of") {
+        return Ok(None);
+    }
+    let invalid = || JoinError::InvalidAsOfCondition(condition.to_string());
+
+    let mut indexes = vec![];
+    for arg in &function.args {
+        let FunctionArg::Unnamed(FunctionArgExpr::Expr(expr)) = arg else {
+            return Err(invalid());
+        };
+        let ident = match expr {
+            SqlExpr::Identifier(ident) => vec![ident.clone()],
+            SqlExpr::CompoundIdentifier(ident) => ident.clone(),
+            _ => return Err(invalid()),
+        };
+        indexes.push(parse_identifier(&ident, left_join_table, right_join_table)?);
+    }
+    match indexes.as_slice() {
+        [(Some(left_time_index), None), (None, Some(right_version_index))] => Ok(Some(AsOf {
+            left_time_index: *left_time_index,
+            right_version_index: *right_version_index,
+        })),
+        _ => Err(invalid()),
+    }
+}
+
+fn collect_conjuncts<'a>(expression: &'a SqlExpr, conditions: &mut Vec<&'a SqlExpr>) {
+    match expression {
+        SqlExpr::BinaryOp {
+            left,
+            op: BinaryOperator::And,
+            right,
+        } => {
+            collect_conjuncts(left, conditions);
+            collect_conjuncts(right, conditions);
+        }
+        SqlExpr::Nested(nested)
+            if matches!(
+                **nested,
+                SqlExpr::BinaryOp {
+                    op: BinaryOperator::And,
+                    ..
+                }
+            ) =>
+        {
+            collect_conjuncts(nested, conditions)
+        }
+        _ => conditions.push(expression),
+    }
+}
+
+pub(crate) fn parse_join_constraint(
     expression: &dozer_sql_expression::sqlparser::ast::Expr,
     left_join_table: &Schema,
     right_join_table: &Schema,
@@ -0,0 +1,44 @@
+use dozer_types::types::Record;
+
+/// Matches each left record against the version of the right record that's valid at the left record's time,
+/// which is the right record with the same join key and the latest version not after that time.
+/// Records with a `NULL` time or version never match.
+///
+/// Every version is kept until it expires, and so is every left record, because a new version can change its match.
+/// TTLs on both inputs bound the state.
+///
+/// So the right history goes back as far as the right input's `TTL`, and without one it's never dropped.
+/// An expired version is dropped without retracting the records joined with it,
+/// and a left record arriving after that only matches the latest version up to its time that's still kept, if any.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AsOf {
+    /// Index of the event time in the left records.
+    pub left_time_index: usize,
+    /// Index of the time a version is valid from in the right records.
+    pub right_version_index: usize,
+}
+
+/// The version that's valid out of `versions`, which are valid from the same time.
+///
+/// Ties are broken by the record values, so the match doesn't depend on arrival order.
+pub fn latest_version<'a>(versions: impl IntoIterator<Item = &'a Record>) -> Option<&'a Record> {
+    versions.into_iter().max_by(|a, b| a.values.cmp(&b.values))
+}
+
+#[cfg(test)]
+mod tests {
+    use dozer_types::types::Field;
+
+    use super::*;
+
+    #[test]
+    fn test_latest_version() {
+        let versions = [
+            Record::new(vec![Field::Int(2), Field::Int(10)]),
+            Record::new(vec![Field::Int(1), Field::Int(10)]),
+        ];
+        assert_eq!(latest_version(&versions), Some(&versions[0]));
+        assert_eq!(latest_version(versions.iter().rev()), Some(&versions[0]));
+        assert_eq!(latest_version(&[]), None);
+    }
+}
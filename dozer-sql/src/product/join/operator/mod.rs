@@ -1,7 +1,14 @@
-use dozer_types::types::{Record, Schema, Timestamp};
+use std::ops::Bound;
+
+use dozer_core::checkpoint::{deserialize_bincode, serialize_bincode_into, StateWriter};
+use dozer_sql_expression::execution::Expression;
+use dozer_types::models::flags::JoinStateBackend;
+use dozer_types::types::{Field, Record, Schema, Timestamp};
 
 use crate::errors::JoinError;
 
+use self::as_of::{latest_version, AsOf};
+use self::record_map::JoinRecordMapEnum;
 use self::table::{JoinKey, JoinTable};
 
 use super::JoinResult;
@@ -17,6 +24,38 @@ pub enum JoinType {
     Inner,
     LeftOuter,
     RightOuter,
+    FullOuter,
+    /// Left records that have a matching right record, without the right columns.
+    LeftSemi,
+    /// Left records that don't have a matching right record, without the right columns.
+    LeftAnti,
+    /// Right records that have a matching left record, without the left columns.
+    RightSemi,
+    /// Right records that don't have a matching left record, without the left columns.
+    RightAnti,
+}
+
+impl JoinType {
+    /// Whether records of `branch` are output even if they have no match.
+    fn is_outer(&self, branch: JoinBranch) -> bool {
+        matches!(
+            (self, branch),
+            (JoinType::LeftOuter | JoinType::FullOuter, JoinBranch::Left)
+                | (
+                    JoinType::RightOuter | JoinType::FullOuter,
+                    JoinBranch::Right
+                )
+        )
+    }
+}
+
+impl JoinBranch {
+    fn other(self) -> Self {
+        match self {
+            JoinBranch::Left => JoinBranch::Right,
+            JoinBranch::Right => JoinBranch::Left,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -25,137 +64,420 @@ pub enum JoinAction {
     Delete,
 }
 
+impl JoinAction {
+    fn reverse(self) -> Self {
+        match self {
+            JoinAction::Insert => JoinAction::Delete,
+            JoinAction::Delete => JoinAction::Insert,
+        }
+    }
+}
+
+pub mod as_of;
+mod record_map;
 mod table;
 
-#[derive(Debug, Clone)]
+/// The part of the join condition that isn't an equality of the join keys.
+#[derive(Debug)]
+struct Residual {
+    expression: Expression,
+    /// Schema of a left record followed by a right record.
+    schema: Schema,
+}
+
+impl Residual {
+    fn is_satisfied(
+        &mut self,
+        record: &Record,
+        record_branch: JoinBranch,
+        other: &Record,
+    ) -> JoinResult<bool> {
+        let (left, right) = match record_branch {
+            JoinBranch::Left => (record, other),
+            JoinBranch::Right => (other, record),
+        };
+        let joined = Record::new([left.values(), right.values()].concat());
+        Ok(self.expression.evaluate(&joined, &self.schema)? == Field::Boolean(true))
+    }
+}
+
+#[derive(Debug)]
 pub struct JoinOperator {
     join_type: JoinType,
 
     left: JoinTable,
     right: JoinTable,
+    residual: Option<Residual>,
+    as_of: Option<AsOf>,
 }
 
 impl JoinOperator {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
+        id: &str,
         join_type: JoinType,
         (left_join_key_indexes, right_join_key_indexes): (Vec<usize>, Vec<usize>),
+        residual: Option<Expression>,
+        as_of: Option<AsOf>,
         (left_schema, right_schema): (&Schema, &Schema),
         enable_probabilistic_optimizations: bool,
+        state_backend: &JoinStateBackend,
     ) -> Result<Self, JoinError> {
         let accurate_keys = !enable_probabilistic_optimizations;
-        let left = JoinTable::new(left_schema, left_join_key_indexes, accurate_keys)?;
-        let right = JoinTable::new(right_schema, right_join_key_indexes, accurate_keys)?;
+        let left = JoinTable::new(
+            left_schema,
+            left_join_key_indexes,
+            as_of.map(|as_of| as_of.left_time_index),
+            accurate_keys,
+            JoinRecordMapEnum::new(state_backend, &format!("{id}-left"))?,
+        )?;
+        let right = JoinTable::new(
+            right_schema,
+            right_join_key_indexes,
+            as_of.map(|as_of| as_of.right_version_index),
+            accurate_keys,
+            JoinRecordMapEnum::new(state_backend, &format!("{id}-right"))?,
+        )?;
+        let residual = residual.map(|expression| {
+            let mut schema = left_schema.clone();
+            schema.fields.extend(right_schema.fields.iter().cloned());
+            Residual { expression, schema }
+        });
         Ok(Self {
             join_type,
             left,
             right,
+            residual,
+            as_of,
         })
     }
 
-    fn inner_join(
-        &self,
+    /// Inner and outer joins.
+    fn outer_join(
+        &mut self,
         action: JoinAction,
         join_key: &JoinKey,
         record: &Record,
         record_branch: JoinBranch,
-        default_if_no_match: bool,
-    ) -> Vec<(JoinAction, Record)> {
-        let table = match record_branch {
-            JoinBranch::Left => &self.right,
-            JoinBranch::Right => &self.left,
+    ) -> JoinResult<Vec<(JoinAction, Record)>> {
+        let is_record_outer = self.join_type.is_outer(record_branch);
+        let is_other_outer = self.join_type.is_outer(record_branch.other());
+        let (table_of_record, table_to_match) = match record_branch {
+            JoinBranch::Left => (&mut self.left, &mut self.right),
+            JoinBranch::Right => (&mut self.right, &mut self.left),
         };
+        let residual = &mut self.residual;
         let join_records = create_join_records_fn(record, record_branch);
+        let default_record = table_of_record.default_record().clone();
+        let default_join_records = create_join_records_fn(&default_record, record_branch);
 
-        table
-            .get_matching_records(join_key, default_if_no_match)
-            .map(|matching_record| (action, join_records(matching_record)))
-            .collect()
+        // Without a residual condition, the matches of every matching record are the records with `join_key` in this record's table.
+        let shared_matching_count = if is_other_outer && residual.is_none() {
+            Some(count_matching_records(
+                table_of_record,
+                None,
+                join_key,
+                record,
+                record_branch,
+                2,
+            )?)
+        } else {
+            None
+        };
+
+        let mut has_match = false;
+        let mut output_records = vec![];
+        for matching_record in table_to_match.get_matching_records(join_key, false)? {
+            if let Some(residual) = residual.as_mut() {
+                if !residual.is_satisfied(record, record_branch, matching_record)? {
+                    continue;
+                }
+            }
+            has_match = true;
+
+            let join_record = join_records(matching_record);
+            if !is_other_outer {
+                output_records.push((action, join_record));
+                continue;
+            }
+
+            // The matching record is joined with the default record if this record is its only match:
+            // - For JoinAction::Insert, the matching record has only this record as a match iif the matching count is now 1.
+            // - For JoinAction::Delete, the matching record has no match left iif the matching count is now 0.
+            let matching_count = match shared_matching_count {
+                Some(count) => count,
+                None => count_matching_records(
+                    table_of_record,
+                    residual.as_mut(),
+                    join_key,
+                    matching_record,
+                    record_branch.other(),
+                    2,
+                )?,
+            };
+            match action {
+                JoinAction::Insert if matching_count == 1 => {
+                    // delete the default join record
+                    output_records
+                        .push((JoinAction::Delete, default_join_records(matching_record)));
+                    // insert the new join record
+                    output_records.push((JoinAction::Insert, join_record));
+                }
+                JoinAction::Delete if matching_count == 0 => {
+                    output_records.push((JoinAction::Delete, join_record));
+                    output_records
+                        .push((JoinAction::Insert, default_join_records(matching_record)));
+                }
+                _ => output_records.push((action, join_record)),
+            }
+        }
+
+        if !has_match && is_record_outer {
+            output_records.push((action, join_records(table_to_match.default_record())));
+        }
+        Ok(output_records)
     }
 
-    fn outer_join(
-        &self,
+    /// Semi and anti joins, which output records of `output_branch`.
+    fn semi_join(
+        &mut self,
         action: JoinAction,
         join_key: &JoinKey,
         record: &Record,
         record_branch: JoinBranch,
-    ) -> Vec<(JoinAction, Record)> {
-        let (table_to_match, table_of_record) = match record_branch {
-            JoinBranch::Left => (&self.right, &self.left),
-            JoinBranch::Right => (&self.left, &self.right),
+        output_branch: JoinBranch,
+        anti: bool,
+    ) -> JoinResult<Vec<(JoinAction, Record)>> {
+        let (table_of_record, table_to_match) = match record_branch {
+            JoinBranch::Left => (&mut self.left, &mut self.right),
+            JoinBranch::Right => (&mut self.right, &mut self.left),
         };
-        let join_records = create_join_records_fn(record, record_branch);
-        let default_join_records =
-            create_join_records_fn(table_of_record.default_record(), record_branch);
+        let residual = &mut self.residual;
+
+        if record_branch == output_branch {
+            let has_match = count_matching_records(
+                table_to_match,
+                residual.as_mut(),
+                join_key,
+                record,
+                record_branch,
+                1,
+            )? > 0;
+            return Ok(if has_match != anti {
+                vec![(action, record.clone())]
+            } else {
+                vec![]
+            });
+        }
 
-        // We need to query from the table where this record is from:
-        // - For JoinAction::Insert, did this join key exist before this insert? If not, we need to remove the default record.
-        // - For JoinAction::Delete, does this join key exist after this delete? If not, we need to insert the default record.
-        let need_to_act_on_default_record = match action {
+        // Output records only change when they get their first match or lose their last one.
+        let need_to_act = |matching_count| match action {
+            JoinAction::Insert => matching_count == 1,
+            JoinAction::Delete => matching_count == 0,
+        };
+        // Without a residual condition, every matching record has the same matches, so they all change or none does.
+        if residual.is_none()
+            && !need_to_act(count_matching_records(
+                table_of_record,
+                None,
+                join_key,
+                record,
+                record_branch,
+                2,
+            )?)
+        {
+            return Ok(vec![]);
+        }
+
+        let output_action = if anti { action.reverse() } else { action };
+        let mut output_records = vec![];
+        for matching_record in table_to_match.get_matching_records(join_key, false)? {
+            if let Some(residual) = residual.as_mut() {
+                if !residual.is_satisfied(record, record_branch, matching_record)?
+                    || !need_to_act(count_matching_records(
+                        table_of_record,
+                        Some(&mut *residual),
+                        join_key,
+                        matching_record,
+                        output_branch,
+                        2,
+                    )?)
+                {
+                    continue;
+                }
+            }
+            output_records.push((output_action, matching_record.clone()));
+        }
+        Ok(output_records)
+    }
+
+    /// Inner and left outer joins of each left record with the right version valid at its time.
+    ///
+    /// A right record only changes the output for the left records from its version until the next one,
+    /// so a new version doesn't retract the records joined with the previous ones.
+    fn as_of_join(
+        &mut self,
+        as_of: AsOf,
+        action: JoinAction,
+        join_key: &JoinKey,
+        record: &Record,
+        record_branch: JoinBranch,
+    ) -> JoinResult<Vec<(JoinAction, Record)>> {
+        if record_branch == JoinBranch::Left {
+            let time = &record.values[as_of.left_time_index];
+            let version = if *time == Field::Null {
+                None
+            } else {
+                self.get_version(join_key, Bound::Included(time))?
+            };
+            let output_record = self.as_of_join_record(record, version.as_ref())?;
+            return Ok(output_record
+                .map(|record| (action, record))
+                .into_iter()
+                .collect());
+        }
+
+        let valid_from = &record.values[as_of.right_version_index];
+        if *valid_from == Field::Null {
+            return Ok(vec![]);
+        }
+        let next_version = self
+            .right
+            .get_ordered_records(join_key)
+            .and_then(|versions| {
+                versions
+                    .range((Bound::Excluded(valid_from), Bound::Unbounded))
+                    .next()
+            })
+            .map(|(next_version, _)| next_version.clone());
+        let upper_bound = next_version
+            .as_ref()
+            .map_or(Bound::Unbounded, Bound::Excluded);
+        let left_times = self
+            .left
+            .get_ordered_records(join_key)
+            .map(|times| {
+                times
+                    .range((Bound::Included(valid_from), upper_bound))
+                    .map(|(time, _)| time.clone())
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        if left_times.is_empty() {
+            return Ok(vec![]);
+        }
+
+        // The right table already reflects this operation, so undo it to get the version before.
+        let mut versions = self
+            .right
+            .get_records_at(join_key, valid_from)?
+            .into_iter()
+            .cloned()
+            .collect::<Vec<_>>();
+        let new_version = latest_version(&versions).cloned();
+        match action {
             JoinAction::Insert => {
-                // Because this record is already inserted, the join key didn't exist before this insert iif the matching count is now 1.
-                table_of_record
-                    .get_matching_records(join_key, false)
-                    .take(2)
-                    .count()
-                    == 1
+                if let Some(index) = versions.iter().position(|version| version == record) {
+                    versions.remove(index);
+                }
             }
-            JoinAction::Delete => {
-                table_of_record
-                    .get_matching_records(join_key, false)
-                    .take(1)
-                    .count()
-                    == 0
+            JoinAction::Delete => versions.push(record.clone()),
+        }
+        let old_version = latest_version(&versions).cloned();
+        let (old_version, new_version) = match (old_version, new_version) {
+            (Some(old_version), Some(new_version)) => (Some(old_version), Some(new_version)),
+            (old_version, new_version) => {
+                let previous_version = self.get_version(join_key, Bound::Excluded(valid_from))?;
+                (
+                    old_version.or_else(|| previous_version.clone()),
+                    new_version.or(previous_version),
+                )
             }
         };
+        if old_version == new_version {
+            return Ok(vec![]);
+        }
 
         let mut output_records = vec![];
-        for matching_record in table_to_match.get_matching_records(join_key, false) {
-            let join_record = join_records(matching_record);
-
-            if need_to_act_on_default_record {
-                let default_join_record = default_join_records(matching_record);
-                match action {
-                    JoinAction::Insert => {
-                        // delete the default join record
-                        output_records.push((JoinAction::Delete, default_join_record));
-                        // insert the new join record
-                        output_records.push((JoinAction::Insert, join_record));
-                    }
-                    JoinAction::Delete => {
-                        output_records.push((JoinAction::Delete, join_record));
-                        output_records.push((JoinAction::Insert, default_join_record));
-                    }
+        for time in left_times {
+            let left_records = self
+                .left
+                .get_records_at(join_key, &time)?
+                .into_iter()
+                .cloned()
+                .collect::<Vec<_>>();
+            for left_record in &left_records {
+                let old_record = self.as_of_join_record(left_record, old_version.as_ref())?;
+                let new_record = self.as_of_join_record(left_record, new_version.as_ref())?;
+                if old_record != new_record {
+                    output_records.extend(old_record.map(|record| (JoinAction::Delete, record)));
+                    output_records.extend(new_record.map(|record| (JoinAction::Insert, record)));
                 }
-            } else {
-                output_records.push((action, join_record));
             }
         }
+        Ok(output_records)
+    }
+
+    /// The right record with `join_key` and the latest version up to `time`.
+    fn get_version(
+        &mut self,
+        join_key: &JoinKey,
+        time: Bound<&Field>,
+    ) -> JoinResult<Option<Record>> {
+        let Some((valid_from, _)) = self
+            .right
+            .get_ordered_records(join_key)
+            .and_then(|versions| versions.range((Bound::Unbounded, time)).next_back())
+        else {
+            return Ok(None);
+        };
+        let valid_from = valid_from.clone();
+        let versions = self.right.get_records_at(join_key, &valid_from)?;
+        Ok(latest_version(versions).cloned())
+    }
 
-        output_records
+    /// The output record of `left_record` joined with `version`, if there's one.
+    fn as_of_join_record(
+        &mut self,
+        left_record: &Record,
+        version: Option<&Record>,
+    ) -> JoinResult<Option<Record>> {
+        let join_records = create_join_records_fn(left_record, JoinBranch::Left);
+        if let Some(version) = version {
+            let is_satisfied = match &mut self.residual {
+                Some(residual) => residual.is_satisfied(left_record, JoinBranch::Left, version)?,
+                None => true,
+            };
+            if is_satisfied {
+                return Ok(Some(join_records(version)));
+            }
+        }
+        Ok(self
+            .join_type
+            .is_outer(JoinBranch::Left)
+            .then(|| join_records(self.right.default_record())))
     }
 
     fn join(
-        &self,
+        &mut self,
         action: JoinAction,
         join_key: &JoinKey,
         record: &Record,
         record_branch: JoinBranch,
-    ) -> Vec<(JoinAction, Record)> {
-        match (&self.join_type, record_branch) {
-            (JoinType::Inner, _) => self.inner_join(action, join_key, record, record_branch, false),
-            (JoinType::LeftOuter, JoinBranch::Left) => {
-                self.inner_join(action, join_key, record, JoinBranch::Left, true)
-            }
-            (JoinType::LeftOuter, JoinBranch::Right) => {
-                self.outer_join(action, join_key, record, JoinBranch::Right)
-            }
-            (JoinType::RightOuter, JoinBranch::Left) => {
-                self.outer_join(action, join_key, record, JoinBranch::Left)
-            }
-            (JoinType::RightOuter, JoinBranch::Right) => {
-                self.inner_join(action, join_key, record, JoinBranch::Right, true)
-            }
+    ) -> JoinResult<Vec<(JoinAction, Record)>> {
+        if let Some(as_of) = self.as_of {
+            return self.as_of_join(as_of, action, join_key, record, record_branch);
         }
+        let (output_branch, anti) = match &self.join_type {
+            JoinType::LeftSemi => (JoinBranch::Left, false),
+            JoinType::LeftAnti => (JoinBranch::Left, true),
+            JoinType::RightSemi => (JoinBranch::Right, false),
+            JoinType::RightAnti => (JoinBranch::Right, true),
+            JoinType::Inner | JoinType::LeftOuter | JoinType::RightOuter | JoinType::FullOuter => {
+                return self.outer_join(action, join_key, record, record_branch)
+            }
+        };
+        self.semi_join(action, join_key, record, record_branch, output_branch, anti)
     }
 
     pub fn delete(
@@ -163,10 +485,10 @@ impl JoinOperator {
         from: JoinBranch,
         old: &Record,
         old_decoded: &Record,
-    ) -> Vec<(JoinAction, Record)> {
+    ) -> JoinResult<Vec<(JoinAction, Record)>> {
         let join_key = match from {
-            JoinBranch::Left => self.left.remove(old_decoded),
-            JoinBranch::Right => self.right.remove(old_decoded),
+            JoinBranch::Left => self.left.remove(old_decoded)?,
+            JoinBranch::Right => self.right.remove(old_decoded)?,
         };
 
         self.join(JoinAction::Delete, &join_key, old, from)
@@ -183,13 +505,56 @@ impl JoinOperator {
             JoinBranch::Right => self.right.insert(new.clone(), new_decoded)?,
         };
 
-        Ok(self.join(JoinAction::Insert, &join_key, new, from))
+        self.join(JoinAction::Insert, &join_key, new, from)
+    }
+
+    pub fn evict_index(&mut self, now: &Timestamp) -> JoinResult<()> {
+        self.left.evict_index(now)?;
+        self.right.evict_index(now)
     }
 
-    pub fn evict_index(&mut self, now: &Timestamp) {
-        self.left.evict_index(now);
-        self.right.evict_index(now);
+    pub fn serialize_state(&self, writer: &mut StateWriter) -> JoinResult<()> {
+        Ok(serialize_bincode_into(
+            (
+                self.left.serialize_state(writer.dir())?,
+                self.right.serialize_state(writer.dir())?,
+            ),
+            writer,
+        )?)
+    }
+
+    pub fn restore_state(&mut self, state: &[u8]) -> Result<(), JoinError> {
+        let (left, right): (Vec<u8>, Vec<u8>) = deserialize_bincode(state)?;
+        self.left.restore_state(&left)?;
+        self.right.restore_state(&right)?;
+        Ok(())
+    }
+}
+
+/// Counts the records of `table` with `join_key` that satisfy the residual condition together with `record`,
+/// which comes from the other branch, up to `limit`.
+fn count_matching_records(
+    table: &mut JoinTable,
+    mut residual: Option<&mut Residual>,
+    join_key: &JoinKey,
+    record: &Record,
+    record_branch: JoinBranch,
+    limit: usize,
+) -> JoinResult<usize> {
+    let mut count = 0;
+    for matching_record in table.get_matching_records(join_key, false)? {
+        if count == limit {
+            break;
+        }
+        let is_satisfied = match residual.as_mut() {
+            Some(residual) => residual.is_satisfied(record, record_branch, matching_record)?,
+            None => true,
+        };
+        if is_satisfied {
+            count += 1;
+        }
     }
+    Ok(count)
 }
 
 fn create_join_records_fn(
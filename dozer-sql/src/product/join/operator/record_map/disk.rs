@@ -0,0 +1,420 @@
+use std::{
+    collections::HashMap,
+    fs::{self, File, OpenOptions},
+    io::{self, BufWriter, ErrorKind, Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use dozer_core::checkpoint::{deserialize_bincode, serialize_bincode};
+use dozer_types::types::Record;
+use linked_hash_map::LinkedHashMap;
+
+use crate::errors::JoinError;
+
+use super::{
+    encode_into, for_each_entry, remove_record, JoinKey, JoinKeyRecords, JoinRecordMap,
+    StoredRecords,
+};
+
+/// Don't bother compacting the log file before it reaches this size.
+const MIN_COMPACTION_FILE_LEN: u64 = 64 * 1024 * 1024;
+
+/// Distinguishes the log files of join operators in the same process, which may share a name across pipelines.
+static NEXT_LOG_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Join records kept in a log-structured file, with the most recently used join keys cached in memory.
+///
+/// Records of a join key are appended to the file when they're evicted from the cache after being modified.
+/// The file is compacted when more than half of it is stale.
+#[derive(Debug)]
+pub struct DiskJoinRecordMap {
+    cache: LinkedHashMap<JoinKey, CacheEntry>,
+    cache_size: usize,
+    log: RecordLog,
+}
+
+#[derive(Debug)]
+struct CacheEntry {
+    records: JoinKeyRecords,
+    /// Whether `records` have changed since they were read from the log.
+    dirty: bool,
+}
+
+impl DiskJoinRecordMap {
+    pub fn new(dir: &Path, name: &str, cache_size: usize) -> Result<Self, JoinError> {
+        fs::create_dir_all(dir).map_err(|e| JoinError::StateFile(dir.to_path_buf(), e))?;
+        let path = dir.join(format!(
+            "join-{}-{}-{name}.log",
+            std::process::id(),
+            NEXT_LOG_ID.fetch_add(1, Ordering::Relaxed)
+        ));
+        Ok(Self {
+            cache: LinkedHashMap::new(),
+            cache_size: cache_size.max(1),
+            log: RecordLog::new(path)?,
+        })
+    }
+
+    /// Makes sure `join_key` is cached if it exists, and marks it as the most recently used.
+    fn load(&mut self, join_key: &JoinKey) -> Result<Option<&mut CacheEntry>, JoinError> {
+        if !self.cache.contains_key(join_key) {
+            let Some(records) = self.log.get(join_key)? else {
+                return Ok(None);
+            };
+            self.insert_into_cache(
+                join_key.clone(),
+                CacheEntry {
+                    records,
+                    dirty: false,
+                },
+            )?;
+        }
+        Ok(self.cache.get_refresh(join_key))
+    }
+
+    fn insert_into_cache(&mut self, join_key: JoinKey, entry: CacheEntry) -> Result<(), JoinError> {
+        while self.cache.len() >= self.cache_size {
+            let (evicted_key, evicted_entry) =
+                self.cache.pop_front().expect("cache size is at least 1");
+            if evicted_entry.dirty {
+                self.log.put(evicted_key, &evicted_entry.records)?;
+            }
+        }
+        self.cache.insert(join_key, entry);
+        Ok(())
+    }
+}
+
+impl JoinRecordMap for DiskJoinRecordMap {
+    fn get(&mut self, join_key: &JoinKey) -> Result<Option<&JoinKeyRecords>, JoinError> {
+        Ok(self.load(join_key)?.map(|entry| &entry.records))
+    }
+
+    fn insert(
+        &mut self,
+        join_key: &JoinKey,
+        primary_key: u64,
+        record: Record,
+    ) -> Result<(), JoinError> {
+        match self.load(join_key)? {
+            Some(entry) => {
+                entry.records.entry(primary_key).or_default().push(record);
+                entry.dirty = true;
+            }
+            None => {
+                let records = [(primary_key, vec![record])].into_iter().collect();
+                self.insert_into_cache(
+                    join_key.clone(),
+                    CacheEntry {
+                        records,
+                        dirty: true,
+                    },
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    fn remove(&mut self, join_key: &JoinKey, primary_key: u64) -> Result<(), JoinError> {
+        let Some(entry) = self.load(join_key)? else {
+            return Ok(());
+        };
+        remove_record(&mut entry.records, primary_key);
+        entry.dirty = true;
+        if entry.records.is_empty() {
+            self.cache.remove(join_key);
+            self.log.delete(join_key);
+        }
+        Ok(())
+    }
+
+    fn serialize(&self, checkpoint_dir: &Path) -> Result<Vec<u8>, JoinError> {
+        // Only the cached records are encoded, the logged records are referenced in a snapshot of the log.
+        let mut inline = vec![];
+        encode_into(&mut inline, self.cache.len() as u64)?;
+        for (join_key, entry) in self.cache.iter() {
+            encode_into(&mut inline, (join_key, &entry.records))?;
+        }
+        let locations = self
+            .log
+            .index
+            .iter()
+            .filter(|(join_key, _)| !self.cache.contains_key(join_key))
+            .map(|(join_key, location)| (join_key.clone(), *location))
+            .collect::<Vec<_>>();
+        let log = if locations.is_empty() {
+            None
+        } else {
+            Some(StoredLog {
+                path: self.log.snapshot(checkpoint_dir)?,
+                locations,
+            })
+        };
+        Ok(serialize_bincode(StoredRecords { inline, log })?)
+    }
+
+    fn restore(&mut self, state: &[u8]) -> Result<(), JoinError> {
+        let stored: StoredRecords = deserialize_bincode(state)?;
+        if let Some(log) = stored.log {
+            self.log.restore(log)?;
+        }
+        for_each_entry(&stored.inline, |join_key, records| {
+            self.log.put(join_key, &records)
+        })
+    }
+}
+
+/// A snapshot of a [`RecordLog`] in a checkpoint, and where the records of each join key are in it.
+#[derive(Debug, bincode::Encode, bincode::Decode)]
+pub(super) struct StoredLog {
+    path: PathBuf,
+    locations: Vec<(JoinKey, Location)>,
+}
+
+impl StoredLog {
+    /// Calls `f` on the records of every join key in the snapshot.
+    pub(super) fn for_each_entry(
+        self,
+        mut f: impl FnMut(JoinKey, JoinKeyRecords) -> Result<(), JoinError>,
+    ) -> Result<(), JoinError> {
+        let file =
+            File::open(&self.path).map_err(|e| JoinError::StateFile(self.path.clone(), e))?;
+        for (join_key, location) in self.locations {
+            let data = read_location(&file, location)
+                .map_err(|e| JoinError::StateFile(self.path.clone(), e))?;
+            f(join_key, deserialize_bincode(&data)?)?;
+        }
+        Ok(())
+    }
+}
+
+/// Where the records of a join key are in the log file.
+#[derive(Debug, Clone, Copy, bincode::Encode, bincode::Decode)]
+struct Location {
+    offset: u64,
+    len: u64,
+}
+
+/// An append-only file of encoded [`JoinKeyRecords`], and an index of the latest records of every join key.
+#[derive(Debug)]
+struct RecordLog {
+    path: PathBuf,
+    file: File,
+    file_len: u64,
+    /// Total length of the records that are still referenced by `index`.
+    live_len: u64,
+    index: HashMap<JoinKey, Location>,
+}
+
+impl RecordLog {
+    fn new(path: PathBuf) -> Result<Self, JoinError> {
+        // The log only spills state of the running pipeline. Anything left from a previous run is stale.
+        match fs::remove_file(&path) {
+            Ok(()) => {}
+            Err(e) if e.kind() == ErrorKind::NotFound => {}
+            Err(e) => return Err(JoinError::StateFile(path, e)),
+        }
+        let file = open_log_file(&path).map_err(|e| JoinError::StateFile(path.clone(), e))?;
+        Ok(Self {
+            path,
+            file,
+            file_len: 0,
+            live_len: 0,
+            index: HashMap::new(),
+        })
+    }
+
+    fn get(&self, join_key: &JoinKey) -> Result<Option<JoinKeyRecords>, JoinError> {
+        let Some(location) = self.index.get(join_key) else {
+            return Ok(None);
+        };
+        let data = self.read(*location)?;
+        Ok(Some(deserialize_bincode(&data)?))
+    }
+
+    fn put(&mut self, join_key: JoinKey, records: &JoinKeyRecords) -> Result<(), JoinError> {
+        let data = serialize_bincode(records)?;
+        (&self.file)
+            .write_all(&data)
+            .map_err(|e| JoinError::StateFile(self.path.clone(), e))?;
+
+        let location = Location {
+            offset: self.file_len,
+            len: data.len() as u64,
+        };
+        self.file_len += location.len;
+        self.live_len += location.len;
+        if let Some(old_location) = self.index.insert(join_key, location) {
+            self.live_len -= old_location.len;
+        }
+
+        if self.file_len >= MIN_COMPACTION_FILE_LEN && self.live_len < self.file_len / 2 {
+            self.compact()
+                .map_err(|e| JoinError::StateFile(self.path.clone(), e))?;
+        }
+        Ok(())
+    }
+
+    fn delete(&mut self, join_key: &JoinKey) {
+        if let Some(location) = self.index.remove(join_key) {
+            self.live_len -= location.len;
+        }
+    }
+
+    fn read(&self, location: Location) -> Result<Vec<u8>, JoinError> {
+        read_location(&self.file, location).map_err(|e| JoinError::StateFile(self.path.clone(), e))
+    }
+
+    /// Links or copies the log file into `dir`, returning the path of the snapshot.
+    ///
+    /// The log is only appended to, and compaction writes a new file, so the records in the snapshot never change.
+    fn snapshot(&self, dir: &Path) -> Result<PathBuf, JoinError> {
+        let path = dir.join(self.path.file_name().expect("log path has a file name"));
+        self.file
+            .sync_data()
+            .map_err(|e| JoinError::StateFile(self.path.clone(), e))?;
+        link_or_copy(&self.path, &path).map_err(|e| JoinError::StateFile(path.clone(), e))?;
+        Ok(path)
+    }
+
+    /// Continues from a snapshot. Must be called before anything is put into the log.
+    fn restore(&mut self, log: StoredLog) -> Result<(), JoinError> {
+        debug_assert!(self.index.is_empty());
+        // The snapshot is removed with its checkpoint, so the log doesn't use it directly.
+        link_or_copy(&log.path, &self.path)
+            .and_then(|()| open_log_file(&self.path))
+            .and_then(|file| {
+                self.file_len = file.metadata()?.len();
+                self.file = file;
+                Ok(())
+            })
+            .map_err(|e| JoinError::StateFile(self.path.clone(), e))?;
+        self.live_len = log.locations.iter().map(|(_, location)| location.len).sum();
+        self.index = log.locations.into_iter().collect();
+        Ok(())
+    }
+
+    /// Rewrites the log with only the records referenced by the index.
+    fn compact(&mut self) -> io::Result<()> {
+        let temp_path = self.path.with_extension("compacting");
+        let mut writer = BufWriter::new(File::create(&temp_path)?);
+        let mut offset = 0;
+        for location in self.index.values_mut() {
+            writer.write_all(&read_location(&self.file, *location)?)?;
+            location.offset = offset;
+            offset += location.len;
+        }
+        writer.into_inner()?.sync_all()?;
+
+        fs::rename(&temp_path, &self.path)?;
+        self.file = open_log_file(&self.path)?;
+        self.file_len = offset;
+        self.live_len = offset;
+        Ok(())
+    }
+}
+
+impl Drop for RecordLog {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+fn open_log_file(path: &Path) -> io::Result<File> {
+    OpenOptions::new()
+        .read(true)
+        .append(true)
+        .create(true)
+        .open(path)
+}
+
+/// Hard links `from` to `to`, or copies it if it can't be linked, e.g. across file systems.
+fn link_or_copy(from: &Path, to: &Path) -> io::Result<()> {
+    match fs::remove_file(to) {
+        Ok(()) => {}
+        Err(e) if e.kind() == ErrorKind::NotFound => {}
+        Err(e) => return Err(e),
+    }
+    if fs::hard_link(from, to).is_err() {
+        fs::copy(from, to)?;
+    }
+    Ok(())
+}
+
+fn read_location(mut file: &File, location: Location) -> io::Result<Vec<u8>> {
+    file.seek(SeekFrom::Start(location.offset))?;
+    let mut data = vec![0; location.len as usize];
+    file.read_exact(&mut data)?;
+    Ok(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use dozer_types::types::Field;
+
+    use super::super::InMemoryJoinRecordMap;
+    use super::*;
+
+    #[test]
+    fn test_disk_join_record_map() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut map = DiskJoinRecordMap::new(dir.path(), "test", 2).unwrap();
+
+        let keys = (0..10).map(JoinKey::Hash).collect::<Vec<_>>();
+        for (i, key) in keys.iter().enumerate() {
+            let record = Record::new(vec![Field::UInt(i as u64)]);
+            map.insert(key, 0, record.clone()).unwrap();
+            map.insert(key, 1, record).unwrap();
+        }
+        // Only two join keys stay in memory.
+        assert_eq!(map.cache.len(), 2);
+        assert_eq!(map.log.index.len(), 8);
+
+        map.remove(&keys[0], 0).unwrap();
+        map.remove(&keys[1], 0).unwrap();
+        map.remove(&keys[1], 1).unwrap();
+        assert_eq!(map.get(&keys[0]).unwrap().unwrap().len(), 1);
+        assert!(map.get(&keys[1]).unwrap().is_none());
+        for (i, key) in keys.iter().enumerate().skip(2) {
+            let records = map.get(key).unwrap().unwrap();
+            assert_eq!(records[&0], vec![Record::new(vec![Field::UInt(i as u64)])]);
+            assert_eq!(records[&1], vec![Record::new(vec![Field::UInt(i as u64)])]);
+        }
+
+        let checkpoint_dir = tempfile::tempdir().unwrap();
+        let state = map.serialize(checkpoint_dir.path()).unwrap();
+        // The logged records are referenced in a snapshot of the log, not copied into the state.
+        let snapshot = checkpoint_dir
+            .path()
+            .join(map.log.path.file_name().unwrap());
+        assert_eq!(fs::metadata(&snapshot).unwrap().len(), map.log.file_len);
+
+        // Changes after the checkpoint don't change the checkpointed records.
+        for key in &keys {
+            map.insert(key, 2, Record::new(vec![Field::Null])).unwrap();
+        }
+
+        let mut restored = DiskJoinRecordMap::new(dir.path(), "test-restored", 2).unwrap();
+        restored.restore(&state).unwrap();
+        let mut in_memory = InMemoryJoinRecordMap::default();
+        in_memory.restore(&state).unwrap();
+        for restored in [&mut restored as &mut dyn JoinRecordMap, &mut in_memory] {
+            assert_eq!(restored.get(&keys[0]).unwrap().unwrap().len(), 1);
+            assert!(restored.get(&keys[1]).unwrap().is_none());
+            for (i, key) in keys.iter().enumerate().skip(2) {
+                let records = restored.get(key).unwrap().unwrap();
+                assert_eq!(records.len(), 2);
+                assert_eq!(records[&0], vec![Record::new(vec![Field::UInt(i as u64)])]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_disk_join_record_maps_with_same_name_use_different_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let map1 = DiskJoinRecordMap::new(dir.path(), "test", 2).unwrap();
+        let map2 = DiskJoinRecordMap::new(dir.path(), "test", 2).unwrap();
+        assert_ne!(map1.log.path, map2.log.path);
+    }
+}
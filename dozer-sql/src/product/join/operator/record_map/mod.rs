@@ -0,0 +1,172 @@
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    path::{Path, PathBuf},
+};
+
+use dozer_core::checkpoint::{deserialize_bincode, serialize_bincode};
+use dozer_types::{
+    bincode::{self, config},
+    errors::types::{DeserializationError, SerializationError},
+    models::flags::{default_join_state_cache_size, JoinStateBackend},
+    types::Record,
+};
+use enum_dispatch::enum_dispatch;
+
+use crate::errors::JoinError;
+
+use super::table::JoinKey;
+
+use disk::StoredLog;
+
+pub use disk::DiskJoinRecordMap;
+
+/// Records of a join key, grouped by the hash of their primary key.
+pub type JoinKeyRecords = HashMap<u64, Vec<Record>>;
+
+#[enum_dispatch(JoinRecordMap)]
+#[derive(Debug)]
+pub enum JoinRecordMapEnum {
+    InMemoryJoinRecordMap,
+    DiskJoinRecordMap,
+}
+
+impl JoinRecordMapEnum {
+    /// Creates the record map of a join input. `name` must be unique among the join inputs of this process.
+    pub fn new(backend: &JoinStateBackend, name: &str) -> Result<Self, JoinError> {
+        Ok(match backend {
+            JoinStateBackend::Memory => InMemoryJoinRecordMap::default().into(),
+            JoinStateBackend::Disk(options) => {
+                let dir = options
+                    .dir
+                    .as_ref()
+                    .map_or_else(std::env::temp_dir, PathBuf::from);
+                let cache_size = options
+                    .cache_size
+                    .unwrap_or_else(default_join_state_cache_size);
+                DiskJoinRecordMap::new(&dir, name, cache_size)?.into()
+            }
+        })
+    }
+}
+
+#[enum_dispatch]
+pub trait JoinRecordMap {
+    /// Returns the records with `join_key`, or `None` if there's no such record.
+    fn get(&mut self, join_key: &JoinKey) -> Result<Option<&JoinKeyRecords>, JoinError>;
+
+    /// Adds `record` under `join_key` and `primary_key`.
+    fn insert(
+        &mut self,
+        join_key: &JoinKey,
+        primary_key: u64,
+        record: Record,
+    ) -> Result<(), JoinError>;
+
+    /// Removes the last record added under `join_key` and `primary_key`, if any.
+    fn remove(&mut self, join_key: &JoinKey, primary_key: u64) -> Result<(), JoinError>;
+
+    /// Serializes all the records. Records that are already in a file are referenced rather than copied,
+    /// and the file is linked or copied into `checkpoint_dir`.
+    fn serialize(&self, checkpoint_dir: &Path) -> Result<Vec<u8>, JoinError>;
+
+    /// Adds all the records from the output of [`JoinRecordMap::serialize`] of either backend.
+    fn restore(&mut self, state: &[u8]) -> Result<(), JoinError>;
+}
+
+/// Serialized records of a [`JoinRecordMap`].
+#[derive(Debug, bincode::Encode, bincode::Decode)]
+struct StoredRecords {
+    /// Records encoded as a `HashMap<JoinKey, JoinKeyRecords>`.
+    inline: Vec<u8>,
+    /// Records referenced in a log file.
+    log: Option<StoredLog>,
+}
+
+#[derive(Debug, Default)]
+pub struct InMemoryJoinRecordMap {
+    map: HashMap<JoinKey, JoinKeyRecords>,
+}
+
+impl JoinRecordMap for InMemoryJoinRecordMap {
+    fn get(&mut self, join_key: &JoinKey) -> Result<Option<&JoinKeyRecords>, JoinError> {
+        Ok(self.map.get(join_key))
+    }
+
+    fn insert(
+        &mut self,
+        join_key: &JoinKey,
+        primary_key: u64,
+        record: Record,
+    ) -> Result<(), JoinError> {
+        self.map
+            .entry(join_key.clone())
+            .or_default()
+            .entry(primary_key)
+            .or_default()
+            .push(record);
+        Ok(())
+    }
+
+    fn remove(&mut self, join_key: &JoinKey, primary_key: u64) -> Result<(), JoinError> {
+        if let Entry::Occupied(mut records) = self.map.entry(join_key.clone()) {
+            remove_record(records.get_mut(), primary_key);
+            if records.get().is_empty() {
+                records.remove();
+            }
+        }
+        Ok(())
+    }
+
+    fn serialize(&self, _checkpoint_dir: &Path) -> Result<Vec<u8>, JoinError> {
+        Ok(serialize_bincode(StoredRecords {
+            inline: serialize_bincode(&self.map)?,
+            log: None,
+        })?)
+    }
+
+    fn restore(&mut self, state: &[u8]) -> Result<(), JoinError> {
+        let stored: StoredRecords = deserialize_bincode(state)?;
+        let mut insert = |join_key, records| {
+            self.map.insert(join_key, records);
+            Ok(())
+        };
+        if let Some(log) = stored.log {
+            log.for_each_entry(&mut insert)?;
+        }
+        for_each_entry(&stored.inline, insert)
+    }
+}
+
+fn remove_record(records: &mut JoinKeyRecords, primary_key: u64) {
+    if let Entry::Occupied(mut record_vec) = records.entry(primary_key) {
+        record_vec.get_mut().pop();
+        if record_vec.get().is_empty() {
+            record_vec.remove();
+        }
+    }
+}
+
+fn encode_into(data: &mut Vec<u8>, value: impl bincode::Encode) -> Result<(), JoinError> {
+    bincode::encode_into_std_write(value, data, config::legacy())
+        .map_err(SerializationError::from)?;
+    Ok(())
+}
+
+/// Calls `f` on every entry of an encoded `HashMap<JoinKey, JoinKeyRecords>`, without decoding the whole map at once.
+fn for_each_entry(
+    state: &[u8],
+    mut f: impl FnMut(JoinKey, JoinKeyRecords) -> Result<(), JoinError>,
+) -> Result<(), JoinError> {
+    let (num_entries, mut offset): (u64, _) =
+        bincode::decode_from_slice(state, config::legacy()).map_err(DeserializationError::from)?;
+    for _ in 0..num_entries {
+        let ((join_key, records), len) =
+            bincode::decode_from_slice(&state[offset..], config::legacy())
+                .map_err(DeserializationError::from)?;
+        offset += len;
+        f(join_key, records)?;
+    }
+    Ok(())
+}
+
+mod disk;
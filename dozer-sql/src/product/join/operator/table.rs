@@ -1,11 +1,10 @@
 use std::{
-    collections::{
-        hash_map::{self, Values},
-        HashMap,
-    },
+    collections::{hash_map::Values, BTreeMap, HashMap},
     iter::{once, Flatten, Once},
+    path::Path,
 };
 
+use dozer_core::checkpoint::{deserialize_bincode, serialize_bincode};
 use dozer_types::{
     chrono,
     types::{Field, Record, Schema, Timestamp},
@@ -17,24 +16,42 @@ use crate::{
     utils::record_hashtable_key::{get_record_hash, RecordKey},
 };
 
+use super::record_map::{JoinRecordMap, JoinRecordMapEnum};
+
 pub type JoinKey = RecordKey;
 type IndexKey = (JoinKey, u64); // (join_key, primary_key)
 
-#[derive(Debug, Clone)]
+/// Primary keys of the records with a join key, by the value of the field they're ordered by.
+pub type OrderedRecords = BTreeMap<Field, Vec<u64>>;
+
+#[derive(Debug, bincode::Encode, bincode::Decode)]
+struct EvictionInstant(#[bincode(with_serde)] Timestamp);
+
+#[derive(Debug)]
 pub struct JoinTable {
     join_key_indexes: Vec<usize>,
     primary_key_indexes: Vec<usize>,
     default_record: Record,
-    map: HashMap<JoinKey, HashMap<u64, Vec<Record>>>,
+    map: JoinRecordMapEnum,
     lifetime_map: LinkedHashMap<Timestamp, Vec<IndexKey>>,
     accurate_keys: bool,
+    /// The records ordered by a field within each join key, for AS_OF joins. Records with a `NULL` in the field are left out.
+    order: Option<RecordOrder>,
+}
+
+#[derive(Debug)]
+struct RecordOrder {
+    field_index: usize,
+    records: HashMap<JoinKey, OrderedRecords>,
 }
 
 impl JoinTable {
     pub fn new(
         schema: &Schema,
         join_key_indexes: Vec<usize>,
+        order_index: Option<usize>,
         accurate_keys: bool,
+        map: JoinRecordMapEnum,
     ) -> Result<Self, JoinError> {
         let primary_key_indexes = if schema.primary_index.is_empty() {
             (0..schema.fields.len()).collect()
@@ -46,24 +63,63 @@ impl JoinTable {
             join_key_indexes,
             primary_key_indexes,
             default_record: Record::nulls_from_schema(schema),
-            map: Default::default(),
+            map,
             lifetime_map: Default::default(),
             accurate_keys,
+            order: order_index.map(|field_index| RecordOrder {
+                field_index,
+                records: HashMap::new(),
+            }),
         })
     }
 
-    pub fn get_matching_records<'a>(
-        &'a self,
+    /// The records with `join_key` by the value of the order field, if the table is ordered.
+    pub fn get_ordered_records(&self, join_key: &JoinKey) -> Option<&OrderedRecords> {
+        self.order.as_ref()?.records.get(join_key)
+    }
+
+    /// The records with `join_key` whose order field is `value`.
+    pub fn get_records_at(
+        &mut self,
+        join_key: &JoinKey,
+        value: &Field,
+    ) -> Result<Vec<&Record>, JoinError> {
+        let Some(order) = &self.order else {
+            return Ok(vec![]);
+        };
+        let Some(primary_keys) = order
+            .records
+            .get(join_key)
+            .and_then(|records| records.get(value))
+        else {
+            return Ok(vec![]);
+        };
+        let Some(records) = self.map.get(join_key)? else {
+            return Ok(vec![]);
+        };
+        let mut primary_keys = primary_keys.clone();
+        primary_keys.sort_unstable();
+        primary_keys.dedup();
+        Ok(primary_keys
+            .iter()
+            .filter_map(|primary_key| records.get(primary_key))
+            .flatten()
+            .filter(|record| record.values[order.field_index] == *value)
+            .collect())
+    }
+
+    pub fn get_matching_records(
+        &mut self,
         join_key: &JoinKey,
         default_if_no_match: bool,
-    ) -> MatchingRecords<'a> {
-        if let Some(records_map) = self.map.get(join_key) {
+    ) -> Result<MatchingRecords<'_>, JoinError> {
+        Ok(if let Some(records_map) = self.map.get(join_key)? {
             MatchingRecords::Values(records_map.values().flatten())
         } else if default_if_no_match {
             MatchingRecords::Default(once(&self.default_record))
         } else {
             MatchingRecords::Empty
-        }
+        })
     }
 
     pub fn default_record(&self) -> &Record {
@@ -95,35 +151,37 @@ impl JoinTable {
                 .push((join_key.clone(), primary_key));
         }
 
-        self.map
-            .entry(join_key.clone())
-            .or_default()
-            .entry(primary_key)
-            .or_default()
-            .push(record);
+        if let Some(order) = &mut self.order {
+            let value = &record.values[order.field_index];
+            if *value != Field::Null {
+                order
+                    .records
+                    .entry(join_key.clone())
+                    .or_default()
+                    .entry(value.clone())
+                    .or_default()
+                    .push(primary_key);
+            }
+        }
+        self.map.insert(&join_key, primary_key, record)?;
 
         Ok(join_key)
     }
 
-    pub fn remove(&mut self, record: &Record) -> JoinKey {
+    pub fn remove(&mut self, record: &Record) -> Result<JoinKey, JoinError> {
         let join_key = self.get_join_key(record);
-        if let hash_map::Entry::Occupied(record_map) = self.map.entry(join_key.clone()) {
-            let primary_key = get_record_key_hash(record, &self.primary_key_indexes);
-            remove_record_using_primary_key(record_map, primary_key);
-        }
-        join_key
+        let primary_key = get_record_key_hash(record, &self.primary_key_indexes);
+        remove_record(&mut self.map, &mut self.order, &join_key, primary_key)?;
+        Ok(join_key)
     }
 
-    pub fn evict_index(&mut self, now: &Timestamp) {
+    pub fn evict_index(&mut self, now: &Timestamp) -> Result<(), JoinError> {
         let mut keys_to_remove = vec![];
         for (eviction_instant, join_index_keys) in self.lifetime_map.iter() {
             if eviction_instant <= now {
                 keys_to_remove.push(*eviction_instant);
                 for (join_key, primary_key) in join_index_keys {
-                    if let hash_map::Entry::Occupied(record_map) = self.map.entry(join_key.clone())
-                    {
-                        remove_record_using_primary_key(record_map, *primary_key);
-                    }
+                    remove_record(&mut self.map, &mut self.order, join_key, *primary_key)?;
                 }
             } else {
                 break;
@@ -133,6 +191,35 @@ impl JoinTable {
         for key in keys_to_remove {
             self.lifetime_map.remove(&key);
         }
+        Ok(())
+    }
+
+    /// Serializes the table. Files the state refers to are put in `checkpoint_dir`.
+    pub fn serialize_state(&self, checkpoint_dir: &Path) -> Result<Vec<u8>, JoinError> {
+        let lifetime_map = self
+            .lifetime_map
+            .iter()
+            .map(|(eviction_instant, keys)| (EvictionInstant(*eviction_instant), keys.clone()))
+            .collect::<Vec<_>>();
+        let order = self.order.as_ref().map(|order| &order.records);
+        Ok(serialize_bincode((
+            self.map.serialize(checkpoint_dir)?,
+            lifetime_map,
+            order,
+        ))?)
+    }
+
+    pub fn restore_state(&mut self, state: &[u8]) -> Result<(), JoinError> {
+        let (map, lifetime_map, order): StoredState = deserialize_bincode(state)?;
+        self.map.restore(&map)?;
+        self.lifetime_map = lifetime_map
+            .into_iter()
+            .map(|(eviction_instant, keys)| (eviction_instant.0, keys))
+            .collect();
+        if let (Some(order), Some(records)) = (&mut self.order, order) {
+            order.records = records;
+        }
+        Ok(())
     }
 
     fn get_join_key(&self, record: &Record) -> JoinKey {
@@ -144,6 +231,42 @@ impl JoinTable {
     }
 }
 
+type StoredState = (
+    Vec<u8>,
+    Vec<(EvictionInstant, Vec<IndexKey>)>,
+    Option<HashMap<JoinKey, OrderedRecords>>,
+);
+
+/// Removes the last record added under `join_key` and `primary_key` from `map` and from `order`.
+fn remove_record(
+    map: &mut JoinRecordMapEnum,
+    order: &mut Option<RecordOrder>,
+    join_key: &JoinKey,
+    primary_key: u64,
+) -> Result<(), JoinError> {
+    if let Some(order) = order {
+        let value = map
+            .get(join_key)?
+            .and_then(|records| records.get(&primary_key))
+            .and_then(|records| records.last())
+            .map(|record| &record.values[order.field_index]);
+        if let (Some(value), Some(ordered)) = (value, order.records.get_mut(join_key)) {
+            if let Some(primary_keys) = ordered.get_mut(value) {
+                if let Some(position) = primary_keys.iter().position(|key| *key == primary_key) {
+                    primary_keys.swap_remove(position);
+                }
+                if primary_keys.is_empty() {
+                    ordered.remove(value);
+                }
+            }
+            if ordered.is_empty() {
+                order.records.remove(join_key);
+            }
+        }
+    }
+    map.remove(join_key, primary_key)
+}
+
 #[derive(Debug)]
 pub enum MatchingRecords<'a> {
     Values(Flatten<Values<'a, u64, Vec<Record>>>),
@@ -175,28 +298,25 @@ fn get_record_key_fields(record: &Record, key_indexes: &[usize]) -> Vec<Field> {
         .collect()
 }
 
-fn remove_record_using_primary_key(
-    mut record_map: hash_map::OccupiedEntry<JoinKey, HashMap<u64, Vec<Record>>>,
-    primary_key: u64,
-) {
-    if let hash_map::Entry::Occupied(mut record_vec) = record_map.get_mut().entry(primary_key) {
-        record_vec.get_mut().pop();
-        if record_vec.get().is_empty() {
-            record_vec.remove();
-        }
-    }
-
-    if record_map.get().is_empty() {
-        record_map.remove();
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use dozer_types::types::{FieldDefinition, FieldType};
 
+    use crate::product::join::operator::record_map::InMemoryJoinRecordMap;
+
     use super::*;
 
+    fn new_table(schema: &Schema) -> JoinTable {
+        JoinTable::new(
+            schema,
+            vec![0],
+            None,
+            true,
+            InMemoryJoinRecordMap::default().into(),
+        )
+        .unwrap()
+    }
+
     #[test]
     fn test_match_insert_remove() {
         let schema = Schema {
@@ -209,19 +329,54 @@ mod tests {
             }],
             primary_index: vec![0],
         };
-        let mut table = JoinTable::new(&schema, vec![0], true).unwrap();
+        let mut table = new_table(&schema);
 
         let record = Record::new(vec![Field::Int(1)]);
         let join_key = table.get_join_key(&record);
-        assert_eq!(table.get_matching_records(&join_key, true).count(), 1);
-        assert_eq!(table.get_matching_records(&join_key, false).count(), 0);
+        let count = |table: &mut JoinTable, default_if_no_match| {
+            table
+                .get_matching_records(&join_key, default_if_no_match)
+                .unwrap()
+                .count()
+        };
+        assert_eq!(count(&mut table, true), 1);
+        assert_eq!(count(&mut table, false), 0);
+
+        table.insert(record.clone(), &record).unwrap();
+        assert_eq!(count(&mut table, true), 1);
+        assert_eq!(count(&mut table, false), 1);
+
+        table.remove(&record).unwrap();
+        assert_eq!(count(&mut table, true), 1);
+        assert_eq!(count(&mut table, false), 0);
+    }
 
+    #[test]
+    fn test_serialize_restore() {
+        let schema = Schema {
+            fields: vec![FieldDefinition {
+                name: "a".to_string(),
+                typ: FieldType::Int,
+                nullable: false,
+                source: Default::default(),
+                description: None,
+            }],
+            primary_index: vec![0],
+        };
+        let mut table = new_table(&schema);
+        let record = Record::new(vec![Field::Int(1)]);
         let join_key = table.insert(record.clone(), &record).unwrap();
-        assert_eq!(table.get_matching_records(&join_key, true).count(), 1);
-        assert_eq!(table.get_matching_records(&join_key, false).count(), 1);
 
-        let join_key = table.remove(&record);
-        assert_eq!(table.get_matching_records(&join_key, true).count(), 1);
-        assert_eq!(table.get_matching_records(&join_key, false).count(), 0);
+        let checkpoint_dir = tempfile::tempdir().unwrap();
+        let state = table.serialize_state(checkpoint_dir.path()).unwrap();
+        let mut restored = new_table(&schema);
+        restored.restore_state(&state).unwrap();
+        assert_eq!(
+            restored
+                .get_matching_records(&join_key, false)
+                .unwrap()
+                .collect::<Vec<_>>(),
+            vec![&record]
+        );
     }
 }
@@ -1,3 +1,5 @@
+use dozer_core::checkpoint::StateWriter;
+
 use dozer_core::channels::ProcessorChannelForwarder;
 use dozer_core::epoch::Epoch;
 use dozer_core::node::Processor;
@@ -19,8 +21,10 @@ impl ProductProcessor {
         Self { join_operator }
     }
 
-    fn update_eviction_index(&mut self, lifetime: Lifetime) {
-        self.join_operator.evict_index(&lifetime.reference);
+    fn update_eviction_index(&mut self, lifetime: Lifetime) -> Result<(), PipelineError> {
+        self.join_operator
+            .evict_index(&lifetime.reference)
+            .map_err(PipelineError::JoinError)
     }
 }
 
@@ -29,6 +33,10 @@ impl Processor for ProductProcessor {
         Ok(())
     }
 
+    fn serialize_state(&self, writer: &mut StateWriter) -> Result<(), BoxedError> {
+        Ok(self.join_operator.serialize_state(writer)?)
+    }
+
     fn process(
         &mut self,
         op: TableOperation,
@@ -42,14 +50,16 @@ impl Processor for ProductProcessor {
         let records = match op.op {
             Operation::Delete { old } => {
                 if let Some(lifetime) = old.get_lifetime() {
-                    self.update_eviction_index(lifetime);
+                    self.update_eviction_index(lifetime)?;
                 }
 
-                self.join_operator.delete(from_branch, &old, &old)
+                self.join_operator
+                    .delete(from_branch, &old, &old)
+                    .map_err(PipelineError::JoinError)?
             }
             Operation::Insert { new } => {
                 if let Some(lifetime) = new.get_lifetime() {
-                    self.update_eviction_index(lifetime);
+                    self.update_eviction_index(lifetime)?;
                 }
 
                 self.join_operator
@@ -58,10 +68,13 @@ impl Processor for ProductProcessor {
             }
             Operation::Update { old, new } => {
                 if let Some(lifetime) = old.get_lifetime() {
-                    self.update_eviction_index(lifetime);
+                    self.update_eviction_index(lifetime)?;
                 }
 
-                let mut old_records = self.join_operator.delete(from_branch, &old, &old);
+                let mut old_records = self
+                    .join_operator
+                    .delete(from_branch, &old, &old)
+                    .map_err(PipelineError::JoinError)?;
 
                 let new_records = self
                     .join_operator
@@ -115,13 +128,17 @@ mod tests {
     use dozer_core::{event::EventHub, node::ProcessorFactory};
     use dozer_sql_expression::builder::NameOrAlias;
     use dozer_sql_expression::sqlparser::ast::JoinOperator as SqlJoinOperator;
+    use dozer_types::models::flags::JoinStateBackend;
     use dozer_types::types::{Field, FieldDefinition, Record, Schema};
 
     use crate::product::join::{
         factory::{LEFT_JOIN_PORT, RIGHT_JOIN_PORT},
         operator::JoinType,
     };
-    use crate::{product::join::factory::JoinProcessorFactory, tests::utils::get_select};
+    use crate::{
+        product::join::factory::JoinProcessorFactory,
+        tests::utils::{create_test_runtime, get_select},
+    };
 
     use super::*;
 
@@ -178,30 +195,33 @@ mod tests {
     }
 
     impl Executor {
-        async fn new(kind: JoinType) -> Self {
-            let left_schema = create_schema("left");
-            let right_schema = create_schema("right");
+        fn new(kind: JoinType) -> Self {
+            Self::with_constraint(kind, "left.joinkey = right.joinkey")
+        }
 
-            let stmt = get_select(
-                "SELECT left.joinkey FROM left INNER JOIN right ON left.joinkey = right.joinkey",
-            )
-            .unwrap();
-            let join = &stmt.from[0].joins[0];
-            let join_op = join.join_operator.clone();
-            let SqlJoinOperator::Inner(constraint) = join_op else {
-                unreachable!()
-            };
-            let join_op = match kind {
-                JoinType::Inner => SqlJoinOperator::Inner(constraint),
-                JoinType::LeftOuter => SqlJoinOperator::LeftOuter(constraint),
-                JoinType::RightOuter => SqlJoinOperator::RightOuter(constraint),
-            };
+        fn with_constraint(kind: JoinType, constraint: &str) -> Self {
+            Self::with_operator(join_operator(kind, constraint))
+        }
+
+        fn with_operator(join_op: SqlJoinOperator) -> Self {
+            Self::with_schemas(join_op, create_schema("left"), create_schema("right"))
+        }
+
+        fn with_schemas(
+            join_op: SqlJoinOperator,
+            left_schema: Schema,
+            right_schema: Schema,
+        ) -> Self {
+            let runtime = create_test_runtime();
             let factory = JoinProcessorFactory::new(
                 "test".into(),
                 Some(NameOrAlias("left".into(), None)),
                 Some(NameOrAlias("right".into(), None)),
                 join_op,
                 false,
+                JoinStateBackend::Memory,
+                vec![],
+                runtime.clone(),
             );
 
             let schemas = [
@@ -210,9 +230,8 @@ mod tests {
             ]
             .into_iter()
             .collect();
-            let processor = factory
-                .build(schemas, HashMap::new(), EventHub::new(1))
-                .await
+            let processor = runtime
+                .block_on(factory.build(schemas, HashMap::new(), EventHub::new(1), None))
                 .unwrap();
 
             let forwarder = TestChannelForwarder { operations: vec![] };
@@ -266,15 +285,36 @@ mod tests {
         }
     }
 
+    fn join_operator(kind: JoinType, constraint: &str) -> SqlJoinOperator {
+        let stmt = get_select(&format!(
+            "SELECT left.joinkey FROM left INNER JOIN right ON {constraint}"
+        ))
+        .unwrap();
+        let join = &stmt.from[0].joins[0];
+        let SqlJoinOperator::Inner(constraint) = join.join_operator.clone() else {
+            unreachable!()
+        };
+        match kind {
+            JoinType::Inner => SqlJoinOperator::Inner(constraint),
+            JoinType::LeftOuter => SqlJoinOperator::LeftOuter(constraint),
+            JoinType::RightOuter => SqlJoinOperator::RightOuter(constraint),
+            JoinType::FullOuter => SqlJoinOperator::FullOuter(constraint),
+            JoinType::LeftSemi => SqlJoinOperator::LeftSemi(constraint),
+            JoinType::LeftAnti => SqlJoinOperator::LeftAnti(constraint),
+            JoinType::RightSemi => SqlJoinOperator::RightSemi(constraint),
+            JoinType::RightAnti => SqlJoinOperator::RightAnti(constraint),
+        }
+    }
+
     fn join_record(left: Record, right: Record) -> Record {
         let mut values = left.values;
         values.extend(right.values);
         Record::new(values)
     }
 
-    #[tokio::test]
-    async fn test_inner_join() {
-        let mut exec = Executor::new(JoinType::Inner).await;
+    #[test]
+    fn test_inner_join() {
+        let mut exec = Executor::new(JoinType::Inner);
 
         let (left_record, ops) = exec.insert(JoinSide::Left, &[Field::UInt(0), Field::UInt(1)]);
         assert_eq!(ops, &[]);
@@ -311,9 +351,9 @@ mod tests {
         );
     }
 
-    #[tokio::test]
-    async fn test_left_outer_join() {
-        let mut exec = Executor::new(JoinType::LeftOuter).await;
+    #[test]
+    fn test_left_outer_join() {
+        let mut exec = Executor::new(JoinType::LeftOuter);
 
         let null_record = Record::new(vec![Field::Null, Field::Null]);
 
@@ -375,9 +415,9 @@ mod tests {
         );
     }
 
-    #[tokio::test]
-    async fn test_right_outer_join() {
-        let mut exec = Executor::new(JoinType::RightOuter).await;
+    #[test]
+    fn test_right_outer_join() {
+        let mut exec = Executor::new(JoinType::RightOuter);
 
         let null_record = Record::new(vec![Field::Null, Field::Null]);
 
@@ -434,4 +474,304 @@ mod tests {
             },]
         );
     }
+
+    #[test]
+    fn test_left_semi_join() {
+        let mut exec = Executor::new(JoinType::LeftSemi);
+
+        let (left_record, ops) = exec.insert(JoinSide::Left, &[Field::UInt(0), Field::UInt(1)]);
+        assert_eq!(ops, &[]);
+
+        let (right_record, ops) = exec.insert(JoinSide::Right, &[Field::UInt(0), Field::UInt(2)]);
+        assert_eq!(
+            ops,
+            &[Operation::Insert {
+                new: left_record.clone()
+            }]
+        );
+        // A second match doesn't change the output.
+        let (second_right_record, ops) =
+            exec.insert(JoinSide::Right, &[Field::UInt(0), Field::UInt(3)]);
+        assert_eq!(ops, &[]);
+        assert_eq!(exec.delete(JoinSide::Right, right_record), &[]);
+
+        assert_eq!(
+            exec.delete(JoinSide::Right, second_right_record),
+            &[Operation::Delete { old: left_record }]
+        );
+    }
+
+    #[test]
+    fn test_left_anti_join() {
+        let mut exec = Executor::new(JoinType::LeftAnti);
+
+        let (left_record, ops) = exec.insert(JoinSide::Left, &[Field::UInt(0), Field::UInt(1)]);
+        assert_eq!(
+            ops,
+            &[Operation::Insert {
+                new: left_record.clone()
+            }]
+        );
+
+        let (right_record, ops) = exec.insert(JoinSide::Right, &[Field::UInt(0), Field::UInt(2)]);
+        assert_eq!(
+            ops,
+            &[Operation::Delete {
+                old: left_record.clone()
+            }]
+        );
+        let (_, ops) = exec.insert(JoinSide::Left, &[Field::UInt(0), Field::UInt(3)]);
+        assert_eq!(ops, &[]);
+
+        let ops = exec.delete(JoinSide::Right, right_record);
+        assert_eq!(ops.len(), 2);
+        assert!(ops.contains(&Operation::Insert { new: left_record }));
+    }
+
+    #[test]
+    fn test_full_outer_join() {
+        let mut exec = Executor::new(JoinType::FullOuter);
+
+        let null_record = Record::new(vec![Field::Null, Field::Null]);
+
+        let (left_record, ops) = exec.insert(JoinSide::Left, &[Field::UInt(0), Field::UInt(1)]);
+        assert_eq!(
+            ops,
+            &[Operation::Insert {
+                new: join_record(left_record.clone(), null_record.clone())
+            }]
+        );
+        let (other_right_record, ops) =
+            exec.insert(JoinSide::Right, &[Field::UInt(1), Field::UInt(1)]);
+        assert_eq!(
+            ops,
+            &[Operation::Insert {
+                new: join_record(null_record.clone(), other_right_record.clone())
+            }]
+        );
+
+        let (right_record, ops) = exec.insert(JoinSide::Right, &[Field::UInt(0), Field::UInt(2)]);
+        assert_eq!(
+            ops,
+            &[
+                Operation::Delete {
+                    old: join_record(left_record.clone(), null_record.clone()),
+                },
+                Operation::Insert {
+                    new: join_record(left_record.clone(), right_record.clone())
+                }
+            ]
+        );
+
+        assert_eq!(
+            exec.delete(JoinSide::Left, left_record.clone()),
+            &[
+                Operation::Delete {
+                    old: join_record(left_record.clone(), right_record.clone())
+                },
+                Operation::Insert {
+                    new: join_record(null_record.clone(), right_record.clone())
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cross_join() {
+        let mut exec = Executor::with_operator(SqlJoinOperator::CrossJoin);
+
+        let (left_record, ops) = exec.insert(JoinSide::Left, &[Field::UInt(0), Field::UInt(1)]);
+        assert_eq!(ops, &[]);
+        let (right_record, ops) = exec.insert(JoinSide::Right, &[Field::UInt(1), Field::UInt(2)]);
+        assert_eq!(
+            ops,
+            &[Operation::Insert {
+                new: join_record(left_record.clone(), right_record.clone())
+            }]
+        );
+        let (other_left_record, ops) =
+            exec.insert(JoinSide::Left, &[Field::UInt(2), Field::UInt(3)]);
+        assert_eq!(
+            ops,
+            &[Operation::Insert {
+                new: join_record(other_left_record, right_record)
+            }]
+        );
+    }
+
+    #[test]
+    fn test_right_semi_join() {
+        let mut exec = Executor::new(JoinType::RightSemi);
+
+        let (right_record, ops) = exec.insert(JoinSide::Right, &[Field::UInt(0), Field::UInt(2)]);
+        assert_eq!(ops, &[]);
+
+        let (left_record, ops) = exec.insert(JoinSide::Left, &[Field::UInt(0), Field::UInt(1)]);
+        assert_eq!(
+            ops,
+            &[Operation::Insert {
+                new: right_record.clone()
+            }]
+        );
+        assert_eq!(
+            exec.delete(JoinSide::Left, left_record),
+            &[Operation::Delete { old: right_record }]
+        );
+    }
+
+    #[test]
+    fn test_residual_join_condition() {
+        let mut exec = Executor::with_constraint(
+            JoinType::LeftOuter,
+            "left.joinkey = right.joinkey AND left.data < right.data",
+        );
+
+        let null_record = Record::new(vec![Field::Null, Field::Null]);
+
+        let (left_record, _) = exec.insert(JoinSide::Left, &[Field::UInt(0), Field::UInt(5)]);
+        // Same key, but the residual condition doesn't hold.
+        let (_, ops) = exec.insert(JoinSide::Right, &[Field::UInt(0), Field::UInt(3)]);
+        assert_eq!(ops, &[]);
+
+        let (right_record, ops) = exec.insert(JoinSide::Right, &[Field::UInt(0), Field::UInt(8)]);
+        assert_eq!(
+            ops,
+            &[
+                Operation::Delete {
+                    old: join_record(left_record.clone(), null_record.clone()),
+                },
+                Operation::Insert {
+                    new: join_record(left_record.clone(), right_record.clone())
+                }
+            ]
+        );
+
+        // The left record no longer satisfies the residual condition after the update.
+        let (new_left_record, ops) = exec.update(
+            JoinSide::Left,
+            left_record.clone(),
+            &[Field::UInt(0), Field::UInt(9)],
+        );
+        assert_eq!(
+            ops,
+            &[
+                Operation::Delete {
+                    old: join_record(left_record, right_record)
+                },
+                Operation::Insert {
+                    new: join_record(new_left_record, null_record)
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_residual_semi_join_condition() {
+        let mut exec = Executor::with_constraint(
+            JoinType::LeftSemi,
+            "left.joinkey = right.joinkey AND left.data < right.data",
+        );
+
+        let (low_record, _) = exec.insert(JoinSide::Left, &[Field::UInt(0), Field::UInt(1)]);
+        let (high_record, _) = exec.insert(JoinSide::Left, &[Field::UInt(0), Field::UInt(5)]);
+
+        // Only the left records satisfying the residual condition get their first match.
+        let (right_record, ops) = exec.insert(JoinSide::Right, &[Field::UInt(0), Field::UInt(3)]);
+        assert_eq!(
+            ops,
+            &[Operation::Insert {
+                new: low_record.clone()
+            }]
+        );
+        let (second_right_record, ops) =
+            exec.insert(JoinSide::Right, &[Field::UInt(0), Field::UInt(8)]);
+        assert_eq!(
+            ops,
+            &[Operation::Insert {
+                new: high_record.clone()
+            }]
+        );
+
+        assert_eq!(exec.delete(JoinSide::Right, right_record), &[]);
+        assert_eq!(
+            exec.delete(JoinSide::Right, second_right_record),
+            &[
+                Operation::Delete { old: low_record },
+                Operation::Delete { old: high_record }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_as_of_join() {
+        // The versions of a key are told apart by the time they're valid from.
+        let mut right_schema = create_schema("right");
+        right_schema.primary_index = vec![0, 1];
+        let mut exec = Executor::with_schemas(
+            join_operator(
+                JoinType::LeftOuter,
+                "left.joinkey = right.joinkey AND AS_OF(left.data, right.data)",
+            ),
+            create_schema("left"),
+            right_schema,
+        );
+
+        let null_record = Record::new(vec![Field::Null, Field::Null]);
+
+        let (version_10, ops) = exec.insert(JoinSide::Right, &[Field::UInt(0), Field::UInt(10)]);
+        assert_eq!(ops, &[]);
+
+        // There's no version yet at 5.
+        let (left_5, ops) = exec.insert(JoinSide::Left, &[Field::UInt(0), Field::UInt(5)]);
+        assert_eq!(
+            ops,
+            &[Operation::Insert {
+                new: join_record(left_5.clone(), null_record.clone())
+            }]
+        );
+        let (left_15, ops) = exec.insert(JoinSide::Left, &[Field::UInt(0), Field::UInt(15)]);
+        assert_eq!(
+            ops,
+            &[Operation::Insert {
+                new: join_record(left_15, version_10.clone())
+            }]
+        );
+
+        // A new version doesn't change the records joined with the previous one.
+        let (version_20, ops) = exec.insert(JoinSide::Right, &[Field::UInt(0), Field::UInt(20)]);
+        assert_eq!(ops, &[]);
+        let (left_25, ops) = exec.insert(JoinSide::Left, &[Field::UInt(0), Field::UInt(25)]);
+        assert_eq!(
+            ops,
+            &[Operation::Insert {
+                new: join_record(left_25.clone(), version_20.clone())
+            }]
+        );
+
+        // An earlier version only changes the records before the next one.
+        let (version_3, ops) = exec.insert(JoinSide::Right, &[Field::UInt(0), Field::UInt(3)]);
+        assert_eq!(
+            ops,
+            &[
+                Operation::Delete {
+                    old: join_record(left_5.clone(), null_record)
+                },
+                Operation::Insert {
+                    new: join_record(left_5, version_3)
+                }
+            ]
+        );
+
+        assert_eq!(
+            exec.delete(JoinSide::Right, version_20.clone()),
+            &[
+                Operation::Delete {
+                    old: join_record(left_25.clone(), version_20)
+                },
+                Operation::Insert {
+                    new: join_record(left_25, version_10)
+                }
+            ]
+        );
+    }
 }
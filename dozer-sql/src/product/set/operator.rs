@@ -10,6 +10,13 @@ pub enum SetAction {
     // Update,
 }
 
+/// The input of a set operation a record comes from.
+#[derive(Clone, Debug, PartialEq, Eq, Copy)]
+pub enum SetBranch {
+    Left,
+    Right,
+}
+
 #[derive(Clone, Debug)]
 pub struct SetOperation {
     pub op: SetOperator,
@@ -24,21 +31,79 @@ impl SetOperation {
         }
     }
 
+    /// Whether the operation counts the records of each input separately,
+    /// in `record_map` for the left input and in `right_record_map` for the right one.
+    pub fn counts_branches(&self) -> bool {
+        matches!(self.op, SetOperator::Intersect | SetOperator::Except)
+    }
+
+    /// `UNION` only uses `record_map`, which counts the records of both inputs.
     pub fn execute(
         &self,
         action: SetAction,
+        branch: SetBranch,
         record: Record,
         record_map: &mut CountingRecordMapEnum,
+        right_record_map: &mut CountingRecordMapEnum,
     ) -> Result<Vec<(SetAction, Record)>, PipelineError> {
         match (self.op, self.quantifier) {
             (SetOperator::Union, SetQuantifier::All) => Ok(vec![(action, record)]),
             (SetOperator::Union, SetQuantifier::None) => {
                 self.execute_union(action, record, record_map)
             }
+            (SetOperator::Intersect | SetOperator::Except, _) => {
+                Ok(self.execute_multiset(action, branch, record, record_map, right_record_map))
+            }
             _ => Err(PipelineError::InvalidOperandType(self.op.to_string())),
         }
     }
 
+    /// `INTERSECT` and `EXCEPT`, which output a record as many times as the counts of both inputs allow.
+    fn execute_multiset(
+        &self,
+        action: SetAction,
+        branch: SetBranch,
+        record: Record,
+        left_record_map: &mut CountingRecordMapEnum,
+        right_record_map: &mut CountingRecordMapEnum,
+    ) -> Vec<(SetAction, Record)> {
+        let old_count = self.output_count(
+            left_record_map.estimate_count(&record),
+            right_record_map.estimate_count(&record),
+        );
+        let record_map = match branch {
+            SetBranch::Left => &mut *left_record_map,
+            SetBranch::Right => &mut *right_record_map,
+        };
+        match action {
+            SetAction::Insert => record_map.insert(&record),
+            SetAction::Delete => record_map.remove(&record),
+        }
+        let new_count = self.output_count(
+            left_record_map.estimate_count(&record),
+            right_record_map.estimate_count(&record),
+        );
+
+        let (output_action, difference) = if new_count >= old_count {
+            (SetAction::Insert, new_count - old_count)
+        } else {
+            (SetAction::Delete, old_count - new_count)
+        };
+        vec![(output_action, record); difference as usize]
+    }
+
+    /// How many times a record is output, given how many times each input has it.
+    fn output_count(&self, left_count: u64, right_count: u64) -> u64 {
+        match (self.op, self.quantifier) {
+            (SetOperator::Intersect, SetQuantifier::All) => left_count.min(right_count),
+            (SetOperator::Intersect, _) => u64::from(left_count > 0 && right_count > 0),
+            (SetOperator::Except, SetQuantifier::All) => left_count.saturating_sub(right_count),
+            (SetOperator::Except, _) => u64::from(left_count > 0 && right_count == 0),
+            (SetOperator::Union, SetQuantifier::All) => left_count + right_count,
+            (SetOperator::Union, _) => u64::from(left_count + right_count > 0),
+        }
+    }
+
     fn execute_union(
         &self,
         action: SetAction,
@@ -94,3 +159,93 @@ impl SetOperation {
         record_map.estimate_count(&record)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use dozer_types::types::Field;
+
+    use super::*;
+    use crate::product::set::record_map::AccurateCountingRecordMap;
+
+    struct Executor {
+        operation: SetOperation,
+        record_map: CountingRecordMapEnum,
+        right_record_map: CountingRecordMapEnum,
+    }
+
+    impl Executor {
+        fn new(op: SetOperator, quantifier: SetQuantifier) -> Self {
+            Self {
+                operation: SetOperation { op, quantifier },
+                record_map: AccurateCountingRecordMap::new(None).unwrap().into(),
+                right_record_map: AccurateCountingRecordMap::new(None).unwrap().into(),
+            }
+        }
+
+        /// The number of inserted records minus the number of deleted records.
+        fn execute(&mut self, action: SetAction, branch: SetBranch, value: i64) -> i64 {
+            self.operation
+                .execute(
+                    action,
+                    branch,
+                    Record::new(vec![Field::Int(value)]),
+                    &mut self.record_map,
+                    &mut self.right_record_map,
+                )
+                .unwrap()
+                .into_iter()
+                .map(|(action, record)| {
+                    assert_eq!(record.values, vec![Field::Int(value)]);
+                    match action {
+                        SetAction::Insert => 1,
+                        SetAction::Delete => -1,
+                    }
+                })
+                .sum()
+        }
+    }
+
+    #[test]
+    fn test_intersect() {
+        let mut exec = Executor::new(SetOperator::Intersect, SetQuantifier::None);
+        assert_eq!(exec.execute(SetAction::Insert, SetBranch::Left, 1), 0);
+        assert_eq!(exec.execute(SetAction::Insert, SetBranch::Left, 1), 0);
+        assert_eq!(exec.execute(SetAction::Insert, SetBranch::Right, 1), 1);
+        assert_eq!(exec.execute(SetAction::Insert, SetBranch::Right, 1), 0);
+        assert_eq!(exec.execute(SetAction::Delete, SetBranch::Right, 1), 0);
+        assert_eq!(exec.execute(SetAction::Delete, SetBranch::Right, 1), -1);
+    }
+
+    #[test]
+    fn test_intersect_all() {
+        let mut exec = Executor::new(SetOperator::Intersect, SetQuantifier::All);
+        assert_eq!(exec.execute(SetAction::Insert, SetBranch::Left, 1), 0);
+        assert_eq!(exec.execute(SetAction::Insert, SetBranch::Left, 1), 0);
+        assert_eq!(exec.execute(SetAction::Insert, SetBranch::Right, 1), 1);
+        assert_eq!(exec.execute(SetAction::Insert, SetBranch::Right, 1), 1);
+        assert_eq!(exec.execute(SetAction::Insert, SetBranch::Right, 1), 0);
+        assert_eq!(exec.execute(SetAction::Delete, SetBranch::Left, 1), -1);
+    }
+
+    #[test]
+    fn test_except() {
+        let mut exec = Executor::new(SetOperator::Except, SetQuantifier::None);
+        assert_eq!(exec.execute(SetAction::Insert, SetBranch::Left, 1), 1);
+        assert_eq!(exec.execute(SetAction::Insert, SetBranch::Left, 1), 0);
+        assert_eq!(exec.execute(SetAction::Insert, SetBranch::Right, 1), -1);
+        assert_eq!(exec.execute(SetAction::Insert, SetBranch::Right, 2), 0);
+        assert_eq!(exec.execute(SetAction::Delete, SetBranch::Right, 1), 1);
+    }
+
+    #[test]
+    fn test_except_all() {
+        let mut exec = Executor::new(SetOperator::Except, SetQuantifier::All);
+        assert_eq!(exec.execute(SetAction::Insert, SetBranch::Left, 1), 1);
+        assert_eq!(exec.execute(SetAction::Insert, SetBranch::Left, 1), 1);
+        assert_eq!(exec.execute(SetAction::Insert, SetBranch::Right, 1), -1);
+        assert_eq!(exec.execute(SetAction::Insert, SetBranch::Right, 1), -1);
+        assert_eq!(exec.execute(SetAction::Insert, SetBranch::Right, 1), 0);
+        assert_eq!(exec.execute(SetAction::Insert, SetBranch::Left, 1), 0);
+        assert_eq!(exec.execute(SetAction::Delete, SetBranch::Right, 1), 1);
+    }
+}
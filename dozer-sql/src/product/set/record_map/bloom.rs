@@ -90,11 +90,20 @@ mod hash {
 
     impl Default for BloomHasher {
         fn default() -> Self {
-            // We're using fixed keys because `RandomState` cannot be serialized. These are just two random numbers.
-            const KEY1: usize = 11636376767615148353;
-            const KEY2: usize = 1474968174732524820;
-            let random_state_1 = RandomState::with_seed(KEY1);
-            let random_state_2 = RandomState::with_seed(KEY2);
+            // We're using fixed keys because `RandomState` cannot be serialized. `RandomState::with_seed` still mixes
+            // in per-process random seeds, so all four keys are given explicitly. These are just random numbers.
+            let random_state_1 = RandomState::with_seeds(
+                0x5e726f7780212c8a,
+                0x55fcefc8cb6b8459,
+                0xb6de6bfbb623550b,
+                0x8d2b7ae2638793ad,
+            );
+            let random_state_2 = RandomState::with_seeds(
+                0x68bda8d15699fb67,
+                0x4c1b46f89cc8365a,
+                0x06f5ba29029eee5d,
+                0xadcf40c71a185723,
+            );
             Self {
                 random_state_1,
                 random_state_2,
@@ -146,6 +155,20 @@ mod tests {
         }
     }
 
+    #[test]
+    fn bloom_hashes_are_stable() {
+        let hasher = hash::BloomHasher::default();
+        let hashes = hasher.calculate_hashes(&"foo", 3).collect::<Vec<_>>();
+        assert_eq!(
+            hashes,
+            vec![
+                6054357464477707951,
+                9983403437889357076,
+                13912449411301006201
+            ]
+        );
+    }
+
     #[test]
     fn test_counting_bloom_filter() {
         let mut filter = CountingBloomFilter::with_rate(0.01, 100);
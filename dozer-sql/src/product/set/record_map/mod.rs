@@ -1,5 +1,7 @@
+use dozer_core::checkpoint::{deserialize_bincode, serialize_bincode};
 use dozer_types::{
-    errors::types::DeserializationError,
+    bincode::{self, config},
+    errors::types::{DeserializationError, SerializationError},
     serde::{Deserialize, Serialize},
     types::Record,
 };
@@ -26,6 +28,9 @@ pub trait CountingRecordMap {
 
     /// Clears the map, removing all records.
     fn clear(&mut self);
+
+    /// Serializes the map, which can be restored by passing the result to the map's constructor.
+    fn serialize(&self) -> Result<Vec<u8>, SerializationError>;
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -34,10 +39,12 @@ pub struct AccurateCountingRecordMap {
 }
 
 impl AccurateCountingRecordMap {
-    pub fn new() -> Result<Self, DeserializationError> {
-        Ok(Self {
-            map: Default::default(),
-        })
+    pub fn new(checkpoint_data: Option<&[u8]>) -> Result<Self, DeserializationError> {
+        let map = match checkpoint_data {
+            Some(checkpoint_data) => deserialize_bincode(checkpoint_data)?,
+            None => Default::default(),
+        };
+        Ok(Self { map })
     }
 }
 
@@ -65,6 +72,10 @@ impl CountingRecordMap for AccurateCountingRecordMap {
     fn clear(&mut self) {
         self.map.clear();
     }
+
+    fn serialize(&self) -> Result<Vec<u8>, SerializationError> {
+        serialize_bincode(&self.map)
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -77,13 +88,17 @@ impl ProbabilisticCountingRecordMap {
     const FALSE_POSITIVE_RATE: f32 = 0.01;
     const EXPECTED_NUM_ITEMS: u32 = 10000000;
 
-    pub fn new() -> Result<Self, DeserializationError> {
-        Ok(Self {
-            map: bloom::CountingBloomFilter::with_rate(
+    pub fn new(checkpoint_data: Option<&[u8]>) -> Result<Self, DeserializationError> {
+        let map = match checkpoint_data {
+            Some(checkpoint_data) => {
+                bincode::serde::decode_from_slice(checkpoint_data, config::legacy())?.0
+            }
+            None => bloom::CountingBloomFilter::with_rate(
                 Self::FALSE_POSITIVE_RATE,
                 Self::EXPECTED_NUM_ITEMS,
             ),
-        })
+        };
+        Ok(Self { map })
     }
 }
 
@@ -103,6 +118,10 @@ impl CountingRecordMap for ProbabilisticCountingRecordMap {
     fn clear(&mut self) {
         self.map.clear();
     }
+
+    fn serialize(&self) -> Result<Vec<u8>, SerializationError> {
+        Ok(bincode::serde::encode_to_vec(&self.map, config::legacy())?)
+    }
 }
 
 mod bloom;
@@ -150,10 +169,29 @@ mod tests {
 
     #[test]
     fn test_maps() {
-        let accurate_map = AccurateCountingRecordMap::new().unwrap().into();
+        let accurate_map = AccurateCountingRecordMap::new(None).unwrap().into();
         test_map(accurate_map);
 
-        let probabilistic_map = ProbabilisticCountingRecordMap::new().unwrap().into();
+        let probabilistic_map = ProbabilisticCountingRecordMap::new(None).unwrap().into();
         test_map(probabilistic_map);
     }
+
+    #[test]
+    fn test_serialize_maps() {
+        let a = Record::new(vec![Field::String('a'.into())]);
+
+        let mut accurate_map = AccurateCountingRecordMap::new(None).unwrap();
+        accurate_map.insert(&a);
+        accurate_map.insert(&a);
+        let restored =
+            AccurateCountingRecordMap::new(Some(&accurate_map.serialize().unwrap())).unwrap();
+        assert_eq!(restored.estimate_count(&a), 2);
+
+        let mut probabilistic_map = ProbabilisticCountingRecordMap::new(None).unwrap();
+        probabilistic_map.insert(&a);
+        let restored =
+            ProbabilisticCountingRecordMap::new(Some(&probabilistic_map.serialize().unwrap()))
+                .unwrap();
+        assert_eq!(restored.estimate_count(&a), 1);
+    }
 }
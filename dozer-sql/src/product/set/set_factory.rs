@@ -19,6 +19,7 @@ use super::set_processor::SetProcessor;
 #[derive(Debug)]
 pub struct SetProcessorFactory {
     id: String,
+    op: SetOperator,
     set_quantifier: SetQuantifier,
     enable_probabilistic_optimizations: bool,
 }
@@ -27,11 +28,13 @@ impl SetProcessorFactory {
     /// Creates a new [`FromProcessorFactory`].
     pub fn new(
         id: String,
+        op: SetOperator,
         set_quantifier: SetQuantifier,
         enable_probabilistic_optimizations: bool,
     ) -> Self {
         Self {
             id,
+            op,
             set_quantifier,
             enable_probabilistic_optimizations,
         }
@@ -75,14 +78,16 @@ impl ProcessorFactory for SetProcessorFactory {
         _input_schemas: HashMap<PortHandle, Schema>,
         _output_schemas: HashMap<PortHandle, Schema>,
         _event_hub: EventHub,
+        checkpoint_data: Option<Vec<u8>>,
     ) -> Result<Box<dyn Processor>, BoxedError> {
         Ok(Box::new(SetProcessor::new(
             self.id.clone(),
             SetOperation {
-                op: SetOperator::Union,
+                op: self.op,
                 quantifier: self.set_quantifier,
             },
             self.enable_probabilistic_optimizations,
+            checkpoint_data,
         )?))
     }
 }
@@ -103,7 +108,7 @@ fn validate_set_operation_input_schemas(
         }
         output_fields.push(FieldDefinition::new(
             left.name.clone(),
-            left.typ,
+            left.typ.clone(),
             left.nullable,
             SourceDefinition::Dynamic,
         ));
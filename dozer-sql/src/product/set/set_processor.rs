@@ -1,15 +1,18 @@
-use super::operator::{SetAction, SetOperation};
+use super::operator::{SetAction, SetBranch, SetOperation};
 use super::record_map::{
-    AccurateCountingRecordMap, CountingRecordMapEnum, ProbabilisticCountingRecordMap,
+    AccurateCountingRecordMap, CountingRecordMap, CountingRecordMapEnum,
+    ProbabilisticCountingRecordMap,
 };
 use crate::errors::{PipelineError, ProductError, SetError};
 use dozer_core::channels::ProcessorChannelForwarder;
+use dozer_core::checkpoint::{deserialize_bincode, serialize_bincode_into, StateWriter};
 use dozer_core::epoch::Epoch;
 use dozer_core::node::Processor;
 use dozer_core::DEFAULT_PORT_HANDLE;
 use dozer_types::errors::internal::BoxedError;
 use dozer_types::types::{Operation, Record, TableOperation};
 use std::fmt::{Debug, Formatter};
+use std::io::Write;
 
 pub struct SetProcessor {
     _id: String,
@@ -17,6 +20,8 @@ pub struct SetProcessor {
     operator: SetOperation,
     /// Hashmap containing records with its occurrence
     record_map: CountingRecordMapEnum,
+    /// Occurrences of the right input's records, for operations that count the inputs separately
+    right_record_map: CountingRecordMapEnum,
 }
 
 impl SetProcessor {
@@ -25,52 +30,103 @@ impl SetProcessor {
         id: String,
         operator: SetOperation,
         enable_probabilistic_optimizations: bool,
+        checkpoint_data: Option<Vec<u8>>,
     ) -> Result<Self, SetError> {
+        let (checkpoint_data, right_checkpoint_data) = match checkpoint_data {
+            Some(checkpoint_data) if operator.counts_branches() => {
+                let (left, right): (Vec<u8>, Vec<u8>) = deserialize_bincode(&checkpoint_data)?;
+                (Some(left), Some(right))
+            }
+            checkpoint_data => (checkpoint_data, None),
+        };
+        let new_record_map =
+            |checkpoint_data: Option<&[u8]>| -> Result<CountingRecordMapEnum, SetError> {
+                Ok(if enable_probabilistic_optimizations {
+                    ProbabilisticCountingRecordMap::new(checkpoint_data)?.into()
+                } else {
+                    AccurateCountingRecordMap::new(checkpoint_data)?.into()
+                })
+            };
+        // Avoid allocating a bloom filter that `UNION` doesn't use.
+        let right_record_map = if operator.counts_branches() {
+            new_record_map(right_checkpoint_data.as_deref())?
+        } else {
+            AccurateCountingRecordMap::new(None)?.into()
+        };
         Ok(Self {
             _id: id,
             operator,
-            record_map: if enable_probabilistic_optimizations {
-                ProbabilisticCountingRecordMap::new()?.into()
-            } else {
-                AccurateCountingRecordMap::new()?.into()
-            },
+            record_map: new_record_map(checkpoint_data.as_deref())?,
+            right_record_map,
         })
     }
 
-    fn delete(&mut self, record: Record) -> Result<Vec<(SetAction, Record)>, ProductError> {
-        self.operator
-            .execute(SetAction::Delete, record, &mut self.record_map)
+    fn execute(
+        &mut self,
+        action: SetAction,
+        branch: SetBranch,
+        record: Record,
+    ) -> Result<Vec<(SetAction, Record)>, PipelineError> {
+        self.operator.execute(
+            action,
+            branch,
+            record,
+            &mut self.record_map,
+            &mut self.right_record_map,
+        )
+    }
+
+    fn delete(
+        &mut self,
+        branch: SetBranch,
+        record: Record,
+    ) -> Result<Vec<(SetAction, Record)>, ProductError> {
+        self.execute(SetAction::Delete, branch, record)
             .map_err(|err| {
-                ProductError::DeleteError("UNION query error:".to_string(), Box::new(err))
+                ProductError::DeleteError(
+                    format!("{} query error:", self.operator.op),
+                    Box::new(err),
+                )
             })
     }
 
-    fn insert(&mut self, record: Record) -> Result<Vec<(SetAction, Record)>, ProductError> {
-        self.operator
-            .execute(SetAction::Insert, record, &mut self.record_map)
+    fn insert(
+        &mut self,
+        branch: SetBranch,
+        record: Record,
+    ) -> Result<Vec<(SetAction, Record)>, ProductError> {
+        self.execute(SetAction::Insert, branch, record)
             .map_err(|err| {
-                ProductError::InsertError("UNION query error:".to_string(), Box::new(err))
+                ProductError::InsertError(
+                    format!("{} query error:", self.operator.op),
+                    Box::new(err),
+                )
             })
     }
 
     #[allow(clippy::type_complexity)]
     fn update(
         &mut self,
+        branch: SetBranch,
         old: Record,
         new: Record,
     ) -> Result<(Vec<(SetAction, Record)>, Vec<(SetAction, Record)>), ProductError> {
         let old_records = self
-            .operator
-            .execute(SetAction::Delete, old, &mut self.record_map)
+            .execute(SetAction::Delete, branch, old)
             .map_err(|err| {
-                ProductError::UpdateOldError("UNION query error:".to_string(), Box::new(err))
+                ProductError::UpdateOldError(
+                    format!("{} query error:", self.operator.op),
+                    Box::new(err),
+                )
             })?;
 
         let new_records = self
-            .operator
-            .execute(SetAction::Insert, new, &mut self.record_map)
+            .execute(SetAction::Insert, branch, new)
             .map_err(|err| {
-                ProductError::UpdateNewError("UNION query error:".to_string(), Box::new(err))
+                ProductError::UpdateNewError(
+                    format!("{} query error:", self.operator.op),
+                    Box::new(err),
+                )
             })?;
 
         Ok((old_records, new_records))
@@ -88,14 +144,34 @@ impl Processor for SetProcessor {
         Ok(())
     }
 
+    fn serialize_state(&self, writer: &mut StateWriter) -> Result<(), BoxedError> {
+        if self.operator.counts_branches() {
+            return Ok(serialize_bincode_into(
+                (
+                    self.record_map.serialize()?,
+                    self.right_record_map.serialize()?,
+                ),
+                writer,
+            )?);
+        }
+        Ok(writer.write_all(&self.record_map.serialize()?)?)
+    }
+
     fn process(
         &mut self,
         op: TableOperation,
         fw: &mut dyn ProcessorChannelForwarder,
     ) -> Result<(), BoxedError> {
+        let branch = match op.port {
+            0 => SetBranch::Left,
+            1 => SetBranch::Right,
+            _ => return Err(PipelineError::InvalidPortHandle(op.port).into()),
+        };
         match op.op {
             Operation::Delete { old } => {
-                let records = self.delete(old).map_err(PipelineError::ProductError)?;
+                let records = self
+                    .delete(branch, old)
+                    .map_err(PipelineError::ProductError)?;
 
                 for (action, record) in records.into_iter() {
                     match action {
@@ -115,7 +191,9 @@ impl Processor for SetProcessor {
                 }
             }
             Operation::Insert { new } => {
-                let records = self.insert(new).map_err(PipelineError::ProductError)?;
+                let records = self
+                    .insert(branch, new)
+                    .map_err(PipelineError::ProductError)?;
 
                 for (action, record) in records.into_iter() {
                     match action {
@@ -135,8 +213,9 @@ impl Processor for SetProcessor {
                 }
             }
             Operation::Update { old, new } => {
-                let (old_records, new_records) =
-                    self.update(old, new).map_err(PipelineError::ProductError)?;
+                let (old_records, new_records) = self
+                    .update(branch, old, new)
+                    .map_err(PipelineError::ProductError)?;
 
                 for (action, old) in old_records.into_iter() {
                     match action {
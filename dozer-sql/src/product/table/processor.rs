@@ -1,3 +1,5 @@
+use dozer_core::checkpoint::StateWriter;
+
 use dozer_core::channels::ProcessorChannelForwarder;
 use dozer_core::epoch::Epoch;
 use dozer_core::node::Processor;
@@ -21,6 +23,10 @@ impl Processor for TableProcessor {
         Ok(())
     }
 
+    fn serialize_state(&self, _writer: &mut StateWriter) -> Result<(), BoxedError> {
+        Ok(())
+    }
+
     fn process(
         &mut self,
         mut op: TableOperation,
@@ -1,3 +1,5 @@
+use dozer_core::checkpoint::StateWriter;
+
 use crate::errors::PipelineError;
 use dozer_sql_expression::execution::Expression;
 
@@ -47,6 +49,27 @@ impl ProjectionProcessor {
         Ok(output_record)
     }
 
+    fn insert_batch(&mut self, records: &[Record]) -> Result<Vec<Record>, PipelineError> {
+        let mut results = vec![Vec::with_capacity(self.expressions.len()); records.len()];
+
+        for expr in &mut self.expressions {
+            let values = expr.evaluate_batch(records, &self.input_schema)?;
+            for (result, value) in results.iter_mut().zip(values) {
+                result.push(value);
+            }
+        }
+
+        Ok(results
+            .into_iter()
+            .zip(records)
+            .map(|(results, record)| {
+                let mut output_record = Record::new(results);
+                output_record.set_lifetime(record.lifetime.to_owned());
+                output_record
+            })
+            .collect())
+    }
+
     fn update(&mut self, old: &Record, new: &Record) -> Result<Operation, PipelineError> {
         let mut old_results = vec![];
         let mut new_results = vec![];
@@ -79,13 +102,9 @@ impl Processor for ProjectionProcessor {
                 new: self.insert(new)?,
             },
             Operation::Update { ref old, ref new } => self.update(old, new)?,
-            Operation::BatchInsert { new } => {
-                let records = new
-                    .iter()
-                    .map(|record| self.insert(record))
-                    .collect::<Result<Vec<_>, _>>()?;
-                Operation::BatchInsert { new: records }
-            }
+            Operation::BatchInsert { new } => Operation::BatchInsert {
+                new: self.insert_batch(&new)?,
+            },
         };
         fw.send(TableOperation {
             id: op.id,
@@ -98,4 +117,8 @@ impl Processor for ProjectionProcessor {
     fn commit(&self, _epoch: &Epoch) -> Result<(), BoxedError> {
         Ok(())
     }
+
+    fn serialize_state(&self, _writer: &mut StateWriter) -> Result<(), BoxedError> {
+        Ok(())
+    }
 }
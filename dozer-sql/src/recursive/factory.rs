@@ -0,0 +1,205 @@
+use std::{collections::HashMap, sync::Arc};
+
+use dozer_core::{
+    checkpoint::deserialize_bincode,
+    event::EventHub,
+    node::{PortHandle, Processor, ProcessorFactory},
+    DEFAULT_PORT_HANDLE,
+};
+use dozer_sql_expression::{
+    builder::{ExpressionBuilder, NameOrAlias},
+    sqlparser::ast::{Expr as SqlExpr, SelectItem},
+};
+use dozer_types::{
+    errors::internal::BoxedError,
+    models::udf_config::UdfConfig,
+    tonic::async_trait,
+    types::{Schema, SourceDefinition},
+};
+use tokio::runtime::Runtime;
+
+use crate::{errors::PipelineError, product::join::factory::parse_join_constraint};
+
+use super::{operator::RecursiveOperator, processor::RecursiveProcessor};
+
+pub(crate) const ANCHOR_PORT: PortHandle = 0;
+pub(crate) const RECURSIVE_PORT: PortHandle = 1;
+
+/// Evaluates a recursive CTE whose recursive term joins the CTE, named by `table`,
+/// with the input on [`RECURSIVE_PORT`].
+#[derive(Debug)]
+pub struct RecursiveProcessorFactory {
+    id: String,
+    table: NameOrAlias,
+    columns: Vec<String>,
+    join_constraint: SqlExpr,
+    projection: Vec<SelectItem>,
+    selection: Option<SqlExpr>,
+    udfs: Vec<UdfConfig>,
+    runtime: Arc<Runtime>,
+}
+
+impl RecursiveProcessorFactory {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        id: String,
+        table: NameOrAlias,
+        columns: Vec<String>,
+        join_constraint: SqlExpr,
+        projection: Vec<SelectItem>,
+        selection: Option<SqlExpr>,
+        udfs: Vec<UdfConfig>,
+        runtime: Arc<Runtime>,
+    ) -> Self {
+        Self {
+            id,
+            table,
+            columns,
+            join_constraint,
+            projection,
+            selection,
+            udfs,
+            runtime,
+        }
+    }
+
+    /// The CTE has the columns of the anchor, renamed by the column list of the CTE if there is one.
+    fn get_cte_schema(&self, anchor_schema: &Schema) -> Result<Schema, PipelineError> {
+        let mut schema = anchor_schema.clone();
+        // Rows are deduplicated, but the anchor's key doesn't identify the rows the recursive term adds.
+        schema.primary_index = vec![];
+        if !self.columns.is_empty() {
+            if self.columns.len() != schema.fields.len() {
+                return Err(PipelineError::InvalidQuery(format!(
+                    "WITH query {} has {} columns available but {} columns specified",
+                    self.table.0,
+                    schema.fields.len(),
+                    self.columns.len()
+                )));
+            }
+            for (field, name) in schema.fields.iter_mut().zip(&self.columns) {
+                field.name = name.clone();
+            }
+        }
+        Ok(schema)
+    }
+
+    fn get_input_schema(
+        input_schemas: &HashMap<PortHandle, Schema>,
+        port: PortHandle,
+    ) -> Result<&Schema, PipelineError> {
+        input_schemas
+            .get(&port)
+            .ok_or(PipelineError::InvalidPortHandle(port))
+    }
+}
+
+#[async_trait]
+impl ProcessorFactory for RecursiveProcessorFactory {
+    fn id(&self) -> String {
+        self.id.clone()
+    }
+
+    fn type_name(&self) -> String {
+        "Recursive".to_string()
+    }
+
+    fn get_input_ports(&self) -> Vec<PortHandle> {
+        vec![ANCHOR_PORT, RECURSIVE_PORT]
+    }
+
+    fn get_output_ports(&self) -> Vec<PortHandle> {
+        vec![DEFAULT_PORT_HANDLE]
+    }
+
+    async fn get_output_schema(
+        &self,
+        _output_port: &PortHandle,
+        input_schemas: &HashMap<PortHandle, Schema>,
+    ) -> Result<Schema, BoxedError> {
+        let anchor_schema = Self::get_input_schema(input_schemas, ANCHOR_PORT)?;
+        Ok(self.get_cte_schema(anchor_schema)?)
+    }
+
+    async fn build(
+        &self,
+        input_schemas: HashMap<PortHandle, Schema>,
+        _output_schemas: HashMap<PortHandle, Schema>,
+        _event_hub: EventHub,
+        checkpoint_data: Option<Vec<u8>>,
+    ) -> Result<Box<dyn Processor>, BoxedError> {
+        let mut cte_schema =
+            self.get_cte_schema(Self::get_input_schema(&input_schemas, ANCHOR_PORT)?)?;
+        let cte_name = self.table.1.as_ref().unwrap_or(&self.table.0);
+        for field in cte_schema.fields.iter_mut() {
+            field.source = SourceDefinition::Alias {
+                name: cte_name.clone(),
+            };
+        }
+        let base_schema = Self::get_input_schema(&input_schemas, RECURSIVE_PORT)?;
+
+        let keys = parse_join_constraint(&self.join_constraint, &cte_schema, base_schema)
+            .map_err(PipelineError::JoinError)?;
+
+        // The recursive term is evaluated on a CTE row followed by a recursive input row.
+        let mut schema = cte_schema.clone();
+        schema.fields.extend(base_schema.fields.iter().cloned());
+
+        let mut projection = vec![];
+        for item in &self.projection {
+            let expression = match item {
+                SelectItem::UnnamedExpr(expression)
+                | SelectItem::ExprWithAlias {
+                    expr: expression, ..
+                } => expression,
+                _ => {
+                    return Err(PipelineError::InvalidQuery(format!(
+                        "Wildcards aren't supported in the recursive term of {}",
+                        self.table.0
+                    ))
+                    .into())
+                }
+            };
+            projection.push(
+                ExpressionBuilder::new(schema.fields.len(), self.runtime.clone())
+                    .build(false, expression, &schema, &self.udfs)
+                    .await?,
+            );
+        }
+        if projection.len() != cte_schema.fields.len() {
+            return Err(PipelineError::InvalidQuery(format!(
+                "The recursive term of {} returns {} columns, but the anchor returns {}",
+                self.table.0,
+                projection.len(),
+                cte_schema.fields.len()
+            ))
+            .into());
+        }
+
+        let selection = match &self.selection {
+            Some(selection) => Some(
+                ExpressionBuilder::new(schema.fields.len(), self.runtime.clone())
+                    .build(false, selection, &schema, &self.udfs)
+                    .await?,
+            ),
+            None => None,
+        };
+
+        let state = match checkpoint_data {
+            Some(checkpoint_data) => deserialize_bincode(&checkpoint_data)?,
+            None => Default::default(),
+        };
+
+        Ok(Box::new(RecursiveProcessor::new(
+            self.id.clone(),
+            RecursiveOperator::new(
+                self.table.0.clone(),
+                schema,
+                keys,
+                projection,
+                selection,
+                state,
+            ),
+        )))
+    }
+}
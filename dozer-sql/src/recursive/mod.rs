@@ -0,0 +1,6 @@
+pub(crate) mod factory;
+mod operator;
+mod processor;
+
+#[cfg(test)]
+mod tests;
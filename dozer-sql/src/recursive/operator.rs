@@ -0,0 +1,332 @@
+use std::collections::{HashMap, HashSet};
+
+use dozer_sql_expression::execution::Expression;
+use dozer_types::bincode;
+use dozer_types::types::{Field, Operation, Record, Schema};
+
+use crate::errors::PipelineError;
+
+/// How many times the recursive term can be applied to a row and its descendants
+/// before the CTE is considered not to terminate.
+const MAX_RECURSION_DEPTH: usize = 10_000;
+
+#[derive(Debug, Default, bincode::Encode, bincode::Decode)]
+pub struct RecursiveState {
+    /// Rows of the recursive input, grouped by join key, with the number of times they occur.
+    base: HashMap<Vec<Field>, HashMap<Vec<Field>, usize>>,
+    /// Rows of the CTE with the number of ways they're derived,
+    /// either from the anchor or by joining another row of the CTE with a recursive input row.
+    rows: HashMap<Vec<Field>, usize>,
+    /// Rows of the CTE grouped by join key.
+    rows_by_key: HashMap<Vec<Field>, HashSet<Vec<Field>>>,
+}
+
+/// Maintains the fixpoint of `anchor UNION SELECT .. FROM cte JOIN input ON ..`.
+///
+/// Rows are added semi-naively: only rows that are new to the CTE are joined with the recursive input.
+/// Rows are removed by deleting everything derived from a lost row and then rederiving what's still supported.
+#[derive(Debug)]
+pub struct RecursiveOperator {
+    name: String,
+    /// Schema of a CTE row followed by a recursive input row.
+    schema: Schema,
+    key_indexes: Vec<usize>,
+    base_key_indexes: Vec<usize>,
+    projection: Vec<Expression>,
+    selection: Option<Expression>,
+    state: RecursiveState,
+}
+
+impl RecursiveOperator {
+    pub fn new(
+        name: String,
+        schema: Schema,
+        (key_indexes, base_key_indexes): (Vec<usize>, Vec<usize>),
+        projection: Vec<Expression>,
+        selection: Option<Expression>,
+        state: RecursiveState,
+    ) -> Self {
+        Self {
+            name,
+            schema,
+            key_indexes,
+            base_key_indexes,
+            projection,
+            selection,
+            state,
+        }
+    }
+
+    pub fn state(&self) -> &RecursiveState {
+        &self.state
+    }
+
+    pub fn insert_anchor(&mut self, row: Vec<Field>) -> Result<Vec<Operation>, PipelineError> {
+        let mut ops = vec![];
+        if self.add_derivation(row.clone(), 1, &mut ops) {
+            self.propagate(vec![(row, 0)], &mut ops)?;
+        }
+        Ok(ops)
+    }
+
+    pub fn delete_anchor(&mut self, row: Vec<Field>) -> Result<Vec<Operation>, PipelineError> {
+        self.remove_derivations(vec![(row, 1)])
+    }
+
+    pub fn insert_base(&mut self, row: Vec<Field>) -> Result<Vec<Operation>, PipelineError> {
+        let Some(key) = get_key(&row, &self.base_key_indexes) else {
+            // A `NULL` join key never matches.
+            return Ok(vec![]);
+        };
+        let derived = self.derive_from_base(&key, &row)?;
+        *self
+            .state
+            .base
+            .entry(key)
+            .or_default()
+            .entry(row)
+            .or_default() += 1;
+
+        let mut ops = vec![];
+        let mut queue = vec![];
+        for derived_row in derived {
+            if self.add_derivation(derived_row.clone(), 1, &mut ops) {
+                queue.push((derived_row, 1));
+            }
+        }
+        self.propagate(queue, &mut ops)?;
+        Ok(ops)
+    }
+
+    pub fn delete_base(&mut self, row: Vec<Field>) -> Result<Vec<Operation>, PipelineError> {
+        let Some(key) = get_key(&row, &self.base_key_indexes) else {
+            return Ok(vec![]);
+        };
+        let Some(rows) = self.state.base.get_mut(&key) else {
+            return Ok(vec![]);
+        };
+        let Some(count) = rows.get_mut(&row) else {
+            return Ok(vec![]);
+        };
+        *count -= 1;
+        if *count == 0 {
+            rows.remove(&row);
+            if rows.is_empty() {
+                self.state.base.remove(&key);
+            }
+        }
+
+        let lost = self
+            .derive_from_base(&key, &row)?
+            .into_iter()
+            .map(|derived_row| (derived_row, 1))
+            .collect();
+        self.remove_derivations(lost)
+    }
+
+    /// Joins new rows with the recursive input until no more new rows are found.
+    fn propagate(
+        &mut self,
+        mut queue: Vec<(Vec<Field>, usize)>,
+        ops: &mut Vec<Operation>,
+    ) -> Result<(), PipelineError> {
+        while let Some((row, depth)) = queue.pop() {
+            if depth >= MAX_RECURSION_DEPTH {
+                return Err(PipelineError::RecursionLimit(
+                    self.name.clone(),
+                    MAX_RECURSION_DEPTH,
+                ));
+            }
+            for (derived_row, count) in self.derive(&row)? {
+                if self.add_derivation(derived_row.clone(), count, ops) {
+                    queue.push((derived_row, depth + 1));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Removes one derivation of each of `lost`, and every row that can't be derived any more.
+    fn remove_derivations(
+        &mut self,
+        lost: Vec<(Vec<Field>, usize)>,
+    ) -> Result<Vec<Operation>, PipelineError> {
+        // Over-delete the lost rows and everything derived from them.
+        let mut queue = vec![];
+        for (row, count) in lost {
+            if let Some(derivations) = self.state.rows.get_mut(&row) {
+                *derivations = derivations.saturating_sub(count);
+                queue.push(row);
+            }
+        }
+        let mut deleted = HashSet::new();
+        while let Some(row) = queue.pop() {
+            if !deleted.insert(row.clone()) {
+                continue;
+            }
+            for (derived_row, count) in self.derive(&row)? {
+                if let Some(derivations) = self.state.rows.get_mut(&derived_row) {
+                    *derivations = derivations.saturating_sub(count);
+                    queue.push(derived_row);
+                }
+            }
+        }
+
+        // What's left of their derivation counts comes from the anchor or from rows that weren't deleted.
+        let mut pending = deleted
+            .into_iter()
+            .map(|row| {
+                let derivations = self.remove_row(&row);
+                (row, derivations)
+            })
+            .collect::<HashMap<_, _>>();
+
+        // Rederive the rows that are still supported, and everything derived from them.
+        let supported = pending
+            .iter()
+            .filter(|(_, derivations)| **derivations > 0)
+            .map(|(row, _)| row.clone())
+            .collect::<Vec<_>>();
+        let mut queue = vec![];
+        for row in supported {
+            let derivations = pending.remove(&row).unwrap_or_default();
+            self.insert_row(row.clone(), derivations);
+            queue.push(row);
+        }
+        let mut ops = vec![];
+        while let Some(row) = queue.pop() {
+            for (derived_row, count) in self.derive(&row)? {
+                if let Some(derivations) = self.state.rows.get_mut(&derived_row) {
+                    *derivations += count;
+                } else {
+                    let derivations = pending.remove(&derived_row).unwrap_or_else(|| {
+                        ops.push(Operation::Insert {
+                            new: Record::new(derived_row.clone()),
+                        });
+                        0
+                    });
+                    self.insert_row(derived_row.clone(), derivations + count);
+                    queue.push(derived_row);
+                }
+            }
+        }
+
+        ops.extend(pending.into_keys().map(|row| Operation::Delete {
+            old: Record::new(row),
+        }));
+        Ok(ops)
+    }
+
+    /// Adds a derivation of `row`, returning whether the row is new.
+    fn add_derivation(&mut self, row: Vec<Field>, count: usize, ops: &mut Vec<Operation>) -> bool {
+        if let Some(derivations) = self.state.rows.get_mut(&row) {
+            *derivations += count;
+            return false;
+        }
+        ops.push(Operation::Insert {
+            new: Record::new(row.clone()),
+        });
+        self.insert_row(row, count);
+        true
+    }
+
+    fn insert_row(&mut self, row: Vec<Field>, derivations: usize) {
+        if let Some(key) = get_key(&row, &self.key_indexes) {
+            self.state
+                .rows_by_key
+                .entry(key)
+                .or_default()
+                .insert(row.clone());
+        }
+        self.state.rows.insert(row, derivations);
+    }
+
+    fn remove_row(&mut self, row: &[Field]) -> usize {
+        if let Some(key) = get_key(row, &self.key_indexes) {
+            if let Some(rows) = self.state.rows_by_key.get_mut(&key) {
+                rows.remove(row);
+                if rows.is_empty() {
+                    self.state.rows_by_key.remove(&key);
+                }
+            }
+        }
+        self.state.rows.remove(row).unwrap_or_default()
+    }
+
+    /// The rows derived from a CTE row, with the number of recursive input rows each is derived from.
+    fn derive(&mut self, row: &[Field]) -> Result<Vec<(Vec<Field>, usize)>, PipelineError> {
+        let Some(key) = get_key(row, &self.key_indexes) else {
+            return Ok(vec![]);
+        };
+        let Some(base_rows) = self.state.base.get(&key) else {
+            return Ok(vec![]);
+        };
+        let mut derived = vec![];
+        for (base_row, count) in base_rows {
+            if let Some(derived_row) = evaluate(
+                &mut self.projection,
+                &mut self.selection,
+                &self.schema,
+                row,
+                base_row,
+            )? {
+                derived.push((derived_row, *count));
+            }
+        }
+        Ok(derived)
+    }
+
+    /// The rows derived from a recursive input row, one for each CTE row it joins.
+    fn derive_from_base(
+        &mut self,
+        key: &[Field],
+        base_row: &[Field],
+    ) -> Result<Vec<Vec<Field>>, PipelineError> {
+        let Some(rows) = self.state.rows_by_key.get(key) else {
+            return Ok(vec![]);
+        };
+        let mut derived = vec![];
+        for row in rows {
+            if let Some(derived_row) = evaluate(
+                &mut self.projection,
+                &mut self.selection,
+                &self.schema,
+                row,
+                base_row,
+            )? {
+                derived.push(derived_row);
+            }
+        }
+        Ok(derived)
+    }
+}
+
+fn evaluate(
+    projection: &mut [Expression],
+    selection: &mut Option<Expression>,
+    schema: &Schema,
+    row: &[Field],
+    base_row: &[Field],
+) -> Result<Option<Vec<Field>>, PipelineError> {
+    let record = Record::new([row, base_row].concat());
+    if let Some(selection) = selection {
+        if selection.evaluate(&record, schema)? != Field::Boolean(true) {
+            return Ok(None);
+        }
+    }
+    let mut values = Vec::with_capacity(projection.len());
+    for expression in projection.iter_mut() {
+        values.push(expression.evaluate(&record, schema)?);
+    }
+    Ok(Some(values))
+}
+
+fn get_key(row: &[Field], indexes: &[usize]) -> Option<Vec<Field>> {
+    indexes
+        .iter()
+        .map(|index| match &row[*index] {
+            Field::Null => None,
+            field => Some(field.clone()),
+        })
+        .collect()
+}
@@ -0,0 +1,85 @@
+use dozer_core::channels::ProcessorChannelForwarder;
+use dozer_core::checkpoint::{serialize_bincode_into, StateWriter};
+use dozer_core::epoch::Epoch;
+use dozer_core::node::{PortHandle, Processor};
+use dozer_core::DEFAULT_PORT_HANDLE;
+use dozer_types::errors::internal::BoxedError;
+use dozer_types::types::{Field, Operation, TableOperation};
+
+use crate::errors::PipelineError;
+
+use super::factory::{ANCHOR_PORT, RECURSIVE_PORT};
+use super::operator::RecursiveOperator;
+
+#[derive(Debug)]
+pub struct RecursiveProcessor {
+    _id: String,
+    operator: RecursiveOperator,
+}
+
+impl RecursiveProcessor {
+    pub fn new(id: String, operator: RecursiveOperator) -> Self {
+        Self { _id: id, operator }
+    }
+
+    fn insert(
+        &mut self,
+        port: PortHandle,
+        row: Vec<Field>,
+    ) -> Result<Vec<Operation>, PipelineError> {
+        match port {
+            ANCHOR_PORT => self.operator.insert_anchor(row),
+            RECURSIVE_PORT => self.operator.insert_base(row),
+            _ => Err(PipelineError::InvalidPortHandle(port)),
+        }
+    }
+
+    fn delete(
+        &mut self,
+        port: PortHandle,
+        row: Vec<Field>,
+    ) -> Result<Vec<Operation>, PipelineError> {
+        match port {
+            ANCHOR_PORT => self.operator.delete_anchor(row),
+            RECURSIVE_PORT => self.operator.delete_base(row),
+            _ => Err(PipelineError::InvalidPortHandle(port)),
+        }
+    }
+}
+
+impl Processor for RecursiveProcessor {
+    fn commit(&self, _epoch: &Epoch) -> Result<(), BoxedError> {
+        Ok(())
+    }
+
+    fn serialize_state(&self, writer: &mut StateWriter) -> Result<(), BoxedError> {
+        Ok(serialize_bincode_into(self.operator.state(), writer)?)
+    }
+
+    fn process(
+        &mut self,
+        op: TableOperation,
+        fw: &mut dyn ProcessorChannelForwarder,
+    ) -> Result<(), BoxedError> {
+        let ops = match op.op {
+            Operation::Insert { new } => self.insert(op.port, new.values)?,
+            Operation::Delete { old } => self.delete(op.port, old.values)?,
+            Operation::Update { old, new } => {
+                let mut ops = self.delete(op.port, old.values)?;
+                ops.extend(self.insert(op.port, new.values)?);
+                ops
+            }
+            Operation::BatchInsert { new } => {
+                let mut ops = vec![];
+                for record in new {
+                    ops.extend(self.insert(op.port, record.values)?);
+                }
+                ops
+            }
+        };
+        for output_op in ops {
+            fw.send(TableOperation::without_id(output_op, DEFAULT_PORT_HANDLE));
+        }
+        Ok(())
+    }
+}
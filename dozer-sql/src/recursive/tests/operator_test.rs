@@ -0,0 +1,150 @@
+use dozer_sql_expression::execution::Expression;
+use dozer_sql_expression::operator::BinaryOperatorType;
+use dozer_types::types::{Field, FieldDefinition, FieldType, Operation, Schema, SourceDefinition};
+
+use crate::errors::PipelineError;
+use crate::recursive::operator::{RecursiveOperator, RecursiveState};
+
+fn schema(names: &[&str]) -> Schema {
+    let mut schema = Schema::default();
+    for name in names {
+        schema.field(
+            FieldDefinition::new(
+                name.to_string(),
+                FieldType::Int,
+                true,
+                SourceDefinition::Dynamic,
+            ),
+            false,
+        );
+    }
+    schema
+}
+
+fn ints(values: &[i64]) -> Vec<Field> {
+    values.iter().map(|value| Field::Int(*value)).collect()
+}
+
+/// Inserted rows as `(true, row)` and deleted rows as `(false, row)`, sorted.
+fn changes(ops: Vec<Operation>) -> Vec<(bool, Vec<Field>)> {
+    let mut changes = ops
+        .into_iter()
+        .map(|op| match op {
+            Operation::Insert { new } => (true, new.values),
+            Operation::Delete { old } => (false, old.values),
+            op => panic!("unexpected operation {op:?}"),
+        })
+        .collect::<Vec<_>>();
+    changes.sort();
+    changes
+}
+
+/// `SELECT e.id, h.depth + 1 FROM hierarchy h JOIN employees e ON h.id = e.manager_id`
+fn hierarchy() -> RecursiveOperator {
+    RecursiveOperator::new(
+        "hierarchy".to_string(),
+        schema(&["id", "depth", "employee_id", "manager_id"]),
+        (vec![0], vec![1]),
+        vec![
+            Expression::Column { index: 2 },
+            Expression::BinaryOperator {
+                left: Box::new(Expression::Column { index: 1 }),
+                operator: BinaryOperatorType::Add,
+                right: Box::new(Expression::Literal(Field::Int(1))),
+            },
+        ],
+        None,
+        RecursiveState::default(),
+    )
+}
+
+/// `SELECT e.to FROM reachable r JOIN edges e ON r.node = e.from`
+fn reachable() -> RecursiveOperator {
+    RecursiveOperator::new(
+        "reachable".to_string(),
+        schema(&["node", "from", "to"]),
+        (vec![0], vec![0]),
+        vec![Expression::Column { index: 2 }],
+        None,
+        RecursiveState::default(),
+    )
+}
+
+#[test]
+fn test_recursive_insert() {
+    let mut operator = hierarchy();
+
+    // Employee 2 reports to 1, who isn't in the hierarchy yet.
+    assert_eq!(operator.insert_base(ints(&[2, 1])).unwrap(), vec![]);
+    assert_eq!(
+        changes(operator.insert_anchor(ints(&[1, 0])).unwrap()),
+        vec![(true, ints(&[1, 0])), (true, ints(&[2, 1]))]
+    );
+    assert_eq!(
+        changes(operator.insert_base(ints(&[3, 2])).unwrap()),
+        vec![(true, ints(&[3, 2]))]
+    );
+    // A `NULL` manager never joins.
+    assert_eq!(
+        operator
+            .insert_base(vec![Field::Int(4), Field::Null])
+            .unwrap(),
+        vec![]
+    );
+}
+
+#[test]
+fn test_recursive_delete() {
+    let mut operator = hierarchy();
+    operator.insert_anchor(ints(&[1, 0])).unwrap();
+    operator.insert_base(ints(&[2, 1])).unwrap();
+    operator.insert_base(ints(&[3, 2])).unwrap();
+
+    // Deleting a row that's derived more than once keeps it.
+    operator.insert_anchor(ints(&[2, 1])).unwrap();
+    assert_eq!(operator.delete_anchor(ints(&[2, 1])).unwrap(), vec![]);
+
+    assert_eq!(
+        changes(operator.delete_base(ints(&[2, 1])).unwrap()),
+        vec![(false, ints(&[2, 1])), (false, ints(&[3, 2]))]
+    );
+    assert_eq!(
+        changes(operator.delete_anchor(ints(&[1, 0])).unwrap()),
+        vec![(false, ints(&[1, 0]))]
+    );
+}
+
+#[test]
+fn test_recursive_cycle() {
+    let mut operator = reachable();
+    operator.insert_anchor(ints(&[1])).unwrap();
+    for edge in [[1, 2], [2, 3], [3, 2], [1, 3]] {
+        operator.insert_base(ints(&edge)).unwrap();
+    }
+
+    // 2 is still reachable through 3.
+    assert_eq!(operator.delete_base(ints(&[1, 2])).unwrap(), vec![]);
+    // 2 and 3 can only reach each other.
+    assert_eq!(
+        changes(operator.delete_base(ints(&[1, 3])).unwrap()),
+        vec![(false, ints(&[2])), (false, ints(&[3]))]
+    );
+    // They come back when 2 is reachable again.
+    assert_eq!(
+        changes(operator.insert_base(ints(&[1, 2])).unwrap()),
+        vec![(true, ints(&[2])), (true, ints(&[3]))]
+    );
+}
+
+#[test]
+fn test_recursive_limit() {
+    let mut operator = hierarchy();
+    operator.insert_base(ints(&[2, 1])).unwrap();
+    operator.insert_base(ints(&[1, 2])).unwrap();
+
+    // The depth keeps growing around the cycle.
+    assert!(matches!(
+        operator.insert_anchor(ints(&[1, 0])),
+        Err(PipelineError::RecursionLimit(name, _)) if name == "hierarchy"
+    ));
+}
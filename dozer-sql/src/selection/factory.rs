@@ -71,6 +71,7 @@ impl ProcessorFactory for SelectionProcessorFactory {
         input_schemas: HashMap<PortHandle, Schema>,
         _output_schemas: HashMap<PortHandle, Schema>,
         _event_hub: EventHub,
+        _checkpoint_data: Option<Vec<u8>>,
     ) -> Result<Box<dyn Processor>, BoxedError> {
         let schema = input_schemas
             .get(&DEFAULT_PORT_HANDLE)
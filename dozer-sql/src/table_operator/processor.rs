@@ -1,3 +1,5 @@
+use dozer_core::checkpoint::StateWriter;
+
 use dozer_core::channels::ProcessorChannelForwarder;
 use dozer_core::epoch::Epoch;
 use dozer_core::node::Processor;
@@ -31,6 +33,10 @@ impl Processor for TableOperatorProcessor {
         Ok(())
     }
 
+    fn serialize_state(&self, _writer: &mut StateWriter) -> Result<(), BoxedError> {
+        Ok(())
+    }
+
     fn process(
         &mut self,
         op: TableOperation,
@@ -0,0 +1,151 @@
+use std::{collections::HashMap, sync::Arc};
+
+use crate::errors::PipelineError;
+use dozer_core::{
+    event::EventHub,
+    node::{PortHandle, Processor, ProcessorFactory},
+    DEFAULT_PORT_HANDLE,
+};
+use dozer_sql_expression::builder::ExpressionBuilder;
+use dozer_sql_expression::execution::Expression;
+use dozer_sql_expression::sqlparser::ast::{Expr as SqlExpr, OrderByExpr};
+use dozer_types::models::udf_config::UdfConfig;
+use dozer_types::types::{FieldDefinition, FieldType, Schema, SourceDefinition};
+use dozer_types::{errors::internal::BoxedError, tonic::async_trait};
+use tokio::runtime::Runtime;
+
+use super::operator::SortDirection;
+use super::processor::TopNProcessor;
+
+/// Keeps the rows at positions `offset..offset + limit` of each partition, in `order_by` order.
+/// Optionally appends each row's 1-based position as a `row_number` column.
+///
+/// Every input row is kept in the processor's state, see [`TopNState`](super::operator::TopNState).
+#[derive(Debug)]
+pub struct TopNProcessorFactory {
+    id: String,
+    partition_by: Vec<SqlExpr>,
+    order_by: Vec<OrderByExpr>,
+    offset: usize,
+    limit: usize,
+    row_number: Option<String>,
+    udfs: Vec<UdfConfig>,
+    runtime: Arc<Runtime>,
+}
+
+impl TopNProcessorFactory {
+    /// Creates a new [`TopNProcessorFactory`].
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        id: String,
+        partition_by: Vec<SqlExpr>,
+        order_by: Vec<OrderByExpr>,
+        offset: usize,
+        limit: usize,
+        row_number: Option<String>,
+        udfs: Vec<UdfConfig>,
+        runtime: Arc<Runtime>,
+    ) -> Self {
+        Self {
+            id,
+            partition_by,
+            order_by,
+            offset,
+            limit,
+            row_number,
+            udfs,
+            runtime,
+        }
+    }
+
+    async fn build_expression(
+        &self,
+        expression: &SqlExpr,
+        schema: &Schema,
+    ) -> Result<Expression, PipelineError> {
+        // `ORDER BY COUNT(*)` refers to the projected `COUNT(*)` column, which can't be evaluated again.
+        let name = expression.to_string();
+        if let Some(index) = schema.fields.iter().position(|field| field.name == name) {
+            return Ok(Expression::Column { index });
+        }
+        Ok(
+            ExpressionBuilder::new(schema.fields.len(), self.runtime.clone())
+                .build(false, expression, schema, &self.udfs)
+                .await?,
+        )
+    }
+}
+
+#[async_trait]
+impl ProcessorFactory for TopNProcessorFactory {
+    fn id(&self) -> String {
+        self.id.clone()
+    }
+
+    fn type_name(&self) -> String {
+        "TopN".to_string()
+    }
+
+    fn get_input_ports(&self) -> Vec<PortHandle> {
+        vec![DEFAULT_PORT_HANDLE]
+    }
+
+    fn get_output_ports(&self) -> Vec<PortHandle> {
+        vec![DEFAULT_PORT_HANDLE]
+    }
+
+    async fn get_output_schema(
+        &self,
+        _output_port: &PortHandle,
+        input_schemas: &HashMap<PortHandle, Schema>,
+    ) -> Result<Schema, BoxedError> {
+        let mut schema = input_schemas
+            .get(&DEFAULT_PORT_HANDLE)
+            .ok_or(PipelineError::InvalidPortHandle(DEFAULT_PORT_HANDLE))?
+            .clone();
+        if let Some(row_number) = &self.row_number {
+            schema.fields.push(FieldDefinition::new(
+                row_number.clone(),
+                FieldType::UInt,
+                false,
+                SourceDefinition::Dynamic,
+            ));
+        }
+        Ok(schema)
+    }
+
+    async fn build(
+        &self,
+        input_schemas: HashMap<PortHandle, Schema>,
+        _output_schemas: HashMap<PortHandle, Schema>,
+        _event_hub: EventHub,
+        checkpoint_data: Option<Vec<u8>>,
+    ) -> Result<Box<dyn Processor>, BoxedError> {
+        let schema = input_schemas
+            .get(&DEFAULT_PORT_HANDLE)
+            .ok_or(PipelineError::InvalidPortHandle(DEFAULT_PORT_HANDLE))?;
+
+        let mut partition_by = vec![];
+        for expression in &self.partition_by {
+            partition_by.push(self.build_expression(expression, schema).await?);
+        }
+        let mut order_by = vec![];
+        for expression in &self.order_by {
+            order_by.push((
+                self.build_expression(&expression.expr, schema).await?,
+                SortDirection::new(expression.asc, expression.nulls_first),
+            ));
+        }
+
+        Ok(Box::new(TopNProcessor::new(
+            self.id.clone(),
+            partition_by,
+            order_by,
+            self.offset,
+            self.limit,
+            self.row_number.is_some(),
+            schema.clone(),
+            checkpoint_data,
+        )?))
+    }
+}
@@ -0,0 +1,256 @@
+use std::cmp::Ordering;
+use std::collections::{btree_map::Entry, BTreeMap, HashMap};
+
+use dozer_types::bincode;
+use dozer_types::types::{Field, Operation, Record};
+
+/// Direction of one `ORDER BY` expression.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, bincode::Encode, bincode::Decode)]
+pub struct SortDirection {
+    pub descending: bool,
+    pub nulls_first: bool,
+}
+
+impl SortDirection {
+    /// `ASC` puts `NULL`s last and `DESC` puts them first, unless `NULLS FIRST` or `NULLS LAST` is given.
+    pub fn new(asc: Option<bool>, nulls_first: Option<bool>) -> Self {
+        let descending = asc == Some(false);
+        Self {
+            descending,
+            nulls_first: nulls_first.unwrap_or(descending),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, bincode::Encode, bincode::Decode)]
+pub struct SortValue {
+    pub value: Field,
+    pub direction: SortDirection,
+}
+
+impl Ord for SortValue {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let null_ordering = if self.direction.nulls_first {
+            Ordering::Less
+        } else {
+            Ordering::Greater
+        };
+        match (&self.value, &other.value) {
+            (Field::Null, Field::Null) => Ordering::Equal,
+            (Field::Null, _) => null_ordering,
+            (_, Field::Null) => null_ordering.reverse(),
+            (left, right) if self.direction.descending => right.cmp(left),
+            (left, right) => left.cmp(right),
+        }
+    }
+}
+
+impl PartialOrd for SortValue {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// A row in a partition, ordered by its sort values and then by its own values.
+pub type SortKey = (Vec<SortValue>, Vec<Field>);
+
+/// Every row of every partition with the number of times it occurs.
+///
+/// The state is not bounded by `offset + limit`: it grows with the input. A row deleted from the top N is replaced by
+/// the next one in order, and a processor can't read its input again to find it, so every row has to be kept. Only
+/// the output is computed incrementally, from the rows around a change.
+#[derive(Debug, Default, bincode::Encode, bincode::Decode)]
+pub struct TopNState {
+    partitions: HashMap<Vec<Field>, BTreeMap<SortKey, usize>>,
+}
+
+#[derive(Debug)]
+pub struct TopN {
+    offset: usize,
+    limit: usize,
+    row_number: bool,
+    primary_index: Vec<usize>,
+    state: TopNState,
+}
+
+impl TopN {
+    pub fn new(
+        offset: usize,
+        limit: usize,
+        row_number: bool,
+        primary_index: Vec<usize>,
+        state: TopNState,
+    ) -> Self {
+        Self {
+            offset,
+            limit,
+            row_number,
+            primary_index,
+            state,
+        }
+    }
+
+    pub fn state(&self) -> &TopNState {
+        &self.state
+    }
+
+    /// Removes `deleted` from and adds `inserted` to a partition,
+    /// returning the operations that turn the old top N of the partition into the new one.
+    pub fn apply(
+        &mut self,
+        partition: Vec<Field>,
+        deleted: Vec<SortKey>,
+        inserted: Vec<SortKey>,
+    ) -> Vec<Operation> {
+        let rows = self.state.partitions.entry(partition.clone()).or_default();
+        // Rows sorted after the last visible position don't move any visible row.
+        let end = self.offset.saturating_add(self.limit);
+        let changes_visible_rows = deleted
+            .iter()
+            .chain(&inserted)
+            .any(|key| is_before(rows, key, end));
+        let before = if changes_visible_rows {
+            visible_rows(rows, self.offset, self.limit, self.row_number)
+        } else {
+            vec![]
+        };
+
+        for key in deleted {
+            if let Entry::Occupied(mut entry) = rows.entry(key) {
+                *entry.get_mut() -= 1;
+                if *entry.get() == 0 {
+                    entry.remove();
+                }
+            }
+        }
+        for key in inserted {
+            *rows.entry(key).or_default() += 1;
+        }
+
+        if rows.is_empty() {
+            self.state.partitions.remove(&partition);
+            return self.diff(before, vec![]);
+        }
+        if !changes_visible_rows {
+            return vec![];
+        }
+        let after = visible_rows(rows, self.offset, self.limit, self.row_number);
+        self.diff(before, after)
+    }
+
+    fn diff(&self, before: Vec<Vec<Field>>, after: Vec<Vec<Field>>) -> Vec<Operation> {
+        diff_rows(before, after, |row| self.identity(row))
+    }
+
+    fn identity(&self, row: &[Field]) -> Vec<Field> {
+        if self.primary_index.is_empty() {
+            let len = row.len() - self.row_number as usize;
+            row[..len].to_vec()
+        } else {
+            self.primary_index
+                .iter()
+                .map(|index| row[*index].clone())
+                .collect()
+        }
+    }
+}
+
+/// The operations that turn the rows `before` into the rows `after`.
+/// Rows with the same `identity` that leave and enter at once are output as updates.
+pub fn diff_rows(
+    before: Vec<Vec<Field>>,
+    after: Vec<Vec<Field>>,
+    identity: impl Fn(&[Field]) -> Vec<Field>,
+) -> Vec<Operation> {
+    let removed = subtract(&before, &after);
+    let mut added = subtract(&after, &before)
+        .into_iter()
+        .map(Some)
+        .collect::<Vec<_>>();
+
+    // A row that leaves and enters at once, because it was updated or its row number changed, is an update.
+    let mut added_by_identity: HashMap<Vec<Field>, Vec<usize>> = HashMap::new();
+    for (index, row) in added.iter().enumerate().rev() {
+        if let Some(row) = row {
+            added_by_identity
+                .entry(identity(row))
+                .or_default()
+                .push(index);
+        }
+    }
+
+    let mut deletes = vec![];
+    let mut updates = vec![];
+    for old in removed {
+        let new = added_by_identity
+            .get_mut(&identity(&old))
+            .and_then(|indexes| indexes.pop())
+            .and_then(|index| added[index].take());
+        match new {
+            Some(new) => updates.push(Operation::Update {
+                old: Record::new(old),
+                new: Record::new(new),
+            }),
+            None => deletes.push(Operation::Delete {
+                old: Record::new(old),
+            }),
+        }
+    }
+    let inserts = added.into_iter().flatten().map(|new| Operation::Insert {
+        new: Record::new(new),
+    });
+
+    deletes.into_iter().chain(updates).chain(inserts).collect()
+}
+
+/// The rows at positions `offset..offset + limit`, followed by their row numbers if `row_number` is set.
+fn visible_rows(
+    rows: &BTreeMap<SortKey, usize>,
+    offset: usize,
+    limit: usize,
+    row_number: bool,
+) -> Vec<Vec<Field>> {
+    rows.iter()
+        .flat_map(|((_, values), count)| std::iter::repeat(values).take(*count))
+        .enumerate()
+        .skip(offset)
+        .take(limit)
+        .map(|(position, values)| {
+            let mut values = values.clone();
+            if row_number {
+                values.push(Field::UInt(position as u64 + 1));
+            }
+            values
+        })
+        .collect()
+}
+
+/// Whether fewer than `end` rows sort before `key`, counting at most `end` rows.
+fn is_before(rows: &BTreeMap<SortKey, usize>, key: &SortKey, end: usize) -> bool {
+    let mut position = 0;
+    for count in rows.range(..key).map(|(_, count)| *count) {
+        position += count;
+        if position >= end {
+            return false;
+        }
+    }
+    end > 0
+}
+
+/// The rows of `rows` that are left after removing one occurrence of each row of `other`.
+fn subtract(rows: &[Vec<Field>], other: &[Vec<Field>]) -> Vec<Vec<Field>> {
+    let mut counts: HashMap<&[Field], usize> = HashMap::new();
+    for row in other {
+        *counts.entry(row.as_slice()).or_default() += 1;
+    }
+    rows.iter()
+        .filter(|row| match counts.get_mut(row.as_slice()) {
+            Some(count) if *count > 0 => {
+                *count -= 1;
+                false
+            }
+            _ => true,
+        })
+        .cloned()
+        .collect()
+}
@@ -0,0 +1,132 @@
+use std::collections::HashMap;
+
+use crate::errors::PipelineError;
+use dozer_core::channels::ProcessorChannelForwarder;
+use dozer_core::checkpoint::{deserialize_bincode, serialize_bincode_into, StateWriter};
+use dozer_core::epoch::Epoch;
+use dozer_core::node::Processor;
+use dozer_core::DEFAULT_PORT_HANDLE;
+use dozer_sql_expression::execution::Expression;
+use dozer_types::errors::internal::BoxedError;
+use dozer_types::types::{Field, Operation, Record, Schema, TableOperation};
+
+use super::operator::{SortDirection, SortKey, SortValue, TopN};
+
+#[derive(Debug)]
+pub struct TopNProcessor {
+    _id: String,
+    partition_by: Vec<Expression>,
+    order_by: Vec<(Expression, SortDirection)>,
+    input_schema: Schema,
+    top_n: TopN,
+}
+
+impl TopNProcessor {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        id: String,
+        partition_by: Vec<Expression>,
+        order_by: Vec<(Expression, SortDirection)>,
+        offset: usize,
+        limit: usize,
+        row_number: bool,
+        input_schema: Schema,
+        checkpoint_data: Option<Vec<u8>>,
+    ) -> Result<Self, BoxedError> {
+        let state = match checkpoint_data {
+            Some(checkpoint_data) => deserialize_bincode(&checkpoint_data)?,
+            None => Default::default(),
+        };
+        let primary_index = input_schema.primary_index.clone();
+
+        Ok(Self {
+            _id: id,
+            partition_by,
+            order_by,
+            input_schema,
+            top_n: TopN::new(offset, limit, row_number, primary_index, state),
+        })
+    }
+
+    fn get_key(&mut self, record: &Record) -> Result<(Vec<Field>, SortKey), PipelineError> {
+        let mut partition = Vec::with_capacity(self.partition_by.len());
+        for expression in self.partition_by.iter_mut() {
+            partition.push(expression.evaluate(record, &self.input_schema)?);
+        }
+        let mut sort_values = Vec::with_capacity(self.order_by.len());
+        for (expression, direction) in self.order_by.iter_mut() {
+            sort_values.push(SortValue {
+                value: expression.evaluate(record, &self.input_schema)?,
+                direction: *direction,
+            });
+        }
+        Ok((partition, (sort_values, record.values.clone())))
+    }
+
+    fn execute(&mut self, op: Operation) -> Result<Vec<Operation>, PipelineError> {
+        match op {
+            Operation::Insert { new } => {
+                let (partition, key) = self.get_key(&new)?;
+                Ok(self.top_n.apply(partition, vec![], vec![key]))
+            }
+            Operation::Delete { old } => {
+                let (partition, key) = self.get_key(&old)?;
+                Ok(self.top_n.apply(partition, vec![key], vec![]))
+            }
+            Operation::Update { old, new } => {
+                let (old_partition, old_key) = self.get_key(&old)?;
+                let (new_partition, new_key) = self.get_key(&new)?;
+                if old_partition == new_partition {
+                    Ok(self
+                        .top_n
+                        .apply(new_partition, vec![old_key], vec![new_key]))
+                } else {
+                    let mut ops = self.top_n.apply(old_partition, vec![old_key], vec![]);
+                    ops.extend(self.top_n.apply(new_partition, vec![], vec![new_key]));
+                    Ok(ops)
+                }
+            }
+            Operation::BatchInsert { new } => {
+                let mut partitions: Vec<(Vec<Field>, Vec<SortKey>)> = vec![];
+                let mut partition_indexes = HashMap::new();
+                for record in new {
+                    let (partition, key) = self.get_key(&record)?;
+                    let index = *partition_indexes
+                        .entry(partition.clone())
+                        .or_insert_with(|| {
+                            partitions.push((partition, vec![]));
+                            partitions.len() - 1
+                        });
+                    partitions[index].1.push(key);
+                }
+
+                let mut ops = vec![];
+                for (partition, keys) in partitions {
+                    ops.extend(self.top_n.apply(partition, vec![], keys));
+                }
+                Ok(ops)
+            }
+        }
+    }
+}
+
+impl Processor for TopNProcessor {
+    fn commit(&self, _epoch: &Epoch) -> Result<(), BoxedError> {
+        Ok(())
+    }
+
+    fn serialize_state(&self, writer: &mut StateWriter) -> Result<(), BoxedError> {
+        Ok(serialize_bincode_into(self.top_n.state(), writer)?)
+    }
+
+    fn process(
+        &mut self,
+        op: TableOperation,
+        fw: &mut dyn ProcessorChannelForwarder,
+    ) -> Result<(), BoxedError> {
+        for output_op in self.execute(op.op)? {
+            fw.send(TableOperation::without_id(output_op, DEFAULT_PORT_HANDLE));
+        }
+        Ok(())
+    }
+}
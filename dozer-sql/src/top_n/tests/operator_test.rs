@@ -0,0 +1,192 @@
+use dozer_types::types::{Field, Operation, Record};
+
+use crate::top_n::operator::{SortDirection, SortKey, SortValue, TopN, TopNState};
+
+fn key(id: i64, score: i64) -> SortKey {
+    (
+        vec![SortValue {
+            value: Field::Int(score),
+            direction: SortDirection::new(Some(false), None),
+        }],
+        vec![Field::Int(id), Field::Int(score)],
+    )
+}
+
+fn row(id: i64, score: i64) -> Record {
+    Record::new(vec![Field::Int(id), Field::Int(score)])
+}
+
+fn numbered_row(id: i64, score: i64, row_number: u64) -> Record {
+    Record::new(vec![
+        Field::Int(id),
+        Field::Int(score),
+        Field::UInt(row_number),
+    ])
+}
+
+#[test]
+fn test_sort_direction() {
+    let null = |direction| SortValue {
+        value: Field::Null,
+        direction,
+    };
+    let one = |direction| SortValue {
+        value: Field::Int(1),
+        direction,
+    };
+    let two = |direction| SortValue {
+        value: Field::Int(2),
+        direction,
+    };
+
+    let asc = SortDirection::new(None, None);
+    assert!(one(asc) < two(asc));
+    assert!(two(asc) < null(asc));
+
+    let desc = SortDirection::new(Some(false), None);
+    assert!(two(desc) < one(desc));
+    assert!(null(desc) < two(desc));
+
+    let asc_nulls_first = SortDirection::new(Some(true), Some(true));
+    assert!(null(asc_nulls_first) < one(asc_nulls_first));
+}
+
+#[test]
+fn test_top_n_insert_and_delete() {
+    let mut top_n = TopN::new(0, 2, false, vec![0], TopNState::default());
+    let partition = vec![];
+
+    assert_eq!(
+        top_n.apply(partition.clone(), vec![], vec![key(1, 10)]),
+        vec![Operation::Insert { new: row(1, 10) }]
+    );
+    assert_eq!(
+        top_n.apply(partition.clone(), vec![], vec![key(2, 30)]),
+        vec![Operation::Insert { new: row(2, 30) }]
+    );
+    // Pushes the lowest score out of the top 2.
+    assert_eq!(
+        top_n.apply(partition.clone(), vec![], vec![key(3, 20)]),
+        vec![
+            Operation::Delete { old: row(1, 10) },
+            Operation::Insert { new: row(3, 20) },
+        ]
+    );
+    // Doesn't make it into the top 2.
+    assert_eq!(
+        top_n.apply(partition.clone(), vec![], vec![key(4, 5)]),
+        vec![]
+    );
+    // The next row takes the place of the deleted one.
+    assert_eq!(
+        top_n.apply(partition.clone(), vec![key(2, 30)], vec![]),
+        vec![
+            Operation::Delete { old: row(2, 30) },
+            Operation::Insert { new: row(1, 10) },
+        ]
+    );
+    // Stays in the top 2.
+    assert_eq!(
+        top_n.apply(partition, vec![key(1, 10)], vec![key(1, 40)]),
+        vec![Operation::Update {
+            old: row(1, 10),
+            new: row(1, 40),
+        }]
+    );
+}
+
+#[test]
+fn test_top_n_offset() {
+    let mut top_n = TopN::new(1, 1, false, vec![0], TopNState::default());
+    let partition = vec![];
+
+    assert_eq!(
+        top_n.apply(partition.clone(), vec![], vec![key(1, 10)]),
+        vec![]
+    );
+    assert_eq!(
+        top_n.apply(partition.clone(), vec![], vec![key(2, 20)]),
+        vec![Operation::Insert { new: row(1, 10) }]
+    );
+    assert_eq!(
+        top_n.apply(partition, vec![], vec![key(3, 30)]),
+        vec![
+            Operation::Delete { old: row(1, 10) },
+            Operation::Insert { new: row(2, 20) },
+        ]
+    );
+}
+
+#[test]
+fn test_top_n_partitions() {
+    let mut top_n = TopN::new(0, 1, false, vec![0], TopNState::default());
+
+    assert_eq!(
+        top_n.apply(vec![Field::Int(1)], vec![], vec![key(1, 10)]),
+        vec![Operation::Insert { new: row(1, 10) }]
+    );
+    assert_eq!(
+        top_n.apply(vec![Field::Int(2)], vec![], vec![key(2, 5)]),
+        vec![Operation::Insert { new: row(2, 5) }]
+    );
+    assert_eq!(
+        top_n.apply(vec![Field::Int(1)], vec![], vec![key(3, 5)]),
+        vec![]
+    );
+}
+
+#[test]
+fn test_top_n_row_number() {
+    let mut top_n = TopN::new(0, 2, true, vec![0], TopNState::default());
+    let partition = vec![];
+
+    assert_eq!(
+        top_n.apply(partition.clone(), vec![], vec![key(1, 10)]),
+        vec![Operation::Insert {
+            new: numbered_row(1, 10, 1)
+        }]
+    );
+    // Moves the existing row down to second place.
+    assert_eq!(
+        top_n.apply(partition.clone(), vec![], vec![key(2, 20)]),
+        vec![
+            Operation::Update {
+                old: numbered_row(1, 10, 1),
+                new: numbered_row(1, 10, 2),
+            },
+            Operation::Insert {
+                new: numbered_row(2, 20, 1)
+            },
+        ]
+    );
+    // Takes second place and pushes the last row out.
+    assert_eq!(
+        top_n.apply(partition, vec![], vec![key(3, 15)]),
+        vec![
+            Operation::Delete {
+                old: numbered_row(1, 10, 2)
+            },
+            Operation::Insert {
+                new: numbered_row(3, 15, 2)
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_top_n_duplicates() {
+    let mut top_n = TopN::new(0, 3, false, vec![], TopNState::default());
+    let partition = vec![];
+
+    assert_eq!(
+        top_n.apply(partition.clone(), vec![], vec![key(1, 10), key(1, 10)]),
+        vec![
+            Operation::Insert { new: row(1, 10) },
+            Operation::Insert { new: row(1, 10) },
+        ]
+    );
+    assert_eq!(
+        top_n.apply(partition, vec![key(1, 10)], vec![]),
+        vec![Operation::Delete { old: row(1, 10) }]
+    );
+}
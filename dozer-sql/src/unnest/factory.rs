@@ -0,0 +1,107 @@
+use std::{collections::HashMap, sync::Arc};
+
+use dozer_core::{
+    event::EventHub,
+    node::{PortHandle, Processor, ProcessorFactory},
+    DEFAULT_PORT_HANDLE,
+};
+use dozer_sql_expression::{
+    builder::ExpressionBuilder, execution::Expression, sqlparser::ast::Expr as SqlExpr,
+};
+use dozer_types::{
+    errors::internal::BoxedError, models::udf_config::UdfConfig, tonic::async_trait, types::Schema,
+};
+use tokio::runtime::Runtime;
+
+use crate::errors::PipelineError;
+
+use super::{
+    operator::{get_output_schema, UnnestColumns, UnnestOperator},
+    processor::UnnestProcessor,
+};
+
+#[derive(Debug)]
+pub struct UnnestProcessorFactory {
+    id: String,
+    array_expr: SqlExpr,
+    columns: UnnestColumns,
+    udfs: Vec<UdfConfig>,
+    runtime: Arc<Runtime>,
+}
+
+impl UnnestProcessorFactory {
+    pub fn new(
+        id: String,
+        array_expr: SqlExpr,
+        columns: UnnestColumns,
+        udfs: Vec<UdfConfig>,
+        runtime: Arc<Runtime>,
+    ) -> Self {
+        Self {
+            id,
+            array_expr,
+            columns,
+            udfs,
+            runtime,
+        }
+    }
+
+    async fn build_expression(&self, input_schema: &Schema) -> Result<Expression, PipelineError> {
+        Ok(
+            ExpressionBuilder::new(input_schema.fields.len(), self.runtime.clone())
+                .build(false, &self.array_expr, input_schema, &self.udfs)
+                .await?,
+        )
+    }
+}
+
+#[async_trait]
+impl ProcessorFactory for UnnestProcessorFactory {
+    fn id(&self) -> String {
+        self.id.clone()
+    }
+
+    fn type_name(&self) -> String {
+        "Unnest".to_string()
+    }
+
+    fn get_input_ports(&self) -> Vec<PortHandle> {
+        vec![DEFAULT_PORT_HANDLE]
+    }
+
+    fn get_output_ports(&self) -> Vec<PortHandle> {
+        vec![DEFAULT_PORT_HANDLE]
+    }
+
+    async fn get_output_schema(
+        &self,
+        _output_port: &PortHandle,
+        input_schemas: &HashMap<PortHandle, Schema>,
+    ) -> Result<Schema, BoxedError> {
+        let input_schema = input_schemas
+            .get(&DEFAULT_PORT_HANDLE)
+            .ok_or(PipelineError::InvalidPortHandle(DEFAULT_PORT_HANDLE))?;
+
+        let expression = self.build_expression(input_schema).await?;
+        Ok(get_output_schema(input_schema, &expression, &self.columns)?)
+    }
+
+    async fn build(
+        &self,
+        input_schemas: HashMap<PortHandle, Schema>,
+        _output_schemas: HashMap<PortHandle, Schema>,
+        _event_hub: EventHub,
+        _checkpoint_data: Option<Vec<u8>>,
+    ) -> Result<Box<dyn Processor>, BoxedError> {
+        let input_schema = input_schemas
+            .get(&DEFAULT_PORT_HANDLE)
+            .ok_or(PipelineError::InvalidPortHandle(DEFAULT_PORT_HANDLE))?;
+
+        let expression = self.build_expression(input_schema).await?;
+        Ok(Box::new(UnnestProcessor::new(UnnestOperator::new(
+            input_schema.clone(),
+            expression,
+            self.columns.offset.is_some(),
+        ))))
+    }
+}
@@ -0,0 +1,113 @@
+use dozer_sql_expression::execution::Expression;
+use dozer_types::types::{Field, FieldDefinition, FieldType, Record, Schema, SourceDefinition};
+
+use crate::errors::PipelineError;
+
+pub const DEFAULT_ELEMENT_COLUMN: &str = "unnest";
+pub const DEFAULT_OFFSET_COLUMN: &str = "offset";
+
+/// Names of the columns an `UNNEST` appends to its input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnnestColumns {
+    /// Table alias the appended columns are attributed to, if any.
+    pub alias: Option<String>,
+    pub element: String,
+    /// Present when `WITH OFFSET` was requested.
+    pub offset: Option<String>,
+}
+
+/// Flattens a list valued expression, emitting one record per element.
+#[derive(Debug)]
+pub struct UnnestOperator {
+    input_schema: Schema,
+    expression: Expression,
+    with_offset: bool,
+}
+
+impl UnnestOperator {
+    pub fn new(input_schema: Schema, expression: Expression, with_offset: bool) -> Self {
+        Self {
+            input_schema,
+            expression,
+            with_offset,
+        }
+    }
+
+    /// Returns the input record extended with each element of the list (and its 0-based offset).
+    /// NULL and empty lists produce no records.
+    pub fn unnest(&mut self, record: &Record) -> Result<Vec<Record>, PipelineError> {
+        let list = self.expression.evaluate(record, &self.input_schema)?;
+        let elements = match list {
+            Field::List(elements) => elements,
+            Field::Null => return Ok(vec![]),
+            other => {
+                return Err(PipelineError::InvalidUnnest(format!(
+                    "expected a list, got {other}"
+                )))
+            }
+        };
+
+        Ok(elements
+            .into_iter()
+            .enumerate()
+            .map(|(offset, element)| {
+                let mut values = record.values.clone();
+                values.push(element);
+                if self.with_offset {
+                    values.push(Field::UInt(offset as u64));
+                }
+                Record {
+                    values,
+                    lifetime: record.lifetime.clone(),
+                }
+            })
+            .collect())
+    }
+}
+
+pub fn get_output_schema(
+    input_schema: &Schema,
+    expression: &Expression,
+    columns: &UnnestColumns,
+) -> Result<Schema, PipelineError> {
+    let list_type = expression.get_type(input_schema)?.return_type;
+    let Some(element_type) = list_type.element_type() else {
+        return Err(PipelineError::InvalidUnnest(format!(
+            "expected a list, got {list_type}"
+        )));
+    };
+
+    let source = match &columns.alias {
+        Some(alias) => SourceDefinition::Alias {
+            name: alias.clone(),
+        },
+        None => SourceDefinition::Dynamic,
+    };
+
+    let mut output_schema = input_schema.clone();
+    output_schema.fields.push(FieldDefinition::new(
+        columns.element.clone(),
+        element_type.clone(),
+        true,
+        source.clone(),
+    ));
+
+    match &columns.offset {
+        // An input record and an offset identify an output record.
+        Some(offset) if !input_schema.primary_index.is_empty() => {
+            output_schema.field(
+                FieldDefinition::new(offset.clone(), FieldType::UInt, false, source),
+                true,
+            );
+        }
+        Some(offset) => {
+            output_schema.field(
+                FieldDefinition::new(offset.clone(), FieldType::UInt, false, source),
+                false,
+            );
+        }
+        None => output_schema.primary_index.clear(),
+    }
+
+    Ok(output_schema)
+}
@@ -0,0 +1,79 @@
+use dozer_core::checkpoint::StateWriter;
+
+use dozer_core::channels::ProcessorChannelForwarder;
+use dozer_core::epoch::Epoch;
+use dozer_core::node::Processor;
+use dozer_types::errors::internal::BoxedError;
+use dozer_types::types::{Operation, Record, TableOperation};
+
+use crate::errors::PipelineError;
+use crate::utils::send_batched;
+
+use super::operator::UnnestOperator;
+
+#[derive(Debug)]
+pub struct UnnestProcessor {
+    operator: UnnestOperator,
+}
+
+impl UnnestProcessor {
+    pub fn new(operator: UnnestOperator) -> Self {
+        Self { operator }
+    }
+
+    fn delete(&mut self, old: &Record) -> Result<Vec<Operation>, PipelineError> {
+        Ok(self
+            .operator
+            .unnest(old)?
+            .into_iter()
+            .map(|old| Operation::Delete { old })
+            .collect())
+    }
+
+    fn insert(&mut self, new: &Record) -> Result<Vec<Operation>, PipelineError> {
+        Ok(self
+            .operator
+            .unnest(new)?
+            .into_iter()
+            .map(|new| Operation::Insert { new })
+            .collect())
+    }
+
+    fn execute(&mut self, op: Operation) -> Result<Vec<Operation>, PipelineError> {
+        Ok(match op {
+            Operation::Delete { old } => self.delete(&old)?,
+            Operation::Insert { new } => self.insert(&new)?,
+            Operation::Update { old, new } => {
+                let mut ops = self.delete(&old)?;
+                ops.extend(self.insert(&new)?);
+                ops
+            }
+            Operation::BatchInsert { new } => {
+                let mut ops = vec![];
+                for record in new {
+                    ops.extend(self.insert(&record)?);
+                }
+                ops
+            }
+        })
+    }
+}
+
+impl Processor for UnnestProcessor {
+    fn commit(&self, _epoch: &Epoch) -> Result<(), BoxedError> {
+        Ok(())
+    }
+
+    fn serialize_state(&self, _writer: &mut StateWriter) -> Result<(), BoxedError> {
+        Ok(())
+    }
+
+    fn process(
+        &mut self,
+        op: TableOperation,
+        fw: &mut dyn ProcessorChannelForwarder,
+    ) -> Result<(), BoxedError> {
+        send_batched(self.execute(op.op)?, fw);
+        Ok(())
+    }
+}
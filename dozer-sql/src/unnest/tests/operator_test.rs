@@ -0,0 +1,136 @@
+use dozer_sql_expression::execution::Expression;
+use dozer_types::types::{Field, FieldDefinition, FieldType, Record, Schema, SourceDefinition};
+
+use crate::unnest::operator::{get_output_schema, UnnestColumns, UnnestOperator};
+
+fn input_schema() -> Schema {
+    Schema::default()
+        .field(
+            FieldDefinition::new(
+                "id".to_string(),
+                FieldType::Int,
+                false,
+                SourceDefinition::Table {
+                    connection: "conn".to_string(),
+                    name: "posts".to_string(),
+                },
+            ),
+            true,
+        )
+        .field(
+            FieldDefinition::new(
+                "tags".to_string(),
+                FieldType::List(Box::new(FieldType::String)),
+                true,
+                SourceDefinition::Table {
+                    connection: "conn".to_string(),
+                    name: "posts".to_string(),
+                },
+            ),
+            false,
+        )
+        .clone()
+}
+
+fn tags(values: &[&str]) -> Field {
+    Field::List(
+        values
+            .iter()
+            .map(|value| Field::String(value.to_string()))
+            .collect(),
+    )
+}
+
+#[test]
+fn test_unnest() {
+    let mut operator = UnnestOperator::new(input_schema(), Expression::Column { index: 1 }, false);
+
+    let record = Record::new(vec![Field::Int(1), tags(&["a", "b"])]);
+    assert_eq!(
+        operator.unnest(&record).unwrap(),
+        vec![
+            Record::new(vec![
+                Field::Int(1),
+                tags(&["a", "b"]),
+                Field::String("a".to_string())
+            ]),
+            Record::new(vec![
+                Field::Int(1),
+                tags(&["a", "b"]),
+                Field::String("b".to_string())
+            ]),
+        ]
+    );
+
+    let record = Record::new(vec![Field::Int(2), tags(&[])]);
+    assert_eq!(operator.unnest(&record).unwrap(), vec![]);
+
+    let record = Record::new(vec![Field::Int(3), Field::Null]);
+    assert_eq!(operator.unnest(&record).unwrap(), vec![]);
+}
+
+#[test]
+fn test_unnest_with_offset() {
+    let mut operator = UnnestOperator::new(input_schema(), Expression::Column { index: 1 }, true);
+
+    let record = Record::new(vec![Field::Int(1), tags(&["a", "b"])]);
+    let offsets = operator
+        .unnest(&record)
+        .unwrap()
+        .into_iter()
+        .map(|record| record.values[3].clone())
+        .collect::<Vec<_>>();
+    assert_eq!(offsets, vec![Field::UInt(0), Field::UInt(1)]);
+}
+
+#[test]
+fn test_unnest_schema() {
+    let input_schema = input_schema();
+    let expression = Expression::Column { index: 1 };
+
+    let output_schema = get_output_schema(
+        &input_schema,
+        &expression,
+        &UnnestColumns {
+            alias: Some("t".to_string()),
+            element: "tag".to_string(),
+            offset: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(output_schema.fields.len(), 3);
+    assert_eq!(output_schema.fields[2].name, "tag");
+    assert_eq!(output_schema.fields[2].typ, FieldType::String);
+    assert_eq!(
+        output_schema.fields[2].source,
+        SourceDefinition::Alias {
+            name: "t".to_string()
+        }
+    );
+    assert!(output_schema.primary_index.is_empty());
+
+    let output_schema = get_output_schema(
+        &input_schema,
+        &expression,
+        &UnnestColumns {
+            alias: None,
+            element: "tag".to_string(),
+            offset: Some("offset".to_string()),
+        },
+    )
+    .unwrap();
+    assert_eq!(output_schema.fields[3].name, "offset");
+    assert_eq!(output_schema.fields[3].typ, FieldType::UInt);
+    assert_eq!(output_schema.primary_index, vec![0, 3]);
+
+    assert!(get_output_schema(
+        &input_schema,
+        &Expression::Column { index: 0 },
+        &UnnestColumns {
+            alias: None,
+            element: "tag".to_string(),
+            offset: None,
+        },
+    )
+    .is_err());
+}
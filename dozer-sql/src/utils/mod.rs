@@ -1 +1,33 @@
+use dozer_core::channels::ProcessorChannelForwarder;
+use dozer_core::DEFAULT_PORT_HANDLE;
+use dozer_types::types::{Operation, Record, TableOperation};
+
 pub mod record_hashtable_key;
+
+/// Sends `ops` to the default port, with consecutive inserts sent as a batch.
+pub fn send_batched(ops: Vec<Operation>, fw: &mut dyn ProcessorChannelForwarder) {
+    let mut batch = vec![];
+    for op in ops {
+        match op {
+            Operation::Insert { new } => batch.push(new),
+            op => {
+                send_batch(&mut batch, fw);
+                fw.send(TableOperation::without_id(op, DEFAULT_PORT_HANDLE));
+            }
+        }
+    }
+    send_batch(&mut batch, fw);
+}
+
+fn send_batch(batch: &mut Vec<Record>, fw: &mut dyn ProcessorChannelForwarder) {
+    let op = match batch.len() {
+        0 => return,
+        1 => Operation::Insert {
+            new: batch.remove(0),
+        },
+        _ => Operation::BatchInsert {
+            new: std::mem::take(batch),
+        },
+    };
+    fw.send(TableOperation::without_id(op, DEFAULT_PORT_HANDLE));
+}
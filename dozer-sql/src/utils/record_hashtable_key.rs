@@ -1,9 +1,9 @@
-use ahash::AHasher;
+use ahash::RandomState;
 use dozer_types::{
     serde::{Deserialize, Serialize},
     types::Field,
 };
-use std::hash::{Hash, Hasher};
+use std::hash::{BuildHasher, Hash, Hasher};
 
 #[derive(
     Clone, Debug, Hash, PartialEq, Eq, bincode::Encode, bincode::Decode, Deserialize, Serialize,
@@ -14,11 +14,19 @@ pub enum RecordKey {
     Hash(u64),
 }
 
+/// Record hashes are checkpointed, so they must not use `ahash`'s default per-process random seeds.
+const RECORD_HASH_STATE: RandomState = RandomState::with_seeds(
+    0xd7e074eba8c38a15,
+    0x205f6cb52f17e78e,
+    0x1c51eb4ba2cef14a,
+    0x600bdd476d354025,
+);
+
 pub fn get_record_hash<'a, I>(fields_iter: I) -> u64
 where
     I: Iterator<Item = &'a Field>,
 {
-    let mut hasher = AHasher::default();
+    let mut hasher = RECORD_HASH_STATE.build_hasher();
     for field in fields_iter {
         field.hash(&mut hasher);
     }
@@ -36,3 +44,9 @@ fn test_record_hash() {
     assert_ne!(record_a, record_b);
     assert_ne!(hash_a, hash_b);
 }
+
+#[test]
+fn test_record_hash_is_stable() {
+    let record = [Field::Int(1), Field::String("a".into())];
+    assert_eq!(get_record_hash(record.iter()), 15689098199109662592);
+}
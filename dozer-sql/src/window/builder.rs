@@ -19,8 +19,14 @@ const ARG_COLUMN: usize = 1;
 
 const ARG_TUMBLE_INTERVAL: usize = 2;
 
+const ARG_TUMBLE_WATERMARK: usize = 3;
+
 const ARG_HOP_SIZE: usize = 2;
 const ARG_HOP_INTERVAL: usize = 3;
+const ARG_HOP_WATERMARK: usize = 4;
+
+const ARG_SESSION_GAP: usize = 2;
+const ARG_SESSION_WATERMARK: usize = 3;
 
 pub(crate) fn window_from_table_operator(
     operator: &TableOperatorDescriptor,
@@ -71,6 +77,18 @@ pub(crate) fn window_from_table_operator(
             hop_size,
             interval,
         }));
+    } else if operator.name.to_uppercase() == "SESSION" {
+        let column_index = get_window_column_index(&operator.args, schema)?;
+        let gap_arg = operator
+            .args
+            .get(ARG_SESSION_GAP)
+            .ok_or(WindowError::WindowMissingGapArgument)?;
+        let TableOperatorArg::Argument(argument) = gap_arg else {
+            return Err(WindowError::WindowInvalidGap("".to_string()));
+        };
+        let gap = get_window_duration(argument, WindowError::WindowInvalidGap)?;
+
+        return Ok(Some(WindowType::Session { column_index, gap }));
     } else {
         return Err(WindowError::UnsupportedRelationFunction(
             operator.name.clone(),
@@ -78,6 +96,50 @@ pub(crate) fn window_from_table_operator(
     }
 }
 
+/// The watermark delay, the optional last argument of a window function.
+pub(crate) fn watermark_from_table_operator(
+    operator: &TableOperatorDescriptor,
+) -> Result<Option<Duration>, WindowError> {
+    let index = match operator.name.to_uppercase().as_str() {
+        "TUMBLE" => ARG_TUMBLE_WATERMARK,
+        "HOP" => ARG_HOP_WATERMARK,
+        "SESSION" => ARG_SESSION_WATERMARK,
+        _ => {
+            return Err(WindowError::UnsupportedRelationFunction(
+                operator.name.clone(),
+            ))
+        }
+    };
+    match operator.args.get(index) {
+        None => Ok(None),
+        Some(TableOperatorArg::Argument(argument)) => Ok(Some(get_window_duration(
+            argument,
+            WindowError::WindowInvalidWatermark,
+        )?)),
+        Some(TableOperatorArg::Descriptor(_)) => {
+            Err(WindowError::WindowInvalidWatermark("".to_string()))
+        }
+    }
+}
+
+fn get_window_duration(
+    arg: &FunctionArg,
+    invalid: fn(String) -> WindowError,
+) -> Result<Duration, WindowError> {
+    match arg {
+        FunctionArg::Unnamed(FunctionArgExpr::Expr(Expr::Value(
+            Value::SingleQuotedString(s) | Value::DoubleQuotedString(s),
+        ))) => {
+            let duration = parse_duration_string(s).map_err(|_| invalid(s.to_owned()))?;
+            if duration <= Duration::zero() {
+                return Err(invalid(s.to_owned()));
+            }
+            Ok(duration)
+        }
+        _ => Err(invalid(arg.to_string())),
+    }
+}
+
 fn get_window_interval(interval_arg: &FunctionArg) -> Result<Duration, WindowError> {
     match interval_arg {
         FunctionArg::Named { name, arg: _ } => {
@@ -12,7 +12,10 @@ use crate::{
     errors::{PipelineError, WindowError},
 };
 
-use super::{builder::window_from_table_operator, processor::WindowProcessor};
+use super::{
+    builder::{watermark_from_table_operator, window_from_table_operator},
+    processor::WindowProcessor,
+};
 
 #[derive(Debug)]
 pub struct WindowProcessorFactory {
@@ -73,6 +76,7 @@ impl ProcessorFactory for WindowProcessorFactory {
         input_schemas: HashMap<PortHandle, dozer_types::types::Schema>,
         _output_schemas: HashMap<PortHandle, dozer_types::types::Schema>,
         _event_hub: EventHub,
+        checkpoint_data: Option<Vec<u8>>,
     ) -> Result<Box<dyn Processor>, BoxedError> {
         let input_schema = input_schemas
             .get(&DEFAULT_PORT_HANDLE)
@@ -81,10 +85,17 @@ impl ProcessorFactory for WindowProcessorFactory {
             ))?
             .clone();
 
+        let watermark_delay =
+            watermark_from_table_operator(&self.table).map_err(PipelineError::WindowError)?;
         match window_from_table_operator(&self.table, &input_schema)
             .map_err(PipelineError::WindowError)?
         {
-            Some(window) => Ok(Box::new(WindowProcessor::new(self.id.clone(), window))),
+            Some(window) => Ok(Box::new(WindowProcessor::new(
+                self.id.clone(),
+                window,
+                watermark_delay,
+                checkpoint_data,
+            )?)),
             None => Err(PipelineError::WindowError(WindowError::InvalidWindow()).into()),
         }
     }
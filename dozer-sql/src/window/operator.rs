@@ -1,10 +1,16 @@
 use dozer_types::{
+    bincode,
     chrono::{Duration, DurationRound},
-    types::{Field, FieldDefinition, FieldType, Record, Schema, SourceDefinition},
+    types::{
+        Field, FieldDefinition, FieldType, Lifetime, Operation, Record, Schema, SourceDefinition,
+        Timestamp,
+    },
 };
 
 use crate::errors::WindowError;
 
+use super::session::{EventTime, Sessions};
+
 #[derive(Clone, Debug)]
 pub enum WindowType {
     Tumble {
@@ -16,6 +22,10 @@ pub enum WindowType {
         hop_size: Duration,
         interval: Duration,
     },
+    Session {
+        column_index: usize,
+        gap: Duration,
+    },
 }
 
 impl WindowType {
@@ -30,6 +40,16 @@ impl WindowType {
                 hop_size,
                 interval,
             } => execute_hop_window(record, *column_index, *hop_size, *interval),
+            // Sessions depend on the other records, see `WindowOperator`.
+            WindowType::Session { .. } => Err(WindowError::InvalidWindow()),
+        }
+    }
+
+    fn column_index(&self) -> usize {
+        match self {
+            WindowType::Tumble { column_index, .. }
+            | WindowType::Hop { column_index, .. }
+            | WindowType::Session { column_index, .. } => *column_index,
         }
     }
 
@@ -128,3 +148,176 @@ fn tumble(field: &Field, interval: Duration) -> Result<(Field, Field), WindowErr
         Err(WindowError::TumbleInvalidColumnType())
     }
 }
+
+#[derive(Debug, Default, bincode::Encode, bincode::Decode)]
+pub struct WindowState {
+    /// The latest event time seen, which the watermark trails by the watermark delay.
+    max_event_time: Option<EventTime>,
+    sessions: Sessions,
+}
+
+/// Assigns records to windows.
+///
+/// With a watermark delay, a window closes once the watermark passes its end:
+/// changes to closed windows are dropped as late, and the window records carry a lifetime
+/// that ends with the window, so that downstream processors can evict the window's state.
+#[derive(Debug)]
+pub struct WindowOperator {
+    window: WindowType,
+    watermark_delay: Option<Duration>,
+    state: WindowState,
+    late_records: u64,
+}
+
+impl WindowOperator {
+    pub fn new(window: WindowType, watermark_delay: Option<Duration>, state: WindowState) -> Self {
+        Self {
+            window,
+            watermark_delay,
+            state,
+            late_records: 0,
+        }
+    }
+
+    pub fn state(&self) -> &WindowState {
+        &self.state
+    }
+
+    /// The number of changes dropped as late since the last call.
+    pub fn take_late_records(&mut self) -> u64 {
+        std::mem::take(&mut self.late_records)
+    }
+
+    /// Moves the watermark to `time` minus the watermark delay, unless it's already past that.
+    pub fn advance(&mut self, time: Timestamp) {
+        let time = EventTime(time);
+        if self.state.max_event_time.map_or(true, |max| max < time) {
+            self.state.max_event_time = Some(time);
+        }
+        if let (WindowType::Session { gap, .. }, Some(watermark)) = (&self.window, self.watermark())
+        {
+            self.state.sessions.evict(watermark, *gap);
+        }
+    }
+
+    pub fn insert(&mut self, record: Record) -> Result<Vec<Operation>, WindowError> {
+        let time = self.get_event_time(&record)?;
+        self.advance(time);
+        match self.window {
+            WindowType::Session { gap, .. } => {
+                if self.is_closed(time + gap) {
+                    self.late_records += 1;
+                    return Ok(vec![]);
+                }
+                let delay = self.watermark_delay;
+                Ok(self
+                    .state
+                    .sessions
+                    .insert(record, time, gap, |record, time, start, end| {
+                        session_record(record, time, start, end, delay)
+                    }))
+            }
+            _ => Ok(self
+                .get_windows(record, time)?
+                .into_iter()
+                .map(|new| Operation::Insert { new })
+                .collect()),
+        }
+    }
+
+    pub fn delete(&mut self, record: Record) -> Result<Vec<Operation>, WindowError> {
+        let time = self.get_event_time(&record)?;
+        match self.window {
+            WindowType::Session { gap, .. } => {
+                let delay = self.watermark_delay;
+                let ops =
+                    self.state
+                        .sessions
+                        .delete(&record, time, gap, |record, time, start, end| {
+                            session_record(record, time, start, end, delay)
+                        });
+                // Records of closed sessions have been evicted.
+                if ops.is_none() {
+                    self.late_records += 1;
+                }
+                Ok(ops.unwrap_or_default())
+            }
+            _ => Ok(self
+                .get_windows(record, time)?
+                .into_iter()
+                .map(|old| Operation::Delete { old })
+                .collect()),
+        }
+    }
+
+    /// The `TUMBLE` or `HOP` windows of a record that are still open.
+    fn get_windows(&mut self, record: Record, time: Timestamp) -> Result<Vec<Record>, WindowError> {
+        let mut windows = vec![];
+        let mut is_late = false;
+        for mut window in self.window.execute(record)? {
+            let Some(Field::Timestamp(end)) = window.values.last() else {
+                return Err(WindowError::InvalidWindow());
+            };
+            let end = *end;
+            if self.is_closed(end) {
+                is_late = true;
+                continue;
+            }
+            set_window_lifetime(&mut window, time, end, self.watermark_delay);
+            windows.push(window);
+        }
+        if is_late {
+            self.late_records += 1;
+        }
+        Ok(windows)
+    }
+
+    fn get_event_time(&self, record: &Record) -> Result<Timestamp, WindowError> {
+        match &record.values[self.window.column_index()] {
+            Field::Timestamp(time) => Ok(*time),
+            _ => Err(match self.window {
+                WindowType::Tumble { .. } => WindowError::TumbleInvalidColumnType(),
+                WindowType::Hop { .. } => WindowError::HopInvalidColumnType(),
+                WindowType::Session { .. } => WindowError::SessionInvalidColumnType(),
+            }),
+        }
+    }
+
+    fn watermark(&self) -> Option<Timestamp> {
+        Some(self.state.max_event_time?.0 - self.watermark_delay?)
+    }
+
+    fn is_closed(&self, window_end: Timestamp) -> bool {
+        self.watermark()
+            .map_or(false, |watermark| window_end <= watermark)
+    }
+}
+
+/// Appends a session window to a record.
+fn session_record(
+    record: &Record,
+    time: Timestamp,
+    start: Timestamp,
+    end: Timestamp,
+    watermark_delay: Option<Duration>,
+) -> Record {
+    let mut session_record =
+        Record::appended(record, &[Field::Timestamp(start), Field::Timestamp(end)]);
+    set_window_lifetime(&mut session_record, time, end, watermark_delay);
+    session_record
+}
+
+/// Gives a window record of an event at `time` a lifetime that ends when the window closes.
+fn set_window_lifetime(
+    record: &mut Record,
+    time: Timestamp,
+    end: Timestamp,
+    watermark_delay: Option<Duration>,
+) {
+    if let Some(delay) = watermark_delay {
+        record.set_lifetime(Some(Lifetime {
+            reference: time,
+            duration: (end + delay - time).to_std().unwrap_or_default(),
+        }));
+    }
+}
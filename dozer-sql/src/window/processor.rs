@@ -1,85 +1,111 @@
+use std::sync::Mutex;
+
 use crate::errors::PipelineError;
+use crate::utils::send_batched;
 use dozer_core::channels::ProcessorChannelForwarder;
+use dozer_core::checkpoint::{deserialize_bincode, serialize_bincode_into, StateWriter};
 use dozer_core::epoch::Epoch;
 use dozer_core::node::Processor;
-use dozer_core::DEFAULT_PORT_HANDLE;
+use dozer_tracing::constants::{DOZER_METER_NAME, LATE_RECORDS_COUNTER_NAME, PROCESSOR_LABEL};
+use dozer_tracing::opentelemetry_metrics::Counter;
+use dozer_tracing::KeyValue;
+use dozer_types::chrono::{DateTime, Duration, TimeZone, Utc};
+use dozer_types::epoch::SourceTime;
 use dozer_types::errors::internal::BoxedError;
 use dozer_types::types::{Operation, TableOperation};
 
-use super::operator::WindowType;
+use super::operator::{WindowOperator, WindowType};
 
 #[derive(Debug)]
 pub struct WindowProcessor {
-    _id: String,
-    window: WindowType,
+    id: String,
+    operator: WindowOperator,
+    /// The source time of the last committed epoch, which the watermark doesn't fall behind.
+    source_time: Mutex<Option<SourceTime>>,
+    late_records_counter: Counter<u64>,
 }
 
 impl WindowProcessor {
-    pub fn new(id: String, window: WindowType) -> Self {
-        Self { _id: id, window }
+    pub fn new(
+        id: String,
+        window: WindowType,
+        watermark_delay: Option<Duration>,
+        checkpoint_data: Option<Vec<u8>>,
+    ) -> Result<Self, BoxedError> {
+        let state = match checkpoint_data {
+            // Windows without state checkpoint nothing.
+            Some(checkpoint_data) if !checkpoint_data.is_empty() => {
+                deserialize_bincode(&checkpoint_data)?
+            }
+            _ => Default::default(),
+        };
+        let late_records_counter = dozer_tracing::global::meter(DOZER_METER_NAME)
+            .u64_counter(LATE_RECORDS_COUNTER_NAME)
+            .with_description("Number of changes dropped because their window had closed")
+            .init();
+
+        Ok(Self {
+            id,
+            operator: WindowOperator::new(window, watermark_delay, state),
+            source_time: Mutex::new(None),
+            late_records_counter,
+        })
+    }
+
+    fn execute(&mut self, op: Operation) -> Result<Vec<Operation>, PipelineError> {
+        Ok(match op {
+            Operation::Delete { old } => self.operator.delete(old)?,
+            Operation::Insert { new } => self.operator.insert(new)?,
+            Operation::Update { old, new } => {
+                let mut ops = self.operator.delete(old)?;
+                ops.extend(self.operator.insert(new)?);
+                ops
+            }
+            Operation::BatchInsert { new } => {
+                let mut ops = vec![];
+                for record in new {
+                    ops.extend(self.operator.insert(record)?);
+                }
+                ops
+            }
+        })
     }
 }
 
 impl Processor for WindowProcessor {
-    fn commit(&self, _epoch: &Epoch) -> Result<(), BoxedError> {
+    fn commit(&self, epoch: &Epoch) -> Result<(), BoxedError> {
+        if let Some(source_time) = epoch.source_time {
+            *self.source_time.lock().unwrap() = Some(source_time);
+        }
         Ok(())
     }
 
+    fn serialize_state(&self, writer: &mut StateWriter) -> Result<(), BoxedError> {
+        Ok(serialize_bincode_into(self.operator.state(), writer)?)
+    }
+
     fn process(
         &mut self,
         op: TableOperation,
         fw: &mut dyn ProcessorChannelForwarder,
     ) -> Result<(), BoxedError> {
-        match op.op {
-            Operation::Delete { old } => {
-                let records = self
-                    .window
-                    .execute(old)
-                    .map_err(PipelineError::WindowError)?;
-                for record in records {
-                    fw.send(TableOperation::without_id(
-                        Operation::Delete { old: record },
-                        DEFAULT_PORT_HANDLE,
-                    ));
-                }
-            }
-            Operation::Insert { new } => {
-                let records = self
-                    .window
-                    .execute(new)
-                    .map_err(PipelineError::WindowError)?;
-                for record in records {
-                    fw.send(TableOperation::without_id(
-                        Operation::Insert { new: record },
-                        DEFAULT_PORT_HANDLE,
-                    ));
-                }
+        if let Some(source_time) = self.source_time.lock().unwrap().take() {
+            if let Some(time) = Utc
+                .timestamp_millis_opt(source_time.millis_since_epoch() as i64)
+                .single()
+            {
+                self.operator.advance(DateTime::from(time));
             }
-            Operation::Update { old, new } => {
-                self.process(
-                    TableOperation::without_id(Operation::Delete { old }, DEFAULT_PORT_HANDLE),
-                    fw,
-                )?;
+        }
 
-                self.process(
-                    TableOperation::without_id(Operation::Insert { new }, DEFAULT_PORT_HANDLE),
-                    fw,
-                )?;
-            }
-            Operation::BatchInsert { new } => {
-                let mut records = vec![];
-                for record in new {
-                    records.extend(
-                        self.window
-                            .execute(record)
-                            .map_err(PipelineError::WindowError)?,
-                    );
-                }
-                fw.send(TableOperation::without_id(
-                    Operation::BatchInsert { new: records },
-                    DEFAULT_PORT_HANDLE,
-                ));
-            }
+        send_batched(self.execute(op.op)?, fw);
+
+        let late_records = self.operator.take_late_records();
+        if late_records > 0 {
+            self.late_records_counter.add(
+                late_records,
+                &[KeyValue::new(PROCESSOR_LABEL, self.id.clone())],
+            );
         }
         Ok(())
     }
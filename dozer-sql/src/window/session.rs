@@ -0,0 +1,200 @@
+use std::collections::BTreeMap;
+use std::ops::Bound;
+
+use dozer_types::{
+    bincode,
+    chrono::Duration,
+    types::{Operation, Record, Timestamp},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, bincode::Encode, bincode::Decode)]
+pub struct EventTime(#[bincode(with_serde)] pub Timestamp);
+
+/// A session, from the event time of its first record to the event time of its last record.
+type Session = (Timestamp, Timestamp);
+
+/// The records of the open sessions by event time.
+///
+/// Records closer than `gap` to each other are in the same session,
+/// whose window starts at its first record and ends `gap` after its last record.
+#[derive(Debug, Default, bincode::Encode, bincode::Decode)]
+pub struct Sessions {
+    records: BTreeMap<EventTime, Vec<Record>>,
+}
+
+impl Sessions {
+    /// Adds a record at `time`, returning the operations on the window records of the sessions that changed.
+    ///
+    /// `window_record` appends a session window to a record.
+    pub fn insert(
+        &mut self,
+        record: Record,
+        time: Timestamp,
+        gap: Duration,
+        window_record: impl Fn(&Record, Timestamp, Timestamp, Timestamp) -> Record,
+    ) -> Vec<Operation> {
+        let before = self.get_sessions(time, gap);
+        self.records
+            .entry(EventTime(time))
+            .or_default()
+            .push(record.clone());
+        let after = self.get_sessions(time, gap);
+
+        let mut ops = self.delete_sessions(&before, &after, gap, &window_record);
+        for session in &after {
+            if !before.contains(session) {
+                ops.extend(
+                    self.session_records(session, gap, &window_record)
+                        .map(|new| Operation::Insert { new }),
+                );
+            } else if contains(session, time) {
+                ops.push(Operation::Insert {
+                    new: window_record(&record, time, session.0, session.1 + gap),
+                });
+            }
+        }
+        ops
+    }
+
+    /// Removes a record at `time`, returning the operations on the window records of the sessions that changed,
+    /// or `None` if the record isn't in an open session.
+    pub fn delete(
+        &mut self,
+        record: &Record,
+        time: Timestamp,
+        gap: Duration,
+        window_record: impl Fn(&Record, Timestamp, Timestamp, Timestamp) -> Record,
+    ) -> Option<Vec<Operation>> {
+        let before = self.get_sessions(time, gap);
+        let records = self.records.get_mut(&EventTime(time))?;
+        let index = records
+            .iter()
+            .position(|existing| existing.values == record.values)?;
+        let record = records.remove(index);
+        if records.is_empty() {
+            self.records.remove(&EventTime(time));
+        }
+        let after = self.get_sessions(time, gap);
+
+        let mut ops = self.delete_sessions(&before, &after, gap, &window_record);
+        if let Some(session) = before.iter().find(|session| contains(session, time)) {
+            ops.push(Operation::Delete {
+                old: window_record(&record, time, session.0, session.1 + gap),
+            });
+        }
+        for session in &after {
+            if !before.contains(session) {
+                ops.extend(
+                    self.session_records(session, gap, &window_record)
+                        .map(|new| Operation::Insert { new }),
+                );
+            }
+        }
+        Some(ops)
+    }
+
+    /// Removes the sessions whose windows end at or before `watermark`.
+    pub fn evict(&mut self, watermark: Timestamp, gap: Duration) {
+        while let Some((first, _)) = self.records.first_key_value() {
+            let (start, end) = self.get_session_around(first.0, gap);
+            if end + gap > watermark {
+                break;
+            }
+            let times = self
+                .records
+                .range(EventTime(start)..=EventTime(end))
+                .map(|(time, _)| *time)
+                .collect::<Vec<_>>();
+            for time in times {
+                self.records.remove(&time);
+            }
+        }
+    }
+
+    /// Deletes the window records of the sessions in `before` that aren't in `after`,
+    /// leaving out the record that was just inserted or deleted.
+    fn delete_sessions(
+        &self,
+        before: &[Session],
+        after: &[Session],
+        gap: Duration,
+        window_record: &impl Fn(&Record, Timestamp, Timestamp, Timestamp) -> Record,
+    ) -> Vec<Operation> {
+        let mut ops = vec![];
+        for session in before {
+            if !after.contains(session) {
+                ops.extend(
+                    self.session_records(session, gap, window_record)
+                        .map(|old| Operation::Delete { old }),
+                );
+            }
+        }
+        ops
+    }
+
+    fn session_records<'a>(
+        &'a self,
+        session: &Session,
+        gap: Duration,
+        window_record: &'a impl Fn(&Record, Timestamp, Timestamp, Timestamp) -> Record,
+    ) -> impl Iterator<Item = Record> + 'a {
+        let (start, last) = *session;
+        self.records
+            .range(EventTime(start)..=EventTime(last))
+            .flat_map(move |(time, records)| {
+                records
+                    .iter()
+                    .map(move |record| window_record(record, time.0, start, last + gap))
+            })
+    }
+
+    /// The sessions with records within `gap` of `time`, which are the ones a record at `time` can change.
+    fn get_sessions(&self, time: Timestamp, gap: Duration) -> Vec<Session> {
+        let times = self
+            .records
+            .range(EventTime(time - gap)..=EventTime(time + gap))
+            .map(|(time, _)| time.0)
+            .collect::<Vec<_>>();
+        let (Some(first), Some(last)) = (times.first(), times.last()) else {
+            return vec![];
+        };
+        let start = self.get_session_around(*first, gap).0;
+        let end = self.get_session_around(*last, gap).1;
+
+        let mut sessions: Vec<Session> = vec![];
+        for (time, _) in self.records.range(EventTime(start)..=EventTime(end)) {
+            match sessions.last_mut() {
+                Some(session) if time.0 - session.1 < gap => session.1 = time.0,
+                _ => sessions.push((time.0, time.0)),
+            }
+        }
+        sessions
+    }
+
+    /// The session of the record at `time`.
+    fn get_session_around(&self, time: Timestamp, gap: Duration) -> Session {
+        let mut start = time;
+        while let Some((previous, _)) = self.records.range(..EventTime(start)).next_back() {
+            if start - previous.0 >= gap {
+                break;
+            }
+            start = previous.0;
+        }
+        let mut end = time;
+        while let Some((next, _)) = self
+            .records
+            .range((Bound::Excluded(EventTime(end)), Bound::Unbounded))
+            .next()
+        {
+            if next.0 - end >= gap {
+                break;
+            }
+            end = next.0;
+        }
+        (start, end)
+    }
+}
+
+fn contains(session: &Session, time: Timestamp) -> bool {
+    session.0 <= time && time <= session.1
+}
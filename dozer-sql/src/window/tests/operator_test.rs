@@ -1,10 +1,10 @@
-use dozer_types::types::Record;
+use dozer_types::types::{Lifetime, Operation, Record};
 use dozer_types::{
     chrono::{DateTime, Duration},
     types::{Field, FieldDefinition, FieldType, Schema, SourceDefinition},
 };
 
-use crate::window::operator::WindowType;
+use crate::window::operator::{WindowOperator, WindowState, WindowType};
 
 #[test]
 fn test_hop() {
@@ -145,3 +145,174 @@ fn test_window_schema() {
 
     assert_eq!(result, expected_schema);
 }
+
+fn at_minute(minute: i64) -> Field {
+    Field::Timestamp(
+        DateTime::parse_from_rfc3339("2020-01-01T00:00:00Z").unwrap() + Duration::minutes(minute),
+    )
+}
+
+fn record_at_minute(id: i64, minute: i64) -> Record {
+    Record::new(vec![Field::Int(id), at_minute(minute)])
+}
+
+fn in_window(id: i64, minute: i64, start: i64, end: i64) -> Record {
+    Record::appended(
+        &record_at_minute(id, minute),
+        &[at_minute(start), at_minute(end)],
+    )
+}
+
+#[test]
+fn test_session() {
+    let mut window = WindowOperator::new(
+        WindowType::Session {
+            column_index: 1,
+            gap: Duration::minutes(5),
+        },
+        None,
+        WindowState::default(),
+    );
+
+    assert_eq!(
+        window.insert(record_at_minute(1, 0)).unwrap(),
+        vec![Operation::Insert {
+            new: in_window(1, 0, 0, 5)
+        }]
+    );
+    // Extends the session.
+    assert_eq!(
+        window.insert(record_at_minute(2, 2)).unwrap(),
+        vec![
+            Operation::Delete {
+                old: in_window(1, 0, 0, 5)
+            },
+            Operation::Insert {
+                new: in_window(1, 0, 0, 7)
+            },
+            Operation::Insert {
+                new: in_window(2, 2, 0, 7)
+            },
+        ]
+    );
+    // Starts a new session.
+    assert_eq!(
+        window.insert(record_at_minute(3, 10)).unwrap(),
+        vec![Operation::Insert {
+            new: in_window(3, 10, 10, 15)
+        }]
+    );
+    // Joins the sessions.
+    assert_eq!(
+        window.insert(record_at_minute(4, 6)).unwrap(),
+        vec![
+            Operation::Delete {
+                old: in_window(1, 0, 0, 7)
+            },
+            Operation::Delete {
+                old: in_window(2, 2, 0, 7)
+            },
+            Operation::Delete {
+                old: in_window(3, 10, 10, 15)
+            },
+            Operation::Insert {
+                new: in_window(1, 0, 0, 15)
+            },
+            Operation::Insert {
+                new: in_window(2, 2, 0, 15)
+            },
+            Operation::Insert {
+                new: in_window(4, 6, 0, 15)
+            },
+            Operation::Insert {
+                new: in_window(3, 10, 0, 15)
+            },
+        ]
+    );
+    // Doesn't change the session.
+    assert_eq!(
+        window.insert(record_at_minute(5, 1)).unwrap(),
+        vec![Operation::Insert {
+            new: in_window(5, 1, 0, 15)
+        }]
+    );
+    assert_eq!(
+        window.delete(record_at_minute(5, 1)).unwrap(),
+        vec![Operation::Delete {
+            old: in_window(5, 1, 0, 15)
+        }]
+    );
+    // Splits the session.
+    assert_eq!(
+        window.delete(record_at_minute(4, 6)).unwrap(),
+        vec![
+            Operation::Delete {
+                old: in_window(1, 0, 0, 15)
+            },
+            Operation::Delete {
+                old: in_window(2, 2, 0, 15)
+            },
+            Operation::Delete {
+                old: in_window(3, 10, 0, 15)
+            },
+            Operation::Delete {
+                old: in_window(4, 6, 0, 15)
+            },
+            Operation::Insert {
+                new: in_window(1, 0, 0, 7)
+            },
+            Operation::Insert {
+                new: in_window(2, 2, 0, 7)
+            },
+            Operation::Insert {
+                new: in_window(3, 10, 10, 15)
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_watermark() {
+    let mut window = WindowOperator::new(
+        WindowType::Tumble {
+            column_index: 1,
+            interval: Duration::minutes(5),
+        },
+        Some(Duration::minutes(1)),
+        WindowState::default(),
+    );
+
+    let ops = window.insert(record_at_minute(1, 7)).unwrap();
+    let mut expected = in_window(1, 7, 5, 10);
+    let Field::Timestamp(reference) = at_minute(7) else {
+        unreachable!()
+    };
+    expected.set_lifetime(Some(Lifetime {
+        reference,
+        duration: std::time::Duration::from_secs(4 * 60),
+    }));
+    assert_eq!(ops, vec![Operation::Insert { new: expected }]);
+
+    // The watermark is at minute 6, so the window ending at minute 5 is closed.
+    assert_eq!(window.insert(record_at_minute(2, 4)).unwrap(), vec![]);
+    assert_eq!(window.delete(record_at_minute(3, 3)).unwrap(), vec![]);
+    assert_eq!(window.take_late_records(), 2);
+    assert_eq!(window.insert(record_at_minute(4, 5)).unwrap().len(), 1);
+    assert_eq!(window.take_late_records(), 0);
+
+    let mut sessions = WindowOperator::new(
+        WindowType::Session {
+            column_index: 1,
+            gap: Duration::minutes(5),
+        },
+        Some(Duration::minutes(1)),
+        WindowState::default(),
+    );
+    sessions.insert(record_at_minute(1, 0)).unwrap();
+    sessions.insert(record_at_minute(2, 10)).unwrap();
+    // The first session closed and was evicted.
+    assert_eq!(sessions.delete(record_at_minute(1, 0)).unwrap(), vec![]);
+    assert_eq!(sessions.insert(record_at_minute(3, 3)).unwrap(), vec![]);
+    assert_eq!(sessions.take_late_records(), 2);
+    assert_eq!(sessions.insert(record_at_minute(4, 5)).unwrap().len(), 1);
+}
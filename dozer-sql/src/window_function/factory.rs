@@ -0,0 +1,329 @@
+use std::{collections::HashMap, sync::Arc};
+
+use crate::aggregation::aggregator::get_aggregator_type_from_aggregation_expression;
+use crate::errors::{PipelineError, UnsupportedSqlError};
+use crate::top_n::operator::SortDirection;
+use dozer_core::{
+    event::EventHub,
+    node::{PortHandle, Processor, ProcessorFactory},
+    DEFAULT_PORT_HANDLE,
+};
+use dozer_sql_expression::builder::ExpressionBuilder;
+use dozer_sql_expression::execution::Expression;
+use dozer_sql_expression::sqlparser::ast::{
+    Expr as SqlExpr, Function, FunctionArg, FunctionArgExpr, OrderByExpr, Value as SqlValue,
+    WindowFrame, WindowFrameBound, WindowFrameUnits,
+};
+use dozer_types::models::udf_config::UdfConfig;
+use dozer_types::types::{FieldDefinition, FieldType, Schema, SourceDefinition};
+use dozer_types::{errors::internal::BoxedError, tonic::async_trait};
+use tokio::runtime::Runtime;
+
+use super::operator::{Frame, FrameBound, FrameUnits, WindowFunction, WindowFunctionType};
+use super::processor::WindowFunctionProcessor;
+
+/// A window function call without its `OVER` clause, whose value is output in the column `name`.
+#[derive(Debug, Clone)]
+pub struct WindowFunctionCall {
+    pub name: String,
+    pub function: Function,
+    pub frame: Option<WindowFrame>,
+}
+
+/// Appends the values of window functions that share a `PARTITION BY` and `ORDER BY` to the rows.
+#[derive(Debug)]
+pub struct WindowFunctionProcessorFactory {
+    id: String,
+    partition_by: Vec<SqlExpr>,
+    order_by: Vec<OrderByExpr>,
+    functions: Vec<WindowFunctionCall>,
+    udfs: Vec<UdfConfig>,
+    runtime: Arc<Runtime>,
+}
+
+impl WindowFunctionProcessorFactory {
+    /// Creates a new [`WindowFunctionProcessorFactory`].
+    pub fn new(
+        id: String,
+        partition_by: Vec<SqlExpr>,
+        order_by: Vec<OrderByExpr>,
+        functions: Vec<WindowFunctionCall>,
+        udfs: Vec<UdfConfig>,
+        runtime: Arc<Runtime>,
+    ) -> Self {
+        Self {
+            id,
+            partition_by,
+            order_by,
+            functions,
+            udfs,
+            runtime,
+        }
+    }
+
+    async fn build_expression(
+        &self,
+        expression: &SqlExpr,
+        schema: &Schema,
+    ) -> Result<Expression, PipelineError> {
+        Ok(
+            ExpressionBuilder::new(schema.fields.len(), self.runtime.clone())
+                .build(false, expression, schema, &self.udfs)
+                .await?,
+        )
+    }
+
+    async fn build_function(
+        &self,
+        call: &WindowFunctionCall,
+        schema: &Schema,
+    ) -> Result<(WindowFunction, FieldDefinition), PipelineError> {
+        let unsupported = || {
+            PipelineError::UnsupportedSqlError(UnsupportedSqlError::WindowFunction(
+                call.function.to_string(),
+            ))
+        };
+        let mut args = vec![];
+        for arg in &call.function.args {
+            match arg {
+                FunctionArg::Unnamed(FunctionArgExpr::Expr(expr)) => args.push(expr),
+                FunctionArg::Unnamed(FunctionArgExpr::Wildcard) => (),
+                _ => return Err(unsupported()),
+            }
+        }
+
+        let name = call.function.name.to_string().to_lowercase();
+        let (function_type, args, return_type, nullable) = match name.as_str() {
+            "row_number" | "rank" | "dense_rank" => {
+                if !args.is_empty() {
+                    return Err(unsupported());
+                }
+                let function_type = match name.as_str() {
+                    "row_number" => WindowFunctionType::RowNumber,
+                    "rank" => WindowFunctionType::Rank,
+                    _ => WindowFunctionType::DenseRank,
+                };
+                (function_type, vec![], FieldType::UInt, false)
+            }
+            "lag" | "lead" => {
+                let (value, offset, default) = match args.as_slice() {
+                    [value] => (value, 1.0, None),
+                    [value, offset] => (value, parse_offset(offset).ok_or_else(unsupported)?, None),
+                    [value, offset, default] => (
+                        value,
+                        parse_offset(offset).ok_or_else(unsupported)?,
+                        Some(default),
+                    ),
+                    _ => return Err(unsupported()),
+                };
+                if offset.fract() != 0.0 {
+                    return Err(unsupported());
+                }
+                let offset = offset as usize;
+                let function_type = if name == "lag" {
+                    WindowFunctionType::Lag { offset }
+                } else {
+                    WindowFunctionType::Lead { offset }
+                };
+                let value = self.build_expression(value, schema).await?;
+                let return_type = value.get_type(schema)?.return_type;
+                let mut args = vec![value];
+                if let Some(default) = default {
+                    args.push(self.build_expression(default, schema).await?);
+                }
+                (function_type, args, return_type, true)
+            }
+            "first_value" | "last_value" => {
+                let [value] = args.as_slice() else {
+                    return Err(unsupported());
+                };
+                let function_type = if name == "first_value" {
+                    WindowFunctionType::FirstValue
+                } else {
+                    WindowFunctionType::LastValue
+                };
+                let value = self.build_expression(value, schema).await?;
+                let return_type = value.get_type(schema)?.return_type;
+                (function_type, vec![value], return_type, true)
+            }
+            _ => {
+                let mut builder = ExpressionBuilder::new(schema.fields.len(), self.runtime.clone());
+                let expression = builder
+                    .build(
+                        true,
+                        &SqlExpr::Function(call.function.clone()),
+                        schema,
+                        &self.udfs,
+                    )
+                    .await?;
+                let (Expression::Column { .. }, [measure]) =
+                    (expression, builder.aggregations.as_slice())
+                else {
+                    return Err(unsupported());
+                };
+                let (args, aggregator_type) =
+                    get_aggregator_type_from_aggregation_expression(measure, schema)?;
+                let return_type = measure.get_type(schema)?.return_type;
+                (
+                    WindowFunctionType::Aggregate(aggregator_type),
+                    args,
+                    return_type,
+                    true,
+                )
+            }
+        };
+
+        let frame = match &call.frame {
+            Some(frame) => get_frame(frame, self.order_by.len()).ok_or_else(unsupported)?,
+            None => Frame::default_for(!self.order_by.is_empty()),
+        };
+        Ok((
+            WindowFunction {
+                function_type,
+                args,
+                frame,
+                return_type: return_type.clone(),
+            },
+            FieldDefinition::new(
+                call.name.clone(),
+                return_type,
+                nullable,
+                SourceDefinition::Dynamic,
+            ),
+        ))
+    }
+
+    async fn build_functions(
+        &self,
+        schema: &Schema,
+    ) -> Result<(Vec<WindowFunction>, Vec<FieldDefinition>), PipelineError> {
+        let mut functions = vec![];
+        let mut fields = vec![];
+        for call in &self.functions {
+            let (function, field) = self.build_function(call, schema).await?;
+            functions.push(function);
+            fields.push(field);
+        }
+        Ok((functions, fields))
+    }
+}
+
+/// Parses the offset of `LAG` and `LEAD`, or of a frame bound.
+fn parse_offset(expr: &SqlExpr) -> Option<f64> {
+    match expr {
+        SqlExpr::Value(SqlValue::Number(n, _)) => {
+            n.to_string().parse().ok().filter(|n: &f64| *n >= 0.0)
+        }
+        _ => None,
+    }
+}
+
+/// `ROWS` frame offsets must be whole numbers, and `RANGE` frame offsets need a single `ORDER BY` expression.
+fn get_frame(frame: &WindowFrame, order_by_len: usize) -> Option<Frame> {
+    let units = match frame.units {
+        WindowFrameUnits::Rows => FrameUnits::Rows,
+        WindowFrameUnits::Range => FrameUnits::Range,
+        WindowFrameUnits::Groups => return None,
+    };
+    let get_bound = |bound: &WindowFrameBound| {
+        let bound = match bound {
+            WindowFrameBound::CurrentRow => FrameBound::CurrentRow,
+            WindowFrameBound::Preceding(None) => FrameBound::UnboundedPreceding,
+            WindowFrameBound::Following(None) => FrameBound::UnboundedFollowing,
+            WindowFrameBound::Preceding(Some(offset)) => {
+                FrameBound::Preceding(parse_offset(offset)?)
+            }
+            WindowFrameBound::Following(Some(offset)) => {
+                FrameBound::Following(parse_offset(offset)?)
+            }
+        };
+        match (units, bound) {
+            (FrameUnits::Rows, FrameBound::Preceding(offset) | FrameBound::Following(offset))
+                if offset.fract() != 0.0 =>
+            {
+                None
+            }
+            (FrameUnits::Range, FrameBound::Preceding(_) | FrameBound::Following(_))
+                if order_by_len != 1 =>
+            {
+                None
+            }
+            _ => Some(bound),
+        }
+    };
+    Some(Frame {
+        units,
+        start: get_bound(&frame.start_bound)?,
+        end: match &frame.end_bound {
+            Some(end_bound) => get_bound(end_bound)?,
+            None => FrameBound::CurrentRow,
+        },
+    })
+}
+
+#[async_trait]
+impl ProcessorFactory for WindowFunctionProcessorFactory {
+    fn id(&self) -> String {
+        self.id.clone()
+    }
+
+    fn type_name(&self) -> String {
+        "WindowFunction".to_string()
+    }
+
+    fn get_input_ports(&self) -> Vec<PortHandle> {
+        vec![DEFAULT_PORT_HANDLE]
+    }
+
+    fn get_output_ports(&self) -> Vec<PortHandle> {
+        vec![DEFAULT_PORT_HANDLE]
+    }
+
+    async fn get_output_schema(
+        &self,
+        _output_port: &PortHandle,
+        input_schemas: &HashMap<PortHandle, Schema>,
+    ) -> Result<Schema, BoxedError> {
+        let mut schema = input_schemas
+            .get(&DEFAULT_PORT_HANDLE)
+            .ok_or(PipelineError::InvalidPortHandle(DEFAULT_PORT_HANDLE))?
+            .clone();
+        let (_, fields) = self.build_functions(&schema).await?;
+        schema.fields.extend(fields);
+        Ok(schema)
+    }
+
+    async fn build(
+        &self,
+        input_schemas: HashMap<PortHandle, Schema>,
+        _output_schemas: HashMap<PortHandle, Schema>,
+        _event_hub: EventHub,
+        checkpoint_data: Option<Vec<u8>>,
+    ) -> Result<Box<dyn Processor>, BoxedError> {
+        let schema = input_schemas
+            .get(&DEFAULT_PORT_HANDLE)
+            .ok_or(PipelineError::InvalidPortHandle(DEFAULT_PORT_HANDLE))?;
+
+        let mut partition_by = vec![];
+        for expression in &self.partition_by {
+            partition_by.push(self.build_expression(expression, schema).await?);
+        }
+        let mut order_by = vec![];
+        for expression in &self.order_by {
+            order_by.push((
+                self.build_expression(&expression.expr, schema).await?,
+                SortDirection::new(expression.asc, expression.nulls_first),
+            ));
+        }
+        let (functions, _) = self.build_functions(schema).await?;
+
+        Ok(Box::new(WindowFunctionProcessor::new(
+            self.id.clone(),
+            partition_by,
+            order_by,
+            functions,
+            schema.clone(),
+            checkpoint_data,
+        )?))
+    }
+}
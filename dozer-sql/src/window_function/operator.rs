@@ -0,0 +1,737 @@
+use std::collections::{btree_map::Entry, BTreeMap, HashMap};
+use std::ops::Bound::{Excluded, Included, Unbounded};
+use std::ops::Range;
+
+use dozer_sql_expression::execution::Expression;
+use dozer_types::bincode;
+use dozer_types::types::{Field, FieldType, Operation, Record, Schema};
+
+use crate::aggregation::aggregator::{
+    get_aggregator_from_aggregator_type, Aggregator, AggregatorType,
+};
+use crate::errors::PipelineError;
+use crate::top_n::operator::{diff_rows, SortKey, SortValue};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WindowFunctionType {
+    RowNumber,
+    Rank,
+    DenseRank,
+    /// The value `offset` rows before, or the optional second argument if there's no such row.
+    Lag {
+        offset: usize,
+    },
+    /// The value `offset` rows after, or the optional second argument if there's no such row.
+    Lead {
+        offset: usize,
+    },
+    FirstValue,
+    LastValue,
+    Aggregate(AggregatorType),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FrameUnits {
+    Rows,
+    Range,
+}
+
+/// A frame bound, with offsets in rows for `ROWS` frames
+/// and in units of the `ORDER BY` value for `RANGE` frames.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FrameBound {
+    UnboundedPreceding,
+    Preceding(f64),
+    CurrentRow,
+    Following(f64),
+    UnboundedFollowing,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Frame {
+    pub units: FrameUnits,
+    pub start: FrameBound,
+    pub end: FrameBound,
+}
+
+impl Frame {
+    /// The frame of a window without a frame clause.
+    pub fn default_for(has_order_by: bool) -> Self {
+        Self {
+            units: FrameUnits::Range,
+            start: FrameBound::UnboundedPreceding,
+            end: if has_order_by {
+                FrameBound::CurrentRow
+            } else {
+                FrameBound::UnboundedFollowing
+            },
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct WindowFunction {
+    pub function_type: WindowFunctionType,
+    pub args: Vec<Expression>,
+    pub frame: Frame,
+    pub return_type: FieldType,
+}
+
+/// Every row of every partition with the number of times it occurs and its window function values.
+#[derive(Debug, Default, bincode::Encode, bincode::Decode)]
+pub struct WindowFunctionState {
+    partitions: HashMap<Vec<Field>, BTreeMap<SortKey, StoredRow>>,
+}
+
+#[derive(Debug, bincode::Encode, bincode::Decode)]
+struct StoredRow {
+    count: usize,
+    /// The evaluated arguments of each window function, so they're evaluated once per row.
+    args: Vec<Vec<Field>>,
+    /// The window function values of each occurrence of the row, as last sent.
+    outputs: Vec<Vec<Field>>,
+    /// The number of rows up to the last occurrence of the row and the number of peer groups up to it,
+    /// if a window function numbers the rows. Rows recomputed after it are numbered from here.
+    numbers: Option<(usize, usize)>,
+}
+
+/// A row of a partition, in partition order.
+#[derive(Clone, Copy)]
+struct PartitionRow<'a> {
+    sort_values: &'a [SortValue],
+    args: &'a [Vec<Field>],
+}
+
+/// Appends the value of each window function of one `OVER (PARTITION BY .. ORDER BY ..)` clause to the rows.
+#[derive(Debug)]
+pub struct WindowFunctions {
+    functions: Vec<WindowFunction>,
+    input_schema: Schema,
+    state: WindowFunctionState,
+}
+
+impl WindowFunctions {
+    pub fn new(
+        functions: Vec<WindowFunction>,
+        input_schema: Schema,
+        state: WindowFunctionState,
+    ) -> Self {
+        Self {
+            functions,
+            input_schema,
+            state,
+        }
+    }
+
+    pub fn state(&self) -> &WindowFunctionState {
+        &self.state
+    }
+
+    /// Removes `deleted` from and adds `inserted` to a partition,
+    /// returning the operations on the rows whose values or window function values changed.
+    ///
+    /// Only the rows whose window function values can change are computed, from the rows their frames reach.
+    /// Their previous values are the stored ones.
+    pub fn apply(
+        &mut self,
+        partition: Vec<Field>,
+        deleted: Vec<SortKey>,
+        inserted: Vec<SortKey>,
+    ) -> Result<Vec<Operation>, PipelineError> {
+        let changed = deleted.iter().chain(&inserted);
+        let (Some(first_changed), Some(last_changed)) =
+            (changed.clone().min().cloned(), changed.max().cloned())
+        else {
+            return Ok(vec![]);
+        };
+        let mut rows = self.state.partitions.remove(&partition).unwrap_or_default();
+
+        // The rows before and after the changed ones are the same before and after the change.
+        let first = walk(
+            rows.range(..&first_changed).rev(),
+            |sort_values, distance| {
+                self.depends_on(sort_values, &first_changed.0, distance, true, false)
+            },
+        )?
+        .unwrap_or(&first_changed)
+        .clone();
+        let mut before = stored_output_rows(rows.range(&first..=&last_changed)).collect::<Vec<_>>();
+        let numbers_before = count_rows_and_groups(&rows, &first_changed, &last_changed);
+
+        for key in deleted {
+            if let Entry::Occupied(mut entry) = rows.entry(key) {
+                entry.get_mut().count -= 1;
+                if entry.get().count == 0 {
+                    entry.remove();
+                }
+            }
+        }
+        for key in inserted {
+            match rows.entry(key) {
+                Entry::Occupied(mut entry) => entry.get_mut().count += 1,
+                Entry::Vacant(entry) => {
+                    let args = self.evaluate_args(&entry.key().1)?;
+                    entry.insert(StoredRow {
+                        count: 1,
+                        args,
+                        outputs: vec![],
+                        numbers: None,
+                    });
+                }
+            }
+        }
+
+        // The rows after the changed ones are only renumbered
+        // if there are a different number of rows or peer groups up to them.
+        let numbers_changed = self.numbers_rows()
+            && count_rows_and_groups(&rows, &first_changed, &last_changed) != numbers_before;
+        let last = walk(
+            rows.range((Excluded(&last_changed), Unbounded)),
+            |sort_values, distance| {
+                self.depends_on(
+                    sort_values,
+                    &last_changed.0,
+                    distance,
+                    false,
+                    numbers_changed,
+                )
+            },
+        )?
+        .unwrap_or(&last_changed)
+        .clone();
+        before.extend(stored_output_rows(
+            rows.range((Excluded(&last_changed), Included(&last))),
+        ));
+
+        let after = self.update_outputs(&mut rows, &first, &last)?;
+        if !rows.is_empty() {
+            self.state.partitions.insert(partition, rows);
+        }
+
+        let primary_index = &self.input_schema.primary_index;
+        let num_fields = self.input_schema.fields.len();
+        Ok(diff_rows(before, after, |row| {
+            if primary_index.is_empty() {
+                row[..num_fields].to_vec()
+            } else {
+                primary_index
+                    .iter()
+                    .map(|index| row[*index].clone())
+                    .collect()
+            }
+        }))
+    }
+
+    fn evaluate_args(&mut self, values: &[Field]) -> Result<Vec<Vec<Field>>, PipelineError> {
+        let record = Record::new(values.to_vec());
+        let mut args = Vec::with_capacity(self.functions.len());
+        for function in self.functions.iter_mut() {
+            let mut function_args = Vec::with_capacity(function.args.len());
+            for arg in function.args.iter_mut() {
+                function_args.push(arg.evaluate(&record, &self.input_schema)?);
+            }
+            args.push(function_args);
+        }
+        Ok(args)
+    }
+
+    /// Whether the window function values of a row with `sort_values` can depend on the row
+    /// `distance` rows after it (if `is_after`) or before it, with `other_sort_values`.
+    ///
+    /// Rows before a row only count for its number if `counts_rows_before`.
+    /// The farther a row is, the fewer rows can depend on it,
+    /// so the rows depending on a row are found by walking away from it.
+    fn depends_on(
+        &self,
+        sort_values: &[SortValue],
+        other_sort_values: &[SortValue],
+        distance: usize,
+        is_after: bool,
+        counts_rows_before: bool,
+    ) -> Result<bool, PipelineError> {
+        // Peers are in each other's `RANGE` frames and ranks.
+        if sort_values == other_sort_values {
+            return Ok(true);
+        }
+        let positions = if self.needs_positions() {
+            (
+                get_range_position(sort_values)?,
+                get_range_position(other_sort_values)?,
+            )
+        } else {
+            (None, None)
+        };
+        Ok(self
+            .functions
+            .iter()
+            .any(|function| match function.function_type {
+                WindowFunctionType::RowNumber
+                | WindowFunctionType::Rank
+                | WindowFunctionType::DenseRank => !is_after && counts_rows_before,
+                WindowFunctionType::Lag { offset } => !is_after && distance <= offset,
+                WindowFunctionType::Lead { offset } => is_after && distance <= offset,
+                WindowFunctionType::FirstValue
+                | WindowFunctionType::LastValue
+                | WindowFunctionType::Aggregate(_) => {
+                    // How far the frame reaches towards the other row.
+                    let reach = if is_after {
+                        function.frame.end.offset()
+                    } else {
+                        -function.frame.start.offset()
+                    };
+                    match (function.frame.units, positions) {
+                        (FrameUnits::Rows, _) => distance as f64 <= reach,
+                        (FrameUnits::Range, (Some(position), Some(other_position))) => {
+                            (other_position - position).abs() <= reach
+                        }
+                        (FrameUnits::Range, _) => reach == f64::INFINITY,
+                    }
+                }
+            }))
+    }
+
+    /// Whether a frame needs the `ORDER BY` values of the rows as numbers.
+    fn needs_positions(&self) -> bool {
+        self.functions.iter().any(|function| {
+            function.frame.units == FrameUnits::Range
+                && [function.frame.start, function.frame.end]
+                    .iter()
+                    .any(|bound| {
+                        matches!(bound, FrameBound::Preceding(_) | FrameBound::Following(_))
+                    })
+        })
+    }
+
+    /// Whether a window function numbers the rows.
+    fn numbers_rows(&self) -> bool {
+        self.functions.iter().any(|function| {
+            matches!(
+                function.function_type,
+                WindowFunctionType::RowNumber
+                    | WindowFunctionType::Rank
+                    | WindowFunctionType::DenseRank
+            )
+        })
+    }
+
+    /// Computes and stores the window function values of the rows from `first` to `last`,
+    /// returning the rows followed by their window function values.
+    ///
+    /// Only the rows the frames of those rows reach are read.
+    fn update_outputs(
+        &self,
+        rows: &mut BTreeMap<SortKey, StoredRow>,
+        first: &SortKey,
+        last: &SortKey,
+    ) -> Result<Vec<Vec<Field>>, PipelineError> {
+        let context_first = walk(rows.range(..first).rev(), |sort_values, distance| {
+            self.depends_on(&first.0, sort_values, distance, false, false)
+        })?
+        .unwrap_or(first)
+        .clone();
+        let context_last = walk(
+            rows.range((Excluded(last), Unbounded)),
+            |sort_values, distance| self.depends_on(&last.0, sort_values, distance, true, false),
+        )?
+        .unwrap_or(last)
+        .clone();
+        // Peer groups are ranked and framed as a whole.
+        let context_first = walk(rows.range(..&context_first).rev(), |sort_values, _| {
+            Ok(sort_values == context_first.0)
+        })?
+        .unwrap_or(&context_first)
+        .clone();
+        let context_last = walk(
+            rows.range((Excluded(&context_last), Unbounded)),
+            |sort_values, _| Ok(sort_values == context_last.0),
+        )?
+        .unwrap_or(&context_last)
+        .clone();
+        // The context starts a peer group, so it's numbered from the row before it.
+        let base = rows
+            .range(..&context_first)
+            .next_back()
+            .and_then(|(_, row)| row.numbers)
+            .unwrap_or_default();
+
+        let start = count_rows(rows.range(&context_first..first));
+        let end = start + count_rows(rows.range(first..=last));
+        let (columns, numbers) = {
+            let partition = Partition::new(
+                rows.range(&context_first..=&context_last),
+                self.needs_positions(),
+                base,
+            )?;
+            let mut columns = Vec::with_capacity(self.functions.len());
+            for (function_index, function) in self.functions.iter().enumerate() {
+                columns.push(partition.evaluate(function, function_index, start..end)?);
+            }
+            let numbers = (start..end)
+                .map(|index| partition.numbers(index))
+                .collect::<Vec<_>>();
+            (columns, numbers)
+        };
+
+        let numbers_rows = self.numbers_rows();
+        let mut output_rows = Vec::with_capacity(end - start);
+        let mut index = 0;
+        for ((_, values), row) in rows.range_mut(first..=last) {
+            row.outputs = (index..index + row.count)
+                .map(|index| columns.iter().map(|column| column[index].clone()).collect())
+                .collect();
+            index += row.count;
+            row.numbers = numbers_rows.then(|| numbers[index - 1]);
+            output_rows.extend(row.outputs.iter().map(|outputs| {
+                let mut output_row = values.clone();
+                output_row.extend(outputs.iter().cloned());
+                output_row
+            }));
+        }
+        Ok(output_rows)
+    }
+}
+
+impl FrameBound {
+    /// The offset of the bound from the current row, negative for preceding rows.
+    fn offset(self) -> f64 {
+        match self {
+            FrameBound::UnboundedPreceding => f64::NEG_INFINITY,
+            FrameBound::Preceding(offset) => -offset,
+            FrameBound::CurrentRow => 0.0,
+            FrameBound::Following(offset) => offset,
+            FrameBound::UnboundedFollowing => f64::INFINITY,
+        }
+    }
+}
+
+/// Walks away from a row through `rows`, which are the rows before it in reverse or the rows after it,
+/// returning the farthest one for which `is_reached` holds at the distance of its occurrence closest to the row.
+fn walk<'a>(
+    rows: impl Iterator<Item = (&'a SortKey, &'a StoredRow)>,
+    mut is_reached: impl FnMut(&[SortValue], usize) -> Result<bool, PipelineError>,
+) -> Result<Option<&'a SortKey>, PipelineError> {
+    let mut farthest = None;
+    let mut distance = 1;
+    for (key, row) in rows {
+        if !is_reached(&key.0, distance)? {
+            break;
+        }
+        farthest = Some(key);
+        distance += row.count;
+    }
+    Ok(farthest)
+}
+
+fn count_rows<'a>(rows: impl Iterator<Item = (&'a SortKey, &'a StoredRow)>) -> usize {
+    rows.map(|(_, row)| row.count).sum()
+}
+
+/// The number of rows and of peer groups starting from `first` to the last peer of `last`.
+///
+/// The rows after them start a new peer group, so their numbers only change if these do.
+fn count_rows_and_groups(
+    rows: &BTreeMap<SortKey, StoredRow>,
+    first: &SortKey,
+    last: &SortKey,
+) -> (usize, usize) {
+    let mut previous = rows
+        .range(..first)
+        .next_back()
+        .map(|((sort_values, _), _)| sort_values);
+    let mut counts = (0, 0);
+    for (key, row) in rows.range(first..) {
+        let (sort_values, _) = key;
+        if key > last && *sort_values != last.0 {
+            break;
+        }
+        counts.0 += row.count;
+        if previous != Some(sort_values) {
+            counts.1 += 1;
+        }
+        previous = Some(sort_values);
+    }
+    counts
+}
+
+/// The rows followed by their stored window function values.
+fn stored_output_rows<'a>(
+    rows: impl Iterator<Item = (&'a SortKey, &'a StoredRow)> + 'a,
+) -> impl Iterator<Item = Vec<Field>> + 'a {
+    rows.flat_map(|((_, values), row)| {
+        row.outputs.iter().map(move |outputs| {
+            let mut output_row = values.clone();
+            output_row.extend(outputs.iter().cloned());
+            output_row
+        })
+    })
+}
+
+/// Consecutive rows of a partition with what the window functions need to know about their order.
+///
+/// The rows start and end with whole peer groups.
+struct Partition<'a> {
+    rows: Vec<PartitionRow<'a>>,
+    /// The number of rows and of peer groups before the first row.
+    base: (usize, usize),
+    /// See [`get_peer_groups`].
+    peers: Vec<(usize, usize, usize)>,
+    /// The `ORDER BY` value of each row as a number, if a `RANGE` frame has an offset.
+    positions: Vec<Option<f64>>,
+    /// The rows with a position, as `NULL`s are sorted first or last.
+    non_null: Range<usize>,
+}
+
+impl<'a> Partition<'a> {
+    fn new(
+        rows: impl Iterator<Item = (&'a SortKey, &'a StoredRow)>,
+        needs_positions: bool,
+        base: (usize, usize),
+    ) -> Result<Self, PipelineError> {
+        let rows = rows
+            .flat_map(|((sort_values, _), row)| {
+                let partition_row = PartitionRow {
+                    sort_values,
+                    args: &row.args,
+                };
+                std::iter::repeat(partition_row).take(row.count)
+            })
+            .collect::<Vec<_>>();
+        let peers = get_peer_groups(&rows);
+        let positions = if needs_positions {
+            rows.iter()
+                .map(|row| get_range_position(row.sort_values))
+                .collect::<Result<Vec<_>, _>>()?
+        } else {
+            vec![]
+        };
+        let non_null_start = positions
+            .iter()
+            .position(Option::is_some)
+            .unwrap_or(positions.len());
+        let non_null_end = positions
+            .iter()
+            .rposition(Option::is_some)
+            .map_or(non_null_start, |position| position + 1);
+        Ok(Self {
+            rows,
+            base,
+            peers,
+            positions,
+            non_null: non_null_start..non_null_end,
+        })
+    }
+
+    /// The row number of the row at `index` and the number of its peer group in the whole partition.
+    fn numbers(&self, index: usize) -> (usize, usize) {
+        (
+            self.base.0 + index + 1,
+            self.base.1 + self.peers[index].2 + 1,
+        )
+    }
+
+    /// The values of a window function for the rows at `indexes`.
+    fn evaluate(
+        &self,
+        function: &WindowFunction,
+        function_index: usize,
+        indexes: Range<usize>,
+    ) -> Result<Vec<Field>, PipelineError> {
+        let arg = |index: usize, arg_index: usize| {
+            self.rows[index].args[function_index]
+                .get(arg_index)
+                .cloned()
+                .unwrap_or(Field::Null)
+        };
+        let len = self.rows.len();
+        let mut values = Vec::with_capacity(indexes.len());
+        if let WindowFunctionType::Aggregate(aggregator_type) = function.function_type {
+            self.aggregate(
+                function,
+                function_index,
+                aggregator_type,
+                indexes,
+                &mut values,
+            )?;
+            return Ok(values);
+        }
+        for index in indexes {
+            values.push(match function.function_type {
+                WindowFunctionType::RowNumber => Field::UInt(self.numbers(index).0 as u64),
+                WindowFunctionType::Rank => {
+                    Field::UInt((self.base.0 + self.peers[index].0) as u64 + 1)
+                }
+                WindowFunctionType::DenseRank => Field::UInt(self.numbers(index).1 as u64),
+                WindowFunctionType::Lag { offset } => match index.checked_sub(offset) {
+                    Some(other) => arg(other, 0),
+                    None => arg(index, 1),
+                },
+                WindowFunctionType::Lead { offset } if index + offset < len => {
+                    arg(index + offset, 0)
+                }
+                WindowFunctionType::Lead { .. } => arg(index, 1),
+                WindowFunctionType::FirstValue => match self.get_frame(&function.frame, index)? {
+                    Some((start, _)) => arg(start, 0),
+                    None => Field::Null,
+                },
+                WindowFunctionType::LastValue => match self.get_frame(&function.frame, index)? {
+                    Some((_, end)) => arg(end, 0),
+                    None => Field::Null,
+                },
+                WindowFunctionType::Aggregate(_) => unreachable!("aggregates are evaluated above"),
+            });
+        }
+        Ok(values)
+    }
+
+    /// Aggregates the frames of the rows at `indexes`,
+    /// adding the rows that enter a frame and removing the ones that leave it as the frames move forward.
+    fn aggregate(
+        &self,
+        function: &WindowFunction,
+        function_index: usize,
+        aggregator_type: AggregatorType,
+        indexes: Range<usize>,
+        values: &mut Vec<Field>,
+    ) -> Result<(), PipelineError> {
+        let new_aggregator = || {
+            let mut aggregator = get_aggregator_from_aggregator_type(aggregator_type);
+            aggregator.init(function.return_type.clone());
+            aggregator
+        };
+        let mut aggregator = new_aggregator();
+        // The rows `window.0..window.1` are in the aggregator.
+        let mut window = (0, 0);
+        let mut value = Field::Null;
+        for index in indexes {
+            let Some((start, end)) = self.get_frame(&function.frame, index)? else {
+                // An empty frame has no rows to count.
+                values.push(if aggregator_type == AggregatorType::Count {
+                    new_aggregator().insert(&[])?
+                } else {
+                    Field::Null
+                });
+                continue;
+            };
+            if start < window.0 || start >= window.1 || end + 1 < window.1 {
+                aggregator = new_aggregator();
+                window = (start, start);
+            }
+            while window.1 <= end {
+                value = aggregator.insert(&self.rows[window.1].args[function_index])?;
+                window.1 += 1;
+            }
+            while window.0 < start {
+                value = aggregator.delete(&self.rows[window.0].args[function_index])?;
+                window.0 += 1;
+            }
+            values.push(value.clone());
+        }
+        Ok(())
+    }
+
+    /// The first and last row of the frame of the row at `index`, or `None` if the frame is empty.
+    fn get_frame(
+        &self,
+        frame: &Frame,
+        index: usize,
+    ) -> Result<Option<(usize, usize)>, PipelineError> {
+        let last = self.rows.len() - 1;
+        let (start, end) = match frame.units {
+            FrameUnits::Rows => {
+                let position = |bound: FrameBound| match bound {
+                    FrameBound::UnboundedPreceding => 0,
+                    FrameBound::Preceding(offset) => index as i64 - offset as i64,
+                    FrameBound::CurrentRow => index as i64,
+                    FrameBound::Following(offset) => index as i64 + offset as i64,
+                    FrameBound::UnboundedFollowing => last as i64,
+                };
+                (position(frame.start), position(frame.end))
+            }
+            FrameUnits::Range => (
+                self.get_range_bound(frame.start, true, index),
+                self.get_range_bound(frame.end, false, index),
+            ),
+        };
+        let (start, end) = (start.max(0), end.min(last as i64));
+        Ok((start <= end).then_some((start as usize, end as usize)))
+    }
+
+    /// The first row (if `is_start`) or the last row within `bound` of the row at `index`.
+    fn get_range_bound(&self, bound: FrameBound, is_start: bool, index: usize) -> i64 {
+        let (first_peer, last_peer, _) = self.peers[index];
+        let peer_bound = if is_start { first_peer } else { last_peer } as i64;
+        let offset = match bound {
+            FrameBound::UnboundedPreceding => return 0,
+            FrameBound::UnboundedFollowing => return self.rows.len() as i64 - 1,
+            FrameBound::CurrentRow => return peer_bound,
+            FrameBound::Preceding(offset) => -offset,
+            FrameBound::Following(offset) => offset,
+        };
+
+        // Rows with a `NULL` value are only within an offset of each other.
+        let Some(current) = self.positions[index] else {
+            return peer_bound;
+        };
+        let target = current + offset;
+        // The positions increase in partition order, so the bound is found by a binary search.
+        let positions = &self.positions[self.non_null.clone()];
+        if is_start {
+            let start = self.non_null.start
+                + positions.partition_point(
+                    |position| matches!(position, Some(position) if *position < target),
+                );
+            if start < self.non_null.end {
+                start as i64
+            } else {
+                self.rows.len() as i64
+            }
+        } else {
+            let end = self.non_null.start
+                + positions.partition_point(
+                    |position| matches!(position, Some(position) if *position <= target),
+                );
+            end as i64 - 1
+        }
+    }
+}
+
+/// The range of rows with the same `ORDER BY` values as each row, and the number of the range.
+fn get_peer_groups(partition: &[PartitionRow]) -> Vec<(usize, usize, usize)> {
+    let mut peers = Vec::with_capacity(partition.len());
+    let mut start = 0;
+    let mut group = 0;
+    for index in 0..partition.len() {
+        let is_last_peer = partition.get(index + 1).map_or(true, |next| {
+            next.sort_values != partition[index].sort_values
+        });
+        if is_last_peer {
+            peers.extend(std::iter::repeat((start, index, group)).take(index + 1 - start));
+            start = index + 1;
+            group += 1;
+        }
+    }
+    peers
+}
+
+/// The `ORDER BY` value of a row as a number that increases in partition order.
+fn get_range_position(sort_values: &[SortValue]) -> Result<Option<f64>, PipelineError> {
+    let [sort_value] = sort_values else {
+        return Err(PipelineError::InvalidValue(
+            "RANGE frames with an offset need exactly one ORDER BY expression".to_string(),
+        ));
+    };
+    if sort_value.value == Field::Null {
+        return Ok(None);
+    }
+    let value = sort_value.value.to_float().ok_or_else(|| {
+        PipelineError::InvalidValue(format!(
+            "RANGE frames with an offset need a numeric ORDER BY value, found {}",
+            sort_value.value
+        ))
+    })?;
+    Ok(Some(if sort_value.direction.descending {
+        -value
+    } else {
+        value
+    }))
+}
@@ -0,0 +1 @@
+mod operator_test;
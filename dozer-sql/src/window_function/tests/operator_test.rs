@@ -0,0 +1,413 @@
+use std::collections::BTreeMap;
+
+use dozer_sql_expression::execution::Expression;
+use dozer_types::types::{
+    Field, FieldDefinition, FieldType, Operation, Record, Schema, SourceDefinition,
+};
+use proptest::prelude::*;
+
+use crate::aggregation::aggregator::AggregatorType;
+use crate::top_n::operator::{SortDirection, SortKey, SortValue};
+use crate::window_function::operator::{
+    Frame, FrameBound, FrameUnits, WindowFunction, WindowFunctionState, WindowFunctionType,
+    WindowFunctions,
+};
+
+fn schema() -> Schema {
+    let mut schema = Schema::default();
+    for name in ["id", "score"] {
+        schema.field(
+            FieldDefinition::new(
+                name.to_string(),
+                FieldType::Int,
+                true,
+                SourceDefinition::Dynamic,
+            ),
+            name == "id",
+        );
+    }
+    schema
+}
+
+/// `ORDER BY score`
+fn key(id: i64, score: i64) -> SortKey {
+    (
+        vec![SortValue {
+            value: Field::Int(score),
+            direction: SortDirection::new(None, None),
+        }],
+        vec![Field::Int(id), Field::Int(score)],
+    )
+}
+
+fn function(function_type: WindowFunctionType, args: Vec<Expression>) -> WindowFunction {
+    WindowFunction {
+        function_type,
+        args,
+        frame: Frame::default_for(true),
+        return_type: FieldType::Int,
+    }
+}
+
+fn score() -> Expression {
+    Expression::Column { index: 1 }
+}
+
+fn row(values: &[i64], window_values: Vec<Field>) -> Record {
+    let mut values = values
+        .iter()
+        .map(|value| Field::Int(*value))
+        .collect::<Vec<_>>();
+    values.extend(window_values);
+    Record::new(values)
+}
+
+/// The window function values of the inserted rows, by id.
+fn inserted_values(ops: Vec<Operation>) -> Vec<Vec<Field>> {
+    let mut rows = ops
+        .into_iter()
+        .map(|op| match op {
+            Operation::Insert { new } => new.values,
+            op => panic!("unexpected operation {op:?}"),
+        })
+        .collect::<Vec<_>>();
+    rows.sort();
+    rows.into_iter().map(|row| row[2..].to_vec()).collect()
+}
+
+#[test]
+fn test_window_functions_only_update_affected_rows() {
+    // ROW_NUMBER(), LAG(score), SUM(score) OVER (ORDER BY score)
+    let mut window_functions = WindowFunctions::new(
+        vec![
+            function(WindowFunctionType::RowNumber, vec![]),
+            function(WindowFunctionType::Lag { offset: 1 }, vec![score()]),
+            function(
+                WindowFunctionType::Aggregate(AggregatorType::Sum),
+                vec![score()],
+            ),
+        ],
+        schema(),
+        WindowFunctionState::default(),
+    );
+    let partition = vec![];
+
+    let ops = window_functions
+        .apply(
+            partition.clone(),
+            vec![],
+            vec![key(1, 10), key(2, 20), key(3, 30)],
+        )
+        .unwrap();
+    assert_eq!(
+        inserted_values(ops),
+        vec![
+            vec![Field::UInt(1), Field::Null, Field::Int(10)],
+            vec![Field::UInt(2), Field::Int(10), Field::Int(30)],
+            vec![Field::UInt(3), Field::Int(20), Field::Int(60)],
+        ]
+    );
+
+    // A row at the end doesn't change the others.
+    assert_eq!(
+        window_functions
+            .apply(partition.clone(), vec![], vec![key(4, 40)])
+            .unwrap(),
+        vec![Operation::Insert {
+            new: row(
+                &[4, 40],
+                vec![Field::UInt(4), Field::Int(30), Field::Int(100)]
+            )
+        }]
+    );
+
+    // Only the rows after a deleted row change.
+    assert_eq!(
+        window_functions
+            .apply(partition.clone(), vec![key(2, 20)], vec![])
+            .unwrap(),
+        vec![
+            Operation::Delete {
+                old: row(
+                    &[2, 20],
+                    vec![Field::UInt(2), Field::Int(10), Field::Int(30)]
+                )
+            },
+            Operation::Update {
+                old: row(
+                    &[3, 30],
+                    vec![Field::UInt(3), Field::Int(20), Field::Int(60)]
+                ),
+                new: row(
+                    &[3, 30],
+                    vec![Field::UInt(2), Field::Int(10), Field::Int(40)]
+                ),
+            },
+            Operation::Update {
+                old: row(
+                    &[4, 40],
+                    vec![Field::UInt(4), Field::Int(30), Field::Int(100)]
+                ),
+                new: row(
+                    &[4, 40],
+                    vec![Field::UInt(3), Field::Int(30), Field::Int(80)]
+                ),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_ranks() {
+    let mut window_functions = WindowFunctions::new(
+        vec![
+            function(WindowFunctionType::Rank, vec![]),
+            function(WindowFunctionType::DenseRank, vec![]),
+            // Peers are in the default frame.
+            function(
+                WindowFunctionType::Aggregate(AggregatorType::Sum),
+                vec![score()],
+            ),
+        ],
+        schema(),
+        WindowFunctionState::default(),
+    );
+    let ops = window_functions
+        .apply(vec![], vec![], vec![key(1, 10), key(2, 10), key(3, 20)])
+        .unwrap();
+    assert_eq!(
+        inserted_values(ops),
+        vec![
+            vec![Field::UInt(1), Field::UInt(1), Field::Int(20)],
+            vec![Field::UInt(1), Field::UInt(1), Field::Int(20)],
+            vec![Field::UInt(3), Field::UInt(2), Field::Int(40)],
+        ]
+    );
+}
+
+#[test]
+fn test_frames() {
+    let frame = |units, start, end| Frame { units, start, end };
+    let mut window_functions = WindowFunctions::new(
+        vec![
+            // SUM(score) OVER (ORDER BY score ROWS BETWEEN 1 PRECEDING AND CURRENT ROW)
+            WindowFunction {
+                frame: frame(
+                    FrameUnits::Rows,
+                    FrameBound::Preceding(1.0),
+                    FrameBound::CurrentRow,
+                ),
+                ..function(
+                    WindowFunctionType::Aggregate(AggregatorType::Sum),
+                    vec![score()],
+                )
+            },
+            // COUNT(score) OVER (ORDER BY score RANGE BETWEEN 10 PRECEDING AND CURRENT ROW)
+            WindowFunction {
+                frame: frame(
+                    FrameUnits::Range,
+                    FrameBound::Preceding(10.0),
+                    FrameBound::CurrentRow,
+                ),
+                ..function(
+                    WindowFunctionType::Aggregate(AggregatorType::Count),
+                    vec![score()],
+                )
+            },
+            // LAST_VALUE(score) OVER (ORDER BY score ROWS BETWEEN CURRENT ROW AND UNBOUNDED FOLLOWING)
+            WindowFunction {
+                frame: frame(
+                    FrameUnits::Rows,
+                    FrameBound::CurrentRow,
+                    FrameBound::UnboundedFollowing,
+                ),
+                ..function(WindowFunctionType::LastValue, vec![score()])
+            },
+            // LEAD(score, 2, 0) OVER (ORDER BY score)
+            function(
+                WindowFunctionType::Lead { offset: 2 },
+                vec![score(), Expression::Literal(Field::Int(0))],
+            ),
+        ],
+        schema(),
+        WindowFunctionState::default(),
+    );
+    let ops = window_functions
+        .apply(
+            vec![],
+            vec![],
+            vec![key(1, 10), key(2, 20), key(3, 25), key(4, 40)],
+        )
+        .unwrap();
+    let int = Field::Int;
+    assert_eq!(
+        inserted_values(ops),
+        vec![
+            vec![int(10), int(1), int(40), int(25)],
+            vec![int(30), int(2), int(40), int(40)],
+            vec![int(45), int(2), int(40), int(0)],
+            vec![int(65), int(1), int(40), int(0)],
+        ]
+    );
+}
+
+#[test]
+fn test_frames_reaching_a_changed_row() {
+    // SUM(score) OVER (ORDER BY score ROWS BETWEEN 1 PRECEDING AND 1 FOLLOWING)
+    let mut window_functions = WindowFunctions::new(
+        vec![WindowFunction {
+            frame: Frame {
+                units: FrameUnits::Rows,
+                start: FrameBound::Preceding(1.0),
+                end: FrameBound::Following(1.0),
+            },
+            ..function(
+                WindowFunctionType::Aggregate(AggregatorType::Sum),
+                vec![score()],
+            )
+        }],
+        schema(),
+        WindowFunctionState::default(),
+    );
+    let ops = window_functions
+        .apply(
+            vec![],
+            vec![],
+            vec![key(1, 10), key(2, 20), key(3, 30), key(4, 40)],
+        )
+        .unwrap();
+    let int = Field::Int;
+    assert_eq!(
+        inserted_values(ops),
+        vec![vec![int(30)], vec![int(60)], vec![int(90)], vec![int(70)]]
+    );
+
+    // The row before the new one has it in its frame, the rows after it only up to a frame away change.
+    assert_eq!(
+        window_functions
+            .apply(vec![], vec![], vec![key(5, 25)])
+            .unwrap(),
+        vec![
+            Operation::Update {
+                old: row(&[2, 20], vec![int(60)]),
+                new: row(&[2, 20], vec![int(55)]),
+            },
+            Operation::Update {
+                old: row(&[3, 30], vec![int(90)]),
+                new: row(&[3, 30], vec![int(95)]),
+            },
+            Operation::Insert {
+                new: row(&[5, 25], vec![int(75)])
+            },
+        ]
+    );
+}
+
+/// Window functions of every kind, with a frame reaching every earlier row if `running_sum`.
+fn all_functions(running_sum: bool) -> Vec<WindowFunction> {
+    let frame = |units, start, end| Frame { units, start, end };
+    let mut functions = vec![
+        function(WindowFunctionType::RowNumber, vec![]),
+        function(WindowFunctionType::Rank, vec![]),
+        function(WindowFunctionType::DenseRank, vec![]),
+        function(WindowFunctionType::Lag { offset: 2 }, vec![score()]),
+        function(WindowFunctionType::Lead { offset: 1 }, vec![score()]),
+        // SUM(score) OVER (ORDER BY score ROWS BETWEEN 2 PRECEDING AND 2 FOLLOWING)
+        WindowFunction {
+            frame: frame(
+                FrameUnits::Rows,
+                FrameBound::Preceding(2.0),
+                FrameBound::Following(2.0),
+            ),
+            ..function(
+                WindowFunctionType::Aggregate(AggregatorType::Sum),
+                vec![score()],
+            )
+        },
+        // COUNT(score) OVER (ORDER BY score RANGE BETWEEN 2 PRECEDING AND CURRENT ROW)
+        WindowFunction {
+            frame: frame(
+                FrameUnits::Range,
+                FrameBound::Preceding(2.0),
+                FrameBound::CurrentRow,
+            ),
+            ..function(
+                WindowFunctionType::Aggregate(AggregatorType::Count),
+                vec![score()],
+            )
+        },
+        // LAST_VALUE(id) OVER (ORDER BY score RANGE BETWEEN CURRENT ROW AND 1 FOLLOWING)
+        WindowFunction {
+            frame: frame(
+                FrameUnits::Range,
+                FrameBound::CurrentRow,
+                FrameBound::Following(1.0),
+            ),
+            ..function(
+                WindowFunctionType::LastValue,
+                vec![Expression::Column { index: 0 }],
+            )
+        },
+    ];
+    if running_sum {
+        // SUM(score) OVER (ORDER BY score)
+        functions.push(function(
+            WindowFunctionType::Aggregate(AggregatorType::Sum),
+            vec![score()],
+        ));
+    }
+    functions
+}
+
+#[test]
+fn test_incremental_values_match_recomputed_values() {
+    // Each change inserts, updates or deletes the row with an id.
+    proptest!(ProptestConfig::with_cases(200), |(
+        running_sum: bool,
+        changes in prop::collection::vec((0i64..8, prop::option::of(0i64..6)), 1..40))| {
+        let mut window_functions =
+            WindowFunctions::new(all_functions(running_sum), schema(), WindowFunctionState::default());
+        let mut scores = BTreeMap::new();
+        let mut output = vec![];
+        for (id, new_score) in changes {
+            let deleted = scores.remove(&id).map(|score| key(id, score)).into_iter().collect();
+            let inserted = new_score.map(|score| key(id, score)).into_iter().collect();
+            if let Some(score) = new_score {
+                scores.insert(id, score);
+            }
+            for op in window_functions.apply(vec![], deleted, inserted).unwrap() {
+                match op {
+                    Operation::Insert { new } => output.push(new.values),
+                    Operation::Delete { old } => {
+                        let index = output.iter().position(|row| *row == old.values).unwrap();
+                        output.remove(index);
+                    }
+                    Operation::Update { old, new } => {
+                        let index = output.iter().position(|row| *row == old.values).unwrap();
+                        output[index] = new.values;
+                    }
+                    op => panic!("unexpected operation {op:?}"),
+                }
+            }
+
+            let mut recomputed =
+                WindowFunctions::new(all_functions(running_sum), schema(), WindowFunctionState::default());
+            let mut expected = recomputed
+                .apply(
+                    vec![],
+                    vec![],
+                    scores.iter().map(|(id, score)| key(*id, *score)).collect(),
+                )
+                .unwrap()
+                .into_iter()
+                .map(|op| match op {
+                    Operation::Insert { new } => new.values,
+                    op => panic!("unexpected operation {op:?}"),
+                })
+                .collect::<Vec<_>>();
+            expected.sort();
+            output.sort();
+            prop_assert_eq!(&output, &expected);
+        }
+    });
+}
@@ -204,7 +204,7 @@ fn get_record_from_json(data: String, schema: &Schema) -> Record {
     };
 
     for field_definition in schema.fields.iter() {
-        let field_type = field_definition.typ;
+        let field_type = &field_definition.typ;
         let field_name = &field_definition.name;
         let json_value = root.get(field_name).unwrap();
 
@@ -217,11 +217,11 @@ fn get_record_from_json(data: String, schema: &Schema) -> Record {
             | FieldType::String
             | FieldType::Text => json_value_to_field(
                 json_value.clone(),
-                field_definition.typ,
+                field_definition.typ.clone(),
                 field_definition.nullable,
             )
             .unwrap(),
-            FieldType::Decimal => {
+            FieldType::Decimal(_) => {
                 Field::Decimal(Decimal::from_f64(json_value.as_f64().unwrap()).unwrap())
             }
             FieldType::Timestamp => {
@@ -22,7 +22,7 @@ pub fn get_schema(columns: &[rusqlite::Column]) -> Schema {
                     "integer" => FieldType::Int,
                     "string" | "text" => FieldType::String,
                     "real" => FieldType::Float,
-                    "numeric" => FieldType::Decimal,
+                    "numeric" => FieldType::Decimal(None),
                     "timestamp" => FieldType::Timestamp,
                     f => panic!("unknown field_type : {f}"),
                 },
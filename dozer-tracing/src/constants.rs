@@ -8,10 +8,13 @@ pub const TOTAL_LATENCY_HISTOGRAM_NAME: &str = "total_latency";
 
 pub const SOURCE_OPERATION_COUNTER_NAME: &str = "source_operation";
 
+pub const LATE_RECORDS_COUNTER_NAME: &str = "late_records";
+
 //  Labels
 pub const OPERATION_TYPE_LABEL: &str = "operation_type";
 pub const TABLE_LABEL: &str = "table";
 pub const CONNECTION_LABEL: &str = "connection";
+pub const PROCESSOR_LABEL: &str = "processor";
 
 // Traces
 pub const CONNECTOR_EVENTS: &str = "connector_events";
@@ -33,6 +33,12 @@ pub enum FromArrowError {
     #[error("Duration conversion failed")]
     DurationConversionError,
 
+    #[error("Uuid conversion failed")]
+    UuidConversionError,
+
+    #[error("Invalid value of \"{0}\" field")]
+    InvalidValueError(String),
+
     #[error("Schema has {0} fields, but batch has {1}")]
     SchemaMismatchError(usize, usize),
 
@@ -3,24 +3,28 @@ use super::errors::FromArrowError::DateConversionError;
 use super::errors::FromArrowError::DateTimeConversionError;
 use super::errors::FromArrowError::DurationConversionError;
 use super::errors::FromArrowError::FieldTypeNotSupported;
+use super::errors::FromArrowError::InvalidValueError;
 use super::errors::FromArrowError::TimeConversionError;
+use super::errors::FromArrowError::UuidConversionError;
 use super::to_arrow;
 use crate::arrow_types::to_arrow::DOZER_SCHEMA_KEY;
 use crate::json_types::json_from_str;
+use crate::rust_decimal::Decimal;
 use crate::types::{
-    Field as DozerField, FieldDefinition, FieldType, Record, Schema as DozerSchema, Schema,
-    SourceDefinition,
+    DecimalType, DozerPoint, Field as DozerField, FieldDefinition, FieldType, Record,
+    Schema as DozerSchema, Schema, SourceDefinition,
 };
 use arrow::array;
 use arrow::array::ArrayAccessor;
-use arrow::array::{Array, ArrayRef};
-use arrow::datatypes::{DataType, TimeUnit};
+use arrow::array::{Array, ArrayRef, AsArray, GenericListArray, OffsetSizeTrait};
+use arrow::datatypes::{DataType, Decimal128Type, Decimal256Type, TimeUnit};
 use arrow::ipc::writer::StreamWriter;
 use arrow::record_batch::RecordBatch;
 use arrow::row::SortField;
 
 use log::error;
 use std::sync::Arc;
+use uuid::Uuid;
 
 fn make_from<A: Array + 'static>(column: &Arc<dyn Array>, row: usize) -> DozerField
 where
@@ -162,6 +166,82 @@ fn make_json(column: &ArrayRef, row: usize) -> Result<DozerField, FromArrowError
     }
 }
 
+fn make_uuid(column: &ArrayRef, row: usize) -> Result<DozerField, FromArrowError> {
+    match column.as_fixed_size_binary_opt() {
+        Some(r) if !r.is_null(row) => Uuid::from_slice(r.value(row))
+            .map(DozerField::Uuid)
+            .map_err(|_| UuidConversionError),
+        _ => Ok(DozerField::Null),
+    }
+}
+
+fn make_decimal(column: &ArrayRef, row: usize, scale: u32) -> DozerField {
+    match column.as_primitive_opt::<Decimal128Type>() {
+        Some(r) if !r.is_null(row) => {
+            DozerField::Decimal(Decimal::from_i128_with_scale(r.value(row), scale))
+        }
+        _ => DozerField::Null,
+    }
+}
+
+/// The largest scale a `Decimal` can have.
+const MAX_DECIMAL_SCALE: u32 = 28;
+
+/// Decimals with an unspecified precision and scale are written as `Decimal256` with a large scale, which is
+/// reduced back to one a `Decimal` can hold.
+fn make_decimal256(
+    column: &ArrayRef,
+    row: usize,
+    scale: i8,
+    column_name: &str,
+) -> Result<DozerField, FromArrowError> {
+    match column.as_primitive_opt::<Decimal256Type>() {
+        Some(r) if !r.is_null(row) => {
+            let mut value = r.value(row);
+            let mut scale = scale.max(0) as u32;
+            let ten = arrow::datatypes::i256::from_i128(10);
+            // Dropping the trailing zeros makes the mantissa small enough for a `Decimal`.
+            while scale > MAX_DECIMAL_SCALE
+                || (scale > 0 && value.wrapping_rem(ten) == arrow::datatypes::i256::ZERO)
+            {
+                value = value.wrapping_div(ten);
+                scale -= 1;
+            }
+            value
+                .to_i128()
+                .and_then(|mantissa| Decimal::try_from_i128_with_scale(mantissa, scale).ok())
+                .map(DozerField::Decimal)
+                .ok_or_else(|| InvalidValueError(column_name.to_string()))
+        }
+        _ => Ok(DozerField::Null),
+    }
+}
+
+/// Parses a column that holds the string representation of a value, like 128-bit integers.
+fn make_parsed<T: std::str::FromStr>(
+    column: &ArrayRef,
+    row: usize,
+    column_name: &str,
+    field: impl Fn(T) -> DozerField,
+) -> Result<DozerField, FromArrowError> {
+    match column.as_string_opt::<i32>() {
+        Some(r) if !r.is_null(row) => r
+            .value(row)
+            .parse()
+            .map(field)
+            .map_err(|_| InvalidValueError(column_name.to_string())),
+        _ => Ok(DozerField::Null),
+    }
+}
+
+fn field_type<'a>(schema: &'a Schema, column_name: &str) -> Option<&'a FieldType> {
+    schema
+        .fields
+        .iter()
+        .find(|fd| fd.name == column_name)
+        .map(|fd| &fd.typ)
+}
+
 pub fn map_schema_to_dozer(
     schema: &arrow::datatypes::Schema,
 ) -> Result<DozerSchema, FromArrowError> {
@@ -202,9 +282,8 @@ fn handle_with_dozer_schema(
 pub fn map_arrow_to_dozer_type(dt: &DataType) -> Result<FieldType, FromArrowError> {
     match dt {
         DataType::Boolean => Ok(FieldType::Boolean),
-        DataType::Time32(_)
-        | DataType::Time64(_)
-        | DataType::Duration(_)
+        DataType::Time32(_) | DataType::Time64(_) => Ok(FieldType::Time),
+        DataType::Duration(_)
         | DataType::Interval(_)
         | DataType::Int8
         | DataType::Int16
@@ -221,18 +300,57 @@ pub fn map_arrow_to_dozer_type(dt: &DataType) -> Result<FieldType, FromArrowErro
         }
         DataType::Utf8 => Ok(FieldType::String),
         DataType::LargeUtf8 => Ok(FieldType::Text),
-        // DataType::List(_) => {}
-        // DataType::FixedSizeList(_, _) => {}
-        // DataType::LargeList(_) => {}
-        // DataType::Struct(_) => {}
+        DataType::List(field) | DataType::FixedSizeList(field, _) | DataType::LargeList(field) => {
+            Ok(FieldType::List(Box::new(map_arrow_to_dozer_type(
+                field.data_type(),
+            )?)))
+        }
+        DataType::Struct(fields) => Ok(FieldType::Struct(
+            fields
+                .iter()
+                .map(|field| {
+                    Ok(FieldDefinition::new(
+                        field.name().clone(),
+                        map_arrow_to_dozer_type(field.data_type())?,
+                        field.is_nullable(),
+                        SourceDefinition::Dynamic,
+                    ))
+                })
+                .collect::<Result<_, FromArrowError>>()?,
+        )),
+        DataType::Decimal128(precision, scale) if *scale >= 0 => Ok(FieldType::Decimal(Some(
+            DecimalType::new(*precision, *scale as u8),
+        ))),
         // DataType::Union(_, _, _) => {}
         // DataType::Dictionary(_, _) => {}
-        // DataType::Decimal128(_, _) => {}
-        // DataType::Decimal256(_, _) => {}
+        DataType::Decimal256(_, _) => Ok(FieldType::Decimal(None)),
         _ => Err(FieldTypeNotSupported(format!("{dt:?}"))),
     }
 }
 
+fn make_list<O: OffsetSizeTrait>(
+    array: Option<&GenericListArray<O>>,
+    row: usize,
+    column_name: &str,
+    schema: &Schema,
+) -> Result<DozerField, FromArrowError> {
+    match array {
+        Some(r) if !r.is_null(row) => make_list_values(&r.value(row), column_name, schema),
+        _ => Ok(DozerField::Null),
+    }
+}
+
+fn make_list_values(
+    values: &ArrayRef,
+    column_name: &str,
+    schema: &Schema,
+) -> Result<DozerField, FromArrowError> {
+    (0..values.len())
+        .map(|row| map_value_to_dozer_field(values, row, column_name, schema))
+        .collect::<Result<_, _>>()
+        .map(DozerField::List)
+}
+
 pub fn map_value_to_dozer_field(
     column: &ArrayRef,
     row: usize,
@@ -245,6 +363,14 @@ pub fn map_value_to_dozer_field(
         DataType::Int8 => Ok(make_from::<array::Int8Array>(column, row)),
         DataType::Int16 => Ok(make_from::<array::Int16Array>(column, row)),
         DataType::Int32 => Ok(make_from::<array::Int32Array>(column, row)),
+        DataType::Int64 if field_type(schema, column_name) == Some(&FieldType::Int8) => {
+            match make_from::<array::Int64Array>(column, row) {
+                DozerField::Int(v) => i8::try_from(v)
+                    .map(DozerField::Int8)
+                    .map_err(|_| InvalidValueError(column_name.to_string())),
+                field => Ok(field),
+            }
+        }
         DataType::Int64 => Ok(make_from::<array::Int64Array>(column, row)),
         DataType::UInt8 => Ok(make_from::<array::UInt8Array>(column, row)),
         DataType::UInt16 => Ok(make_from::<array::UInt16Array>(column, row)),
@@ -289,9 +415,28 @@ pub fn map_value_to_dozer_field(
         DataType::Duration(TimeUnit::Second) => {
             make_duration!(array::DurationSecondArray, column, row)
         }
+        DataType::Binary if field_type(schema, column_name) == Some(&FieldType::Point) => {
+            match column.as_binary_opt::<i32>() {
+                Some(r) if !r.is_null(row) => DozerPoint::from_bytes(r.value(row))
+                    .map(DozerField::Point)
+                    .map_err(|_| InvalidValueError(column_name.to_string())),
+                _ => Ok(DozerField::Null),
+            }
+        }
         DataType::Binary => make_binary!(array::BinaryArray, column, row),
+        DataType::FixedSizeBinary(16)
+            if field_type(schema, column_name) == Some(&FieldType::Uuid) =>
+        {
+            make_uuid(column, row)
+        }
         DataType::FixedSizeBinary(_) => make_binary!(array::FixedSizeBinaryArray, column, row),
         DataType::LargeBinary => make_binary!(array::LargeBinaryArray, column, row),
+        DataType::Utf8 if field_type(schema, column_name) == Some(&FieldType::U128) => {
+            make_parsed(column, row, column_name, DozerField::U128)
+        }
+        DataType::Utf8 if field_type(schema, column_name) == Some(&FieldType::I128) => {
+            make_parsed(column, row, column_name, DozerField::I128)
+        }
         DataType::Utf8 => {
             for fd in schema.fields.clone().into_iter() {
                 if fd.name == *column_name && fd.typ == FieldType::Json {
@@ -302,14 +447,28 @@ pub fn map_value_to_dozer_field(
         }
         DataType::LargeUtf8 => make_text!(array::LargeStringArray, column, row),
         // DataType::Interval(TimeUnit::) => make_from!(array::BooleanArray, x, x0),
-        // DataType::List(_) => {}
-        // DataType::FixedSizeList(_, _) => {}
-        // DataType::LargeList(_) => {}
-        // DataType::Struct(_) => {}
+        DataType::List(_) => make_list(column.as_list_opt::<i32>(), row, column_name, schema),
+        DataType::LargeList(_) => make_list(column.as_list_opt::<i64>(), row, column_name, schema),
+        DataType::FixedSizeList(_, _) => match column.as_fixed_size_list_opt() {
+            Some(r) if !r.is_null(row) => make_list_values(&r.value(row), column_name, schema),
+            _ => Ok(DozerField::Null),
+        },
+        DataType::Struct(_) => match column.as_struct_opt() {
+            Some(r) if !r.is_null(row) => r
+                .columns()
+                .iter()
+                .zip(r.column_names())
+                .map(|(column, name)| map_value_to_dozer_field(column, row, name, schema))
+                .collect::<Result<_, _>>()
+                .map(DozerField::Struct),
+            _ => Ok(DozerField::Null),
+        },
+        DataType::Decimal128(_, scale) if *scale >= 0 => {
+            Ok(make_decimal(column, row, *scale as u32))
+        }
+        DataType::Decimal256(_, scale) => make_decimal256(column, row, *scale, column_name),
         // DataType::Union(_, _, _) => {}
         // DataType::Dictionary(_, _) => {}
-        // DataType::Decimal128(_, _) => {}
-        // DataType::Decimal256(_, _) => {}
         // DataType::Map(_, _) => {}
         _ => Err(FieldTypeNotSupported(column_name.to_string())),
     }
@@ -331,7 +490,7 @@ pub fn map_record_batch_to_dozer_records(
     let dozer_schema = map_schema_to_dozer(&batch_schema)?;
     let mut sort_fields = vec![];
     for x in schema.fields.iter() {
-        let dt = to_arrow::map_field_type(x.typ);
+        let dt = to_arrow::map_field_type(&x.typ);
         sort_fields.push(SortField::new(dt));
     }
     let num_rows = batch.num_rows();
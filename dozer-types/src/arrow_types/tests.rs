@@ -1,6 +1,8 @@
 use crate::arrow_types::from_arrow::map_schema_to_dozer;
 use crate::arrow_types::to_arrow::DOZER_SCHEMA_KEY;
-use crate::types::{FieldDefinition, FieldType, Schema as DozerSchema, SourceDefinition};
+use crate::types::{
+    DecimalType, FieldDefinition, FieldType, Schema as DozerSchema, SourceDefinition,
+};
 use arrow::datatypes::{self as arrow_types};
 
 use std::collections::HashMap;
@@ -132,3 +134,192 @@ fn roundtrip_record_to_record_batch() {
 
     assert_eq!(original_schema, arrow_field_test_cases_schema());
 }
+
+#[test]
+fn null_record_to_record_batch() {
+    use crate::arrow_types::to_arrow::map_record_to_arrow;
+    use crate::types::{Field, Record};
+
+    let types = [
+        FieldType::UInt,
+        FieldType::U128,
+        FieldType::Int,
+        FieldType::Int8,
+        FieldType::I128,
+        FieldType::Float,
+        FieldType::Boolean,
+        FieldType::String,
+        FieldType::Text,
+        FieldType::Binary,
+        FieldType::Decimal(None),
+        FieldType::Decimal(Some(DecimalType::new(10, 2))),
+        FieldType::Timestamp,
+        FieldType::Date,
+        FieldType::Json,
+        FieldType::Point,
+        FieldType::Duration,
+        FieldType::List(Box::new(FieldType::Int)),
+        FieldType::Struct(vec![FieldDefinition::new(
+            "a".to_string(),
+            FieldType::Int,
+            true,
+            SourceDefinition::Dynamic,
+        )]),
+        FieldType::Uuid,
+        FieldType::Time,
+        FieldType::Enum(vec!["a".to_string()]),
+    ];
+    let mut schema = DozerSchema::default();
+    for (index, typ) in types.iter().enumerate() {
+        schema.field(
+            FieldDefinition::new(
+                index.to_string(),
+                typ.clone(),
+                true,
+                SourceDefinition::Dynamic,
+            ),
+            false,
+        );
+    }
+    let record = Record::new(vec![Field::Null; types.len()]);
+    let record_batch = map_record_to_arrow(record, &schema).unwrap();
+    assert_eq!(record_batch.num_rows(), 1);
+    assert!(record_batch
+        .columns()
+        .iter()
+        .all(|column| column.null_count() == 1));
+}
+
+#[test]
+fn roundtrip_decimal_with_precision_and_scale() {
+    use crate::arrow_types::from_arrow::map_record_batch_to_dozer_records;
+    use crate::arrow_types::to_arrow::map_record_to_arrow;
+    use crate::rust_decimal::Decimal;
+    use crate::types::{Field, Record};
+
+    let schema = DozerSchema::default()
+        .field(
+            FieldDefinition::new(
+                "price".to_string(),
+                FieldType::Decimal(Some(DecimalType::new(10, 2))),
+                false,
+                SourceDefinition::Dynamic,
+            ),
+            false,
+        )
+        .clone();
+
+    let record = Record::new(vec![Field::Decimal(Decimal::new(12346, 3))]);
+    let record_batch = map_record_to_arrow(record, &schema).unwrap();
+    assert_eq!(
+        record_batch.schema().field(0).data_type(),
+        &arrow_types::DataType::Decimal128(10, 2)
+    );
+
+    let records = map_record_batch_to_dozer_records(record_batch, &schema).unwrap();
+    assert_eq!(
+        records[0].values,
+        vec![Field::Decimal(Decimal::new(1235, 2))]
+    );
+}
+
+#[test]
+fn roundtrip_records_to_record_batch() {
+    use crate::arrow_types::from_arrow::map_record_batch_to_dozer_records;
+    use crate::arrow_types::to_arrow::map_records_to_arrow;
+    use crate::types::{Field, Record};
+
+    let mut schema = DozerSchema::default();
+    for (name, typ) in [
+        ("id", FieldType::Int),
+        ("name", FieldType::String),
+        ("tags", FieldType::List(Box::new(FieldType::String))),
+        (
+            "point",
+            FieldType::Struct(vec![
+                FieldDefinition::new(
+                    "x".to_string(),
+                    FieldType::Int,
+                    false,
+                    SourceDefinition::Dynamic,
+                ),
+                FieldDefinition::new(
+                    "y".to_string(),
+                    FieldType::Int,
+                    true,
+                    SourceDefinition::Dynamic,
+                ),
+            ]),
+        ),
+    ] {
+        schema.field(
+            FieldDefinition::new(name.to_string(), typ, true, SourceDefinition::Dynamic),
+            false,
+        );
+    }
+
+    let records = vec![
+        Record::new(vec![
+            Field::Int(1),
+            Field::String("a".to_string()),
+            Field::List(vec![
+                Field::String("x".to_string()),
+                Field::String("y".to_string()),
+            ]),
+            Field::Struct(vec![Field::Int(1), Field::Int(2)]),
+        ]),
+        Record::new(vec![Field::Null; 4]),
+        Record::new(vec![
+            Field::Int(3),
+            Field::String("c".to_string()),
+            Field::List(vec![]),
+            Field::Struct(vec![Field::Int(3), Field::Null]),
+        ]),
+    ];
+    let record_batch = map_records_to_arrow(records.clone(), &schema).unwrap();
+    assert_eq!(record_batch.num_rows(), 3);
+    assert_eq!(
+        map_record_batch_to_dozer_records(record_batch, &schema).unwrap(),
+        records
+    );
+
+    assert!(map_records_to_arrow(vec![Record::new(vec![Field::UInt(1); 4])], &schema).is_err());
+}
+
+#[test]
+fn roundtrip_types_written_as_other_arrow_types() {
+    use crate::arrow_types::from_arrow::map_record_batch_to_dozer_records;
+    use crate::arrow_types::to_arrow::map_record_to_arrow;
+    use crate::rust_decimal::Decimal;
+    use crate::types::{DozerPoint, Field, Record};
+
+    let mut schema = DozerSchema::default();
+    for (index, typ) in [
+        FieldType::Int8,
+        FieldType::U128,
+        FieldType::I128,
+        FieldType::Point,
+        FieldType::Decimal(None),
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        schema.field(
+            FieldDefinition::new(index.to_string(), typ, true, SourceDefinition::Dynamic),
+            false,
+        );
+    }
+
+    let record = Record::new(vec![
+        Field::Int8(-3),
+        Field::U128(u128::MAX),
+        Field::I128(i128::MIN),
+        Field::Point(DozerPoint::from((1.5, -2.0))),
+        Field::Decimal(Decimal::new(-123456, 3)),
+    ]);
+    let record_batch = map_record_to_arrow(record.clone(), &schema).unwrap();
+    assert_eq!(
+        map_record_batch_to_dozer_records(record_batch, &schema).unwrap(),
+        vec![record]
+    );
+}
@@ -2,12 +2,12 @@ use crate::types::{Field, FieldDefinition, FieldType, Record, Schema};
 use arrow::datatypes::{self as arrow_types, DataType};
 use arrow::{
     array::{self as arrow_array, ArrayRef},
+    buffer::{NullBuffer, OffsetBuffer},
     datatypes::i256,
-    record_batch::RecordBatch,
-};
-use arrow_schema::{
-    TimeUnit, DECIMAL128_MAX_PRECISION, DECIMAL128_MAX_SCALE, DECIMAL256_MAX_PRECISION,
+    record_batch::{RecordBatch, RecordBatchOptions},
 };
+use arrow_schema::{TimeUnit, DECIMAL128_MAX_SCALE, DECIMAL256_MAX_PRECISION};
+use chrono::Timelike;
 use std::{collections::HashMap, sync::Arc};
 
 pub const DOZER_SCHEMA_KEY: &str = "dozer_schema";
@@ -31,124 +31,273 @@ pub fn map_to_arrow_schema(
 }
 
 // Maps a Dozer Record to an Arrow RecordBatch of size 1
-// TODO: We can extend this as we build our micro batching support.
-// In a micro batch we can send a record batch that is of size > 1
 pub fn map_record_to_arrow(
     rec: Record,
     schema: &Schema,
 ) -> Result<RecordBatch, arrow::error::ArrowError> {
-    let mut columns = vec![];
+    map_records_to_arrow(vec![rec], schema)
+}
 
-    for (idx, f) in rec.values.iter().enumerate() {
-        let fd = schema.fields.get(idx).unwrap();
-        let column = match (f, fd.typ) {
-            (Field::UInt(v), FieldType::UInt) => {
-                Arc::new(arrow_array::UInt64Array::from_iter_values([*v])) as ArrayRef
-            }
-            (Field::Null, FieldType::UInt) => {
-                Arc::new(arrow_array::UInt64Array::from(vec![None as Option<u64>])) as ArrayRef
-            }
-            (Field::Int(v), FieldType::Int) => {
-                Arc::new(arrow_array::Int64Array::from_iter_values([*v])) as ArrayRef
-            }
-            (Field::Null, FieldType::Int) => {
-                Arc::new(arrow_array::Int64Array::from(vec![None as Option<i64>])) as ArrayRef
-            }
-            (Field::Float(v), FieldType::Float) => {
-                Arc::new(arrow_array::Float64Array::from_iter_values([**v])) as ArrayRef
-            }
-            (Field::Null, FieldType::Float) => {
-                Arc::new(arrow_array::Float64Array::from(vec![None as Option<f64>])) as ArrayRef
-            }
-            (Field::Boolean(v), FieldType::Boolean) => {
-                Arc::new(arrow_array::BooleanArray::from(vec![*v])) as ArrayRef
-            }
-            (Field::Null, FieldType::Boolean) => {
-                Arc::new(arrow_array::BooleanArray::from(vec![None as Option<bool>])) as ArrayRef
-            }
-            (Field::String(v), FieldType::String) => {
-                Arc::new(arrow_array::StringArray::from_iter_values([v])) as ArrayRef
-            }
-            (Field::Null, FieldType::String) => {
-                Arc::new(arrow_array::StringArray::from(vec![None as Option<String>])) as ArrayRef
-            }
-            (Field::Text(v), FieldType::Text) => {
-                Arc::new(arrow_array::LargeStringArray::from_iter_values([v])) as ArrayRef
-            }
-            (Field::Null, FieldType::Text) => Arc::new(arrow_array::LargeStringArray::from(vec![
-                None as Option<String>,
-            ])) as ArrayRef,
-            (Field::Decimal(v), FieldType::Decimal) => arrow_cast::cast(
-                &arrow_array::Decimal128Array::from(vec![v.mantissa()])
-                    .with_precision_and_scale(DECIMAL128_MAX_PRECISION, v.scale() as i8)?,
-                &DataType::Decimal256(DECIMAL256_MAX_PRECISION, DECIMAL128_MAX_SCALE),
-            )?,
-            (Field::Null, FieldType::Decimal) => Arc::new(arrow_array::Decimal256Array::from(vec![
-                None as Option<i256>,
-            ])) as ArrayRef,
-            (Field::Timestamp(v), FieldType::Timestamp) => {
-                Arc::new(arrow_array::TimestampNanosecondArray::from_iter_values([{
-                    v.timestamp_nanos_opt().expect(
-                        "value can not be represented in a timestamp with nanosecond precision.",
-                    )
-                }])) as ArrayRef
-            }
-            (Field::Null, FieldType::Timestamp) => {
-                Arc::new(arrow_array::TimestampNanosecondArray::from(vec![
-                    None as Option<i64>,
-                ])) as ArrayRef
-            }
-            (Field::Date(v), FieldType::Date) => {
-                let d = v.and_hms_milli_opt(0, 0, 0, 0).unwrap();
-                Arc::new(arrow_array::Date64Array::from_iter_values([
-                    d.timestamp_millis()
-                ])) as ArrayRef
-            }
-            (Field::Null, FieldType::Date) => {
-                Arc::new(arrow_array::Date64Array::from(vec![None as Option<i64>])) as ArrayRef
-            }
-            (Field::Binary(v), FieldType::Binary) => {
-                Arc::new(arrow_array::BinaryArray::from_iter_values([v])) as ArrayRef
-            }
-            (Field::Json(v), FieldType::Json) => Arc::new(
-                arrow_array::StringArray::from_iter_values([format!("{v:?}")]),
-            ) as ArrayRef,
-            (Field::Null, FieldType::Json) => {
-                Arc::new(arrow_array::StringArray::from(vec![None as Option<String>])) as ArrayRef
-            }
-            (Field::Point(v), FieldType::Point) => {
-                Arc::new(arrow_array::BinaryArray::from_iter_values([v.to_bytes()])) as ArrayRef
-            }
-            (Field::Null, FieldType::Point) => {
-                Arc::new(arrow_array::BinaryArray::from_opt_vec(vec![
-                    None as Option<&[u8]>,
-                ])) as ArrayRef
-            }
-            (Field::Duration(d), FieldType::Duration) => {
-                Arc::new(arrow_array::DurationNanosecondArray::from_iter_values([
-                    d.0.as_nanos() as i64,
-                ])) as ArrayRef
-            }
-            (Field::Null, FieldType::Duration) => {
-                Arc::new(arrow_array::BinaryArray::from_opt_vec(vec![
-                    None as Option<&[u8]>,
-                ])) as ArrayRef
-            }
-            (a, b) => Err(arrow::error::ArrowError::InvalidArgumentError(format!(
-                "Invalid field type {b:?} for the field: {a:?}",
-            )))?,
-        };
-        columns.push(column);
-    }
+// Maps Dozer Records to an Arrow RecordBatch, building every column in one go
+pub fn map_records_to_arrow(
+    records: Vec<Record>,
+    schema: &Schema,
+) -> Result<RecordBatch, arrow::error::ArrowError> {
+    let columns = schema
+        .fields
+        .iter()
+        .enumerate()
+        .map(|(idx, fd)| {
+            let fields = records
+                .iter()
+                .map(|record| &record.values[idx])
+                .collect::<Vec<_>>();
+            map_fields_to_array(&fields, &fd.typ)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let schema = map_to_arrow_schema(schema)?;
+    RecordBatch::try_new_with_options(
+        Arc::new(schema),
+        columns,
+        &RecordBatchOptions::new().with_row_count(Some(records.len())),
+    )
+}
 
-    let schema = map_to_arrow_schema(schema).unwrap();
-    RecordBatch::try_new(Arc::new(schema), columns)
+fn invalid_field(f: &Field, typ: &FieldType) -> arrow::error::ArrowError {
+    arrow::error::ArrowError::InvalidArgumentError(format!(
+        "Invalid field type {typ:?} for the field: {f:?}",
+    ))
+}
+
+// Collects the values of a column, mapping `Field::Null` to `None` and rejecting fields of another type
+fn column_values<'a, T>(
+    fields: &[&'a Field],
+    typ: &FieldType,
+    value: impl Fn(&'a Field) -> Option<T>,
+) -> Result<Vec<Option<T>>, arrow::error::ArrowError> {
+    fields
+        .iter()
+        .map(|f| match f {
+            Field::Null => Ok(None),
+            f => value(f).map(Some).ok_or_else(|| invalid_field(f, typ)),
+        })
+        .collect()
+}
+
+// Maps a column of Dozer Fields to an Arrow array
+fn map_fields_to_array(
+    fields: &[&Field],
+    typ: &FieldType,
+) -> Result<ArrayRef, arrow::error::ArrowError> {
+    let array = match typ {
+        FieldType::UInt => Arc::new(arrow_array::UInt64Array::from(column_values(
+            fields,
+            typ,
+            |f| match f {
+                Field::UInt(v) => Some(*v),
+                _ => None,
+            },
+        )?)) as ArrayRef,
+        FieldType::Int => Arc::new(arrow_array::Int64Array::from(column_values(
+            fields,
+            typ,
+            |f| match f {
+                Field::Int(v) => Some(*v),
+                _ => None,
+            },
+        )?)) as ArrayRef,
+        FieldType::Int8 => Arc::new(arrow_array::Int64Array::from(column_values(
+            fields,
+            typ,
+            |f| match f {
+                Field::Int8(v) => Some(*v as i64),
+                _ => None,
+            },
+        )?)) as ArrayRef,
+        FieldType::U128 => Arc::new(arrow_array::StringArray::from_iter(column_values(
+            fields,
+            typ,
+            |f| match f {
+                Field::U128(v) => Some(v.to_string()),
+                _ => None,
+            },
+        )?)) as ArrayRef,
+        FieldType::I128 => Arc::new(arrow_array::StringArray::from_iter(column_values(
+            fields,
+            typ,
+            |f| match f {
+                Field::I128(v) => Some(v.to_string()),
+                _ => None,
+            },
+        )?)) as ArrayRef,
+        FieldType::Float => Arc::new(arrow_array::Float64Array::from(column_values(
+            fields,
+            typ,
+            |f| match f {
+                Field::Float(v) => Some(**v),
+                _ => None,
+            },
+        )?)) as ArrayRef,
+        FieldType::Boolean => Arc::new(arrow_array::BooleanArray::from(column_values(
+            fields,
+            typ,
+            |f| match f {
+                Field::Boolean(v) => Some(*v),
+                _ => None,
+            },
+        )?)) as ArrayRef,
+        FieldType::String | FieldType::Enum(_) => Arc::new(arrow_array::StringArray::from_iter(
+            column_values(fields, typ, |f| match f {
+                Field::String(v) => Some(v.as_str()),
+                _ => None,
+            })?,
+        )) as ArrayRef,
+        FieldType::Text => Arc::new(arrow_array::LargeStringArray::from_iter(column_values(
+            fields,
+            typ,
+            |f| match f {
+                Field::Text(v) => Some(v.as_str()),
+                _ => None,
+            },
+        )?)) as ArrayRef,
+        FieldType::Decimal(Some(decimal_type)) => Arc::new(
+            arrow_array::Decimal128Array::from(column_values(fields, typ, |f| match f {
+                Field::Decimal(v) => {
+                    let mut v = *v;
+                    v.rescale(decimal_type.scale as u32);
+                    Some(v.mantissa())
+                }
+                _ => None,
+            })?)
+            .with_precision_and_scale(decimal_type.precision, decimal_type.scale as i8)?,
+        ) as ArrayRef,
+        FieldType::Decimal(None) => Arc::new(
+            arrow_array::Decimal256Array::from(column_values(fields, typ, |f| match f {
+                // Every value is scaled up to the column's scale, which is larger than any `Decimal`'s.
+                Field::Decimal(v) => Some(i256::from_i128(v.mantissa()).wrapping_mul(
+                    i256::from_i128(10).wrapping_pow(DECIMAL128_MAX_SCALE as u32 - v.scale()),
+                )),
+                _ => None,
+            })?)
+            .with_precision_and_scale(DECIMAL256_MAX_PRECISION, DECIMAL128_MAX_SCALE)?,
+        ) as ArrayRef,
+        FieldType::Timestamp => Arc::new(arrow_array::TimestampNanosecondArray::from(
+            column_values(fields, typ, |f| match f {
+                Field::Timestamp(v) => Some(v.timestamp_nanos_opt().expect(
+                    "value can not be represented in a timestamp with nanosecond precision.",
+                )),
+                _ => None,
+            })?,
+        )) as ArrayRef,
+        FieldType::Date => Arc::new(arrow_array::Date64Array::from(column_values(
+            fields,
+            typ,
+            |f| match f {
+                Field::Date(v) => Some(v.and_hms_milli_opt(0, 0, 0, 0).unwrap().timestamp_millis()),
+                _ => None,
+            },
+        )?)) as ArrayRef,
+        FieldType::Binary => Arc::new(arrow_array::BinaryArray::from_iter(column_values(
+            fields,
+            typ,
+            |f| match f {
+                Field::Binary(v) => Some(v.as_slice()),
+                _ => None,
+            },
+        )?)) as ArrayRef,
+        FieldType::Json => Arc::new(arrow_array::StringArray::from_iter(column_values(
+            fields,
+            typ,
+            |f| match f {
+                Field::Json(v) => Some(format!("{v:?}")),
+                _ => None,
+            },
+        )?)) as ArrayRef,
+        FieldType::Point => Arc::new(arrow_array::BinaryArray::from_iter(column_values(
+            fields,
+            typ,
+            |f| match f {
+                Field::Point(v) => Some(v.to_bytes()),
+                _ => None,
+            },
+        )?)) as ArrayRef,
+        FieldType::Duration => Arc::new(arrow_array::DurationNanosecondArray::from(column_values(
+            fields,
+            typ,
+            |f| match f {
+                Field::Duration(d) => Some(d.0.as_nanos() as i64),
+                _ => None,
+            },
+        )?)) as ArrayRef,
+        FieldType::Uuid => Arc::new(
+            arrow_array::FixedSizeBinaryArray::try_from_sparse_iter_with_size(
+                column_values(fields, typ, |f| match f {
+                    Field::Uuid(v) => Some(v.as_bytes()),
+                    _ => None,
+                })?
+                .into_iter(),
+                16,
+            )?,
+        ) as ArrayRef,
+        FieldType::Time => Arc::new(arrow_array::Time64NanosecondArray::from(column_values(
+            fields,
+            typ,
+            |f| match f {
+                Field::Time(v) => Some(
+                    v.num_seconds_from_midnight() as i64 * 1_000_000_000 + v.nanosecond() as i64,
+                ),
+                _ => None,
+            },
+        )?)) as ArrayRef,
+        FieldType::List(element_type) => {
+            let lists = column_values(fields, typ, |f| match f {
+                Field::List(v) => Some(v),
+                _ => None,
+            })?;
+            let elements = lists
+                .iter()
+                .flatten()
+                .flat_map(|v| v.iter())
+                .collect::<Vec<_>>();
+            Arc::new(arrow_array::ListArray::try_new(
+                Arc::new(list_element_field(element_type)),
+                OffsetBuffer::from_lengths(lists.iter().map(|v| v.map_or(0, |v| v.len()))),
+                map_fields_to_array(&elements, element_type)?,
+                Some(NullBuffer::from_iter(lists.iter().map(Option::is_some))),
+            )?) as ArrayRef
+        }
+        FieldType::Struct(struct_fields_def) => {
+            let structs = column_values(fields, typ, |f| match f {
+                Field::Struct(v) if v.len() == struct_fields_def.len() => Some(v),
+                _ => None,
+            })?;
+            // Children of a null struct are masked by the struct's null buffer.
+            let columns = struct_fields_def
+                .iter()
+                .enumerate()
+                .map(|(idx, fd)| {
+                    let children = structs
+                        .iter()
+                        .map(|v| v.map_or(&Field::Null, |v| &v[idx]))
+                        .collect::<Vec<_>>();
+                    map_fields_to_array(&children, &fd.typ)
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            Arc::new(arrow_array::StructArray::try_new(
+                struct_fields(struct_fields_def),
+                columns,
+                Some(NullBuffer::from_iter(structs.iter().map(Option::is_some))),
+            )?) as ArrayRef
+        }
+    };
+    Ok(array)
 }
 
 // Maps the dozer field type to the arrow data type
 // Optionally takes a metadata map to add additional metadata to the field
 
-pub fn map_field_type(typ: FieldType) -> DataType {
+pub fn map_field_type(typ: &FieldType) -> DataType {
     match typ {
         FieldType::UInt => DataType::UInt64,
         FieldType::U128 => DataType::Utf8,
@@ -159,19 +308,40 @@ pub fn map_field_type(typ: FieldType) -> DataType {
         FieldType::Boolean => DataType::Boolean,
         FieldType::String => DataType::Utf8,
         FieldType::Text => DataType::LargeUtf8,
-        FieldType::Decimal => DataType::Decimal256(DECIMAL256_MAX_PRECISION, DECIMAL128_MAX_SCALE),
+        FieldType::Decimal(Some(decimal_type)) => {
+            DataType::Decimal128(decimal_type.precision, decimal_type.scale as i8)
+        }
+        FieldType::Decimal(None) => {
+            DataType::Decimal256(DECIMAL256_MAX_PRECISION, DECIMAL128_MAX_SCALE)
+        }
         FieldType::Timestamp => DataType::Timestamp(arrow_types::TimeUnit::Nanosecond, None),
         FieldType::Date => DataType::Date64,
         FieldType::Binary => DataType::Binary,
         FieldType::Json => DataType::Utf8,
         FieldType::Point => DataType::Binary,
         FieldType::Duration => DataType::Duration(TimeUnit::Nanosecond),
+        FieldType::Uuid => DataType::FixedSizeBinary(16),
+        FieldType::Time => DataType::Time64(TimeUnit::Nanosecond),
+        FieldType::Enum(_) => DataType::Utf8,
+        FieldType::List(element_type) => DataType::List(Arc::new(list_element_field(element_type))),
+        FieldType::Struct(fields) => DataType::Struct(struct_fields(fields)),
     }
 }
 
+fn list_element_field(element_type: &FieldType) -> arrow_types::Field {
+    arrow_types::Field::new("item", map_field_type(element_type), true)
+}
+
+fn struct_fields(fields: &[FieldDefinition]) -> arrow_types::Fields {
+    fields
+        .iter()
+        .map(|fd| arrow_types::Field::from(fd.clone()))
+        .collect()
+}
+
 impl From<FieldDefinition> for arrow_types::Field {
     fn from(f: FieldDefinition) -> Self {
-        let dt = map_field_type(f.typ);
+        let dt = map_field_type(&f.typ);
         arrow_types::Field::new(f.name, dt, f.nullable)
     }
 }
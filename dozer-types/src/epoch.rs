@@ -51,6 +51,10 @@ impl SourceTime {
             accuracy,
         }
     }
+
+    pub fn millis_since_epoch(&self) -> u64 {
+        self.millis_since_epoch
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -58,6 +62,8 @@ pub struct Epoch {
     pub common_info: EpochCommonInfo,
     pub decision_instant: SystemTime,
     pub source_time: Option<SourceTime>,
+    /// Whether nodes should persist their state when committing this epoch.
+    pub checkpoint: bool,
 }
 
 impl Epoch {
@@ -66,6 +72,7 @@ impl Epoch {
             common_info: EpochCommonInfo { id, source_states },
             decision_instant,
             source_time: None,
+            checkpoint: false,
         }
     }
 
@@ -73,4 +80,9 @@ impl Epoch {
         self.source_time = Some(source_time);
         self
     }
+
+    pub fn with_checkpoint(mut self) -> Self {
+        self.checkpoint = true;
+        self
+    }
 }
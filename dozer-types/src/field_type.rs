@@ -1,8 +1,9 @@
 use crate::types::{DozerDuration, DozerPoint, Field};
-use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, Offset, Timelike, Utc};
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, Offset, Utc};
 use geo::Point;
 use ordered_float::OrderedFloat;
 use rust_decimal::Decimal;
+use uuid::Uuid;
 
 impl From<bool> for Field {
     fn from(value: bool) -> Self {
@@ -108,7 +109,13 @@ impl From<NaiveDate> for Field {
 
 impl From<NaiveTime> for Field {
     fn from(value: NaiveTime) -> Self {
-        Field::UInt(value.nanosecond().into())
+        Field::Time(value)
+    }
+}
+
+impl From<Uuid> for Field {
+    fn from(value: Uuid) -> Self {
+        Field::Uuid(value)
     }
 }
 
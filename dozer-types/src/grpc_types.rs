@@ -62,10 +62,10 @@ pub mod generated {
 
 pub mod conversions {
     use super::types::{value, DurationType, PointType, RustDecimal, Type, Value};
-    use crate::json_types::json_value_to_prost;
+    use crate::json_types::{field_to_json_value, json_value_to_prost};
     use crate::ordered_float::OrderedFloat;
     use crate::rust_decimal::Decimal;
-    use crate::types::{DozerDuration, Field, FieldType, DATE_FORMAT};
+    use crate::types::{DozerDuration, Field, FieldType, DATE_FORMAT, TIME_FORMAT};
     use prost_types::Timestamp;
 
     fn map_x_y_to_prost_coord_map((x, y): (OrderedFloat<f64>, OrderedFloat<f64>)) -> Value {
@@ -145,6 +145,19 @@ pub mod conversions {
             },
             Field::Point(point) => map_x_y_to_prost_coord_map(point.0.x_y()),
             Field::Duration(d) => map_duration_to_prost_coord_map(d),
+            Field::Uuid(uuid) => Value {
+                value: Some(value::Value::StringValue(uuid.to_string())),
+            },
+            Field::Time(time) => Value {
+                value: Some(value::Value::StringValue(
+                    time.format(TIME_FORMAT).to_string(),
+                )),
+            },
+            Field::List(_) | Field::Struct(_) => Value {
+                value: Some(value::Value::JsonValue(json_value_to_prost(
+                    field_to_json_value(f),
+                ))),
+            },
         }
     }
 
@@ -173,12 +186,15 @@ pub mod conversions {
             FieldType::String => Type::String,
             FieldType::Text => Type::Text,
             FieldType::Binary => Type::Binary,
-            FieldType::Decimal => Type::Decimal,
+            FieldType::Decimal(_) => Type::Decimal,
             FieldType::Timestamp => Type::Timestamp,
             FieldType::Json => Type::Json,
-            FieldType::Date => Type::String,
+            FieldType::Date | FieldType::Uuid | FieldType::Time | FieldType::Enum(_) => {
+                Type::String
+            }
             FieldType::Point => Type::Point,
             FieldType::Duration => Type::Duration,
+            FieldType::List(_) | FieldType::Struct(_) => Type::Json,
         }
     }
     pub fn map_schema(schema: crate::types::Schema) -> crate::grpc_types::types::Schema {
@@ -1,8 +1,8 @@
 use crate::errors::types::{DeserializationError, TypeError};
 use crate::json_types::{json_from_str, serde_json_to_json_value};
-use crate::types::{DozerDuration, DozerPoint, TimeUnit, DATE_FORMAT};
+use crate::types::{DozerDuration, DozerPoint, TimeUnit, DATE_FORMAT, TIME_FORMAT};
 use crate::types::{Field, FieldType};
-use chrono::{DateTime, NaiveDate};
+use chrono::{DateTime, NaiveDate, NaiveTime};
 use ordered_float::OrderedFloat;
 use rust_decimal::Decimal;
 use schemars::JsonSchema;
@@ -10,6 +10,7 @@ use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde_json::Value;
 use std::str::FromStr;
 use std::time::Duration;
+use uuid::Uuid;
 
 /// Used in REST APIs and query expressions for converting JSON value to `Field`
 pub fn json_value_to_field(
@@ -64,7 +65,7 @@ pub fn json_value_to_field(
         FieldType::Binary => serde_json::from_value(value)
             .map_err(DeserializationError::Json)
             .map(Field::Binary),
-        FieldType::Decimal => match value {
+        FieldType::Decimal(_) => match value {
             Value::String(str) => return Field::from_str(str.as_str(), typ, nullable),
             Value::Number(number) => return Field::from_str(&number.to_string(), typ, nullable),
             _ => Err(DeserializationError::Custom(
@@ -81,7 +82,7 @@ pub fn json_value_to_field(
                     .into(),
             )),
         },
-        FieldType::Date => match value {
+        FieldType::Date | FieldType::Uuid | FieldType::Time | FieldType::Enum(_) => match value {
             Value::String(str) => return Field::from_str(str.as_str(), typ, nullable),
             _ => Err(DeserializationError::Custom(
                 "Json value type does not match field type"
@@ -138,6 +139,37 @@ pub fn json_value_to_field(
                     .into(),
             )),
         },
+        FieldType::List(element_type) => match value {
+            Value::Array(values) => {
+                return values
+                    .into_iter()
+                    .map(|value| json_value_to_field(value, element_type.as_ref().clone(), true))
+                    .collect::<Result<_, _>>()
+                    .map(Field::List)
+            }
+            _ => Err(DeserializationError::Custom(
+                "Json value type does not match field type"
+                    .to_string()
+                    .into(),
+            )),
+        },
+        FieldType::Struct(fields) => match value {
+            Value::Object(mut object) => {
+                return fields
+                    .into_iter()
+                    .map(|field| {
+                        let value = object.remove(&field.name).unwrap_or(Value::Null);
+                        json_value_to_field(value, field.typ, field.nullable)
+                    })
+                    .collect::<Result<_, _>>()
+                    .map(Field::Struct)
+            }
+            _ => Err(DeserializationError::Custom(
+                "Json value type does not match field type"
+                    .to_string()
+                    .into(),
+            )),
+        },
     }
     .map_err(TypeError::DeserializationError)
 }
@@ -252,7 +284,7 @@ impl Field {
                     })
                 }
             }
-            FieldType::Decimal => {
+            FieldType::Decimal(_) => {
                 if nullable && (value.is_empty() || value == "null") {
                     Ok(Field::Null)
                 } else {
@@ -318,6 +350,58 @@ impl Field {
                     value.parse::<DozerDuration>().map(Field::Duration)
                 }
             }
+            FieldType::Uuid => {
+                if nullable && (value.is_empty() || value == "null") {
+                    Ok(Field::Null)
+                } else {
+                    Uuid::parse_str(value).map(Field::Uuid).map_err(|_| {
+                        TypeError::InvalidFieldValue {
+                            field_type: typ,
+                            nullable,
+                            value: value.to_string(),
+                        }
+                    })
+                }
+            }
+            FieldType::Time => {
+                if nullable && (value.is_empty() || value == "null") {
+                    Ok(Field::Null)
+                } else {
+                    NaiveTime::parse_from_str(value, TIME_FORMAT)
+                        .map(Field::Time)
+                        .map_err(|_| TypeError::InvalidFieldValue {
+                            field_type: typ,
+                            nullable,
+                            value: value.to_string(),
+                        })
+                }
+            }
+            FieldType::Enum(ref values) => {
+                if nullable && (value.is_empty() || value == "null") {
+                    Ok(Field::Null)
+                } else if values.iter().any(|v| v == value) {
+                    Ok(Field::String(value.to_string()))
+                } else {
+                    Err(TypeError::InvalidFieldValue {
+                        field_type: typ.clone(),
+                        nullable,
+                        value: value.to_string(),
+                    })
+                }
+            }
+            FieldType::List(_) | FieldType::Struct(_) => {
+                if nullable && (value.is_empty() || value == "null") {
+                    Ok(Field::Null)
+                } else {
+                    let json =
+                        serde_json::from_str(value).map_err(|_| TypeError::InvalidFieldValue {
+                            field_type: typ.clone(),
+                            nullable,
+                            value: value.to_string(),
+                        })?;
+                    json_value_to_field(json, typ, nullable)
+                }
+            }
         }
     }
 }
@@ -343,6 +427,7 @@ pub fn f64_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema:
 #[cfg(test)]
 mod tests {
     use crate::json_types::json;
+    use crate::types::{FieldDefinition, SourceDefinition};
     use chrono::FixedOffset;
     use geo::Point;
     use rust_decimal::prelude::FromPrimitive;
@@ -373,7 +458,7 @@ mod tests {
             ),
             (
                 "1.1",
-                FieldType::Decimal,
+                FieldType::Decimal(None),
                 false,
                 Field::Decimal(Decimal::from_f64(1.1).unwrap()),
             ),
@@ -405,6 +490,49 @@ mod tests {
                 false,
                 Field::Json(json!({"abc": "foo"})),
             ),
+            (
+                "[1,null]",
+                FieldType::List(Box::new(FieldType::Int)),
+                false,
+                Field::List(vec![Field::Int(1), Field::Null]),
+            ),
+            (
+                "{\"a\":\"foo\"}",
+                FieldType::Struct(vec![
+                    FieldDefinition::new(
+                        "a".to_string(),
+                        FieldType::String,
+                        false,
+                        SourceDefinition::Dynamic,
+                    ),
+                    FieldDefinition::new(
+                        "b".to_string(),
+                        FieldType::Int,
+                        true,
+                        SourceDefinition::Dynamic,
+                    ),
+                ]),
+                false,
+                Field::Struct(vec![Field::String("foo".to_string()), Field::Null]),
+            ),
+            (
+                "67e55044-10b1-426f-9247-bb680e5fe0c8",
+                FieldType::Uuid,
+                false,
+                Field::Uuid(Uuid::parse_str("67e55044-10b1-426f-9247-bb680e5fe0c8").unwrap()),
+            ),
+            (
+                "12:30:00.5",
+                FieldType::Time,
+                false,
+                Field::Time(NaiveTime::from_hms_milli_opt(12, 30, 0, 500).unwrap()),
+            ),
+            (
+                "b",
+                FieldType::Enum(vec!["a".to_string(), "b".to_string()]),
+                false,
+                Field::String("b".to_string()),
+            ),
             ("null", FieldType::UInt, true, Field::Null),
             ("null", FieldType::U128, true, Field::Null),
             ("null", FieldType::Int, true, Field::Null),
@@ -424,13 +552,15 @@ mod tests {
                 Field::Text("null".to_string()),
             ),
             ("null", FieldType::Binary, true, Field::Null),
-            ("null", FieldType::Decimal, true, Field::Null),
+            ("null", FieldType::Decimal(None), true, Field::Null),
             ("null", FieldType::Timestamp, true, Field::Null),
             ("null", FieldType::Date, true, Field::Null),
             ("null", FieldType::Json, true, Field::Null),
             ("null", FieldType::Point, true, Field::Null),
             ("null", FieldType::Json, true, Field::Null),
             ("null", FieldType::Duration, true, Field::Null),
+            ("null", FieldType::Uuid, true, Field::Null),
+            ("null", FieldType::Time, true, Field::Null),
             ("", FieldType::UInt, true, Field::Null),
             ("", FieldType::U128, true, Field::Null),
             ("", FieldType::Int, true, Field::Null),
@@ -440,7 +570,7 @@ mod tests {
             ("", FieldType::String, true, Field::String(String::new())),
             ("", FieldType::Text, true, Field::Text(String::new())),
             ("", FieldType::Binary, true, Field::Null),
-            ("", FieldType::Decimal, true, Field::Null),
+            ("", FieldType::Decimal(None), true, Field::Null),
             ("", FieldType::Timestamp, true, Field::Null),
             ("", FieldType::Date, true, Field::Null),
             ("", FieldType::Json, true, Field::Null),
@@ -460,7 +590,7 @@ mod tests {
             ("null", FieldType::Float, false),
             ("null", FieldType::Boolean, false),
             ("null", FieldType::Binary, false),
-            ("null", FieldType::Decimal, false),
+            ("null", FieldType::Decimal(None), false),
             ("null", FieldType::Timestamp, false),
             ("null", FieldType::Date, false),
             ("null", FieldType::Point, false),
@@ -472,11 +602,18 @@ mod tests {
             ("", FieldType::Float, false),
             ("", FieldType::Boolean, false),
             ("", FieldType::Binary, false),
-            ("", FieldType::Decimal, false),
+            ("", FieldType::Decimal(None), false),
             ("", FieldType::Timestamp, false),
             ("", FieldType::Date, false),
             ("", FieldType::Point, false),
             ("", FieldType::Duration, false),
+            ("", FieldType::Uuid, false),
+            ("", FieldType::Time, false),
+            (
+                "c",
+                FieldType::Enum(vec!["a".to_string(), "b".to_string()]),
+                false,
+            ),
         ];
         for err_case in err_cases {
             assert!(Field::from_str(err_case.0, err_case.1, err_case.2).is_err());
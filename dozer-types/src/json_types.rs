@@ -1,7 +1,7 @@
 use std::cmp::Ordering;
 
 use crate::errors::types::DeserializationError;
-use crate::types::{DozerDuration, Field, DATE_FORMAT};
+use crate::types::{DozerDuration, Field, DATE_FORMAT, TIME_FORMAT};
 use chrono::SecondsFormat;
 use ordered_float::OrderedFloat;
 use prost_types::value::Kind;
@@ -127,7 +127,14 @@ pub fn field_to_json_value(field: Field) -> JsonValue {
         Field::Json(b) => b,
         Field::Point(point) => convert_x_y_to_object(point.0.x_y()),
         Field::Duration(d) => convert_duration_to_object(d),
+        Field::Uuid(uuid) => uuid.to_string().into(),
+        Field::Time(time) => time.format(TIME_FORMAT).to_string().into(),
         Field::Null => JsonValue::NULL,
+        Field::List(v) | Field::Struct(v) => v
+            .into_iter()
+            .map(field_to_json_value)
+            .collect::<IArray>()
+            .into(),
     }
 }
 
@@ -329,7 +336,10 @@ mod tests {
             (FieldType::Boolean, Field::Boolean(true)),
             (FieldType::String, Field::String("a".to_string())),
             (FieldType::Binary, Field::Binary(b"asdf".to_vec())),
-            (FieldType::Decimal, Field::Decimal(Decimal::new(202, 2))),
+            (
+                FieldType::Decimal(None),
+                Field::Decimal(Decimal::new(202, 2)),
+            ),
             (
                 FieldType::Timestamp,
                 Field::Timestamp(Utc.fix().with_ymd_and_hms(2001, 1, 1, 0, 4, 0).unwrap()),
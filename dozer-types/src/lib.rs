@@ -48,3 +48,4 @@ pub use serde_json;
 pub use serde_yaml;
 pub use thiserror;
 pub use tracing;
+pub use uuid;
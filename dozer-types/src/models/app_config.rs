@@ -15,6 +15,10 @@ pub struct AppConfig {
     /// The event hub's queue capacity. Events that are not processed will be dropped.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub event_hub_capacity: Option<usize>,
+
+    /// Minimal interval between two checkpoints of the pipeline state, in milliseconds. Only used if app checkpoints are enabled.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checkpoint_interval: Option<u64>,
 }
 
 pub fn default_app_buffer_size() -> u32 {
@@ -28,3 +32,7 @@ pub fn default_error_threshold() -> u32 {
 pub fn default_event_hub_capacity() -> usize {
     100
 }
+
+pub fn default_checkpoint_interval() -> u64 {
+    60_000
+}
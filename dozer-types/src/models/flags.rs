@@ -28,6 +28,10 @@ pub struct Flags {
 
     /// app checkpoints can be used to resume execution of a query.; Default: false
     pub enable_app_checkpoints: Option<bool>,
+
+    /// where join processors keep their state.; Default: Memory
+    #[serde(default, skip_serializing_if = "equal_default")]
+    pub join_state: JoinStateBackend,
 }
 
 pub fn default_dynamic() -> bool {
@@ -50,6 +54,32 @@ pub struct EnableProbabilisticOptimizations {
     pub in_aggregations: Option<bool>,
 }
 
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Default, PartialEq, Eq, Clone)]
+#[serde(deny_unknown_fields)]
+pub enum JoinStateBackend {
+    /// keep all join state in memory.
+    #[default]
+    Memory,
+    /// keep join state in a file on disk, with a bounded in-memory cache in front of it.
+    Disk(DiskJoinStateOptions),
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Default, PartialEq, Eq, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct DiskJoinStateOptions {
+    /// directory to write join state files to.; Default: the system temporary directory
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dir: Option<String>,
+
+    /// maximum number of join keys cached in memory, per join input.; Default: 100000
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache_size: Option<usize>,
+}
+
+pub fn default_join_state_cache_size() -> usize {
+    100_000
+}
+
 pub fn default_push_events() -> bool {
     true
 }
@@ -109,6 +109,9 @@ pub enum SinkConfig {
     Aerospike(AerospikeSinkConfig),
     Clickhouse(ClickhouseSinkConfig),
     Oracle(OracleSinkConfig),
+    Postgres(PostgresSinkConfig),
+    Kafka(KafkaSinkConfig),
+    File(FileSinkConfig),
 }
 impl SinkConfig {
     pub fn name(&self) -> String {
@@ -117,6 +120,9 @@ impl SinkConfig {
             SinkConfig::Aerospike(_) => "aerospike",
             SinkConfig::Clickhouse(_) => "clickhouse",
             SinkConfig::Oracle(_) => "oracle",
+            SinkConfig::Postgres(_) => "postgres",
+            SinkConfig::Kafka(_) => "kafka",
+            SinkConfig::File(_) => "file",
         };
         return name.to_string();
     }
@@ -216,6 +222,26 @@ pub struct ClickhouseSinkConfig {
     #[serde(default = "ClickhouseSinkConfig::default_database")]
     pub database: String,
     pub options: Vec<(String, String)>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tables: Vec<ClickhouseSinkTable>,
+    /// Source table of a single-table sink, as configured before `tables`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_table_name: Option<String>,
+    /// Sink table of a single-table sink, as configured before `tables`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sink_table_name: Option<String>,
+    /// Table options of a single-table sink, as configured before `tables`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub create_table_options: Option<ClickhouseTableOptions>,
+    /// Add new nullable columns and widen column types when the source schema changes,
+    /// instead of failing.
+    #[serde(default)]
+    pub schema_evolution: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Eq, PartialEq, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct ClickhouseSinkTable {
     pub source_table_name: String,
     pub sink_table_name: String,
     pub create_table_options: Option<ClickhouseTableOptions>,
@@ -237,6 +263,20 @@ impl ClickhouseSinkConfig {
     fn default_user() -> String {
         "default".to_string()
     }
+
+    /// Moves the table of the top-level `source_table_name`, `sink_table_name` and `create_table_options`
+    /// to the front of `tables`.
+    pub fn move_single_table_to_tables(&mut self) {
+        if self.source_table_name.is_none() || self.sink_table_name.is_none() {
+            return;
+        }
+        let table = ClickhouseSinkTable {
+            source_table_name: self.source_table_name.take().unwrap(),
+            sink_table_name: self.sink_table_name.take().unwrap(),
+            create_table_options: self.create_table_options.take(),
+        };
+        self.tables.insert(0, table);
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema, Eq, PartialEq, Clone)]
@@ -261,6 +301,83 @@ pub struct OracleSinkConfig {
     pub owner: Option<String>,
 }
 
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Eq, PartialEq, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct PostgresSinkConfig {
+    pub connection: String,
+    pub source_table_name: String,
+    pub sink_table_name: String,
+    /// The schema of the sink table. If not set, the table is created in the first schema of the search path.
+    #[serde(default)]
+    pub schema: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Eq, PartialEq, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct KafkaSinkConfig {
+    pub connection: String,
+    pub source_table_name: String,
+    pub topic: String,
+    #[serde(default, skip_serializing_if = "equal_default")]
+    pub format: KafkaSinkFormat,
+}
+
+/// How the Debezium change events are serialized.
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Eq, PartialEq, Clone, Copy, Default)]
+#[serde(deny_unknown_fields)]
+pub enum KafkaSinkFormat {
+    /// JSON with the schema embedded in every message, like Kafka Connect's `JsonConverter`.
+    #[default]
+    Json,
+    /// Avro, with the schemas registered in the connection's schema registry.
+    Avro,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Eq, PartialEq, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct FileSinkConfig {
+    /// Name of a `S3Storage` or `LocalStorage` connection.
+    pub connection: String,
+    pub source_table_name: String,
+    /// Folder the files are written to, relative to the storage's root.
+    pub path: String,
+    #[serde(default, skip_serializing_if = "equal_default")]
+    pub format: FileSinkFormat,
+    #[serde(default, skip_serializing_if = "equal_default")]
+    pub mode: FileSinkMode,
+    /// Start a new file once the current one reaches this size. Only applies to `Cdc` mode.
+    #[serde(default)]
+    pub max_file_size_bytes: Option<u64>,
+    /// Start a new file once the current one is this old. Only applies to `Cdc` mode.
+    #[serde(default)]
+    pub max_file_age_secs: Option<u64>,
+    /// Start a new file on every commit. Only applies to `Cdc` mode,
+    /// where it is the default if no other limit is set.
+    #[serde(default, skip_serializing_if = "equal_default")]
+    pub roll_on_commit: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Eq, PartialEq, Clone, Copy, Default)]
+#[serde(deny_unknown_fields)]
+pub enum FileSinkFormat {
+    #[default]
+    Parquet,
+    Csv,
+    /// JSON Lines, one object per row.
+    Ndjson,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Eq, PartialEq, Clone, Copy, Default)]
+#[serde(deny_unknown_fields)]
+pub enum FileSinkMode {
+    /// Append every change to the files, with an `__dozer_op` column
+    /// holding `insert`, `update` or `delete`.
+    #[default]
+    Cdc,
+    /// Rewrite a single file with the latest state of every row on each commit.
+    Snapshot,
+}
+
 pub fn default_log_reader_batch_size() -> u32 {
     1000
 }
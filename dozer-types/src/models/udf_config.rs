@@ -30,4 +30,12 @@ pub struct OnnxConfig {
 pub struct JavaScriptConfig {
     /// path to the module file
     pub module: String,
+
+    /// type of the values returned by the function, e.g. `int` or `string`; Default: json
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub return_type: Option<String>,
+
+    /// call the function once per batch of records, with an array of values per argument, expecting an array of results; Default: false
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub batch: Option<bool>,
 }
@@ -0,0 +1,60 @@
+use crate::models::sink::{ClickhouseSinkConfig, ClickhouseSinkTable, ClickhouseTableOptions};
+
+#[test]
+fn single_table_config() {
+    let config = r#"
+    options: []
+    source_table_name: trips
+    sink_table_name: trips_sink
+    create_table_options:
+      engine: MergeTree
+  "#;
+    let mut config = serde_yaml::from_str::<ClickhouseSinkConfig>(config).unwrap();
+    config.move_single_table_to_tables();
+    assert_eq!(
+        config.tables,
+        vec![ClickhouseSinkTable {
+            source_table_name: "trips".to_string(),
+            sink_table_name: "trips_sink".to_string(),
+            create_table_options: Some(ClickhouseTableOptions {
+                engine: Some("MergeTree".to_string()),
+                primary_keys: None,
+                partition_by: None,
+                sample_by: None,
+                order_by: None,
+                cluster: None,
+            }),
+        }]
+    );
+    assert_eq!(config.source_table_name, None);
+    assert_eq!(config.sink_table_name, None);
+    assert_eq!(config.create_table_options, None);
+}
+
+#[test]
+fn multiple_tables_config() {
+    let config = r#"
+    options: []
+    tables:
+      - source_table_name: trips
+        sink_table_name: trips_sink
+      - source_table_name: zones
+        sink_table_name: zones_sink
+  "#;
+    let mut config = serde_yaml::from_str::<ClickhouseSinkConfig>(config).unwrap();
+    config.move_single_table_to_tables();
+    let table_names = config
+        .tables
+        .iter()
+        .map(|table| {
+            (
+                table.source_table_name.as_str(),
+                table.sink_table_name.as_str(),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        table_names,
+        vec![("trips", "trips_sink"), ("zones", "zones_sink")]
+    );
+}
@@ -1,12 +1,15 @@
 use crate::errors::types::DeserializationError;
 use crate::json_types::{
-    json_cmp, json_from_bytes, json_from_str, json_to_bytes, json_to_bytes_size, JsonValue,
+    field_to_json_value, json_cmp, json_from_bytes, json_from_str, json_to_bytes,
+    json_to_bytes_size, JsonValue,
 };
 use crate::types::{
     DozerDuration, DozerPoint, FieldDefinition, Schema, SourceDefinition, TimeUnit,
 };
 #[allow(unused_imports)]
-use chrono::{DateTime, Datelike, FixedOffset, LocalResult, NaiveDate, TimeZone, Utc};
+use chrono::{
+    DateTime, Datelike, FixedOffset, LocalResult, NaiveDate, NaiveTime, TimeZone, Timelike, Utc,
+};
 use ijson::DestructuredRef;
 use ordered_float::OrderedFloat;
 use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
@@ -16,8 +19,14 @@ use std::borrow::Cow;
 use std::fmt::{Display, Formatter};
 use std::str::FromStr;
 use std::time::Duration;
+use uuid::Uuid;
 
 pub const DATE_FORMAT: &str = "%Y-%m-%d";
+pub const TIME_FORMAT: &str = "%H:%M:%S%.f";
+
+/// Size of the length prefix of each element in the encoding of a list or struct.
+const LIST_ELEMENT_LENGTH_SIZE: usize = 4;
+
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum Field {
@@ -38,6 +47,12 @@ pub enum Field {
     Point(DozerPoint),
     Duration(DozerDuration),
     Null,
+    /// The elements of an array.
+    List(Vec<Field>),
+    /// The values of a nested record, in the order of its `FieldType::Struct` fields.
+    Struct(Vec<Field>),
+    Uuid(#[cfg_attr(feature= "arbitrary", arbitrary(with = arbitrary_uuid))] Uuid),
+    Time(#[cfg_attr(feature= "arbitrary", arbitrary(with = arbitrary_time))] NaiveTime),
 }
 
 impl bincode::Decode for Field {
@@ -76,9 +91,16 @@ impl bincode::Decode for Field {
             13 => Ok(Field::Point(DozerPoint::decode(decoder)?)),
             14 => Ok(Field::Duration(DozerDuration::decode(decoder)?)),
             15 => Ok(Field::Null),
+            17 => Ok(Field::List(Vec::<Field>::decode(decoder)?)),
+            18 => Ok(Field::Struct(Vec::<Field>::decode(decoder)?)),
+            19 => Ok(Field::Uuid(Uuid::from_bytes(<[u8; 16]>::decode(decoder)?))),
+            20 => {
+                let decoded = bincode::serde::Compat::decode(decoder)?;
+                Ok(Field::Time(decoded.0))
+            }
             other => Err(bincode::error::DecodeError::UnexpectedVariant {
                 type_name: "Field",
-                allowed: &bincode::error::AllowedEnumVariants::Range { min: 0, max: 15 },
+                allowed: &bincode::error::AllowedEnumVariants::Range { min: 0, max: 20 },
                 found: other,
             }),
         }
@@ -121,9 +143,18 @@ impl<'de> bincode::BorrowDecode<'de> for Field {
             13 => Ok(Field::Point(DozerPoint::borrow_decode(decoder)?)),
             14 => Ok(Field::Duration(DozerDuration::borrow_decode(decoder)?)),
             15 => Ok(Field::Null),
+            17 => Ok(Field::List(Vec::<Field>::borrow_decode(decoder)?)),
+            18 => Ok(Field::Struct(Vec::<Field>::borrow_decode(decoder)?)),
+            19 => Ok(Field::Uuid(Uuid::from_bytes(<[u8; 16]>::borrow_decode(
+                decoder,
+            )?))),
+            20 => {
+                let decoded = bincode::serde::Compat::borrow_decode(decoder)?;
+                Ok(Field::Time(decoded.0))
+            }
             other => Err(bincode::error::DecodeError::UnexpectedVariant {
                 type_name: "Field",
-                allowed: &bincode::error::AllowedEnumVariants::Range { min: 0, max: 15 },
+                allowed: &bincode::error::AllowedEnumVariants::Range { min: 0, max: 20 },
                 found: other,
             }),
         }
@@ -158,6 +189,9 @@ impl bincode::Encode for Field {
             Field::Point(v) => v.encode(encoder),
             Field::Duration(v) => v.encode(encoder),
             Field::Null => Ok(()),
+            Field::List(v) | Field::Struct(v) => v.encode(encoder),
+            Field::Uuid(v) => v.as_bytes().encode(encoder),
+            Field::Time(v) => bincode::serde::Compat(v).encode(encoder),
         }
     }
 }
@@ -180,6 +214,10 @@ impl Ord for Field {
             (Self::Json(l), Self::Json(r)) => json_cmp(l, r),
             (Self::Point(l), Self::Point(r)) => l.cmp(r),
             (Self::Duration(l), Self::Duration(r)) => l.cmp(r),
+            (Self::List(l), Self::List(r)) => l.cmp(r),
+            (Self::Struct(l), Self::Struct(r)) => l.cmp(r),
+            (Self::Uuid(l), Self::Uuid(r)) => l.cmp(r),
+            (Self::Time(l), Self::Time(r)) => l.cmp(r),
             (Self::Null, Self::Null) => std::cmp::Ordering::Equal,
             (Self::Null, _) => std::cmp::Ordering::Greater,
             (_, Self::Null) => std::cmp::Ordering::Less,
@@ -200,6 +238,20 @@ pub(crate) fn arbitrary_float(
     Ok(OrderedFloat(arbitrary.arbitrary()?))
 }
 
+#[cfg(feature = "arbitrary")]
+pub(crate) fn arbitrary_uuid(arbitrary: &mut arbitrary::Unstructured) -> arbitrary::Result<Uuid> {
+    Ok(Uuid::from_bytes(arbitrary.arbitrary()?))
+}
+
+#[cfg(feature = "arbitrary")]
+pub(crate) fn arbitrary_time(
+    arbitrary: &mut arbitrary::Unstructured,
+) -> arbitrary::Result<NaiveTime> {
+    let secs = arbitrary.int_in_range(0..=86_399)?;
+    let nano = arbitrary.int_in_range(0..=999_999_999)?;
+    Ok(NaiveTime::from_num_seconds_from_midnight_opt(secs, nano).unwrap())
+}
+
 #[cfg(feature = "arbitrary")]
 mod arb_json {
     use arbitrary::Arbitrary;
@@ -272,6 +324,12 @@ impl Field {
             Field::Point(_p) => 16,
             Field::Duration(_) => 17,
             Field::Null => 0,
+            Field::List(v) | Field::Struct(v) => v
+                .iter()
+                .map(|field| LIST_ELEMENT_LENGTH_SIZE + field.encoding_len())
+                .sum(),
+            Field::Uuid(_) => 16,
+            Field::Time(_) => 8,
         }
     }
 
@@ -294,6 +352,18 @@ impl Field {
             Field::Point(p) => Cow::Owned(p.to_bytes().into()),
             Field::Duration(d) => Cow::Owned(d.to_bytes().into()),
             Field::Null => Cow::Owned([].into()),
+            Field::List(v) | Field::Struct(v) => {
+                // Each element is prefixed with its encoded length.
+                let mut result = Vec::with_capacity(self.data_encoding_len());
+                for field in v {
+                    let encoded = field.encode();
+                    result.extend_from_slice(&(encoded.len() as u32).to_be_bytes());
+                    result.extend_from_slice(&encoded);
+                }
+                Cow::Owned(result)
+            }
+            Field::Uuid(u) => Cow::Borrowed(u.as_bytes()),
+            Field::Time(t) => Cow::Owned(time_to_nanos(t).to_be_bytes().into()),
         }
     }
 
@@ -374,10 +444,43 @@ impl Field {
                 DozerDuration::from_bytes(val).map_err(|_| DeserializationError::BadDataLength)?,
             )),
             15 => Ok(Field::Null),
+            17 => Ok(Field::List(Self::decode_elements(val)?)),
+            18 => Ok(Field::Struct(Self::decode_elements(val)?)),
+            19 => Ok(Field::Uuid(Uuid::from_bytes(
+                val.try_into()
+                    .map_err(|_| DeserializationError::BadDataLength)?,
+            ))),
+            20 => Ok(Field::Time(
+                time_from_nanos(u64::from_be_bytes(
+                    val.try_into()
+                        .map_err(|_| DeserializationError::BadDataLength)?,
+                ))
+                .ok_or(DeserializationError::Custom(
+                    "Invalid time".to_string().into(),
+                ))?,
+            )),
             other => Err(DeserializationError::UnrecognisedFieldType(other)),
         }
     }
 
+    fn decode_elements(mut buf: &[u8]) -> Result<Vec<Field>, DeserializationError> {
+        let mut result = vec![];
+        while !buf.is_empty() {
+            if buf.len() < LIST_ELEMENT_LENGTH_SIZE {
+                return Err(DeserializationError::BadDataLength);
+            }
+            let (len, rest) = buf.split_at(LIST_ELEMENT_LENGTH_SIZE);
+            let len = u32::from_be_bytes(len.try_into().unwrap()) as usize;
+            if rest.len() < len {
+                return Err(DeserializationError::BadDataLength);
+            }
+            let (element, rest) = rest.split_at(len);
+            result.push(Field::decode(element)?);
+            buf = rest;
+        }
+        Ok(result)
+    }
+
     fn get_type_prefix(&self) -> u8 {
         match self {
             Field::UInt(_) => 0,
@@ -397,6 +500,10 @@ impl Field {
             Field::Duration(_) => 14,
             Field::Null => 15,
             Field::Int8(_) => 16,
+            Field::List(_) => 17,
+            Field::Struct(_) => 18,
+            Field::Uuid(_) => 19,
+            Field::Time(_) => 20,
         }
     }
 
@@ -412,13 +519,33 @@ impl Field {
             Field::String(_) => Some(FieldType::String),
             Field::Text(_) => Some(FieldType::Text),
             Field::Binary(_) => Some(FieldType::Binary),
-            Field::Decimal(_) => Some(FieldType::Decimal),
+            Field::Decimal(_) => Some(FieldType::Decimal(None)),
             Field::Timestamp(_) => Some(FieldType::Timestamp),
             Field::Date(_) => Some(FieldType::Date),
             Field::Json(_) => Some(FieldType::Json),
             Field::Point(_) => Some(FieldType::Point),
             Field::Duration(_) => Some(FieldType::Duration),
             Field::Null => None,
+            // The element type is the type of the first non-null element, and `Json` if there's none.
+            Field::List(v) => Some(FieldType::List(Box::new(
+                v.iter().find_map(Field::ty).unwrap_or(FieldType::Json),
+            ))),
+            // The field names of a struct value are not known, so they're named like Postgres' anonymous records.
+            Field::Struct(v) => Some(FieldType::Struct(
+                v.iter()
+                    .enumerate()
+                    .map(|(i, field)| {
+                        FieldDefinition::new(
+                            format!("f{}", i + 1),
+                            field.ty().unwrap_or(FieldType::Json),
+                            true,
+                            SourceDefinition::Dynamic,
+                        )
+                    })
+                    .collect(),
+            )),
+            Field::Uuid(_) => Some(FieldType::Uuid),
+            Field::Time(_) => Some(FieldType::Time),
         }
     }
 
@@ -563,6 +690,34 @@ impl Field {
         }
     }
 
+    pub fn as_list(&self) -> Option<&[Field]> {
+        match self {
+            Field::List(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn as_struct(&self) -> Option<&[Field]> {
+        match self {
+            Field::Struct(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn as_uuid(&self) -> Option<Uuid> {
+        match self {
+            Field::Uuid(u) => Some(*u),
+            _ => None,
+        }
+    }
+
+    pub fn as_time(&self) -> Option<NaiveTime> {
+        match self {
+            Field::Time(t) => Some(*t),
+            _ => None,
+        }
+    }
+
     pub fn as_null(&self) -> Option<()> {
         match self {
             Field::Null => Some(()),
@@ -773,6 +928,26 @@ impl Field {
             Field::String(s) => json_from_str(s.as_str()).ok(),
             Field::Text(t) => Some(t.into()),
             Field::Null => Some(JsonValue::NULL),
+            Field::List(_) | Field::Struct(_) => Some(field_to_json_value(self.clone())),
+            Field::Uuid(_) | Field::Time(_) => Some(self.to_string().into()),
+            _ => None,
+        }
+    }
+
+    pub fn to_uuid(&self) -> Option<Uuid> {
+        match self {
+            Field::Uuid(u) => Some(*u),
+            Field::String(s) | Field::Text(s) => Uuid::parse_str(s).ok(),
+            Field::Binary(b) => Uuid::from_slice(b).ok(),
+            _ => None,
+        }
+    }
+
+    pub fn to_time(&self) -> Option<NaiveTime> {
+        match self {
+            Field::Time(t) => Some(*t),
+            Field::Timestamp(t) => Some(t.time()),
+            Field::String(s) | Field::Text(s) => NaiveTime::parse_from_str(s, TIME_FORMAT).ok(),
             _ => None,
         }
     }
@@ -848,13 +1023,45 @@ impl Display for Field {
             }
             Field::Duration(d) => write!(f, "PT{},{:09}S", d.0.as_secs(), d.0.subsec_nanos()),
             Field::Null => write!(f, ""),
+            Field::List(v) => {
+                f.write_str("[")?;
+                write_elements(f, v)?;
+                f.write_str("]")
+            }
+            Field::Struct(v) => {
+                f.write_str("(")?;
+                write_elements(f, v)?;
+                f.write_str(")")
+            }
+            Field::Uuid(u) => write!(f, "{u}"),
+            Field::Time(t) => write!(f, "{}", t.format(TIME_FORMAT)),
         }
     }
 }
 
+fn time_to_nanos(time: &NaiveTime) -> u64 {
+    time.num_seconds_from_midnight() as u64 * 1_000_000_000 + time.nanosecond() as u64
+}
+
+fn time_from_nanos(nanos: u64) -> Option<NaiveTime> {
+    NaiveTime::from_num_seconds_from_midnight_opt(
+        (nanos / 1_000_000_000) as u32,
+        (nanos % 1_000_000_000) as u32,
+    )
+}
+
+fn write_elements(f: &mut Formatter<'_>, fields: &[Field]) -> std::fmt::Result {
+    for (i, field) in fields.iter().enumerate() {
+        if i > 0 {
+            f.write_str(", ")?;
+        }
+        write!(f, "{field}")?;
+    }
+    Ok(())
+}
+
 #[derive(
     Clone,
-    Copy,
     Serialize,
     Deserialize,
     Debug,
@@ -866,6 +1073,7 @@ impl Display for Field {
     bincode::Encode,
     bincode::Decode,
 )]
+#[serde(remote = "Self")]
 /// All field types supported in Dozer.
 pub enum FieldType {
     /// Unsigned 64-bit integer.
@@ -891,7 +1099,10 @@ pub enum FieldType {
     /// The finite set of values of type `Decimal` are of the form m / 10<sup>e</sup>,
     /// where m is an integer such that -2<sup>96</sup> < m < 2<sup>96</sup>, and e is an integer
     /// between 0 and 28 inclusive.
-    Decimal,
+    ///
+    /// The precision and scale declared by the source, if any, are kept so sinks can create
+    /// matching columns.
+    Decimal(Option<DecimalType>),
     /// Timestamp up to nanoseconds.
     Timestamp,
     /// Allows for every date from Jan 1, 262145 BCE to Dec 31, 262143 CE.
@@ -902,6 +1113,118 @@ pub enum FieldType {
     Point,
     /// Duration up to nanoseconds.
     Duration,
+    /// An array of elements of the same type.
+    List(Box<FieldType>),
+    /// A nested record.
+    Struct(Vec<FieldDefinition>),
+    /// A 128-bit universally unique identifier.
+    Uuid,
+    /// Time of day up to nanoseconds, without a time zone.
+    Time,
+    /// A string restricted to the listed values.
+    Enum(Vec<String>),
+}
+
+impl Serialize for FieldType {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        FieldType::serialize(self, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for FieldType {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        /// `Decimal` was written as a bare string before it had a precision and scale.
+        #[derive(Deserialize)]
+        #[serde(rename = "FieldType")]
+        enum BareDecimal {
+            Decimal,
+        }
+
+        #[derive(Deserialize)]
+        #[serde(untagged, expecting = "a field type")]
+        enum FieldTypeOrBareDecimal {
+            FieldType(#[serde(deserialize_with = "FieldType::deserialize")] FieldType),
+            BareDecimal(BareDecimal),
+        }
+
+        Ok(match FieldTypeOrBareDecimal::deserialize(deserializer)? {
+            FieldTypeOrBareDecimal::FieldType(field_type) => field_type,
+            FieldTypeOrBareDecimal::BareDecimal(BareDecimal::Decimal) => FieldType::Decimal(None),
+        })
+    }
+}
+
+/// Precision (total number of digits) and scale (number of digits after the decimal point)
+/// of a decimal type.
+#[derive(
+    Clone,
+    Copy,
+    Serialize,
+    Deserialize,
+    Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    bincode::Encode,
+    bincode::Decode,
+)]
+pub struct DecimalType {
+    pub precision: u8,
+    pub scale: u8,
+}
+
+impl DecimalType {
+    pub fn new(precision: u8, scale: u8) -> Self {
+        Self { precision, scale }
+    }
+}
+
+impl FieldType {
+    /// Returns the type of the elements of a list.
+    pub fn element_type(&self) -> Option<&FieldType> {
+        match self {
+            FieldType::List(element_type) => Some(element_type),
+            _ => None,
+        }
+    }
+
+    /// Returns the index and definition of the `name` field of a struct.
+    pub fn struct_field(&self, name: &str) -> Option<(usize, &FieldDefinition)> {
+        match self {
+            FieldType::Struct(fields) => fields
+                .iter()
+                .enumerate()
+                .find(|(_, field)| field.name.eq_ignore_ascii_case(name)),
+            _ => None,
+        }
+    }
+
+    /// Returns the declared precision and scale of a decimal.
+    pub fn decimal_type(&self) -> Option<DecimalType> {
+        match self {
+            FieldType::Decimal(decimal_type) => *decimal_type,
+            _ => None,
+        }
+    }
+
+    /// Returns the values an enum may take.
+    pub fn enum_values(&self) -> Option<&[String]> {
+        match self {
+            FieldType::Enum(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    /// Compares two types, ignoring decimal precision and scale and enum values.
+    pub fn same_kind(&self, other: &FieldType) -> bool {
+        match (self, other) {
+            (FieldType::Decimal(_), FieldType::Decimal(_))
+            | (FieldType::Enum(_), FieldType::Enum(_)) => true,
+            _ => self == other,
+        }
+    }
 }
 
 impl TryFrom<&str> for FieldType {
@@ -914,7 +1237,7 @@ impl TryFrom<&str> for FieldType {
             "int" => FieldType::Int,
             "i128" => FieldType::I128,
             "float" => FieldType::Float,
-            "decimal" => FieldType::Decimal,
+            "decimal" => FieldType::Decimal(None),
             "boolean" => FieldType::Boolean,
             "string" => FieldType::String,
             "text" => FieldType::Text,
@@ -927,6 +1250,8 @@ impl TryFrom<&str> for FieldType {
             "jsonb_array" => FieldType::Json,
             "point" => FieldType::Point,
             "duration" => FieldType::Duration,
+            "uuid" => FieldType::Uuid,
+            "time" => FieldType::Time,
             _ => return Err(format!("Unsupported '{value}' type")),
         };
 
@@ -948,12 +1273,38 @@ impl Display for FieldType {
             FieldType::String => f.write_str("string"),
             FieldType::Text => f.write_str("text"),
             FieldType::Binary => f.write_str("binary"),
-            FieldType::Decimal => f.write_str("decimal"),
+            FieldType::Decimal(None) => f.write_str("decimal"),
+            FieldType::Decimal(Some(DecimalType { precision, scale })) => {
+                write!(f, "decimal({precision}, {scale})")
+            }
             FieldType::Timestamp => f.write_str("timestamp"),
             FieldType::Date => f.write_str("date"),
             FieldType::Json => f.write_str("json"),
             FieldType::Point => f.write_str("point"),
             FieldType::Duration => f.write_str("duration"),
+            FieldType::List(element_type) => write!(f, "list<{element_type}>"),
+            FieldType::Struct(fields) => {
+                f.write_str("struct<")?;
+                for (i, field) in fields.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{}: {}", field.name, field.typ)?;
+                }
+                f.write_str(">")
+            }
+            FieldType::Uuid => f.write_str("uuid"),
+            FieldType::Time => f.write_str("time"),
+            FieldType::Enum(values) => {
+                f.write_str("enum(")?;
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "'{value}'")?;
+                }
+                f.write_str(")")
+            }
         }
     }
 }
@@ -994,6 +1345,16 @@ pub fn field_test_cases() -> impl Iterator<Item = Field> {
             ]
             .into(),
         ),
+        Field::List(vec![]),
+        Field::List(vec![Field::Int(1), Field::Null]),
+        Field::Struct(vec![
+            Field::String("a".to_string()),
+            Field::List(vec![Field::Int(1)]),
+        ]),
+        Field::Uuid(Uuid::nil()),
+        Field::Uuid(Uuid::from_u128(1)),
+        Field::Time(NaiveTime::from_hms_opt(0, 0, 0).unwrap()),
+        Field::Time(NaiveTime::from_hms_nano_opt(12, 30, 15, 1).unwrap()),
         Field::Null,
     ]
     .into_iter()
@@ -1185,6 +1546,82 @@ pub fn arrow_field_test_cases_schema() -> Schema {
             ),
             false,
         )
+        .field(
+            FieldDefinition::new(
+                "list1".to_string(),
+                FieldType::List(Box::new(FieldType::Int)),
+                false,
+                SourceDefinition::Dynamic,
+            ),
+            false,
+        )
+        .field(
+            FieldDefinition::new(
+                "list2".to_string(),
+                FieldType::List(Box::new(FieldType::Int)),
+                false,
+                SourceDefinition::Dynamic,
+            ),
+            false,
+        )
+        .field(
+            FieldDefinition::new(
+                "struct".to_string(),
+                FieldType::Struct(vec![
+                    FieldDefinition::new(
+                        "a".to_string(),
+                        FieldType::String,
+                        true,
+                        SourceDefinition::Dynamic,
+                    ),
+                    FieldDefinition::new(
+                        "b".to_string(),
+                        FieldType::List(Box::new(FieldType::Int)),
+                        true,
+                        SourceDefinition::Dynamic,
+                    ),
+                ]),
+                false,
+                SourceDefinition::Dynamic,
+            ),
+            false,
+        )
+        .field(
+            FieldDefinition::new(
+                "uuid1".to_string(),
+                FieldType::Uuid,
+                false,
+                SourceDefinition::Dynamic,
+            ),
+            false,
+        )
+        .field(
+            FieldDefinition::new(
+                "uuid2".to_string(),
+                FieldType::Uuid,
+                false,
+                SourceDefinition::Dynamic,
+            ),
+            false,
+        )
+        .field(
+            FieldDefinition::new(
+                "time1".to_string(),
+                FieldType::Time,
+                false,
+                SourceDefinition::Dynamic,
+            ),
+            false,
+        )
+        .field(
+            FieldDefinition::new(
+                "time2".to_string(),
+                FieldType::Time,
+                false,
+                SourceDefinition::Dynamic,
+            ),
+            false,
+        )
         .field(
             FieldDefinition::new(
                 "null".to_string(),
@@ -1225,6 +1662,18 @@ impl pyo3::ToPyObject for Field {
             Field::Point(_val) => todo!(),
             Field::Duration(_d) => todo!(),
             Field::Null => unreachable!(),
+            Field::List(val) | Field::Struct(val) => val.to_object(py),
+            Field::Uuid(val) => val.to_string().to_object(py),
+            Field::Time(val) => pyo3::types::PyTime::new(
+                py,
+                val.hour() as u8,
+                val.minute() as u8,
+                val.second() as u8,
+                val.nanosecond() / 1_000,
+                None,
+            )
+            .unwrap()
+            .to_object(py),
         }
     }
 }
@@ -19,7 +19,7 @@ mod tests;
 
 use crate::errors::internal::BoxedError;
 use crate::errors::types::TypeError::InvalidFieldValue;
-pub use field::{field_test_cases, Field, FieldType, DATE_FORMAT};
+pub use field::{field_test_cases, DecimalType, Field, FieldType, DATE_FORMAT, TIME_FORMAT};
 
 #[derive(
     Clone,
@@ -30,6 +30,7 @@ pub use field::{field_test_cases, Field, FieldType, DATE_FORMAT};
     Eq,
     PartialOrd,
     Ord,
+    Hash,
     Default,
     bincode::Encode,
     bincode::Decode,
@@ -46,7 +47,19 @@ pub enum SourceDefinition {
     Dynamic,
 }
 
-#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(
+    Clone,
+    Serialize,
+    Deserialize,
+    Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    bincode::Encode,
+    bincode::Decode,
+)]
 pub struct FieldDefinition {
     pub name: String,
     pub typ: FieldType,
@@ -1,5 +1,8 @@
-use crate::types::{field_test_cases, DozerDuration, DozerPoint, Field, TimeUnit};
-use chrono::{DateTime, NaiveDate, TimeZone, Utc};
+use crate::types::{
+    field_test_cases, DecimalType, DozerDuration, DozerPoint, Field, FieldDefinition, FieldType,
+    TimeUnit,
+};
+use chrono::{DateTime, NaiveDate, NaiveTime, TimeZone, Utc};
 use ordered_float::OrderedFloat;
 use rust_decimal::Decimal;
 
@@ -565,3 +568,64 @@ fn test_to_conversion() {
     assert!(field.to_duration().is_some());
     assert!(field.to_null().is_some());
 }
+
+#[test]
+fn test_uuid_and_time_conversion() {
+    let uuid = "67e55044-10b1-426f-9247-bb680e5fe0c8";
+    let field = Field::String(uuid.to_string());
+    assert_eq!(field.to_uuid().unwrap().to_string(), uuid);
+    assert!(field.to_time().is_none());
+    assert_eq!(
+        Field::Binary(field.to_uuid().unwrap().as_bytes().to_vec()).to_uuid(),
+        field.to_uuid()
+    );
+
+    let field = Field::String("10:30:00.25".to_string());
+    assert_eq!(
+        field.to_time(),
+        NaiveTime::from_hms_milli_opt(10, 30, 0, 250)
+    );
+    assert!(field.to_uuid().is_none());
+
+    let field = Field::Timestamp(DateTime::parse_from_rfc3339("2020-01-01T10:30:00Z").unwrap());
+    assert_eq!(field.to_time(), NaiveTime::from_hms_opt(10, 30, 0));
+}
+
+#[test]
+fn test_field_type_parameters() {
+    let typ = FieldType::Decimal(Some(DecimalType::new(10, 2)));
+    assert_eq!(typ.to_string(), "decimal(10, 2)");
+    assert_eq!(typ.decimal_type(), Some(DecimalType::new(10, 2)));
+    assert!(typ.same_kind(&FieldType::Decimal(None)));
+    assert!(!typ.same_kind(&FieldType::Float));
+
+    let typ = FieldType::Enum(vec!["a".to_string(), "b".to_string()]);
+    assert_eq!(typ.to_string(), "enum('a', 'b')");
+    assert_eq!(
+        typ.enum_values(),
+        Some(&["a".to_string(), "b".to_string()][..])
+    );
+
+    assert_eq!(FieldType::try_from("uuid"), Ok(FieldType::Uuid));
+    assert_eq!(FieldType::try_from("time"), Ok(FieldType::Time));
+}
+
+#[test]
+fn test_deserialize_bare_decimal_field_type() {
+    let definition: FieldDefinition = serde_json::from_str(
+        r#"{"name": "price", "typ": "Decimal", "nullable": false, "source": "Dynamic"}"#,
+    )
+    .unwrap();
+    assert_eq!(definition.typ, FieldType::Decimal(None));
+
+    for typ in [
+        FieldType::Decimal(None),
+        FieldType::Decimal(Some(DecimalType::new(10, 2))),
+        FieldType::List(Box::new(FieldType::Int)),
+        FieldType::String,
+    ] {
+        let json = serde_json::to_string(&typ).unwrap();
+        assert_eq!(serde_json::from_str::<FieldType>(&json).unwrap(), typ);
+    }
+    assert!(serde_json::from_str::<FieldType>(r#""Decimals""#).is_err());
+}